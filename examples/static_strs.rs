@@ -10,7 +10,7 @@ const CAPACITY: usize = 5;
 // #![no_std] demo mutable manipulation of SgMap<isize, &str, 5>
 fn main() {
     let mut example = SgMap::<_, _, CAPACITY>::new(); // BTreeMap::new()
-    let mut stack_str = "your friend the";
+    let stack_str = "your friend the";
 
     // Insert "dynamically" (as if heap)
     example.insert(3, "the");
@@ -43,11 +43,11 @@ fn main() {
         [(isize, &str); CAPACITY] =>
         (1337, "safety!"), (0, "Leverage"), (100, "for")
     ];
-    example.extend(iterable.into_iter());
+    example.extend(iterable);
 
     // Value mutation
     if let Some(three_val) = example.get_mut(&3) {
-        *three_val = &mut stack_str;
+        *three_val = stack_str;
     }
 
     // New message :)
@@ -19,4 +19,6 @@ fn main() {
     {
         assert_eq!(size_of_val(&tiny_map), 1_352);
     }
+
+    println!("SgMap<u8, u8, 128> -> {} bytes", size_of_val(&tiny_map));
 }
@@ -22,7 +22,7 @@ enum ArenaMethod<K: Ord + fmt::Debug, V: fmt::Debug> {
     Iter,
     IterMut,
     HardRemove { idx: usize },
-    Len,
+    SlotCnt,
     IsOccupied { idx: usize },
     // sort() exercised through SgMap fuzz target (input invariants are complex, tree structure related)
     // node_size() returns a constant. Omitted, irrelevant coverage.
@@ -56,8 +56,8 @@ fuzz_target!(|methods: Vec<ArenaMethod<usize, usize>>| {
                     let _ = arena.hard_remove(idx);
                 }
             },
-            ArenaMethod::Len => {
-                let _ = arena.len();
+            ArenaMethod::SlotCnt => {
+                let _ = arena.slot_cnt();
             }
             ArenaMethod::IsOccupied { idx } => match idx_set.contains(&idx) {
                 false => continue,
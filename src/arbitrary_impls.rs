@@ -0,0 +1,126 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{ArenaIdx, Compare, SgMap, SgSet};
+
+// Containers are built from arbitrary pairs/elements, clamped to capacity: decoding stops once full.
+// Duplicate keys keep the last value, like `BTreeMap`.
+
+// Map -----------------------------------------------------------------------------------------------------------------
+
+fn map_from_pairs<K, V, const N: usize, U: ArenaIdx, C>(
+    pairs: impl Iterator<Item = Result<(K, V)>>,
+) -> Result<SgMap<K, V, N, U, C>>
+where
+    K: Ord,
+    C: Compare<K> + Default,
+{
+    let mut map = SgMap::new();
+    for pair in pairs {
+        if map.is_full() {
+            break;
+        }
+
+        let (key, val) = pair?;
+        map.insert(key, val);
+    }
+
+    Ok(map)
+}
+
+impl<'a, K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Arbitrary<'a> for SgMap<K, V, N, U, C>
+where
+    K: Ord + Arbitrary<'a>,
+    V: Arbitrary<'a>,
+    C: Default,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        map_from_pairs(u.arbitrary_iter()?)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        map_from_pairs(u.arbitrary_take_rest_iter()?)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+// Set -----------------------------------------------------------------------------------------------------------------
+
+fn set_from_elems<T, const N: usize, U: ArenaIdx, C>(
+    elems: impl Iterator<Item = Result<T>>,
+) -> Result<SgSet<T, N, U, C>>
+where
+    T: Ord,
+    C: Compare<T> + Default,
+{
+    let mut set = SgSet::new();
+    for elem in elems {
+        if set.is_full() {
+            break;
+        }
+
+        set.insert(elem?);
+    }
+
+    Ok(set)
+}
+
+impl<'a, T, const N: usize, U: ArenaIdx, C: Compare<T>> Arbitrary<'a> for SgSet<T, N, U, C>
+where
+    T: Ord + Arbitrary<'a>,
+    C: Default,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        set_from_elems(u.arbitrary_iter()?)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        set_from_elems(u.arbitrary_take_rest_iter()?)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+// Test ----------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::{SgMap, SgSet};
+    use arbitrary::{Arbitrary, Unstructured};
+    use std::collections::{BTreeMap, BTreeSet};
+
+    #[test]
+    fn test_arbitrary_clamped_to_capacity() {
+        // Odd bytes: `arbitrary_iter` reads the low bit as "continue"
+        let bytes: Vec<u8> = (0..4096_u32).map(|i| (i * 2 + 1) as u8).collect();
+
+        let map = SgMap::<u16, u16, 16>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        assert_eq!(map.len(), map.capacity());
+        assert!(map.check_invariants().is_valid());
+
+        let set = SgSet::<u16, 16>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+        assert_eq!(set.len(), set.capacity());
+        assert!(set.check_invariants().is_valid());
+
+        let map = SgMap::<u8, u8, 16>::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_arbitrary_matches_btree() {
+        // Narrow key space, plenty of duplicates
+        let bytes: Vec<u8> = (0..=255_u8).map(|b| b.wrapping_mul(37) % 8).collect();
+
+        let map = SgMap::<u8, u8, 256>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+        let bt_map = BTreeMap::<u8, u8>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+        assert!(map.iter().eq(bt_map.iter()));
+
+        let set = SgSet::<u8, 256>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+        let bt_set = BTreeSet::<u8>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+        assert!(set.iter().eq(bt_set.iter()));
+    }
+}
@@ -0,0 +1,414 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+use core::ops::RangeBounds;
+
+use crate::augment_types::Iter;
+use crate::tree::{
+    above_end, below_start, ArenaIdx, AugmentHook, Compare, Natural, SgError, SgTree, SmallNode,
+};
+
+/// Derived per-node metadata (e.g. subtree sums, maxima, counts) for a [`SgAugmentedMap`][crate::SgAugmentedMap].
+///
+/// The map stores one `Self` per pair, summarizing that pair's entire subtree,
+/// and keeps it current through every insert, remove, and rebalance.
+///
+/// [`augment`][crate::Augment::augment] must describe combining the in-order sequence `left, (key, val), right`,
+/// so the result doesn't depend on tree shape. Associative combinations (sum, min/max, count) all qualify.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::Augment;
+///
+/// // Subtree sum of values
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Sum(u64);
+///
+/// impl<K> Augment<K, u64> for Sum {
+///     fn augment(_key: &K, val: &u64, left: Option<&Self>, right: Option<&Self>) -> Self {
+///         Sum(val + left.map_or(0, |s| s.0) + right.map_or(0, |s| s.0))
+///     }
+/// }
+/// ```
+pub trait Augment<K, V>: Sized {
+    /// Computes a node's metadata from its own pair and its children's metadata (`None` for a missing child).
+    fn augment(key: &K, val: &V, left: Option<&Self>, right: Option<&Self>) -> Self;
+}
+
+/// Ordered map that maintains user-defined per-node metadata, see [`Augment`][crate::Augment].
+///
+/// Enables `O(log n)` aggregates over the whole map or any key range, where a plain [`SgMap`][crate::SgMap] needs a scan.
+/// Values can only be modified through [`modify`][crate::SgAugmentedMap::modify], which keeps metadata current.
+///
+/// ### Fallible APIs
+///
+/// * [`try_insert`][crate::SgAugmentedMap::try_insert]
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::{Augment, SgAugmentedMap};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Sum(u64);
+///
+/// impl<K> Augment<K, u64> for Sum {
+///     fn augment(_key: &K, val: &u64, left: Option<&Self>, right: Option<&Self>) -> Self {
+///         Sum(val + left.map_or(0, |s| s.0) + right.map_or(0, |s| s.0))
+///     }
+/// }
+///
+/// let mut sales = SgAugmentedMap::<u32, u64, Sum, 10>::new();
+/// sales.insert(1, 100);
+/// sales.insert(2, 50);
+/// sales.insert(3, 25);
+/// sales.insert(4, 10);
+///
+/// assert_eq!(sales.aggregate(), Some(&Sum(185)));
+/// assert_eq!(sales.range_aggregate(2..4), Some(Sum(75)));
+///
+/// sales.modify(&3, |v| *v = 0);
+/// assert_eq!(sales.range_aggregate(2..), Some(Sum(60)));
+/// ```
+#[derive(Clone)]
+pub struct SgAugmentedMap<
+    K: Ord,
+    V,
+    A: Augment<K, V>,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
+    pub(crate) bst: SgTree<K, AugVal<V, A>, N, U, C, UpdateAug>,
+}
+
+impl<K: Ord, V, A: Augment<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>>
+    SgAugmentedMap<K, V, A, N, U, C>
+{
+    /// Makes a new, empty `SgAugmentedMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{Augment, SgAugmentedMap};
+    ///
+    /// struct Cnt(usize);
+    ///
+    /// impl<K, V> Augment<K, V> for Cnt {
+    ///     fn augment(_: &K, _: &V, left: Option<&Self>, right: Option<&Self>) -> Self {
+    ///         Cnt(1 + left.map_or(0, |c| c.0) + right.map_or(0, |c| c.0))
+    ///     }
+    /// }
+    ///
+    /// let map = SgAugmentedMap::<isize, &str, Cnt, 10>::new();
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_comparator(C::default())
+    }
+
+    /// Makes a new, empty `SgAugmentedMap` ordered by `cmp` instead of `K`'s [`Ord`] implementation.
+    /// See [`Compare`][crate::Compare].
+    pub fn with_comparator(cmp: C) -> Self {
+        SgAugmentedMap {
+            bst: SgTree::with_comparator(cmp),
+        }
+    }
+
+    /// Total capacity, e.g. maximum number of key-value pairs.
+    pub fn capacity(&self) -> usize {
+        self.bst.capacity()
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.bst.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.bst.is_empty()
+    }
+
+    /// Returns `true` if the map's capacity is filled.
+    pub fn is_full(&self) -> bool {
+        self.bst.is_full()
+    }
+
+    /// Clears the map, removing all elements.
+    pub fn clear(&mut self) {
+        self.bst.clear();
+    }
+
+    /// Insert a key-value pair into the map.
+    /// If the map did not have this key present, `None` is returned.
+    /// If the map did have this key present, the value is updated, the old value is returned,
+    /// and the key is updated. This accommodates types that can be `==` without being identical.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is full and the key isn't already present.
+    /// See [`try_insert`][crate::SgAugmentedMap::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        let aug = A::augment(&key, &val, None, None);
+        self.bst.insert(key, AugVal { val, aug }).map(|av| av.val)
+    }
+
+    /// Insert a key-value pair into the map.
+    /// Returns `Err` if the map is full and the key isn't already present.
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<Option<V>, SgError> {
+        let aug = A::augment(&key, &val, None, None);
+        self.bst
+            .try_insert(key, AugVal { val, aug })
+            .map(|opt_av| opt_av.map(|av| av.val))
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get(key).map(|av| &av.val)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.contains_key(key)
+    }
+
+    /// Calls `f` on the value corresponding to the key, then updates metadata along its root path.
+    /// Returns `false` if the key isn't present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{Augment, SgAugmentedMap};
+    ///
+    /// struct Max(u8);
+    ///
+    /// impl<K> Augment<K, u8> for Max {
+    ///     fn augment(_: &K, val: &u8, left: Option<&Self>, right: Option<&Self>) -> Self {
+    ///         Max(*[Some(*val), left.map(|m| m.0), right.map(|m| m.0)].iter().flatten().max().unwrap())
+    ///     }
+    /// }
+    ///
+    /// let mut map = SgAugmentedMap::<_, _, Max, 10>::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 9);
+    ///
+    /// assert!(map.modify(&"b", |v| *v = 2));
+    /// assert!(!map.modify(&"c", |v| *v = 3));
+    /// assert_eq!(map.aggregate().map(|m| m.0), Some(2));
+    /// ```
+    pub fn modify<Q, F>(&mut self, key: &Q, f: F) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+        F: FnOnce(&mut V),
+    {
+        match self.bst.priv_get::<Q, U>(None, key).node_idx() {
+            Some(idx) => {
+                f(&mut self.bst.arena[idx].get_mut().1.val);
+                self.bst.augment_root_path(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.remove(key).map(|av| av.val)
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    pub fn iter(&self) -> Iter<'_, K, V, A, N, U, C> {
+        Iter::new(self)
+    }
+
+    /// Returns the metadata summarizing the whole map, `None` if empty. `O(1)`.
+    pub fn aggregate(&self) -> Option<&A> {
+        self.bst
+            .opt_root_idx
+            .map(|root_idx| &self.bst.arena[root_idx].val().aug)
+    }
+
+    /// Returns the metadata summarizing every pair within a key range, `None` if no pairs fall in it.
+    /// Reuses cached subtree metadata, so only `O(log n)` nodes along the two range boundaries are combined.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    pub fn range_aggregate<T, R>(&self, range: R) -> Option<A>
+    where
+        T: ?Sized,
+        K: Borrow<T>,
+        C: Compare<T>,
+        R: RangeBounds<T>,
+        A: Clone,
+    {
+        self.bst.assert_valid_range(&range);
+        self.bst
+            .opt_root_idx
+            .and_then(|root_idx| self.range_aggregate_from(root_idx, &range, false, false))
+    }
+
+    // Private API -----------------------------------------------------------------------------------------------------
+
+    // Aggregate of the in-range pairs of the subtree rooted at `idx`.
+    // `start_ok`/`end_ok` mean every key in this subtree is already known to satisfy that bound.
+    // Recursion depth is bounded by tree height.
+    fn range_aggregate_from<T, R>(
+        &self,
+        idx: usize,
+        range: &R,
+        start_ok: bool,
+        end_ok: bool,
+    ) -> Option<A>
+    where
+        T: ?Sized,
+        K: Borrow<T>,
+        C: Compare<T>,
+        R: RangeBounds<T>,
+        A: Clone,
+    {
+        let node = &self.bst.arena[idx];
+        if start_ok && end_ok {
+            return Some(node.val().aug.clone());
+        }
+
+        let key = node.key().borrow();
+        let cmp = &self.bst.cmp;
+        let (opt_left_idx, opt_right_idx) = (node.left_idx(), node.right_idx());
+
+        if !start_ok && below_start(cmp, range.start_bound(), key) {
+            return opt_right_idx
+                .and_then(|r| self.range_aggregate_from(r, range, start_ok, end_ok));
+        }
+
+        if !end_ok && above_end(cmp, range.end_bound(), key) {
+            return opt_left_idx
+                .and_then(|l| self.range_aggregate_from(l, range, start_ok, end_ok));
+        }
+
+        let opt_left =
+            opt_left_idx.and_then(|l| self.range_aggregate_from(l, range, start_ok, true));
+        let opt_right =
+            opt_right_idx.and_then(|r| self.range_aggregate_from(r, range, true, end_ok));
+        Some(A::augment(
+            node.key(),
+            &node.val().val,
+            opt_left.as_ref(),
+            opt_right.as_ref(),
+        ))
+    }
+}
+
+// Convenience Traits --------------------------------------------------------------------------------------------------
+
+// Debug
+impl<K, V, A, const N: usize, U: ArenaIdx, C: Compare<K>> Debug for SgAugmentedMap<K, V, A, N, U, C>
+where
+    K: Ord + Debug,
+    V: Debug,
+    A: Augment<K, V>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Default
+impl<K: Ord, V, A: Augment<K, V>, const N: usize, U: ArenaIdx, C: Compare<K> + Default> Default
+    for SgAugmentedMap<K, V, A, N, U, C>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// From iterator
+impl<K: Ord, V, A: Augment<K, V>, const N: usize, U: ArenaIdx, C: Compare<K> + Default>
+    FromIterator<(K, V)> for SgAugmentedMap<K, V, A, N, U, C>
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut amap = SgAugmentedMap::new();
+        amap.extend(iter);
+        amap
+    }
+}
+
+// Extension from iterator
+impl<K: Ord, V, A: Augment<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>> Extend<(K, V)>
+    for SgAugmentedMap<K, V, A, N, U, C>
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(move |(k, v)| {
+            self.insert(k, v);
+        });
+    }
+}
+
+// Iterators -----------------------------------------------------------------------------------------------------------
+
+// Reference iterator
+impl<'a, K: Ord, V, A: Augment<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>> IntoIterator
+    for &'a SgAugmentedMap<K, V, A, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, A, N, U, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Internal Value ------------------------------------------------------------------------------------------------------
+
+/// Tree value: the user's value, plus metadata summarizing this node's subtree.
+#[derive(Clone)]
+pub(crate) struct AugVal<V, A> {
+    pub(crate) val: V,
+    pub(crate) aug: A,
+}
+
+/// Augmentation callback, maintained by the tree through every insert, remove, and rebuild.
+#[derive(Clone, Copy)]
+pub(crate) struct UpdateAug;
+
+impl<K, V, A: Augment<K, V>> AugmentHook<K, AugVal<V, A>> for UpdateAug {
+    fn augment(
+        key: &K,
+        node: &mut AugVal<V, A>,
+        opt_left: Option<&AugVal<V, A>>,
+        opt_right: Option<&AugVal<V, A>>,
+    ) {
+        node.aug = A::augment(
+            key,
+            &node.val,
+            opt_left.map(|child| &child.aug),
+            opt_right.map(|child| &child.aug),
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt::{Debug, Write};
+use std::string::String;
+use std::vec::Vec;
+
+/// Emit Rust source for a `static` [`FrozenMap`](crate::FrozenMap), ready to `include!` into `#![no_std]` firmware.
+///
+/// Intended for use on the host, e.g. in a `build.rs`.
+/// Keys and values are emitted with their `Debug` representation, which is valid Rust source for
+/// primitives, `&str`, tuples, and arrays thereof.
+/// `key_type` and `val_type` are the spelled-out types for the generated declaration.
+///
+/// # Panics
+///
+/// Panics if `sorted_pairs` isn't sorted by key, or contains duplicate keys.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::codegen::frozen_map_src;
+///
+/// let src = frozen_map_src("LOOKUP", "u8", "&str", &[(1, "a"), (2, "b"), (3, "c")]);
+/// assert_eq!(
+///     src,
+///     "pub static LOOKUP: scapegoat::FrozenMap<'static, u8, &str> = \
+///     scapegoat::FrozenMap::new(&[(2, \"b\"), (1, \"a\"), (3, \"c\")]);\n"
+/// );
+/// ```
+///
+/// In `build.rs`:
+///
+/// ```ignore
+/// let src = scapegoat::codegen::frozen_map_src("LOOKUP", "u32", "u32", &host_side_data);
+/// let out_dir = std::env::var("OUT_DIR").unwrap();
+/// std::fs::write(std::path::Path::new(&out_dir).join("lookup.rs"), src).unwrap();
+/// ```
+///
+/// And in firmware:
+///
+/// ```ignore
+/// include!(concat!(env!("OUT_DIR"), "/lookup.rs"));
+///
+/// assert_eq!(LOOKUP.get(&42), Some(&1337));
+/// ```
+pub fn frozen_map_src<K: Ord + Debug, V: Debug>(
+    name: &str,
+    key_type: &str,
+    val_type: &str,
+    sorted_pairs: &[(K, V)],
+) -> String {
+    assert!(
+        sorted_pairs.windows(2).all(|w| w[0].0 < w[1].0),
+        "Input must be sorted by key, without duplicates!"
+    );
+
+    let mut src = String::new();
+    write!(
+        src,
+        "pub static {}: scapegoat::FrozenMap<'static, {}, {}> = scapegoat::FrozenMap::new(&[",
+        name, key_type, val_type
+    )
+    .unwrap();
+
+    for (i, sorted_idx) in eytzinger_order(sorted_pairs.len()).into_iter().enumerate() {
+        let (k, v) = &sorted_pairs[sorted_idx];
+        if i > 0 {
+            src.push_str(", ");
+        }
+        write!(src, "({:?}, {:?})", k, v).unwrap();
+    }
+
+    src.push_str("]);\n");
+    src
+}
+
+/// For a sorted sequence of length `len`, get the sorted index to store at each Eytzinger position.
+pub fn eytzinger_order(len: usize) -> Vec<usize> {
+    let mut order = std::vec![0; len];
+    let mut next_sorted_idx = 0;
+
+    // Iterative in-order traversal of the implicit tree
+    let mut stack = Vec::new();
+    let mut curr_idx = 0;
+    loop {
+        while curr_idx < len {
+            stack.push(curr_idx);
+            curr_idx = (2 * curr_idx) + 1;
+        }
+
+        match stack.pop() {
+            Some(idx) => {
+                order[idx] = next_sorted_idx;
+                next_sorted_idx += 1;
+                curr_idx = (2 * idx) + 2;
+            }
+            None => break,
+        }
+    }
+
+    debug_assert_eq!(next_sorted_idx, len);
+    order
+}
+
+// Test ----------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{eytzinger_order, frozen_map_src};
+    use crate::FrozenMap;
+
+    #[test]
+    fn test_eytzinger_lookup() {
+        for len in 0..100 {
+            let pairs: Vec<(usize, usize)> = eytzinger_order(len)
+                .into_iter()
+                .map(|sorted_idx| (sorted_idx * 2, sorted_idx))
+                .collect();
+
+            let map = FrozenMap::new(&pairs);
+            assert_eq!(map.len(), len);
+            for i in 0..len {
+                assert_eq!(map.get(&(i * 2)), Some(&i));
+                assert!(!map.contains_key(&((i * 2) + 1)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_frozen_map_src() {
+        let src = frozen_map_src("EMPTY", "u8", "u8", &[] as &[(u8, u8)]);
+        assert_eq!(
+            src,
+            "pub static EMPTY: scapegoat::FrozenMap<'static, u8, u8> = scapegoat::FrozenMap::new(&[]);\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Input must be sorted by key, without duplicates!")]
+    fn test_frozen_map_src_unsorted() {
+        frozen_map_src("BAD", "u8", "u8", &[(2, 0), (1, 0)]);
+    }
+}
@@ -0,0 +1,383 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+
+use crate::fork_types::Iter;
+use crate::map::SgMap;
+use crate::tree::{ArenaIdx, Compare, Natural, SgError};
+
+/// Copy-on-write view of a [`SgMap`][crate::SgMap]: reads fall through to a borrowed base map, writes land in a private delta.
+///
+/// Forking is `O(1)` and copies nothing, the base is never modified.
+/// Only entries written through the fork are stored, up to `M` of them: each insert, overwrite, or removal of a base
+/// key takes one delta slot. So a fork of an almost-full map costs `M` entries of storage, not `N`.
+/// Useful for per-frame or speculative state that differs from a large base in a handful of entries,
+/// where a full [`clone`](Clone::clone) would copy the whole arena.
+///
+/// Lookups check the delta first, then the base: `O(log m + log n)`.
+/// Iteration walks both in lockstep: `O(n + m)`.
+///
+/// ### Fallible APIs
+///
+/// * [`try_insert`][crate::SgMapFork::try_insert]
+/// * [`try_remove`][crate::SgMapFork::try_remove]
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgMap;
+///
+/// let base: SgMap<_, _, 1024> = (0..1000).map(|k| (k, k)).collect();
+///
+/// // Only 8 entries of storage, regardless of the base's size
+/// let mut next_frame = base.fork::<8>();
+/// next_frame.insert(7, 70);
+/// next_frame.remove(&8);
+/// next_frame.insert(1000, 1000);
+///
+/// assert_eq!(next_frame.get(&7), Some(&70));
+/// assert_eq!(next_frame.get(&8), None);
+/// assert_eq!(next_frame.len(), 1000);
+///
+/// // Base is untouched
+/// assert_eq!(base.get(&7), Some(&7));
+/// assert_eq!(base.get(&8), Some(&8));
+/// ```
+pub struct SgMapFork<
+    'a,
+    K: Ord,
+    V,
+    const N: usize,
+    const M: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
+    base: &'a SgMap<K, V, N, U, C>,
+    pub(crate) delta: SgMap<K, Option<V>, M, U, C>, // `None` shadows a removed base entry
+    len: usize,
+}
+
+impl<'a, K: Ord, V, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>>
+    SgMapFork<'a, K, V, N, M, U, C>
+{
+    /// Makes a new fork of `base`, with no changes yet.
+    /// Same as [`SgMap::fork`][crate::SgMap::fork].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgMapFork};
+    ///
+    /// let base = SgMap::from([(1, "a"), (2, "b")]);
+    /// let fork = SgMapFork::<_, _, 2, 4>::new(&base);
+    /// assert!(fork.iter().eq(base.iter()));
+    /// ```
+    pub fn new(base: &'a SgMap<K, V, N, U, C>) -> Self
+    where
+        C: Clone,
+    {
+        SgMapFork {
+            base,
+            delta: SgMap::with_comparator(base.bst.cmp.clone()),
+            len: base.len(),
+        }
+    }
+
+    /// Returns the map this fork reads through to.
+    pub fn base(&self) -> &'a SgMap<K, V, N, U, C> {
+        self.base
+    }
+
+    /// Returns the number of elements visible through the fork.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no elements are visible through the fork.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of delta slots in use, e.g. entries written through the fork.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let base = SgMap::from([(1, "a"), (2, "b")]);
+    /// let mut fork = base.fork::<4>();
+    ///
+    /// fork.insert(1, "c");
+    /// fork.remove(&2);
+    /// fork.insert(3, "d");
+    /// fork.remove(&3);
+    /// assert_eq!(fork.delta_len(), 2);
+    /// ```
+    pub fn delta_len(&self) -> usize {
+        self.delta.len()
+    }
+
+    /// Returns the maximum number of delta slots, e.g. `M`.
+    pub fn delta_capacity(&self) -> usize {
+        self.delta.capacity()
+    }
+
+    /// Returns a reference to the value corresponding to the key, as seen through the fork.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let base = SgMap::from([(1, "a"), (2, "b")]);
+    /// let mut fork = base.fork::<4>();
+    ///
+    /// fork.insert(1, "c");
+    /// fork.remove(&2);
+    /// assert_eq!(fork.get(&1), Some(&"c"));
+    /// assert_eq!(fork.get(&2), None);
+    /// ```
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        match self.delta.get(key) {
+            Some(opt_val) => opt_val.as_ref(),
+            None => self.base.get(key),
+        }
+    }
+
+    /// Returns `true` if the key is visible through the fork.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the fork, shadowing any base entry with the same key.
+    /// Returns `true` if the key wasn't previously visible through the fork.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key isn't already in the delta and all `M` delta slots are in use.
+    /// For a non-panicking alternative, see [`try_insert`][crate::SgMapFork::try_insert].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let base = SgMap::from([(1, "a")]);
+    /// let mut fork = base.fork::<4>();
+    ///
+    /// assert!(!fork.insert(1, "b"));
+    /// assert!(fork.insert(2, "c"));
+    /// assert_eq!(fork.len(), 2);
+    /// ```
+    pub fn insert(&mut self, key: K, val: V) -> bool {
+        match self.try_insert(key, val) {
+            Ok(is_new) => is_new,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Inserts a key-value pair into the fork, shadowing any base entry with the same key.
+    /// Returns `Err` if the key isn't already in the delta and all `M` delta slots are in use,
+    /// else the `Ok` contains `true` if the key wasn't previously visible through the fork.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let base = SgMap::from([(1, "a"), (2, "b")]);
+    /// let mut fork = base.fork::<1>();
+    ///
+    /// assert_eq!(fork.try_insert(1, "c"), Ok(false));
+    /// assert_eq!(fork.try_insert(1, "d"), Ok(false));
+    /// assert_eq!(fork.try_insert(2, "e"), Err(SgError::StackCapacityExceeded));
+    /// ```
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<bool, SgError> {
+        let was_visible = self.contains_key(&key);
+        self.delta.try_insert(key, Some(val))?;
+        if !was_visible {
+            self.len += 1;
+        }
+
+        Ok(!was_visible)
+    }
+
+    /// Removes a key from the fork, leaving the base untouched.
+    /// Returns `true` if the key was visible through the fork.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is a base entry not already in the delta and all `M` delta slots are in use.
+    /// For a non-panicking alternative, see [`try_remove`][crate::SgMapFork::try_remove].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let base = SgMap::from([(1, "a")]);
+    /// let mut fork = base.fork::<4>();
+    ///
+    /// assert!(fork.remove(&1));
+    /// assert!(!fork.remove(&1));
+    /// assert!(fork.is_empty());
+    /// assert_eq!(base.len(), 1);
+    /// ```
+    pub fn remove<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q> + Clone,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        match self.try_remove(key) {
+            Ok(was_visible) => was_visible,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Removes a key from the fork, leaving the base untouched.
+    /// Returns `Err` if the key is a base entry not already in the delta and all `M` delta slots are in use,
+    /// else the `Ok` contains `true` if the key was visible through the fork.
+    ///
+    /// Removing a base entry takes a delta slot (to shadow it), removing a fork-only entry frees one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let base = SgMap::from([(1, "a"), (2, "b")]);
+    /// let mut fork = base.fork::<1>();
+    ///
+    /// assert_eq!(fork.try_remove(&1), Ok(true));
+    /// assert_eq!(fork.try_remove(&2), Err(SgError::StackCapacityExceeded));
+    /// assert_eq!(fork.try_remove(&3), Ok(false));
+    /// ```
+    pub fn try_remove<Q>(&mut self, key: &Q) -> Result<bool, SgError>
+    where
+        K: Borrow<Q> + Clone,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let was_visible = match self.base.get_key_value(key) {
+            // Base entry, shadow it
+            Some((base_key, _)) => match self.delta.get_mut(key) {
+                Some(opt_val) => opt_val.take().is_some(),
+                None => {
+                    self.delta.try_insert(base_key.clone(), None)?;
+                    true
+                }
+            },
+            // Fork-only entry, if any, drop it
+            None => self.delta.remove(key).is_some(),
+        };
+
+        if was_visible {
+            self.len -= 1;
+        }
+
+        Ok(was_visible)
+    }
+
+    /// Discards every change, so the fork matches its base again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let base = SgMap::from([(1, "a")]);
+    /// let mut fork = base.fork::<4>();
+    ///
+    /// fork.insert(2, "b");
+    /// fork.revert();
+    /// assert!(fork.iter().eq(base.iter()));
+    /// assert_eq!(fork.delta_len(), 0);
+    /// ```
+    pub fn revert(&mut self) {
+        self.delta.clear();
+        self.len = self.base.len();
+    }
+
+    /// Gets an iterator over the entries visible through the fork, sorted by key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let base = SgMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    /// let mut fork = base.fork::<4>();
+    ///
+    /// fork.insert(0, "z");
+    /// fork.remove(&2);
+    /// assert!(fork.iter().eq([(&0, &"z"), (&1, &"a"), (&3, &"c")]));
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V, N, M, U, C> {
+        Iter::new(self)
+    }
+
+    /// Consumes the fork, returning its changes: `Some(val)` for an inserted or overwritten entry, `None` for a removed one.
+    /// Once the base is no longer borrowed, the changes can be applied to it (or to another map).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::from([(1, "a"), (2, "b")]);
+    ///
+    /// let mut fork = map.fork::<4>();
+    /// fork.insert(1, "c");
+    /// fork.remove(&2);
+    /// let delta = fork.into_delta();
+    ///
+    /// for (key, opt_val) in delta {
+    ///     match opt_val {
+    ///         Some(val) => map.insert(key, val),
+    ///         None => map.remove(&key),
+    ///     };
+    /// }
+    ///
+    /// assert!(map.into_iter().eq([(1, "c")]));
+    /// ```
+    pub fn into_delta(self) -> SgMap<K, Option<V>, M, U, C> {
+        self.delta
+    }
+}
+
+// Convenience Traits --------------------------------------------------------------------------------------------------
+
+// Debug
+impl<'a, K, V, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>> Debug
+    for SgMapFork<'a, K, V, N, M, U, C>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Reference iterator
+impl<'a, 'b, K: Ord, V, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>> IntoIterator
+    for &'b SgMapFork<'a, K, V, N, M, U, C>
+{
+    type Item = (&'b K, &'b V);
+    type IntoIter = Iter<'b, K, V, N, M, U, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
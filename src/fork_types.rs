@@ -0,0 +1,95 @@
+use core::cmp::Ordering;
+use core::iter::{FusedIterator, Peekable};
+
+use crate::fork::SgMapFork;
+use crate::map_types::Iter as MapIter;
+use crate::tree::{ArenaIdx, Compare, Natural};
+
+/// An iterator over the entries visible through a [`SgMapFork`][crate::SgMapFork].
+///
+/// This `struct` is created by the [`iter`][crate::SgMapFork::iter] method on [`SgMapFork`][crate::SgMapFork].
+/// See its documentation for more.
+pub struct Iter<
+    'a,
+    K: Ord,
+    V,
+    const N: usize,
+    const M: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
+    base_iter: Peekable<MapIter<'a, K, V, N, U, C>>,
+    delta_iter: Peekable<MapIter<'a, K, Option<V>, M, U, C>>,
+    cmp: &'a C,
+    remaining: usize,
+}
+
+impl<'a, K: Ord, V, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>>
+    Iter<'a, K, V, N, M, U, C>
+{
+    /// Construct reference iterator.
+    pub(crate) fn new(fork: &'a SgMapFork<'_, K, V, N, M, U, C>) -> Self {
+        Iter {
+            base_iter: fork.base().iter().peekable(),
+            delta_iter: fork.delta.iter().peekable(),
+            cmp: &fork.delta.bst.cmp,
+            remaining: fork.len(),
+        }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for Iter<'a, K, V, N, M, U, C>
+{
+    type Item = (&'a K, &'a V);
+
+    // Lockstep in-order walk, delta entries shadow base entries with an equal key
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let ord = match (self.base_iter.peek(), self.delta_iter.peek()) {
+                (None, None) => return None,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some((base_key, _)), Some((delta_key, _))) => {
+                    self.cmp.compare(base_key, delta_key)
+                }
+            };
+
+            let opt_entry = match ord {
+                Ordering::Less => self.base_iter.next(),
+                Ordering::Greater => self
+                    .delta_iter
+                    .next()
+                    .and_then(|(k, v)| v.as_ref().map(|v| (k, v))),
+                Ordering::Equal => {
+                    self.base_iter.next();
+                    self.delta_iter
+                        .next()
+                        .and_then(|(k, v)| v.as_ref().map(|v| (k, v)))
+                }
+            };
+
+            if opt_entry.is_some() {
+                self.remaining -= 1;
+                return opt_entry;
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for Iter<'a, K, V, N, M, U, C>
+{
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for Iter<'a, K, V, N, M, U, C>
+{
+}
@@ -0,0 +1,117 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+
+/// Read-only, immutable map over a `'static` (or borrowed) slice, laid out as an implicit balanced binary tree.
+///
+/// Intended for data prepared on a host and compiled into a `#![no_std]` target, see the `codegen` feature.
+/// Pairs are stored in [Eytzinger order](https://arxiv.org/abs/1509.05053): the root at index `0`,
+/// the children of index `i` at `2i + 1` (left) and `2i + 2` (right).
+/// So there's no per-node child metadata, construction is `const`, and lookup is `O(log n)` with no rebalancing.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::FrozenMap;
+///
+/// // Sorted keys `1..=3`, in Eytzinger order
+/// static MAP: FrozenMap<u8, &str> = FrozenMap::new(&[(2, "b"), (1, "a"), (3, "c")]);
+///
+/// assert_eq!(MAP.get(&1), Some(&"a"));
+/// assert_eq!(MAP.get(&4), None);
+/// assert_eq!(MAP.len(), 3);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FrozenMap<'a, K, V> {
+    pairs: &'a [(K, V)],
+}
+
+impl<'a, K, V> FrozenMap<'a, K, V> {
+    /// Wraps pairs already in Eytzinger order.
+    ///
+    /// Correctness of lookups depends on that order, which isn't checked here.
+    /// Prefer generating the slice with `scapegoat::codegen` (`codegen` feature).
+    pub const fn new(pairs: &'a [(K, V)]) -> Self {
+        FrozenMap { pairs }
+    }
+
+    /// Returns the number of elements in the map.
+    pub const fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    ///
+    /// The supplied key may be any borrowed form of the map's key type,
+    /// but the ordering on the borrowed form must match the ordering on the key type.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&'a K, &'a V)>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        let pairs = self.pairs;
+        let mut idx = 0;
+        while let Some((k, v)) = pairs.get(idx) {
+            match key.cmp(k.borrow()) {
+                Ordering::Less => idx = (2 * idx) + 1,
+                Ordering::Greater => idx = (2 * idx) + 2,
+                Ordering::Equal => return Some((k, v)),
+            }
+        }
+
+        None
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    pub fn get<Q>(&self, key: &Q) -> Option<&'a V>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+    {
+        self.get_key_value(key).is_some()
+    }
+}
+
+// Test ----------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::FrozenMap;
+
+    #[test]
+    fn test_frozen_get() {
+        // Sorted keys `0..7`, in Eytzinger order
+        let pairs = [(3, 30), (1, 10), (5, 50), (0, 0), (2, 20), (4, 40), (6, 60)];
+        let map = FrozenMap::new(&pairs);
+
+        assert_eq!(map.len(), 7);
+        for k in 0..7 {
+            assert_eq!(map.get(&k), Some(&(k * 10)));
+        }
+        assert!(!map.contains_key(&-1));
+        assert!(!map.contains_key(&7));
+
+        let empty = FrozenMap::<u8, u8>::new(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.get(&0), None);
+    }
+}
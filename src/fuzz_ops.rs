@@ -0,0 +1,196 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{ArenaIdx, SgMap};
+
+/// A single [`SgMap`][crate::SgMap] API call, decodable from raw fuzzer bytes.
+///
+/// Ops never `panic!` on a full map: inserts are routed through the fallible API and
+/// capacity errors are ignored, so only a genuine library bug can abort a replay.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Op<K, V> {
+    /// [`SgMap::try_insert`][crate::SgMap::try_insert], capacity errors ignored.
+    Insert {
+        /// Key to insert.
+        key: K,
+        /// Value to insert.
+        val: V,
+    },
+    /// [`SgMap::remove`][crate::SgMap::remove].
+    Remove {
+        /// Key to remove.
+        key: K,
+    },
+    /// [`SgMap::get`][crate::SgMap::get].
+    Get {
+        /// Key to look up.
+        key: K,
+    },
+    /// [`SgMap::get_mut`][crate::SgMap::get_mut], overwriting the value if present.
+    GetMut {
+        /// Key to look up.
+        key: K,
+        /// Replacement value.
+        val: V,
+    },
+    /// [`SgMap::contains_key`][crate::SgMap::contains_key].
+    ContainsKey {
+        /// Key to look up.
+        key: K,
+    },
+    /// [`SgMap::pop_first`][crate::SgMap::pop_first].
+    PopFirst,
+    /// [`SgMap::pop_last`][crate::SgMap::pop_last].
+    PopLast,
+    /// [`SgMap::retain`][crate::SgMap::retain], keeping only keys less than `key`.
+    Retain {
+        /// Exclusive upper bound of retained keys.
+        key: K,
+    },
+    /// [`SgMap::split_off`][crate::SgMap::split_off], discarding the upper half.
+    SplitOff {
+        /// Split point.
+        key: K,
+    },
+    /// [`SgMap::clear`][crate::SgMap::clear].
+    Clear,
+}
+
+// Hand-written (vs `#[derive(Arbitrary)]`), derive output requires `std`.
+impl<'a, K: Arbitrary<'a>, V: Arbitrary<'a>> Arbitrary<'a> for Op<K, V> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=9_u8)? {
+            0 => Op::Insert {
+                key: u.arbitrary()?,
+                val: u.arbitrary()?,
+            },
+            1 => Op::Remove {
+                key: u.arbitrary()?,
+            },
+            2 => Op::Get {
+                key: u.arbitrary()?,
+            },
+            3 => Op::GetMut {
+                key: u.arbitrary()?,
+                val: u.arbitrary()?,
+            },
+            4 => Op::ContainsKey {
+                key: u.arbitrary()?,
+            },
+            5 => Op::PopFirst,
+            6 => Op::PopLast,
+            7 => Op::Retain {
+                key: u.arbitrary()?,
+            },
+            8 => Op::SplitOff {
+                key: u.arbitrary()?,
+            },
+            _ => Op::Clear,
+        })
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> Op<K, V> {
+    /// Apply this operation to a map.
+    pub fn apply<const N: usize, U: ArenaIdx>(&self, map: &mut SgMap<K, V, N, U>) {
+        match self {
+            Op::Insert { key, val } => {
+                let _ = map.try_insert(key.clone(), val.clone());
+            }
+            Op::Remove { key } => {
+                map.remove(key);
+            }
+            Op::Get { key } => {
+                map.get(key);
+            }
+            Op::GetMut { key, val } => {
+                if let Some(v) = map.get_mut(key) {
+                    *v = val.clone();
+                }
+            }
+            Op::ContainsKey { key } => {
+                map.contains_key(key);
+            }
+            Op::PopFirst => {
+                map.pop_first();
+            }
+            Op::PopLast => {
+                map.pop_last();
+            }
+            Op::Retain { key } => {
+                map.retain(|k, _| k < key);
+            }
+            Op::SplitOff { key } => {
+                map.split_off(key);
+            }
+            Op::Clear => {
+                map.clear();
+            }
+        }
+
+        debug_assert!(map.len() <= map.capacity());
+    }
+}
+
+/// Apply a sequence of operations to a map, in order.
+///
+/// # Examples
+///
+/// ```
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use scapegoat::fuzz_ops::{apply_ops, Op};
+/// use scapegoat::SgMap;
+///
+/// let raw_fuzz_input = [0x4a, 0x01, 0xff, 0x13, 0x37, 0x00, 0x2a, 0x02, 0x07];
+/// let mut u = Unstructured::new(&raw_fuzz_input);
+/// let ops = Vec::<Op<u8, u8>>::arbitrary(&mut u).unwrap();
+///
+/// let mut map = SgMap::<u8, u8, 4>::new();
+/// apply_ops(&mut map, &ops);
+/// assert!(map.len() <= map.capacity());
+/// ```
+pub fn apply_ops<K, V, const N: usize, U: ArenaIdx>(map: &mut SgMap<K, V, N, U>, ops: &[Op<K, V>])
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    for op in ops {
+        op.apply(map);
+    }
+}
+
+// Test ----------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_ops, Op};
+    use crate::SgMap;
+    use arbitrary::{Arbitrary, Unstructured};
+
+    #[test]
+    fn test_apply_ops() {
+        let mut map = SgMap::<u8, u8, 3>::new();
+        let ops = [
+            Op::Insert { key: 1, val: 1 },
+            Op::Insert { key: 2, val: 2 },
+            Op::Insert { key: 3, val: 3 },
+            Op::Insert { key: 4, val: 4 }, // Full, ignored
+            Op::GetMut { key: 2, val: 20 },
+            Op::PopFirst,
+            Op::Retain { key: 3 },
+        ];
+
+        apply_ops(&mut map, &ops);
+        assert!(map.into_iter().eq([(2, 20)]));
+    }
+
+    #[test]
+    fn test_apply_ops_from_bytes() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let mut u = Unstructured::new(&bytes);
+        let ops = Vec::<Op<u16, u16>>::arbitrary(&mut u).unwrap();
+
+        let mut map = SgMap::<u16, u16, 16>::new();
+        apply_ops(&mut map, &ops);
+        assert!(map.len() <= map.capacity());
+    }
+}
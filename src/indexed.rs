@@ -0,0 +1,346 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+use core::ops::RangeBounds;
+
+use crate::indexed_types::{Iter, ValueIter};
+use crate::tree::{
+    above_end, below_start, ArenaIdx, Compare, Natural, SgError, SgTree, SmallNode, StackVec,
+};
+
+/// Ordered map with a secondary index ordering its entries by value.
+///
+/// Iterates sorted by key via [`iter`][crate::SgIndexedMap::iter],
+/// or sorted by value via [`iter_by_value`][crate::SgIndexedMap::iter_by_value] (ties broken by key order).
+/// The index is an array of the tree's arena slots, kept sorted by value: [`min_by_value`][crate::SgIndexedMap::min_by_value]
+/// and [`max_by_value`][crate::SgIndexedMap::max_by_value] are `O(1)`, [`range_by_value`][crate::SgIndexedMap::range_by_value]
+/// takes `O(log n)` comparisons to locate its bounds. Key lookups are unaffected.
+/// Inserts and removals also shift the index, an `O(n)` `memmove` of `N` small integers at most.
+///
+/// Values can't be mutated in place (that would silently reorder the index),
+/// use [`modify`][crate::SgIndexedMap::modify] instead.
+///
+/// ### Fallible APIs
+///
+/// * [`try_insert`][crate::SgIndexedMap::try_insert]
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgIndexedMap;
+///
+/// let mut scores = SgIndexedMap::<&str, u32, 10>::new();
+/// scores.insert("carol", 70);
+/// scores.insert("alice", 90);
+/// scores.insert("bob", 70);
+///
+/// assert!(scores.iter().map(|(k, _)| *k).eq(["alice", "bob", "carol"]));
+/// assert!(scores.iter_by_value().map(|(k, _)| *k).eq(["bob", "carol", "alice"]));
+/// assert_eq!(scores.min_by_value(), Some((&"bob", &70)));
+///
+/// assert!(scores.modify(&"bob", |s| *s += 25));
+/// assert_eq!(scores.max_by_value(), Some((&"bob", &95)));
+/// assert!(scores.range_by_value(80..).map(|(k, _)| *k).eq(["alice", "bob"]));
+/// ```
+pub struct SgIndexedMap<K: Ord, V: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural>
+{
+    pub(crate) bst: SgTree<K, V, N, U, C>,
+    pub(crate) by_val: StackVec<[U; N]>,
+}
+
+impl<K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K>> SgIndexedMap<K, V, N, U, C> {
+    /// Makes a new, empty `SgIndexedMap`.
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_comparator(C::default())
+    }
+
+    /// Makes a new, empty `SgIndexedMap` ordered by `cmp` instead of `K`'s [`Ord`] implementation.
+    /// See [`Compare`][crate::Compare]. Values are always ordered by their own [`Ord`] implementation.
+    pub fn with_comparator(cmp: C) -> Self {
+        SgIndexedMap {
+            bst: SgTree::with_comparator(cmp),
+            by_val: StackVec::new(),
+        }
+    }
+
+    /// Total capacity, e.g. maximum number of key-value pairs.
+    pub fn capacity(&self) -> usize {
+        self.bst.capacity()
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.bst.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.bst.is_empty()
+    }
+
+    /// Returns `true` if the map's capacity is filled.
+    pub fn is_full(&self) -> bool {
+        self.bst.is_full()
+    }
+
+    /// Clears the map, removing all elements.
+    pub fn clear(&mut self) {
+        self.bst.clear();
+        self.by_val.clear();
+    }
+
+    /// Insert a key-value pair into the map.
+    /// If the map did not have this key present, `None` is returned.
+    /// If the map did have this key present, the value is updated (and re-indexed), the old value is returned,
+    /// and the key is updated. This accommodates types that can be `==` without being identical.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is full and the key isn't already present.
+    /// See [`try_insert`][crate::SgIndexedMap::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        if let Some(idx) = self.bst.priv_get::<K, U>(None, &key).node_idx() {
+            self.unindex(idx);
+        }
+
+        // Replacing a present key keeps its arena slot
+        let (opt_old, idx) = self.bst.priv_balancing_insert(key, val);
+        self.index(idx);
+        opt_old
+    }
+
+    /// Insert a key-value pair into the map.
+    /// Returns `Err` if the map is full and the key isn't already present.
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<Option<V>, SgError> {
+        if self.is_full() && !self.bst.contains_key::<K>(&key) {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        Ok(self.insert(key, val))
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get(key)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.contains_key(key)
+    }
+
+    /// Calls `f` on the value corresponding to the key, then re-indexes it by its new value.
+    /// Returns `false` if the key isn't present.
+    pub fn modify<Q, F>(&mut self, key: &Q, f: F) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+        F: FnOnce(&mut V),
+    {
+        match self.bst.priv_get::<Q, U>(None, key).node_idx() {
+            Some(idx) => {
+                self.unindex(idx);
+                f(self.bst.arena[idx].get_mut().1);
+                self.index(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        let idx = self.bst.priv_get::<Q, U>(None, key).node_idx()?;
+        self.remove_by_idx(idx).map(|(_, v)| v)
+    }
+
+    /// Returns the key-value pair with the smallest value, `None` if empty. `O(1)`.
+    /// Ties are broken by key order.
+    pub fn min_by_value(&self) -> Option<(&K, &V)> {
+        self.by_val.first().map(|idx| self.pair(*idx))
+    }
+
+    /// Returns the key-value pair with the largest value, `None` if empty. `O(1)`.
+    /// Ties are broken by key order.
+    pub fn max_by_value(&self) -> Option<(&K, &V)> {
+        self.by_val.last().map(|idx| self.pair(*idx))
+    }
+
+    /// Removes and returns the key-value pair with the smallest value, `None` if empty.
+    pub fn pop_min_by_value(&mut self) -> Option<(K, V)> {
+        let idx = self.by_val.first()?.usize();
+        self.remove_by_idx(idx)
+    }
+
+    /// Removes and returns the key-value pair with the largest value, `None` if empty.
+    pub fn pop_max_by_value(&mut self) -> Option<(K, V)> {
+        let idx = self.by_val.last()?.usize();
+        self.remove_by_idx(idx)
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    pub fn iter(&self) -> Iter<'_, K, V, N, U, C> {
+        Iter::new(self)
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by value (ties broken by key order).
+    pub fn iter_by_value(&self) -> ValueIter<'_, K, V, N, U, C> {
+        ValueIter::new(self, &self.by_val)
+    }
+
+    /// Gets an iterator over the entries whose values fall within `range`, sorted by value (ties broken by key order).
+    ///
+    /// The range may be over any borrowed form of the map's value type, but the ordering
+    /// on the borrowed form *must* match the ordering on the value type.
+    pub fn range_by_value<Q, R>(&self, range: R) -> ValueIter<'_, K, V, N, U, C>
+    where
+        V: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let val = |idx: &U| self.bst.arena[idx.usize()].val().borrow();
+        let start = self
+            .by_val
+            .partition_point(|idx| below_start(&Natural, range.start_bound(), val(idx)));
+        let end = self
+            .by_val
+            .partition_point(|idx| !above_end(&Natural, range.end_bound(), val(idx)));
+
+        ValueIter::new(self, &self.by_val[start..end.max(start)])
+    }
+
+    // Pair stored at a given arena index.
+    fn pair(&self, idx: U) -> (&K, &V) {
+        let node = &self.bst.arena[idx.usize()];
+        (node.key(), node.val())
+    }
+
+    // Index position of a node's pair, whether or not it's currently indexed.
+    fn index_pos(&self, idx: usize) -> usize {
+        let (key, val) = self.pair(U::checked_from(idx));
+        self.by_val.partition_point(|probe| {
+            let (probe_key, probe_val) = self.pair(*probe);
+            probe_val
+                .cmp(val)
+                .then_with(|| self.bst.cmp.compare(probe_key, key))
+                == Ordering::Less
+        })
+    }
+
+    // Add a node to the value index.
+    fn index(&mut self, idx: usize) {
+        let pos = self.index_pos(idx);
+        self.by_val.insert(pos, U::checked_from(idx));
+    }
+
+    // Remove a node from the value index.
+    fn unindex(&mut self, idx: usize) {
+        let pos = self.index_pos(idx);
+        debug_assert_eq!(self.by_val.get(pos).map(|i| i.usize()), Some(idx));
+        self.by_val.remove(pos);
+    }
+
+    // Remove a node from both the index and the tree.
+    fn remove_by_idx(&mut self, idx: usize) -> Option<(K, V)> {
+        self.unindex(idx);
+        let opt_kv = self.bst.priv_remove_by_idx(idx);
+        self.bst.rebuild_if_sparse();
+        opt_kv
+    }
+}
+
+// Convenience Traits --------------------------------------------------------------------------------------------------
+
+// Clone
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Clone for SgIndexedMap<K, V, N, U, C>
+where
+    K: Ord + Clone,
+    V: Ord + Clone,
+    C: Clone,
+{
+    fn clone(&self) -> Self {
+        SgIndexedMap {
+            bst: self.bst.clone(),
+            by_val: self.by_val.clone(),
+        }
+    }
+}
+
+// Debug
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Debug for SgIndexedMap<K, V, N, U, C>
+where
+    K: Ord + Debug,
+    V: Ord + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Default
+impl<K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K> + Default> Default
+    for SgIndexedMap<K, V, N, U, C>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// From iterator
+impl<K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K> + Default> FromIterator<(K, V)>
+    for SgIndexedMap<K, V, N, U, C>
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut imap = SgIndexedMap::new();
+        imap.extend(iter);
+        imap
+    }
+}
+
+// Extension from iterator
+impl<K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K>> Extend<(K, V)>
+    for SgIndexedMap<K, V, N, U, C>
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(move |(k, v)| {
+            self.insert(k, v);
+        });
+    }
+}
+
+// Iterators -----------------------------------------------------------------------------------------------------------
+
+// Reference iterator
+impl<'a, K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K>> IntoIterator
+    for &'a SgIndexedMap<K, V, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, N, U, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
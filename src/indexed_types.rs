@@ -0,0 +1,120 @@
+use core::iter::FusedIterator;
+
+use crate::indexed::SgIndexedMap;
+use crate::tree::{ArenaIdx, Compare, Iter as TreeIter, Natural, SgTree, SmallNode};
+
+// General Iterators ---------------------------------------------------------------------------------------------------
+
+/// An iterator over the entries of a [`SgIndexedMap`][crate::SgIndexedMap], sorted by key.
+///
+/// This `struct` is created by the [`iter`][crate::SgIndexedMap::iter] method on [`SgIndexedMap`][crate::SgIndexedMap].
+/// See its documentation for more.
+pub struct Iter<'a, K: Ord, V: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    ref_iter: TreeIter<'a, K, V, N, U, C>,
+}
+
+impl<'a, K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K>> Iter<'a, K, V, N, U, C> {
+    /// Construct reference iterator.
+    pub(crate) fn new(imap: &'a SgIndexedMap<K, V, N, U, C>) -> Self {
+        Iter {
+            ref_iter: TreeIter::new(&imap.bst),
+        }
+    }
+}
+
+impl<'a, K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for Iter<'a, K, V, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ref_iter.size_hint()
+    }
+}
+
+impl<'a, K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for Iter<'a, K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next_back()
+    }
+}
+
+impl<'a, K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for Iter<'a, K, V, N, U, C>
+{
+    fn len(&self) -> usize {
+        self.ref_iter.len()
+    }
+}
+
+impl<'a, K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for Iter<'a, K, V, N, U, C>
+{
+}
+
+/// An iterator over the entries of a [`SgIndexedMap`][crate::SgIndexedMap], sorted by value (ties broken by key order).
+///
+/// This `struct` is created by the [`iter_by_value`][crate::SgIndexedMap::iter_by_value] and
+/// [`range_by_value`][crate::SgIndexedMap::range_by_value] methods on [`SgIndexedMap`][crate::SgIndexedMap].
+/// See their documentation for more.
+pub struct ValueIter<'a, K: Ord, V: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural>
+{
+    bst: &'a SgTree<K, V, N, U, C>,
+    idx_iter: core::slice::Iter<'a, U>,
+}
+
+impl<'a, K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K>> ValueIter<'a, K, V, N, U, C> {
+    /// Construct value order iterator, over a (sub-)slice of the map's value index.
+    pub(crate) fn new(imap: &'a SgIndexedMap<K, V, N, U, C>, idxs: &'a [U]) -> Self {
+        ValueIter {
+            bst: &imap.bst,
+            idx_iter: idxs.iter(),
+        }
+    }
+
+    // Pair stored at a given arena index.
+    fn pair(&self, idx: U) -> (&'a K, &'a V) {
+        let node = &self.bst.arena[idx.usize()];
+        (node.key(), node.val())
+    }
+}
+
+impl<'a, K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for ValueIter<'a, K, V, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.idx_iter.next().map(|idx| self.pair(*idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.idx_iter.size_hint()
+    }
+}
+
+impl<'a, K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for ValueIter<'a, K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.idx_iter.next_back().map(|idx| self.pair(*idx))
+    }
+}
+
+impl<'a, K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for ValueIter<'a, K, V, N, U, C>
+{
+    fn len(&self) -> usize {
+        self.idx_iter.len()
+    }
+}
+
+impl<'a, K: Ord, V: Ord, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for ValueIter<'a, K, V, N, U, C>
+{
+}
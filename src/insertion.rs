@@ -0,0 +1,324 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+
+use crate::insertion_types::{InsertionOrderIter, Iter};
+use crate::tree::{ArenaIdx, AugmentHook, Compare, Natural, SgError, SgTree, SmallNode};
+
+/// Ordered map that also remembers the order in which keys arrived.
+///
+/// Iterates sorted by key via [`iter`][crate::SgInsertionMap::iter],
+/// or first-inserted-first via [`iter_insertion_order`][crate::SgInsertionMap::iter_insertion_order].
+/// Each node stores a sequence number, plus the oldest sequence number in its subtree,
+/// so [`oldest`][crate::SgInsertionMap::oldest] and [`pop_oldest`][crate::SgInsertionMap::pop_oldest] run in `O(log n)` time.
+///
+/// Re-inserting a present key updates its value but keeps its original position in insertion order.
+/// To move a key to the back, [`remove`][crate::SgInsertionMap::remove] it first.
+///
+/// ### Fallible APIs
+///
+/// * [`try_insert`][crate::SgInsertionMap::try_insert]
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgInsertionMap;
+///
+/// let mut arrivals = SgInsertionMap::<&str, u32, 10>::new();
+/// arrivals.insert("carol", 3);
+/// arrivals.insert("alice", 1);
+/// arrivals.insert("bob", 2);
+///
+/// assert!(arrivals.iter().map(|(k, _)| *k).eq(["alice", "bob", "carol"]));
+/// assert!(arrivals.iter_insertion_order().map(|(k, _)| *k).eq(["carol", "alice", "bob"]));
+///
+/// assert_eq!(arrivals.pop_oldest(), Some(("carol", 3)));
+/// assert_eq!(arrivals.oldest(), Some((&"alice", &1)));
+/// ```
+pub struct SgInsertionMap<K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    pub(crate) bst: SgTree<K, SeqVal<V>, N, U, C, UpdateMinSeq>,
+    next_seq: u64,
+}
+
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> SgInsertionMap<K, V, N, U, C> {
+    /// Makes a new, empty `SgInsertionMap`.
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_comparator(C::default())
+    }
+
+    /// Makes a new, empty `SgInsertionMap` ordered by `cmp` instead of `K`'s [`Ord`] implementation.
+    /// See [`Compare`][crate::Compare].
+    pub fn with_comparator(cmp: C) -> Self {
+        SgInsertionMap {
+            bst: SgTree::with_comparator(cmp),
+            next_seq: 0,
+        }
+    }
+
+    /// Total capacity, e.g. maximum number of key-value pairs.
+    pub fn capacity(&self) -> usize {
+        self.bst.capacity()
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.bst.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.bst.is_empty()
+    }
+
+    /// Returns `true` if the map's capacity is filled.
+    pub fn is_full(&self) -> bool {
+        self.bst.is_full()
+    }
+
+    /// Clears the map, removing all elements.
+    pub fn clear(&mut self) {
+        self.bst.clear();
+        self.next_seq = 0;
+    }
+
+    /// Insert a key-value pair into the map.
+    /// If the map did not have this key present, `None` is returned and the key becomes the newest.
+    /// If the map did have this key present, the value is updated, the old value is returned,
+    /// and the key is updated, but its position in insertion order is unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is full and the key isn't already present.
+    /// See [`try_insert`][crate::SgInsertionMap::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        let seq = self.seq_for(&key);
+        self.bst.insert(key, SeqVal::new(seq, val)).map(|sv| sv.val)
+    }
+
+    /// Insert a key-value pair into the map.
+    /// Returns `Err` if the map is full and the key isn't already present.
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<Option<V>, SgError> {
+        if self.is_full() && !self.bst.contains_key::<K>(&key) {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        Ok(self.insert(key, val))
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get(key).map(|sv| &sv.val)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    /// Doesn't affect insertion order.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get_mut(key).map(|sv| &mut sv.val)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.contains_key(key)
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.remove(key).map(|sv| sv.val)
+    }
+
+    /// Returns the least recently inserted key-value pair, `None` if empty. `O(log n)`.
+    pub fn oldest(&self) -> Option<(&K, &V)> {
+        self.oldest_idx().map(|idx| {
+            let node = &self.bst.arena[idx];
+            (node.key(), &node.val().val)
+        })
+    }
+
+    /// Removes and returns the least recently inserted key-value pair, `None` if empty. `O(log n)`.
+    pub fn pop_oldest(&mut self) -> Option<(K, V)> {
+        let idx = self.oldest_idx()?;
+        let (key, sv) = self.bst.priv_remove_by_idx(idx)?;
+        self.bst.rebuild_if_sparse();
+        Some((key, sv.val))
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    pub fn iter(&self) -> Iter<'_, K, V, N, U, C> {
+        Iter::new(self)
+    }
+
+    /// Gets an iterator over the entries of the map, least recently inserted first.
+    /// Construction sorts an index buffer of `N` elements on the stack, in `O(n log n)` time.
+    #[doc(alias = "iter_arrival_order")]
+    pub fn iter_insertion_order(&self) -> InsertionOrderIter<'_, K, V, N, U, C> {
+        InsertionOrderIter::new(self)
+    }
+
+    // Sequence number for an insert: existing keys keep theirs, new keys get the next one.
+    fn seq_for(&mut self, key: &K) -> u64 {
+        match self.bst.get::<K>(key) {
+            Some(sv) => sv.seq,
+            None => {
+                let seq = self.next_seq;
+                self.next_seq += 1;
+                seq
+            }
+        }
+    }
+
+    // Follow the subtree minimum down to the node that holds it.
+    fn oldest_idx(&self) -> Option<usize> {
+        let mut idx = self.bst.opt_root_idx?;
+        loop {
+            let node = &self.bst.arena[idx];
+            let min_seq = node.val().min_seq;
+            let in_subtree = |opt_idx: Option<usize>| {
+                opt_idx.filter(|&i| self.bst.arena[i].val().min_seq == min_seq)
+            };
+
+            match (in_subtree(node.left_idx()), in_subtree(node.right_idx())) {
+                (Some(left_idx), _) => idx = left_idx,
+                (None, Some(right_idx)) if node.val().seq != min_seq => idx = right_idx,
+                _ => return Some(idx),
+            }
+        }
+    }
+}
+
+// Convenience Traits --------------------------------------------------------------------------------------------------
+
+// Clone
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Clone for SgInsertionMap<K, V, N, U, C>
+where
+    K: Ord + Clone,
+    V: Clone,
+    C: Clone,
+{
+    fn clone(&self) -> Self {
+        SgInsertionMap {
+            bst: self.bst.clone(),
+            next_seq: self.next_seq,
+        }
+    }
+}
+
+// Debug
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Debug for SgInsertionMap<K, V, N, U, C>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Default
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K> + Default> Default
+    for SgInsertionMap<K, V, N, U, C>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// From iterator
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K> + Default> FromIterator<(K, V)>
+    for SgInsertionMap<K, V, N, U, C>
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut imap = SgInsertionMap::new();
+        imap.extend(iter);
+        imap
+    }
+}
+
+// Extension from iterator
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Extend<(K, V)>
+    for SgInsertionMap<K, V, N, U, C>
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(move |(k, v)| {
+            self.insert(k, v);
+        });
+    }
+}
+
+// Iterators -----------------------------------------------------------------------------------------------------------
+
+// Reference iterator
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> IntoIterator
+    for &'a SgInsertionMap<K, V, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, N, U, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Internal Types ------------------------------------------------------------------------------------------------------
+
+// Value plus its insertion sequence number, and the oldest sequence number in its subtree.
+#[derive(Clone, Debug)]
+pub(crate) struct SeqVal<V> {
+    pub(crate) val: V,
+    pub(crate) seq: u64,
+    min_seq: u64,
+}
+
+impl<V> SeqVal<V> {
+    fn new(seq: u64, val: V) -> Self {
+        SeqVal {
+            val,
+            seq,
+            min_seq: seq,
+        }
+    }
+}
+
+/// Augmentation callback, maintained by the tree through every insert, remove, and rebuild.
+#[derive(Clone, Copy)]
+pub(crate) struct UpdateMinSeq;
+
+impl<K, V> AugmentHook<K, SeqVal<V>> for UpdateMinSeq {
+    fn augment(
+        _key: &K,
+        node: &mut SeqVal<V>,
+        opt_left: Option<&SeqVal<V>>,
+        opt_right: Option<&SeqVal<V>>,
+    ) {
+        node.min_seq = [opt_left, opt_right]
+            .iter()
+            .flatten()
+            .map(|child| child.min_seq)
+            .fold(node.seq, u64::min);
+    }
+}
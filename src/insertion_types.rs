@@ -0,0 +1,156 @@
+use core::iter::FusedIterator;
+
+use crate::insertion::{SeqVal, SgInsertionMap, UpdateMinSeq};
+use crate::tree::{ArenaIdx, Compare, Iter as TreeIter, Natural, SgTree, SmallNode};
+
+use crate::tree::StackVec;
+
+// General Iterators ---------------------------------------------------------------------------------------------------
+
+/// An iterator over the entries of a [`SgInsertionMap`][crate::SgInsertionMap], sorted by key.
+///
+/// This `struct` is created by the [`iter`][crate::SgInsertionMap::iter] method on [`SgInsertionMap`][crate::SgInsertionMap].
+/// See its documentation for more.
+pub struct Iter<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    ref_iter: TreeIter<'a, K, SeqVal<V>, N, U, C, UpdateMinSeq>,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iter<'a, K, V, N, U, C> {
+    /// Construct reference iterator.
+    pub(crate) fn new(imap: &'a SgInsertionMap<K, V, N, U, C>) -> Self {
+        Iter {
+            ref_iter: TreeIter::new(&imap.bst),
+        }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for Iter<'a, K, V, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next().map(|(k, sv)| (k, &sv.val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ref_iter.size_hint()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for Iter<'a, K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next_back().map(|(k, sv)| (k, &sv.val))
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for Iter<'a, K, V, N, U, C>
+{
+    fn len(&self) -> usize {
+        self.ref_iter.len()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for Iter<'a, K, V, N, U, C>
+{
+}
+
+/// An iterator over the entries of a [`SgInsertionMap`][crate::SgInsertionMap], least recently inserted first.
+///
+/// This `struct` is created by the [`iter_insertion_order`][crate::SgInsertionMap::iter_insertion_order] method on
+/// [`SgInsertionMap`][crate::SgInsertionMap]. See its documentation for more.
+pub struct InsertionOrderIter<
+    'a,
+    K: Ord,
+    V,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
+    bst: &'a SgTree<K, SeqVal<V>, N, U, C, UpdateMinSeq>,
+    idxs: StackVec<[U; N]>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>>
+    InsertionOrderIter<'a, K, V, N, U, C>
+{
+    /// Construct insertion order iterator.
+    pub(crate) fn new(imap: &'a SgInsertionMap<K, V, N, U, C>) -> Self {
+        let bst = &imap.bst;
+        let mut idxs: StackVec<[U; N]> = bst
+            .arena
+            .iter()
+            .enumerate()
+            .filter(|(_, opt_node)| opt_node.is_some())
+            .map(|(idx, _)| U::checked_from(idx))
+            .collect();
+
+        idxs.sort_unstable_by_key(|idx| bst.arena[idx.usize()].val().seq);
+
+        InsertionOrderIter {
+            bst,
+            back: idxs.len(),
+            idxs,
+            front: 0,
+        }
+    }
+
+    // Pair stored at a given arena index.
+    fn pair(&self, idx: U) -> (&'a K, &'a V) {
+        let node = &self.bst.arena[idx.usize()];
+        (node.key(), &node.val().val)
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for InsertionOrderIter<'a, K, V, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            let idx = self.idxs[self.front];
+            self.front += 1;
+            Some(self.pair(idx))
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for InsertionOrderIter<'a, K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.pair(self.idxs[self.back]))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for InsertionOrderIter<'a, K, V, N, U, C>
+{
+    fn len(&self) -> usize {
+        self.back - self.front
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for InsertionOrderIter<'a, K, V, N, U, C>
+{
+}
@@ -0,0 +1,362 @@
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+use core::ops::Range;
+
+use crate::augment::{AugVal, Augment, UpdateAug};
+use crate::interval_types::{Iter, Overlaps};
+use crate::tree::{ArenaIdx, Natural, SgError, SgTree};
+
+/// Safe, fallible, embedded-friendly interval map: associates half-open ranges (`start..end`) with values.
+///
+/// Backed by an augmented scapegoat tree: every node caches the maximum `end` within its subtree,
+/// so [`overlaps`][crate::SgIntervalMap::overlaps] and [`stab`][crate::SgIntervalMap::stab] skip
+/// subtrees that can't contain a match instead of scanning every entry.
+///
+/// Ranges are ordered by `start`, then by `end`.
+/// Each distinct range is a unique key, overlapping ranges may coexist.
+/// Empty ranges (`start >= end`) can be stored, but never overlap anything.
+///
+/// ### Fallible APIs
+///
+/// * [`try_insert`][crate::SgIntervalMap::try_insert]
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgIntervalMap;
+///
+/// let mut regions = SgIntervalMap::<_, _, 10>::new();
+/// regions.insert(0x0000..0x4000, "flash");
+/// regions.insert(0x2000..0x3000, "bootloader");
+/// regions.insert(0x8000..0x9000, "sram");
+///
+/// // Every region containing an address
+/// assert!(regions.stab(&0x2800).eq([(&(0x0000..0x4000), &"flash"), (&(0x2000..0x3000), &"bootloader")]));
+///
+/// // Every region touching an address range
+/// assert!(regions.overlaps(0x3800..0x8001).eq([(&(0x0000..0x4000), &"flash"), (&(0x8000..0x9000), &"sram")]));
+/// ```
+#[derive(Clone)]
+pub struct SgIntervalMap<K: Ord + Clone, V, const N: usize, U: ArenaIdx = u16> {
+    pub(crate) bst: SgTree<IntervalKey<K>, AugVal<V, MaxEnd<K>>, N, U, Natural, UpdateAug>,
+}
+
+impl<K: Ord + Clone, V, const N: usize, U: ArenaIdx> SgIntervalMap<K, V, N, U> {
+    /// Makes a new, empty `SgIntervalMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgIntervalMap;
+    ///
+    /// let map: SgIntervalMap<u32, &str, 10> = SgIntervalMap::new();
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        SgIntervalMap {
+            bst: SgTree::with_comparator(Natural),
+        }
+    }
+
+    /// Total capacity, e.g. maximum number of ranges.
+    pub fn capacity(&self) -> usize {
+        self.bst.capacity()
+    }
+
+    /// Returns the number of ranges in the map.
+    pub fn len(&self) -> usize {
+        self.bst.len()
+    }
+
+    /// Returns `true` if the map contains no ranges.
+    pub fn is_empty(&self) -> bool {
+        self.bst.is_empty()
+    }
+
+    /// Returns `true` if the map's capacity is filled.
+    pub fn is_full(&self) -> bool {
+        self.bst.is_full()
+    }
+
+    /// Clears the map, removing all ranges.
+    pub fn clear(&mut self) {
+        self.bst.clear();
+    }
+
+    /// Inserts a range-value pair into the map.
+    /// If an identical range was already present, its value is replaced and the old value returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is full and the range isn't already present.
+    /// See [`try_insert`][crate::SgIntervalMap::try_insert] for a fallible alternative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgIntervalMap;
+    ///
+    /// let mut map = SgIntervalMap::<_, _, 10>::new();
+    /// assert_eq!(map.insert(1..5, "a"), None);
+    /// assert_eq!(map.insert(1..5, "b"), Some("a"));
+    /// assert_eq!(map.insert(1..6, "c"), None);
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    pub fn insert(&mut self, range: Range<K>, val: V) -> Option<V> {
+        let aug = MaxEnd(range.end.clone());
+        self.bst
+            .insert(IntervalKey(range), AugVal { val, aug })
+            .map(|av| av.val)
+    }
+
+    /// Inserts a range-value pair into the map.
+    /// Returns `Err` if the map is full and the range isn't already present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgIntervalMap, SgError};
+    ///
+    /// let mut map = SgIntervalMap::<_, _, 1>::new();
+    /// assert_eq!(map.try_insert(1..5, "a"), Ok(None));
+    /// assert_eq!(map.try_insert(1..5, "b"), Ok(Some("a")));
+    /// assert_eq!(map.try_insert(2..5, "c"), Err(SgError::StackCapacityExceeded));
+    /// ```
+    pub fn try_insert(&mut self, range: Range<K>, val: V) -> Result<Option<V>, SgError> {
+        let aug = MaxEnd(range.end.clone());
+        self.bst
+            .try_insert(IntervalKey(range), AugVal { val, aug })
+            .map(|opt_av| opt_av.map(|av| av.val))
+    }
+
+    /// Returns a reference to the value associated with an exact range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgIntervalMap;
+    ///
+    /// let mut map = SgIntervalMap::<_, _, 10>::new();
+    /// map.insert(1..5, "a");
+    ///
+    /// assert_eq!(map.get(&(1..5)), Some(&"a"));
+    /// assert_eq!(map.get(&(1..4)), None);
+    /// ```
+    pub fn get(&self, range: &Range<K>) -> Option<&V> {
+        self.bst.get(&IntervalKey(range.clone())).map(|av| &av.val)
+    }
+
+    /// Returns a mutable reference to the value associated with an exact range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgIntervalMap;
+    ///
+    /// let mut map = SgIntervalMap::<_, _, 10>::new();
+    /// map.insert(1..5, "a");
+    /// if let Some(val) = map.get_mut(&(1..5)) {
+    ///     *val = "b";
+    /// }
+    ///
+    /// assert_eq!(map.get(&(1..5)), Some(&"b"));
+    /// ```
+    pub fn get_mut(&mut self, range: &Range<K>) -> Option<&mut V> {
+        self.bst
+            .get_mut(&IntervalKey(range.clone()))
+            .map(|av| &mut av.val)
+    }
+
+    /// Returns `true` if the map contains the exact range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgIntervalMap;
+    ///
+    /// let mut map = SgIntervalMap::<_, _, 10>::new();
+    /// map.insert(1..5, "a");
+    ///
+    /// assert!(map.contains_range(&(1..5)));
+    /// assert!(!map.contains_range(&(2..5)));
+    /// ```
+    pub fn contains_range(&self, range: &Range<K>) -> bool {
+        self.bst.contains_key(&IntervalKey(range.clone()))
+    }
+
+    /// Removes an exact range from the map, returning its value if it was present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgIntervalMap;
+    ///
+    /// let mut map = SgIntervalMap::<_, _, 10>::new();
+    /// map.insert(1..5, "a");
+    ///
+    /// assert_eq!(map.remove(&(1..5)), Some("a"));
+    /// assert_eq!(map.remove(&(1..5)), None);
+    /// ```
+    pub fn remove(&mut self, range: &Range<K>) -> Option<V> {
+        self.bst
+            .remove(&IntervalKey(range.clone()))
+            .map(|av| av.val)
+    }
+
+    /// Gets an iterator over all range-value pairs, ordered by range start (then end).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgIntervalMap;
+    ///
+    /// let mut map = SgIntervalMap::<_, _, 10>::new();
+    /// map.insert(3..4, "b");
+    /// map.insert(1..9, "a");
+    ///
+    /// assert!(map.iter().eq([(&(1..9), &"a"), (&(3..4), &"b")]));
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V, N, U> {
+        Iter::new(self)
+    }
+
+    /// Gets an iterator over every range-value pair whose range overlaps `range`, ordered by range start (then end).
+    /// Ranges are half-open, so ranges that merely touch (e.g. `1..3` and `3..5`) don't overlap.
+    ///
+    /// Lazy: subtrees whose maximum `end` can't reach `range.start`, or whose ranges all start at or past `range.end`,
+    /// are never visited. Reporting `k` overlaps in a balanced tree of `n` ranges takes `O(log n + k)` node visits
+    /// when overlaps are contiguous in start order, and at most `O(k log n)` in general.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgIntervalMap;
+    ///
+    /// let mut map = SgIntervalMap::<_, _, 10>::new();
+    /// map.insert(0..10, 'a');
+    /// map.insert(3..5, 'b');
+    /// map.insert(10..20, 'c');
+    ///
+    /// assert!(map.overlaps(4..11).eq([(&(0..10), &'a'), (&(3..5), &'b'), (&(10..20), &'c')]));
+    /// assert!(map.overlaps(5..10).eq([(&(0..10), &'a')]));
+    /// assert_eq!(map.overlaps(20..30).next(), None);
+    /// ```
+    pub fn overlaps(&self, range: Range<K>) -> Overlaps<'_, K, V, N, U> {
+        Overlaps::new(self, range.start, range.end, false)
+    }
+
+    /// Gets an iterator over every range-value pair whose range contains `point`, ordered by range start (then end).
+    /// Same pruning and complexity as [`overlaps`][crate::SgIntervalMap::overlaps].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgIntervalMap;
+    ///
+    /// let mut map = SgIntervalMap::<_, _, 10>::new();
+    /// map.insert(0..10, 'a');
+    /// map.insert(3..5, 'b');
+    /// map.insert(10..20, 'c');
+    ///
+    /// assert!(map.stab(&4).eq([(&(0..10), &'a'), (&(3..5), &'b')]));
+    /// assert!(map.stab(&10).eq([(&(10..20), &'c')]));
+    /// assert_eq!(map.stab(&20).next(), None);
+    /// ```
+    pub fn stab(&self, point: &K) -> Overlaps<'_, K, V, N, U> {
+        Overlaps::new(self, point.clone(), point.clone(), true)
+    }
+}
+
+// Convenience Traits --------------------------------------------------------------------------------------------------
+
+// Debug
+impl<K, V, const N: usize, U: ArenaIdx> Debug for SgIntervalMap<K, V, N, U>
+where
+    K: Ord + Clone + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Default
+impl<K: Ord + Clone, V, const N: usize, U: ArenaIdx> Default for SgIntervalMap<K, V, N, U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// From iterator
+impl<K: Ord + Clone, V, const N: usize, U: ArenaIdx> FromIterator<(Range<K>, V)>
+    for SgIntervalMap<K, V, N, U>
+{
+    fn from_iter<I: IntoIterator<Item = (Range<K>, V)>>(iter: I) -> Self {
+        let mut imap = SgIntervalMap::new();
+        imap.extend(iter);
+        imap
+    }
+}
+
+// Extension from iterator
+impl<K: Ord + Clone, V, const N: usize, U: ArenaIdx> Extend<(Range<K>, V)>
+    for SgIntervalMap<K, V, N, U>
+{
+    fn extend<T: IntoIterator<Item = (Range<K>, V)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(move |(r, v)| {
+            self.insert(r, v);
+        });
+    }
+}
+
+// Iterators -----------------------------------------------------------------------------------------------------------
+
+// Reference iterator
+impl<'a, K: Ord + Clone, V, const N: usize, U: ArenaIdx> IntoIterator
+    for &'a SgIntervalMap<K, V, N, U>
+{
+    type Item = (&'a Range<K>, &'a V);
+    type IntoIter = Iter<'a, K, V, N, U>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Internal Key and Value ----------------------------------------------------------------------------------------------
+
+/// Tree key: a range, ordered by `start` then `end` (`Range` itself isn't `Ord`).
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct IntervalKey<K>(pub(crate) Range<K>);
+
+impl<K: Ord> Ord for IntervalKey<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .start
+            .cmp(&other.0.start)
+            .then_with(|| self.0.end.cmp(&other.0.end))
+    }
+}
+
+impl<K: Ord> PartialOrd for IntervalKey<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Tree metadata: the maximum `end` of any range in a node's subtree.
+#[derive(Clone)]
+pub(crate) struct MaxEnd<K>(pub(crate) K);
+
+impl<K: Ord + Clone, V> Augment<IntervalKey<K>, V> for MaxEnd<K> {
+    fn augment(key: &IntervalKey<K>, _val: &V, left: Option<&Self>, right: Option<&Self>) -> Self {
+        let max_end = [left, right]
+            .iter()
+            .flatten()
+            .map(|child| &child.0)
+            .fold(&key.0.end, |max, end| max.max(end));
+
+        MaxEnd(max_end.clone())
+    }
+}
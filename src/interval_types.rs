@@ -0,0 +1,154 @@
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::tree::StackVec;
+
+use crate::augment::{AugVal, UpdateAug};
+use crate::interval::{IntervalKey, MaxEnd, SgIntervalMap};
+use crate::tree::{ArenaIdx, Iter as TreeIter, Natural, SmallNode};
+
+// General Iterators ---------------------------------------------------------------------------------------------------
+
+/// An iterator over the entries of a [`SgIntervalMap`][crate::SgIntervalMap].
+///
+/// This `struct` is created by the [`iter`][crate::SgIntervalMap::iter] method on [`SgIntervalMap`][crate::SgIntervalMap].
+/// See its documentation for more.
+pub struct Iter<'a, K: Ord + Clone, V, const N: usize, U: ArenaIdx = u16> {
+    ref_iter: TreeIter<'a, IntervalKey<K>, AugVal<V, MaxEnd<K>>, N, U, Natural, UpdateAug>,
+}
+
+impl<'a, K: Ord + Clone, V, const N: usize, U: ArenaIdx> Iter<'a, K, V, N, U> {
+    /// Construct reference iterator.
+    pub(crate) fn new(imap: &'a SgIntervalMap<K, V, N, U>) -> Self {
+        Iter {
+            ref_iter: TreeIter::new(&imap.bst),
+        }
+    }
+}
+
+impl<'a, K: Ord + Clone, V, const N: usize, U: ArenaIdx> Iterator for Iter<'a, K, V, N, U> {
+    type Item = (&'a Range<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next().map(|(ik, av)| (&ik.0, &av.val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ref_iter.size_hint()
+    }
+}
+
+impl<'a, K: Ord + Clone, V, const N: usize, U: ArenaIdx> DoubleEndedIterator
+    for Iter<'a, K, V, N, U>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next_back().map(|(ik, av)| (&ik.0, &av.val))
+    }
+}
+
+impl<'a, K: Ord + Clone, V, const N: usize, U: ArenaIdx> ExactSizeIterator
+    for Iter<'a, K, V, N, U>
+{
+    fn len(&self) -> usize {
+        self.ref_iter.len()
+    }
+}
+
+impl<'a, K: Ord + Clone, V, const N: usize, U: ArenaIdx> FusedIterator for Iter<'a, K, V, N, U> {}
+
+// Query Iterators -----------------------------------------------------------------------------------------------------
+
+/// An iterator over the entries of a [`SgIntervalMap`][crate::SgIntervalMap] whose ranges intersect a query.
+///
+/// This `struct` is created by the [`overlaps`][crate::SgIntervalMap::overlaps] and [`stab`][crate::SgIntervalMap::stab]
+/// methods on [`SgIntervalMap`][crate::SgIntervalMap]. See their documentation for more.
+pub struct Overlaps<'a, K: Ord + Clone, V, const N: usize, U: ArenaIdx = u16> {
+    imap: &'a SgIntervalMap<K, V, N, U>,
+    idx_stack: StackVec<[usize; N]>,
+    query_start: K,
+    query_end: K,
+    end_inclusive: bool,
+}
+
+impl<'a, K: Ord + Clone, V, const N: usize, U: ArenaIdx> Overlaps<'a, K, V, N, U> {
+    // Matches ranges that end after `query_start` and start before `query_end` (or at it, if `end_inclusive`).
+    // In-order traversal, pruned by each subtree's maximum end (left side) and by start order (right side).
+    pub(crate) fn new(
+        imap: &'a SgIntervalMap<K, V, N, U>,
+        query_start: K,
+        query_end: K,
+        end_inclusive: bool,
+    ) -> Self {
+        let mut overlaps = Overlaps {
+            imap,
+            idx_stack: StackVec::<[usize; N]>::new(),
+            query_start,
+            query_end,
+            end_inclusive,
+        };
+
+        let is_empty_query = !end_inclusive && (overlaps.query_start >= overlaps.query_end);
+        if let (Some(root_idx), false) = (imap.bst.opt_root_idx, is_empty_query) {
+            overlaps.push_left_spine(root_idx);
+        }
+
+        overlaps
+    }
+
+    // Push `idx` and its left descendants, stopping at the first subtree that ends before the query starts
+    fn push_left_spine(&mut self, idx: usize) {
+        let mut opt_idx = Some(idx);
+        while let Some(curr_idx) = opt_idx {
+            let node = &self.imap.bst.arena[curr_idx];
+            if node.val().aug.0 <= self.query_start {
+                break;
+            }
+
+            self.idx_stack.push(curr_idx);
+            opt_idx = node.left_idx();
+        }
+    }
+
+    fn starts_before_query_end(&self, start: &K) -> bool {
+        match self.end_inclusive {
+            true => *start <= self.query_end,
+            false => *start < self.query_end,
+        }
+    }
+}
+
+impl<'a, K: Ord + Clone, V, const N: usize, U: ArenaIdx> Iterator for Overlaps<'a, K, V, N, U> {
+    type Item = (&'a Range<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(pop_idx) = self.idx_stack.pop() {
+            let node = &self.imap.bst.arena[pop_idx];
+            let range = &node.key().0;
+
+            // Every later range starts here or after, none can match
+            if !self.starts_before_query_end(&range.start) {
+                self.idx_stack.clear();
+                return None;
+            }
+
+            if let Some(gt_idx) = node.right_idx() {
+                self.push_left_spine(gt_idx);
+            }
+
+            if (range.end > self.query_start) && (range.start < range.end) {
+                return Some((range, &node.val().val));
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.imap.len()))
+    }
+}
+
+impl<'a, K: Ord + Clone, V, const N: usize, U: ArenaIdx> FusedIterator
+    for Overlaps<'a, K, V, N, U>
+{
+}
@@ -2,7 +2,7 @@
 Ordered set and map data structures via an arena-based [scapegoat tree](https://people.csail.mit.edu/rivest/pubs/GR93.pdf) (memory-efficient, self-balancing binary search tree).
 
 * Embedded-friendly: `#![no_std]` by default.
-* Safe: `#![forbid(unsafe_code)]`, including all dependencies.
+* Safe: `#![forbid(unsafe_code)]`, including all default dependencies.
 * Validated via [differential fuzzing](https://tiemoko.com/blog/diff-fuzz/), against the standard library's `BTreeSet` and `BTreeMap`.
 
 ### About
@@ -31,7 +31,8 @@ Strives for three properties:
 
 Other features:
 
-* **Generic:** map keys and set elements can be any type that implements traits [`Ord`](https://doc.rust-lang.org/std/cmp/trait.Ord.html) and [`Default`](https://doc.rust-lang.org/std/default/trait.Default.html).
+* **Generic:** map keys and set elements can be any type that implements trait [`Ord`](https://doc.rust-lang.org/std/cmp/trait.Ord.html). Values can be any type.
+* **Custom ordering:** an optional comparator type parameter (e.g. `Reversed`, or a `fn(&K, &K) -> Ordering`) replaces the key's `Ord` order, no newtype wrappers needed.
 * **Arbitrarily mutable:** elements can be inserted and removed, map values can be mutated. Safely.
 
 ### Usage
@@ -115,18 +116,24 @@ let big_map: SgMap<u64, u64, 2_048> = SgMap::new(); // 2,048 item capacity
 #[cfg(target_pointer_width = "64")]
 #[cfg(not(feature = "low_mem_insert"))]
 #[cfg(not(feature = "fast_rebalance"))]
+#[cfg(not(feature = "rebal_stats"))]
+#[cfg(not(feature = "telemetry"))]
 {
-    assert_eq!(size_of_val(&small_map), 2_680); // 2.7 KB
-    assert_eq!(size_of_val(&big_map), 53_328);  // 53.3 KB
+    assert_eq!(size_of_val(&small_map), 2_688); // 2.7 KB
+    assert_eq!(size_of_val(&big_map), 53_336);  // 53.3 KB
 }
 ```
 
-The maximum supported capacity is `65_535` (e.g. `0xffff` or [`u16::MAX`](https://doc.rust-lang.org/std/primitive.u16.html#associatedconstant.MAX)) items.
+With the default index width, the maximum supported capacity is `65_534` (e.g. [`u16::MAX`](https://doc.rust-lang.org/std/primitive.u16.html#associatedconstant.MAX) minus one, the last value marks absent child links) items.
+Internal child links are `u16` by default; an optional fourth generic parameter selects a different width.
+Use `u8` to shrink every node of a tiny tree (capacity up to `254`), or `u32` to go past `65_534` (e.g. `SgMap<u64, u64, 100, u8>` or `SgMap<u64, u64, 100_000, u32>`).
+The [`arena_idx!`](crate::arena_idx) macro picks the narrowest width for a capacity constant: `SgMap<u64, u64, CAP, arena_idx!(CAP)>`.
 Please note:
 
 * For embedded platforms, stack size limit (bound by available RAM) is indicated in the manufacturer's datasheet.
 * On Linux, the default stack limit is 8MB for the main thread and 2MB for spawned threads (unless [overwritten](https://doc.rust-lang.org/std/thread/struct.Builder.html#method.stack_size)).
 * Running `cargo test` on any OS, 2MB is the limit unless the environment variable [`RUST_MIN_STACK`](https://doc.rust-lang.org/std/thread/index.html#stack-size) is set.
+* Rebuilds borrow scratch space on top of the collection itself: one link-width index per item (e.g. `2 * N` bytes for `u16`), plus a worklist of fixed size regardless of `N` (about 1 KB for `u16`). The `low_mem_rebuild` feature drops the index buffer, see [CONFIG.md](https://github.com/tnballo/scapegoat/blob/master/CONFIG.md).
 
 
 > **WARNING:**
@@ -139,16 +146,26 @@ For advanced configuration options, please see [the documentation here](https://
 
 ### Trusted Dependencies
 
-This library has three dependencies, each of which have no dependencies of their own (e.g. exactly three total dependencies).
+With default features, this library has two dependencies, each of which have no dependencies of their own (e.g. exactly two total dependencies).
 
 * [`tinyvec`](https://crates.io/crates/tinyvec) - `#![no_std]`, `#![forbid(unsafe_code)]` alternative to `Vec`.
-* [`micromath`](https://crates.io/crates/micromath) - `#![no_std]`, `#![forbid(unsafe_code)]` floating point approximations.
 * [`smallnum`](https://crates.io/crates/smallnum) - `#![no_std]`, `#![forbid(unsafe_code)]` integer abstraction.
 
-Because this library and all dependencies are `#![forbid(unsafe_code)]`, no 3rd-party `unsafe` code is introduced into your project.
+Because this library and both default dependencies are `#![forbid(unsafe_code)]`, no 3rd-party `unsafe` code is introduced into your project.
 This maximizes **static guarantees** for memory safety (enforced via Rust's type system).
 Robustness and correctness properties beyond memory safety are **validated dynamically**, via differential fuzzing.
 
+Optional features pull in additional dependencies, none of which are `#![forbid(unsafe_code)]`.
+Each is only compiled if its feature is enabled, see [the configuration documentation](https://github.com/tnballo/scapegoat/blob/master/CONFIG.md) for details.
+
+* [`arbitrary`](https://crates.io/crates/arbitrary) - `arbitrary` feature, uses `unsafe` internally, requires `std`.
+* [`rayon`](https://crates.io/crates/rayon) - `rayon` feature, uses `unsafe` internally, requires `std`.
+* [`serde`](https://crates.io/crates/serde) - `serde` feature, uses `unsafe` internally (UTF-8 conversion only).
+* [`rkyv`](https://crates.io/crates/rkyv) - `rkyv` feature, uses `unsafe` extensively (zero-copy archive access), `no_std`.
+* [`bytemuck`](https://crates.io/crates/bytemuck) - `bytemuck` feature, uses `unsafe` internally (`Pod` casts), `no_std`.
+* [`metrics`](https://crates.io/crates/metrics) - `metrics` feature, uses `unsafe` internally, requires `std`.
+* [`rand`](https://crates.io/crates/rand) - `rand` feature, uses `unsafe` internally, `no_std`.
+
 ### Additional Considerations
 
 **General Goals**
@@ -166,7 +183,7 @@ It offers:
 
 * **Dynamic Validation:** [Coverage-guided, structure-aware, differential fuzzing](https://github.com/tnballo/scapegoat/tree/master/fuzz) is used to demonstrate that this implementation is logically equivalent and equally reliable.
 
-* **Tunable Performance:** A [single floating point value](https://github.com/tnballo/scapegoat/blob/master/CONFIG.md#tuning-the-the-trees-a-factor) optimizes relative performance of `insert`, `get`, and `remove` operation classes. And it can be changed at runtime.
+* **Tunable Performance:** A [single rational value](https://github.com/tnballo/scapegoat/blob/master/CONFIG.md#tuning-the-the-trees-a-factor) optimizes relative performance of `insert`, `get`, and `remove` operation classes. And it can be changed at runtime.
 
 **Algorithmic Complexity**
 
@@ -203,12 +220,34 @@ Licensed under the [MIT license](https://github.com/tnballo/scapegoat/blob/maste
     html_logo_url = "https://raw.githubusercontent.com/tnballo/scapegoat/master/img/scapegoat.svg"
 )]
 
+// Host-side tooling only
+#[cfg(feature = "codegen")]
+extern crate std;
+
+// Owned `Vec` conversions only, containers never allocate
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 // Only expose arena internals for fuzzing harness
 #[cfg(fuzzing)]
-pub use crate::tree::{Arena, Node, NodeGetHelper, NodeRebuildHelper};
+pub use crate::tree::{Arena, Node, NodeGetHelper, NodeRebuildHelper, SlotStorage, StackVec};
+
+mod macros;
 
 mod tree;
-pub use crate::tree::SgError;
+pub use crate::tree::{
+    ArenaIdx, CapacityError, Compare, DotOptions, DupPolicy, EvictPolicy, InvariantReport,
+    LayoutPolicy, MemoryUsage, Natural, NodeHandle, Reversed, SgError, TreeStats,
+};
+
+#[cfg(feature = "rebal_stats")]
+pub use crate::tree::RebalStats;
+
+#[cfg(feature = "telemetry")]
+pub use crate::tree::Telemetry;
+
+#[doc(hidden)]
+pub use crate::tree::FitArenaIdx;
 
 mod map;
 pub use crate::map::SgMap;
@@ -221,3 +260,83 @@ pub use crate::set::SgSet;
 
 /// [`SgSet`][crate::set::SgSet]'s iterator return types.
 pub mod set_types;
+
+/// Operation-sequence interpreter for fuzz targets and regression replays.
+#[cfg(feature = "arbitrary")]
+pub mod fuzz_ops;
+
+// Trait impls only, no public items
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
+
+// Trait impls only, no public items
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+// Trait impls only, no public items
+#[cfg(feature = "rkyv")]
+mod rkyv_impls;
+
+mod multimap;
+pub use crate::multimap::SgMultiMap;
+
+/// [`SgMultiMap`][crate::SgMultiMap]'s iterator return types.
+pub mod multimap_types;
+
+mod priority_queue;
+pub use crate::priority_queue::SgPriorityQueue;
+
+/// [`SgPriorityQueue`][crate::SgPriorityQueue]'s iterator return types.
+pub mod priority_queue_types;
+
+mod fork;
+pub use crate::fork::SgMapFork;
+
+/// [`SgMapFork`][crate::SgMapFork]'s iterator return types.
+pub mod fork_types;
+
+mod augment;
+pub use crate::augment::{Augment, SgAugmentedMap};
+
+/// [`SgAugmentedMap`][crate::SgAugmentedMap]'s iterator return types.
+pub mod augment_types;
+
+mod observe;
+pub use crate::observe::{Change, Observer, SgObservedMap};
+
+mod merkle;
+pub use crate::merkle::{MerkleHasher, MerkleProof, SgMerkleMap};
+
+/// [`SgMerkleMap`][crate::SgMerkleMap]'s iterator return types.
+pub mod merkle_types;
+
+mod insertion;
+pub use crate::insertion::SgInsertionMap;
+
+/// [`SgInsertionMap`][crate::SgInsertionMap]'s iterator return types.
+pub mod insertion_types;
+
+mod interval;
+pub use crate::interval::SgIntervalMap;
+
+/// [`SgIntervalMap`][crate::SgIntervalMap]'s iterator return types.
+pub mod interval_types;
+
+mod ttl;
+pub use crate::ttl::SgTtlMap;
+
+/// [`SgTtlMap`][crate::SgTtlMap]'s iterator return types.
+pub mod ttl_types;
+
+mod indexed;
+pub use crate::indexed::SgIndexedMap;
+
+/// [`SgIndexedMap`][crate::SgIndexedMap]'s iterator return types.
+pub mod indexed_types;
+
+mod frozen;
+pub use crate::frozen::FrozenMap;
+
+/// Host-side source generation for [`FrozenMap`][crate::FrozenMap]s (requires `std`).
+#[cfg(feature = "codegen")]
+pub mod codegen;
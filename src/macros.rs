@@ -0,0 +1,58 @@
+/// Fail the build if a set/map's node size exceeds a byte budget.
+///
+/// Node layout depends on key/value types and enabled features (e.g. `fast_rebalance`).
+/// This catches layout regressions (say, a dependency update growing a key type) at compile time,
+/// before they silently blow a RAM budget.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::{assert_node_size, SgMap, SgSet};
+///
+/// assert_node_size!(SgMap<u32, u32, 1024>, 20);
+/// assert_node_size!(SgSet<u8, 8>, 12);
+/// ```
+///
+/// Exceeding the budget is a compile error:
+///
+/// ```compile_fail
+/// use scapegoat::{assert_node_size, SgMap};
+///
+/// assert_node_size!(SgMap<u64, [u8; 64], 8>, 16);
+/// ```
+#[macro_export]
+macro_rules! assert_node_size {
+    ($t:ty, $budget:expr) => {
+        const _: () = assert!(<$t>::NODE_SIZE <= $budget, "Node size budget exceeded!");
+    };
+}
+
+/// Narrowest [`ArenaIdx`][crate::ArenaIdx] for a given capacity, resolved at compile time.
+///
+/// Pass the same constant used for the capacity `N`: `u8` for up to `254` items, `u16` for up to `65_534`, else `u32`.
+/// Stable Rust can't default a type parameter based on a const generic, so this is spelled out at the call site.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::{arena_idx, SgMap};
+/// use core::mem::size_of_val;
+///
+/// const SMALL_CAP: usize = 100;
+///
+/// let mut small_map = SgMap::<u16, u16, SMALL_CAP, arena_idx!(SMALL_CAP)>::new();
+/// let default_map = SgMap::<u16, u16, SMALL_CAP>::new();
+/// assert!(size_of_val(&small_map) < size_of_val(&default_map));
+///
+/// small_map.insert(1, 2);
+/// assert_eq!(small_map.get(&1), Some(&2));
+/// ```
+#[macro_export]
+macro_rules! arena_idx {
+    ($cap:expr $(,)?) => {
+        <() as $crate::FitArenaIdx<
+            { ($cap as u128) < (u8::MAX as u128) },
+            { ($cap as u128) < (u16::MAX as u128) },
+        >>::Idx
+    };
+}
@@ -1,37 +1,81 @@
 use core::borrow::Borrow;
+use core::convert::TryFrom;
 use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
 use core::iter::FromIterator;
-use core::ops::{Index, RangeBounds};
+use core::mem::MaybeUninit;
+use core::ops::{Bound, Index, RangeBounds};
 
+use crate::fork::SgMapFork;
 use crate::map_types::{
-    Entry, IntoIter, IntoKeys, IntoValues, Iter, IterMut, Keys, OccupiedEntry, OccupiedError,
-    Range, RangeMut, VacantEntry, Values, ValuesMut,
+    BulkLoad, Cursor, CursorMut, Drain, Entry, EntryRef, ExtractIf, FullJoin, IntoIter, IntoKeys,
+    IntoValues, Iter, IterMut, Join, Keys, LeftJoin, Levels, OccupiedEntry, OccupiedError, Range,
+    RangeMut, Transaction, VacantEntry, VacantEntryRef, Values, ValuesMut,
 };
-use crate::tree::{SgError, SgTree};
+#[cfg(feature = "rebal_stats")]
+use crate::tree::RebalStats;
+#[cfg(feature = "telemetry")]
+use crate::tree::Telemetry;
+use crate::tree::{
+    ArenaIdx, CapacityError, Compare, Cursor as TreeCursor, CursorMut as TreeCursorMut, DotOptions,
+    DupPolicy, EvictPolicy, InvariantReport, LayoutPolicy, MemoryUsage, Natural, NodeHandle,
+    SgError, SgTree, TreeStats,
+};
+
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
 
 /// Safe, fallible, embedded-friendly ordered map.
 ///
 /// ### Fallible APIs
 ///
 /// * [`try_insert`][crate::map::SgMap::try_insert]
+/// * [`insert_within_capacity`][crate::map::SgMap::insert_within_capacity]
 /// * [`try_append`][crate::map::SgMap::try_append]
 /// * [`try_extend`][crate::map::SgMap::try_extend]
 /// * [`try_from_iter`][crate::map::SgMap::try_from_iter]
+/// * [`try_with_rebal_ratio`][crate::map::SgMap::try_with_rebal_ratio]
+/// * [`try_split_off`][crate::map::SgMap::try_split_off]
+/// * [`try_reserve`][crate::map::SgMap::try_reserve]
+/// * [`try_insert_at_end`][crate::map::SgMap::try_insert_at_end]
 ///
-/// [`TryFrom`](https://doc.rust-lang.org/stable/std/convert/trait.TryFrom.html) isn't implemented because it would collide with the blanket implementation.
+/// [`TryFrom`](https://doc.rust-lang.org/stable/std/convert/trait.TryFrom.html) is implemented for slices (and, with the `alloc` feature, `Vec`s) of any length.
+/// It isn't implemented for arrays because it would collide with the blanket implementation.
 /// See [this open GitHub issue](https://github.com/rust-lang/rust/issues/50133#issuecomment-64690839) from 2018,
 /// this is a known Rust limitation that should be fixed via specialization in the future.
 ///
+/// ### Index Width
+///
+/// Internal child links are `u16` by default. The optional last generic parameter, any [`ArenaIdx`][crate::ArenaIdx],
+/// picks a different width: e.g. `SgMap<u8, u8, 200, u8>` for smaller nodes, or `u32` for capacities beyond `65_534`.
+///
 /// ### Attribution Note
 ///
 /// The majority of API examples and descriptions are adapted or directly copied from the standard library's [`BTreeMap`](https://doc.rust-lang.org/std/collections/struct.BTreeMap.html).
 /// The goal is to offer embedded developers familiar, ergonomic APIs on resource constrained systems that otherwise don't get the luxury of dynamic collections.
 #[derive(Default, Clone, Hash, PartialEq, Eq, Ord, PartialOrd)]
-pub struct SgMap<K: Ord + Default, V: Default, const N: usize> {
-    pub(crate) bst: SgTree<K, V, N>,
+pub struct SgMap<K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    pub(crate) bst: SgTree<K, V, N, U, C>,
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> SgMap<K, V, N, U, C> {
+    /// Size of an individual internal node (one key-value pair plus metadata), in bytes.
+    /// Total storage is roughly `N * NODE_SIZE`.
+    /// Can be checked against a budget at compile time, via [`assert_node_size`](crate::assert_node_size).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// assert!(SgMap::<u32, u32, 10>::NODE_SIZE >= 2 * core::mem::size_of::<u32>());
+    /// ```
+    pub const NODE_SIZE: usize = SgTree::<K, V, N, U, C>::NODE_SIZE;
+
     /// Makes a new, empty `SgMap`.
     ///
     /// # Examples
@@ -43,10 +87,131 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     ///
     /// map.insert(1, "a");
     /// ```
-    pub fn new() -> Self {
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
         SgMap { bst: SgTree::new() }
     }
 
+    /// Makes a new, empty `SgMap` whose keys are ordered by `cmp` instead of their [`Ord`] implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::cmp::Ordering;
+    /// use scapegoat::SgMap;
+    ///
+    /// fn case_insensitive(a: &&str, b: &&str) -> Ordering {
+    ///     let lower = |s: &str| s.bytes().map(|c| c.to_ascii_lowercase()).collect::<Vec<_>>();
+    ///     lower(a).cmp(&lower(b))
+    /// }
+    ///
+    /// let mut map: SgMap<&str, u32, 10, u16, fn(&&str, &&str) -> Ordering> =
+    ///     SgMap::with_comparator(case_insensitive);
+    ///
+    /// map.insert("b", 2);
+    /// map.insert("A", 1);
+    /// map.insert("B", 3);
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map.get(&"a"), Some(&1));
+    /// assert_eq!(map.into_iter().collect::<Vec<_>>(), [("A", 1), ("B", 3)]);
+    /// ```
+    pub fn with_comparator(cmp: C) -> Self {
+        SgMap {
+            bst: SgTree::with_comparator(cmp),
+        }
+    }
+
+    /// Makes a new, empty `SgMap` in caller-provided memory, returning a reference to it.
+    /// The map's storage is inline, so it lives wherever `slot` does: e.g. a static buffer,
+    /// a DMA-capable region, or an external RAM section placed by a linker script.
+    ///
+    /// This controls where the map ends up, not how much stack it takes to get there.
+    /// Without `unsafe`, `slot` can't be initialized field by field: the whole map (all `N` slots) is built
+    /// as a temporary, then moved into `slot`. Peak stack use is the same as for [`new`][SgMap::new],
+    /// unless the optimizer happens to elide the copy (not guaranteed, least of all in debug builds).
+    ///
+    /// Pair with a safe `&'static mut MaybeUninit<_>` source (e.g. `cortex_m::singleton!` or `static_cell::StaticCell`)
+    /// for a `'static` map. Any previous contents of `slot` are overwritten without being dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::mem::MaybeUninit;
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut slot = MaybeUninit::uninit();
+    /// let map: &mut SgMap<u32, &str, 10> = SgMap::new_in(&mut slot);
+    ///
+    /// map.insert(1, "a");
+    /// assert_eq!(map.get(&1), Some(&"a"));
+    /// ```
+    pub fn new_in(slot: &mut MaybeUninit<Self>) -> &mut Self
+    where
+        C: Default,
+    {
+        slot.write(Self::new())
+    }
+
+    /// Makes a new, empty `SgMap` with the rebalance parameter, alpha, set to `alpha_num / alpha_denom`.
+    /// Saves a separate, fallible [`set_rebal_ratio`][SgMap::set_rebal_ratio] call when alpha is a constant.
+    /// See that method for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    /// For a non-panicking alternative, see [`try_with_rebal_ratio`][SgMap::try_with_rebal_ratio].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// // Read-heavy workload, favor a lower height
+    /// let map: SgMap<u32, &str, 10> = SgMap::with_rebal_ratio(4, 7);
+    /// assert_eq!(map.rebal_ratio(), (4, 7));
+    /// ```
+    #[doc(alias = "alpha")]
+    #[doc(alias = "with_alpha")]
+    pub fn with_rebal_ratio(alpha_num: u32, alpha_denom: u32) -> Self
+    where
+        C: Default,
+    {
+        match Self::try_with_rebal_ratio(alpha_num, alpha_denom) {
+            Ok(map) => map,
+            Err(_) => panic!("Rebalance factor out of range, requires 0.5 <= alpha < 1.0!"),
+        }
+    }
+
+    /// Makes a new, empty `SgMap` with the rebalance parameter, alpha, set to `alpha_num / alpha_denom`.
+    /// See [`set_rebal_ratio`][SgMap::set_rebal_ratio] for more details.
+    ///
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// assert!(SgMap::<u32, &str, 10>::try_with_rebal_ratio(3, 4).is_ok());
+    /// assert_eq!(
+    ///     SgMap::<u32, &str, 10>::try_with_rebal_ratio(1, 3).unwrap_err(),
+    ///     SgError::RebalanceFactorOutOfRange
+    /// );
+    /// ```
+    #[doc(alias = "alpha")]
+    #[doc(alias = "with_alpha")]
+    pub fn try_with_rebal_ratio(alpha_num: u32, alpha_denom: u32) -> Result<Self, SgError>
+    where
+        C: Default,
+    {
+        Ok(SgMap {
+            bst: SgTree::try_with_rebal_ratio(alpha_num, alpha_denom)?,
+        })
+    }
+
     /// The [original scapegoat tree paper's](https://people.csail.mit.edu/rivest/pubs/GR93.pdf) alpha, `a`, can be chosen in the range `0.5 <= a < 1.0`.
     /// `a` tunes how "aggressively" the data structure self-balances.
     /// It controls the trade-off between total rebuild time and maximum height guarantees.
@@ -57,6 +222,8 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// * As `a` approaches `1.0`, the tree will rebalance less often. This means quicker insertions, but slower lookups and deletions.
     ///     * If `a` reached `1.0`, it'd mean a tree that never rebalances.
     ///
+    /// `a` is an integer ratio, `alpha_num / alpha_denom`, so balance decisions never need floating point math (e.g. on FPU-less targets).
+    ///
     /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
     ///
     /// # Examples
@@ -67,16 +234,54 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// let mut map: SgMap<isize, isize, 10> = SgMap::new();
     ///
     /// // Set 2/3, e.g. `a = 0.666...` (it's default value).
-    /// assert!(map.set_rebal_param(2.0, 3.0).is_ok());
+    /// assert!(map.set_rebal_ratio(2, 3).is_ok());
     /// ```
     #[doc(alias = "rebalance")]
     #[doc(alias = "alpha")]
-    pub fn set_rebal_param(&mut self, alpha_num: f32, alpha_denom: f32) -> Result<(), SgError> {
-        self.bst.set_rebal_param(alpha_num, alpha_denom)
+    pub fn set_rebal_ratio(&mut self, alpha_num: u32, alpha_denom: u32) -> Result<(), SgError> {
+        self.bst.set_rebal_ratio(alpha_num, alpha_denom)
+    }
+
+    /// Set the rebalance parameter, alpha, and immediately rebuild the whole tree if its current height exceeds the new bound.
+    /// See [the corresponding setter method][SgMap::set_rebal_ratio] for more details.
+    ///
+    /// Plain [`set_rebal_ratio`][SgMap::set_rebal_ratio] leaves the tree in its old shape until enough inserts trigger rebalances.
+    /// This variant is useful when switching phases at runtime, e.g. from write-heavy (lax alpha) to read-heavy (strict alpha).
+    /// The rebuild is `O(n)`.
+    ///
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<usize, usize, 100> = SgMap::new();
+    ///
+    /// // Write-heavy phase, rebalance rarely
+    /// assert!(map.set_rebal_ratio(9, 10).is_ok());
+    /// for k in 0..100 {
+    ///     map.insert(k, k);
+    /// }
+    ///
+    /// // Read-heavy phase, tighten to perfect balance right away
+    /// assert!(map.set_rebal_ratio_and_rebalance(1, 2).is_ok());
+    /// assert_eq!(map.rebal_ratio(), (1, 2));
+    /// assert!(map.check_invariants().is_valid());
+    /// ```
+    #[doc(alias = "rebalance")]
+    #[doc(alias = "alpha")]
+    pub fn set_rebal_ratio_and_rebalance(
+        &mut self,
+        alpha_num: u32,
+        alpha_denom: u32,
+    ) -> Result<(), SgError> {
+        self.bst
+            .set_rebal_ratio_and_rebalance(alpha_num, alpha_denom)
     }
 
     /// Get the current rebalance parameter, alpha, as a tuple of `(alpha_numerator, alpha_denominator)`.
-    /// See [the corresponding setter method][SgMap::set_rebal_param] for more details.
+    /// See [the corresponding setter method][SgMap::set_rebal_ratio] for more details.
     ///
     /// # Examples
     ///
@@ -86,228 +291,239 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// let mut map: SgMap<isize, isize, 10> = SgMap::new();
     ///
     /// // Set 2/3, e.g. `a = 0.666...` (it's default value).
-    /// assert!(map.set_rebal_param(2.0, 3.0).is_ok());
+    /// assert!(map.set_rebal_ratio(2, 3).is_ok());
     ///
     /// // Get the currently set value
-    /// assert_eq!(map.rebal_param(), (2.0, 3.0));
+    /// assert_eq!(map.rebal_ratio(), (2, 3));
     /// ```
     #[doc(alias = "rebalance")]
     #[doc(alias = "alpha")]
+    pub fn rebal_ratio(&self) -> (u32, u32) {
+        self.bst.rebal_ratio()
+    }
+
+    /// Set the rebalance parameter from a floating point `alpha_num / alpha_denom`.
+    /// Whole-number arguments (e.g. `2.0, 3.0`) convert exactly, otherwise `a` is rounded down to a multiple of `1 / 2^16`.
+    /// See [`set_rebal_ratio`][SgMap::set_rebal_ratio], which replaces it, for more details.
+    ///
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    #[deprecated(note = "use `set_rebal_ratio`, which takes an integer ratio")]
+    #[allow(deprecated)]
+    pub fn set_rebal_param(&mut self, alpha_num: f32, alpha_denom: f32) -> Result<(), SgError> {
+        self.bst.set_rebal_param(alpha_num, alpha_denom)
+    }
+
+    /// Get the current rebalance parameter, alpha, as a floating point tuple of `(alpha_numerator, alpha_denominator)`.
+    /// See [`rebal_ratio`][SgMap::rebal_ratio], which replaces it.
+    #[deprecated(note = "use `rebal_ratio`, which returns an integer ratio")]
+    #[allow(deprecated)]
     pub fn rebal_param(&self) -> (f32, f32) {
         self.bst.rebal_param()
     }
 
-    /// Total capacity, e.g. maximum number of map pairs.
+    /// Get rebalance cost statistics: number of rebuilds, total nodes moved, and largest single rebuild.
+    /// Accumulated since creation or the last [`reset_rebal_stats`][crate::map::SgMap::reset_rebal_stats].
+    ///
+    /// Useful for performance engineering, e.g. catching a single `O(n)` rebuild that could blow a deadline.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map = SgMap::<usize, &str, 10>::new();
+    /// let mut map = SgMap::<_, _, 100>::new();
+    /// assert_eq!(map.rebal_stats().rebuild_cnt, 0);
     ///
-    /// assert!(map.capacity() == 10);
+    /// for i in 0..100 {
+    ///     map.insert(i, i);
+    /// }
+    ///
+    /// let stats = map.rebal_stats();
+    /// assert!(stats.rebuild_cnt > 0);
+    /// assert!(stats.nodes_moved >= stats.max_rebuild_size);
+    /// assert!(stats.max_rebuild_size <= map.len());
     /// ```
-    pub fn capacity(&self) -> usize {
-        self.bst.capacity()
+    #[cfg(feature = "rebal_stats")]
+    #[doc(alias = "rebalance")]
+    pub fn rebal_stats(&self) -> RebalStats {
+        self.bst.rebal_stats()
     }
 
-    /// Gets an iterator over the keys of the map, in sorted order.
+    /// Reset rebalance cost statistics, see [`rebal_stats`][crate::map::SgMap::rebal_stats].
     ///
     /// # Examples
     ///
     /// ```
-    /// use scapegoat::SgMap;
+    /// use scapegoat::{RebalStats, SgMap};
     ///
-    /// let mut a = SgMap::<_, _, 10>::new();
-    /// a.insert(2, "b");
-    /// a.insert(1, "a");
+    /// let mut map = SgMap::<_, _, 100>::new();
+    /// for i in 0..100 {
+    ///     map.insert(i, i);
+    /// }
     ///
-    /// let keys: Vec<_> = a.keys().cloned().collect();
-    /// assert_eq!(keys, [1, 2]);
+    /// map.reset_rebal_stats();
+    /// assert_eq!(map.rebal_stats(), RebalStats::default());
     /// ```
-    pub fn keys(&self) -> Keys<'_, K, V, N> {
-        Keys { inner: self.iter() }
+    #[cfg(feature = "rebal_stats")]
+    pub fn reset_rebal_stats(&mut self) {
+        self.bst.reset_rebal_stats();
     }
 
-    /// Creates a consuming iterator visiting all the keys, in sorted order.
-    /// The map cannot be used after calling this.
-    /// The iterator element type is `K`.
+    /// Get a summary of shape, rebalancing history, and arena occupancy, see [`TreeStats`][crate::TreeStats].
+    /// Runs in `O(n)` time (to measure height).
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut a = SgMap::<_, _, 10>::new();
-    /// a.insert(2, "b");
-    /// a.insert(1, "a");
+    /// let mut map = SgMap::<_, _, 100>::new();
+    /// for i in 0..100 {
+    ///     map.insert(i, i);
+    /// }
+    /// map.remove(&0);
     ///
-    /// let keys: Vec<i32> = a.into_keys().collect();
-    /// assert_eq!(keys, [1, 2]);
+    /// let stats = map.stats();
+    /// assert_eq!(stats.len, 99);
+    /// assert_eq!(stats.arena_slots, 100);
+    /// assert!(stats.rebal_cnt > 0);
+    /// assert!(stats.height >= 7 && stats.height < 99);
     /// ```
-    pub fn into_keys(self) -> IntoKeys<K, V, N> {
-        IntoKeys {
-            inner: self.into_iter(),
-        }
+    #[doc(alias = "metrics")]
+    pub fn stats(&self) -> TreeStats {
+        self.bst.stats()
     }
 
-    /// Gets an iterator over the values of the map, in order by key.
+    /// Get resource usage high-water marks: maximum length ever reached and largest transient scratch buffer usage.
+    /// Accumulated since creation or the last [`reset_telemetry`][crate::map::SgMap::reset_telemetry].
+    ///
+    /// Useful for sizing capacity, `N`, from field data.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut a = SgMap::<_, _, 10>::new();
-    /// a.insert(1, "hello");
-    /// a.insert(2, "goodbye");
+    /// let mut map = SgMap::<_, _, 100>::new();
+    /// for i in 0..50 {
+    ///     map.insert(i, i);
+    /// }
+    /// map.clear();
     ///
-    /// let values: Vec<&str> = a.values().cloned().collect();
-    /// assert_eq!(values, ["hello", "goodbye"]);
+    /// let telemetry = map.telemetry();
+    /// assert_eq!(telemetry.max_len, 50);
+    /// assert!(telemetry.max_scratch_len <= map.capacity());
     /// ```
-    pub fn values(&self) -> Values<'_, K, V, N> {
-        Values { inner: self.iter() }
+    #[cfg(feature = "telemetry")]
+    pub fn telemetry(&self) -> Telemetry {
+        self.bst.telemetry()
     }
 
-    /// Creates a consuming iterator visiting all the values, in order by key.
-    /// The map cannot be used after calling this.
-    /// The iterator element type is `V`.
+    /// Reset resource usage high-water marks, see [`telemetry`][crate::map::SgMap::telemetry].
     ///
     /// # Examples
     ///
     /// ```
-    /// use scapegoat::SgMap;
+    /// use scapegoat::{SgMap, Telemetry};
     ///
-    /// let mut a = SgMap::<_, _, 10>::new();
-    /// a.insert(1, "hello");
-    /// a.insert(2, "goodbye");
+    /// let mut map = SgMap::<_, _, 100>::new();
+    /// map.insert(1, 1);
     ///
-    /// let values: Vec<&str> = a.into_values().collect();
-    /// assert_eq!(values, ["hello", "goodbye"]);
+    /// map.reset_telemetry();
+    /// assert_eq!(map.telemetry(), Telemetry::default());
     /// ```
-    pub fn into_values(self) -> IntoValues<K, V, N> {
-        IntoValues {
-            inner: self.into_iter(),
-        }
+    #[cfg(feature = "telemetry")]
+    pub fn reset_telemetry(&mut self) {
+        self.bst.reset_telemetry();
     }
 
-    /// Gets a mutable iterator over the values of the map, in order by key.
+    /// Get a breakdown of storage usage, in bytes: live elements, freed slots, spare capacity, and overhead.
+    /// Storage is fixed-size and never spills to the heap, so the total is constant for a given type.
+    ///
+    /// Useful for budgeting RAM on constrained targets, where [`NODE_SIZE`][crate::map::SgMap::NODE_SIZE] alone doesn't account for metadata.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut a = SgMap::<_, _, 10>::new();
-    /// a.insert(1, String::from("hello"));
-    /// a.insert(2, String::from("goodbye"));
-    ///
-    /// for value in a.values_mut() {
-    ///     value.push_str("!");
+    /// let mut map = SgMap::<u32, u32, 100>::new();
+    /// for i in 0..10 {
+    ///     map.insert(i, i);
     /// }
+    /// map.remove(&0);
     ///
-    /// let values: Vec<String> = a.values().cloned().collect();
-    /// assert_eq!(values, [String::from("hello!"),
-    ///                     String::from("goodbye!")]);
+    /// let usage = map.memory_usage();
+    /// assert_eq!(usage.total_bytes, core::mem::size_of_val(&map));
+    /// assert_eq!(usage.used_bytes, 9 * SgMap::<u32, u32, 100>::NODE_SIZE);
+    /// assert!(usage.free_slot_bytes > 0);
+    /// assert_eq!(
+    ///     usage.used_bytes + usage.free_slot_bytes + usage.spare_bytes + usage.overhead_bytes,
+    ///     usage.total_bytes
+    /// );
     /// ```
-    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V, N> {
-        ValuesMut {
-            inner: self.iter_mut(),
-        }
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.bst.memory_usage()
     }
 
-    /// Moves all elements from `other` into `self`, leaving `other` empty.
+    /// Computes a content digest: the map's length, then each key-value pair in ascending key order, fed to `hasher`.
+    /// Two maps with equal contents always produce the same digest, regardless of insertion order,
+    /// removals, or rebalancing history. Useful for verifying a map restored from persistent storage.
+    ///
+    /// The result is only as deterministic as `hasher` and the `Hash` implementations of `K` and `V`.
+    /// Use a fixed-key hasher (not a randomly seeded one), and note that `core`'s integer `Hash` implementations
+    /// write native-endian bytes, so digests of the same map may differ across architectures.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
+    /// use std::collections::hash_map::DefaultHasher;
     ///
-    /// let mut a = SgMap::<_, _, 10>::new();
+    /// let mut a = SgMap::<u32, &str, 10>::new();
     /// a.insert(1, "a");
     /// a.insert(2, "b");
     /// a.insert(3, "c");
+    /// a.remove(&3);
     ///
-    /// let mut b = SgMap::<_, _, 10>::new();
-    /// b.insert(3, "d");
-    /// b.insert(4, "e");
-    /// b.insert(5, "f");
-    ///
-    /// a.append(&mut b);
-    ///
-    /// assert_eq!(a.len(), 5);
-    /// assert_eq!(b.len(), 0);
+    /// let b: SgMap<u32, &str, 10> = vec![(2, "b"), (1, "a")].into_iter().collect();
+    /// assert_eq!(a.digest(DefaultHasher::new()), b.digest(DefaultHasher::new()));
     ///
-    /// assert_eq!(a[&1], "a");
-    /// assert_eq!(a[&2], "b");
-    /// assert_eq!(a[&3], "d");
-    /// assert_eq!(a[&4], "e");
-    /// assert_eq!(a[&5], "f");
+    /// a.insert(2, "B");
+    /// assert_ne!(a.digest(DefaultHasher::new()), b.digest(DefaultHasher::new()));
     /// ```
-    pub fn append(&mut self, other: &mut SgMap<K, V, N>) {
-        self.bst.append(&mut other.bst);
+    pub fn digest<H: Hasher>(&self, hasher: H) -> u64
+    where
+        K: Hash,
+        V: Hash,
+    {
+        self.bst.digest(hasher)
     }
 
-    /// Attempts to move all elements from `other` into `self`, leaving `other` empty.
+    /// Validate internal structure: ordering, links, cached metadata, and the alpha height bound.
+    /// Runs in `O(n)` time. Intended for fuzz harnesses and integration tests, see [`InvariantReport`].
     ///
     /// # Examples
     ///
     /// ```
-    /// use core::iter::FromIterator;
-    /// use scapegoat::{SgMap, SgError};
-    ///
-    /// let mut a = SgMap::<_, _, 10>::new();
-    /// a.try_insert(1, "a").is_ok();
-    /// a.try_insert(2, "b").is_ok();
-    /// a.try_insert(3, "c").is_ok();
-    ///
-    /// let mut b = SgMap::<_, _, 10>::new();
-    /// b.try_insert(3, "d").is_ok(); // Overwrite previous
-    /// b.try_insert(4, "e").is_ok();
-    /// b.try_insert(5, "f").is_ok();
-    ///
-    /// // Successful append
-    /// assert!(a.try_append(&mut b).is_ok());
-    ///
-    /// // Elements moved
-    /// assert_eq!(a.len(), 5);
-    /// assert_eq!(b.len(), 0);
-    ///
-    /// assert_eq!(a[&1], "a");
-    /// assert_eq!(a[&2], "b");
-    /// assert_eq!(a[&3], "d");
-    /// assert_eq!(a[&4], "e");
-    /// assert_eq!(a[&5], "f");
+    /// use scapegoat::SgMap;
     ///
-    /// // Fill remaining capacity
-    /// let mut key = 6;
-    /// while a.len() < a.capacity() {
-    ///     assert!(a.try_insert(key, "filler").is_ok());
-    ///     key += 1;
+    /// let mut map = SgMap::<_, _, 100>::new();
+    /// for i in 0..100 {
+    ///     map.insert(i, i);
     /// }
     ///
-    /// // Full
-    /// assert!(a.is_full());
-    ///
-    /// // More data
-    /// let mut c = SgMap::<_, _, 10>::from_iter([(11, "k"), (12, "l")]);
-    /// let mut d = SgMap::<_, _, 10>::from_iter([(1, "a2"), (2, "b2")]);
-    ///
-    /// // Cannot append new pairs
-    /// assert_eq!(a.try_append(&mut c), Err(SgError::StackCapacityExceeded));
-    ///
-    /// // Can still replace existing pairs
-    /// assert!(a.try_append(&mut d).is_ok());
+    /// let report = map.check_invariants();
+    /// assert!(report.is_valid());
+    /// assert_eq!(report.node_cnt, 100);
+    /// assert!(report.height <= report.max_height);
     /// ```
-    pub fn try_append(&mut self, other: &mut SgMap<K, V, N>) -> Result<(), SgError> {
-        self.bst.try_append(&mut other.bst)
+    pub fn check_invariants(&self) -> InvariantReport {
+        self.bst.check_invariants()
     }
 
-    /// Insert a key-value pair into the map.
-    /// If the map did not have this key present, `None` is returned.
-    /// If the map did have this key present, the value is updated, the old value is returned,
-    /// and the key is updated. This accommodates types that can be `==` without being identical.
+    /// Writes the internal tree structure in [Graphviz](https://graphviz.org/) DOT format, labelling nodes with their keys.
+    /// Useful for debugging balance: render with `dot -Tsvg`.
     ///
     /// # Examples
     ///
@@ -315,192 +531,179 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// use scapegoat::SgMap;
     ///
     /// let mut map = SgMap::<_, _, 10>::new();
-    /// assert_eq!(map.insert(37, "a"), None);
-    /// assert_eq!(map.is_empty(), false);
+    /// map.extend([(2, "b"), (1, "a"), (3, "c")]);
     ///
-    /// map.insert(37, "b");
-    /// assert_eq!(map.insert(37, "c"), Some("b"));
-    /// assert_eq!(map[&37], "c");
+    /// let mut dot = String::new();
+    /// map.to_dot(&mut dot).unwrap();
+    ///
+    /// assert!(dot.starts_with("digraph {"));
+    /// assert_eq!(dot.matches(" -> ").count(), 2);
     /// ```
-    pub fn insert(&mut self, key: K, val: V) -> Option<V>
+    pub fn to_dot<W: fmt::Write>(&self, w: &mut W) -> fmt::Result
     where
-        K: Ord,
+        K: Debug,
     {
-        self.bst.insert(key, val)
+        self.bst.to_dot(w)
     }
 
-    /// Insert a key-value pair into the map.
-    /// Returns `Err` if the operation can't be completed, else the `Ok` contains:
-    /// * `None` if the map did not have this key present.
-    /// * The old value if the map did have this key present (both the value and key are updated,
-    /// this accommodates types that can be `==` without being identical).
-    ///
-    /// ### Warning
-    ///
-    /// Unlike other APIs in this crate, the semantics and return type of this API are *NOT* the same as `BTreeMap`'s nightly [`try_insert`](https://doc.rust-lang.org/std/collections/struct.BTreeMap.html#method.try_insert).
-    /// For an equivalent, use [`try_insert_std`][`SgMap::try_insert_std`].
+    /// Writes the internal tree structure in [Graphviz](https://graphviz.org/) DOT format,
+    /// optionally annotating nodes with their storage index and subtree size.
     ///
     /// # Examples
     ///
     /// ```
-    /// use scapegoat::{SgMap, SgError};
+    /// use scapegoat::{SgMap, DotOptions};
     ///
     /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.extend([(2, "b"), (1, "a"), (3, "c")]);
     ///
-    /// // Add a new pair
-    /// assert_eq!(map.try_insert(37, "a"), Ok(None));
-    /// assert_eq!(map.is_empty(), false);
-    ///
-    /// // Replace existing pair
-    /// map.insert(37, "b");
-    /// assert_eq!(map.try_insert(37, "c"), Ok(Some("b")));
-    /// assert_eq!(map[&37], "c");
-    ///
-    /// // Fill remaining capacity
-    /// let mut key = 38;
-    /// while map.len() < map.capacity() {
-    ///     assert!(map.try_insert(key, "filler").is_ok());
-    ///     key += 1;
-    /// }
-    ///
-    /// // Full
-    /// assert!(map.is_full());
+    /// let opts = DotOptions {
+    ///     arena_idxs: true,
+    ///     subtree_sizes: true,
+    /// };
     ///
-    /// // Cannot insert new pair
-    /// assert_eq!(map.try_insert(key, "out of bounds"), Err(SgError::StackCapacityExceeded));
+    /// let mut dot = String::new();
+    /// map.to_dot_with(&mut dot, opts).unwrap();
     ///
-    /// // Can still replace existing pair
-    /// assert_eq!(map.try_insert(key - 1, "overwrite filler"), Ok(Some("filler")));
+    /// assert!(dot.contains("size: 3"));
     /// ```
-    ///
-    pub fn try_insert(&mut self, key: K, val: V) -> Result<Option<V>, SgError>
+    pub fn to_dot_with<W: fmt::Write>(&self, w: &mut W, opts: DotOptions) -> fmt::Result
     where
-        K: Ord,
+        K: Debug,
     {
-        self.bst.try_insert(key, val)
+        self.bst.to_dot_with(w, opts)
     }
 
-    /// Tries to insert a key-value pair into the map, and returns
-    /// a mutable reference to the value in the entry.
+    /// Perform up to `max_work` units of optional housekeeping, returning `true` if more remains.
+    /// Lets maintenance be scheduled in idle time, e.g. between iterations of a main loop.
     ///
-    /// If the map already had this key present, nothing is updated, and
-    /// an error containing the occupied entry and the value is returned.
-    ///
-    /// ### Warning
+    /// Rebalancing required for `O(log n)` operations always happens synchronously, inside `insert`/`remove`.
+    /// This API performs *extra* work beyond that minimum. A unit of work is one node visited, checks included.
+    /// Each task first checks if it's needed (up to `self.len()` units), then costs `self.len()` more units
+    /// and is only started if it fits in the remaining budget. A budget of `3 * self.len()` always makes progress:
     ///
-    /// The semantics and return type of this API match `BTreeMap`'s nightly [`try_insert`](https://doc.rust-lang.org/std/collections/struct.BTreeMap.html#method.try_insert), *NOT* the other `try_*` APIs in this crate.
-    /// For a fallible insert, use [`try_insert`][`SgMap::try_insert`].
+    /// 1. Rebuild to minimal height, speeding up subsequent lookups.
+    /// 2. Compact internal storage into sorted order, speeding up subsequent iteration.
     ///
     /// # Examples
     ///
-    /// Basic usage:
-    ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// assert_eq!(map.try_insert_std(37, "a").unwrap(), &"a");
+    /// let mut map = SgMap::<_, _, 100>::new();
+    /// for i in 0..100 {
+    ///     map.insert(i, i);
+    /// }
     ///
-    /// let err = map.try_insert_std(37, "b").unwrap_err();
-    /// assert_eq!(err.entry.key(), &37);
-    /// assert_eq!(err.entry.get(), &"a");
-    /// assert_eq!(err.value, "b");
+    /// // Budget too small, no work done
+    /// assert!(map.maintain(10));
+    ///
+    /// // Run to completion
+    /// while map.maintain(300) {}
+    /// assert!(!map.maintain(300));
     /// ```
-    pub fn try_insert_std(&mut self, key: K, value: V) -> Result<&mut V, OccupiedError<'_, K, V, N>>
-    where
-        K: Ord,
-    {
-        match self.entry(key) {
-            Entry::Occupied(entry) => Err(OccupiedError { entry, value }),
-            Entry::Vacant(entry) => Ok(entry.insert(value)),
-        }
+    pub fn maintain(&mut self, max_work: usize) -> bool {
+        self.bst.maintain(max_work)
     }
 
-    /// Attempt to extend a collection with the contents of an iterator.
+    /// Suspend rebalancing for a batched insert phase, returning a guard that derefs to the map.
+    /// When the guard is dropped, the configured rebalance parameter is restored and the whole map is rebuilt once.
+    ///
+    /// Inserting `n` items through the guard skips the per-insert scapegoat search and partial rebuilds.
+    /// Lookups during the phase may be slower, tree height is only bounded once the guard is dropped.
+    /// Best for shuffled input: sorted input degrades the temporary tree to a list, making each insert `O(n)`.
     ///
     /// # Examples
     ///
     /// ```
-    /// use core::iter::FromIterator;
-    /// use scapegoat::{SgMap, SgError};
-    ///
-    /// let mut a = SgMap::<_, _, 2>::new();
-    /// let mut b = SgMap::<_, _, 3>::from_iter([(1, "a"), (2, "b"), (3, "c")]);
-    /// let mut c = SgMap::<_, _, 2>::from_iter([(1, "a"), (2, "b")]);
+    /// use scapegoat::SgMap;
     ///
-    /// // Too big
-    /// assert_eq!(a.try_extend(b.into_iter()), Err(SgError::StackCapacityExceeded));
+    /// let mut map = SgMap::<_, _, 100>::new();
+    /// {
+    ///     let mut map = map.bulk_load();
+    ///     for i in (0..100).map(|i| (i * 37) % 100) {
+    ///         map.insert(i, i);
+    ///     }
+    ///     assert_eq!(map.stats().rebal_cnt, 0);
+    /// }
     ///
-    /// // Fits
-    /// assert!(a.try_extend(c.into_iter()).is_ok());
+    /// assert_eq!(map.len(), 100);
+    /// assert_eq!(map.stats().rebal_cnt, 1);
     /// ```
-    ///
-    /// ### Note
-    ///
-    /// There is no `TryExtend` trait in `core`/`std`.
-    pub fn try_extend<I: ExactSizeIterator + IntoIterator<Item = (K, V)>>(
-        &mut self,
-        iter: I,
-    ) -> Result<(), SgError> {
-        self.bst.try_extend(iter)
+    pub fn bulk_load(&mut self) -> BulkLoad<'_, K, V, N, U, C> {
+        BulkLoad::new(self)
     }
 
-    /// Attempt conversion from an iterator.
-    /// Will fail if iterator length exceeds `u16::MAX`.
+    /// Begin a transaction that can be rolled back, returning a guard that derefs to the map for reads.
+    /// Mutations made through the guard are kept only if it's [`commit`][crate::map_types::Transaction::commit]ted,
+    /// dropping it (e.g. on an early `?` return) or calling [`rollback`][crate::map_types::Transaction::rollback]
+    /// undoes them all, newest first.
+    ///
+    /// Instead of copying the map up front, the guard logs how to undo each mutation.
+    /// Each insert or removal takes one of `M` log slots, so a transaction costs `M` entries of storage, not `N`.
+    /// Rollback restores the map's contents, not necessarily its internal layout.
     ///
     /// # Examples
     ///
     /// ```
-    /// use scapegoat::{SgMap, SgError};
+    /// use scapegoat::SgMap;
     ///
-    /// const CAPACITY_1: usize = 1_000;
-    /// let vec: Vec<(usize, usize)> = (0..CAPACITY_1).map(|n|(n, n)).collect();
-    /// assert!(SgMap::<usize, usize, CAPACITY_1>::try_from_iter(vec.into_iter()).is_ok());
+    /// let mut map: SgMap<_, _, 10> = vec![(1, "a"), (2, "b")].into_iter().collect();
+    ///
+    /// let mut txn = map.transaction::<4>();
+    /// txn.insert(1, "c");
+    /// txn.remove(&2);
+    /// txn.insert(3, "d");
+    /// assert!(txn.iter().eq([(&1, &"c"), (&3, &"d")]));
+    /// txn.rollback();
+    /// assert!(map.iter().eq([(&1, &"a"), (&2, &"b")]));
+    ///
+    /// let mut txn = map.transaction::<4>();
+    /// txn.insert(3, "d");
+    /// txn.commit();
+    /// assert_eq!(map.len(), 3);
+    /// ```
+    #[doc(alias = "snapshot")]
+    #[doc(alias = "undo")]
+    pub fn transaction<const M: usize>(&mut self) -> Transaction<'_, K, V, N, M, U, C> {
+        Transaction::new(self)
+    }
+
+    /// Total capacity, e.g. maximum number of map pairs.
+    ///
+    /// # Examples
     ///
-    /// const CAPACITY_2: usize = (u16::MAX as usize) + 1;
-    /// let vec: Vec<(usize, usize)> = (0..CAPACITY_2).map(|n|(n, n)).collect();
-    /// assert_eq!(
-    ///     SgMap::<usize, usize, CAPACITY_2>::try_from_iter(vec.into_iter()),
-    ///     Err(SgError::MaximumCapacityExceeded)
-    /// );
     /// ```
+    /// use scapegoat::SgMap;
     ///
-    /// ### Note
+    /// let mut map = SgMap::<usize, &str, 10>::new();
     ///
-    /// There is no `TryFromIterator` trait in `core`/`std`.
-    pub fn try_from_iter<I: ExactSizeIterator + IntoIterator<Item = (K, V)>>(
-        iter: I,
-    ) -> Result<Self, SgError> {
-        match iter.len() <= SgTree::<K, V, N>::max_capacity() {
-            true => Ok(SgMap::from_iter(iter)),
-            false => Err(SgError::MaximumCapacityExceeded),
-        }
+    /// assert!(map.capacity() == 10);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.bst.capacity()
     }
 
-    /// Gets an iterator over the entries of the map, sorted by key.
+    /// Checks that `additional` more pairs fit in the fixed capacity `N`, returning `Err` otherwise.
+    /// Nothing is reserved or allocated (capacity never changes), this lets a batch of inserts fail up front instead of midway.
     ///
     /// # Examples
     ///
     /// ```
-    /// use scapegoat::SgMap;
+    /// use scapegoat::{SgMap, SgError};
     ///
     /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert(3, "c");
-    /// map.insert(2, "b");
-    /// map.insert(1, "a");
-    ///
-    /// for (key, value) in map.iter() {
-    ///     println!("{}: {}", key, value);
-    /// }
+    /// map.extend([(1, "a"), (2, "b"), (3, "c")]);
     ///
-    /// let (first_key, first_value) = map.iter().next().unwrap();
-    /// assert_eq!((*first_key, *first_value), (1, "a"));
+    /// assert_eq!(map.try_reserve(7), Ok(()));
+    /// assert_eq!(map.try_reserve(8), Err(SgError::StackCapacityExceeded));
     /// ```
-    pub fn iter(&self) -> Iter<'_, K, V, N> {
-        Iter::new(self)
+    pub fn try_reserve(&self, additional: usize) -> Result<(), SgError> {
+        self.bst.try_reserve(additional)
     }
 
-    /// Gets a mutable iterator over the entries of the map, sorted by key.
+    /// Defragments storage: pairs are moved into contiguous, in-order (by key) slots and all free slots are released.
+    /// Speeds up subsequent iteration. Runs in `O(n log n)` time.
     ///
     /// # Examples
     ///
@@ -508,73 +711,61 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// use scapegoat::SgMap;
     ///
     /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert("a", 1);
-    /// map.insert("b", 2);
-    /// map.insert("c", 3);
-    ///
-    /// // Add 10 to the value if the key isn't "a"
-    /// for (key, value) in map.iter_mut() {
-    ///     if key != &"a" {
-    ///         *value += 10;
-    ///     }
-    /// }
+    /// map.extend([(3, "c"), (1, "a"), (4, "d"), (2, "b")]);
+    /// map.remove(&1);
+    /// map.remove(&4);
     ///
-    /// let (second_key, second_value) = map.iter().skip(1).next().unwrap();
-    /// assert_eq!((*second_key, *second_value), ("b", 12));
+    /// map.compact();
+    /// assert!(map.into_iter().eq([(2, "b"), (3, "c")]));
     /// ```
-    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, N> {
-        IterMut::new(self)
+    pub fn compact(&mut self) {
+        self.bst.compact()
     }
 
-    /// Removes a key from the map, returning the stored key and value if the key
-    /// was previously in the map.
+    /// Reorders storage slots for cache locality, leaving the tree's shape (and so search paths) unchanged.
+    /// Worth it after a build-then-read-mostly phase: later inserts and removals gradually undo the layout.
+    /// Handles issued before the call are invalidated. Runs in `O(n log n)` time.
     ///
-    /// The key may be any borrowed form of the map's key type, but the ordering
-    /// on the borrowed form *must* match the ordering on the key type.
+    /// [`LayoutPolicy::InOrder`] speeds up iteration and range queries,
+    /// [`LayoutPolicy::VanEmdeBoas`] speeds up point lookups on maps larger than the cache.
+    /// [`maintain`](Self::maintain) reverts the latter, it compacts into in-order layout.
     ///
     /// # Examples
     ///
     /// ```
-    /// use scapegoat::SgMap;
+    /// use scapegoat::{LayoutPolicy, SgMap};
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert(1, "a");
-    /// assert_eq!(map.remove_entry(&1), Some((1, "a")));
-    /// assert_eq!(map.remove_entry(&1), None);
+    /// let mut map: SgMap<_, _, 100> = (0..100).map(|i| (i, i * 2)).collect();
+    ///
+    /// map.optimize_layout(LayoutPolicy::VanEmdeBoas);
+    /// assert_eq!(map.get(&42), Some(&84));
+    /// assert!(map.keys().copied().eq(0..100));
     /// ```
-    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
-    where
-        K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
-    {
-        self.bst.remove_entry(key)
+    pub fn optimize_layout(&mut self, policy: LayoutPolicy) {
+        self.bst.optimize_layout(policy)
     }
 
-    /// Retains only the elements specified by the predicate.
-    ///
-    /// In other words, remove all pairs `(k, v)` such that `f(&k, &mut v)` returns `false`.
-    /// The elements are visited in ascending key order.
+    /// Gets an iterator over the keys of the map, in sorted order.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map: SgMap<i32, i32, 10> = (0..8).map(|x| (x, x*10)).collect();
-    /// // Keep only the elements with even-numbered keys.
-    /// map.retain(|&k, _| k % 2 == 0);
-    /// assert!(map.into_iter().eq(vec![(0, 0), (2, 20), (4, 40), (6, 60)]));
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// a.insert(2, "b");
+    /// a.insert(1, "a");
+    ///
+    /// let keys: Vec<_> = a.keys().cloned().collect();
+    /// assert_eq!(keys, [1, 2]);
     /// ```
-    pub fn retain<F>(&mut self, mut f: F)
-    where
-        K: Ord,
-        F: FnMut(&K, &mut V) -> bool,
-    {
-        self.bst.retain(|k, v| f(k, v));
+    pub fn keys(&self) -> Keys<'_, K, V, N, U, C> {
+        Keys { inner: self.iter() }
     }
 
-    /// Splits the collection into two at the given key. Returns everything after the given key,
-    /// including the key.
+    /// Creates a consuming iterator visiting all the keys, in sorted order.
+    /// The map cannot be used after calling this.
+    /// The iterator element type is `K`.
     ///
     /// # Examples
     ///
@@ -582,105 +773,2090 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// use scapegoat::SgMap;
     ///
     /// let mut a = SgMap::<_, _, 10>::new();
-    /// a.insert(1, "a");
     /// a.insert(2, "b");
-    /// a.insert(3, "c");
-    /// a.insert(17, "d");
-    /// a.insert(41, "e");
-    ///
-    /// let b = a.split_off(&3);
-    ///
-    /// assert_eq!(a.len(), 2);
-    /// assert_eq!(b.len(), 3);
-    ///
-    /// assert_eq!(a[&1], "a");
-    /// assert_eq!(a[&2], "b");
+    /// a.insert(1, "a");
     ///
-    /// assert_eq!(b[&3], "c");
-    /// assert_eq!(b[&17], "d");
-    /// assert_eq!(b[&41], "e");
+    /// let keys: Vec<i32> = a.into_keys().collect();
+    /// assert_eq!(keys, [1, 2]);
     /// ```
-    pub fn split_off<Q>(&mut self, key: &Q) -> SgMap<K, V, N>
-    where
-        K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
-    {
-        SgMap {
-            bst: self.bst.split_off(key),
+    pub fn into_keys(self) -> IntoKeys<K, V, N, U, C> {
+        IntoKeys {
+            inner: self.into_iter(),
         }
     }
 
-    /// Removes a key from the map, returning the value at the key if the key
-    /// was previously in the map.
-    ///
-    /// The key may be any borrowed form of the map's key type, but the ordering
-    /// on the borrowed form *must* match the ordering on the key type.
+    /// Gets an iterator over the values of the map, in order by key.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert(1, "a");
-    /// assert_eq!(map.remove(&1), Some("a"));
-    /// assert_eq!(map.remove(&1), None);
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// a.insert(1, "hello");
+    /// a.insert(2, "goodbye");
+    ///
+    /// let values: Vec<&str> = a.values().cloned().collect();
+    /// assert_eq!(values, ["hello", "goodbye"]);
     /// ```
-    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
-    where
-        K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
-    {
-        self.bst.remove(key)
+    pub fn values(&self) -> Values<'_, K, V, N, U, C> {
+        Values { inner: self.iter() }
     }
 
-    /// Returns the key-value pair corresponding to the supplied key.
-    ///
-    /// The supplied key may be any borrowed form of the map's key type, but the ordering
-    /// on the borrowed form *must* match the ordering on the key type.
+    /// Creates a consuming iterator visiting all the values, in order by key.
+    /// The map cannot be used after calling this.
+    /// The iterator element type is `V`.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert(1, "a");
-    /// assert_eq!(map.get_key_value(&1), Some((&1, &"a")));
-    /// assert_eq!(map.get_key_value(&2), None);
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// a.insert(1, "hello");
+    /// a.insert(2, "goodbye");
+    ///
+    /// let values: Vec<&str> = a.into_values().collect();
+    /// assert_eq!(values, ["hello", "goodbye"]);
     /// ```
-    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
-    where
-        K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
-    {
-        self.bst.get_key_value(key)
+    pub fn into_values(self) -> IntoValues<K, V, N, U, C> {
+        IntoValues {
+            inner: self.into_iter(),
+        }
     }
 
-    /// Returns a reference to the value corresponding to the key.
+    /// Consumes the map, returning a map with the same keys and each value replaced by `f(&key, val)`.
+    /// `f` is called in ascending key order.
     ///
-    /// The key may be any borrowed form of the map's key type, but the ordering
-    /// on the borrowed form *must* match the ordering on the key type.
+    /// The internal tree is reused as-is, so no key comparisons or rebalancing take place: `O(n)`.
+    /// Cheaper than collecting [`into_iter`][crate::SgMap::into_iter] into a new map.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert(1, "a");
+    /// let mut cfg = SgMap::<_, _, 10>::new();
+    /// cfg.insert("retries", "3");
+    /// cfg.insert("timeout", "30");
+    ///
+    /// let resolved = cfg.map_values(|_, v| v.parse::<u32>().unwrap());
+    /// assert_eq!(resolved.get("retries"), Some(&3));
+    /// assert_eq!(resolved.get("timeout"), Some(&30));
+    /// ```
+    pub fn map_values<W, F>(self, f: F) -> SgMap<K, W, N, U, C>
+    where
+        F: FnMut(&K, V) -> W,
+    {
+        SgMap {
+            bst: self.bst.map_values(f),
+        }
+    }
+
+    /// Gets a mutable iterator over the values of the map, in order by key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// a.insert(1, String::from("hello"));
+    /// a.insert(2, String::from("goodbye"));
+    ///
+    /// for value in a.values_mut() {
+    ///     value.push_str("!");
+    /// }
+    ///
+    /// let values: Vec<String> = a.values().cloned().collect();
+    /// assert_eq!(values, [String::from("hello!"),
+    ///                     String::from("goodbye!")]);
+    /// ```
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V, N, U> {
+        ValuesMut {
+            inner: self.iter_mut(),
+        }
+    }
+
+    /// Moves all elements from `other` into `self`, leaving `other` empty.
+    /// Runs in `O(n + m)` time: both are merged in sorted order, then rebuilt once.
+    /// If the key ranges don't overlap, `other`'s entries are moved over without a merge,
+    /// using a constant number of comparisons.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    ///
+    /// let mut b = SgMap::<_, _, 10>::new();
+    /// b.insert(3, "d");
+    /// b.insert(4, "e");
+    /// b.insert(5, "f");
+    ///
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(a.len(), 5);
+    /// assert_eq!(b.len(), 0);
+    ///
+    /// assert_eq!(a[&1], "a");
+    /// assert_eq!(a[&2], "b");
+    /// assert_eq!(a[&3], "d");
+    /// assert_eq!(a[&4], "e");
+    /// assert_eq!(a[&5], "f");
+    /// ```
+    pub fn append(&mut self, other: &mut SgMap<K, V, N, U, C>) {
+        self.bst.append(&mut other.bst);
+    }
+
+    /// Attempts to move all elements from `other` into `self`, leaving `other` empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::iter::FromIterator;
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// a.try_insert(1, "a").is_ok();
+    /// a.try_insert(2, "b").is_ok();
+    /// a.try_insert(3, "c").is_ok();
+    ///
+    /// let mut b = SgMap::<_, _, 10>::new();
+    /// b.try_insert(3, "d").is_ok(); // Overwrite previous
+    /// b.try_insert(4, "e").is_ok();
+    /// b.try_insert(5, "f").is_ok();
+    ///
+    /// // Successful append
+    /// assert!(a.try_append(&mut b).is_ok());
+    ///
+    /// // Elements moved
+    /// assert_eq!(a.len(), 5);
+    /// assert_eq!(b.len(), 0);
+    ///
+    /// assert_eq!(a[&1], "a");
+    /// assert_eq!(a[&2], "b");
+    /// assert_eq!(a[&3], "d");
+    /// assert_eq!(a[&4], "e");
+    /// assert_eq!(a[&5], "f");
+    ///
+    /// // Fill remaining capacity
+    /// let mut key = 6;
+    /// while a.len() < a.capacity() {
+    ///     assert!(a.try_insert(key, "filler").is_ok());
+    ///     key += 1;
+    /// }
+    ///
+    /// // Full
+    /// assert!(a.is_full());
+    ///
+    /// // More data
+    /// let mut c = SgMap::<_, _, 10>::from_iter([(11, "k"), (12, "l")]);
+    /// let mut d = SgMap::<_, _, 10>::from_iter([(1, "a2"), (2, "b2")]);
+    ///
+    /// // Cannot append new pairs
+    /// assert_eq!(a.try_append(&mut c), Err(SgError::StackCapacityExceeded));
+    ///
+    /// // Can still replace existing pairs
+    /// assert!(a.try_append(&mut d).is_ok());
+    /// ```
+    pub fn try_append(&mut self, other: &mut SgMap<K, V, N, U, C>) -> Result<(), SgError> {
+        self.bst.try_append(&mut other.bst)
+    }
+
+    /// Insert a key-value pair into the map.
+    /// If the map did not have this key present, `None` is returned.
+    /// If the map did have this key present, the value is updated, the old value is returned,
+    /// and the key is updated. This accommodates types that can be `==` without being identical.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// assert_eq!(map.insert(37, "a"), None);
+    /// assert_eq!(map.is_empty(), false);
+    ///
+    /// map.insert(37, "b");
+    /// assert_eq!(map.insert(37, "c"), Some("b"));
+    /// assert_eq!(map[&37], "c");
+    /// ```
+    pub fn insert(&mut self, key: K, val: V) -> Option<V>
+    where
+        K: Ord,
+    {
+        self.bst.insert(key, val)
+    }
+
+    /// Insert a key-value pair into the map.
+    /// Returns `Err` if the operation can't be completed, else the `Ok` contains:
+    /// * `None` if the map did not have this key present.
+    /// * The old value if the map did have this key present (both the value and key are updated,
+    ///   this accommodates types that can be `==` without being identical).
+    ///
+    /// ### Warning
+    ///
+    /// Unlike other APIs in this crate, the semantics and return type of this API are *NOT* the same as `BTreeMap`'s nightly [`try_insert`](https://doc.rust-lang.org/std/collections/struct.BTreeMap.html#method.try_insert).
+    /// For an equivalent, use [`try_insert_std`][`SgMap::try_insert_std`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    ///
+    /// // Add a new pair
+    /// assert_eq!(map.try_insert(37, "a"), Ok(None));
+    /// assert_eq!(map.is_empty(), false);
+    ///
+    /// // Replace existing pair
+    /// map.insert(37, "b");
+    /// assert_eq!(map.try_insert(37, "c"), Ok(Some("b")));
+    /// assert_eq!(map[&37], "c");
+    ///
+    /// // Fill remaining capacity
+    /// let mut key = 38;
+    /// while map.len() < map.capacity() {
+    ///     assert!(map.try_insert(key, "filler").is_ok());
+    ///     key += 1;
+    /// }
+    ///
+    /// // Full
+    /// assert!(map.is_full());
+    ///
+    /// // Cannot insert new pair
+    /// assert_eq!(map.try_insert(key, "out of bounds"), Err(SgError::StackCapacityExceeded));
+    ///
+    /// // Can still replace existing pair
+    /// assert_eq!(map.try_insert(key - 1, "overwrite filler"), Ok(Some("filler")));
+    /// ```
+    ///
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<Option<V>, SgError>
+    where
+        K: Ord,
+    {
+        self.bst.try_insert(key, val)
+    }
+
+    /// Insert a key-value pair into the map, never exceeding capacity.
+    /// If the map is full and the key isn't present, first evicts the pair at the end selected by `policy`.
+    /// Returns the old value (as [`insert`][`SgMap::insert`]) alongside the evicted pair, if any.
+    /// If the new key would itself be the one evicted, the new pair is handed back and the map is unchanged.
+    ///
+    /// Makes the map a fixed-memory "keep the `N` most recent (or largest)" cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{EvictPolicy, SgMap};
+    ///
+    /// // Keep the 3 latest readings, keyed by timestamp
+    /// let mut latest = SgMap::<u64, f32, 3>::new();
+    /// for (ts, reading) in [(1, 0.5), (2, 0.7), (3, 0.6)] {
+    ///     assert_eq!(latest.insert_or_evict(ts, reading, EvictPolicy::First), (None, None));
+    /// }
+    ///
+    /// assert_eq!(latest.insert_or_evict(4, 0.9, EvictPolicy::First), (None, Some((1, 0.5))));
+    /// assert_eq!(latest.insert_or_evict(3, 0.8, EvictPolicy::First), (Some(0.6), None));
+    ///
+    /// // Too old to keep
+    /// assert_eq!(latest.insert_or_evict(0, 0.1, EvictPolicy::First), (None, Some((0, 0.1))));
+    /// assert!(latest.keys().eq(&[2, 3, 4]));
+    /// ```
+    #[doc(alias = "bounded")]
+    #[doc(alias = "cache")]
+    pub fn insert_or_evict(
+        &mut self,
+        key: K,
+        val: V,
+        policy: EvictPolicy,
+    ) -> (Option<V>, Option<(K, V)>)
+    where
+        K: Ord,
+    {
+        self.bst.insert_or_evict(key, val, policy)
+    }
+
+    /// Insert a key-value pair into the map, handing the pair back if there's no room for it.
+    /// Returns `Err` if the operation can't be completed, else the `Ok` contains:
+    /// * `None` if the map did not have this key present.
+    /// * The old value if the map did have this key present (both the value and key are updated,
+    ///   this accommodates types that can be `==` without being identical).
+    ///
+    /// Unlike [`try_insert`][`SgMap::try_insert`], a failed insert doesn't drop the key and value:
+    /// the [`CapacityError`][crate::CapacityError] owns the rejected pair, so non-`Clone` payloads can be retried or routed elsewhere.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, CapacityError};
+    ///
+    /// let mut map = SgMap::<_, _, 2>::new();
+    /// assert_eq!(map.insert_within_capacity(1, String::from("a")), Ok(None));
+    /// assert_eq!(map.insert_within_capacity(2, String::from("b")), Ok(None));
+    ///
+    /// // Full, the rejected pair is returned
+    /// let rejected = map.insert_within_capacity(3, String::from("c"));
+    /// assert_eq!(rejected, Err(CapacityError((3, String::from("c")))));
+    ///
+    /// // Route it elsewhere
+    /// let mut overflow = SgMap::<_, _, 2>::new();
+    /// let (k, v) = rejected.unwrap_err().into_inner();
+    /// assert_eq!(overflow.insert_within_capacity(k, v), Ok(None));
+    ///
+    /// // Can still replace existing pair
+    /// assert_eq!(map.insert_within_capacity(2, String::from("B")), Ok(Some(String::from("b"))));
+    /// ```
+    pub fn insert_within_capacity(
+        &mut self,
+        key: K,
+        val: V,
+    ) -> Result<Option<V>, CapacityError<(K, V)>>
+    where
+        K: Ord,
+    {
+        self.bst.insert_within_capacity(key, val)
+    }
+
+    /// Tries to insert a key-value pair into the map, and returns
+    /// a mutable reference to the value in the entry.
+    ///
+    /// If the map already had this key present, nothing is updated, and
+    /// an error containing the occupied entry and the value is returned.
+    ///
+    /// ### Warning
+    ///
+    /// The semantics and return type of this API match `BTreeMap`'s nightly [`try_insert`](https://doc.rust-lang.org/std/collections/struct.BTreeMap.html#method.try_insert), *NOT* the other `try_*` APIs in this crate.
+    /// For a fallible insert, use [`try_insert`][`SgMap::try_insert`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// assert_eq!(map.try_insert_std(37, "a").unwrap(), &"a");
+    ///
+    /// let err = map.try_insert_std(37, "b").unwrap_err();
+    /// assert_eq!(err.entry.key(), &37);
+    /// assert_eq!(err.entry.get(), &"a");
+    /// assert_eq!(err.value, "b");
+    /// ```
+    pub fn try_insert_std(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<&mut V, OccupiedError<'_, K, V, N, U, C>>
+    where
+        K: Ord,
+    {
+        match self.entry(key) {
+            Entry::Occupied(entry) => Err(OccupiedError { entry, value }),
+            Entry::Vacant(entry) => Ok(entry.insert(value)),
+        }
+    }
+
+    /// Attempt to extend a collection with the contents of an iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::iter::FromIterator;
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let mut a = SgMap::<_, _, 2>::new();
+    /// let mut b = SgMap::<_, _, 3>::from_iter([(1, "a"), (2, "b"), (3, "c")]);
+    /// let mut c = SgMap::<_, _, 2>::from_iter([(1, "a"), (2, "b")]);
+    ///
+    /// // Too big
+    /// assert_eq!(a.try_extend(b.into_iter()), Err(SgError::StackCapacityExceeded));
+    ///
+    /// // Fits
+    /// assert!(a.try_extend(c.into_iter()).is_ok());
+    /// ```
+    ///
+    /// ### Note
+    ///
+    /// There is no `TryExtend` trait in `core`/`std`.
+    pub fn try_extend<I: ExactSizeIterator + IntoIterator<Item = (K, V)>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), SgError> {
+        self.bst.try_extend(iter)
+    }
+
+    /// Attempt conversion from an iterator.
+    /// Will fail if iterator length exceeds the index type's maximum capacity (`65_534` by default),
+    /// or if the map's capacity, `N`, is exceeded.
+    /// Duplicate keys keep the last occurrence, same as [`FromIterator`](core::iter::FromIterator).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// const CAPACITY_1: usize = 1_000;
+    /// let vec: Vec<(usize, usize)> = (0..CAPACITY_1).map(|n|(n, n)).collect();
+    /// assert!(SgMap::<usize, usize, CAPACITY_1>::try_from_iter(vec.into_iter()).is_ok());
+    ///
+    /// let vec = vec![(1, "a"), (2, "b"), (3, "c")];
+    /// assert_eq!(
+    ///     SgMap::<_, _, 2>::try_from_iter(vec.into_iter()),
+    ///     Err(SgError::StackCapacityExceeded)
+    /// );
+    ///
+    /// let vec: Vec<(usize, usize)> = (0..255).map(|n|(n, n)).collect();
+    /// assert_eq!(
+    ///     SgMap::<usize, usize, 254, u8>::try_from_iter(vec.into_iter()),
+    ///     Err(SgError::MaximumCapacityExceeded)
+    /// );
+    /// ```
+    ///
+    /// ### Note
+    ///
+    /// There is no `TryFromIterator` trait in `core`/`std`.
+    pub fn try_from_iter<I: ExactSizeIterator + IntoIterator<Item = (K, V)>>(
+        iter: I,
+    ) -> Result<Self, SgError>
+    where
+        C: Default,
+    {
+        match iter.len() <= SgTree::<K, V, N, U, C>::max_capacity() {
+            true => SgMap::from_iter_with_policy(iter, DupPolicy::KeepLast),
+            false => Err(SgError::MaximumCapacityExceeded),
+        }
+    }
+
+    /// Attempt conversion from an iterator, with explicit handling of duplicate keys.
+    /// [`FromIterator`](core::iter::FromIterator) always keeps the last occurrence, i.e. [`DupPolicy::KeepLast`].
+    ///
+    /// Returns `Err(SgError::StackCapacityExceeded)` if the map's capacity is exceeded,
+    /// or `Err(SgError::KeyAlreadyExists)` on a duplicate key under [`DupPolicy::Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{DupPolicy, SgError, SgMap};
+    ///
+    /// let log = [(1, "a"), (2, "b"), (1, "c")];
+    ///
+    /// let map = SgMap::<_, _, 10>::from_iter_with_policy(log, DupPolicy::KeepFirst).unwrap();
+    /// assert_eq!(map[&1], "a");
+    ///
+    /// let map = SgMap::<_, _, 10>::from_iter_with_policy(log, DupPolicy::KeepLast).unwrap();
+    /// assert_eq!(map[&1], "c");
+    ///
+    /// assert_eq!(
+    ///     SgMap::<_, _, 10>::from_iter_with_policy(log, DupPolicy::Error),
+    ///     Err(SgError::KeyAlreadyExists)
+    /// );
+    /// ```
+    pub fn from_iter_with_policy<I: IntoIterator<Item = (K, V)>>(
+        iter: I,
+        policy: DupPolicy,
+    ) -> Result<Self, SgError>
+    where
+        C: Default,
+    {
+        Ok(SgMap {
+            bst: SgTree::from_iter_with_policy(iter, policy)?,
+        })
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(3, "c");
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    ///
+    /// for (key, value) in map.iter() {
+    ///     println!("{}: {}", key, value);
+    /// }
+    ///
+    /// let (first_key, first_value) = map.iter().next().unwrap();
+    /// assert_eq!((*first_key, *first_value), (1, "a"));
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V, N, U, C> {
+        Iter::new(self)
+    }
+
+    /// Gets an iterator over the entries of the map in level order (breadth-first), with each entry's depth.
+    /// The root has depth `0`.
+    ///
+    /// Intended for diagnostics (e.g. visualizing balance) and level-ordered serialization.
+    /// Use [`iter`][crate::map::SgMap::iter] for sorted order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    /// map.insert(3, "c");
+    ///
+    /// let mut levels = map.iter_levels();
+    /// assert_eq!(levels.next(), Some((0, &2, &"b")));
+    /// assert_eq!(levels.next(), Some((1, &1, &"a")));
+    /// assert_eq!(levels.next(), Some((1, &3, &"c")));
+    /// assert_eq!(levels.next(), None);
+    /// ```
+    pub fn iter_levels(&self) -> Levels<'_, K, V, N, U, C> {
+        Levels::new(self)
+    }
+
+    /// Gets an iterator over the keys present in both `self` and `other`, in ascending order,
+    /// yielding each key with its value from both maps (an inner join).
+    ///
+    /// Walks both maps in lockstep, `O(n + m)` total, instead of `O(n log m)` for a lookup per key.
+    /// Maps may differ in value type and capacity. Keys are compared with `self`'s comparator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let temps: SgMap<u32, f32, 10> = [(100, 20.5), (200, 21.0), (300, 21.5)].iter().copied().collect();
+    /// let humidity: SgMap<u32, u8, 5> = [(200, 40), (300, 45), (400, 50)].iter().copied().collect();
+    ///
+    /// assert!(temps
+    ///     .join(&humidity)
+    ///     .eq([(&200, &21.0, &40), (&300, &21.5, &45)]));
+    /// ```
+    pub fn join<'a, V2, const M: usize>(
+        &'a self,
+        other: &'a SgMap<K, V2, M, U, C>,
+    ) -> Join<'a, K, V, V2, N, M, U, C> {
+        Join::new(self, other)
+    }
+
+    /// Gets an iterator over the keys of `self`, in ascending order, yielding each key with its value
+    /// and the matching value from `other`, if any (a left outer join).
+    ///
+    /// Walks both maps in lockstep, `O(n + m)` total. See [`join`][crate::map::SgMap::join].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let temps: SgMap<u32, f32, 10> = [(100, 20.5), (200, 21.0)].iter().copied().collect();
+    /// let humidity: SgMap<u32, u8, 5> = [(200, 40), (300, 45)].iter().copied().collect();
+    ///
+    /// assert!(temps
+    ///     .left_join(&humidity)
+    ///     .eq([(&100, &20.5, None), (&200, &21.0, Some(&40))]));
+    /// ```
+    pub fn left_join<'a, V2, const M: usize>(
+        &'a self,
+        other: &'a SgMap<K, V2, M, U, C>,
+    ) -> LeftJoin<'a, K, V, V2, N, M, U, C> {
+        LeftJoin::new(self, other)
+    }
+
+    /// Gets an iterator over the keys present in either `self` or `other`, in ascending order,
+    /// yielding each key with its value from either map, if any (a full outer join).
+    /// At least one of each pair of values is `Some`.
+    ///
+    /// Walks both maps in lockstep, `O(n + m)` total. See [`join`][crate::map::SgMap::join].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let temps: SgMap<u32, f32, 10> = [(100, 20.5), (200, 21.0)].iter().copied().collect();
+    /// let humidity: SgMap<u32, u8, 5> = [(200, 40), (300, 45)].iter().copied().collect();
+    ///
+    /// assert!(temps.full_join(&humidity).eq([
+    ///     (&100, Some(&20.5), None),
+    ///     (&200, Some(&21.0), Some(&40)),
+    ///     (&300, None, Some(&45)),
+    /// ]));
+    /// ```
+    pub fn full_join<'a, V2, const M: usize>(
+        &'a self,
+        other: &'a SgMap<K, V2, M, U, C>,
+    ) -> FullJoin<'a, K, V, V2, N, M, U, C> {
+        FullJoin::new(self, other)
+    }
+
+    /// Gets a mutable iterator over the entries of the map, sorted by key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 3);
+    ///
+    /// // Add 10 to the value if the key isn't "a"
+    /// for (key, value) in map.iter_mut() {
+    ///     if key != &"a" {
+    ///         *value += 10;
+    ///     }
+    /// }
+    ///
+    /// let (second_key, second_value) = map.iter().skip(1).next().unwrap();
+    /// assert_eq!((*second_key, *second_value), ("b", 12));
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, N, U> {
+        IterMut::new(self)
+    }
+
+    /// Splits the map's entries into two mutable iterators, both sorted by key:
+    /// keys less than `key` ("before"), and keys greater than or equal to it ("after").
+    /// The iterators borrow disjoint entries, so both can be used at once, e.g. moved to separate threads.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<_, _, 10> = (1..=6).map(|k| (k, 0)).collect();
+    ///
+    /// let (before, after) = map.split_range_mut(&4);
+    /// for ((_, lo), (_, hi)) in before.zip(after) {
+    ///     *lo -= 1;
+    ///     *hi += 1;
+    /// }
+    ///
+    /// assert!(map.values().eq([-1, -1, -1, 1, 1, 1].iter()));
+    /// ```
+    #[doc(alias = "split_at_mut")]
+    #[doc(alias = "get_disjoint_ranges_mut")]
+    pub fn split_range_mut<Q>(
+        &mut self,
+        key: &Q,
+    ) -> (IterMut<'_, K, V, N, U>, IterMut<'_, K, V, N, U>)
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let (lo_iter, hi_iter) = self.bst.split_iter_mut(key);
+        (
+            IterMut::from_tree_iter(lo_iter),
+            IterMut::from_tree_iter(hi_iter),
+        )
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove_entry(&1), Some((1, "a")));
+    /// assert_eq!(map.remove_entry(&1), None);
+    /// ```
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.remove_entry(key)
+    }
+
+    /// Creates an iterator that visits all pairs in ascending key order, and uses a closure to determine if a pair
+    /// should be removed.
+    ///
+    /// If the closure returns `true`, the pair is removed from the map and yielded.
+    /// If the closure returns `false`, or panics, the pair remains in the map and will not be yielded.
+    /// The closure may mutate values that are kept.
+    ///
+    /// If the returned iterator is not exhausted, e.g. because it is dropped without iterating
+    /// or the iteration short-circuits, then the remaining pairs will be retained.
+    /// Use [`retain`][crate::map::SgMap::retain] with a negated predicate if you do not need the returned iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<i32, i32, 10> = (0..8).map(|x| (x, x)).collect();
+    /// let evens: Vec<_> = map.extract_if(|k, _v| k % 2 == 0).collect();
+    /// let odds = map;
+    /// assert_eq!(evens, vec![(0, 0), (2, 2), (4, 4), (6, 6)]);
+    /// assert!(odds.into_iter().eq(vec![(1, 1), (3, 3), (5, 5), (7, 7)]));
+    /// ```
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, K, V, F, N, U, C>
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        ExtractIf::new(self, pred)
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all pairs `(k, v)` such that `f(&k, &mut v)` returns `false`.
+    /// The elements are visited in ascending key order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<i32, i32, 10> = (0..8).map(|x| (x, x*10)).collect();
+    /// // Keep only the elements with even-numbered keys.
+    /// map.retain(|&k, _| k % 2 == 0);
+    /// assert!(map.into_iter().eq(vec![(0, 0), (2, 20), (4, 40), (6, 60)]));
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.bst.retain(|k, v| f(k, v));
+    }
+
+    /// Keeps only the `len` smallest pairs, dropping the rest.
+    /// No-op if `len` is at least the map's current length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<i32, i32, 10> = (0..8).map(|x| (x, x*10)).collect();
+    /// map.truncate(3);
+    /// assert!(map.into_iter().eq(vec![(0, 0), (1, 10), (2, 20)]));
+    /// ```
+    pub fn truncate(&mut self, len: usize)
+    where
+        K: Ord,
+    {
+        self.bst.truncate(len);
+    }
+
+    /// Keeps only the `len` largest pairs, dropping the rest.
+    /// No-op if `len` is at least the map's current length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// // Bounded leaderboard, score to name
+    /// let mut top = SgMap::<u32, &str, 10>::new();
+    /// for (score, name) in [(80, "a"), (95, "b"), (70, "c"), (99, "d")] {
+    ///     top.insert(score, name);
+    ///     top.truncate_back(2);
+    /// }
+    /// assert!(top.into_iter().eq(vec![(95, "b"), (99, "d")]));
+    /// ```
+    pub fn truncate_back(&mut self, len: usize)
+    where
+        K: Ord,
+    {
+        self.bst.truncate_back(len);
+    }
+
+    /// Parallel [`retain`][crate::map::SgMap::retain]: the predicate is evaluated on multiple threads,
+    /// then all removals are merged into a single rebuild.
+    /// Unlike `retain`, the predicate may be called in any order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<i32, i32, 10> = (0..8).map(|x| (x, x*10)).collect();
+    /// // Keep only the elements with even-numbered keys.
+    /// map.par_retain(|&k, _| k % 2 == 0);
+    /// assert!(map.into_iter().eq(vec![(0, 0), (2, 20), (4, 40), (6, 60)]));
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_retain<F>(&mut self, f: F)
+    where
+        K: Send + Sync,
+        V: Send + Sync,
+        F: Fn(&K, &mut V) -> bool + Send + Sync,
+    {
+        self.bst.par_retain(f);
+    }
+
+    /// Calls a closure on each pair within a range, on multiple threads.
+    /// The closure may be called in any order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map: SgMap<i32, i32, 10> = (0..8).map(|x| (x, x)).collect();
+    /// map.par_range_for_each(2..5, |_, v| *v *= 10);
+    /// assert!(map.into_values().eq(vec![0, 1, 20, 30, 40, 5, 6, 7]));
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_range_for_each<T, R, F>(&mut self, range: R, f: F)
+    where
+        T: ?Sized,
+        C: Compare<T>,
+        K: Borrow<T> + Send + Sync,
+        V: Send,
+        R: RangeBounds<T>,
+        F: Fn(&K, &mut V) + Send + Sync,
+    {
+        self.bst.par_range_for_each(range, f);
+    }
+
+    /// Gets a parallel iterator over the entries of the map, in no particular order.
+    /// Takes `&self`, so unlike [`par_iter_mut`][crate::map::SgMap::par_iter_mut], the arena isn't sorted first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    /// use rayon::prelude::*;
+    ///
+    /// let map: SgMap<i32, i32, 10> = (0..8).map(|x| (x, x * 10)).collect();
+    /// assert_eq!(map.par_iter().map(|(_, v)| v).sum::<i32>(), 280);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = (&K, &V)>
+    where
+        K: Sync,
+        V: Sync,
+    {
+        self.bst.par_iter()
+    }
+
+    /// Gets a parallel mutable iterator over the entries of the map, sorted by key.
+    /// The iterator is indexed, so order-preserving adapters (e.g. `collect` into a `Vec`) see ascending key order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut map: SgMap<i32, i32, 10> = (0..8).map(|x| (x, x)).collect();
+    /// map.par_iter_mut().for_each(|(k, v)| *v += k);
+    /// assert!(map.into_values().eq(vec![0, 2, 4, 6, 8, 10, 12, 14]));
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_mut(&mut self) -> impl IndexedParallelIterator<Item = (&K, &mut V)>
+    where
+        K: Send + Sync,
+        V: Send,
+    {
+        self.bst.par_iter_mut()
+    }
+
+    /// Splits the collection into two at the given key. Returns everything after the given key,
+    /// including the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// a.insert(1, "a");
+    /// a.insert(2, "b");
+    /// a.insert(3, "c");
+    /// a.insert(17, "d");
+    /// a.insert(41, "e");
+    ///
+    /// let b = a.split_off(&3);
+    ///
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(b.len(), 3);
+    ///
+    /// assert_eq!(a[&1], "a");
+    /// assert_eq!(a[&2], "b");
+    ///
+    /// assert_eq!(b[&3], "c");
+    /// assert_eq!(b[&17], "d");
+    /// assert_eq!(b[&41], "e");
+    /// ```
+    pub fn split_off<Q>(&mut self, key: &Q) -> SgMap<K, V, N, U, C>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        SgMap {
+            bst: self.bst.split_off(key),
+        }
+    }
+
+    /// Moves everything after the given key, including the key, into `dest`.
+    /// Unlike [`split_off`][crate::map::SgMap::split_off], no new map is constructed (and returned on the stack),
+    /// so `dest` can be a pre-allocated or re-used instance.
+    ///
+    /// Existing pairs in `dest` are kept, unless overwritten by a moved pair with an equal key.
+    /// Returns `Err` if `dest` lacks capacity, in which case neither map is modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let mut a = SgMap::from([(1, "a"), (2, "b"), (3, "c"), (17, "d"), (41, "e")]);
+    /// let mut b = SgMap::new();
+    ///
+    /// assert_eq!(a.split_off_into(&3, &mut b), Ok(()));
+    /// assert!(a.into_iter().eq([(1, "a"), (2, "b")]));
+    /// assert!(b.iter().eq([(&3, &"c"), (&17, &"d"), (&41, &"e")]));
+    ///
+    /// // Not enough room
+    /// let mut c = SgMap::from([(0, "z"), (1, "y"), (2, "x"), (3, "w"), (4, "v")]);
+    /// assert_eq!(b.split_off_into(&0, &mut c), Err(SgError::StackCapacityExceeded));
+    /// assert_eq!(b.len(), 3);
+    /// ```
+    pub fn split_off_into<Q>(
+        &mut self,
+        key: &Q,
+        dest: &mut SgMap<K, V, N, U, C>,
+    ) -> Result<(), SgError>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.split_off_into(key, &mut dest.bst)
+    }
+
+    /// Splits the collection into two at the given key, returning everything after the given key
+    /// (including the key) in a new map of capacity `M`.
+    /// Unlike [`split_off`][crate::map::SgMap::split_off], the returned map can be smaller than this one.
+    ///
+    /// Returns `Err` if the split-off pairs don't fit in `M`, in which case this map is not modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// a.extend([(1, "a"), (2, "b"), (3, "c"), (17, "d"), (41, "e")]);
+    ///
+    /// // Not enough room
+    /// assert_eq!(a.try_split_off::<_, 2>(&3).unwrap_err(), SgError::StackCapacityExceeded);
+    /// assert_eq!(a.len(), 5);
+    ///
+    /// let b: SgMap<_, _, 3> = a.try_split_off(&3).unwrap();
+    /// assert!(a.into_iter().eq([(1, "a"), (2, "b")]));
+    /// assert!(b.into_iter().eq([(3, "c"), (17, "d"), (41, "e")]));
+    /// ```
+    pub fn try_split_off<Q, const M: usize>(
+        &mut self,
+        key: &Q,
+    ) -> Result<SgMap<K, V, M, U, C>, SgError>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        Ok(SgMap {
+            bst: self.bst.try_split_off(key)?,
+        })
+    }
+
+    /// Splits the collection into two at sorted position `n` (0-indexed), returning the pairs of rank `n` or greater
+    /// in a new map. The `n` smallest pairs are kept in this one.
+    /// If `n` is at least the length, the returned map is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut a = SgMap::from([(1, "a"), (2, "b"), (3, "c"), (17, "d"), (41, "e")]);
+    ///
+    /// // Keep the bottom half
+    /// let b = a.split_at_rank(a.len() / 2);
+    /// assert!(a.into_iter().eq([(1, "a"), (2, "b")]));
+    /// assert!(b.into_iter().eq([(3, "c"), (17, "d"), (41, "e")]));
+    /// ```
+    pub fn split_at_rank(&mut self, n: usize) -> SgMap<K, V, N, U, C>
+    where
+        K: Ord,
+    {
+        SgMap {
+            bst: self.bst.split_at_rank(n),
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove(&1), Some("a"));
+    /// assert_eq!(map.remove(&1), None);
+    /// ```
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.remove(key)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    ///
+    /// The supplied key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.get_key_value(&1), Some((&1, &"a")));
+    /// assert_eq!(map.get_key_value(&2), None);
+    /// ```
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get_key_value(key)
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
     /// assert_eq!(map.get(&1), Some(&"a"));
     /// assert_eq!(map.get(&2), None);
     /// ```
-    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get(key)
+    }
+
+    /// Returns a reference to the value corresponding to each key, in input order.
+    /// When keys arrive in ascending order, each search resumes from the previous one's path instead of the root,
+    /// so a sorted batch costs fewer comparisons than the equivalent [`get`][`SgMap::get`] calls.
+    /// Unsorted keys are still correct, each descending key just restarts from the root.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let map: SgMap<_, _, 10> = (1..=5).map(|k| (k, k * 10)).collect();
+    ///
+    /// let vals: Vec<_> = map.get_batch(&[1, 3, 4, 6]).collect();
+    /// assert_eq!(vals, [Some(&10), Some(&30), Some(&40), None]);
+    ///
+    /// let vals: Vec<_> = map.get_batch(&[5, 0, 2]).collect();
+    /// assert_eq!(vals, [Some(&50), None, Some(&20)]);
+    /// ```
+    pub fn get_batch<'a, Q, I>(&'a self, keys: I) -> impl Iterator<Item = Option<&'a V>> + 'a
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized + 'a,
+        C: Compare<Q>,
+        I: IntoIterator<Item = &'a Q>,
+        I::IntoIter: 'a,
+    {
+        self.bst.get_batch(keys)
+    }
+
+    // Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// if let Some(x) = map.get_mut(&1) {
+    ///     *x = "b";
+    /// }
+    /// assert_eq!(map[&1], "b");
+    /// ```
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get_mut(key)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key,
+    /// first inserting the value returned by `f` if the key isn't present.
+    ///
+    /// Unlike [`entry`][`SgMap::entry`], takes a single traversal of the tree.
+    /// If the key is present, `f` isn't called and the given key is dropped (the stored one is kept).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is full and the key isn't already present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// *map.get_or_insert_with("a", || 1) += 10;
+    /// assert_eq!(map["a"], 11);
+    ///
+    /// *map.get_or_insert_with("a", || unreachable!()) += 10;
+    /// assert_eq!(map["a"], 21);
+    /// ```
+    pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> &mut V
+    where
+        K: Ord,
+        F: FnOnce() -> V,
+    {
+        self.bst.get_or_insert_with(key, f).1
+    }
+
+    /// Insert a key-value pair into the map (as [`insert`][`SgMap::insert`]),
+    /// also returning a [`NodeHandle`][crate::NodeHandle] for O(1) access to the stored pair.
+    ///
+    /// Handles survive inserts and rebalancing, but *any* removal invalidates all of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// let (old, h) = map.insert_with_handle(1, "a");
+    /// assert_eq!(old, None);
+    ///
+    /// map.insert(2, "b");
+    /// *map.get_mut_by_handle(h).unwrap() = "z";
+    /// assert_eq!(map.get_by_handle(h), Some((&1, &"z")));
+    ///
+    /// map.remove(&2);
+    /// assert_eq!(map.get_by_handle(h), None);
+    /// ```
+    pub fn insert_with_handle(&mut self, key: K, val: V) -> (Option<V>, NodeHandle)
+    where
+        K: Ord,
+    {
+        self.bst.insert_with_handle(key, val)
+    }
+
+    /// Insert a key-value pair into the map (as [`try_insert`][`SgMap::try_insert`]),
+    /// also returning a [`NodeHandle`][crate::NodeHandle] for O(1) access to the stored pair.
+    /// Returns `Err` if the map's stack capacity is full and the key isn't already present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let mut map = SgMap::<_, _, 1>::new();
+    /// let (_, h) = map.try_insert_with_handle(1, "a").unwrap();
+    /// assert_eq!(map.try_insert_with_handle(2, "b"), Err(SgError::StackCapacityExceeded));
+    /// assert_eq!(map.try_insert_with_handle(1, "c"), Ok((Some("a"), h)));
+    /// ```
+    pub fn try_insert_with_handle(
+        &mut self,
+        key: K,
+        val: V,
+    ) -> Result<(Option<V>, NodeHandle), SgError>
+    where
+        K: Ord,
+    {
+        self.bst.try_insert_with_handle(key, val)
+    }
+
+    /// Append/prepend helper: insert a key-value pair (as [`insert_with_handle`][`SgMap::insert_with_handle`])
+    /// past the first or last pair, given a handle to that pair.
+    /// Pass the handle returned by the previous call: for increasing (or decreasing) input, each insert then compares
+    /// the new key against that end alone, instead of every key on the path from the root.
+    ///
+    /// * If `end` is live and its key equals `key`, the pair is replaced in place.
+    /// * If `end` is the last (or first) pair and `key` sorts after (or before) it, the pair is appended to that end.
+    ///   Comparisons are `O(1)`, but updating the tree's bookkeeping along that edge is still `O(log n)`.
+    /// * Otherwise, including a `key` that falls between existing keys or a stale `end`, this is a regular insert
+    ///   (full search from the root). There's no finger search from an interior pair.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is full and the key isn't already present.
+    /// See [`try_insert_at_end`][crate::map::SgMap::try_insert_at_end] for a fallible alternative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// let (_, mut end) = map.insert_with_handle(10, "a");
+    ///
+    /// // Mostly increasing, `25` falls back to a regular insert
+    /// for (k, v) in [(20, "b"), (30, "c"), (25, "d"), (40, "e")].iter().copied() {
+    ///     end = map.insert_at_end(end, k, v).1;
+    /// }
+    ///
+    /// assert!(map.keys().copied().eq([10, 20, 25, 30, 40]));
+    /// assert_eq!(map.get_by_handle(end), Some((&40, &"e")));
+    /// ```
+    #[doc(alias = "push_back")]
+    #[doc(alias = "push_front")]
+    pub fn insert_at_end(&mut self, end: NodeHandle, key: K, val: V) -> (Option<V>, NodeHandle)
+    where
+        K: Ord,
+    {
+        self.bst.insert_at_end(end, key, val)
+    }
+
+    /// Insert a key-value pair into the map (as [`insert_at_end`][`SgMap::insert_at_end`]).
+    /// Returns `Err` if the map's stack capacity is full and the key isn't already present.
+    /// A new key past the hinted end is rejected without searching the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let mut map = SgMap::<_, _, 2>::new();
+    /// let (_, h) = map.try_insert_with_handle(1, "a").unwrap();
+    /// let (_, h) = map.try_insert_at_end(h, 2, "b").unwrap();
+    /// assert_eq!(map.try_insert_at_end(h, 3, "c"), Err(SgError::StackCapacityExceeded));
+    /// assert_eq!(map.try_insert_at_end(h, 2, "d"), Ok((Some("b"), h)));
+    /// ```
+    pub fn try_insert_at_end(
+        &mut self,
+        end: NodeHandle,
+        key: K,
+        val: V,
+    ) -> Result<(Option<V>, NodeHandle), SgError>
+    where
+        K: Ord,
+    {
+        self.bst.try_insert_at_end(end, key, val)
+    }
+
+    /// Returns a [`NodeHandle`][crate::NodeHandle] to the pair corresponding to the key, for later O(1) access.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// let h = map.get_handle(&1).unwrap();
+    /// assert_eq!(map.get_by_handle(h), Some((&1, &"a")));
+    /// assert_eq!(map.get_handle(&2), None);
+    /// ```
+    pub fn get_handle<Q>(&self, key: &Q) -> Option<NodeHandle>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get_handle(key)
+    }
+
+    /// Returns the key-value pair referenced by a handle, without searching the tree.
+    /// Returns `None` if the handle has been invalidated by a removal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// let (_, h) = map.insert_with_handle(1, "a");
+    /// assert_eq!(map.get_by_handle(h), Some((&1, &"a")));
+    /// ```
+    pub fn get_by_handle(&self, handle: NodeHandle) -> Option<(&K, &V)> {
+        self.bst.get_by_handle(handle)
+    }
+
+    /// Returns a mutable reference to the value referenced by a handle, without searching the tree.
+    /// Returns `None` if the handle has been invalidated by a removal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// let (_, h) = map.insert_with_handle("counter", 0);
+    /// for _ in 0..3 {
+    ///     *map.get_mut_by_handle(h).unwrap() += 1;
+    /// }
+    /// assert_eq!(map["counter"], 3);
+    /// ```
+    pub fn get_mut_by_handle(&mut self, handle: NodeHandle) -> Option<&mut V> {
+        self.bst.get_mut_by_handle(handle)
+    }
+
+    /// Removes the key-value pair referenced by a handle, returning it.
+    /// Returns `None` if the handle has been invalidated by a removal.
+    /// On success, all other outstanding handles are invalidated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// let (_, h) = map.insert_with_handle(1, "a");
+    /// assert_eq!(map.remove_by_handle(h), Some((1, "a")));
+    /// assert_eq!(map.remove_by_handle(h), None);
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn remove_by_handle(&mut self, handle: NodeHandle) -> Option<(K, V)> {
+        self.bst.remove_by_handle(handle)
+    }
+
+    /// Returns mutable references to the values corresponding to each of `M` keys, in order.
+    /// Returns `None` if any key isn't present, or if any two keys are equal (references must be disjoint).
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert("alice", 100);
+    /// map.insert("bob", 50);
+    ///
+    /// // Transfer between two entries, no remove and re-insert needed
+    /// if let Some([from, to]) = map.get_many_mut(["alice", "bob"]) {
+    ///     *from -= 30;
+    ///     *to += 30;
+    /// }
+    /// assert_eq!(map["alice"], 70);
+    /// assert_eq!(map["bob"], 80);
+    ///
+    /// assert!(map.get_many_mut(["alice", "alice"]).is_none());
+    /// assert!(map.get_many_mut(["alice", "carol"]).is_none());
+    /// ```
+    #[doc(alias = "get_disjoint_mut")]
+    pub fn get_many_mut<Q, const M: usize>(&mut self, keys: [&Q; M]) -> Option<[&mut V; M]>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get_many_mut(keys)
+    }
+
+    /// Returns the key-value pair with the greatest key less than or equal to `key` (a.k.a. floor),
+    /// or `None` if every key is greater. Found in a single traversal.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let map = SgMap::from([(10, "a"), (20, "b"), (30, "c")]);
+    /// assert_eq!(map.get_prev(&20), Some((&20, &"b")));
+    /// assert_eq!(map.get_prev(&25), Some((&20, &"b")));
+    /// assert_eq!(map.get_prev(&5), None);
+    /// ```
+    #[doc(alias = "floor")]
+    pub fn get_prev<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get_prev(key)
+    }
+
+    /// Returns the key-value pair with the smallest key greater than or equal to `key` (a.k.a. ceiling),
+    /// or `None` if every key is smaller. Found in a single traversal.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let map = SgMap::from([(10, "a"), (20, "b"), (30, "c")]);
+    /// assert_eq!(map.get_next(&20), Some((&20, &"b")));
+    /// assert_eq!(map.get_next(&25), Some((&30, &"c")));
+    /// assert_eq!(map.get_next(&35), None);
+    /// ```
+    #[doc(alias = "ceiling")]
+    pub fn get_next<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get_next(key)
+    }
+
+    /// Returns the key-value pair at sorted position `n` (0-indexed), or `None` if `n >= len`.
+    ///
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let map = SgMap::from([(30, "c"), (10, "a"), (20, "b")]);
+    /// assert_eq!(map.get_index(0), Some((&10, &"a")));
+    /// assert_eq!(map.get_index(map.len() / 2), Some((&20, &"b")));
+    /// assert_eq!(map.get_index(3), None);
+    /// ```
+    pub fn get_index(&self, n: usize) -> Option<(&K, &V)> {
+        self.bst.get_index(n)
+    }
+
+    /// Returns the key and a mutable reference to the value at sorted position `n` (0-indexed),
+    /// or `None` if `n >= len`.
+    ///
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::from([(1, "a"), (2, "b")]);
+    /// if let Some((_, v)) = map.get_index_mut(1) {
+    ///     *v = "c";
+    /// }
+    /// assert_eq!(map[&2], "c");
+    /// ```
+    pub fn get_index_mut(&mut self, n: usize) -> Option<(&K, &mut V)> {
+        self.bst.get_index_mut(n)
+    }
+
+    /// Removes and returns the key-value pair at sorted position `n` (0-indexed), or `None` if `n >= len`.
+    ///
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::from([(30, "c"), (10, "a"), (20, "b")]);
+    /// assert_eq!(map.remove_nth(1), Some((20, "b")));
+    /// assert_eq!(map.remove_nth(2), None);
+    /// assert!(map.into_iter().eq([(10, "a"), (30, "c")]));
+    /// ```
+    pub fn remove_nth(&mut self, n: usize) -> Option<(K, V)> {
+        self.bst.remove_nth(n)
+    }
+
+    /// Returns a uniformly random key-value pair, or `None` if the map is empty.
+    ///
+    /// Samples a rank, so every pair is equally likely regardless of the tree's shape.
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(42);
+    /// let map = SgMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let (k, v) = map.choose(&mut rng).unwrap();
+    /// assert_eq!(map.get(k), Some(v));
+    /// assert_eq!(SgMap::<u8, u8, 1>::new().choose(&mut rng), None);
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn choose<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<(&K, &V)> {
+        self.bst.choose(rng)
+    }
+
+    /// Returns the key and a mutable reference to the value of a uniformly random pair,
+    /// or `None` if the map is empty.
+    ///
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut rng = SmallRng::seed_from_u64(42);
+    /// let mut map = SgMap::from([(1, 0), (2, 0), (3, 0)]);
+    ///
+    /// // Random audit, marking visited entries
+    /// for _ in 0..10 {
+    ///     if let Some((_, v)) = map.choose_mut(&mut rng) {
+    ///         *v += 1;
+    ///     }
+    /// }
+    /// assert_eq!(map.values().sum::<i32>(), 10);
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn choose_mut<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<(&K, &mut V)> {
+        self.bst.choose_mut(rng)
+    }
+
+    /// Returns the key-value pair at the sorted position picked by `rand_idx`, or `None` if the map is empty.
+    ///
+    /// `rand_idx` is called once, with the map's length, and should return a uniformly random index below it.
+    /// An out-of-range index returns `None`.
+    /// This is the form of `choose` that doesn't need the `rand` feature:
+    /// bring your own random number generator.
+    ///
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// // Toy xorshift, any source of random indexes will do
+    /// let mut state = 0x2545_f491_u32;
+    /// let mut next = || {
+    ///     state ^= state << 13;
+    ///     state ^= state >> 17;
+    ///     state ^= state << 5;
+    ///     state as usize
+    /// };
+    ///
+    /// let map = SgMap::from([(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// let (k, v) = map.choose_with(|len| next() % len).unwrap();
+    /// assert_eq!(map.get(k), Some(v));
+    /// assert_eq!(map.choose_with(|_| 3), None);
+    /// ```
+    pub fn choose_with<F: FnOnce(usize) -> usize>(&self, rand_idx: F) -> Option<(&K, &V)> {
+        self.bst.choose_with(rand_idx)
+    }
+
+    /// Returns the key and a mutable reference to the value at the sorted position picked by `rand_idx`,
+    /// or `None` if the map is empty.
+    ///
+    /// `rand_idx` is called as for [`choose_with`][crate::map::SgMap::choose_with].
+    ///
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::from([(1, 0), (2, 0), (3, 0)]);
+    ///
+    /// // Round-robin stands in for a random source here
+    /// for i in 0..9 {
+    ///     if let Some((_, v)) = map.choose_mut_with(|len| i % len) {
+    ///         *v += 1;
+    ///     }
+    /// }
+    /// assert!(map.values().all(|v| *v == 3));
+    /// ```
+    pub fn choose_mut_with<F: FnOnce(usize) -> usize>(
+        &mut self,
+        rand_idx: F,
+    ) -> Option<(&K, &mut V)> {
+        self.bst.choose_mut_with(rand_idx)
+    }
+
+    /// Returns the sorted position (0-indexed) of `key`, or `None` if the key isn't present.
+    /// The inverse of [`get_index`][crate::map::SgMap::get_index].
+    ///
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let map = SgMap::from([(30, "c"), (10, "a"), (20, "b")]);
+    /// assert_eq!(map.rank(&10), Some(0));
+    /// assert_eq!(map.rank(&30), Some(2));
+    /// assert_eq!(map.rank(&25), None);
+    /// ```
+    pub fn rank<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.rank(key)
+    }
+
+    /// Returns the sorted position (0-indexed) of the first key for which `pred` returns `false`,
+    /// or the map's length if there's no such key. Analogous to [`slice::partition_point`].
+    ///
+    /// The map is assumed to be partitioned according to `pred`: all keys for which it returns `true` precede
+    /// all keys for which it returns `false`. If not, the returned position is unspecified.
+    ///
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let map = SgMap::from([(30, "c"), (10, "a"), (20, "b")]);
+    /// assert_eq!(map.partition_point(|&k| k < 15), 1);
+    /// assert_eq!(map.partition_point(|&k| k < 100), 3);
+    /// assert_eq!(map.partition_point(|_| false), 0);
+    /// ```
+    pub fn partition_point<F>(&self, pred: F) -> usize
+    where
+        F: FnMut(&K) -> bool,
+    {
+        self.bst.partition_point(pred)
+    }
+
+    /// Returns the key-value pair with the first key for which `pred` returns `false`,
+    /// or `None` if there's no such key. Found in a single traversal.
+    ///
+    /// The map is assumed to be partitioned according to `pred`, as per
+    /// [`partition_point`][crate::map::SgMap::partition_point]. If not, the returned pair is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// // Keys ordered by (major, minor) version
+    /// let map = SgMap::from([((1, 2), "a"), ((2, 0), "b"), ((2, 5), "c"), ((3, 1), "d")]);
+    ///
+    /// // First release of major version 2, without constructing a bounding key
+    /// assert_eq!(map.get_partition_point(|&(major, _)| major < 2), Some((&(2, 0), &"b")));
+    /// assert_eq!(map.get_partition_point(|&(major, _)| major < 4), None);
+    /// ```
+    pub fn get_partition_point<F>(&self, pred: F) -> Option<(&K, &V)>
+    where
+        F: FnMut(&K) -> bool,
+    {
+        self.bst.get_partition_point(pred)
+    }
+
+    /// Exchanges the values of two existing keys.
+    /// Returns `true` if both keys are present, otherwise returns `false` and leaves the map unmodified.
+    ///
+    /// Unlike calling [`get_mut`][crate::map::SgMap::get_mut] twice, this borrow-checks.
+    /// Unlike removing and re-inserting, the tree structure isn't modified and no values are cloned.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    ///
+    /// assert!(map.swap_values(&1, &2));
+    /// assert_eq!(map[&1], "b");
+    /// assert_eq!(map[&2], "a");
+    ///
+    /// assert!(!map.swap_values(&1, &3));
+    /// assert_eq!(map[&1], "b");
+    /// ```
+    pub fn swap_values<Q>(&mut self, key_a: &Q, key_b: &Q) -> bool
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.swap_values(key_a, key_b)
+    }
+
+    /// Moves the value at `old_key` to `new_key`, as a single operation.
+    ///
+    /// Returns `Err(SgError::KeyNotFound)` if `old_key` isn't present,
+    /// or `Err(SgError::KeyAlreadyExists)` if `new_key` is present (and not equal to `old_key`).
+    /// On `Err` the map is left unmodified, so the value is never lost.
+    /// Renaming can't exceed capacity: the old entry's slot is re-used.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let mut map = SgMap::<_, _, 2>::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    ///
+    /// assert_eq!(map.rename_key(&1, 3), Ok(()));
+    /// assert!(map.into_iter().eq([(2, "b"), (3, "a")]));
+    ///
+    /// let mut map = SgMap::<_, _, 2>::from([(1, "a"), (2, "b")]);
+    /// assert_eq!(map.rename_key(&1, 2), Err(SgError::KeyAlreadyExists));
+    /// assert_eq!(map.rename_key(&3, 4), Err(SgError::KeyNotFound));
+    /// assert!(map.into_iter().eq([(1, "a"), (2, "b")]));
+    /// ```
+    pub fn rename_key<Q>(&mut self, old_key: &Q, new_key: K) -> Result<(), SgError>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.rename_key(old_key, new_key)
+    }
+
+    /// Clears the map, returning all key-value pairs as an iterator, in ascending key order.
+    ///
+    /// The map is empty as soon as this method returns, even if the iterator is dropped or leaked before being fully
+    /// consumed. Pairs not yet yielded are dropped with the iterator.
+    ///
+    /// Unlike [`clear`][crate::map::SgMap::clear], which starts over with a fresh tree, draining keeps the map's
+    /// existing storage: nodes are released one at a time and their arena slots reused by later inserts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// a.insert(2, "b");
+    /// a.insert(1, "a");
+    ///
+    /// let v: Vec<_> = a.drain().collect();
+    /// assert_eq!(v, [(1, "a"), (2, "b")]);
+    /// assert!(a.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, K, V, N, U, C> {
+        Drain::new(self)
+    }
+
+    /// Clears the map, removing all elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// a.insert(1, "a");
+    /// a.clear();
+    /// assert!(a.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.bst.clear()
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.contains_key(&1), true);
+    /// assert_eq!(map.contains_key(&2), false);
+    /// ```
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.contains_key(key)
+    }
+
+    /// Returns `true` if the map contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// assert!(a.is_empty());
+    /// a.insert(1, "a");
+    /// assert!(!a.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.bst.is_empty()
+    }
+
+    /// Returns `true` if the map's capacity is filled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut a = SgMap::<_, _, 2>::new();
+    /// a.insert(1, "a");
+    /// assert!(!a.is_full());
+    /// a.insert(2, "b");
+    /// assert!(a.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.bst.is_full()
+    }
+
+    /// Returns a reference to the first key-value pair in the map.
+    /// The key in this pair is the minimum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// assert_eq!(map.first_key_value(), None);
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.first_key_value(), Some((&1, &"b")));
+    /// ```
+    pub fn first_key_value(&self) -> Option<(&K, &V)>
+    where
+        K: Ord,
+    {
+        self.bst.first_key_value()
+    }
+
+    /// Returns a reference to the first/minium key in the map, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// assert_eq!(map.first_key_value(), None);
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.first_key(), Some(&1));
+    /// ```
+    pub fn first_key(&self) -> Option<&K>
+    where
+        K: Ord,
+    {
+        self.bst.first_key()
+    }
+
+    /// Removes and returns the first element in the map.
+    /// The key of this element is the minimum key that was in the map.
+    ///
+    /// # Examples
+    ///
+    /// Draining elements in ascending order, while keeping a usable map each iteration.
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// while let Some((key, _val)) = map.pop_first() {
+    ///     assert!((&map).into_iter().all(|(k, _v)| *k > key));
+    /// }
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn pop_first(&mut self) -> Option<(K, V)>
+    where
+        K: Ord,
+    {
+        self.bst.pop_first()
+    }
+
+    /// Returns a reference to the last key-value pair in the map.
+    /// The key in this pair is the maximum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.last_key_value(), Some((&2, &"a")));
+    /// ```
+    pub fn last_key_value(&self) -> Option<(&K, &V)>
+    where
+        K: Ord,
+    {
+        self.bst.last_key_value()
+    }
+
+    /// Returns a reference to the last/maximum key in the map, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.last_key(), Some(&2));
+    /// ```
+    pub fn last_key(&self) -> Option<&K>
+    where
+        K: Ord,
+    {
+        self.bst.last_key()
+    }
+
+    /// Removes and returns the last element in the map.
+    /// The key of this element is the maximum key that was in the map.
+    ///
+    /// # Examples
+    ///
+    /// Draining elements in descending order, while keeping a usable map each iteration.
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// while let Some((key, _val)) = map.pop_last() {
+    ///     assert!((&map).into_iter().all(|(k, _v)| *k < key));
+    /// }
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn pop_last(&mut self) -> Option<(K, V)>
     where
-        K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        K: Ord,
     {
-        self.bst.get(key)
+        self.bst.pop_last()
     }
 
-    // Returns a mutable reference to the value corresponding to the key.
+    /// Returns the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut a = SgMap::<_, _, 10>::new();
+    /// assert_eq!(a.len(), 0);
+    /// a.insert(1, "a");
+    /// assert_eq!(a.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.bst.len()
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place manipulation.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut count = SgMap::<&str, usize, 10>::new();
+    ///
+    /// // count the number of occurrences of letters in the vec
+    /// for x in vec!["a", "b", "a", "c", "a", "b"] {
+    ///     *count.entry(x).or_insert(0) += 1;
+    /// }
+    ///
+    /// assert_eq!(count["a"], 3);
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, N, U, C> {
+        use crate::tree::node::NodeGetHelper;
+
+        let ngh: NodeGetHelper<U> = self.bst.priv_get(None, &key);
+        match ngh.node_idx() {
+            Some(node_idx) => Entry::Occupied(OccupiedEntry {
+                node_idx,
+                table: self,
+            }),
+            None => Entry::Vacant(VacantEntry { key, table: self }),
+        }
+    }
+
+    /// Gets the given borrowed key's corresponding entry in the map for in-place manipulation.
+    ///
+    /// Unlike [`entry`][`SgMap::entry`], no owned key is needed up front:
+    /// it's built from the borrowed one (via [`From`]) only if a vacant entry is inserted into.
+    /// So, for example, a `String`-keyed map doesn't allocate when the key is already present.
     ///
     /// The key may be any borrowed form of the map's key type, but the ordering
     /// on the borrowed form *must* match the ordering on the key type.
@@ -690,41 +2866,68 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert(1, "a");
-    /// if let Some(x) = map.get_mut(&1) {
-    ///     *x = "b";
+    /// let mut count = SgMap::<String, usize, 10>::new();
+    ///
+    /// // Only the first occurrence of each word allocates
+    /// for x in "a b a c a b".split(' ') {
+    ///     *count.entry_ref(x).or_insert(0) += 1;
     /// }
-    /// assert_eq!(map[&1], "b");
+    ///
+    /// assert_eq!(count["a"], 3);
+    /// assert_eq!(count.len(), 3);
     /// ```
-    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    pub fn entry_ref<'a, 'b, Q>(&'a mut self, key: &'b Q) -> EntryRef<'a, 'b, K, Q, V, N, U, C>
     where
-        K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        K: Borrow<Q> + From<&'b Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
-        self.bst.get_mut(key)
+        use crate::tree::node::NodeGetHelper;
+
+        let ngh: NodeGetHelper<U> = self.bst.priv_get(None, key);
+        match ngh.node_idx() {
+            Some(node_idx) => EntryRef::Occupied(OccupiedEntry {
+                node_idx,
+                table: self,
+            }),
+            None => EntryRef::Vacant(VacantEntryRef { key, table: self }),
+        }
     }
 
-    /// Clears the map, removing all elements.
+    /// Returns the first entry in the map for in-place manipulation.
+    /// The key of this entry is the minimum key in the map.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut a = SgMap::<_, _, 10>::new();
-    /// a.insert(1, "a");
-    /// a.clear();
-    /// assert!(a.is_empty());
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// if let Some(mut entry) = map.first_entry() {
+    ///     if *entry.key() > 0 {
+    ///         entry.insert("first");
+    ///     }
+    /// }
+    /// assert_eq!(*map.get(&1).unwrap(), "first");
+    /// assert_eq!(*map.get(&2).unwrap(), "b");
     /// ```
-    pub fn clear(&mut self) {
-        self.bst.clear()
+    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, N, U, C>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let node_idx = self.bst.min_idx;
+        Some(OccupiedEntry {
+            node_idx,
+            table: self,
+        })
     }
 
-    /// Returns `true` if the map contains a value for the specified key.
+    /// Returns the last entry in the map for in-place manipulation.
+    /// The key of this entry is the maximum key in the map.
     ///
-    /// The key may be any borrowed form of the map's key type, but the ordering
-    /// on the borrowed form *must* match the ordering on the key type.
     /// # Examples
     ///
     /// ```
@@ -732,380 +2935,558 @@ impl<K: Ord + Default, V: Default, const N: usize> SgMap<K, V, N> {
     ///
     /// let mut map = SgMap::<_, _, 10>::new();
     /// map.insert(1, "a");
-    /// assert_eq!(map.contains_key(&1), true);
-    /// assert_eq!(map.contains_key(&2), false);
+    /// map.insert(2, "b");
+    /// if let Some(mut entry) = map.last_entry() {
+    ///     if *entry.key() > 0 {
+    ///         entry.insert("last");
+    ///     }
+    /// }
+    /// assert_eq!(*map.get(&1).unwrap(), "a");
+    /// assert_eq!(*map.get(&2).unwrap(), "last");
     /// ```
-    pub fn contains_key<Q>(&self, key: &Q) -> bool
-    where
-        K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
-    {
-        self.bst.contains_key(key)
+    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, N, U, C>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let node_idx = self.bst.max_idx;
+        Some(OccupiedEntry {
+            node_idx,
+            table: self,
+        })
     }
 
-    /// Returns `true` if the map contains no elements.
+    /// Constructs a double-ended iterator over a sub-range of elements in the map.
+    /// The simplest way is to use the range syntax `min..max`, thus `range(min..max)` will
+    /// yield elements from min (inclusive) to max (exclusive).
+    /// The range may also be entered as `(Bound<T>, Bound<T>)`, so for example
+    /// `range((Excluded(4), Included(10)))` will yield a left-exclusive, right-inclusive
+    /// range from 4 to 10.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
     ///
     /// # Examples
     ///
+    /// Basic usage:
+    ///
     /// ```
     /// use scapegoat::SgMap;
+    /// use core::ops::Bound::Included;
     ///
-    /// let mut a = SgMap::<_, _, 10>::new();
-    /// assert!(a.is_empty());
-    /// a.insert(1, "a");
-    /// assert!(!a.is_empty());
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert(3, "a");
+    /// map.insert(5, "b");
+    /// map.insert(8, "c");
+    /// for (&key, &value) in map.range((Included(&4), Included(&8))) {
+    ///     println!("{}: {}", key, value);
+    /// }
+    /// assert_eq!(Some((&5, &"b")), map.range(4..).next());
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.bst.is_empty()
+    pub fn range<T, R>(&self, range: R) -> Range<'_, K, V, N, U, C>
+    where
+        T: ?Sized,
+        C: Compare<T>,
+        K: Borrow<T> + Ord,
+        R: RangeBounds<T>,
+    {
+        self.bst.assert_valid_range(&range);
+        Range {
+            table: self,
+            node_idx_iter: self.bst.range_search(&range).into_iter(),
+        }
     }
 
-    /// Returns `true` if the map's capacity is filled.
+    /// Constructs a mutable single-ended iterator over a sub-range of elements in the map.
+    /// The simplest way is to use the range syntax `min..max`, thus `range(min..max)` will
+    /// yield elements from min (inclusive) to max (exclusive).
+    /// The range may also be entered as `(Bound<T>, Bound<T>)`, so for example
+    /// `range((Excluded(4), Included(10)))` will yield a left-exclusive, right-inclusive
+    /// range from 4 to 10.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
     ///
     /// # Examples
     ///
+    /// Basic usage:
+    ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut a = SgMap::<_, _, 2>::new();
-    /// a.insert(1, "a");
-    /// assert!(!a.is_full());
-    /// a.insert(2, "b");
-    /// assert!(a.is_full());
+    /// let mut map: SgMap<_, _, 10> = ["Alice", "Bob", "Carol", "Cheryl"]
+    ///     .iter()
+    ///     .map(|&s| (s, 0))
+    ///     .collect();
+    /// for (_, balance) in map.range_mut("B".."Cheryl") {
+    ///     *balance += 100;
+    /// }
+    /// for (name, balance) in &map {
+    ///     println!("{} => {}", name, balance);
+    /// }
+    ///
+    /// assert_eq!(map["Alice"], 0);
+    /// assert_eq!(map["Bob"], 100);
     /// ```
-    pub fn is_full(&self) -> bool {
-        self.bst.is_full()
+    pub fn range_mut<T, R>(&mut self, range: R) -> RangeMut<'_, K, V, N, U>
+    where
+        T: ?Sized,
+        C: Compare<T>,
+        K: Borrow<T> + Ord,
+        R: RangeBounds<T>,
+    {
+        self.bst.assert_valid_range(&range);
+        RangeMut::new(self, &range)
     }
 
-    /// Returns a reference to the first key-value pair in the map.
-    /// The key in this pair is the minimum key in the map.
+    /// Constructs a double-ended iterator over the map's entries, starting at the first key greater than or equal to `key`.
+    /// Shorthand for `range((Bound::Included(key), Bound::Unbounded))`, e.g. to resume a paged scan from the last-seen key.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// assert_eq!(map.first_key_value(), None);
-    /// map.insert(1, "b");
-    /// map.insert(2, "a");
-    /// assert_eq!(map.first_key_value(), Some((&1, &"b")));
+    /// let map: SgMap<_, _, 10> = (1..=8).map(|k| (k * 10, k)).collect();
+    ///
+    /// // Pages of three, resuming after the last-seen key
+    /// let page: Vec<_> = map.iter_from(&0).take(3).map(|(k, _)| *k).collect();
+    /// assert_eq!(page, [10, 20, 30]);
+    /// let page: Vec<_> = map.iter_from(&31).take(3).map(|(k, _)| *k).collect();
+    /// assert_eq!(page, [40, 50, 60]);
     /// ```
-    pub fn first_key_value(&self) -> Option<(&K, &V)>
+    #[doc(alias = "seek")]
+    pub fn iter_from<Q>(&self, key: &Q) -> Range<'_, K, V, N, U, C>
     where
-        K: Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+        K: Borrow<Q> + Ord,
     {
-        self.bst.first_key_value()
+        self.range::<Q, _>((Bound::Included(key), Bound::Unbounded))
     }
 
-    /// Returns a reference to the first/minium key in the map, if any.
+    /// Constructs a mutable iterator over the map's entries, starting at the first key greater than or equal to `key`.
+    /// Shorthand for `range_mut((Bound::Included(key), Bound::Unbounded))`.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// assert_eq!(map.first_key_value(), None);
-    /// map.insert(1, "b");
-    /// map.insert(2, "a");
-    /// assert_eq!(map.first_key(), Some(&1));
+    /// let mut map: SgMap<_, _, 10> = (1..=4).map(|k| (k, 0)).collect();
+    ///
+    /// for (_, v) in map.iter_mut_from(&3) {
+    ///     *v += 1;
+    /// }
+    ///
+    /// assert!(map.values().eq([0, 0, 1, 1].iter()));
     /// ```
-    pub fn first_key(&self) -> Option<&K>
+    pub fn iter_mut_from<Q>(&mut self, key: &Q) -> RangeMut<'_, K, V, N, U>
     where
-        K: Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+        K: Borrow<Q> + Ord,
     {
-        self.bst.first_key()
+        self.range_mut::<Q, _>((Bound::Included(key), Bound::Unbounded))
     }
 
-    /// Removes and returns the first element in the map.
-    /// The key of this element is the minimum key that was in the map.
+    /// Returns a [`Cursor`][crate::map_types::Cursor] pointing at the gap before the smallest key above the given bound.
     ///
-    /// # Examples
+    /// Passing `Bound::Included(x)` yields a cursor whose [`peek_next`][crate::map_types::Cursor::peek_next]
+    /// is the first entry with key `>= x`. Passing `Bound::Excluded(x)` yields one for key `> x`.
+    /// Passing `Bound::Unbounded` yields a cursor before the first entry.
     ///
-    /// Draining elements in ascending order, while keeping a usable map each iteration.
+    /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
+    /// use core::ops::Bound;
     ///
     /// let mut map = SgMap::<_, _, 10>::new();
     /// map.insert(1, "a");
     /// map.insert(2, "b");
-    /// while let Some((key, _val)) = map.pop_first() {
-    ///     assert!((&map).into_iter().all(|(k, _v)| *k > key));
-    /// }
-    /// assert!(map.is_empty());
+    /// map.insert(3, "c");
+    /// map.insert(4, "d");
+    ///
+    /// let cursor = map.lower_bound(Bound::Included(&2));
+    /// assert_eq!(cursor.peek_prev(), Some((&1, &"a")));
+    /// assert_eq!(cursor.peek_next(), Some((&2, &"b")));
+    ///
+    /// let mut cursor = map.lower_bound(Bound::Excluded(&2));
+    /// assert_eq!(cursor.peek_prev(), Some((&2, &"b")));
+    /// assert_eq!(cursor.next(), Some((&3, &"c")));
+    /// assert_eq!(cursor.peek_prev(), Some((&3, &"c")));
     /// ```
-    pub fn pop_first(&mut self) -> Option<(K, V)>
+    pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V, N, U, C>
     where
-        K: Ord,
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
-        self.bst.pop_first()
+        Cursor::new(TreeCursor::lower_bound(&self.bst, bound))
     }
 
-    /// Returns a reference to the last key-value pair in the map.
-    /// The key in this pair is the maximum key in the map.
+    /// Returns a [`Cursor`][crate::map_types::Cursor] pointing at the gap after the largest key below the given bound.
+    ///
+    /// Passing `Bound::Included(x)` yields a cursor whose [`peek_prev`][crate::map_types::Cursor::peek_prev]
+    /// is the last entry with key `<= x`. Passing `Bound::Excluded(x)` yields one for key `< x`.
+    /// Passing `Bound::Unbounded` yields a cursor after the last entry.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
+    /// use core::ops::Bound;
     ///
     /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert(1, "b");
-    /// map.insert(2, "a");
-    /// assert_eq!(map.last_key_value(), Some((&2, &"a")));
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(3, "c");
+    /// map.insert(4, "d");
+    ///
+    /// let cursor = map.upper_bound(Bound::Included(&3));
+    /// assert_eq!(cursor.peek_prev(), Some((&3, &"c")));
+    /// assert_eq!(cursor.peek_next(), Some((&4, &"d")));
+    ///
+    /// let mut cursor = map.upper_bound(Bound::Excluded(&3));
+    /// assert_eq!(cursor.peek_prev(), Some((&2, &"b")));
+    /// assert_eq!(cursor.prev(), Some((&2, &"b")));
+    /// assert_eq!(cursor.peek_next(), Some((&2, &"b")));
     /// ```
-    pub fn last_key_value(&self) -> Option<(&K, &V)>
+    pub fn upper_bound<Q>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V, N, U, C>
     where
-        K: Ord,
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
-        self.bst.last_key_value()
+        Cursor::new(TreeCursor::upper_bound(&self.bst, bound))
     }
 
-    /// Returns a reference to the last/maximum key in the map, if any.
+    /// Returns a [`CursorMut`][crate::map_types::CursorMut] pointing at the entry with the smallest key above the given bound,
+    /// or at the ghost non-entry if there's no such key.
+    ///
+    /// Passing `Bound::Included(x)` points at the first entry with key `>= x`, `Bound::Excluded(x)` at the first with key `> x`.
+    /// Passing `Bound::Unbounded` points at the first entry.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
+    /// use core::iter::FromIterator;
+    /// use core::ops::Bound;
+    ///
+    /// let mut map = SgMap::<_, _, 10>::from_iter([(1, "a"), (2, "b"), (4, "d")]);
+    ///
+    /// // Scan and edit in a single pass
+    /// let mut cursor = map.lower_bound_mut(Bound::Included(&2));
+    /// assert_eq!(cursor.current(), Some((&2, &mut "b")));
+    /// assert_eq!(cursor.insert_after(3, "c"), Ok(()));
+    /// assert_eq!(cursor.remove_current(), Some((2, "b")));
+    /// assert_eq!(cursor.current(), Some((&3, &mut "c")));
+    /// cursor.move_prev();
+    /// if let Some((_, v)) = cursor.current() {
+    ///     *v = "A";
+    /// }
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert(1, "b");
-    /// map.insert(2, "a");
-    /// assert_eq!(map.last_key(), Some(&2));
+    /// assert!(map.into_iter().eq([(1, "A"), (3, "c"), (4, "d")]));
     /// ```
-    pub fn last_key(&self) -> Option<&K>
+    pub fn lower_bound_mut<Q>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, N, U, C>
     where
-        K: Ord,
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
-        self.bst.last_key()
+        CursorMut::new(TreeCursorMut::lower_bound(&mut self.bst, bound))
     }
 
-    /// Removes and returns the last element in the map.
-    /// The key of this element is the maximum key that was in the map.
+    /// Returns a [`CursorMut`][crate::map_types::CursorMut] pointing at the entry with the largest key below the given bound,
+    /// or at the ghost non-entry if there's no such key.
     ///
-    /// # Examples
+    /// Passing `Bound::Included(x)` points at the last entry with key `<= x`, `Bound::Excluded(x)` at the last with key `< x`.
+    /// Passing `Bound::Unbounded` points at the last entry.
     ///
-    /// Draining elements in descending order, while keeping a usable map each iteration.
+    /// # Examples
     ///
     /// ```
-    /// use scapegoat::SgMap;
+    /// use scapegoat::{SgMap, SgError};
+    /// use core::iter::FromIterator;
+    /// use core::ops::Bound;
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert(1, "a");
-    /// map.insert(2, "b");
-    /// while let Some((key, _val)) = map.pop_last() {
-    ///     assert!((&map).into_iter().all(|(k, _v)| *k < key));
-    /// }
-    /// assert!(map.is_empty());
+    /// let mut map = SgMap::<_, _, 10>::from_iter([(1, "a"), (3, "c")]);
+    ///
+    /// let mut cursor = map.upper_bound_mut(Bound::Excluded(&3));
+    /// assert_eq!(cursor.current(), Some((&1, &mut "a")));
+    /// assert_eq!(cursor.insert_before(2, "b"), Err(SgError::KeyOutOfOrder));
+    /// assert_eq!(cursor.insert_after(3, "c"), Err(SgError::KeyAlreadyExists));
+    /// assert_eq!(cursor.insert_after(2, "b"), Ok(()));
+    ///
+    /// // Wrap around through the ghost
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.current(), None);
+    /// assert_eq!(cursor.insert_after(0, "z"), Ok(()));
+    ///
+    /// assert!(map.keys().eq(&[0, 1, 2, 3]));
     /// ```
-    pub fn pop_last(&mut self) -> Option<(K, V)>
+    pub fn upper_bound_mut<Q>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, N, U, C>
     where
-        K: Ord,
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
-        self.bst.pop_last()
+        CursorMut::new(TreeCursorMut::upper_bound(&mut self.bst, bound))
     }
 
-    /// Returns the number of elements in the map.
+    /// Copies the pairs within a range into caller-provided storage, in ascending key order, without an intermediate collection.
+    /// Returns the number of pairs written, which is the smaller of the range's length and the slice's length.
+    ///
+    /// Slots `[0, written)` are initialized, the rest are left untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
+    /// use core::mem::MaybeUninit;
     ///
-    /// let mut a = SgMap::<_, _, 10>::new();
-    /// assert_eq!(a.len(), 0);
-    /// a.insert(1, "a");
-    /// assert_eq!(a.len(), 1);
+    /// let map = SgMap::from([(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+    /// let mut buf = [MaybeUninit::<(usize, char)>::uninit(); 8];
+    ///
+    /// let written = map.copy_range_to_slice(2.., &mut buf);
+    /// assert_eq!(written, 3);
+    ///
+    /// // SAFETY: the first `written` slots are initialized
+    /// let first = unsafe { buf[0].assume_init() };
+    /// assert_eq!(first, (2, 'b'));
     /// ```
-    pub fn len(&self) -> usize {
-        self.bst.len()
+    pub fn copy_range_to_slice<T, R>(&self, range: R, dst: &mut [MaybeUninit<(K, V)>]) -> usize
+    where
+        T: ?Sized,
+        C: Compare<T>,
+        K: Borrow<T> + Ord + Clone,
+        V: Clone,
+        R: RangeBounds<T>,
+    {
+        let mut written = 0;
+        for (slot, (k, v)) in dst.iter_mut().zip(self.range(range)) {
+            slot.write((k.clone(), v.clone()));
+            written += 1;
+        }
+
+        written
     }
 
-    /// Gets the given key's corresponding entry in the map for in-place manipulation.
+    /// Clones every pair into caller-provided storage, in ascending key order.
+    /// Returns the number of pairs written, always [`len`][crate::map::SgMap::len].
     ///
-    /// # Examples
+    /// Slots `[0, written)` are overwritten, the rest are left untouched.
+    /// If `dst` can't hold every pair, returns `Err(SgError::BufferTooSmall)` and writes nothing.
     ///
-    /// Basic usage:
+    /// # Examples
     ///
     /// ```
-    /// use scapegoat::SgMap;
-    ///
-    /// let mut count = SgMap::<&str, usize, 10>::new();
+    /// use scapegoat::{SgMap, SgError};
     ///
-    /// // count the number of occurrences of letters in the vec
-    /// for x in vec!["a", "b", "a", "c", "a", "b"] {
-    ///     *count.entry(x).or_insert(0) += 1;
-    /// }
+    /// let map = SgMap::from([(2, 'b'), (1, 'a'), (3, 'c')]);
+    /// let mut buf = [(0, ' '); 4];
     ///
-    /// assert_eq!(count["a"], 3);
+    /// assert_eq!(map.copy_to_slice(&mut buf), Ok(3));
+    /// assert_eq!(buf, [(1, 'a'), (2, 'b'), (3, 'c'), (0, ' ')]);
+    /// assert_eq!(map.copy_to_slice(&mut buf[..2]), Err(SgError::BufferTooSmall));
     /// ```
-    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, N> {
-        use crate::tree::node::NodeGetHelper;
-        use crate::tree::Idx;
+    pub fn copy_to_slice(&self, dst: &mut [(K, V)]) -> Result<usize, SgError>
+    where
+        K: Ord + Clone,
+        V: Clone,
+    {
+        if dst.len() < self.len() {
+            return Err(SgError::BufferTooSmall);
+        }
 
-        let ngh: NodeGetHelper<Idx> = self.bst.priv_get(None, &key);
-        match ngh.node_idx() {
-            Some(node_idx) => Entry::Occupied(OccupiedEntry {
-                node_idx,
-                table: self,
-            }),
-            None => Entry::Vacant(VacantEntry { key, table: self }),
+        for (slot, (k, v)) in dst.iter_mut().zip(self.iter()) {
+            *slot = (k.clone(), v.clone());
         }
+
+        Ok(self.len())
     }
 
-    /// Returns the first entry in the map for in-place manipulation.
-    /// The key of this entry is the minimum key in the map.
+    /// Consumes the map, returning its pairs as a `Vec`, in ascending key order.
+    /// Requires the `alloc` feature.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert(1, "a");
-    /// map.insert(2, "b");
-    /// if let Some(mut entry) = map.first_entry() {
-    ///     if *entry.key() > 0 {
-    ///         entry.insert("first");
-    ///     }
-    /// }
-    /// assert_eq!(*map.get(&1).unwrap(), "first");
-    /// assert_eq!(*map.get(&2).unwrap(), "b");
+    /// let map = SgMap::from([(2, 'b'), (1, 'a'), (3, 'c')]);
+    /// assert_eq!(map.into_sorted_vec(), [(1, 'a'), (2, 'b'), (3, 'c')]);
     /// ```
-    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, N>> {
-        if self.is_empty() {
-            return None;
-        }
+    #[cfg(feature = "alloc")]
+    pub fn into_sorted_vec(self) -> alloc::vec::Vec<(K, V)>
+    where
+        K: Ord,
+    {
+        let mut vec = alloc::vec::Vec::with_capacity(self.len());
+        vec.extend(self);
+        vec
+    }
 
-        let node_idx = self.bst.min_idx;
-        Some(OccupiedEntry {
-            node_idx,
-            table: self,
-        })
+    /// Size, in bytes, of the image [`write_raw_snapshot`][crate::SgMap::write_raw_snapshot] produces.
+    /// Requires the `bytemuck` feature.
+    #[cfg(feature = "bytemuck")]
+    pub fn raw_snapshot_len(&self) -> usize
+    where
+        K: Pod,
+        V: Pod,
+    {
+        self.bst.raw_snapshot_len()
     }
 
-    /// Returns the last entry in the map for in-place manipulation.
-    /// The key of this entry is the maximum key in the map.
+    /// Writes the map's internal storage to `dst` as a plain byte image, returning the number of bytes written.
+    /// The image can be stored anywhere (e.g. flash) and read back with [`from_raw_snapshot`][crate::SgMap::from_raw_snapshot].
+    /// Requires the `bytemuck` feature.
+    ///
+    /// If `dst` is shorter than [`raw_snapshot_len`][crate::SgMap::raw_snapshot_len],
+    /// returns `Err(SgError::BufferTooSmall)` and writes nothing.
+    /// The image is native-endian, only meant to be read back on the same target.
     ///
     /// # Examples
     ///
     /// ```
-    /// use scapegoat::SgMap;
+    /// use scapegoat::{SgMap, SgError};
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert(1, "a");
-    /// map.insert(2, "b");
-    /// if let Some(mut entry) = map.last_entry() {
-    ///     if *entry.key() > 0 {
-    ///         entry.insert("last");
-    ///     }
-    /// }
-    /// assert_eq!(*map.get(&1).unwrap(), "a");
-    /// assert_eq!(*map.get(&2).unwrap(), "last");
+    /// let map: SgMap<u32, u64, 10> = [(2, 20), (1, 10), (3, 30)].iter().copied().collect();
+    /// let mut buf = [0; 512];
+    ///
+    /// let len = map.write_raw_snapshot(&mut buf).unwrap();
+    /// assert_eq!(len, map.raw_snapshot_len());
+    /// assert_eq!(map.write_raw_snapshot(&mut buf[..len - 1]), Err(SgError::BufferTooSmall));
+    ///
+    /// let restored = SgMap::<u32, u64, 10>::from_raw_snapshot(&buf[..len]).unwrap();
+    /// assert_eq!(restored, map);
     /// ```
-    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, N>> {
-        if self.is_empty() {
-            return None;
-        }
-
-        let node_idx = self.bst.max_idx;
-        Some(OccupiedEntry {
-            node_idx,
-            table: self,
-        })
+    #[cfg(feature = "bytemuck")]
+    pub fn write_raw_snapshot(&self, dst: &mut [u8]) -> Result<usize, SgError>
+    where
+        K: Pod,
+        V: Pod,
+    {
+        self.bst.write_raw_snapshot(dst)
     }
 
-    /// Constructs a double-ended iterator over a sub-range of elements in the map.
-    /// The simplest way is to use the range syntax `min..max`, thus `range(min..max)` will
-    /// yield elements from min (inclusive) to max (exclusive).
-    /// The range may also be entered as `(Bound<T>, Bound<T>)`, so for example
-    /// `range((Excluded(4), Included(10)))` will yield a left-exclusive, right-inclusive
-    /// range from 4 to 10.
+    /// Reconstitutes a map from an image written by [`write_raw_snapshot`][crate::SgMap::write_raw_snapshot].
+    /// Requires the `bytemuck` feature.
     ///
-    /// # Panics
+    /// Internal storage is copied as-is, so no key is re-inserted and no rebalancing takes place.
+    /// The image is then validated, in `O(n)` time, before the map is returned.
+    /// `src` needn't be aligned, and any trailing bytes are ignored.
     ///
-    /// Panics if range `start > end`.
-    /// Panics if range `start == end` and both bounds are `Excluded`.
+    /// Returns `Err(SgError::InvalidSnapshot)` if the image is truncated or corrupt, was written for different
+    /// key or value sizes, needs more than `N` slots, or isn't ordered according to `C`.
     ///
     /// # Examples
     ///
-    /// Basic usage:
+    /// ```
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let map: SgMap<u16, u16, 10> = [(1, 1), (2, 4), (3, 9)].iter().copied().collect();
+    /// let mut buf = [0; 512];
+    /// let len = map.write_raw_snapshot(&mut buf).unwrap();
+    ///
+    /// // Truncated, or read back as another type
+    /// assert_eq!(
+    ///     SgMap::<u16, u16, 10>::from_raw_snapshot(&buf[..len - 1]),
+    ///     Err(SgError::InvalidSnapshot)
+    /// );
+    /// assert_eq!(
+    ///     SgMap::<u16, u32, 10>::from_raw_snapshot(&buf[..len]),
+    ///     Err(SgError::InvalidSnapshot)
+    /// );
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    pub fn from_raw_snapshot(src: &[u8]) -> Result<Self, SgError>
+    where
+        K: Pod,
+        V: Pod,
+        C: Default,
+    {
+        SgTree::from_raw_snapshot(src).map(|bst| SgMap { bst })
+    }
+
+    /// Makes a copy-on-write view of the map, able to hold up to `M` changes.
+    /// `O(1)`, nothing is copied: reads fall through to this map, writes stay private to the fork.
+    /// See [`SgMapFork`][crate::SgMapFork] for more.
+    ///
+    /// # Examples
     ///
     /// ```
     /// use scapegoat::SgMap;
-    /// use core::ops::Bound::Included;
     ///
-    /// let mut map = SgMap::<_, _, 10>::new();
-    /// map.insert(3, "a");
-    /// map.insert(5, "b");
-    /// map.insert(8, "c");
-    /// for (&key, &value) in map.range((Included(&4), Included(&8))) {
-    ///     println!("{}: {}", key, value);
-    /// }
-    /// assert_eq!(Some((&5, &"b")), map.range(4..).next());
+    /// let map = SgMap::from([(1, "a"), (2, "b")]);
+    /// let mut fork = map.fork::<4>();
+    ///
+    /// fork.insert(3, "c");
+    /// assert_eq!(fork.len(), 3);
+    /// assert_eq!(map.len(), 2);
     /// ```
-    pub fn range<T, R>(&self, range: R) -> Range<'_, K, V, N>
+    pub fn fork<const M: usize>(&self) -> SgMapFork<'_, K, V, N, M, U, C>
     where
-        T: Ord + ?Sized,
-        K: Borrow<T> + Ord,
-        R: RangeBounds<T>,
+        C: Clone,
     {
-        SgTree::<K, V, N>::assert_valid_range(&range);
-        Range {
-            table: self,
-            node_idx_iter: self.bst.range_search(&range).into_iter(),
-        }
+        SgMapFork::new(self)
     }
+}
 
-    /// Constructs a mutable single-ended iterator over a sub-range of elements in the map.
-    /// The simplest way is to use the range syntax `min..max`, thus `range(min..max)` will
-    /// yield elements from min (inclusive) to max (exclusive).
-    /// The range may also be entered as `(Bound<T>, Bound<T>)`, so for example
-    /// `range((Excluded(4), Included(10)))` will yield a left-exclusive, right-inclusive
-    /// range from 4 to 10.
-    ///
-    /// # Panics
+// Byte-ordered keys, where a prefix's matches are contiguous
+impl<K: Ord, V, const N: usize, U: ArenaIdx> SgMap<K, V, N, U, Natural> {
+    /// Constructs a double-ended iterator over all entries whose key starts with `prefix`, in ascending order.
+    /// Spares computing an exclusive end bound by hand (e.g. incrementing the last byte, with carry).
     ///
-    /// Panics if range `start > end`.
-    /// Panics if range `start == end` and both bounds are `Excluded`.
+    /// Only available with the default [`Natural`][crate::Natural] comparator, for keys ordered by their bytes:
+    /// `str`, `String`, `[u8]`, `Vec<u8>`, `[u8; M]`, and references to them.
+    /// For any other `K: AsRef<[u8]>`, the key's [`Ord`] *must* agree with lexicographic byte order.
     ///
     /// # Examples
     ///
-    /// Basic usage:
-    ///
     /// ```
     /// use scapegoat::SgMap;
     ///
-    /// let mut map: SgMap<_, _, 10> = ["Alice", "Bob", "Carol", "Cheryl"]
-    ///     .iter()
-    ///     .map(|&s| (s, 0))
-    ///     .collect();
-    /// for (_, balance) in map.range_mut("B".."Cheryl") {
-    ///     *balance += 100;
-    /// }
-    /// for (name, balance) in &map {
-    ///     println!("{} => {}", name, balance);
-    /// }
-    ///
-    /// assert_eq!(map["Alice"], 0);
-    /// assert_eq!(map["Bob"], 100);
-    /// ```
-    pub fn range_mut<T, R>(&mut self, range: R) -> RangeMut<'_, K, V, N>
+    /// let mut map = SgMap::<_, _, 10>::new();
+    /// map.insert("app", 1);
+    /// map.insert("apple", 2);
+    /// map.insert("apply", 3);
+    /// map.insert("apt", 4);
+    /// map.insert("b", 5);
+    ///
+    /// assert!(map.range_prefix("appl").eq([(&"apple", &2), (&"apply", &3)]));
+    /// assert_eq!(map.range_prefix("ap").count(), 4);
+    /// assert_eq!(map.range_prefix("").count(), 5);
+    /// assert_eq!(map.range_prefix("c").next(), None);
+    /// ```
+    #[doc(alias = "starts_with")]
+    pub fn range_prefix<P>(&self, prefix: &P) -> Range<'_, K, V, N, U>
     where
-        T: Ord + ?Sized,
-        K: Borrow<T> + Ord,
-        R: RangeBounds<T>,
+        K: AsRef<[u8]>,
+        P: AsRef<[u8]> + ?Sized,
     {
-        SgTree::<K, V, N>::assert_valid_range(&range);
-        RangeMut::new(self, &range)
+        Range {
+            table: self,
+            node_idx_iter: self.bst.prefix_search(prefix.as_ref()).into_iter(),
+        }
     }
 }
 
 // Convenience Traits --------------------------------------------------------------------------------------------------
 
 // Debug
-impl<K: Default, V: Default, const N: usize> Debug for SgMap<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Debug for SgMap<K, V, N, U, C>
 where
     K: Ord + Debug,
     V: Debug,
@@ -1115,8 +3496,8 @@ where
     }
 }
 
-// From array.
-impl<K: Default, V: Default, const N: usize> From<[(K, V); N]> for SgMap<K, V, N>
+// From array (default index width, so that `SgMap::from([...])` needs no annotation).
+impl<K, V, const N: usize> From<[(K, V); N]> for SgMap<K, V, N>
 where
     K: Ord,
 {
@@ -1130,7 +3511,8 @@ where
     ///
     /// ### Warning
     ///
-    /// [`TryFrom`](https://doc.rust-lang.org/stable/std/convert/trait.TryFrom.html) isn't implemented because it would collide with the blanket implementation.
+    /// [`TryFrom`](https://doc.rust-lang.org/stable/std/convert/trait.TryFrom.html) isn't implemented for arrays because it would collide with the blanket implementation.
+    /// For input whose length doesn't match `N`, convert from a slice instead: `SgMap::try_from(&arr[..])`.
     /// See [this open GitHub issue](https://github.com/rust-lang/rust/issues/50133#issuecomment-64690839) from 2018,
     /// this is a known Rust limitation that should be fixed via specialization in the future.
     #[doc(alias = "tryfrom")]
@@ -1142,10 +3524,11 @@ where
 }
 
 // Indexing
-impl<K: Default, V: Default, Q, const N: usize> Index<&Q> for SgMap<K, V, N>
+impl<K, V, Q, const N: usize, U: ArenaIdx, C: Compare<K>> Index<&Q> for SgMap<K, V, N, U, C>
 where
     K: Borrow<Q> + Ord,
-    Q: Ord + ?Sized,
+    Q: ?Sized,
+    C: Compare<Q>,
 {
     type Output = V;
 
@@ -1159,10 +3542,123 @@ where
     }
 }
 
+// Fallible conversion from slice, of any length.
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> TryFrom<&[(K, V)]> for SgMap<K, V, N, U, C>
+where
+    K: Ord + Clone,
+    V: Clone,
+    C: Default,
+{
+    type Error = SgError;
+
+    /// Clones the slice's contents into a new `SgMap`, keeping the last occurrence of duplicates.
+    /// Returns `Err(SgError::StackCapacityExceeded)` if capacity `N` is exceeded.
+    ///
+    /// ```
+    /// use core::convert::TryFrom;
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let map = SgMap::<_, _, 4>::try_from(&[(1, "a"), (2, "b")][..]).unwrap();
+    /// assert_eq!(map.len(), 2);
+    ///
+    /// let res = SgMap::<_, _, 1>::try_from(&[(1, "a"), (2, "b")][..]);
+    /// assert_eq!(res, Err(SgError::StackCapacityExceeded));
+    /// ```
+    fn try_from(slice: &[(K, V)]) -> Result<Self, Self::Error> {
+        SgMap::try_from_iter(slice.iter().cloned())
+    }
+}
+
+// Fallible conversion from vector, of any length.
+#[cfg(feature = "alloc")]
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> TryFrom<alloc::vec::Vec<(K, V)>>
+    for SgMap<K, V, N, U, C>
+where
+    K: Ord,
+    C: Default,
+{
+    type Error = SgError;
+
+    /// Moves the vector's contents into a new `SgMap`, keeping the last occurrence of duplicates.
+    /// Returns `Err(SgError::StackCapacityExceeded)` if capacity `N` is exceeded.
+    /// Requires the `alloc` feature.
+    ///
+    /// ```
+    /// use core::convert::TryFrom;
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let map = SgMap::<_, _, 4>::try_from(vec![(1, "a"), (2, "b")]).unwrap();
+    /// assert_eq!(map.len(), 2);
+    ///
+    /// let res = SgMap::<_, _, 1>::try_from(vec![(1, "a"), (2, "b")]);
+    /// assert_eq!(res, Err(SgError::StackCapacityExceeded));
+    /// ```
+    fn try_from(vec: alloc::vec::Vec<(K, V)>) -> Result<Self, Self::Error> {
+        SgMap::try_from_iter(vec.into_iter())
+    }
+}
+
+// Conversion into standard library counterpart.
+#[cfg(feature = "alloc")]
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> From<SgMap<K, V, N, U, C>>
+    for alloc::collections::BTreeMap<K, V>
+where
+    K: Ord,
+{
+    /// Moves the contents of a `SgMap` into a new `BTreeMap`.
+    /// Requires the `alloc` feature.
+    ///
+    /// Note the `BTreeMap` orders by `Ord`, not by the `SgMap`'s comparator, `C`.
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use scapegoat::SgMap;
+    ///
+    /// let sgm = SgMap::from([(2, "b"), (1, "a")]);
+    /// let btm = BTreeMap::from(sgm);
+    /// assert!(btm.into_iter().eq([(1, "a"), (2, "b")]));
+    /// ```
+    fn from(sg: SgMap<K, V, N, U, C>) -> Self {
+        sg.into_iter().collect()
+    }
+}
+
+// Fallible conversion from standard library counterpart.
+#[cfg(feature = "alloc")]
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> TryFrom<alloc::collections::BTreeMap<K, V>>
+    for SgMap<K, V, N, U, C>
+where
+    K: Ord,
+    C: Default,
+{
+    type Error = SgError;
+
+    /// Moves the contents of a `BTreeMap` into a new `SgMap`.
+    /// Returns `Err(SgError::StackCapacityExceeded)` if capacity `N` is exceeded.
+    /// Requires the `alloc` feature.
+    ///
+    /// ```
+    /// use core::convert::TryFrom;
+    /// use std::collections::BTreeMap;
+    /// use scapegoat::{SgMap, SgError};
+    ///
+    /// let btm = BTreeMap::from([(1, "a"), (2, "b")]);
+    /// let sgm = SgMap::<_, _, 4>::try_from(btm.clone()).unwrap();
+    /// assert!(sgm.into_iter().eq(btm.into_iter()));
+    ///
+    /// let btm = BTreeMap::from([(1, "a"), (2, "b")]);
+    /// assert_eq!(SgMap::<_, _, 1>::try_from(btm), Err(SgError::StackCapacityExceeded));
+    /// ```
+    fn try_from(map: alloc::collections::BTreeMap<K, V>) -> Result<Self, Self::Error> {
+        SgMap::try_from_iter(map.into_iter())
+    }
+}
+
 // Construct from iterator.
-impl<K: Default, V: Default, const N: usize> FromIterator<(K, V)> for SgMap<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> FromIterator<(K, V)> for SgMap<K, V, N, U, C>
 where
     K: Ord,
+    C: Default,
 {
     fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
         let mut sgm = SgMap::new();
@@ -1172,7 +3668,7 @@ where
 }
 
 // Extension from iterator.
-impl<K: Default, V: Default, const N: usize> Extend<(K, V)> for SgMap<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Extend<(K, V)> for SgMap<K, V, N, U, C>
 where
     K: Ord,
 {
@@ -1182,7 +3678,8 @@ where
 }
 
 // Extension from reference iterator.
-impl<'a, K: Default, V: Default, const N: usize> Extend<(&'a K, &'a V)> for SgMap<K, V, N>
+impl<'a, K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Extend<(&'a K, &'a V)>
+    for SgMap<K, V, N, U, C>
 where
     K: Ord + Copy,
     V: Copy,
@@ -1195,9 +3692,11 @@ where
 // General Iterators ---------------------------------------------------------------------------------------------------
 
 // Reference iterator
-impl<'a, K: Ord + Default, V: Default, const N: usize> IntoIterator for &'a SgMap<K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> IntoIterator
+    for &'a SgMap<K, V, N, U, C>
+{
     type Item = (&'a K, &'a V);
-    type IntoIter = Iter<'a, K, V, N>;
+    type IntoIter = Iter<'a, K, V, N, U, C>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
@@ -1205,9 +3704,9 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> IntoIterator for &'a SgMa
 }
 
 // Consuming iterator
-impl<K: Ord + Default, V: Default, const N: usize> IntoIterator for SgMap<K, V, N> {
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> IntoIterator for SgMap<K, V, N, U, C> {
     type Item = (K, V);
-    type IntoIter = IntoIter<K, V, N>;
+    type IntoIter = IntoIter<K, V, N, U, C>;
 
     fn into_iter(self) -> Self::IntoIter {
         IntoIter::new(self)
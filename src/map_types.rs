@@ -1,13 +1,16 @@
 use core::borrow::Borrow;
+use core::cmp::Ordering;
 use core::fmt;
 use core::iter::{FusedIterator, Peekable};
-use core::ops::RangeBounds;
+use core::ops::{Deref, DerefMut, RangeBounds};
 
-use tinyvec::ArrayVec;
+use crate::tree::StackVec;
 
 use crate::map::SgMap;
 use crate::tree::{
-    Idx, IntoIter as TreeIntoIter, Iter as TreeIter, IterMut as TreeIterMut, SmallNode,
+    range_contains, ArenaIdx, Compare, Cursor as TreeCursor, CursorMut as TreeCursorMut,
+    Drain as TreeDrain, ExtractIf as TreeExtractIf, IntoIter as TreeIntoIter, Iter as TreeIter,
+    IterMut as TreeIterMut, LevelIter as TreeLevelIter, Natural, SgError, SmallNode,
 };
 
 // General Iterators ---------------------------------------------------------------------------------------------------
@@ -17,244 +20,467 @@ use crate::tree::{
 /// This `struct` is created by the [`iter`][crate::map::SgMap::iter] method on [`SgMap`][crate::map::SgMap].
 /// documentation for more.
 ///
-pub struct Iter<'a, T: Ord + Default, V: Default, const N: usize> {
-    ref_iter: TreeIter<'a, T, V, N>,
+pub struct Iter<'a, T: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    ref_iter: TreeIter<'a, T, V, N, U, C>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iter<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iter<'a, K, V, N, U, C> {
     /// Construct reference iterator.
-    pub(crate) fn new(map: &'a SgMap<K, V, N>) -> Self {
+    pub(crate) fn new(map: &'a SgMap<K, V, N, U, C>) -> Self {
         Iter {
             ref_iter: TreeIter::new(&map.bst),
         }
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Iter<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for Iter<'a, K, V, N, U, C>
+{
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.ref_iter.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ref_iter.size_hint()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.ref_iter.last()
+    }
+
+    fn min(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        self.ref_iter.min()
+    }
+
+    fn max(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        self.ref_iter.max()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for Iter<'a, K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next_back()
+    }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for Iter<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for Iter<'a, K, V, N, U, C>
+{
     fn len(&self) -> usize {
         self.ref_iter.len()
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for Iter<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for Iter<'a, K, V, N, U, C>
+{
+}
 
 /// An owning iterator over the entries of a [`SgMap`][crate::map::SgMap].
 ///
 /// This `struct` is created by the [`into_iter`][crate::map::SgMap::into_iter] method on [`SgMap`][crate::map::SgMap].
 /// documentation for more.
-pub struct IntoIter<K: Ord + Default, V: Default, const N: usize> {
-    cons_iter: TreeIntoIter<K, V, N>,
+pub struct IntoIter<K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    cons_iter: TreeIntoIter<K, V, N, U, C>,
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> IntoIter<K, V, N> {
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> IntoIter<K, V, N, U, C> {
     /// Construct owning iterator.
-    pub(crate) fn new(map: SgMap<K, V, N>) -> Self {
+    pub(crate) fn new(map: SgMap<K, V, N, U, C>) -> Self {
         IntoIter {
             cons_iter: TreeIntoIter::new(map.bst),
         }
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> Iterator for IntoIter<K, V, N> {
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator for IntoIter<K, V, N, U, C> {
     type Item = (K, V);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.cons_iter.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cons_iter.size_hint()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.cons_iter.last()
+    }
+
+    fn min(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        self.cons_iter.min()
+    }
+
+    fn max(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        self.cons_iter.max()
+    }
+}
+
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for IntoIter<K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.cons_iter.next_back()
+    }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for IntoIter<K, V, N> {
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for IntoIter<K, V, N, U, C>
+{
     fn len(&self) -> usize {
         self.cons_iter.len()
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> FusedIterator for IntoIter<K, V, N> {}
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for IntoIter<K, V, N, U, C>
+{
+}
 
 /// An mutable iterator over the entries of a [`SgMap`][crate::map::SgMap].
 ///
 /// This `struct` is created by the [`iter_mut`][crate::map::SgMap::iter_mut] method on [`SgMap`][crate::map::SgMap].
 /// documentation for more.
-pub struct IterMut<'a, K: Ord + Default, V: Default, const N: usize> {
-    mut_iter: TreeIterMut<'a, K, V, N>,
+pub struct IterMut<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16> {
+    mut_iter: TreeIterMut<'a, K, V, N, U>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> IterMut<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> IterMut<'a, K, V, N, U> {
     /// Construct owning iterator.
-    pub(crate) fn new(map: &'a mut SgMap<K, V, N>) -> Self {
+    pub(crate) fn new<C: Compare<K>>(map: &'a mut SgMap<K, V, N, U, C>) -> Self {
         IterMut {
             mut_iter: TreeIterMut::new(&mut map.bst),
         }
     }
+
+    /// Wrap a tree-level mutable iterator.
+    pub(crate) fn from_tree_iter(mut_iter: TreeIterMut<'a, K, V, N, U>) -> Self {
+        IterMut { mut_iter }
+    }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for IterMut<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> Iterator for IterMut<'a, K, V, N, U> {
     type Item = (&'a K, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
         self.mut_iter.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.mut_iter.size_hint()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> DoubleEndedIterator for IterMut<'a, K, V, N, U> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.mut_iter.next_back()
+    }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for IterMut<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> ExactSizeIterator for IterMut<'a, K, V, N, U> {
     fn len(&self) -> usize {
         self.mut_iter.len()
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for IterMut<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> FusedIterator for IterMut<'a, K, V, N, U> {}
 
-// Key Iterators -------------------------------------------------------------------------------------------------------
+/// A level-order (breadth-first) iterator over the entries of a [`SgMap`][crate::map::SgMap], with each entry's depth.
+///
+/// This `struct` is created by the [`iter_levels`][crate::map::SgMap::iter_levels] method on [`SgMap`][crate::map::SgMap].
+/// See its documentation for more.
+pub struct Levels<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    level_iter: TreeLevelIter<'a, K, V, N, U, C>,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Levels<'a, K, V, N, U, C> {
+    /// Construct level-order iterator.
+    pub(crate) fn new(map: &'a SgMap<K, V, N, U, C>) -> Self {
+        Levels {
+            level_iter: TreeLevelIter::new(&map.bst),
+        }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for Levels<'a, K, V, N, U, C>
+{
+    type Item = (usize, &'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.level_iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.level_iter.size_hint()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for Levels<'a, K, V, N, U, C>
+{
+    fn len(&self) -> usize {
+        self.level_iter.len()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for Levels<'a, K, V, N, U, C>
+{
+}
 
-// TODO: these need more trait implementations for full compatibility
+// Key Iterators -------------------------------------------------------------------------------------------------------
 
 /// An iterator over the keys of a [`SgMap`][crate::map::SgMap].
 ///
 /// This `struct` is created by the [`keys`][crate::map::SgMap::keys] method on [`SgMap`][crate::map::SgMap].
 /// See its documentation for more.
-pub struct Keys<'a, K: Ord + Default, V: Default, const N: usize> {
-    pub(crate) inner: Iter<'a, K, V, N>,
+pub struct Keys<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    pub(crate) inner: Iter<'a, K, V, N, U, C>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Keys<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for Keys<'a, K, V, N, U, C>
+{
     type Item = &'a K;
 
     fn next(&mut self) -> Option<&'a K> {
         self.inner.next().map(|(k, _)| k)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn last(self) -> Option<&'a K> {
+        self.inner.last().map(|(k, _)| k)
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for Keys<'a, K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<&'a K> {
+        self.inner.next_back().map(|(k, _)| k)
+    }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for Keys<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for Keys<'a, K, V, N, U, C>
+{
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for Keys<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for Keys<'a, K, V, N, U, C>
+{
+}
 
 /// An owning iterator over the keys of a [`SgMap`][crate::map::SgMap].
 ///
 /// This `struct` is created by the [`into_keys`][crate::map::SgMap::into_keys] method on [`SgMap`][crate::map::SgMap].
 /// See its documentation for more.
-pub struct IntoKeys<K: Ord + Default, V: Default, const N: usize> {
-    pub(crate) inner: IntoIter<K, V, N>,
+pub struct IntoKeys<K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    pub(crate) inner: IntoIter<K, V, N, U, C>,
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> Iterator for IntoKeys<K, V, N> {
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator for IntoKeys<K, V, N, U, C> {
     type Item = K;
 
     fn next(&mut self) -> Option<K> {
         self.inner.next().map(|(k, _)| k)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn last(self) -> Option<K> {
+        self.inner.last().map(|(k, _)| k)
+    }
+}
+
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for IntoKeys<K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<K> {
+        self.inner.next_back().map(|(k, _)| k)
+    }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for IntoKeys<K, V, N> {
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for IntoKeys<K, V, N, U, C>
+{
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> FusedIterator for IntoKeys<K, V, N> {}
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for IntoKeys<K, V, N, U, C>
+{
+}
 
 // Value Iterators -----------------------------------------------------------------------------------------------------
 
-// TODO: these need more trait implementations for full compatibility
-
 /// An iterator over the values of a [`SgMap`][crate::map::SgMap].
 ///
 /// This `struct` is created by the [`values`][crate::map::SgMap::values] method on [`SgMap`][crate::map::SgMap].
 /// See its documentation for more.
-pub struct Values<'a, K: Ord + Default, V: Default, const N: usize> {
-    pub(crate) inner: Iter<'a, K, V, N>,
+pub struct Values<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    pub(crate) inner: Iter<'a, K, V, N, U, C>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Values<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for Values<'a, K, V, N, U, C>
+{
     type Item = &'a V;
 
     fn next(&mut self) -> Option<&'a V> {
         self.inner.next().map(|(_, v)| v)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn last(self) -> Option<&'a V> {
+        self.inner.last().map(|(_, v)| v)
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for Values<'a, K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<&'a V> {
+        self.inner.next_back().map(|(_, v)| v)
+    }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for Values<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for Values<'a, K, V, N, U, C>
+{
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for Values<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for Values<'a, K, V, N, U, C>
+{
+}
 
 /// An owning iterator over the values of a [`SgMap`][crate::map::SgMap].
 ///
 /// This `struct` is created by the [`into_values`][crate::map::SgMap::into_values] method on [`SgMap`][crate::map::SgMap].
 /// See its documentation for more.
-pub struct IntoValues<K: Ord + Default, V: Default, const N: usize> {
-    pub(crate) inner: IntoIter<K, V, N>,
+pub struct IntoValues<K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    pub(crate) inner: IntoIter<K, V, N, U, C>,
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> Iterator for IntoValues<K, V, N> {
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator for IntoValues<K, V, N, U, C> {
     type Item = V;
 
     fn next(&mut self) -> Option<V> {
         self.inner.next().map(|(_, v)| v)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn last(self) -> Option<V> {
+        self.inner.last().map(|(_, v)| v)
+    }
+}
+
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for IntoValues<K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<V> {
+        self.inner.next_back().map(|(_, v)| v)
+    }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for IntoValues<K, V, N> {
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for IntoValues<K, V, N, U, C>
+{
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> FusedIterator for IntoValues<K, V, N> {}
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for IntoValues<K, V, N, U, C>
+{
+}
 
 /// A mutable iterator over the values of a [`SgMap`][crate::map::SgMap].
 ///
 /// This `struct` is created by the [`values_mut`][crate::map::SgMap::values_mut] method on [`SgMap`][crate::map::SgMap].
 /// See its documentation for more.
-pub struct ValuesMut<'a, K: Ord + Default, V: Default, const N: usize> {
-    pub(crate) inner: IterMut<'a, K, V, N>,
+pub struct ValuesMut<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16> {
+    pub(crate) inner: IterMut<'a, K, V, N, U>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for ValuesMut<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> Iterator for ValuesMut<'a, K, V, N, U> {
     type Item = &'a mut V;
 
     fn next(&mut self) -> Option<&'a mut V> {
         self.inner.next().map(|(_, v)| v)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator
-    for ValuesMut<'a, K, V, N>
-{
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> DoubleEndedIterator for ValuesMut<'a, K, V, N, U> {
+    fn next_back(&mut self) -> Option<&'a mut V> {
+        self.inner.next_back().map(|(_, v)| v)
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> ExactSizeIterator for ValuesMut<'a, K, V, N, U> {
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for ValuesMut<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> FusedIterator for ValuesMut<'a, K, V, N, U> {}
 
 // Entry APIs ----------------------------------------------------------------------------------------------------------
 
 /// A view into a single entry in a map, which may either be vacant or occupied.
 ///
 /// This `enum` is constructed from the [`SgMap::entry`] method on [`SgMap`].
-pub enum Entry<'a, K: Ord + Default, V: Default, const N: usize> {
+pub enum Entry<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
     /// A vacant entry.
-    Vacant(VacantEntry<'a, K, V, N>),
+    Vacant(VacantEntry<'a, K, V, N, U, C>),
     /// An occupied entry.
-    Occupied(OccupiedEntry<'a, K, V, N>),
+    Occupied(OccupiedEntry<'a, K, V, N, U, C>),
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Entry<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Entry<'a, K, V, N, U, C> {
     /// Ensures a value is in the entry by inserting the default if empty, and returns a mutable
     /// reference to the value in the entry.
     ///
@@ -361,7 +587,7 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Entry<'a, K, V, N> {
     ///    .or_insert(42);
     /// assert_eq!(map["poneyland"], 43);
     /// ```
-    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Entry<'a, K, V, N> {
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Entry<'a, K, V, N, U, C> {
         match self {
             Entry::Occupied(mut entry) => {
                 f(entry.get_mut());
@@ -384,7 +610,10 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Entry<'a, K, V, N> {
     ///
     /// assert_eq!(map["poneyland"], None);
     /// ```
-    pub fn or_default(self) -> &'a mut V {
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
         match self {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => entry.insert(Default::default()),
@@ -394,12 +623,12 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Entry<'a, K, V, N> {
 
 /// A view into a vacant entry in a [`SgMap`][crate::map::SgMap].
 /// It is part of the [`Entry`] enum.
-pub struct VacantEntry<'a, K: Ord + Default, V: Default, const N: usize> {
+pub struct VacantEntry<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
     pub(super) key: K,
-    pub(super) table: &'a mut SgMap<K, V, N>,
+    pub(super) table: &'a mut SgMap<K, V, N, U, C>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> VacantEntry<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> VacantEntry<'a, K, V, N, U, C> {
     /// Gets a reference to the key that would be used when inserting a value
     /// through the [`VacantEntry`][crate::map_types::VacantEntry].
     ///
@@ -450,20 +679,49 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> VacantEntry<'a, K, V, N>
     /// assert_eq!(map["poneyland"], 37);
     /// ```
     pub fn insert(self, value: V) -> &'a mut V {
-        let (_, new_node_idx) = self.table.bst.priv_balancing_insert::<Idx>(self.key, value);
+        let (_, new_node_idx) = self.table.bst.priv_balancing_insert(self.key, value);
 
         self.table.bst.arena[new_node_idx].get_mut().1
     }
+
+    /// Sets the value of the entry with the [`VacantEntry`][crate::map_types::VacantEntry]'s key,
+    /// and returns a mutable reference to it.
+    /// Returns [`SgError::StackCapacityExceeded`][crate::SgError::StackCapacityExceeded] if the map is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgError, SgMap};
+    /// use scapegoat::map_types::Entry;
+    ///
+    /// let mut map = SgMap::<&str, u32, 1>::new();
+    ///
+    /// if let Entry::Vacant(v) = map.entry("poneyland") {
+    ///     assert_eq!(v.try_insert(37), Ok(&mut 37));
+    /// }
+    ///
+    /// if let Entry::Vacant(v) = map.entry("horseland") {
+    ///     assert_eq!(v.try_insert(12), Err(SgError::StackCapacityExceeded));
+    /// }
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn try_insert(self, value: V) -> Result<&'a mut V, SgError> {
+        match self.table.len() < self.table.capacity() {
+            true => Ok(self.insert(value)),
+            false => Err(SgError::StackCapacityExceeded),
+        }
+    }
 }
 
 /// A view into an occupied entry in a [`SgMap`][crate::map::SgMap].
 /// It is part of the [`Entry`] enum.
-pub struct OccupiedEntry<'a, K: Ord + Default, V: Default, const N: usize> {
+pub struct OccupiedEntry<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural>
+{
     pub(super) node_idx: usize,
-    pub(super) table: &'a mut SgMap<K, V, N>,
+    pub(super) table: &'a mut SgMap<K, V, N, U, C>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> OccupiedEntry<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> OccupiedEntry<'a, K, V, N, U, C> {
     /// Gets a reference to the key in the entry.
     ///
     /// # Examples
@@ -625,15 +883,22 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> OccupiedEntry<'a, K, V, N
 /// The error returned by [`try_insert_std`](SgMap::try_insert_std) when the key already exists.
 ///
 /// Contains the occupied entry, and the value that was not inserted.
-pub struct OccupiedError<'a, K: 'a + Ord + Default, V: 'a + Default, const N: usize> {
+pub struct OccupiedError<
+    'a,
+    K: 'a + Ord,
+    V: 'a,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
     /// The entry in the map that was already occupied.
-    pub entry: OccupiedEntry<'a, K, V, N>,
+    pub entry: OccupiedEntry<'a, K, V, N, U, C>,
     /// The value which was not inserted, because the entry was already occupied.
     pub value: V,
 }
 
-impl<K: fmt::Debug + Ord + Default, V: fmt::Debug + Default, const N: usize> fmt::Debug
-    for OccupiedError<'_, K, V, N>
+impl<K: fmt::Debug + Ord, V: fmt::Debug, const N: usize, U: ArenaIdx, C: Compare<K>> fmt::Debug
+    for OccupiedError<'_, K, V, N, U, C>
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("OccupiedError")
@@ -644,8 +909,8 @@ impl<K: fmt::Debug + Ord + Default, V: fmt::Debug + Default, const N: usize> fmt
     }
 }
 
-impl<'a, K: fmt::Debug + Ord + Default, V: fmt::Debug + Default, const N: usize> fmt::Display
-    for OccupiedError<'a, K, V, N>
+impl<'a, K: fmt::Debug + Ord, V: fmt::Debug, const N: usize, U: ArenaIdx, C: Compare<K>>
+    fmt::Display for OccupiedError<'a, K, V, N, U, C>
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -658,133 +923,380 @@ impl<'a, K: fmt::Debug + Ord + Default, V: fmt::Debug + Default, const N: usize>
     }
 }
 
-// Range APIs ----------------------------------------------------------------------------------------------------------
-
-/// An iterator over a sub-range of entries in a [`SgMap`].
-///
-/// This `struct` is created by the [`range`][`crate::map::SgMap::range`] method on [`SgMap`][crate::map::SgMap]. See its
-/// documentation for more.
-pub struct Range<'a, K: Ord + Default, V: Default, const N: usize> {
-    pub(crate) table: &'a SgMap<K, V, N>,
-    pub(crate) node_idx_iter: <ArrayVec<[usize; N]> as IntoIterator>::IntoIter,
-}
-
-impl<'a, K: Ord + Default, V: Default, const N: usize> Range<'a, K, V, N> {
-    fn to_node_ref(&self, idx: usize) -> (&'a K, &'a V) {
-        let node = &self.table.bst.arena[idx];
-        (node.key(), node.val())
-    }
-}
-
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Range<'a, K, V, N> {
-    type Item = (&'a K, &'a V);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let node_idx = self.node_idx_iter.next()?;
-        Some(self.to_node_ref(node_idx))
-    }
-}
-
-impl<'a, K: Ord + Default, V: Default, const N: usize> DoubleEndedIterator for Range<'a, K, V, N> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        let node_idx = self.node_idx_iter.next_back()?;
-        Some(self.to_node_ref(node_idx))
-    }
-}
-
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for Range<'a, K, V, N> {}
-
-/// A mutable iterator over a sub-range of entries in a [`SgMap`].
+/// A view into a single entry in a map, which may either be vacant or occupied,
+/// looked up by a borrowed key.
 ///
-/// This `struct` is created by the [`range_mut`] method on [`SgMap`]. See its
-/// documentation for more.
+/// Unlike [`Entry`], the owned key is only built (via [`From`]) if a value is actually inserted into a vacant entry.
 ///
-/// [`range_mut`]: SgMap::range_mut
-pub struct RangeMut<'a, K: Ord + Default, V: Default, const N: usize> {
-    inner: RangeMutPeekable<'a, K, V, N>,
-    last: Option<RangeMutLast<'a, K, V, N>>,
-    total_cnt: usize,
-    spent_cnt: usize,
+/// This `enum` is constructed from the [`SgMap::entry_ref`] method on [`SgMap`].
+pub enum EntryRef<
+    'a,
+    'b,
+    K: Ord,
+    Q: ?Sized,
+    V,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
+    /// A vacant entry.
+    Vacant(VacantEntryRef<'a, 'b, K, Q, V, N, U, C>),
+    /// An occupied entry.
+    Occupied(OccupiedEntry<'a, K, V, N, U, C>),
 }
 
-type RangeMutLast<'a, K, V, const N: usize> =
-    <Peekable<TreeIterMut<'a, K, V, N>> as Iterator>::Item;
-
-type RangeMutPeekable<'a, K, V, const N: usize> = Peekable<TreeIterMut<'a, K, V, N>>;
-
-impl<'a, K, V, const N: usize> RangeMut<'a, K, V, N>
+impl<'a, 'b, K, Q, V, const N: usize, U: ArenaIdx, C: Compare<K>> EntryRef<'a, 'b, K, Q, V, N, U, C>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord + Borrow<Q> + From<&'b Q>,
+    Q: ?Sized,
 {
-    // Constructor
-    pub(crate) fn new<T, R>(map: &'a mut SgMap<K, V, N>, range: &R) -> Self
-    where
-        T: Ord + ?Sized,
-        K: Borrow<T> + Ord + Default,
-        R: RangeBounds<T>,
-    {
-        let len = RangeMut::compute_len(map, range);
-
-        let (iter, last) = RangeMut::init_iter_mut(map, range);
-        Self {
-            inner: iter,
-            last,
-            total_cnt: len,
-            spent_cnt: 0,
+    /// Ensures a value is in the entry by inserting the default if empty, and returns a mutable
+    /// reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<String, usize, 10>::new();
+    /// map.entry_ref("poneyland").or_insert(12);
+    ///
+    /// assert_eq!(map["poneyland"], 12);
+    /// ```
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(default),
         }
     }
 
-    // Compute amount of items to return
-    fn compute_len<T, R>(map: &SgMap<K, V, N>, range: &R) -> usize
-    where
-        T: Ord + ?Sized,
-        K: Borrow<T> + Ord + Default,
-        R: RangeBounds<T>,
-    {
-        let mut peekable = map.bst.iter().peekable();
-        let mut len = 0;
-
-        // Advance immutable iter to start
-        while let Some(node) = peekable.peek() {
-            if range.contains(node.0.borrow()) {
-                break;
-            }
-
-            peekable.next();
+    /// Ensures a value is in the entry by inserting the result of the default function if empty, and returns a mutable
+    /// reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<String, usize, 10>::new();
+    /// let x = 42;
+    /// map.entry_ref("poneyland").or_insert_with(|| x);
+    ///
+    /// assert_eq!(map["poneyland"], 42);
+    /// ```
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(default()),
         }
+    }
 
-        // Count remaining
-        for node in peekable {
-            if range.contains(node.0.borrow()) {
-                len += 1;
-            } else {
-                break;
+    /// Ensures a value is in the entry by inserting, if empty, the result of the default function.
+    /// The default function is given the borrowed key, before it's converted into an owned one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<String, usize, 10>::new();
+    ///
+    /// map.entry_ref("poneyland").or_insert_with_key(|key| key.chars().count());
+    ///
+    /// assert_eq!(map["poneyland"], 9);
+    /// ```
+    pub fn or_insert_with_key<F: FnOnce(&Q) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => {
+                let value = default(entry.key());
+                entry.insert(value)
             }
         }
-
-        len
     }
 
-    // Prepare mutable iterator to return first item in range
-    fn init_iter_mut<T, R>(
-        map: &'a mut SgMap<K, V, N>,
-        range: &R,
-    ) -> (
-        RangeMutPeekable<'a, K, V, N>,
-        Option<RangeMutLast<'a, K, V, N>>,
-    )
+    /// Returns a reference to this entry's key, in its borrowed form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<String, usize, 10>::new();
+    /// assert_eq!(map.entry_ref("poneyland").key(), "poneyland");
+    /// ```
+    pub fn key(&self) -> &Q {
+        match self {
+            EntryRef::Occupied(entry) => entry.key().borrow(),
+            EntryRef::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<String, usize, 10>::new();
+    ///
+    /// map.entry_ref("poneyland")
+    ///    .and_modify(|e| { *e += 1 })
+    ///    .or_insert(42);
+    /// assert_eq!(map["poneyland"], 42);
+    ///
+    /// map.entry_ref("poneyland")
+    ///    .and_modify(|e| { *e += 1 })
+    ///    .or_insert(42);
+    /// assert_eq!(map["poneyland"], 43);
+    /// ```
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> EntryRef<'a, 'b, K, Q, V, N, U, C> {
+        match self {
+            EntryRef::Occupied(mut entry) => {
+                f(entry.get_mut());
+                EntryRef::Occupied(entry)
+            }
+            EntryRef::Vacant(entry) => EntryRef::Vacant(entry),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the default value if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    ///
+    /// let mut map = SgMap::<String, Option<usize>, 10>::new();
+    /// map.entry_ref("poneyland").or_default();
+    ///
+    /// assert_eq!(map["poneyland"], None);
+    /// ```
+    pub fn or_default(self) -> &'a mut V
     where
-        T: Ord + ?Sized,
-        K: Borrow<T> + Ord + Default,
+        V: Default,
+    {
+        match self {
+            EntryRef::Occupied(entry) => entry.into_mut(),
+            EntryRef::Vacant(entry) => entry.insert(Default::default()),
+        }
+    }
+}
+
+/// A view into a vacant entry in a [`SgMap`][crate::map::SgMap], holding a borrowed key.
+/// It is part of the [`EntryRef`] enum.
+pub struct VacantEntryRef<
+    'a,
+    'b,
+    K: Ord,
+    Q: ?Sized,
+    V,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
+    pub(super) key: &'b Q,
+    pub(super) table: &'a mut SgMap<K, V, N, U, C>,
+}
+
+impl<'a, 'b, K, Q, V, const N: usize, U: ArenaIdx, C: Compare<K>>
+    VacantEntryRef<'a, 'b, K, Q, V, N, U, C>
+where
+    K: Ord + From<&'b Q>,
+    Q: ?Sized,
+{
+    /// Gets a reference to the borrowed key that would be converted and used when inserting a value
+    /// through the [`VacantEntryRef`][crate::map_types::VacantEntryRef].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    /// use scapegoat::map_types::EntryRef;
+    ///
+    /// let mut map = SgMap::<String, usize, 2>::new();
+    ///
+    /// if let EntryRef::Vacant(v) = map.entry_ref("poneyland") {
+    ///     assert_eq!(v.key(), "poneyland");
+    /// }
+    /// ```
+    pub fn key(&self) -> &'b Q {
+        self.key
+    }
+
+    /// Converts the borrowed key into an owned one, then sets the value of the entry with it,
+    /// and returns a mutable reference to the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMap;
+    /// use scapegoat::map_types::EntryRef;
+    ///
+    /// let mut map = SgMap::<String, u32, 2>::new();
+    ///
+    /// if let EntryRef::Vacant(v) = map.entry_ref("poneyland") {
+    ///     v.insert(37);
+    /// }
+    /// assert_eq!(map["poneyland"], 37);
+    /// ```
+    pub fn insert(self, value: V) -> &'a mut V {
+        let (_, new_node_idx) = self
+            .table
+            .bst
+            .priv_balancing_insert(K::from(self.key), value);
+
+        self.table.bst.arena[new_node_idx].get_mut().1
+    }
+
+    /// Converts the borrowed key into an owned one, then sets the value of the entry with it,
+    /// and returns a mutable reference to the value.
+    /// Returns [`SgError::StackCapacityExceeded`][crate::SgError::StackCapacityExceeded] if the map is full,
+    /// in which case the key is never converted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgError, SgMap};
+    /// use scapegoat::map_types::EntryRef;
+    ///
+    /// let mut map = SgMap::<String, u32, 1>::new();
+    ///
+    /// if let EntryRef::Vacant(v) = map.entry_ref("poneyland") {
+    ///     assert_eq!(v.try_insert(37), Ok(&mut 37));
+    /// }
+    ///
+    /// if let EntryRef::Vacant(v) = map.entry_ref("horseland") {
+    ///     assert_eq!(v.try_insert(12), Err(SgError::StackCapacityExceeded));
+    /// }
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn try_insert(self, value: V) -> Result<&'a mut V, SgError> {
+        match self.table.len() < self.table.capacity() {
+            true => Ok(self.insert(value)),
+            false => Err(SgError::StackCapacityExceeded),
+        }
+    }
+}
+
+// Range APIs ----------------------------------------------------------------------------------------------------------
+
+/// An iterator over a sub-range of entries in a [`SgMap`].
+///
+/// This `struct` is created by the [`range`][`crate::map::SgMap::range`] method on [`SgMap`][crate::map::SgMap]. See its
+/// documentation for more.
+pub struct Range<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    pub(crate) table: &'a SgMap<K, V, N, U, C>,
+    pub(crate) node_idx_iter: <StackVec<[usize; N]> as IntoIterator>::IntoIter,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Range<'a, K, V, N, U, C> {
+    fn to_node_ref(&self, idx: usize) -> (&'a K, &'a V) {
+        let node = &self.table.bst.arena[idx];
+        (node.key(), node.val())
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for Range<'a, K, V, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node_idx = self.node_idx_iter.next()?;
+        Some(self.to_node_ref(node_idx))
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for Range<'a, K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node_idx = self.node_idx_iter.next_back()?;
+        Some(self.to_node_ref(node_idx))
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for Range<'a, K, V, N, U, C>
+{
+}
+
+/// A mutable iterator over a sub-range of entries in a [`SgMap`].
+///
+/// This `struct` is created by the [`range_mut`] method on [`SgMap`]. See its
+/// documentation for more.
+///
+/// [`range_mut`]: SgMap::range_mut
+pub struct RangeMut<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16> {
+    inner: RangeMutPeekable<'a, K, V, N, U>,
+    last: Option<RangeMutLast<'a, K, V>>,
+    total_cnt: usize,
+    spent_cnt: usize,
+}
+
+type RangeMutLast<'a, K, V> = (&'a K, &'a mut V);
+
+type RangeMutPeekable<'a, K, V, const N: usize, U> = Peekable<TreeIterMut<'a, K, V, N, U>>;
+
+impl<'a, K, V, const N: usize, U: ArenaIdx> RangeMut<'a, K, V, N, U>
+where
+    K: Ord,
+{
+    // Constructor
+    pub(crate) fn new<C, T, R>(map: &'a mut SgMap<K, V, N, U, C>, range: &R) -> Self
+    where
+        T: ?Sized,
+        C: Compare<K> + Compare<T>,
+        K: Borrow<T> + Ord,
+        R: RangeBounds<T>,
+    {
+        let len = RangeMut::compute_len(map, range);
+
+        let (iter, last) = RangeMut::init_iter_mut(map, range);
+        Self {
+            inner: iter,
+            last,
+            total_cnt: len,
+            spent_cnt: 0,
+        }
+    }
+
+    // Compute amount of items to return
+    fn compute_len<C, T, R>(map: &SgMap<K, V, N, U, C>, range: &R) -> usize
+    where
+        T: ?Sized,
+        C: Compare<K> + Compare<T>,
+        K: Borrow<T> + Ord,
+        R: RangeBounds<T>,
+    {
+        map.bst.range_search(range).len()
+    }
+
+    // Prepare mutable iterator to return first item in range
+    fn init_iter_mut<C, T, R>(
+        map: &'a mut SgMap<K, V, N, U, C>,
+        range: &R,
+    ) -> (
+        RangeMutPeekable<'a, K, V, N, U>,
+        Option<RangeMutLast<'a, K, V>>,
+    )
+    where
+        T: ?Sized,
+        C: Compare<K> + Compare<T>,
+        K: Borrow<T> + Ord,
         R: RangeBounds<T>,
     {
+        let cmp = map.bst.cmp.clone();
         let mut peekable = map.bst.iter_mut().peekable();
         let mut last = None;
 
         // Advance mutable iter to start
         while let Some(node) = peekable.peek() {
-            if range.contains(node.0.borrow()) {
+            if range_contains(&cmp, range, node.0.borrow()) {
                 break;
             }
 
@@ -792,7 +1304,7 @@ where
         }
 
         while let Some(node) = peekable.next_back() {
-            if range.contains(node.0.borrow()) {
+            if range_contains(&cmp, range, node.0.borrow()) {
                 last = Some(node);
                 break;
             }
@@ -802,10 +1314,9 @@ where
     }
 }
 
-impl<'a, K, V, const N: usize> Iterator for RangeMut<'a, K, V, N>
+impl<'a, K, V, const N: usize, U: ArenaIdx> Iterator for RangeMut<'a, K, V, N, U>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     type Item = (&'a K, &'a mut V);
 
@@ -820,12 +1331,16 @@ where
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.total_cnt - self.spent_cnt;
+        (remaining, Some(remaining))
+    }
 }
 
-impl<'a, K, V, const N: usize> DoubleEndedIterator for RangeMut<'a, K, V, N>
+impl<'a, K, V, const N: usize, U: ArenaIdx> DoubleEndedIterator for RangeMut<'a, K, V, N, U>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
         if self.spent_cnt < self.total_cnt {
@@ -840,14 +1355,13 @@ where
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for RangeMut<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> FusedIterator for RangeMut<'a, K, V, N, U> {}
 
 /*
 // TODO: does commit to this interface limit potential optimizations?
-impl<'a, K, V, const N: usize> ExactSizeIterator for RangeMut<'a, K, V, N>
+impl<'a, K, V, const N: usize, U: ArenaIdx> ExactSizeIterator for RangeMut<'a, K, V, N, U>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     fn len(&self) -> usize {
         debug_assert!(self.spent_cnt <= self.total_cnt);
@@ -855,3 +1369,658 @@ where
     }
 }
 */
+
+// Join Iterators ------------------------------------------------------------------------------------------------------
+
+// Lockstep in-order walk over two maps, `O(n + m)` total. Each step yields the next smallest key,
+// from `this`, `other`, or both (if equal). Keys are compared with `this` map's comparator.
+struct JoinMergeIter<'a, K: Ord, V, V2, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>>
+{
+    this_iter: Peekable<Iter<'a, K, V, N, U, C>>,
+    other_iter: Peekable<Iter<'a, K, V2, M, U, C>>,
+    cmp: &'a C,
+}
+
+impl<'a, K: Ord, V, V2, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>>
+    JoinMergeIter<'a, K, V, V2, N, M, U, C>
+{
+    fn new(this: &'a SgMap<K, V, N, U, C>, other: &'a SgMap<K, V2, M, U, C>) -> Self {
+        JoinMergeIter {
+            this_iter: this.iter().peekable(),
+            other_iter: other.iter().peekable(),
+            cmp: &this.bst.cmp,
+        }
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn nexts(&mut self) -> Option<(Option<(&'a K, &'a V)>, Option<(&'a K, &'a V2)>)> {
+        let ord = match (self.this_iter.peek(), self.other_iter.peek()) {
+            (None, None) => return None,
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some((this_key, _)), Some((other_key, _))) => self.cmp.compare(*this_key, *other_key),
+        };
+
+        match ord {
+            Ordering::Less => Some((self.this_iter.next(), None)),
+            Ordering::Greater => Some((None, self.other_iter.next())),
+            Ordering::Equal => Some((self.this_iter.next(), self.other_iter.next())),
+        }
+    }
+
+    // Remaining lengths of (`this`, `other`).
+    fn lens(&self) -> (usize, usize) {
+        (self.this_iter.len(), self.other_iter.len())
+    }
+}
+
+/// A lazy iterator over the keys present in both of two [`SgMap`][crate::map::SgMap]s, in ascending order,
+/// yielding each key with both of its values.
+///
+/// This `struct` is created by the [`join`][crate::map::SgMap::join] method on [`SgMap`][crate::map::SgMap].
+/// See its documentation for more.
+pub struct Join<
+    'a,
+    K: Ord,
+    V,
+    V2,
+    const N: usize,
+    const M: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
+    merge_iter: JoinMergeIter<'a, K, V, V2, N, M, U, C>,
+}
+
+impl<'a, K: Ord, V, V2, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>>
+    Join<'a, K, V, V2, N, M, U, C>
+{
+    /// Construct `Join` iterator.
+    /// Keys that are both in `this` and `other`.
+    pub(crate) fn new(this: &'a SgMap<K, V, N, U, C>, other: &'a SgMap<K, V2, M, U, C>) -> Self {
+        Join {
+            merge_iter: JoinMergeIter::new(this, other),
+        }
+    }
+}
+
+impl<'a, K: Ord, V, V2, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for Join<'a, K, V, V2, N, M, U, C>
+{
+    type Item = (&'a K, &'a V, &'a V2);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.merge_iter.nexts()? {
+                (Some((key, this_val)), Some((_, other_val))) => {
+                    return Some((key, this_val, other_val))
+                }
+                // Either side exhausted, nothing left in common
+                (Some(_), None) if self.merge_iter.other_iter.peek().is_none() => return None,
+                (None, Some(_)) if self.merge_iter.this_iter.peek().is_none() => return None,
+                _ => continue,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (this_len, other_len) = self.merge_iter.lens();
+        (0, Some(this_len.min(other_len)))
+    }
+}
+
+impl<'a, K: Ord, V, V2, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for Join<'a, K, V, V2, N, M, U, C>
+{
+}
+
+/// A lazy iterator over the keys of a [`SgMap`][crate::map::SgMap], in ascending order,
+/// yielding each key with its value and the matching value from a second map, if any.
+///
+/// This `struct` is created by the [`left_join`][crate::map::SgMap::left_join] method on [`SgMap`][crate::map::SgMap].
+/// See its documentation for more.
+pub struct LeftJoin<
+    'a,
+    K: Ord,
+    V,
+    V2,
+    const N: usize,
+    const M: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
+    merge_iter: JoinMergeIter<'a, K, V, V2, N, M, U, C>,
+}
+
+impl<'a, K: Ord, V, V2, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>>
+    LeftJoin<'a, K, V, V2, N, M, U, C>
+{
+    /// Construct `LeftJoin` iterator.
+    /// Keys that are in `this`, whether or not they're in `other`.
+    pub(crate) fn new(this: &'a SgMap<K, V, N, U, C>, other: &'a SgMap<K, V2, M, U, C>) -> Self {
+        LeftJoin {
+            merge_iter: JoinMergeIter::new(this, other),
+        }
+    }
+}
+
+impl<'a, K: Ord, V, V2, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for LeftJoin<'a, K, V, V2, N, M, U, C>
+{
+    type Item = (&'a K, &'a V, Option<&'a V2>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // `this` exhausted, the rest of `other` is irrelevant
+        self.merge_iter.this_iter.peek()?;
+        loop {
+            match self.merge_iter.nexts()? {
+                (Some((key, this_val)), opt_other) => {
+                    return Some((key, this_val, opt_other.map(|(_, v)| v)))
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (this_len, _) = self.merge_iter.lens();
+        (this_len, Some(this_len))
+    }
+}
+
+impl<'a, K: Ord, V, V2, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>>
+    ExactSizeIterator for LeftJoin<'a, K, V, V2, N, M, U, C>
+{
+    fn len(&self) -> usize {
+        self.merge_iter.lens().0
+    }
+}
+
+impl<'a, K: Ord, V, V2, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for LeftJoin<'a, K, V, V2, N, M, U, C>
+{
+}
+
+/// A lazy iterator over the keys present in either of two [`SgMap`][crate::map::SgMap]s, in ascending order,
+/// yielding each key with its value from either map, if any.
+///
+/// This `struct` is created by the [`full_join`][crate::map::SgMap::full_join] method on [`SgMap`][crate::map::SgMap].
+/// See its documentation for more.
+pub struct FullJoin<
+    'a,
+    K: Ord,
+    V,
+    V2,
+    const N: usize,
+    const M: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
+    merge_iter: JoinMergeIter<'a, K, V, V2, N, M, U, C>,
+}
+
+impl<'a, K: Ord, V, V2, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>>
+    FullJoin<'a, K, V, V2, N, M, U, C>
+{
+    /// Construct `FullJoin` iterator.
+    /// Keys that are in `this`, `other`, or both.
+    pub(crate) fn new(this: &'a SgMap<K, V, N, U, C>, other: &'a SgMap<K, V2, M, U, C>) -> Self {
+        FullJoin {
+            merge_iter: JoinMergeIter::new(this, other),
+        }
+    }
+}
+
+impl<'a, K: Ord, V, V2, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for FullJoin<'a, K, V, V2, N, M, U, C>
+{
+    type Item = (&'a K, Option<&'a V>, Option<&'a V2>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (this_opt, other_opt) = self.merge_iter.nexts()?;
+        let key = this_opt.map(|(k, _)| k).or(other_opt.map(|(k, _)| k))?;
+        Some((key, this_opt.map(|(_, v)| v), other_opt.map(|(_, v)| v)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (this_len, other_len) = self.merge_iter.lens();
+        (this_len.max(other_len), this_len.checked_add(other_len))
+    }
+}
+
+impl<'a, K: Ord, V, V2, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for FullJoin<'a, K, V, V2, N, M, U, C>
+{
+}
+
+// Draining Iterator ---------------------------------------------------------------------------------------------------
+
+/// A draining iterator over the entries of a [`SgMap`][crate::map::SgMap].
+///
+/// This `struct` is created by the [`drain`][crate::map::SgMap::drain] method on [`SgMap`][crate::map::SgMap].
+/// See its documentation for more.
+pub struct Drain<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    inner: TreeDrain<'a, K, V, N, U, C>,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Drain<'a, K, V, N, U, C> {
+    /// Construct draining iterator.
+    pub(crate) fn new(map: &'a mut SgMap<K, V, N, U, C>) -> Self {
+        Drain {
+            inner: TreeDrain::new(&mut map.bst),
+        }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for Drain<'a, K, V, N, U, C>
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for Drain<'a, K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for Drain<'a, K, V, N, U, C>
+{
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for Drain<'a, K, V, N, U, C>
+{
+}
+
+// Extracting Iterator -------------------------------------------------------------------------------------------------
+
+/// An iterator produced by calling [`extract_if`][crate::map::SgMap::extract_if] on [`SgMap`][crate::map::SgMap].
+/// See its documentation for more.
+pub struct ExtractIf<'a, K: Ord, V, F, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural>
+where
+    F: FnMut(&K, &mut V) -> bool,
+{
+    inner: TreeExtractIf<'a, K, V, N, U, C>,
+    pred: F,
+}
+
+impl<'a, K: Ord, V, F, const N: usize, U: ArenaIdx, C: Compare<K>> ExtractIf<'a, K, V, F, N, U, C>
+where
+    F: FnMut(&K, &mut V) -> bool,
+{
+    /// Construct extracting iterator.
+    pub(crate) fn new(map: &'a mut SgMap<K, V, N, U, C>, pred: F) -> Self {
+        ExtractIf {
+            inner: TreeExtractIf::new(&mut map.bst),
+            pred,
+        }
+    }
+}
+
+impl<'a, K: Ord, V, F, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for ExtractIf<'a, K, V, F, N, U, C>
+where
+    F: FnMut(&K, &mut V) -> bool,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next_with(&mut self.pred)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.inner.remaining()))
+    }
+}
+
+impl<'a, K: Ord, V, F, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for ExtractIf<'a, K, V, F, N, U, C>
+where
+    F: FnMut(&K, &mut V) -> bool,
+{
+}
+
+// Cursor APIs ---------------------------------------------------------------------------------------------------------
+
+/// A read-only cursor over a [`SgMap`][crate::map::SgMap].
+///
+/// A cursor points into the gap between two adjacent entries (or before the first/after the last),
+/// and can move in either direction. Each move is `O(log n)`, the cursor itself is `O(1)` space.
+///
+/// This `struct` is created by the [`lower_bound`][crate::map::SgMap::lower_bound] and
+/// [`upper_bound`][crate::map::SgMap::upper_bound] methods on [`SgMap`][crate::map::SgMap].
+/// See their documentation for more.
+pub struct Cursor<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    cursor: TreeCursor<'a, K, V, N, U, C>,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Cursor<'a, K, V, N, U, C> {
+    /// Construct cursor.
+    pub(crate) fn new(cursor: TreeCursor<'a, K, V, N, U, C>) -> Self {
+        Cursor { cursor }
+    }
+
+    /// Returns the entry after the cursor, without moving it.
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        self.cursor.peek_next()
+    }
+
+    /// Returns the entry before the cursor, without moving it.
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        self.cursor.peek_prev()
+    }
+
+    /// Advances the cursor past the next entry, returning it.
+    /// Returns `None`, without moving, if the cursor is after the last entry.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        self.cursor.next()
+    }
+
+    /// Moves the cursor back past the previous entry, returning it.
+    /// Returns `None`, without moving, if the cursor is before the first entry.
+    pub fn prev(&mut self) -> Option<(&'a K, &'a V)> {
+        self.cursor.prev()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Clone
+    for Cursor<'a, K, V, N, U, C>
+{
+    fn clone(&self) -> Self {
+        Cursor {
+            cursor: self.cursor.clone(),
+        }
+    }
+}
+
+/// A cursor over a [`SgMap`][crate::map::SgMap] with editing operations.
+///
+/// Unlike [`Cursor`][crate::map_types::Cursor], a `CursorMut` points *at* an entry, or at a "ghost" non-entry
+/// that sits after the last entry and before the first (the ends wrap through it).
+/// Each move is `O(log n)`, the cursor itself is `O(1)` space.
+///
+/// Insertions are validated against the neighboring keys, so the map's order can't be broken through a cursor.
+///
+/// This `struct` is created by the [`lower_bound_mut`][crate::map::SgMap::lower_bound_mut] and
+/// [`upper_bound_mut`][crate::map::SgMap::upper_bound_mut] methods on [`SgMap`][crate::map::SgMap].
+/// See their documentation for more.
+pub struct CursorMut<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    cursor: TreeCursorMut<'a, K, V, N, U, C>,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> CursorMut<'a, K, V, N, U, C> {
+    /// Construct cursor.
+    pub(crate) fn new(cursor: TreeCursorMut<'a, K, V, N, U, C>) -> Self {
+        CursorMut { cursor }
+    }
+
+    /// Returns the entry the cursor points at, or `None` at the ghost.
+    pub fn current(&mut self) -> Option<(&K, &mut V)> {
+        self.cursor.current()
+    }
+
+    /// Returns the entry after the current one (the first entry, if at the ghost), without moving.
+    pub fn peek_next(&self) -> Option<(&K, &V)> {
+        self.cursor.peek_next()
+    }
+
+    /// Returns the entry before the current one (the last entry, if at the ghost), without moving.
+    pub fn peek_prev(&self) -> Option<(&K, &V)> {
+        self.cursor.peek_prev()
+    }
+
+    /// Moves to the next entry. Moves from the last entry to the ghost, and from the ghost to the first entry.
+    pub fn move_next(&mut self) {
+        self.cursor.move_next();
+    }
+
+    /// Moves to the previous entry. Moves from the first entry to the ghost, and from the ghost to the last entry.
+    pub fn move_prev(&mut self) {
+        self.cursor.move_prev();
+    }
+
+    /// Removes the current entry and moves to the next one, returning the removed pair.
+    /// Returns `None`, without moving, at the ghost.
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        self.cursor.remove_current()
+    }
+
+    /// Inserts a pair just before the current entry (at the end, if at the ghost). The cursor doesn't move.
+    ///
+    /// Returns `Err` if `key` isn't strictly between the previous and current keys
+    /// ([`SgError::KeyOutOfOrder`], or [`SgError::KeyAlreadyExists`] if equal to either),
+    /// or if the map is full ([`SgError::StackCapacityExceeded`]).
+    pub fn insert_before(&mut self, key: K, val: V) -> Result<(), SgError> {
+        self.cursor.insert_before(key, val)
+    }
+
+    /// Inserts a pair just after the current entry (at the front, if at the ghost). The cursor doesn't move.
+    ///
+    /// Returns `Err` if `key` isn't strictly between the current and next keys
+    /// ([`SgError::KeyOutOfOrder`], or [`SgError::KeyAlreadyExists`] if equal to either),
+    /// or if the map is full ([`SgError::StackCapacityExceeded`]).
+    pub fn insert_after(&mut self, key: K, val: V) -> Result<(), SgError> {
+        self.cursor.insert_after(key, val)
+    }
+}
+
+// Bulk Load Guard -----------------------------------------------------------------------------------------------------
+
+/// A scoped guard that suspends rebalancing of a [`SgMap`][crate::map::SgMap] for a batched insert phase.
+///
+/// This `struct` is created by the [`bulk_load`][crate::map::SgMap::bulk_load] method on [`SgMap`][crate::map::SgMap].
+/// See its documentation for more.
+pub struct BulkLoad<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    map: &'a mut SgMap<K, V, N, U, C>,
+    saved_alpha: (u32, u32),
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> BulkLoad<'a, K, V, N, U, C> {
+    /// Construct bulk load guard.
+    pub(crate) fn new(map: &'a mut SgMap<K, V, N, U, C>) -> Self {
+        let saved_alpha = map.bst.begin_bulk_load();
+        BulkLoad { map, saved_alpha }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Deref
+    for BulkLoad<'a, K, V, N, U, C>
+{
+    type Target = SgMap<K, V, N, U, C>;
+
+    fn deref(&self) -> &Self::Target {
+        self.map
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> DerefMut
+    for BulkLoad<'a, K, V, N, U, C>
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.map
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Drop
+    for BulkLoad<'a, K, V, N, U, C>
+{
+    fn drop(&mut self) {
+        self.map.bst.end_bulk_load(self.saved_alpha);
+    }
+}
+
+// Transaction Guard ---------------------------------------------------------------------------------------------------
+
+/// A scoped guard that records an undo log of mutations to a [`SgMap`][crate::map::SgMap], so they can be rolled back.
+///
+/// This `struct` is created by the [`transaction`][crate::map::SgMap::transaction] method on [`SgMap`][crate::map::SgMap].
+/// See its documentation for more.
+pub struct Transaction<
+    'a,
+    K: Ord,
+    V,
+    const N: usize,
+    const M: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
+    map: &'a mut SgMap<K, V, N, U, C>,
+    log: StackVec<[Option<UndoEntry<K, V>>; M]>,
+}
+
+// Key and its prior value, `None` undoes an insert of a new key
+type UndoEntry<K, V> = (K, Option<V>);
+
+impl<'a, K: Ord, V, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>>
+    Transaction<'a, K, V, N, M, U, C>
+{
+    /// Construct transaction guard.
+    pub(crate) fn new(map: &'a mut SgMap<K, V, N, U, C>) -> Self {
+        Transaction {
+            map,
+            log: StackVec::new(),
+        }
+    }
+
+    /// Returns the number of undo log slots in use, e.g. mutations made through the transaction.
+    pub fn log_len(&self) -> usize {
+        self.log.len()
+    }
+
+    /// Returns the maximum number of undo log slots, e.g. `M`.
+    pub fn log_capacity(&self) -> usize {
+        self.log.capacity()
+    }
+
+    /// Inserts a key-value pair into the map, logging how to undo it.
+    /// Returns `true` if the key wasn't previously present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if all `M` undo log slots are in use, or if the map is full and the key isn't already present.
+    /// For a non-panicking alternative, see [`try_insert`][crate::map_types::Transaction::try_insert].
+    pub fn insert(&mut self, key: K, val: V) -> bool
+    where
+        K: Clone,
+    {
+        match self.try_insert(key, val) {
+            Ok(is_new) => is_new,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Inserts a key-value pair into the map, logging how to undo it.
+    /// Returns `Err` if all `M` undo log slots are in use, or if the map is full and the key isn't already present,
+    /// else the `Ok` contains `true` if the key wasn't previously present.
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<bool, SgError>
+    where
+        K: Clone,
+    {
+        if self.log.len() == self.log.capacity() {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        let undo_key = key.clone();
+        let opt_old_val = self.map.try_insert(key, val)?;
+        let is_new = opt_old_val.is_none();
+        self.log.push(Some((undo_key, opt_old_val)));
+
+        Ok(is_new)
+    }
+
+    /// Removes a key from the map, logging how to undo it.
+    /// Returns `true` if the key was present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is present and all `M` undo log slots are in use.
+    /// For a non-panicking alternative, see [`try_remove`][crate::map_types::Transaction::try_remove].
+    pub fn remove<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        match self.try_remove(key) {
+            Ok(was_present) => was_present,
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Removes a key from the map, logging how to undo it.
+    /// Returns `Err` if the key is present and all `M` undo log slots are in use,
+    /// else the `Ok` contains `true` if the key was present. Removing an absent key takes no log slot.
+    pub fn try_remove<Q>(&mut self, key: &Q) -> Result<bool, SgError>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        if !self.map.contains_key(key) {
+            return Ok(false);
+        }
+
+        if self.log.len() == self.log.capacity() {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        if let Some((old_key, old_val)) = self.map.remove_entry(key) {
+            self.log.push(Some((old_key, Some(old_val))));
+        }
+
+        Ok(true)
+    }
+
+    /// Keeps every mutation made through the transaction, discarding the undo log.
+    pub fn commit(mut self) {
+        self.log.clear();
+    }
+
+    /// Undoes every mutation made through the transaction, newest first.
+    /// Same as dropping the guard without calling [`commit`][crate::map_types::Transaction::commit].
+    pub fn rollback(self) {}
+}
+
+impl<'a, K: Ord, V, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>> Deref
+    for Transaction<'a, K, V, N, M, U, C>
+{
+    type Target = SgMap<K, V, N, U, C>;
+
+    fn deref(&self) -> &Self::Target {
+        self.map
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, const M: usize, U: ArenaIdx, C: Compare<K>> Drop
+    for Transaction<'a, K, V, N, M, U, C>
+{
+    fn drop(&mut self) {
+        // Each undo restores a prior state, so capacity is never exceeded
+        while let Some(Some((key, opt_val))) = self.log.pop() {
+            match opt_val {
+                Some(val) => {
+                    self.map.insert(key, val);
+                }
+                None => {
+                    self.map.remove(&key);
+                }
+            }
+        }
+    }
+}
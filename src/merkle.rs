@@ -0,0 +1,492 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+
+use crate::augment::AugVal;
+use crate::merkle_types::Iter;
+use crate::tree::{ArenaIdx, AugmentHook, Compare, Natural, SgError, SgTree, SmallNode};
+
+use crate::tree::StackVec;
+
+/// Hash function for a [`SgMerkleMap`][crate::SgMerkleMap], e.g. a SHA-256 wrapper.
+///
+/// Each node's digest is `hash_node(hash_pair(key, val), left, right)`, where `left` and `right` are
+/// the children's digests (`None` for a missing child). The root's digest authenticates the entire map.
+///
+/// No hash function is bundled, so no dependency is forced.
+/// Proofs are only as strong as the implementation: it should be collision resistant,
+/// domain-separate `hash_pair` from `hash_node`, and encode `None` distinctly from any digest.
+///
+/// # Examples
+///
+/// ```
+/// use core::hash::{Hash, Hasher};
+/// use std::collections::hash_map::DefaultHasher;
+/// use scapegoat::MerkleHasher;
+///
+/// // Not cryptographic, illustration only!
+/// struct Sip;
+///
+/// impl<K: Hash, V: Hash> MerkleHasher<K, V> for Sip {
+///     type Digest = u64;
+///
+///     fn hash_pair(key: &K, val: &V) -> u64 {
+///         let mut h = DefaultHasher::new();
+///         (0_u8, key, val).hash(&mut h);
+///         h.finish()
+///     }
+///
+///     fn hash_node(pair: &u64, left: Option<&u64>, right: Option<&u64>) -> u64 {
+///         let mut h = DefaultHasher::new();
+///         (1_u8, pair, left, right).hash(&mut h);
+///         h.finish()
+///     }
+/// }
+/// ```
+pub trait MerkleHasher<K, V> {
+    /// Digest type, e.g. `[u8; 32]`.
+    type Digest: Clone + Default + PartialEq;
+
+    /// Computes the digest of a single key-value pair.
+    fn hash_pair(key: &K, val: &V) -> Self::Digest;
+
+    /// Computes a node's digest from its pair's digest and its children's digests.
+    fn hash_node(
+        pair: &Self::Digest,
+        left: Option<&Self::Digest>,
+        right: Option<&Self::Digest>,
+    ) -> Self::Digest;
+}
+
+/// Authenticated ordered map: every node caches a digest of its subtree, see [`MerkleHasher`][crate::MerkleHasher].
+///
+/// The [`root_hash`][crate::SgMerkleMap::root_hash] commits to the map's contents (and current shape),
+/// and [`prove`][crate::SgMerkleMap::prove] produces an inclusion proof for any present key.
+/// A verifier holding only the trusted root hash can then check a key-value pair via [`MerkleProof::verify`].
+/// Values can only be modified through [`modify`][crate::SgMerkleMap::modify], which keeps digests current.
+///
+/// Because digests depend on tree shape, two maps with equal contents may have different root hashes.
+/// Compare [`SgMap::digest`][crate::SgMap::digest] for a shape-independent content hash.
+///
+/// ### Fallible APIs
+///
+/// * [`try_insert`][crate::SgMerkleMap::try_insert]
+/// * [`prove`][crate::SgMerkleMap::prove]
+///
+/// # Examples
+///
+/// ```
+/// use core::hash::{Hash, Hasher};
+/// use std::collections::hash_map::DefaultHasher;
+/// use scapegoat::{MerkleHasher, MerkleProof, SgMerkleMap};
+///
+/// // Not cryptographic, illustration only!
+/// struct Sip;
+///
+/// impl<K: Hash, V: Hash> MerkleHasher<K, V> for Sip {
+///     type Digest = u64;
+///
+///     fn hash_pair(key: &K, val: &V) -> u64 {
+///         let mut h = DefaultHasher::new();
+///         (0_u8, key, val).hash(&mut h);
+///         h.finish()
+///     }
+///
+///     fn hash_node(pair: &u64, left: Option<&u64>, right: Option<&u64>) -> u64 {
+///         let mut h = DefaultHasher::new();
+///         (1_u8, pair, left, right).hash(&mut h);
+///         h.finish()
+///     }
+/// }
+///
+/// let mut manifest = SgMerkleMap::<&str, u32, Sip, 10>::new();
+/// manifest.insert("bootloader", 0x1000);
+/// manifest.insert("kernel", 0x8000);
+/// manifest.insert("rootfs", 0x40000);
+///
+/// let root = *manifest.root_hash().unwrap();
+/// let proof: MerkleProof<_, _, Sip, 8> = manifest.prove("kernel").unwrap();
+///
+/// assert!(proof.verify(&root, &"kernel", &0x8000));
+/// assert!(!proof.verify(&root, &"kernel", &0x9000));
+/// ```
+pub struct SgMerkleMap<
+    K: Ord,
+    V,
+    H: MerkleHasher<K, V>,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
+    pub(crate) bst: SgTree<K, AugVal<V, H::Digest>, N, U, C, UpdateDigest<H>>,
+}
+
+impl<K: Ord, V, H: MerkleHasher<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>>
+    SgMerkleMap<K, V, H, N, U, C>
+{
+    /// Makes a new, empty `SgMerkleMap`.
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_comparator(C::default())
+    }
+
+    /// Makes a new, empty `SgMerkleMap` ordered by `cmp` instead of `K`'s [`Ord`] implementation.
+    /// See [`Compare`][crate::Compare].
+    pub fn with_comparator(cmp: C) -> Self {
+        SgMerkleMap {
+            bst: SgTree::with_comparator(cmp),
+        }
+    }
+
+    /// Total capacity, e.g. maximum number of key-value pairs.
+    pub fn capacity(&self) -> usize {
+        self.bst.capacity()
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.bst.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.bst.is_empty()
+    }
+
+    /// Returns `true` if the map's capacity is filled.
+    pub fn is_full(&self) -> bool {
+        self.bst.is_full()
+    }
+
+    /// Clears the map, removing all elements.
+    pub fn clear(&mut self) {
+        self.bst.clear();
+    }
+
+    /// Insert a key-value pair into the map.
+    /// If the map did not have this key present, `None` is returned.
+    /// If the map did have this key present, the value is updated, the old value is returned,
+    /// and the key is updated. This accommodates types that can be `==` without being identical.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is full and the key isn't already present.
+    /// See [`try_insert`][crate::SgMerkleMap::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        let aug = H::Digest::default();
+        self.bst.insert(key, AugVal { val, aug }).map(|av| av.val)
+    }
+
+    /// Insert a key-value pair into the map.
+    /// Returns `Err` if the map is full and the key isn't already present.
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<Option<V>, SgError> {
+        let aug = H::Digest::default();
+        self.bst
+            .try_insert(key, AugVal { val, aug })
+            .map(|opt_av| opt_av.map(|av| av.val))
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get(key).map(|av| &av.val)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.contains_key(key)
+    }
+
+    /// Calls `f` on the value corresponding to the key, then updates digests along its root path.
+    /// Returns `false` if the key isn't present.
+    pub fn modify<Q, F>(&mut self, key: &Q, f: F) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+        F: FnOnce(&mut V),
+    {
+        match self.bst.priv_get::<Q, U>(None, key).node_idx() {
+            Some(idx) => {
+                f(&mut self.bst.arena[idx].get_mut().1.val);
+                self.bst.augment_root_path(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.remove(key).map(|av| av.val)
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    pub fn iter(&self) -> Iter<'_, K, V, H, N, U, C> {
+        Iter::new(self)
+    }
+
+    /// Returns the digest authenticating the whole map, `None` if empty. `O(1)`.
+    pub fn root_hash(&self) -> Option<&H::Digest> {
+        self.bst
+            .opt_root_idx
+            .map(|root_idx| &self.bst.arena[root_idx].val().aug)
+    }
+
+    /// Produces an inclusion proof for the pair at `key`, verifiable with [`MerkleProof::verify`].
+    /// The proof holds one step per ancestor, so `P` must be at least the key's depth (at most the tree's height).
+    /// Runs in `O(log n)` time.
+    ///
+    /// Returns `Err(SgError::KeyNotFound)` if the key isn't present,
+    /// or `Err(SgError::BufferTooSmall)` if the key's depth exceeds `P`.
+    pub fn prove<Q, const P: usize>(&self, key: &Q) -> Result<MerkleProof<K, V, H, P>, SgError>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        // Path holds parents only, root first
+        let mut path = StackVec::<[U; N]>::new();
+        let idx = self
+            .bst
+            .priv_get::<Q, U>(Some(&mut path), key)
+            .node_idx()
+            .ok_or(SgError::KeyNotFound)?;
+
+        if path.len() > P {
+            return Err(SgError::BufferTooSmall);
+        }
+
+        let digest = |opt_idx: Option<usize>| opt_idx.map(|i| self.bst.arena[i].val().aug.clone());
+        let node = &self.bst.arena[idx];
+        let mut proof = MerkleProof {
+            left: digest(node.left_idx()),
+            right: digest(node.right_idx()),
+            path: StackVec::new(),
+            _marker: PhantomData,
+        };
+
+        let mut child_idx = idx;
+        for parent_idx in path.iter().rev() {
+            let parent = &self.bst.arena[parent_idx.usize()];
+            let from_left = parent.left_idx() == Some(child_idx);
+            proof.path.push(MerkleStep {
+                pair: H::hash_pair(parent.key(), &parent.val().val),
+                sibling: digest(match from_left {
+                    true => parent.right_idx(),
+                    false => parent.left_idx(),
+                }),
+                from_left,
+            });
+            child_idx = parent_idx.usize();
+        }
+
+        Ok(proof)
+    }
+}
+
+// Convenience Traits --------------------------------------------------------------------------------------------------
+
+// Clone
+impl<K, V, H, const N: usize, U: ArenaIdx, C: Compare<K>> Clone for SgMerkleMap<K, V, H, N, U, C>
+where
+    K: Ord + Clone,
+    V: Clone,
+    H: MerkleHasher<K, V>,
+    C: Clone,
+{
+    fn clone(&self) -> Self {
+        SgMerkleMap {
+            bst: self.bst.clone(),
+        }
+    }
+}
+
+// Debug
+impl<K, V, H, const N: usize, U: ArenaIdx, C: Compare<K>> Debug for SgMerkleMap<K, V, H, N, U, C>
+where
+    K: Ord + Debug,
+    V: Debug,
+    H: MerkleHasher<K, V>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Default
+impl<K: Ord, V, H: MerkleHasher<K, V>, const N: usize, U: ArenaIdx, C: Compare<K> + Default> Default
+    for SgMerkleMap<K, V, H, N, U, C>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// From iterator
+impl<K: Ord, V, H: MerkleHasher<K, V>, const N: usize, U: ArenaIdx, C: Compare<K> + Default>
+    FromIterator<(K, V)> for SgMerkleMap<K, V, H, N, U, C>
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut mmap = SgMerkleMap::new();
+        mmap.extend(iter);
+        mmap
+    }
+}
+
+// Extension from iterator
+impl<K: Ord, V, H: MerkleHasher<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>> Extend<(K, V)>
+    for SgMerkleMap<K, V, H, N, U, C>
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(move |(k, v)| {
+            self.insert(k, v);
+        });
+    }
+}
+
+// Iterators -----------------------------------------------------------------------------------------------------------
+
+// Reference iterator
+impl<'a, K: Ord, V, H: MerkleHasher<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>> IntoIterator
+    for &'a SgMerkleMap<K, V, H, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, H, N, U, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Inclusion Proof -----------------------------------------------------------------------------------------------------
+
+/// Inclusion proof for a single key-value pair of a [`SgMerkleMap`][crate::SgMerkleMap], holding at most `P` steps.
+///
+/// This `struct` is created by the [`prove`][crate::SgMerkleMap::prove] method on [`SgMerkleMap`][crate::SgMerkleMap].
+/// See its documentation for more.
+pub struct MerkleProof<K, V, H: MerkleHasher<K, V>, const P: usize> {
+    left: Option<H::Digest>,
+    right: Option<H::Digest>,
+    path: StackVec<[MerkleStep<H::Digest>; P]>,
+    _marker: PhantomData<fn(&K, &V)>,
+}
+
+impl<K, V, H: MerkleHasher<K, V>, const P: usize> MerkleProof<K, V, H, P> {
+    /// Returns `true` if this proof shows `(key, val)` is included in the map whose root hash is `root_hash`.
+    /// Needs no access to the map itself. Runs in `O(len)` time, see [`len`][crate::MerkleProof::len].
+    pub fn verify(&self, root_hash: &H::Digest, key: &K, val: &V) -> bool {
+        let mut digest = H::hash_node(
+            &H::hash_pair(key, val),
+            self.left.as_ref(),
+            self.right.as_ref(),
+        );
+
+        for step in &self.path {
+            digest = match step.from_left {
+                true => H::hash_node(&step.pair, Some(&digest), step.sibling.as_ref()),
+                false => H::hash_node(&step.pair, step.sibling.as_ref(), Some(&digest)),
+            };
+        }
+
+        digest == *root_hash
+    }
+
+    /// Returns the number of steps in the proof, e.g. the proven key's depth (`0` for the root).
+    pub fn len(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Returns `true` if the proof has no steps, e.g. it proves the root pair.
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+}
+
+// Clone
+impl<K, V, H: MerkleHasher<K, V>, const P: usize> Clone for MerkleProof<K, V, H, P> {
+    fn clone(&self) -> Self {
+        MerkleProof {
+            left: self.left.clone(),
+            right: self.right.clone(),
+            path: self.path.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+// Debug
+impl<K, V, H: MerkleHasher<K, V>, const P: usize> Debug for MerkleProof<K, V, H, P>
+where
+    H::Digest: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MerkleProof")
+            .field("left", &self.left)
+            .field("right", &self.right)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+// Internal Types ------------------------------------------------------------------------------------------------------
+
+// One ancestor on the proven pair's root path, bottom-up.
+#[derive(Clone, Debug, Default)]
+struct MerkleStep<D> {
+    // Digest of the ancestor's own pair
+    pair: D,
+
+    // Digest of the ancestor's other child, if any
+    sibling: Option<D>,
+
+    // Whether the path arrives from the ancestor's left child
+    from_left: bool,
+}
+
+/// Augmentation callback, maintained by the tree through every insert, remove, and rebuild.
+pub(crate) struct UpdateDigest<H>(PhantomData<fn() -> H>);
+
+// Manual impls: `H` is only a marker, it needn't be `Clone` or `Copy` itself
+impl<H> Clone for UpdateDigest<H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H> Copy for UpdateDigest<H> {}
+
+impl<K, V, H: MerkleHasher<K, V>> AugmentHook<K, AugVal<V, H::Digest>> for UpdateDigest<H> {
+    fn augment(
+        key: &K,
+        node: &mut AugVal<V, H::Digest>,
+        opt_left: Option<&AugVal<V, H::Digest>>,
+        opt_right: Option<&AugVal<V, H::Digest>>,
+    ) {
+        node.aug = H::hash_node(
+            &H::hash_pair(key, &node.val),
+            opt_left.map(|child| &child.aug),
+            opt_right.map(|child| &child.aug),
+        );
+    }
+}
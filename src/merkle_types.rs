@@ -0,0 +1,69 @@
+use core::iter::FusedIterator;
+
+use crate::augment::AugVal;
+use crate::merkle::{MerkleHasher, SgMerkleMap, UpdateDigest};
+use crate::tree::{ArenaIdx, Compare, Iter as TreeIter, Natural};
+
+// General Iterators ---------------------------------------------------------------------------------------------------
+
+/// An iterator over the entries of a [`SgMerkleMap`][crate::SgMerkleMap].
+///
+/// This `struct` is created by the [`iter`][crate::SgMerkleMap::iter] method on [`SgMerkleMap`][crate::SgMerkleMap].
+/// See its documentation for more.
+pub struct Iter<
+    'a,
+    K: Ord,
+    V,
+    H: MerkleHasher<K, V>,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
+    ref_iter: TreeIter<'a, K, AugVal<V, H::Digest>, N, U, C, UpdateDigest<H>>,
+}
+
+impl<'a, K: Ord, V, H: MerkleHasher<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>>
+    Iter<'a, K, V, H, N, U, C>
+{
+    /// Construct reference iterator.
+    pub(crate) fn new(mmap: &'a SgMerkleMap<K, V, H, N, U, C>) -> Self {
+        Iter {
+            ref_iter: TreeIter::new(&mmap.bst),
+        }
+    }
+}
+
+impl<'a, K: Ord, V, H: MerkleHasher<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for Iter<'a, K, V, H, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next().map(|(k, av)| (k, &av.val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ref_iter.size_hint()
+    }
+}
+
+impl<'a, K: Ord, V, H: MerkleHasher<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>>
+    DoubleEndedIterator for Iter<'a, K, V, H, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next_back().map(|(k, av)| (k, &av.val))
+    }
+}
+
+impl<'a, K: Ord, V, H: MerkleHasher<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>>
+    ExactSizeIterator for Iter<'a, K, V, H, N, U, C>
+{
+    fn len(&self) -> usize {
+        self.ref_iter.len()
+    }
+}
+
+impl<'a, K: Ord, V, H: MerkleHasher<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for Iter<'a, K, V, H, N, U, C>
+{
+}
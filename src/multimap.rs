@@ -0,0 +1,393 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+
+use crate::multimap_types::{GetAll, Iter};
+use crate::tree::{ArenaIdx, Compare, Natural, SgError, SgTree, SmallNode};
+
+/// Safe, fallible, embedded-friendly ordered multimap: like [`SgMap`][crate::SgMap], but equal keys may repeat.
+///
+/// Pairs are ordered by key, and pairs with equal keys are ordered by insertion (rank-stable).
+/// Every pair occupies its own slot, so capacity `N` bounds the total number of pairs, not distinct keys.
+///
+/// ### Fallible APIs
+///
+/// * [`try_insert`][crate::SgMultiMap::try_insert]
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgMultiMap;
+///
+/// let mut events = SgMultiMap::<_, _, 10>::new();
+/// events.insert(2, "b1");
+/// events.insert(1, "a");
+/// events.insert(2, "b2");
+///
+/// // Duplicates are kept, in insertion order
+/// assert!(events.get_all(&2).eq([&"b1", &"b2"]));
+/// assert_eq!(events.len(), 3);
+///
+/// // Remove the oldest duplicate
+/// assert_eq!(events.remove_one(&2), Some("b1"));
+/// assert!(events.iter().eq([(&1, &"a"), (&2, &"b2")]));
+/// ```
+#[derive(Default, Clone)]
+pub struct SgMultiMap<K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    pub(crate) bst: SgTree<MultiKey<K>, V, N, U, MultiCmp<C>>,
+    next_seq: u64,
+}
+
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> SgMultiMap<K, V, N, U, C> {
+    /// Makes a new, empty `SgMultiMap`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMultiMap;
+    ///
+    /// let mut map: SgMultiMap<isize, &str, 10> = SgMultiMap::new();
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_comparator(C::default())
+    }
+
+    /// Makes a new, empty `SgMultiMap` ordered by `cmp` instead of `K`'s [`Ord`] implementation.
+    /// See [`Compare`][crate::Compare].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMultiMap, Reversed};
+    ///
+    /// let mut map = SgMultiMap::<_, _, 10, u16, Reversed>::with_comparator(Reversed::default());
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(1, "c");
+    ///
+    /// assert!(map.iter().eq([(&2, &"b"), (&1, &"a"), (&1, &"c")]));
+    /// ```
+    pub fn with_comparator(cmp: C) -> Self {
+        SgMultiMap {
+            bst: SgTree::with_comparator(MultiCmp(cmp)),
+            next_seq: 0,
+        }
+    }
+
+    /// Total capacity, e.g. maximum number of pairs, including duplicates.
+    pub fn capacity(&self) -> usize {
+        self.bst.capacity()
+    }
+
+    /// Returns the number of pairs in the map, including duplicates.
+    pub fn len(&self) -> usize {
+        self.bst.len()
+    }
+
+    /// Returns `true` if the map contains no pairs.
+    pub fn is_empty(&self) -> bool {
+        self.bst.is_empty()
+    }
+
+    /// Returns `true` if the map's capacity is filled.
+    pub fn is_full(&self) -> bool {
+        self.bst.is_full()
+    }
+
+    /// Clears the map, removing all pairs.
+    pub fn clear(&mut self) {
+        self.bst.clear();
+        self.next_seq = 0;
+    }
+
+    /// Inserts a key-value pair into the map.
+    /// Existing pairs with an equal key are kept, the new pair is ordered after them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is full. See [`try_insert`][crate::SgMultiMap::try_insert] for a fallible alternative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMultiMap;
+    ///
+    /// let mut map = SgMultiMap::<_, _, 10>::new();
+    /// map.insert(37, "a");
+    /// map.insert(37, "b");
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map.get(&37), Some(&"a"));
+    /// ```
+    pub fn insert(&mut self, key: K, val: V) {
+        let mk = self.next_key(key);
+        self.bst.insert(mk, val);
+    }
+
+    /// Inserts a key-value pair into the map.
+    /// Returns `Err` if the map is full, existing pairs with an equal key are kept otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgMultiMap, SgError};
+    ///
+    /// let mut map = SgMultiMap::<_, _, 2>::new();
+    /// assert!(map.try_insert(1, "a").is_ok());
+    /// assert!(map.try_insert(1, "b").is_ok());
+    /// assert_eq!(map.try_insert(1, "c"), Err(SgError::StackCapacityExceeded));
+    /// ```
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<(), SgError> {
+        match self.is_full() {
+            true => Err(SgError::StackCapacityExceeded),
+            false => {
+                self.insert(key, val);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns a reference to the value of the first (oldest) pair with the given key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMultiMap;
+    ///
+    /// let mut map = SgMultiMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// map.insert(1, "b");
+    ///
+    /// assert_eq!(map.get(&1), Some(&"a"));
+    /// assert_eq!(map.get(&2), None);
+    /// ```
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.first_idx(key).map(|idx| self.bst.arena[idx].val())
+    }
+
+    /// Gets an iterator over the values of every pair with the given key, in insertion order.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMultiMap;
+    ///
+    /// let mut map = SgMultiMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(1, "c");
+    ///
+    /// assert!(map.get_all(&1).eq([&"a", &"c"]));
+    /// assert_eq!(map.get_all(&3).count(), 0);
+    /// ```
+    pub fn get_all<Q>(&self, key: &Q) -> GetAll<'_, K, V, N, U, C>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let cmp = &self.bst.cmp.0;
+        let opt_end_idx = self
+            .bst
+            .partition_point_idx(|mk| cmp.compare(mk.key.borrow(), key) != Ordering::Greater);
+
+        GetAll::new(self, self.first_idx(key), opt_end_idx)
+    }
+
+    /// Returns `true` if the map contains at least one pair with the given key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.first_idx(key).is_some()
+    }
+
+    /// Removes the first (oldest) pair with the given key, returning its value.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMultiMap;
+    ///
+    /// let mut map = SgMultiMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// map.insert(1, "b");
+    ///
+    /// assert_eq!(map.remove_one(&1), Some("a"));
+    /// assert_eq!(map.remove_one(&1), Some("b"));
+    /// assert_eq!(map.remove_one(&1), None);
+    /// ```
+    pub fn remove_one<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let idx = self.first_idx(key)?;
+        let (_, val) = self.bst.priv_remove_by_idx(idx)?;
+        self.bst.rebuild_if_sparse();
+        Some(val)
+    }
+
+    /// Removes every pair with the given key, returning the number of pairs removed.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgMultiMap;
+    ///
+    /// let mut map = SgMultiMap::<_, _, 10>::new();
+    /// map.insert(1, "a");
+    /// map.insert(2, "b");
+    /// map.insert(1, "c");
+    ///
+    /// assert_eq!(map.remove_all(&1), 2);
+    /// assert!(map.iter().eq([(&2, &"b")]));
+    /// ```
+    pub fn remove_all<Q>(&mut self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let mut cnt = 0;
+        while let Some(idx) = self.first_idx(key) {
+            self.bst.priv_remove_by_idx(idx);
+            cnt += 1;
+        }
+
+        // Single rebuild check for the whole batch
+        if cnt > 0 {
+            self.bst.rebuild_if_sparse();
+        }
+
+        cnt
+    }
+
+    /// Gets an iterator over the pairs of the map, sorted by key, with equal keys in insertion order.
+    pub fn iter(&self) -> Iter<'_, K, V, N, U, C> {
+        Iter::new(self)
+    }
+
+    // Private API -----------------------------------------------------------------------------------------------------
+
+    // Wrap `key` with the next insertion sequence number, so it sorts after existing equal keys.
+    // A `u64` counter can't realistically wrap: that'd take centuries at a billion inserts per second.
+    fn next_key(&mut self, key: K) -> MultiKey<K> {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        MultiKey { key, seq }
+    }
+
+    // Arena index of the first (oldest) pair with `key`, if any.
+    pub(crate) fn first_idx<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let cmp = &self.bst.cmp.0;
+        let idx = self
+            .bst
+            .partition_point_idx(|mk| cmp.compare(mk.key.borrow(), key) == Ordering::Less)?;
+
+        match cmp.compare(self.bst.arena[idx].key().key.borrow(), key) {
+            Ordering::Equal => Some(idx),
+            _ => None,
+        }
+    }
+}
+
+// Internal Key Ordering -----------------------------------------------------------------------------------------------
+
+// User key plus insertion sequence number: makes equal keys distinct and orders them by insertion.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct MultiKey<K> {
+    pub(crate) key: K,
+    pub(crate) seq: u64,
+}
+
+// Orders by user key (via the user's comparator), then by insertion sequence number.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct MultiCmp<C>(pub(crate) C);
+
+impl<K, C: Compare<K>> Compare<MultiKey<K>> for MultiCmp<C> {
+    #[inline]
+    fn compare(&self, a: &MultiKey<K>, b: &MultiKey<K>) -> Ordering {
+        self.0
+            .compare(&a.key, &b.key)
+            .then_with(|| a.seq.cmp(&b.seq))
+    }
+}
+
+// Convenience Traits --------------------------------------------------------------------------------------------------
+
+// Debug
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Debug for SgMultiMap<K, V, N, U, C>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// From iterator
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K> + Default> FromIterator<(K, V)>
+    for SgMultiMap<K, V, N, U, C>
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut mmap = SgMultiMap::new();
+        mmap.extend(iter);
+        mmap
+    }
+}
+
+// Extension from iterator.
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Extend<(K, V)>
+    for SgMultiMap<K, V, N, U, C>
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(move |(k, v)| self.insert(k, v));
+    }
+}
+
+// Reference iterator
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> IntoIterator
+    for &'a SgMultiMap<K, V, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, N, U, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,108 @@
+use core::iter::FusedIterator;
+use core::ops::Bound::Excluded;
+
+use crate::multimap::{MultiCmp, MultiKey, SgMultiMap};
+use crate::tree::{ArenaIdx, Compare, Iter as TreeIter, Natural, SmallNode};
+
+// General Iterators ---------------------------------------------------------------------------------------------------
+
+/// An iterator over the entries of a [`SgMultiMap`][crate::SgMultiMap].
+///
+/// This `struct` is created by the [`iter`][crate::SgMultiMap::iter] method on [`SgMultiMap`][crate::SgMultiMap].
+/// See its documentation for more.
+pub struct Iter<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    ref_iter: TreeIter<'a, MultiKey<K>, V, N, U, MultiCmp<C>>,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iter<'a, K, V, N, U, C> {
+    /// Construct reference iterator.
+    pub(crate) fn new(mmap: &'a SgMultiMap<K, V, N, U, C>) -> Self {
+        Iter {
+            ref_iter: TreeIter::new(&mmap.bst),
+        }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for Iter<'a, K, V, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next().map(|(mk, v)| (&mk.key, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ref_iter.size_hint()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for Iter<'a, K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next_back().map(|(mk, v)| (&mk.key, v))
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for Iter<'a, K, V, N, U, C>
+{
+    fn len(&self) -> usize {
+        self.ref_iter.len()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for Iter<'a, K, V, N, U, C>
+{
+}
+
+// Key Iterators -------------------------------------------------------------------------------------------------------
+
+/// An iterator over the values of every entry with a given key in a [`SgMultiMap`][crate::SgMultiMap],
+/// in insertion order.
+///
+/// This `struct` is created by the [`get_all`][crate::SgMultiMap::get_all] method on [`SgMultiMap`][crate::SgMultiMap].
+/// See its documentation for more.
+pub struct GetAll<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    table: &'a SgMultiMap<K, V, N, U, C>,
+    opt_next_idx: Option<usize>,
+    opt_end_idx: Option<usize>,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> GetAll<'a, K, V, N, U, C> {
+    // Iterates in-order from `opt_start_idx` up to, but excluding, `opt_end_idx` (`None` meaning the end of the map).
+    // Holds no traversal state, each step is a bounded search from the root.
+    pub(crate) fn new(
+        table: &'a SgMultiMap<K, V, N, U, C>,
+        opt_start_idx: Option<usize>,
+        opt_end_idx: Option<usize>,
+    ) -> Self {
+        GetAll {
+            table,
+            opt_next_idx: opt_start_idx,
+            opt_end_idx,
+        }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for GetAll<'a, K, V, N, U, C>
+{
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self
+            .opt_next_idx
+            .filter(|idx| Some(*idx) != self.opt_end_idx)?;
+        let node = &self.table.bst.arena[idx];
+        self.opt_next_idx = self.table.bst.lower_bound_idx(Excluded(node.key()));
+        Some(node.val())
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for GetAll<'a, K, V, N, U, C>
+{
+}
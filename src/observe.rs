@@ -0,0 +1,292 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::ops::Deref;
+
+use crate::map::SgMap;
+use crate::tree::{ArenaIdx, Compare, Natural, NodeHandle, SgError};
+
+/// Kind of mutation reported to an [`Observer`][crate::Observer].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Change {
+    /// A new key was inserted, with the given value.
+    Inserted,
+
+    /// A present key's value was replaced or modified, to the given value.
+    Updated,
+
+    /// A key was removed, along with the given value.
+    Removed,
+}
+
+/// Receives a notification for every mutation of a [`SgObservedMap`][crate::SgObservedMap].
+///
+/// Implemented for any closure of the form `FnMut(Change, &K, &V)`.
+/// Notifications are delivered synchronously, after the map has been updated.
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::{Change, Observer};
+///
+/// // Keys changed since a derived cache was last synced
+/// struct Dirty(Vec<u32>);
+///
+/// impl<V> Observer<u32, V> for Dirty {
+///     fn notify(&mut self, _change: Change, key: &u32, _val: &V) {
+///         if !self.0.contains(key) {
+///             self.0.push(*key);
+///         }
+///     }
+/// }
+/// ```
+pub trait Observer<K, V> {
+    /// Called once per mutated key, with the value now stored (or, for [`Change::Removed`], just removed).
+    fn notify(&mut self, change: Change, key: &K, val: &V);
+}
+
+impl<K, V, F: FnMut(Change, &K, &V)> Observer<K, V> for F {
+    fn notify(&mut self, change: Change, key: &K, val: &V) {
+        self(change, key, val)
+    }
+}
+
+/// A [`SgMap`][crate::SgMap] that reports every insert, update, and removal to an [`Observer`][crate::Observer].
+///
+/// Dereferences to the inner map for all read-only APIs.
+/// Mutation is only possible through this wrapper's own methods, so no change goes unreported.
+/// Values are modified via [`modify`][crate::SgObservedMap::modify], which notifies once the closure returns.
+///
+/// ### Fallible APIs
+///
+/// * [`try_insert`][crate::SgObservedMap::try_insert]
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::{Change, SgObservedMap};
+///
+/// let mut log = Vec::new();
+/// let mut map = SgObservedMap::<_, _, _, 10>::new(|c: Change, k: &u8, _: &&str| log.push((c, *k)));
+///
+/// map.insert(1, "a");
+/// map.insert(1, "b");
+/// map.modify(&1, |v| *v = "c");
+/// map.remove(&1);
+/// assert!(map.is_empty());
+///
+/// drop(map);
+/// assert_eq!(
+///     log,
+///     [(Change::Inserted, 1), (Change::Updated, 1), (Change::Updated, 1), (Change::Removed, 1)]
+/// );
+/// ```
+pub struct SgObservedMap<
+    K: Ord,
+    V,
+    O: Observer<K, V>,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+> {
+    map: SgMap<K, V, N, U, C>,
+    observer: O,
+}
+
+impl<K: Ord, V, O: Observer<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>>
+    SgObservedMap<K, V, O, N, U, C>
+{
+    /// Makes a new, empty `SgObservedMap` reporting to `observer`.
+    pub fn new(observer: O) -> Self
+    where
+        C: Default,
+    {
+        Self::with_comparator(observer, C::default())
+    }
+
+    /// Makes a new, empty `SgObservedMap` reporting to `observer`,
+    /// ordered by `cmp` instead of `K`'s [`Ord`] implementation. See [`Compare`][crate::Compare].
+    pub fn with_comparator(observer: O, cmp: C) -> Self {
+        SgObservedMap {
+            map: SgMap::with_comparator(cmp),
+            observer,
+        }
+    }
+
+    /// Returns a reference to the observer.
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Returns a mutable reference to the observer.
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    /// Consumes the wrapper, returning the inner map and the observer.
+    pub fn into_parts(self) -> (SgMap<K, V, N, U, C>, O) {
+        (self.map, self.observer)
+    }
+
+    /// Insert a key-value pair into the map, reporting [`Change::Inserted`] or [`Change::Updated`].
+    /// If the map did not have this key present, `None` is returned.
+    /// If the map did have this key present, the value is updated, the old value is returned,
+    /// and the key is updated. This accommodates types that can be `==` without being identical.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is full and the key isn't already present.
+    /// See [`try_insert`][crate::SgObservedMap::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        let (opt_old, handle) = self.map.insert_with_handle(key, val);
+        self.notify_handle(Self::insert_change(&opt_old), handle);
+        opt_old
+    }
+
+    /// Insert a key-value pair into the map, reporting [`Change::Inserted`] or [`Change::Updated`].
+    /// Returns `Err` if the map is full and the key isn't already present, in which case nothing is reported.
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<Option<V>, SgError> {
+        let (opt_old, handle) = self.map.try_insert_with_handle(key, val)?;
+        self.notify_handle(Self::insert_change(&opt_old), handle);
+        Ok(opt_old)
+    }
+
+    /// Calls `f` on the value corresponding to the key, then reports [`Change::Updated`].
+    /// Returns `false` (and reports nothing) if the key isn't present.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    pub fn modify<Q, F>(&mut self, key: &Q, f: F) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+        F: FnOnce(&mut V),
+    {
+        match self.map.get_handle(key) {
+            Some(handle) => {
+                if let Some(val) = self.map.get_mut_by_handle(handle) {
+                    f(val);
+                }
+                self.notify_handle(Change::Updated, handle);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes a key from the map, reporting [`Change::Removed`].
+    /// Returns the value at the key if the key was previously in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map, reporting [`Change::Removed`].
+    /// Returns the stored key and value if the key was previously in the map.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        let opt_kv = self.map.remove_entry(key);
+        self.notify_removed(opt_kv)
+    }
+
+    /// Removes and returns the first element in the map, reporting [`Change::Removed`].
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        let opt_kv = self.map.pop_first();
+        self.notify_removed(opt_kv)
+    }
+
+    /// Removes and returns the last element in the map, reporting [`Change::Removed`].
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        let opt_kv = self.map.pop_last();
+        self.notify_removed(opt_kv)
+    }
+
+    /// Retains only the elements specified by the predicate, reporting [`Change::Removed`] for each other element.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let observer = &mut self.observer;
+        self.map.retain(|k, v| {
+            let keep = f(k, v);
+            if !keep {
+                observer.notify(Change::Removed, k, v);
+            }
+            keep
+        });
+    }
+
+    /// Clears the map, reporting [`Change::Removed`] for every element (in ascending key order).
+    pub fn clear(&mut self) {
+        for (k, v) in self.map.drain() {
+            self.observer.notify(Change::Removed, &k, &v);
+        }
+    }
+
+    // Notification kind for an insert, given the value it replaced (if any).
+    fn insert_change(opt_old: &Option<V>) -> Change {
+        match opt_old {
+            Some(_) => Change::Updated,
+            None => Change::Inserted,
+        }
+    }
+
+    // Report a change to the pair a fresh handle refers to.
+    fn notify_handle(&mut self, change: Change, handle: NodeHandle) {
+        if let Some((k, v)) = self.map.get_by_handle(handle) {
+            self.observer.notify(change, k, v);
+        }
+    }
+
+    // Report a removal, passing the removed pair through.
+    fn notify_removed(&mut self, opt_kv: Option<(K, V)>) -> Option<(K, V)> {
+        if let Some((k, v)) = &opt_kv {
+            self.observer.notify(Change::Removed, k, v);
+        }
+        opt_kv
+    }
+}
+
+// Convenience Traits --------------------------------------------------------------------------------------------------
+
+// Read-only access to the inner map
+impl<K: Ord, V, O: Observer<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>> Deref
+    for SgObservedMap<K, V, O, N, U, C>
+{
+    type Target = SgMap<K, V, N, U, C>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+// Debug
+impl<K, V, O, const N: usize, U: ArenaIdx, C: Compare<K>> Debug for SgObservedMap<K, V, O, N, U, C>
+where
+    K: Ord + Debug,
+    V: Debug,
+    O: Observer<K, V>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+// Extension from iterator
+impl<K: Ord, V, O: Observer<K, V>, const N: usize, U: ArenaIdx, C: Compare<K>> Extend<(K, V)>
+    for SgObservedMap<K, V, O, N, U, C>
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(move |(k, v)| {
+            self.insert(k, v);
+        });
+    }
+}
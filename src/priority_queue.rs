@@ -0,0 +1,221 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::iter::FromIterator;
+
+use crate::multimap::SgMultiMap;
+use crate::priority_queue_types::Iter;
+use crate::tree::{ArenaIdx, Compare, Natural, SgError};
+
+/// Double-ended, fixed-capacity priority queue: elements are their own priorities, ordered by [`Ord`] (or `C`).
+///
+/// Both ends are cached, so [`peek_min`][crate::SgPriorityQueue::peek_min] and
+/// [`peek_max`][crate::SgPriorityQueue::peek_max] are `O(1)`.
+/// [`push`][crate::SgPriorityQueue::push], [`pop_min`][crate::SgPriorityQueue::pop_min],
+/// [`pop_max`][crate::SgPriorityQueue::pop_max], and [`change_priority`][crate::SgPriorityQueue::change_priority]
+/// are `O(log n)`.
+///
+/// Equal elements may repeat, and are popped from either end in insertion order (FIFO).
+///
+/// ### Fallible APIs
+///
+/// * [`try_push`][crate::SgPriorityQueue::try_push]
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgPriorityQueue;
+///
+/// #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// struct Job {
+///     deadline: u32,
+///     id: char,
+/// }
+///
+/// let mut jobs = SgPriorityQueue::<Job, 10>::new();
+/// jobs.push(Job { deadline: 30, id: 'a' });
+/// jobs.push(Job { deadline: 10, id: 'b' });
+/// jobs.push(Job { deadline: 20, id: 'c' });
+///
+/// assert_eq!(jobs.peek_min().map(|j| j.id), Some('b'));
+/// assert_eq!(jobs.pop_max().map(|j| j.id), Some('a'));
+///
+/// // Postpone the most urgent job
+/// assert!(jobs.change_priority(&Job { deadline: 10, id: 'b' }, |j| j.deadline = 40));
+/// assert_eq!(jobs.pop_min().map(|j| j.id), Some('c'));
+/// assert_eq!(jobs.pop_min().map(|j| j.id), Some('b'));
+/// assert!(jobs.is_empty());
+/// ```
+#[derive(Default, Clone)]
+pub struct SgPriorityQueue<T: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    pub(crate) mmap: SgMultiMap<T, (), N, U, C>,
+}
+
+impl<T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> SgPriorityQueue<T, N, U, C> {
+    /// Makes a new, empty `SgPriorityQueue`.
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_comparator(C::default())
+    }
+
+    /// Makes a new, empty `SgPriorityQueue` ordered by `cmp` instead of `T`'s [`Ord`] implementation.
+    /// See [`Compare`][crate::Compare].
+    pub fn with_comparator(cmp: C) -> Self {
+        SgPriorityQueue {
+            mmap: SgMultiMap::with_comparator(cmp),
+        }
+    }
+
+    /// Total capacity, e.g. maximum number of elements, including duplicates.
+    pub fn capacity(&self) -> usize {
+        self.mmap.capacity()
+    }
+
+    /// Returns the number of elements in the queue, including duplicates.
+    pub fn len(&self) -> usize {
+        self.mmap.len()
+    }
+
+    /// Returns `true` if the queue contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.mmap.is_empty()
+    }
+
+    /// Returns `true` if the queue's capacity is filled.
+    pub fn is_full(&self) -> bool {
+        self.mmap.is_full()
+    }
+
+    /// Clears the queue, removing all elements.
+    pub fn clear(&mut self) {
+        self.mmap.clear();
+    }
+
+    /// Pushes an element into the queue, after any equal elements already present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the queue is full. See [`try_push`][crate::SgPriorityQueue::try_push] for a fallible alternative.
+    #[doc(alias = "insert")]
+    pub fn push(&mut self, item: T) {
+        self.mmap.insert(item, ());
+    }
+
+    /// Pushes an element into the queue, after any equal elements already present.
+    /// Returns `Err` if the queue is full.
+    pub fn try_push(&mut self, item: T) -> Result<(), SgError> {
+        self.mmap.try_insert(item, ())
+    }
+
+    /// Returns a reference to the minimum element, `None` if empty. `O(1)`.
+    #[doc(alias = "peek")]
+    pub fn peek_min(&self) -> Option<&T> {
+        self.mmap.bst.first_key().map(|mk| &mk.key)
+    }
+
+    /// Returns a reference to the maximum element, `None` if empty. `O(1)`.
+    pub fn peek_max(&self) -> Option<&T> {
+        self.mmap.bst.last_key().map(|mk| &mk.key)
+    }
+
+    /// Removes and returns the minimum element, `None` if empty.
+    #[doc(alias = "pop")]
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.mmap.bst.pop_first().map(|(mk, _)| mk.key)
+    }
+
+    /// Removes and returns the maximum element, `None` if empty.
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.mmap.bst.pop_last().map(|(mk, _)| mk.key)
+    }
+
+    /// Calls `f` on the first (oldest) element equal to `item`, then repositions it according to its new priority.
+    /// The changed element is ordered after any elements equal to it.
+    /// Returns `false` if no such element is present.
+    ///
+    /// The item may be any borrowed form of the queue's element type, but the ordering
+    /// on the borrowed form *must* match the ordering on the element type.
+    #[doc(alias = "update")]
+    pub fn change_priority<Q, F>(&mut self, item: &Q, f: F) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+        F: FnOnce(&mut T),
+    {
+        let opt_mk = self
+            .mmap
+            .first_idx(item)
+            .and_then(|idx| self.mmap.bst.priv_remove_by_idx(idx));
+
+        match opt_mk {
+            Some((mk, _)) => {
+                let mut item = mk.key;
+                f(&mut item);
+
+                // Removal freed a slot, so re-insertion can't exceed capacity
+                self.mmap.insert(item, ());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the queue contains an element equal to `item`.
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.mmap.contains_key(item)
+    }
+
+    /// Gets an iterator that visits the elements in ascending priority order.
+    pub fn iter(&self) -> Iter<'_, T, N, U, C> {
+        Iter::new(self)
+    }
+}
+
+// Convenience Traits --------------------------------------------------------------------------------------------------
+
+// Debug
+impl<T, const N: usize, U: ArenaIdx, C: Compare<T>> Debug for SgPriorityQueue<T, N, U, C>
+where
+    T: Ord + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// From iterator
+impl<T: Ord, const N: usize, U: ArenaIdx, C: Compare<T> + Default> FromIterator<T>
+    for SgPriorityQueue<T, N, U, C>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut pq = SgPriorityQueue::new();
+        pq.extend(iter);
+        pq
+    }
+}
+
+// Extension from iterator.
+impl<T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Extend<T> for SgPriorityQueue<T, N, U, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(move |item| self.push(item));
+    }
+}
+
+// Reference iterator
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> IntoIterator
+    for &'a SgPriorityQueue<T, N, U, C>
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, N, U, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
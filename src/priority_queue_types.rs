@@ -0,0 +1,57 @@
+use core::iter::FusedIterator;
+
+use crate::multimap_types::Iter as MultiMapIter;
+use crate::priority_queue::SgPriorityQueue;
+use crate::tree::{ArenaIdx, Compare, Natural};
+
+// General Iterators ---------------------------------------------------------------------------------------------------
+
+/// An iterator over the elements of a [`SgPriorityQueue`][crate::SgPriorityQueue], in ascending priority order.
+///
+/// This `struct` is created by the [`iter`][crate::SgPriorityQueue::iter] method on
+/// [`SgPriorityQueue`][crate::SgPriorityQueue]. See its documentation for more.
+pub struct Iter<'a, T: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    ref_iter: MultiMapIter<'a, T, (), N, U, C>,
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Iter<'a, T, N, U, C> {
+    /// Construct reference iterator.
+    pub(crate) fn new(pq: &'a SgPriorityQueue<T, N, U, C>) -> Self {
+        Iter {
+            ref_iter: pq.mmap.iter(),
+        }
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Iterator for Iter<'a, T, N, U, C> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next().map(|(item, _)| item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ref_iter.size_hint()
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> DoubleEndedIterator
+    for Iter<'a, T, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next_back().map(|(item, _)| item)
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> ExactSizeIterator
+    for Iter<'a, T, N, U, C>
+{
+    fn len(&self) -> usize {
+        self.ref_iter.len()
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> FusedIterator
+    for Iter<'a, T, N, U, C>
+{
+}
@@ -0,0 +1,165 @@
+use core::fmt;
+use core::ops::ControlFlow;
+
+use rkyv::collections::btree_map::{ArchivedBTreeMap, BTreeMapResolver};
+use rkyv::collections::btree_set::{ArchivedBTreeSet, BTreeSetResolver};
+use rkyv::rancor::{fail, Fallible, Source};
+use rkyv::ser::{Allocator, Writer};
+use rkyv::{Archive, Deserialize, Place, Serialize};
+
+use crate::{ArenaIdx, Compare, Natural, SgMap, SgSet};
+
+// Archived as `rkyv`'s own B-tree collections, built from the already-sorted iterator.
+// The archive is directly queryable in place (`get`, `contains_key`, `visit`) and layout-identical to an archived
+// `BTreeMap`/`BTreeSet`. Archived lookups use `Ord`, hence only the `Natural` comparator can be archived.
+// Any comparator can be deserialized into: pairs are re-inserted.
+
+// Archived collection has more entries than the target's capacity.
+#[derive(Debug)]
+struct CapacityExceeded {
+    len: usize,
+    capacity: usize,
+}
+
+impl fmt::Display for CapacityExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "archived collection has {} entries, exceeding capacity {}",
+            self.len, self.capacity
+        )
+    }
+}
+
+impl core::error::Error for CapacityExceeded {}
+
+// Map -----------------------------------------------------------------------------------------------------------------
+
+impl<K, V, const N: usize, U: ArenaIdx> Archive for SgMap<K, V, N, U, Natural>
+where
+    K: Archive + Ord,
+    K::Archived: Ord,
+    V: Archive,
+{
+    type Archived = ArchivedBTreeMap<K::Archived, V::Archived>;
+    type Resolver = BTreeMapResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedBTreeMap::resolve_from_len(self.len(), resolver, out);
+    }
+}
+
+impl<K, V, const N: usize, U: ArenaIdx, S> Serialize<S> for SgMap<K, V, N, U, Natural>
+where
+    K: Serialize<S> + Ord,
+    K::Archived: Ord,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        Self::Archived::serialize_from_ordered_iter::<_, _, _, K, V, _>(self.iter(), serializer)
+    }
+}
+
+impl<K, V, const N: usize, U: ArenaIdx, C, D> Deserialize<SgMap<K, V, N, U, C>, D>
+    for ArchivedBTreeMap<K::Archived, V::Archived>
+where
+    K: Archive + Ord,
+    K::Archived: Deserialize<K, D>,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    C: Compare<K> + Default,
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<SgMap<K, V, N, U, C>, D::Error> {
+        if self.len() > N {
+            fail!(CapacityExceeded {
+                len: self.len(),
+                capacity: N,
+            });
+        }
+
+        let mut map = SgMap::new();
+        let opt_err = self.visit(|archived_key, archived_val| {
+            let key = match archived_key.deserialize(deserializer) {
+                Ok(key) => key,
+                Err(err) => return ControlFlow::Break(err),
+            };
+            let val = match archived_val.deserialize(deserializer) {
+                Ok(val) => val,
+                Err(err) => return ControlFlow::Break(err),
+            };
+            map.insert(key, val);
+            ControlFlow::Continue(())
+        });
+
+        match opt_err {
+            Some(err) => Err(err),
+            None => Ok(map),
+        }
+    }
+}
+
+// Set -----------------------------------------------------------------------------------------------------------------
+
+impl<T, const N: usize, U: ArenaIdx> Archive for SgSet<T, N, U, Natural>
+where
+    T: Archive + Ord,
+    T::Archived: Ord,
+{
+    type Archived = ArchivedBTreeSet<T::Archived>;
+    type Resolver = BTreeSetResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedBTreeSet::resolve_from_len(self.len(), resolver, out);
+    }
+}
+
+impl<T, const N: usize, U: ArenaIdx, S> Serialize<S> for SgSet<T, N, U, Natural>
+where
+    T: Serialize<S> + Ord,
+    T::Archived: Ord,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        Self::Archived::serialize_from_ordered_iter::<_, T, _>(self.iter(), serializer)
+    }
+}
+
+impl<T, const N: usize, U: ArenaIdx, C, D> Deserialize<SgSet<T, N, U, C>, D>
+    for ArchivedBTreeSet<T::Archived>
+where
+    T: Archive + Ord,
+    T::Archived: Deserialize<T, D>,
+    C: Compare<T> + Default,
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<SgSet<T, N, U, C>, D::Error> {
+        if self.len() > N {
+            fail!(CapacityExceeded {
+                len: self.len(),
+                capacity: N,
+            });
+        }
+
+        let mut set = SgSet::new();
+        let opt_err = self.visit(
+            |archived_elem| match archived_elem.deserialize(deserializer) {
+                Ok(elem) => {
+                    set.insert(elem);
+                    ControlFlow::Continue(())
+                }
+                Err(err) => ControlFlow::Break(err),
+            },
+        );
+
+        match opt_err {
+            Some(err) => Err(err),
+            None => Ok(set),
+        }
+    }
+}
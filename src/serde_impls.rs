@@ -0,0 +1,128 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use crate::{ArenaIdx, Compare, SgMap, SgSet};
+
+// Serialize -----------------------------------------------------------------------------------------------------------
+
+// Map, as an ordered map.
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Serialize for SgMap<K, V, N, U, C>
+where
+    K: Ord + Serialize,
+    V: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (k, v) in self.iter() {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
+
+// Set, as an ordered sequence of elements.
+impl<T, const N: usize, U: ArenaIdx, C: Compare<T>> Serialize for SgSet<T, N, U, C>
+where
+    T: Ord + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for elem in self.iter() {
+            seq.serialize_element(elem)?;
+        }
+        seq.end()
+    }
+}
+
+// Deserialize ---------------------------------------------------------------------------------------------------------
+
+// Input is not required to be sorted. Duplicate keys keep the last value, like `BTreeMap`.
+// Running out of capacity is reported as an `invalid_length` error, never a panic. Length hints aren't trusted
+// up front: duplicates mean a longer input can still fit.
+
+struct MapVisitor<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> {
+    marker: PhantomData<SgMap<K, V, N, U, C>>,
+}
+
+impl<'de, K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Visitor<'de>
+    for MapVisitor<K, V, N, U, C>
+where
+    K: Ord + Deserialize<'de>,
+    V: Deserialize<'de>,
+    C: Default,
+{
+    type Value = SgMap<K, V, N, U, C>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a map with at most {} entries", N)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut map = SgMap::new();
+
+        while let Some((k, v)) = access.next_entry()? {
+            if map.try_insert(k, v).is_err() {
+                return Err(A::Error::invalid_length(map.len() + 1, &self));
+            }
+        }
+
+        Ok(map)
+    }
+}
+
+impl<'de, K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Deserialize<'de>
+    for SgMap<K, V, N, U, C>
+where
+    K: Ord + Deserialize<'de>,
+    V: Deserialize<'de>,
+    C: Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(MapVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+struct SetVisitor<T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> {
+    marker: PhantomData<SgSet<T, N, U, C>>,
+}
+
+impl<'de, T, const N: usize, U: ArenaIdx, C: Compare<T>> Visitor<'de> for SetVisitor<T, N, U, C>
+where
+    T: Ord + Deserialize<'de>,
+    C: Default,
+{
+    type Value = SgSet<T, N, U, C>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a sequence with at most {} elements", N)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut set = SgSet::new();
+
+        while let Some(elem) = access.next_element()? {
+            if set.try_insert(elem).is_err() {
+                return Err(A::Error::invalid_length(set.len() + 1, &self));
+            }
+        }
+
+        Ok(set)
+    }
+}
+
+impl<'de, T, const N: usize, U: ArenaIdx, C: Compare<T>> Deserialize<'de> for SgSet<T, N, U, C>
+where
+    T: Ord + Deserialize<'de>,
+    C: Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(SetVisitor {
+            marker: PhantomData,
+        })
+    }
+}
@@ -1,38 +1,85 @@
 use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
 use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
 use core::iter::FromIterator;
-use core::ops::RangeBounds;
+use core::mem::MaybeUninit;
 use core::ops::{BitAnd, BitOr, BitXor, Sub};
+use core::ops::{Bound, RangeBounds};
+use core::ptr;
 
 use crate::set_types::{
-    Difference, Intersection, IntoIter, Iter, Range, SymmetricDifference, Union,
+    BulkLoad, Cursor, Difference, Drain, ExtractIf, Intersection, IntoIter, Iter, Levels, Range,
+    SymmetricDifference, Union,
 };
-use crate::tree::{SgError, SgTree};
+#[cfg(feature = "rebal_stats")]
+use crate::tree::RebalStats;
+#[cfg(feature = "telemetry")]
+use crate::tree::Telemetry;
+use crate::tree::{
+    ArenaIdx, CapacityError, Compare, Cursor as TreeCursor, DotOptions, DupPolicy, EvictPolicy,
+    InvariantReport, LayoutPolicy, MemoryUsage, Natural, SgError, SgTree, TreeStats,
+};
+
+#[cfg(feature = "bytemuck")]
+use bytemuck::Pod;
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
 
 /// Safe, fallible, embedded-friendly ordered set.
 ///
 /// ### Fallible APIs
 ///
 /// * [`try_insert`][crate::set::SgSet::try_insert]
+/// * [`insert_within_capacity`][crate::set::SgSet::insert_within_capacity]
 /// * [`try_append`][crate::set::SgSet::try_append]
 /// * [`try_extend`][crate::set::SgSet::try_extend]
 /// * [`try_from_iter`][crate::set::SgSet::try_from_iter]
+/// * [`try_with_rebal_ratio`][crate::set::SgSet::try_with_rebal_ratio]
+/// * [`try_split_off`][crate::set::SgSet::try_split_off]
+/// * [`try_reserve`][crate::set::SgSet::try_reserve]
 /// * [`try_replace`][crate::set::SgSet::try_replace]
+/// * [`try_bitor`][crate::set::SgSet::try_bitor]
+/// * [`try_bitxor`][crate::set::SgSet::try_bitxor]
+/// * [`try_union_update`][crate::set::SgSet::try_union_update]
+/// * [`try_symmetric_difference_update`][crate::set::SgSet::try_symmetric_difference_update]
 ///
-/// [`TryFrom`](https://doc.rust-lang.org/stable/std/convert/trait.TryFrom.html) isn't implemented because it would collide with the blanket implementation.
+/// [`TryFrom`](https://doc.rust-lang.org/stable/std/convert/trait.TryFrom.html) is implemented for slices (and, with the `alloc` feature, `Vec`s) of any length.
+/// It isn't implemented for arrays because it would collide with the blanket implementation.
 /// See [this open GitHub issue](https://github.com/rust-lang/rust/issues/50133#issuecomment-64690839) from 2018,
 /// this is a known Rust limitation that should be fixed via specialization in the future.
 ///
+/// ### Index Width
+///
+/// Internal child links are `u16` by default. The optional last generic parameter, any [`ArenaIdx`][crate::ArenaIdx],
+/// picks a different width: e.g. `SgSet<u8, 200, u8>` for smaller nodes, or `u32` for capacities beyond `65_534`.
+///
 /// ### Attribution Note
 ///
 /// The majority of API examples and descriptions are adapted or directly copied from the standard library's [`BTreeSet`](https://doc.rust-lang.org/std/collections/struct.BTreeSet.html).
 /// The goal is to offer embedded developers familiar, ergonomic APIs on resource constrained systems that otherwise don't get the luxury of dynamic collections.
 #[derive(Default, Clone, Hash, PartialEq, Eq, Ord, PartialOrd)]
-pub struct SgSet<T: Ord + Default, const N: usize> {
-    pub(crate) bst: SgTree<T, (), N>,
+pub struct SgSet<T: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    pub(crate) bst: SgTree<T, (), N, U, C>,
 }
 
-impl<T: Ord + Default, const N: usize> SgSet<T, N> {
+impl<T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> SgSet<T, N, U, C> {
+    /// Size of an individual internal node (one element plus metadata), in bytes.
+    /// Total storage is roughly `N * NODE_SIZE`.
+    /// Can be checked against a budget at compile time, via [`assert_node_size`](crate::assert_node_size).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// assert!(SgSet::<u32, 10>::NODE_SIZE >= core::mem::size_of::<u32>());
+    /// ```
+    pub const NODE_SIZE: usize = SgTree::<T, (), N, U, C>::NODE_SIZE;
+
     /// Makes a new, empty `SgSet`.
     ///
     /// # Examples
@@ -42,10 +89,115 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     ///
     /// let mut set: SgSet<i32, 10> = SgSet::new();
     /// ```
-    pub fn new() -> Self {
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
         SgSet { bst: SgTree::new() }
     }
 
+    /// Makes a new, empty `SgSet` whose elements are ordered by `cmp` instead of their [`Ord`] implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{Natural, Reversed, SgSet};
+    ///
+    /// let mut set: SgSet<i32, 10, u16, Reversed> = SgSet::with_comparator(Reversed(Natural));
+    ///
+    /// set.insert(1);
+    /// set.insert(3);
+    /// set.insert(2);
+    ///
+    /// assert_eq!(set.first(), Some(&3));
+    /// assert!(set.into_iter().eq([3, 2, 1]));
+    /// ```
+    pub fn with_comparator(cmp: C) -> Self {
+        SgSet {
+            bst: SgTree::with_comparator(cmp),
+        }
+    }
+
+    /// Makes a new, empty `SgSet` in caller-provided memory, returning a reference to it.
+    /// The set is still built as a stack temporary before being moved into `slot`,
+    /// see [`SgMap::new_in`][crate::SgMap::new_in] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::mem::MaybeUninit;
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut slot = MaybeUninit::uninit();
+    /// let set: &mut SgSet<u32, 10> = SgSet::new_in(&mut slot);
+    ///
+    /// set.insert(1);
+    /// assert!(set.contains(&1));
+    /// ```
+    pub fn new_in(slot: &mut MaybeUninit<Self>) -> &mut Self
+    where
+        C: Default,
+    {
+        slot.write(Self::new())
+    }
+
+    /// Makes a new, empty `SgSet` with the rebalance parameter, alpha, set to `alpha_num / alpha_denom`.
+    /// Saves a separate, fallible [`set_rebal_ratio`][SgSet::set_rebal_ratio] call when alpha is a constant.
+    /// See that method for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    /// For a non-panicking alternative, see [`try_with_rebal_ratio`][SgSet::try_with_rebal_ratio].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// // Read-heavy workload, favor a lower height
+    /// let set: SgSet<u32, 10> = SgSet::with_rebal_ratio(4, 7);
+    /// assert_eq!(set.rebal_ratio(), (4, 7));
+    /// ```
+    #[doc(alias = "alpha")]
+    #[doc(alias = "with_alpha")]
+    pub fn with_rebal_ratio(alpha_num: u32, alpha_denom: u32) -> Self
+    where
+        C: Default,
+    {
+        match Self::try_with_rebal_ratio(alpha_num, alpha_denom) {
+            Ok(set) => set,
+            Err(_) => panic!("Rebalance factor out of range, requires 0.5 <= alpha < 1.0!"),
+        }
+    }
+
+    /// Makes a new, empty `SgSet` with the rebalance parameter, alpha, set to `alpha_num / alpha_denom`.
+    /// See [`set_rebal_ratio`][SgSet::set_rebal_ratio] for more details.
+    ///
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// assert!(SgSet::<u32, 10>::try_with_rebal_ratio(3, 4).is_ok());
+    /// assert_eq!(
+    ///     SgSet::<u32, 10>::try_with_rebal_ratio(1, 3).unwrap_err(),
+    ///     SgError::RebalanceFactorOutOfRange
+    /// );
+    /// ```
+    #[doc(alias = "alpha")]
+    #[doc(alias = "with_alpha")]
+    pub fn try_with_rebal_ratio(alpha_num: u32, alpha_denom: u32) -> Result<Self, SgError>
+    where
+        C: Default,
+    {
+        Ok(SgSet {
+            bst: SgTree::try_with_rebal_ratio(alpha_num, alpha_denom)?,
+        })
+    }
+
     /// The [original scapegoat tree paper's](https://people.csail.mit.edu/rivest/pubs/GR93.pdf) alpha, `a`, can be chosen in the range `0.5 <= a < 1.0`.
     /// `a` tunes how "aggressively" the data structure self-balances.
     /// It controls the trade-off between total rebuild time and maximum height guarantees.
@@ -56,6 +208,8 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// * As `a` approaches `1.0`, the tree will rebalance less often. This means quicker insertions, but slower lookups and deletions.
     ///     * If `a` reached `1.0`, it'd mean a tree that never rebalances.
     ///
+    /// `a` is an integer ratio, `alpha_num / alpha_denom`, so balance decisions never need floating point math (e.g. on FPU-less targets).
+    ///
     /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
     ///
     /// # Examples
@@ -66,16 +220,54 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// let mut set: SgSet<isize, 10> = SgSet::new();
     ///
     /// // Set 2/3, e.g. `a = 0.666...` (it's default value).
-    /// assert!(set.set_rebal_param(2.0, 3.0).is_ok());
+    /// assert!(set.set_rebal_ratio(2, 3).is_ok());
     /// ```
     #[doc(alias = "rebalance")]
     #[doc(alias = "alpha")]
-    pub fn set_rebal_param(&mut self, alpha_num: f32, alpha_denom: f32) -> Result<(), SgError> {
-        self.bst.set_rebal_param(alpha_num, alpha_denom)
+    pub fn set_rebal_ratio(&mut self, alpha_num: u32, alpha_denom: u32) -> Result<(), SgError> {
+        self.bst.set_rebal_ratio(alpha_num, alpha_denom)
+    }
+
+    /// Set the rebalance parameter, alpha, and immediately rebuild the whole tree if its current height exceeds the new bound.
+    /// See [the corresponding setter method][SgSet::set_rebal_ratio] for more details.
+    ///
+    /// Plain [`set_rebal_ratio`][SgSet::set_rebal_ratio] leaves the tree in its old shape until enough inserts trigger rebalances.
+    /// This variant is useful when switching phases at runtime, e.g. from write-heavy (lax alpha) to read-heavy (strict alpha).
+    /// The rebuild is `O(n)`.
+    ///
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<usize, 100> = SgSet::new();
+    ///
+    /// // Write-heavy phase, rebalance rarely
+    /// assert!(set.set_rebal_ratio(9, 10).is_ok());
+    /// for k in 0..100 {
+    ///     set.insert(k);
+    /// }
+    ///
+    /// // Read-heavy phase, tighten to perfect balance right away
+    /// assert!(set.set_rebal_ratio_and_rebalance(1, 2).is_ok());
+    /// assert_eq!(set.rebal_ratio(), (1, 2));
+    /// assert!(set.check_invariants().is_valid());
+    /// ```
+    #[doc(alias = "rebalance")]
+    #[doc(alias = "alpha")]
+    pub fn set_rebal_ratio_and_rebalance(
+        &mut self,
+        alpha_num: u32,
+        alpha_denom: u32,
+    ) -> Result<(), SgError> {
+        self.bst
+            .set_rebal_ratio_and_rebalance(alpha_num, alpha_denom)
     }
 
     /// Get the current rebalance parameter, alpha, as a tuple of `(alpha_numerator, alpha_denominator)`.
-    /// See [the corresponding setter method][SgSet::set_rebal_param] for more details.
+    /// See [the corresponding setter method][SgSet::set_rebal_ratio] for more details.
     ///
     /// # Examples
     ///
@@ -85,287 +277,1195 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// let mut set: SgSet<isize, 10> = SgSet::new();
     ///
     /// // Set 2/3, e.g. `a = 0.666...` (it's default value).
-    /// assert!(set.set_rebal_param(2.0, 3.0).is_ok());
+    /// assert!(set.set_rebal_ratio(2, 3).is_ok());
     ///
     /// // Get the currently set value
-    /// assert_eq!(set.rebal_param(), (2.0, 3.0));
+    /// assert_eq!(set.rebal_ratio(), (2, 3));
     /// ```
     #[doc(alias = "rebalance")]
     #[doc(alias = "alpha")]
+    pub fn rebal_ratio(&self) -> (u32, u32) {
+        self.bst.rebal_ratio()
+    }
+
+    /// Set the rebalance parameter from a floating point `alpha_num / alpha_denom`.
+    /// Whole-number arguments (e.g. `2.0, 3.0`) convert exactly, otherwise `a` is rounded down to a multiple of `1 / 2^16`.
+    /// See [`set_rebal_ratio`][SgSet::set_rebal_ratio], which replaces it, for more details.
+    ///
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    #[deprecated(note = "use `set_rebal_ratio`, which takes an integer ratio")]
+    #[allow(deprecated)]
+    pub fn set_rebal_param(&mut self, alpha_num: f32, alpha_denom: f32) -> Result<(), SgError> {
+        self.bst.set_rebal_param(alpha_num, alpha_denom)
+    }
+
+    /// Get the current rebalance parameter, alpha, as a floating point tuple of `(alpha_numerator, alpha_denominator)`.
+    /// See [`rebal_ratio`][SgSet::rebal_ratio], which replaces it.
+    #[deprecated(note = "use `rebal_ratio`, which returns an integer ratio")]
+    #[allow(deprecated)]
     pub fn rebal_param(&self) -> (f32, f32) {
         self.bst.rebal_param()
     }
 
-    /// Total capacity, e.g. maximum number of set elements.
+    /// Get rebalance cost statistics: number of rebuilds, total nodes moved, and largest single rebuild.
+    /// Accumulated since creation or the last [`reset_rebal_stats`][crate::set::SgSet::reset_rebal_stats].
+    ///
+    /// Useful for performance engineering, e.g. catching a single `O(n)` rebuild that could blow a deadline.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let mut set: SgSet<i32, 10> = SgSet::new();
+    /// let mut set = SgSet::<_, 100>::new();
+    /// assert_eq!(set.rebal_stats().rebuild_cnt, 0);
     ///
-    /// assert!(set.capacity() == 10)
+    /// for i in 0..100 {
+    ///     set.insert(i);
+    /// }
+    ///
+    /// let stats = set.rebal_stats();
+    /// assert!(stats.rebuild_cnt > 0);
+    /// assert!(stats.nodes_moved >= stats.max_rebuild_size);
+    /// assert!(stats.max_rebuild_size <= set.len());
     /// ```
-    pub fn capacity(&self) -> usize {
-        self.bst.capacity()
+    #[cfg(feature = "rebal_stats")]
+    #[doc(alias = "rebalance")]
+    pub fn rebal_stats(&self) -> RebalStats {
+        self.bst.rebal_stats()
     }
 
-    /// Moves all elements from `other` into `self`, leaving `other` empty.
+    /// Reset rebalance cost statistics, see [`rebal_stats`][crate::set::SgSet::rebal_stats].
     ///
     /// # Examples
     ///
     /// ```
-    /// use scapegoat::SgSet;
+    /// use scapegoat::{RebalStats, SgSet};
     ///
-    /// let mut a = SgSet::<_, 10>::new();
-    /// a.insert(1);
-    /// a.insert(2);
-    /// a.insert(3);
+    /// let mut set = SgSet::<_, 100>::new();
+    /// for i in 0..100 {
+    ///     set.insert(i);
+    /// }
     ///
-    /// let mut b = SgSet::<_, 10>::new();
-    /// b.insert(3);
-    /// b.insert(4);
-    /// b.insert(5);
+    /// set.reset_rebal_stats();
+    /// assert_eq!(set.rebal_stats(), RebalStats::default());
+    /// ```
+    #[cfg(feature = "rebal_stats")]
+    pub fn reset_rebal_stats(&mut self) {
+        self.bst.reset_rebal_stats();
+    }
+
+    /// Get a summary of shape, rebalancing history, and arena occupancy, see [`TreeStats`][crate::TreeStats].
+    /// Runs in `O(n)` time (to measure height).
     ///
-    /// a.append(&mut b);
+    /// # Examples
     ///
-    /// assert_eq!(a.len(), 5);
-    /// assert_eq!(b.len(), 0);
+    /// ```
+    /// use scapegoat::SgSet;
     ///
-    /// assert!(a.contains(&1));
-    /// assert!(a.contains(&2));
-    /// assert!(a.contains(&3));
-    /// assert!(a.contains(&4));
-    /// assert!(a.contains(&5));
+    /// let mut set = SgSet::<_, 100>::new();
+    /// for i in 0..100 {
+    ///     set.insert(i);
+    /// }
+    /// set.remove(&0);
+    ///
+    /// let stats = set.stats();
+    /// assert_eq!(stats.len, 99);
+    /// assert_eq!(stats.arena_slots, 100);
+    /// assert!(stats.rebal_cnt > 0);
+    /// assert!(stats.height >= 7 && stats.height < 99);
     /// ```
-    pub fn append(&mut self, other: &mut SgSet<T, N>)
-    where
-        T: Ord,
-    {
-        self.bst.append(&mut other.bst);
+    #[doc(alias = "metrics")]
+    pub fn stats(&self) -> TreeStats {
+        self.bst.stats()
     }
 
-    /// Attempts to move all elements from `other` into `self`, leaving `other` empty.
+    /// Get resource usage high-water marks: maximum length ever reached and largest transient scratch buffer usage.
+    /// Accumulated since creation or the last [`reset_telemetry`][crate::set::SgSet::reset_telemetry].
+    ///
+    /// Useful for sizing capacity, `N`, from field data.
     ///
     /// # Examples
     ///
     /// ```
-    /// use core::iter::FromIterator;
-    /// use scapegoat::{SgSet, SgError};
+    /// use scapegoat::SgSet;
     ///
-    /// let mut a = SgSet::<_, 10>::new();
-    /// assert!(a.try_insert(1).is_ok());
-    /// assert!(a.try_insert(2).is_ok());
-    /// assert!(a.try_insert(3).is_ok());
+    /// let mut set = SgSet::<_, 100>::new();
+    /// for i in 0..50 {
+    ///     set.insert(i);
+    /// }
+    /// set.clear();
     ///
-    /// let mut b = SgSet::<_, 10>::new();
-    /// assert!(b.try_insert(3).is_ok()); // Overwrite previous
-    /// assert!(b.try_insert(4).is_ok());
-    /// assert!(b.try_insert(5).is_ok());
+    /// let telemetry = set.telemetry();
+    /// assert_eq!(telemetry.max_len, 50);
+    /// assert!(telemetry.max_scratch_len <= set.capacity());
+    /// ```
+    #[cfg(feature = "telemetry")]
+    pub fn telemetry(&self) -> Telemetry {
+        self.bst.telemetry()
+    }
+
+    /// Reset resource usage high-water marks, see [`telemetry`][crate::set::SgSet::telemetry].
     ///
-    /// // Successful append
-    /// assert!(a.try_append(&mut b).is_ok());
+    /// # Examples
     ///
-    /// // Elements moved
-    /// assert_eq!(a.len(), 5);
-    /// assert_eq!(b.len(), 0);
+    /// ```
+    /// use scapegoat::{SgSet, Telemetry};
     ///
-    /// assert!(a.contains(&1));
-    /// assert!(a.contains(&2));
-    /// assert!(a.contains(&3));
-    /// assert!(a.contains(&4));
-    /// assert!(a.contains(&5));
+    /// let mut set = SgSet::<_, 100>::new();
+    /// set.insert(1);
     ///
-    /// // Fill remaining capacity
-    /// let mut key = 6;
-    /// while a.len() < a.capacity() {
-    ///     assert!(a.try_insert(key).is_ok());
-    ///     key += 1;
-    /// }
+    /// set.reset_telemetry();
+    /// assert_eq!(set.telemetry(), Telemetry::default());
+    /// ```
+    #[cfg(feature = "telemetry")]
+    pub fn reset_telemetry(&mut self) {
+        self.bst.reset_telemetry();
+    }
+
+    /// Get a breakdown of storage usage, in bytes: live elements, freed slots, spare capacity, and overhead.
+    /// Storage is fixed-size and never spills to the heap, so the total is constant for a given type.
     ///
-    /// // Full
-    /// assert!(a.is_full());
+    /// Useful for budgeting RAM on constrained targets, where [`NODE_SIZE`][crate::set::SgSet::NODE_SIZE] alone doesn't account for metadata.
     ///
-    /// // More data
-    /// let mut c = SgSet::<_, 10>::from_iter([11, 12]);
-    /// let mut d = SgSet::<_, 10>::from_iter([1, 2]);
+    /// # Examples
     ///
-    /// // Cannot append new pairs
-    /// assert_eq!(a.try_append(&mut c), Err(SgError::StackCapacityExceeded));
+    /// ```
+    /// use scapegoat::SgSet;
     ///
-    /// // Can still replace existing pairs
-    /// assert!(a.try_append(&mut d).is_ok());
+    /// let mut set = SgSet::<u32, 100>::new();
+    /// for i in 0..10 {
+    ///     set.insert(i);
+    /// }
+    /// set.remove(&0);
+    ///
+    /// let usage = set.memory_usage();
+    /// assert_eq!(usage.total_bytes, core::mem::size_of_val(&set));
+    /// assert_eq!(usage.used_bytes, 9 * SgSet::<u32, 100>::NODE_SIZE);
+    /// assert!(usage.free_slot_bytes > 0);
+    /// assert_eq!(
+    ///     usage.used_bytes + usage.free_slot_bytes + usage.spare_bytes + usage.overhead_bytes,
+    ///     usage.total_bytes
+    /// );
     /// ```
-    pub fn try_append(&mut self, other: &mut SgSet<T, N>) -> Result<(), SgError> {
-        self.bst.try_append(&mut other.bst)
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.bst.memory_usage()
     }
 
-    /// Adds a value to the set.
-    /// If the set did not have this value present, `true` is returned.
-    /// If the set did have this value present, `false` is returned, and the entry is overwritten.
+    /// Computes a content digest: the set's length, then each value in ascending order, fed to `hasher`.
+    /// Two sets with equal contents always produce the same digest, regardless of insertion order,
+    /// removals, or rebalancing history. Useful for verifying a set restored from persistent storage.
+    ///
+    /// The result is only as deterministic as `hasher` and the `Hash` implementation of `T`.
+    /// Use a fixed-key hasher (not a randomly seeded one), and note that `core`'s integer `Hash` implementations
+    /// write native-endian bytes, so digests of the same set may differ across architectures.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
+    /// use std::collections::hash_map::DefaultHasher;
     ///
-    /// let mut set = SgSet::<_, 10>::new();
+    /// let mut a = SgSet::<u32, 10>::new();
+    /// a.insert(1);
+    /// a.insert(2);
+    /// a.insert(3);
+    /// a.remove(&3);
     ///
-    /// assert_eq!(set.insert(2), true);
-    /// assert_eq!(set.insert(2), false);
-    /// assert_eq!(set.len(), 1);
+    /// let b: SgSet<u32, 10> = vec![2, 1].into_iter().collect();
+    /// assert_eq!(a.digest(DefaultHasher::new()), b.digest(DefaultHasher::new()));
+    ///
+    /// a.insert(3);
+    /// assert_ne!(a.digest(DefaultHasher::new()), b.digest(DefaultHasher::new()));
     /// ```
-    pub fn insert(&mut self, value: T) -> bool
+    pub fn digest<H: Hasher>(&self, hasher: H) -> u64
     where
-        T: Ord,
+        T: Hash,
     {
-        self.bst.insert(value, ()).is_none()
+        self.bst.digest(hasher)
     }
 
-    /// Adds a value to the set.
-    /// Returns `Err` if the operation can't be completed, else the `Ok` contains:
-    /// * `true` if the set did not have this value present.
-    /// * `false` if the set did have this value present (and that old entry is overwritten).
+    /// Validate internal structure: ordering, links, cached metadata, and the alpha height bound.
+    /// Runs in `O(n)` time. Intended for fuzz harnesses and integration tests, see [`InvariantReport`].
     ///
     /// # Examples
     ///
     /// ```
-    /// use scapegoat::{SgSet, SgError};
+    /// use scapegoat::SgSet;
     ///
-    /// let mut set = SgSet::<_, 10>::new();
+    /// let mut set = SgSet::<_, 100>::new();
+    /// for i in 0..100 {
+    ///     set.insert(i);
+    /// }
     ///
-    /// // Add a new element
-    /// assert_eq!(set.try_insert(2), Ok(true));
+    /// let report = set.check_invariants();
+    /// assert!(report.is_valid());
+    /// assert_eq!(report.node_cnt, 100);
+    /// assert!(report.height <= report.max_height);
+    /// ```
+    pub fn check_invariants(&self) -> InvariantReport {
+        self.bst.check_invariants()
+    }
+
+    /// Writes the internal tree structure in [Graphviz](https://graphviz.org/) DOT format, labelling nodes with their elements.
+    /// Useful for debugging balance: render with `dot -Tsvg`.
     ///
-    /// // Replace existing element
-    /// assert_eq!(set.try_insert(2), Ok(false));
-    /// assert_eq!(set.len(), 1);
+    /// # Examples
     ///
-    /// // Fill remaining capacity
-    /// let mut elem = 3;
-    /// while set.len() < set.capacity() {
-    ///     assert!(set.try_insert(elem).is_ok());
-    ///     elem += 1;
-    /// }
+    /// ```
+    /// use scapegoat::SgSet;
     ///
-    /// // Full
-    /// assert!(set.is_full());
+    /// let mut set = SgSet::<_, 10>::new();
+    /// set.extend([2, 1, 3]);
     ///
-    /// // Cannot insert new element
-    /// assert_eq!(set.try_insert(elem), Err(SgError::StackCapacityExceeded));
+    /// let mut dot = String::new();
+    /// set.to_dot(&mut dot).unwrap();
     ///
-    /// // Can still replace existing element
-    /// assert_eq!(set.try_insert(elem - 1), Ok(false));
+    /// assert!(dot.starts_with("digraph {"));
+    /// assert_eq!(dot.matches(" -> ").count(), 2);
     /// ```
-    pub fn try_insert(&mut self, value: T) -> Result<bool, SgError>
+    pub fn to_dot<W: fmt::Write>(&self, w: &mut W) -> fmt::Result
     where
-        T: Ord,
+        T: Debug,
     {
-        match self.bst.try_insert(value, ()) {
-            Ok(opt_val) => Ok(opt_val.is_none()),
-            Err(_) => Err(SgError::StackCapacityExceeded),
-        }
+        self.bst.to_dot(w)
     }
 
-    /// Attempt to extend a collection with the contents of an iterator.
+    /// Writes the internal tree structure in [Graphviz](https://graphviz.org/) DOT format,
+    /// optionally annotating nodes with their storage index and subtree size.
     ///
     /// # Examples
     ///
     /// ```
-    /// use core::iter::FromIterator;
-    /// use scapegoat::{SgSet, SgError};
+    /// use scapegoat::{SgSet, DotOptions};
     ///
-    /// let mut a = SgSet::<_, 2>::new();
+    /// let mut set = SgSet::<_, 10>::new();
+    /// set.extend([2, 1, 3]);
+    ///
+    /// let opts = DotOptions {
+    ///     arena_idxs: true,
+    ///     subtree_sizes: true,
+    /// };
+    ///
+    /// let mut dot = String::new();
+    /// set.to_dot_with(&mut dot, opts).unwrap();
+    ///
+    /// assert!(dot.contains("size: 3"));
+    /// ```
+    pub fn to_dot_with<W: fmt::Write>(&self, w: &mut W, opts: DotOptions) -> fmt::Result
+    where
+        T: Debug,
+    {
+        self.bst.to_dot_with(w, opts)
+    }
+
+    /// Perform up to `max_work` units of optional housekeeping, returning `true` if more remains.
+    /// Lets maintenance be scheduled in idle time, e.g. between iterations of a main loop.
+    ///
+    /// Rebalancing required for `O(log n)` operations always happens synchronously, inside `insert`/`remove`.
+    /// This API performs *extra* work beyond that minimum. A unit of work is one node visited, checks included.
+    /// Each task first checks if it's needed (up to `self.len()` units), then costs `self.len()` more units
+    /// and is only started if it fits in the remaining budget. A budget of `3 * self.len()` always makes progress:
+    ///
+    /// 1. Rebuild to minimal height, speeding up subsequent lookups.
+    /// 2. Compact internal storage into sorted order, speeding up subsequent iteration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set = SgSet::<_, 100>::new();
+    /// for i in 0..100 {
+    ///     set.insert(i);
+    /// }
+    ///
+    /// // Budget too small, no work done
+    /// assert!(set.maintain(10));
+    ///
+    /// // Run to completion
+    /// while set.maintain(300) {}
+    /// assert!(!set.maintain(300));
+    /// ```
+    pub fn maintain(&mut self, max_work: usize) -> bool {
+        self.bst.maintain(max_work)
+    }
+
+    /// Suspend rebalancing for a batched insert phase, returning a guard that derefs to the set.
+    /// When the guard is dropped, the configured rebalance parameter is restored and the whole set is rebuilt once.
+    ///
+    /// Inserting `n` items through the guard skips the per-insert scapegoat search and partial rebuilds.
+    /// Lookups during the phase may be slower, tree height is only bounded once the guard is dropped.
+    /// Best for shuffled input: sorted input degrades the temporary tree to a list, making each insert `O(n)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set = SgSet::<_, 100>::new();
+    /// {
+    ///     let mut set = set.bulk_load();
+    ///     for i in (0..100).map(|i| (i * 37) % 100) {
+    ///         set.insert(i);
+    ///     }
+    ///     assert_eq!(set.stats().rebal_cnt, 0);
+    /// }
+    ///
+    /// assert_eq!(set.len(), 100);
+    /// assert_eq!(set.stats().rebal_cnt, 1);
+    /// ```
+    pub fn bulk_load(&mut self) -> BulkLoad<'_, T, N, U, C> {
+        BulkLoad::new(self)
+    }
+
+    /// Total capacity, e.g. maximum number of set elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<i32, 10> = SgSet::new();
+    ///
+    /// assert!(set.capacity() == 10)
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.bst.capacity()
+    }
+
+    /// Checks that `additional` more elements fit in the fixed capacity `N`, returning `Err` otherwise.
+    /// Nothing is reserved or allocated (capacity never changes), this lets a batch of inserts fail up front instead of midway.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let mut set = SgSet::<_, 10>::new();
+    /// set.extend([1, 2, 3]);
+    ///
+    /// assert_eq!(set.try_reserve(7), Ok(()));
+    /// assert_eq!(set.try_reserve(8), Err(SgError::StackCapacityExceeded));
+    /// ```
+    pub fn try_reserve(&self, additional: usize) -> Result<(), SgError> {
+        self.bst.try_reserve(additional)
+    }
+
+    /// Defragments storage: elements are moved into contiguous, in-order slots and all free slots are released.
+    /// Speeds up subsequent iteration. Runs in `O(n log n)` time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set = SgSet::<_, 10>::new();
+    /// set.extend([3, 1, 4, 2]);
+    /// set.remove(&1);
+    /// set.remove(&4);
+    ///
+    /// set.compact();
+    /// assert!(set.into_iter().eq([2, 3]));
+    /// ```
+    pub fn compact(&mut self) {
+        self.bst.compact()
+    }
+
+    /// Reorders storage slots for cache locality, leaving the tree's shape (and so search paths) unchanged.
+    /// Worth it after a build-then-read-mostly phase: later inserts and removals gradually undo the layout.
+    /// Runs in `O(n log n)` time.
+    ///
+    /// [`LayoutPolicy::InOrder`] speeds up iteration and range queries,
+    /// [`LayoutPolicy::VanEmdeBoas`] speeds up point lookups on sets larger than the cache.
+    /// [`maintain`](Self::maintain) reverts the latter, it compacts into in-order layout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{LayoutPolicy, SgSet};
+    ///
+    /// let mut set: SgSet<_, 100> = (0..100).collect();
+    ///
+    /// set.optimize_layout(LayoutPolicy::VanEmdeBoas);
+    /// assert!(set.contains(&42));
+    /// assert!(set.iter().copied().eq(0..100));
+    /// ```
+    pub fn optimize_layout(&mut self, policy: LayoutPolicy) {
+        self.bst.optimize_layout(policy)
+    }
+
+    /// Moves all elements from `other` into `self`, leaving `other` empty.
+    /// Runs in `O(n + m)` time: both are merged in sorted order, then rebuilt once.
+    /// If the value ranges don't overlap, `other`'s elements are moved over without a merge,
+    /// using a constant number of comparisons.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut a = SgSet::<_, 10>::new();
+    /// a.insert(1);
+    /// a.insert(2);
+    /// a.insert(3);
+    ///
+    /// let mut b = SgSet::<_, 10>::new();
+    /// b.insert(3);
+    /// b.insert(4);
+    /// b.insert(5);
+    ///
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(a.len(), 5);
+    /// assert_eq!(b.len(), 0);
+    ///
+    /// assert!(a.contains(&1));
+    /// assert!(a.contains(&2));
+    /// assert!(a.contains(&3));
+    /// assert!(a.contains(&4));
+    /// assert!(a.contains(&5));
+    /// ```
+    pub fn append(&mut self, other: &mut SgSet<T, N, U, C>)
+    where
+        T: Ord,
+    {
+        self.bst.append(&mut other.bst);
+    }
+
+    /// Attempts to move all elements from `other` into `self`, leaving `other` empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::iter::FromIterator;
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let mut a = SgSet::<_, 10>::new();
+    /// assert!(a.try_insert(1).is_ok());
+    /// assert!(a.try_insert(2).is_ok());
+    /// assert!(a.try_insert(3).is_ok());
+    ///
+    /// let mut b = SgSet::<_, 10>::new();
+    /// assert!(b.try_insert(3).is_ok()); // Overwrite previous
+    /// assert!(b.try_insert(4).is_ok());
+    /// assert!(b.try_insert(5).is_ok());
+    ///
+    /// // Successful append
+    /// assert!(a.try_append(&mut b).is_ok());
+    ///
+    /// // Elements moved
+    /// assert_eq!(a.len(), 5);
+    /// assert_eq!(b.len(), 0);
+    ///
+    /// assert!(a.contains(&1));
+    /// assert!(a.contains(&2));
+    /// assert!(a.contains(&3));
+    /// assert!(a.contains(&4));
+    /// assert!(a.contains(&5));
+    ///
+    /// // Fill remaining capacity
+    /// let mut key = 6;
+    /// while a.len() < a.capacity() {
+    ///     assert!(a.try_insert(key).is_ok());
+    ///     key += 1;
+    /// }
+    ///
+    /// // Full
+    /// assert!(a.is_full());
+    ///
+    /// // More data
+    /// let mut c = SgSet::<_, 10>::from_iter([11, 12]);
+    /// let mut d = SgSet::<_, 10>::from_iter([1, 2]);
+    ///
+    /// // Cannot append new pairs
+    /// assert_eq!(a.try_append(&mut c), Err(SgError::StackCapacityExceeded));
+    ///
+    /// // Can still replace existing pairs
+    /// assert!(a.try_append(&mut d).is_ok());
+    /// ```
+    pub fn try_append(&mut self, other: &mut SgSet<T, N, U, C>) -> Result<(), SgError> {
+        self.bst.try_append(&mut other.bst)
+    }
+
+    /// Adds a value to the set.
+    /// If the set did not have this value present, `true` is returned.
+    /// If the set did have this value present, `false` is returned, and the entry is overwritten.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set = SgSet::<_, 10>::new();
+    ///
+    /// assert_eq!(set.insert(2), true);
+    /// assert_eq!(set.insert(2), false);
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    pub fn insert(&mut self, value: T) -> bool
+    where
+        T: Ord,
+    {
+        self.bst.insert(value, ()).is_none()
+    }
+
+    /// Adds a value to the set.
+    /// Returns `Err` if the operation can't be completed, else the `Ok` contains:
+    /// * `true` if the set did not have this value present.
+    /// * `false` if the set did have this value present (and that old entry is overwritten).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let mut set = SgSet::<_, 10>::new();
+    ///
+    /// // Add a new element
+    /// assert_eq!(set.try_insert(2), Ok(true));
+    ///
+    /// // Replace existing element
+    /// assert_eq!(set.try_insert(2), Ok(false));
+    /// assert_eq!(set.len(), 1);
+    ///
+    /// // Fill remaining capacity
+    /// let mut elem = 3;
+    /// while set.len() < set.capacity() {
+    ///     assert!(set.try_insert(elem).is_ok());
+    ///     elem += 1;
+    /// }
+    ///
+    /// // Full
+    /// assert!(set.is_full());
+    ///
+    /// // Cannot insert new element
+    /// assert_eq!(set.try_insert(elem), Err(SgError::StackCapacityExceeded));
+    ///
+    /// // Can still replace existing element
+    /// assert_eq!(set.try_insert(elem - 1), Ok(false));
+    /// ```
+    pub fn try_insert(&mut self, value: T) -> Result<bool, SgError>
+    where
+        T: Ord,
+    {
+        self.insert_within_capacity(value).map_err(SgError::from)
+    }
+
+    /// Adds a value to the set, never exceeding capacity.
+    /// If the set is full and doesn't contain the value, first evicts the element at the end selected by `policy`.
+    /// Returns the evicted element, if any. If `value` would itself be the one evicted,
+    /// it's handed back and the set is unchanged.
+    ///
+    /// Makes the set a fixed-memory "keep the `N` largest (or smallest)" cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{EvictPolicy, SgSet};
+    ///
+    /// let mut top3 = SgSet::<_, 3>::new();
+    /// for score in [50, 20, 90, 70, 10] {
+    ///     top3.insert_or_evict(score, EvictPolicy::First);
+    /// }
+    /// assert!(top3.iter().eq(&[50, 70, 90]));
+    ///
+    /// assert_eq!(top3.insert_or_evict(60, EvictPolicy::First), Some(50));
+    /// assert_eq!(top3.insert_or_evict(5, EvictPolicy::First), Some(5));
+    /// assert_eq!(top3.insert_or_evict(5, EvictPolicy::Last), Some(90));
+    /// assert!(top3.iter().eq(&[5, 60, 70]));
+    /// ```
+    #[doc(alias = "bounded")]
+    #[doc(alias = "cache")]
+    pub fn insert_or_evict(&mut self, value: T, policy: EvictPolicy) -> Option<T>
+    where
+        T: Ord,
+    {
+        self.bst
+            .insert_or_evict(value, (), policy)
+            .1
+            .map(|(v, _)| v)
+    }
+
+    /// Adds a value to the set, handing the value back if there's no room for it.
+    /// Returns `Err` if the operation can't be completed, else the `Ok` contains:
+    /// * `true` if the set did not have this value present.
+    /// * `false` if the set did have this value present (and that item is updated,
+    ///   this accommodates types that can be `==` without being identical).
+    ///
+    /// Unlike [`try_insert`][`SgSet::try_insert`], a failed insert doesn't drop the value:
+    /// the [`CapacityError`][crate::CapacityError] owns it, so non-`Clone` values can be retried or routed elsewhere.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, CapacityError};
+    ///
+    /// let mut set = SgSet::<_, 2>::new();
+    /// assert_eq!(set.insert_within_capacity(String::from("a")), Ok(true));
+    /// assert_eq!(set.insert_within_capacity(String::from("b")), Ok(true));
+    ///
+    /// // Full, the rejected value is returned
+    /// let rejected = set.insert_within_capacity(String::from("c"));
+    /// assert_eq!(rejected, Err(CapacityError(String::from("c"))));
+    /// assert_eq!(rejected.unwrap_err().into_inner(), "c");
+    ///
+    /// // Can still replace existing value
+    /// assert_eq!(set.insert_within_capacity(String::from("b")), Ok(false));
+    /// ```
+    pub fn insert_within_capacity(&mut self, value: T) -> Result<bool, CapacityError<T>>
+    where
+        T: Ord,
+    {
+        match self.bst.insert_within_capacity(value, ()) {
+            Ok(opt_val) => Ok(opt_val.is_none()),
+            Err(CapacityError((value, ()))) => Err(CapacityError(value)),
+        }
+    }
+
+    /// Attempt to extend a collection with the contents of an iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::iter::FromIterator;
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let mut a = SgSet::<_, 2>::new();
     /// let mut b = SgSet::<_, 3>::from_iter([1, 2, 3]);
     /// let mut c = SgSet::<_, 2>::from_iter([1, 2]);
     ///
     /// // Too big
     /// assert_eq!(a.try_extend(b.into_iter()), Err(SgError::StackCapacityExceeded));
     ///
-    /// // Fits
-    /// assert!(a.try_extend(c.into_iter()).is_ok());
+    /// // Fits
+    /// assert!(a.try_extend(c.into_iter()).is_ok());
+    /// ```
+    ///
+    /// ### Note
+    ///
+    /// There is no `TryExtend` trait in `core`/`std`.
+    pub fn try_extend<I: ExactSizeIterator + IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), SgError> {
+        // Derp :P
+        if iter.len() <= (self.capacity() - self.len()) {
+            let mut map = crate::SgMap::<T, (), N, U, C>::with_comparator(self.bst.cmp.clone());
+            map.extend(iter.into_iter().map(|e| (e, ())));
+            self.bst.try_extend(map.into_iter())
+        } else {
+            Err(SgError::StackCapacityExceeded)
+        }
+    }
+
+    /// Attempt conversion from an iterator.
+    /// Will fail if iterator length exceeds the index type's maximum capacity (`65_534` by default),
+    /// or if the set's capacity, `N`, is exceeded.
+    /// Duplicate values keep the last occurrence, same as [`FromIterator`](core::iter::FromIterator).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// const CAPACITY_1: usize = 1_000;
+    /// assert!(SgSet::<_, CAPACITY_1>::try_from_iter((0..CAPACITY_1)).is_ok());
+    /// assert_eq!(
+    ///     SgSet::<_, 2>::try_from_iter([1, 2, 3].into_iter()),
+    ///     Err(SgError::StackCapacityExceeded)
+    /// );
+    ///
+    /// assert_eq!(
+    ///     SgSet::<usize, 254, u8>::try_from_iter((0..255)),
+    ///     Err(SgError::MaximumCapacityExceeded)
+    /// );
+    /// ```
+    ///
+    /// ### Note
+    ///
+    /// There is no `TryFromIterator` trait in `core`/`std`.
+    pub fn try_from_iter<I: ExactSizeIterator + IntoIterator<Item = T>>(
+        iter: I,
+    ) -> Result<Self, SgError>
+    where
+        C: Default,
+    {
+        match iter.len() <= SgTree::<T, (), N, U, C>::max_capacity() {
+            true => SgSet::from_iter_with_policy(iter, DupPolicy::KeepLast),
+            false => Err(SgError::MaximumCapacityExceeded),
+        }
+    }
+
+    /// Attempt conversion from an iterator, with explicit handling of duplicate (equal) values.
+    /// [`FromIterator`](core::iter::FromIterator) always keeps the last occurrence, i.e. [`DupPolicy::KeepLast`].
+    ///
+    /// Returns `Err(SgError::StackCapacityExceeded)` if the set's capacity is exceeded,
+    /// or `Err(SgError::KeyAlreadyExists)` on a duplicate value under [`DupPolicy::Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{DupPolicy, SgError, SgSet};
+    ///
+    /// let set = SgSet::<_, 10>::from_iter_with_policy([1, 2, 1], DupPolicy::KeepFirst).unwrap();
+    /// assert_eq!(set.len(), 2);
+    ///
+    /// assert_eq!(
+    ///     SgSet::<_, 10>::from_iter_with_policy([1, 2, 1], DupPolicy::Error),
+    ///     Err(SgError::KeyAlreadyExists)
+    /// );
+    /// ```
+    pub fn from_iter_with_policy<I: IntoIterator<Item = T>>(
+        iter: I,
+        policy: DupPolicy,
+    ) -> Result<Self, SgError>
+    where
+        C: Default,
+    {
+        Ok(SgSet {
+            bst: SgTree::from_iter_with_policy(iter.into_iter().map(|e| (e, ())), policy)?,
+        })
+    }
+
+    /// Gets an iterator that visits the values in the `SgSet` in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let set: SgSet<usize, 3> = [1, 2, 3].iter().cloned().collect();
+    /// let mut set_iter = set.iter();
+    /// assert_eq!(set_iter.next(), Some(&1));
+    /// assert_eq!(set_iter.next(), Some(&2));
+    /// assert_eq!(set_iter.next(), Some(&3));
+    /// assert_eq!(set_iter.next(), None);
+    /// ```
+    ///
+    /// Values returned by the iterator are returned in ascending order:
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let set: SgSet<usize, 3> = [3, 1, 2].iter().cloned().collect();
+    /// let mut set_iter = set.iter();
+    /// assert_eq!(set_iter.next(), Some(&1));
+    /// assert_eq!(set_iter.next(), Some(&2));
+    /// assert_eq!(set_iter.next(), Some(&3));
+    /// assert_eq!(set_iter.next(), None);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T, N, U, C> {
+        Iter::new(self)
+    }
+
+    /// Gets an iterator over the items of the set in level order (breadth-first), with each item's depth.
+    /// The root has depth `0`.
+    ///
+    /// Intended for diagnostics (e.g. visualizing balance) and level-ordered serialization.
+    /// Use [`iter`][crate::set::SgSet::iter] for sorted order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set = SgSet::from([1, 2, 3, 4, 5, 6, 7]);
+    ///
+    /// // Rebuild to minimal height, then check
+    /// set.maintain(usize::MAX);
+    /// let max_depth = set.iter_levels().map(|(depth, _)| depth).max();
+    /// assert_eq!(max_depth, Some(2));
+    /// ```
+    pub fn iter_levels(&self) -> Levels<'_, T, N, U, C> {
+        Levels::new(self)
+    }
+
+    /// Removes a value from the set. Returns whether the value was
+    /// present in the set.
+    ///
+    /// The value may be any borrowed form of the set's value type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the value type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set = SgSet::<_, 10>::new();
+    ///
+    /// set.insert(2);
+    /// assert_eq!(set.remove(&2), true);
+    /// assert_eq!(set.remove(&2), false);
+    /// ```
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.remove(value).is_some()
+    }
+
+    /// Splits the collection into two at the given value. Returns everything after the given value,
+    /// including the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut a = SgSet::<_, 5>::new();
+    /// a.insert(1);
+    /// a.insert(2);
+    /// a.insert(3);
+    /// a.insert(17);
+    /// a.insert(41);
+    ///
+    /// let b = a.split_off(&3);
+    ///
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(b.len(), 3);
+    ///
+    /// assert!(a.contains(&1));
+    /// assert!(a.contains(&2));
+    ///
+    /// assert!(b.contains(&3));
+    /// assert!(b.contains(&17));
+    /// assert!(b.contains(&41));
+    /// ```
+    pub fn split_off<Q>(&mut self, value: &Q) -> SgSet<T, N, U, C>
+    where
+        T: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        SgSet {
+            bst: self.bst.split_off(value),
+        }
+    }
+
+    /// Moves everything after the given value, including the value, into `dest`.
+    /// Unlike [`split_off`][crate::set::SgSet::split_off], no new set is constructed (and returned on the stack),
+    /// so `dest` can be a pre-allocated or re-used instance.
+    ///
+    /// Existing values in `dest` are kept, unless replaced by a moved equal value.
+    /// Returns `Err` if `dest` lacks capacity, in which case neither set is modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut a = SgSet::from([1, 2, 3, 17, 41]);
+    /// let mut b = SgSet::new();
+    ///
+    /// assert!(a.split_off_into(&3, &mut b).is_ok());
+    /// assert!(a.into_iter().eq([1, 2]));
+    /// assert!(b.into_iter().eq([3, 17, 41]));
+    /// ```
+    pub fn split_off_into<Q>(
+        &mut self,
+        value: &Q,
+        dest: &mut SgSet<T, N, U, C>,
+    ) -> Result<(), SgError>
+    where
+        T: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.split_off_into(value, &mut dest.bst)
+    }
+
+    /// Splits the collection into two at the given value, returning everything after the given value
+    /// (including the value) in a new set of capacity `M`.
+    /// Unlike [`split_off`][crate::set::SgSet::split_off], the returned set can be smaller than this one.
+    ///
+    /// Returns `Err` if the split-off values don't fit in `M`, in which case this set is not modified.
+    ///
+    /// # Examples
+    ///
     /// ```
+    /// use scapegoat::{SgSet, SgError};
     ///
-    /// ### Note
+    /// let mut a = SgSet::<_, 10>::new();
+    /// a.extend([1, 2, 3, 17, 41]);
     ///
-    /// There is no `TryExtend` trait in `core`/`std`.
-    pub fn try_extend<I: ExactSizeIterator + IntoIterator<Item = T>>(
+    /// // Not enough room
+    /// assert_eq!(a.try_split_off::<_, 2>(&3).unwrap_err(), SgError::StackCapacityExceeded);
+    /// assert_eq!(a.len(), 5);
+    ///
+    /// let b: SgSet<_, 3> = a.try_split_off(&3).unwrap();
+    /// assert!(a.into_iter().eq([1, 2]));
+    /// assert!(b.into_iter().eq([3, 17, 41]));
+    /// ```
+    pub fn try_split_off<Q, const M: usize>(
         &mut self,
-        iter: I,
-    ) -> Result<(), SgError> {
-        // Derp :P
-        if iter.len() <= (self.capacity() - self.len()) {
-            let map: crate::SgMap<T, (), N> = iter.into_iter().map(|e| (e, ())).collect();
-            self.bst.try_extend(map.into_iter())
-        } else {
-            Err(SgError::StackCapacityExceeded)
+        value: &Q,
+    ) -> Result<SgSet<T, M, U, C>, SgError>
+    where
+        T: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        Ok(SgSet {
+            bst: self.bst.try_split_off(value)?,
+        })
+    }
+
+    /// Splits the collection into two at sorted position `n` (0-indexed), returning the values of rank `n` or greater
+    /// in a new set. The `n` smallest values are kept in this one.
+    /// If `n` is at least the length, the returned set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut a = SgSet::from([1, 2, 3, 17, 41]);
+    ///
+    /// // Keep the top half
+    /// let top = a.split_at_rank(a.len() / 2);
+    /// assert!(a.into_iter().eq([1, 2]));
+    /// assert!(top.into_iter().eq([3, 17, 41]));
+    /// ```
+    pub fn split_at_rank(&mut self, n: usize) -> SgSet<T, N, U, C>
+    where
+        T: Ord,
+    {
+        SgSet {
+            bst: self.bst.split_at_rank(n),
         }
     }
 
-    /// Attempt conversion from an iterator.
-    /// Will fail if iterator length exceeds `u16::MAX`.
+    /// Adds a value to the set, replacing the existing value, if any, that is equal to the given
+    /// one. Returns the replaced value.
     ///
     /// # Examples
     ///
     /// ```
-    /// use scapegoat::{SgSet, SgError};
+    /// use scapegoat::SgSet;
     ///
-    /// const CAPACITY_1: usize = 1_000;
-    /// assert!(SgSet::<_, CAPACITY_1>::try_from_iter((0..CAPACITY_1)).is_ok());
+    /// let mut set = SgSet::<_, 10>::new();
+    /// set.insert(Vec::<i32>::new());
     ///
-    /// const CAPACITY_2: usize = (u16::MAX as usize) + 1;
-    /// assert_eq!(
-    ///     SgSet::<_, CAPACITY_2>::try_from_iter((0..CAPACITY_2)),
-    ///     Err(SgError::MaximumCapacityExceeded)
-    /// );
+    /// assert_eq!(set.get(&[][..]).unwrap().capacity(), 0);
+    /// set.replace(Vec::with_capacity(10));
+    /// assert_eq!(set.get(&[][..]).unwrap().capacity(), 10);
     /// ```
+    pub fn replace(&mut self, value: T) -> Option<T>
+    where
+        T: Ord,
+    {
+        let removed = self.bst.remove_entry(&value).map(|(k, _)| k);
+        self.insert(value);
+        removed
+    }
+
+    // TODO: add example
+    /// Attempts to add a value to the set, replacing the existing value, if any, that is equal to the given
+    /// one. Returns the replaced value.
+    pub fn try_replace(&mut self, value: T) -> Result<Option<T>, SgError>
+    where
+        T: Ord,
+    {
+        let removed = self.bst.remove_entry(&value).map(|(k, _)| k);
+        self.try_insert(value)?;
+        Ok(removed)
+    }
+
+    /// Removes and returns the value in the set, if any, that is equal to the given one.
     ///
-    /// ### Note
+    /// The value may be any borrowed form of the set's value type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the value type.
     ///
-    /// There is no `TryFromIterator` trait in `core`/`std`.
-    pub fn try_from_iter<I: ExactSizeIterator + IntoIterator<Item = T>>(
-        iter: I,
-    ) -> Result<Self, SgError> {
-        match iter.len() <= SgTree::<T, (), N>::max_capacity() {
-            true => Ok(SgSet::from_iter(iter)),
-            false => Err(SgError::MaximumCapacityExceeded),
-        }
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<_, 10> = [1, 2, 3].iter().cloned().collect();
+    /// assert_eq!(set.take(&2), Some(2));
+    /// assert_eq!(set.take(&2), None);
+    /// ```
+    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.remove_entry(value).map(|(k, _)| k)
     }
 
-    /// Gets an iterator that visits the values in the `SgSet` in ascending order.
+    /// Creates an iterator that visits all elements in ascending order, and uses a closure to determine if an element
+    /// should be removed.
+    ///
+    /// If the closure returns `true`, the element is removed from the set and yielded.
+    /// If the closure returns `false`, or panics, the element remains in the set and will not be yielded.
+    ///
+    /// If the returned iterator is not exhausted, e.g. because it is dropped without iterating
+    /// or the iteration short-circuits, then the remaining elements will be retained.
+    /// Use [`retain`][crate::set::SgSet::retain] with a negated predicate if you do not need the returned iterator.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let set: SgSet<usize, 3> = [1, 2, 3].iter().cloned().collect();
-    /// let mut set_iter = set.iter();
-    /// assert_eq!(set_iter.next(), Some(&1));
-    /// assert_eq!(set_iter.next(), Some(&2));
-    /// assert_eq!(set_iter.next(), Some(&3));
-    /// assert_eq!(set_iter.next(), None);
+    /// let mut set: SgSet<i32, 10> = (0..8).collect();
+    /// let evens: Vec<_> = set.extract_if(|v| v % 2 == 0).collect();
+    /// let odds = set;
+    /// assert_eq!(evens, vec![0, 2, 4, 6]);
+    /// assert!(odds.into_iter().eq(vec![1, 3, 5, 7]));
     /// ```
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F, N, U, C>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        ExtractIf::new(self, pred)
+    }
+
+    /// Retains only the elements specified by the predicate.
     ///
-    /// Values returned by the iterator are returned in ascending order:
+    /// In other words, remove all elements `e` such that `f(&e)` returns `false`.
+    /// The elements are visited in ascending order.
+    ///
+    /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let set: SgSet<usize, 3> = [3, 1, 2].iter().cloned().collect();
-    /// let mut set_iter = set.iter();
-    /// assert_eq!(set_iter.next(), Some(&1));
-    /// assert_eq!(set_iter.next(), Some(&2));
-    /// assert_eq!(set_iter.next(), Some(&3));
-    /// assert_eq!(set_iter.next(), None);
+    /// let xs = [1, 2, 3, 4, 5, 6];
+    /// let mut set: SgSet<i32, 10> = xs.iter().cloned().collect();
+    /// // Keep only the even numbers.
+    /// set.retain(|&k| k % 2 == 0);
+    /// assert!(set.iter().eq([2, 4, 6].iter()));
     /// ```
-    pub fn iter(&self) -> Iter<'_, T, N> {
-        Iter::new(self)
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        T: Ord,
+        F: FnMut(&T) -> bool,
+    {
+        self.bst.retain(|k, _| f(k));
     }
 
-    /// Removes a value from the set. Returns whether the value was
-    /// present in the set.
+    /// Keeps only the `len` smallest values, dropping the rest.
+    /// No-op if `len` is at least the set's current length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<i32, 10> = (0..8).collect();
+    /// set.truncate(3);
+    /// assert!(set.iter().eq([0, 1, 2].iter()));
+    /// ```
+    pub fn truncate(&mut self, len: usize)
+    where
+        T: Ord,
+    {
+        self.bst.truncate(len);
+    }
+
+    /// Keeps only the `len` largest values, dropping the rest.
+    /// No-op if `len` is at least the set's current length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<i32, 10> = (0..8).collect();
+    /// set.truncate_back(3);
+    /// assert!(set.iter().eq([5, 6, 7].iter()));
+    /// ```
+    pub fn truncate_back(&mut self, len: usize)
+    where
+        T: Ord,
+    {
+        self.bst.truncate_back(len);
+    }
+
+    /// Parallel [`retain`][crate::set::SgSet::retain]: the predicate is evaluated on multiple threads,
+    /// then all removals are merged into a single rebuild.
+    /// Unlike `retain`, the predicate may be called in any order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let xs = [1, 2, 3, 4, 5, 6];
+    /// let mut set: SgSet<i32, 10> = xs.iter().cloned().collect();
+    /// // Keep only the even numbers.
+    /// set.par_retain(|&k| k % 2 == 0);
+    /// assert!(set.iter().eq([2, 4, 6].iter()));
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_retain<F>(&mut self, f: F)
+    where
+        T: Send + Sync,
+        F: Fn(&T) -> bool + Send + Sync,
+    {
+        self.bst.par_retain(|k, _| f(k));
+    }
+
+    /// Calls a closure on each element within a range, on multiple threads.
+    /// The closure may be called in any order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    /// use std::sync::atomic::{AtomicI32, Ordering};
+    ///
+    /// let mut set: SgSet<i32, 10> = (0..8).collect();
+    /// let sum = AtomicI32::new(0);
+    /// set.par_range_for_each(2..5, |&x| {
+    ///     sum.fetch_add(x, Ordering::Relaxed);
+    /// });
+    /// assert_eq!(sum.into_inner(), 2 + 3 + 4);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_range_for_each<K, R, F>(&mut self, range: R, f: F)
+    where
+        K: ?Sized,
+        C: Compare<K>,
+        T: Borrow<K> + Send + Sync,
+        R: RangeBounds<K>,
+        F: Fn(&T) + Send + Sync,
+    {
+        self.bst.par_range_for_each(range, |k, _| f(k));
+    }
+
+    /// Gets a parallel iterator over the values of the set, in no particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    /// use rayon::prelude::*;
+    ///
+    /// let set: SgSet<i32, 10> = (0..8).collect();
+    /// assert_eq!(set.par_iter().sum::<i32>(), 28);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = &T>
+    where
+        T: Sync,
+    {
+        self.bst.par_iter().map(|(k, _)| k)
+    }
+
+    /// Returns a reference to the value in the set, if any, that is equal to the given value.
     ///
     /// The value may be any borrowed form of the set's value type,
     /// but the ordering on the borrowed form *must* match the
@@ -376,94 +1476,187 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let mut set = SgSet::<_, 10>::new();
+    /// let set: SgSet<_, 10> = [1, 2, 3].iter().cloned().collect();
+    /// assert_eq!(set.get(&2), Some(&2));
+    /// assert_eq!(set.get(&4), None);
+    /// ```
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get_key_value(value).map(|(k, _)| k)
+    }
+
+    /// Inserts the given value into the set if it isn't present, then returns a reference to the value in the set.
+    /// Takes a single traversal of the tree. If an equal value is present, the given one is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the set is full and the value isn't already present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut set: SgSet<_, 10> = [1, 2, 3].iter().cloned().collect();
+    /// assert_eq!(set.len(), 3);
+    /// assert_eq!(set.get_or_insert(2), &2);
+    /// assert_eq!(set.get_or_insert(100), &100);
+    /// assert_eq!(set.len(), 4);
+    /// ```
+    pub fn get_or_insert(&mut self, value: T) -> &T
+    where
+        T: Ord,
+    {
+        self.bst.get_or_insert_with(value, || ()).0
+    }
+
+    /// Returns the greatest value less than or equal to `value` (a.k.a. floor),
+    /// or `None` if every value is greater. Found in a single traversal.
+    ///
+    /// The value may be any borrowed form of the set's value type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the value type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let set = SgSet::from([10, 20, 30]);
+    /// assert_eq!(set.get_prev(&20), Some(&20));
+    /// assert_eq!(set.get_prev(&25), Some(&20));
+    /// assert_eq!(set.get_prev(&5), None);
+    /// ```
+    #[doc(alias = "floor")]
+    pub fn get_prev<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get_prev(value).map(|(k, _)| k)
+    }
+
+    /// Returns the smallest value greater than or equal to `value` (a.k.a. ceiling),
+    /// or `None` if every value is smaller. Found in a single traversal.
+    ///
+    /// The value may be any borrowed form of the set's value type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the value type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
     ///
-    /// set.insert(2);
-    /// assert_eq!(set.remove(&2), true);
-    /// assert_eq!(set.remove(&2), false);
+    /// let set = SgSet::from([10, 20, 30]);
+    /// assert_eq!(set.get_next(&20), Some(&20));
+    /// assert_eq!(set.get_next(&25), Some(&30));
+    /// assert_eq!(set.get_next(&35), None);
     /// ```
-    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    #[doc(alias = "ceiling")]
+    pub fn get_next<Q>(&self, value: &Q) -> Option<&T>
     where
         T: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
-        self.bst.remove(value).is_some()
+        self.bst.get_next(value).map(|(k, _)| k)
     }
 
-    /// Splits the collection into two at the given value. Returns everything after the given value,
-    /// including the value.
+    /// Returns the value at sorted position `n` (0-indexed), or `None` if `n >= len`.
+    ///
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let mut a = SgSet::<_, 5>::new();
-    /// a.insert(1);
-    /// a.insert(2);
-    /// a.insert(3);
-    /// a.insert(17);
-    /// a.insert(41);
+    /// let set = SgSet::from([40, 10, 30, 20, 50]);
+    /// assert_eq!(set.get_index(0), Some(&10));
+    /// assert_eq!(set.get_index(set.len() / 2), Some(&30)); // Median
+    /// assert_eq!(set.get_index(5), None);
+    /// ```
+    pub fn get_index(&self, n: usize) -> Option<&T> {
+        self.bst.get_index(n).map(|(k, _)| k)
+    }
+
+    /// Removes and returns the value at sorted position `n` (0-indexed), or `None` if `n >= len`.
     ///
-    /// let b = a.split_off(&3);
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
     ///
-    /// assert_eq!(a.len(), 2);
-    /// assert_eq!(b.len(), 3);
+    /// # Examples
     ///
-    /// assert!(a.contains(&1));
-    /// assert!(a.contains(&2));
+    /// ```
+    /// use scapegoat::SgSet;
     ///
-    /// assert!(b.contains(&3));
-    /// assert!(b.contains(&17));
-    /// assert!(b.contains(&41));
+    /// let mut set = SgSet::from([40, 10, 30, 20, 50]);
+    /// assert_eq!(set.remove_nth(set.len() / 2), Some(30)); // Median
+    /// assert_eq!(set.remove_nth(set.len() / 2), Some(40)); // New upper median
+    /// assert_eq!(set.remove_nth(3), None);
+    /// assert!(set.iter().eq([10, 20, 50].iter()));
     /// ```
-    pub fn split_off<Q>(&mut self, value: &Q) -> SgSet<T, N>
-    where
-        T: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
-    {
-        SgSet {
-            bst: self.bst.split_off(value),
-        }
+    pub fn remove_nth(&mut self, n: usize) -> Option<T> {
+        self.bst.remove_nth(n).map(|(k, _)| k)
     }
 
-    /// Adds a value to the set, replacing the existing value, if any, that is equal to the given
-    /// one. Returns the replaced value.
+    /// Returns a uniformly random value, or `None` if the set is empty.
+    ///
+    /// Samples a rank, so every value is equally likely regardless of the tree's shape.
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
+    /// use rand::{rngs::SmallRng, SeedableRng};
     ///
-    /// let mut set = SgSet::<_, 10>::new();
-    /// set.insert(Vec::<i32>::new());
+    /// let mut rng = SmallRng::seed_from_u64(42);
+    /// let mut set = SgSet::from([1, 2, 3, 4]);
     ///
-    /// assert_eq!(set.get(&[][..]).unwrap().capacity(), 0);
-    /// set.replace(Vec::with_capacity(10));
-    /// assert_eq!(set.get(&[][..]).unwrap().capacity(), 10);
+    /// // Random eviction
+    /// let victim = *set.choose(&mut rng).unwrap();
+    /// assert!(set.remove(&victim));
+    /// assert_eq!(set.len(), 3);
     /// ```
-    pub fn replace(&mut self, value: T) -> Option<T>
-    where
-        T: Ord,
-    {
-        let removed = self.bst.remove_entry(&value).map(|(k, _)| k);
-        self.insert(value);
-        removed
+    #[cfg(feature = "rand")]
+    pub fn choose<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<&T> {
+        self.bst.choose(rng).map(|(k, _)| k)
     }
 
-    // TODO: add example
-    /// Attempts to add a value to the set, replacing the existing value, if any, that is equal to the given
-    /// one. Returns the replaced value.
-    pub fn try_replace(&mut self, value: T) -> Result<Option<T>, SgError>
-    where
-        T: Ord,
-    {
-        let removed = self.bst.remove_entry(&value).map(|(k, _)| k);
-        self.try_insert(value)?;
-        Ok(removed)
+    /// Returns the value at the sorted position picked by `rand_idx`, or `None` if the set is empty.
+    ///
+    /// `rand_idx` is called once, with the set's length, and should return a uniformly random index below it.
+    /// An out-of-range index returns `None`.
+    /// This is the form of `choose` that doesn't need the `rand` feature:
+    /// bring your own random number generator.
+    ///
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let set = SgSet::from([1, 2, 3, 4]);
+    ///
+    /// // Fixed "random" index, for illustration
+    /// assert_eq!(set.choose_with(|len| len / 2), Some(&3));
+    /// assert_eq!(set.choose_with(|len| len), None);
+    /// ```
+    pub fn choose_with<F: FnOnce(usize) -> usize>(&self, rand_idx: F) -> Option<&T> {
+        self.bst.choose_with(rand_idx).map(|(k, _)| k)
     }
 
-    /// Removes and returns the value in the set, if any, that is equal to the given one.
+    /// Returns the sorted position (0-indexed) of `value`, or `None` if the value isn't present.
+    /// The inverse of [`get_index`][crate::set::SgSet::get_index].
+    ///
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
     ///
     /// The value may be any borrowed form of the set's value type,
     /// but the ordering on the borrowed form *must* match the
@@ -474,63 +1667,90 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let mut set: SgSet<_, 10> = [1, 2, 3].iter().cloned().collect();
-    /// assert_eq!(set.take(&2), Some(2));
-    /// assert_eq!(set.take(&2), None);
+    /// let set = SgSet::from([40, 10, 30, 20, 50]);
+    /// assert_eq!(set.rank(&10), Some(0));
+    /// assert_eq!(set.rank(&40), Some(3));
+    /// assert_eq!(set.rank(&35), None);
     /// ```
-    pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+    pub fn rank<Q>(&self, value: &Q) -> Option<usize>
     where
         T: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
-        self.bst.remove_entry(value).map(|(k, _)| k)
+        self.bst.rank(value)
     }
 
-    /// Retains only the elements specified by the predicate.
+    /// Returns the sorted position (0-indexed) of the first value for which `pred` returns `false`,
+    /// or the set's length if there's no such value. Analogous to [`slice::partition_point`].
     ///
-    /// In other words, remove all elements `e` such that `f(&e)` returns `false`.
-    /// The elements are visited in ascending order.
+    /// The set is assumed to be partitioned according to `pred`: all values for which it returns `true` precede
+    /// all values for which it returns `false`. If not, the returned position is unspecified.
+    ///
+    /// Runs in `O(log n)` if the `fast_rebalance` feature is enabled (it caches subtree sizes), `O(n)` otherwise.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let xs = [1, 2, 3, 4, 5, 6];
-    /// let mut set: SgSet<i32, 10> = xs.iter().cloned().collect();
-    /// // Keep only the even numbers.
-    /// set.retain(|&k| k % 2 == 0);
-    /// assert!(set.iter().eq([2, 4, 6].iter()));
+    /// let set = SgSet::from([1, 2, 3, 5, 6, 7]);
+    /// assert_eq!(set.partition_point(|&x| x < 5), 3);
+    /// assert_eq!(set.partition_point(|&x| x < 100), 6);
     /// ```
-    pub fn retain<F>(&mut self, mut f: F)
+    pub fn partition_point<F>(&self, pred: F) -> usize
     where
-        T: Ord,
         F: FnMut(&T) -> bool,
     {
-        self.bst.retain(|k, _| f(k));
+        self.bst.partition_point(pred)
     }
 
-    /// Returns a reference to the value in the set, if any, that is equal to the given value.
+    /// Returns the first value for which `pred` returns `false`, or `None` if there's no such value.
+    /// Found in a single traversal.
     ///
-    /// The value may be any borrowed form of the set's value type,
-    /// but the ordering on the borrowed form *must* match the
-    /// ordering on the value type.
+    /// The set is assumed to be partitioned according to `pred`, as per
+    /// [`partition_point`][crate::set::SgSet::partition_point]. If not, the returned value is unspecified.
     ///
     /// # Examples
     ///
     /// ```
     /// use scapegoat::SgSet;
     ///
-    /// let set: SgSet<_, 10> = [1, 2, 3].iter().cloned().collect();
-    /// assert_eq!(set.get(&2), Some(&2));
-    /// assert_eq!(set.get(&4), None);
+    /// let set = SgSet::from([1, 2, 3, 5, 6, 7]);
+    ///
+    /// // Smallest value whose square is at least 20
+    /// assert_eq!(set.get_partition_point(|&x| x * x < 20), Some(&5));
+    /// assert_eq!(set.get_partition_point(|&x| x * x < 100), None);
     /// ```
-    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    pub fn get_partition_point<F>(&self, pred: F) -> Option<&T>
     where
-        T: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        F: FnMut(&T) -> bool,
     {
-        self.bst.get_key_value(value).map(|(k, _)| k)
+        self.bst.get_partition_point(pred).map(|(k, _)| k)
+    }
+
+    /// Clears the set, returning all values as an iterator, in ascending order.
+    ///
+    /// The set is empty as soon as this method returns, even if the iterator is dropped or leaked before being fully
+    /// consumed. Values not yet yielded are dropped with the iterator.
+    ///
+    /// Unlike [`clear`][crate::set::SgSet::clear], which starts over with a fresh tree, draining keeps the set's
+    /// existing storage: nodes are released one at a time and their arena slots reused by later inserts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut v = SgSet::<_, 10>::new();
+    /// v.insert(2);
+    /// v.insert(1);
+    ///
+    /// assert!(v.drain().eq([1, 2]));
+    /// assert!(v.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T, N, U, C> {
+        Drain::new(self)
     }
 
     /// Clears the set, removing all values.
@@ -567,11 +1787,70 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     pub fn contains<Q>(&self, value: &Q) -> bool
     where
         T: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
         self.bst.contains_key(value)
     }
 
+    /// Returns `true` if the set contains every value yielded by `values`, stopping at the first one missing.
+    /// Ascending runs of values resume each search from the previous one's path instead of the root,
+    /// so a sorted probe walks the set in lockstep. Vacuously `true` for an empty probe.
+    ///
+    /// The values may be any borrowed form of the set's value type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the value type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let perms: SgSet<_, 10> = ["read", "write", "exec"].iter().copied().collect();
+    /// assert!(perms.contains_all(&["exec", "read"]));
+    /// assert!(!perms.contains_all(&["read", "admin"]));
+    /// assert!(perms.contains_all(&[] as &[&str]));
+    /// ```
+    pub fn contains_all<'a, Q, I>(&'a self, values: I) -> bool
+    where
+        T: Borrow<Q> + Ord,
+        Q: ?Sized + 'a,
+        C: Compare<Q>,
+        I: IntoIterator<Item = &'a Q>,
+        I::IntoIter: 'a,
+    {
+        self.bst.get_batch(values).all(|opt_val| opt_val.is_some())
+    }
+
+    /// Returns `true` if the set contains any value yielded by `values`, stopping at the first one present.
+    /// Ascending runs of values resume each search from the previous one's path instead of the root,
+    /// so a sorted probe walks the set in lockstep. `false` for an empty probe.
+    ///
+    /// The values may be any borrowed form of the set's value type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the value type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let perms: SgSet<_, 10> = ["read", "write", "exec"].iter().copied().collect();
+    /// assert!(perms.contains_any(&["admin", "write"]));
+    /// assert!(!perms.contains_any(&["admin", "root"]));
+    /// assert!(!perms.contains_any(&[] as &[&str]));
+    /// ```
+    pub fn contains_any<'a, Q, I>(&'a self, values: I) -> bool
+    where
+        T: Borrow<Q> + Ord,
+        Q: ?Sized + 'a,
+        C: Compare<Q>,
+        I: IntoIterator<Item = &'a Q>,
+        I::IntoIter: 'a,
+    {
+        self.bst.get_batch(values).any(|opt_val| opt_val.is_some())
+    }
+
     /// Returns a reference to the first/minium value in the set, if any.
     ///
     /// # Examples
@@ -692,31 +1971,262 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     ///
     /// ```
     /// use scapegoat::SgSet;
-    /// use core::ops::Bound::Included;
+    /// use core::ops::Bound::Included;
+    ///
+    /// let mut set = SgSet::<_, 5>::new();
+    /// set.insert(3);
+    /// set.insert(5);
+    /// set.insert(8);
+    /// for &elem in set.range((Included(&4), Included(&8))) {
+    ///     println!("{}", elem);
+    /// }
+    /// assert_eq!(Some(&5), set.range(4..).next());
+    /// ```
+    pub fn range<K, R>(&self, range: R) -> Range<'_, T, N, U, C>
+    where
+        K: ?Sized,
+        C: Compare<K>,
+        T: Borrow<K> + Ord,
+        R: RangeBounds<K>,
+    {
+        self.bst.assert_valid_range(&range);
+        Range {
+            table: self,
+            node_idx_iter: self.bst.range_search(&range).into_iter(),
+        }
+    }
+
+    /// Constructs a double-ended iterator over the set's elements, starting at the first element greater than or equal to `value`.
+    /// Shorthand for `range((Bound::Included(value), Bound::Unbounded))`, e.g. to resume a paged scan from the last-seen element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let set: SgSet<_, 10> = (1..=8).map(|x| x * 10).collect();
+    ///
+    /// // Pages of three, resuming after the last-seen element
+    /// let page: Vec<_> = set.iter_from(&0).take(3).copied().collect();
+    /// assert_eq!(page, [10, 20, 30]);
+    /// let page: Vec<_> = set.iter_from(&31).take(3).copied().collect();
+    /// assert_eq!(page, [40, 50, 60]);
+    /// ```
+    #[doc(alias = "seek")]
+    pub fn iter_from<K>(&self, value: &K) -> Range<'_, T, N, U, C>
+    where
+        K: ?Sized,
+        C: Compare<K>,
+        T: Borrow<K> + Ord,
+    {
+        self.range::<K, _>((Bound::Included(value), Bound::Unbounded))
+    }
+
+    /// Returns a [`Cursor`][crate::set_types::Cursor] pointing at the gap before the smallest element above the given bound.
+    ///
+    /// Passing `Bound::Included(x)` yields a cursor whose [`peek_next`][crate::set_types::Cursor::peek_next]
+    /// is the first element `>= x`. Passing `Bound::Excluded(x)` yields one for an element `> x`.
+    /// Passing `Bound::Unbounded` yields a cursor before the first element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    /// use core::ops::Bound;
+    ///
+    /// let set = SgSet::from([1, 2, 3, 4]);
+    ///
+    /// let cursor = set.lower_bound(Bound::Included(&2));
+    /// assert_eq!(cursor.peek_prev(), Some(&1));
+    /// assert_eq!(cursor.peek_next(), Some(&2));
+    ///
+    /// let mut cursor = set.lower_bound(Bound::Excluded(&2));
+    /// assert_eq!(cursor.peek_prev(), Some(&2));
+    /// assert_eq!(cursor.next(), Some(&3));
+    /// assert_eq!(cursor.peek_prev(), Some(&3));
+    /// ```
+    pub fn lower_bound<K>(&self, bound: Bound<&K>) -> Cursor<'_, T, N, U, C>
+    where
+        T: Borrow<K>,
+        K: ?Sized,
+        C: Compare<K>,
+    {
+        Cursor::new(TreeCursor::lower_bound(&self.bst, bound))
+    }
+
+    /// Returns a [`Cursor`][crate::set_types::Cursor] pointing at the gap after the largest element below the given bound.
+    ///
+    /// Passing `Bound::Included(x)` yields a cursor whose [`peek_prev`][crate::set_types::Cursor::peek_prev]
+    /// is the last element `<= x`. Passing `Bound::Excluded(x)` yields one for an element `< x`.
+    /// Passing `Bound::Unbounded` yields a cursor after the last element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    /// use core::ops::Bound;
+    ///
+    /// let set = SgSet::from([1, 2, 3, 4]);
+    ///
+    /// let cursor = set.upper_bound(Bound::Included(&3));
+    /// assert_eq!(cursor.peek_prev(), Some(&3));
+    /// assert_eq!(cursor.peek_next(), Some(&4));
+    ///
+    /// let mut cursor = set.upper_bound(Bound::Excluded(&3));
+    /// assert_eq!(cursor.prev(), Some(&2));
+    /// assert_eq!(cursor.peek_prev(), Some(&1));
+    /// assert_eq!(cursor.peek_next(), Some(&2));
+    /// ```
+    pub fn upper_bound<K>(&self, bound: Bound<&K>) -> Cursor<'_, T, N, U, C>
+    where
+        T: Borrow<K>,
+        K: ?Sized,
+        C: Compare<K>,
+    {
+        Cursor::new(TreeCursor::upper_bound(&self.bst, bound))
+    }
+
+    /// Copies the elements within a range into caller-provided storage, in ascending order, without an intermediate collection.
+    /// Returns the number of elements written, which is the smaller of the range's length and the slice's length.
+    ///
+    /// Slots `[0, written)` are initialized, the rest are left untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let set = SgSet::from([1, 2, 3, 4]);
+    /// let mut buf = [MaybeUninit::<usize>::uninit(); 2];
+    ///
+    /// // Truncated to slice length
+    /// assert_eq!(set.copy_range_to_slice(2.., &mut buf), 2);
+    /// assert_eq!(set.copy_range_to_slice(10.., &mut buf), 0);
+    /// ```
+    pub fn copy_range_to_slice<K, R>(&self, range: R, dst: &mut [MaybeUninit<T>]) -> usize
+    where
+        K: ?Sized,
+        C: Compare<K>,
+        T: Borrow<K> + Ord + Clone,
+        R: RangeBounds<K>,
+    {
+        let mut written = 0;
+        for (slot, elem) in dst.iter_mut().zip(self.range(range)) {
+            slot.write(elem.clone());
+            written += 1;
+        }
+
+        written
+    }
+
+    /// Clones every element into caller-provided storage, in ascending order.
+    /// Returns the number of elements written, always [`len`][crate::set::SgSet::len].
+    ///
+    /// Slots `[0, written)` are overwritten, the rest are left untouched.
+    /// If `dst` can't hold every element, returns `Err(SgError::BufferTooSmall)` and writes nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let set = SgSet::from([3, 1, 2]);
+    /// let mut buf = [0; 4];
+    ///
+    /// assert_eq!(set.copy_to_slice(&mut buf), Ok(3));
+    /// assert_eq!(buf, [1, 2, 3, 0]);
+    /// assert_eq!(set.copy_to_slice(&mut buf[..2]), Err(SgError::BufferTooSmall));
+    /// ```
+    pub fn copy_to_slice(&self, dst: &mut [T]) -> Result<usize, SgError>
+    where
+        T: Ord + Clone,
+    {
+        if dst.len() < self.len() {
+            return Err(SgError::BufferTooSmall);
+        }
+
+        for (slot, elem) in dst.iter_mut().zip(self.iter()) {
+            *slot = elem.clone();
+        }
+
+        Ok(self.len())
+    }
+
+    /// Consumes the set, returning its elements as a `Vec`, in ascending order.
+    /// Requires the `alloc` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let set = SgSet::from([3, 1, 2]);
+    /// assert_eq!(set.into_sorted_vec(), [1, 2, 3]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn into_sorted_vec(self) -> alloc::vec::Vec<T>
+    where
+        T: Ord,
+    {
+        let mut vec = alloc::vec::Vec::with_capacity(self.len());
+        vec.extend(self);
+        vec
+    }
+
+    /// Size, in bytes, of the image [`write_raw_snapshot`][crate::SgSet::write_raw_snapshot] produces.
+    /// Requires the `bytemuck` feature.
+    #[cfg(feature = "bytemuck")]
+    pub fn raw_snapshot_len(&self) -> usize
+    where
+        T: Pod,
+    {
+        self.bst.raw_snapshot_len()
+    }
+
+    /// Writes the set's internal storage to `dst` as a plain byte image, returning the number of bytes written.
+    /// Requires the `bytemuck` feature.
+    /// See [`SgMap::write_raw_snapshot`][crate::SgMap::write_raw_snapshot] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
     ///
-    /// let mut set = SgSet::<_, 5>::new();
-    /// set.insert(3);
-    /// set.insert(5);
-    /// set.insert(8);
-    /// for &elem in set.range((Included(&4), Included(&8))) {
-    ///     println!("{}", elem);
-    /// }
-    /// assert_eq!(Some(&5), set.range(4..).next());
+    /// let set: SgSet<u8, 10> = [3, 1, 2].iter().copied().collect();
+    /// let mut buf = [0; 256];
+    /// let len = set.write_raw_snapshot(&mut buf).unwrap();
+    ///
+    /// let restored = SgSet::<u8, 10>::from_raw_snapshot(&buf[..len]).unwrap();
+    /// assert!(restored.iter().eq([1, 2, 3].iter()));
     /// ```
-    pub fn range<K, R>(&self, range: R) -> Range<'_, T, N>
+    #[cfg(feature = "bytemuck")]
+    pub fn write_raw_snapshot(&self, dst: &mut [u8]) -> Result<usize, SgError>
     where
-        K: Ord + ?Sized,
-        T: Borrow<K> + Ord,
-        R: RangeBounds<K>,
+        T: Pod,
     {
-        SgTree::<T, (), N>::assert_valid_range(&range);
-        Range {
-            table: self,
-            node_idx_iter: self.bst.range_search(&range).into_iter(),
-        }
+        self.bst.write_raw_snapshot(dst)
+    }
+
+    /// Reconstitutes a set from an image written by [`write_raw_snapshot`][crate::SgSet::write_raw_snapshot].
+    /// Requires the `bytemuck` feature.
+    /// See [`SgMap::from_raw_snapshot`][crate::SgMap::from_raw_snapshot] for details.
+    #[cfg(feature = "bytemuck")]
+    pub fn from_raw_snapshot(src: &[u8]) -> Result<Self, SgError>
+    where
+        T: Pod,
+        C: Default,
+    {
+        SgTree::from_raw_snapshot(src).map(|bst| SgSet { bst })
     }
 
     /// Returns an iterator over values representing set difference, e.g., values in `self` but not in `other`, in ascending order.
+    /// Lazy, walks both sets in lockstep: `O(n + m)` total, no intermediate buffer.
     ///
     /// # Examples
     ///
@@ -734,7 +2244,7 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// let diff: Vec<_> = a.difference(&b).cloned().collect();
     /// assert_eq!(diff, [1]);
     /// ```
-    pub fn difference(&self, other: &SgSet<T, N>) -> Difference<T, N>
+    pub fn difference<'a>(&'a self, other: &'a SgSet<T, N, U, C>) -> Difference<'a, T, N, U, C>
     where
         T: Ord,
     {
@@ -742,6 +2252,7 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     }
 
     /// Returns an iterator over values representing symmetric set difference, e.g., values in `self` or `other` but not both, in ascending order.
+    /// Lazy, walks both sets in lockstep: `O(n + m)` total, no intermediate buffer.
     ///
     /// # Examples
     ///
@@ -759,15 +2270,10 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// let sym_diff: Vec<_> = a.symmetric_difference(&b).cloned().collect();
     /// assert_eq!(sym_diff, [1, 3]);
     /// ```
-    ///
-    /// ### Warning
-    ///
-    /// At present, this function may panic if set capacity `N` exceeds `2048`.
-    /// The issue is that this function's returned iterator needs to be `2 * N` long to support disjoint sets,
-    /// but without unstable `feature(generic_const_exprs)` we can't compute `2 * N`.
-    /// So we use `4096` instead of `2 * N` as a workaround, hence `N` should be `<= 2048` to ensure no panic.
-    /// An `N > 2048` may or may not panic, depending on the size of sets' intersection.
-    pub fn symmetric_difference<'a>(&'a self, other: &'a SgSet<T, N>) -> SymmetricDifference<T, N>
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a SgSet<T, N, U, C>,
+    ) -> SymmetricDifference<'a, T, N, U, C>
     where
         T: Ord,
     {
@@ -775,6 +2281,7 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     }
 
     /// Returns an iterator over values representing set intersection, e.g., values in both `self` and `other`, in ascending order.
+    /// Lazy, walks both sets in lockstep: `O(n + m)` total, no intermediate buffer.
     ///
     /// # Examples
     ///
@@ -792,7 +2299,7 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// let intersection: Vec<_> = a.intersection(&b).cloned().collect();
     /// assert_eq!(intersection, [2]);
     /// ```
-    pub fn intersection(&self, other: &SgSet<T, N>) -> Intersection<T, N>
+    pub fn intersection<'a>(&'a self, other: &'a SgSet<T, N, U, C>) -> Intersection<'a, T, N, U, C>
     where
         T: Ord,
     {
@@ -800,6 +2307,7 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     }
 
     /// Returns an iterator over values representing set union, e.g., values in `self` or `other`, in ascending order.
+    /// Lazy, walks both sets in lockstep: `O(n + m)` total, no intermediate buffer.
     ///
     /// # Examples
     ///
@@ -815,19 +2323,236 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// let union: Vec<_> = a.union(&b).cloned().collect();
     /// assert_eq!(union, [1, 2]);
     /// ```
+    pub fn union<'a>(&'a self, other: &'a SgSet<T, N, U, C>) -> Union<'a, T, N, U, C>
+    where
+        T: Ord,
+    {
+        Union::new(self, other)
+    }
+
+    /// Attempt to collect the union of `self` and `other` into a new set, e.g. a fallible `&self | other`.
+    /// Returns `Err` if the union doesn't fit in capacity `N`, checked before any element is cloned.
     ///
-    /// ### Warning
+    /// # Examples
+    ///
+    /// ```
+    /// use core::iter::FromIterator;
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let a = SgSet::<_, 3>::from_iter([1, 2]);
+    /// let b = SgSet::<_, 3>::from_iter([2, 3]);
+    /// let c = SgSet::<_, 3>::from_iter([4]);
+    ///
+    /// assert!(a.try_bitor(&b).unwrap().iter().eq(&[1, 2, 3]));
+    /// assert_eq!(a.try_bitor(&b).unwrap(), &a | &b);
+    ///
+    /// // Too big
+    /// let abc = a.try_bitor(&b).unwrap();
+    /// assert_eq!(abc.try_bitor(&c), Err(SgError::StackCapacityExceeded));
+    /// ```
+    #[doc(alias = "try_union")]
+    pub fn try_bitor(&self, other: &SgSet<T, N, U, C>) -> Result<SgSet<T, N, U, C>, SgError>
+    where
+        T: Ord + Clone,
+    {
+        self.try_collect_set(|| self.union(other))
+    }
+
+    /// Attempt to collect the symmetric difference of `self` and `other` into a new set, e.g. a fallible `&self ^ other`.
+    /// Returns `Err` if the symmetric difference doesn't fit in capacity `N`, checked before any element is cloned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let a = SgSet::<_, 3>::from([1, 2, 3]);
+    /// let b = SgSet::<_, 3>::from([2, 3, 4]);
+    /// let c = SgSet::<_, 3>::from([5, 6, 7]);
+    ///
+    /// assert!(a.try_bitxor(&b).unwrap().iter().eq(&[1, 4]));
+    /// assert_eq!(a.try_bitxor(&b).unwrap(), &a ^ &b);
+    ///
+    /// // Too big
+    /// assert_eq!(a.try_bitxor(&c), Err(SgError::StackCapacityExceeded));
+    /// ```
+    #[doc(alias = "try_symmetric_difference")]
+    pub fn try_bitxor(&self, other: &SgSet<T, N, U, C>) -> Result<SgSet<T, N, U, C>, SgError>
+    where
+        T: Ord + Clone,
+    {
+        self.try_collect_set(|| self.symmetric_difference(other))
+    }
+
+    /// Retains only the values also in `other`, e.g. an in-place `self &= other`.
+    /// Walks both sets in lockstep, `O(n + m)`, with a single rebuild (like [`retain`][crate::set::SgSet::retain]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut a: SgSet<_, 10> = [1, 2, 3].iter().copied().collect();
+    /// let b: SgSet<_, 10> = [2, 3, 4].iter().copied().collect();
     ///
-    /// At present, this function may panic if set capacity `N` exceeds `2048`.
-    /// The issue is that this function's returned iterator needs to be `2 * N` long to support disjoint sets,
-    /// but without unstable `feature(generic_const_exprs)` we can't compute `2 * N`.
-    /// So we use `4096` instead of `2 * N` as a workaround, hence `N` should be `<= 2048` to ensure no panic.
-    /// An `N > 2048` may or may not panic, depending on the size of sets' intersection.
-    pub fn union<'a>(&'a self, other: &'a SgSet<T, N>) -> Union<T, N>
+    /// a.intersection_update(&b);
+    /// assert!(a.iter().eq(&[2, 3]));
+    /// ```
+    pub fn intersection_update(&mut self, other: &SgSet<T, N, U, C>)
     where
         T: Ord,
     {
-        Union::new(self, other)
+        self.priv_retain_common(other, true);
+    }
+
+    /// Removes the values also in `other`, e.g. an in-place `self -= other`.
+    /// Walks both sets in lockstep, `O(n + m)`, with a single rebuild (like [`retain`][crate::set::SgSet::retain]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut a: SgSet<_, 10> = [1, 2, 3].iter().copied().collect();
+    /// let b: SgSet<_, 10> = [2, 3, 4].iter().copied().collect();
+    ///
+    /// a.difference_update(&b);
+    /// assert!(a.iter().eq(&[1]));
+    /// ```
+    pub fn difference_update(&mut self, other: &SgSet<T, N, U, C>)
+    where
+        T: Ord,
+    {
+        self.priv_retain_common(other, false);
+    }
+
+    /// Adds clones of the values in `other` not already in `self`, e.g. an in-place `self |= other`.
+    /// Unlike [`union`][crate::set::SgSet::union] collected into a new set, doesn't need capacity for a second copy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the union doesn't fit in capacity `N`, checked before `self` is modified.
+    /// See [`try_union_update`][crate::set::SgSet::try_union_update] for a fallible alternative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut a: SgSet<_, 10> = [1, 2, 3].iter().copied().collect();
+    /// let b: SgSet<_, 10> = [2, 3, 4].iter().copied().collect();
+    ///
+    /// a.union_update(&b);
+    /// assert!(a.iter().eq(&[1, 2, 3, 4]));
+    /// ```
+    pub fn union_update(&mut self, other: &SgSet<T, N, U, C>)
+    where
+        T: Ord + Clone,
+    {
+        if self.try_union_update(other).is_err() {
+            panic!("Stack-storage capacity exceeded!");
+        }
+    }
+
+    /// Attempts to add clones of the values in `other` not already in `self`, e.g. a fallible in-place `self |= other`.
+    /// Returns `Err` if the union doesn't fit in capacity `N`, checked before `self` is modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let mut a: SgSet<_, 3> = [1, 2].iter().copied().collect();
+    /// let b: SgSet<_, 3> = [2, 3].iter().copied().collect();
+    /// let c: SgSet<_, 3> = [4].iter().copied().collect();
+    ///
+    /// assert!(a.try_union_update(&b).is_ok());
+    /// assert!(a.iter().eq(&[1, 2, 3]));
+    ///
+    /// // Too big, unchanged
+    /// assert_eq!(a.try_union_update(&c), Err(SgError::StackCapacityExceeded));
+    /// assert!(a.iter().eq(&[1, 2, 3]));
+    /// ```
+    pub fn try_union_update(&mut self, other: &SgSet<T, N, U, C>) -> Result<(), SgError>
+    where
+        T: Ord + Clone,
+    {
+        let (is_added, added_cnt) = self.priv_added_by(other);
+        if self.len() + added_cnt > self.capacity() {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        self.priv_insert_added(other, &is_added);
+        Ok(())
+    }
+
+    /// Replaces `self` with the values in `self` or `other` but not both, e.g. an in-place `self ^= other`.
+    /// Common values are removed before any clone of `other`'s is added, so peak length never exceeds the result's.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the symmetric difference doesn't fit in capacity `N`, checked before `self` is modified.
+    /// See [`try_symmetric_difference_update`][crate::set::SgSet::try_symmetric_difference_update] for a fallible alternative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// let mut a: SgSet<_, 10> = [1, 2, 3].iter().copied().collect();
+    /// let b: SgSet<_, 10> = [2, 3, 4].iter().copied().collect();
+    ///
+    /// a.symmetric_difference_update(&b);
+    /// assert!(a.iter().eq(&[1, 4]));
+    /// ```
+    pub fn symmetric_difference_update(&mut self, other: &SgSet<T, N, U, C>)
+    where
+        T: Ord + Clone,
+    {
+        if self.try_symmetric_difference_update(other).is_err() {
+            panic!("Stack-storage capacity exceeded!");
+        }
+    }
+
+    /// Attempts to replace `self` with the values in `self` or `other` but not both, e.g. a fallible in-place `self ^= other`.
+    /// Returns `Err` if the symmetric difference doesn't fit in capacity `N`, checked before `self` is modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let mut a: SgSet<_, 3> = [1, 2, 3].iter().copied().collect();
+    /// let b: SgSet<_, 3> = [3, 4].iter().copied().collect();
+    /// let c: SgSet<_, 3> = [5, 6].iter().copied().collect();
+    ///
+    /// // Fits, despite `a` being full
+    /// assert!(a.try_symmetric_difference_update(&b).is_ok());
+    /// assert!(a.iter().eq(&[1, 2, 4]));
+    ///
+    /// // Too big, unchanged
+    /// assert_eq!(a.try_symmetric_difference_update(&c), Err(SgError::StackCapacityExceeded));
+    /// assert!(a.iter().eq(&[1, 2, 4]));
+    /// ```
+    pub fn try_symmetric_difference_update(
+        &mut self,
+        other: &SgSet<T, N, U, C>,
+    ) -> Result<(), SgError>
+    where
+        T: Ord + Clone,
+    {
+        let (is_added, added_cnt) = self.priv_added_by(other);
+        let common_cnt = other.len() - added_cnt;
+        if self.len() - common_cnt + added_cnt > self.capacity() {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        if common_cnt > 0 {
+            self.priv_retain_common(other, false);
+        }
+
+        self.priv_insert_added(other, &is_added);
+        Ok(())
     }
 
     /// Returns `true` if the set contains no elements.
@@ -864,6 +2589,7 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     }
 
     /// Returns `true` if `self` has no elements in common with other (empty intersection).
+    /// Walks both sets in lockstep, `O(n + m)` worst case, returning as soon as a common element is found.
     ///
     /// # Examples
     ///
@@ -878,14 +2604,16 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// b.insert(1);
     /// assert_eq!(a.is_disjoint(&b), false);
     /// ```
-    pub fn is_disjoint(&self, other: &SgSet<T, N>) -> bool
+    pub fn is_disjoint(&self, other: &SgSet<T, N, U, C>) -> bool
     where
         T: Ord,
     {
-        self.intersection(other).count() == 0
+        // Lockstep walk, stops at the first common value
+        self.intersection(other).next().is_none()
     }
 
     /// Returns `true` if `self` is a subset of `other`, e.g., `other` contains at least all the values in `self`.
+    /// Walks both sets in lockstep, `O(n + m)` worst case, returning as soon as a value missing from `other` is found.
     ///
     /// # Examples
     ///
@@ -901,11 +2629,16 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// set.insert(4);
     /// assert_eq!(set.is_subset(&sup), false);
     /// ```
-    pub fn is_subset(&self, other: &SgSet<T, N>) -> bool
+    pub fn is_subset(&self, other: &SgSet<T, N, U, C>) -> bool
     where
         T: Ord,
     {
-        self.intersection(other).count() == self.len()
+        if self.len() > other.len() {
+            return false;
+        }
+
+        // Lockstep walk, stops at the first value missing from `other`
+        self.difference(other).next().is_none()
     }
 
     /// Returns `true` if `self` is a superset of `other`, e.g., `self` contains at least all the values in `other`.
@@ -927,20 +2660,125 @@ impl<T: Ord + Default, const N: usize> SgSet<T, N> {
     /// set.insert(2);
     /// assert_eq!(set.is_superset(&sub), true);
     /// ```
-    pub fn is_superset(&self, other: &SgSet<T, N>) -> bool
+    pub fn is_superset(&self, other: &SgSet<T, N, U, C>) -> bool
     where
         T: Ord,
     {
         other.is_subset(self)
     }
+
+    // Private API -----------------------------------------------------------------------------------------------------
+
+    // Collect a lazy set-algebra iterator into a new set.
+    // A counting pass first, so nothing is cloned if the result won't fit.
+    fn try_collect_set<'a, I, F>(&'a self, make_iter: F) -> Result<SgSet<T, N, U, C>, SgError>
+    where
+        T: Ord + Clone + 'a,
+        I: Iterator<Item = &'a T>,
+        F: Fn() -> I,
+    {
+        if make_iter().count() > self.capacity() {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        let mut set = SgSet::with_comparator(self.bst.cmp.clone());
+        set.extend(make_iter().cloned());
+        Ok(set)
+    }
+
+    // Retain values by whether they're also in `other`. `retain` visits in ascending order,
+    // so a single lockstep pass over `other` answers every membership query.
+    fn priv_retain_common(&mut self, other: &SgSet<T, N, U, C>, keep_common: bool)
+    where
+        T: Ord,
+    {
+        let cmp = &other.bst.cmp;
+        let mut other_iter = other.iter().peekable();
+        self.bst.retain(|val, _| {
+            while other_iter
+                .next_if(|other_val| cmp.compare(*other_val, val) == Ordering::Less)
+                .is_some()
+            {}
+
+            let is_common = matches!(
+                other_iter.peek(),
+                Some(other_val) if cmp.compare(*other_val, val) == Ordering::Equal
+            );
+            is_common == keep_common
+        });
+    }
+
+    // For each of `other`'s values, in order, whether it's missing from `self`. Plus the total missing.
+    fn priv_added_by(&self, other: &SgSet<T, N, U, C>) -> ([bool; N], usize)
+    where
+        T: Ord,
+    {
+        let mut is_added = [false; N];
+        let mut added_cnt = 0;
+        let mut diff_iter = other.difference(self).peekable();
+
+        // `difference` yields references into `other`, so identity suffices
+        for (rank, val) in other.iter().enumerate() {
+            if diff_iter
+                .next_if(|diff_val| ptr::eq(*diff_val, val))
+                .is_some()
+            {
+                is_added[rank] = true;
+                added_cnt += 1;
+            }
+        }
+
+        (is_added, added_cnt)
+    }
+
+    // Insert clones of `other`'s values flagged by `priv_added_by`. Caller checks capacity.
+    fn priv_insert_added(&mut self, other: &SgSet<T, N, U, C>, is_added: &[bool; N])
+    where
+        T: Ord + Clone,
+    {
+        for (val, _) in other.iter().zip(is_added).filter(|(_, added)| **added) {
+            self.bst.insert(val.clone(), ());
+        }
+    }
+}
+
+// Byte-ordered elements, where a prefix's matches are contiguous
+impl<T: Ord, const N: usize, U: ArenaIdx> SgSet<T, N, U, Natural> {
+    /// Constructs a double-ended iterator over all elements starting with `prefix`, in ascending order.
+    /// Spares computing an exclusive end bound by hand (e.g. incrementing the last byte, with carry).
+    ///
+    /// Only available with the default [`Natural`][crate::Natural] comparator, for elements ordered by their bytes:
+    /// `str`, `String`, `[u8]`, `Vec<u8>`, `[u8; M]`, and references to them.
+    /// For any other `T: AsRef<[u8]>`, the element's [`Ord`] *must* agree with lexicographic byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scapegoat::SgSet;
+    ///
+    /// // Incrementing the prefix's last byte would overflow here
+    /// let set: SgSet<&[u8], 10> = [&b"\x01\xff"[..], b"\x01\xff\x00", b"\x02", b"\x01"].iter().copied().collect();
+    /// assert!(set.range_prefix(b"\x01\xff").copied().eq([&b"\x01\xff"[..], b"\x01\xff\x00"]));
+    /// ```
+    #[doc(alias = "starts_with")]
+    pub fn range_prefix<P>(&self, prefix: &P) -> Range<'_, T, N, U>
+    where
+        T: AsRef<[u8]>,
+        P: AsRef<[u8]> + ?Sized,
+    {
+        Range {
+            table: self,
+            node_idx_iter: self.bst.prefix_search(prefix.as_ref()).into_iter(),
+        }
+    }
 }
 
 // Convenience Traits --------------------------------------------------------------------------------------------------
 
 // Debug
-impl<T, const N: usize> Debug for SgSet<T, N>
+impl<T, const N: usize, U: ArenaIdx, C: Compare<T>> Debug for SgSet<T, N, U, C>
 where
-    T: Ord + Default + Debug,
+    T: Ord + Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_set()
@@ -949,10 +2787,10 @@ where
     }
 }
 
-// From array.
+// From array (default index width, so that `SgSet::from([...])` needs no annotation).
 impl<T, const N: usize> From<[T; N]> for SgSet<T, N>
 where
-    T: Ord + Default,
+    T: Ord,
 {
     /// ```
     /// use scapegoat::SgSet;
@@ -964,7 +2802,8 @@ where
     ///
     /// ### Warning
     ///
-    /// [`TryFrom`](https://doc.rust-lang.org/stable/std/convert/trait.TryFrom.html) isn't implemented because it would collide with the blanket implementation.
+    /// [`TryFrom`](https://doc.rust-lang.org/stable/std/convert/trait.TryFrom.html) isn't implemented for arrays because it would collide with the blanket implementation.
+    /// For input whose length doesn't match `N`, convert from a slice instead: `SgSet::try_from(&arr[..])`.
     /// See [this open GitHub issue](https://github.com/rust-lang/rust/issues/50133#issuecomment-64690839) from 2018,
     /// this is a known Rust limitation that should be fixed via specialization in the future.
     #[doc(alias = "tryfrom")]
@@ -975,10 +2814,122 @@ where
     }
 }
 
+// Fallible conversion from slice, of any length.
+impl<T, const N: usize, U: ArenaIdx, C: Compare<T>> TryFrom<&[T]> for SgSet<T, N, U, C>
+where
+    T: Ord + Clone,
+    C: Default,
+{
+    type Error = SgError;
+
+    /// Clones the slice's contents into a new `SgSet`, keeping the last occurrence of duplicates.
+    /// Returns `Err(SgError::StackCapacityExceeded)` if capacity `N` is exceeded.
+    ///
+    /// ```
+    /// use core::convert::TryFrom;
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let set = SgSet::<_, 4>::try_from(&[1, 2][..]).unwrap();
+    /// assert_eq!(set.len(), 2);
+    ///
+    /// let res = SgSet::<_, 1>::try_from(&[1, 2][..]);
+    /// assert_eq!(res, Err(SgError::StackCapacityExceeded));
+    /// ```
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+        SgSet::try_from_iter(slice.iter().cloned())
+    }
+}
+
+// Fallible conversion from vector, of any length.
+#[cfg(feature = "alloc")]
+impl<T, const N: usize, U: ArenaIdx, C: Compare<T>> TryFrom<alloc::vec::Vec<T>>
+    for SgSet<T, N, U, C>
+where
+    T: Ord,
+    C: Default,
+{
+    type Error = SgError;
+
+    /// Moves the vector's contents into a new `SgSet`, keeping the last occurrence of duplicates.
+    /// Returns `Err(SgError::StackCapacityExceeded)` if capacity `N` is exceeded.
+    /// Requires the `alloc` feature.
+    ///
+    /// ```
+    /// use core::convert::TryFrom;
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let set = SgSet::<_, 4>::try_from(vec![1, 2]).unwrap();
+    /// assert_eq!(set.len(), 2);
+    ///
+    /// let res = SgSet::<_, 1>::try_from(vec![1, 2]);
+    /// assert_eq!(res, Err(SgError::StackCapacityExceeded));
+    /// ```
+    fn try_from(vec: alloc::vec::Vec<T>) -> Result<Self, Self::Error> {
+        SgSet::try_from_iter(vec.into_iter())
+    }
+}
+
+// Conversion into standard library counterpart.
+#[cfg(feature = "alloc")]
+impl<T, const N: usize, U: ArenaIdx, C: Compare<T>> From<SgSet<T, N, U, C>>
+    for alloc::collections::BTreeSet<T>
+where
+    T: Ord,
+{
+    /// Moves the contents of a `SgSet` into a new `BTreeSet`.
+    /// Requires the `alloc` feature.
+    ///
+    /// Note the `BTreeSet` orders by `Ord`, not by the `SgSet`'s comparator, `C`.
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use scapegoat::SgSet;
+    ///
+    /// let sgs = SgSet::from([2, 1]);
+    /// let bts = BTreeSet::from(sgs);
+    /// assert!(bts.into_iter().eq([1, 2]));
+    /// ```
+    fn from(sg: SgSet<T, N, U, C>) -> Self {
+        sg.into_iter().collect()
+    }
+}
+
+// Fallible conversion from standard library counterpart.
+#[cfg(feature = "alloc")]
+impl<T, const N: usize, U: ArenaIdx, C: Compare<T>> TryFrom<alloc::collections::BTreeSet<T>>
+    for SgSet<T, N, U, C>
+where
+    T: Ord,
+    C: Default,
+{
+    type Error = SgError;
+
+    /// Moves the contents of a `BTreeSet` into a new `SgSet`.
+    /// Returns `Err(SgError::StackCapacityExceeded)` if capacity `N` is exceeded.
+    /// Requires the `alloc` feature.
+    ///
+    /// ```
+    /// use core::convert::TryFrom;
+    /// use std::collections::BTreeSet;
+    /// use scapegoat::{SgSet, SgError};
+    ///
+    /// let bts = BTreeSet::from([1, 2]);
+    /// let sgs = SgSet::<_, 4>::try_from(bts.clone()).unwrap();
+    /// assert!(sgs.into_iter().eq(bts.into_iter()));
+    ///
+    /// let bts = BTreeSet::from([1, 2]);
+    /// assert_eq!(SgSet::<_, 1>::try_from(bts), Err(SgError::StackCapacityExceeded));
+    /// ```
+    fn try_from(set: alloc::collections::BTreeSet<T>) -> Result<Self, Self::Error> {
+        SgSet::try_from_iter(set.into_iter())
+    }
+}
+
 // Construct from iterator.
-impl<T, const N: usize> FromIterator<T> for SgSet<T, N>
+impl<T, const N: usize, U: ArenaIdx, C: Compare<T>> FromIterator<T> for SgSet<T, N, U, C>
 where
-    T: Ord + Default,
+    T: Ord,
+    C: Default,
 {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut sgs = SgSet::new();
@@ -988,9 +2939,9 @@ where
 }
 
 // Extension from iterator.
-impl<T, const N: usize> Extend<T> for SgSet<T, N>
+impl<T, const N: usize, U: ArenaIdx, C: Compare<T>> Extend<T> for SgSet<T, N, U, C>
 where
-    T: Ord + Default,
+    T: Ord,
 {
     fn extend<TreeIter: IntoIterator<Item = T>>(&mut self, iter: TreeIter) {
         self.bst.extend(iter.into_iter().map(|e| (e, ())));
@@ -998,9 +2949,9 @@ where
 }
 
 // Extension from reference iterator.
-impl<'a, T, const N: usize> Extend<&'a T> for SgSet<T, N>
+impl<'a, T, const N: usize, U: ArenaIdx, C: Compare<T>> Extend<&'a T> for SgSet<T, N, U, C>
 where
-    T: 'a + Ord + Default + Copy,
+    T: 'a + Ord + Copy,
 {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
         self.extend(iter.into_iter().cloned());
@@ -1010,9 +2961,11 @@ where
 // General Iterators ---------------------------------------------------------------------------------------------------
 
 // Reference iterator
-impl<'a, T: Ord + Default, const N: usize> IntoIterator for &'a SgSet<T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> IntoIterator
+    for &'a SgSet<T, N, U, C>
+{
     type Item = &'a T;
-    type IntoIter = Iter<'a, T, N>;
+    type IntoIter = Iter<'a, T, N, U, C>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
@@ -1020,9 +2973,9 @@ impl<'a, T: Ord + Default, const N: usize> IntoIterator for &'a SgSet<T, N> {
 }
 
 // Consuming iterator
-impl<T: Ord + Default, const N: usize> IntoIterator for SgSet<T, N> {
+impl<T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> IntoIterator for SgSet<T, N, U, C> {
     type Item = T;
-    type IntoIter = IntoIter<T, N>;
+    type IntoIter = IntoIter<T, N, U, C>;
 
     fn into_iter(self) -> Self::IntoIter {
         IntoIter::new(self)
@@ -1031,10 +2984,13 @@ impl<T: Ord + Default, const N: usize> IntoIterator for SgSet<T, N> {
 
 // Operator Overloading ------------------------------------------------------------------------------------------------
 
-impl<T: Ord + Default + Clone, const N: usize> Sub<&SgSet<T, N>> for &SgSet<T, N> {
-    type Output = SgSet<T, N>;
+impl<T: Ord + Clone, const N: usize, U: ArenaIdx, C: Compare<T>> Sub<&SgSet<T, N, U, C>>
+    for &SgSet<T, N, U, C>
+{
+    type Output = SgSet<T, N, U, C>;
 
-    /// Returns the difference of `self` and `rhs` as a new `SgSet<T, N>`.
+    /// Returns the difference of `self` and `rhs` as a new `SgSet<T, N, U, C>`.
+    /// Never exceeds capacity `N`, the result is a subset of `self`.
     ///
     /// # Examples
     ///
@@ -1048,15 +3004,20 @@ impl<T: Ord + Default + Clone, const N: usize> Sub<&SgSet<T, N>> for &SgSet<T, N
     /// let result_vec: Vec<_> = result.into_iter().collect();
     /// assert_eq!(result_vec, [1, 2]);
     /// ```
-    fn sub(self, rhs: &SgSet<T, N>) -> SgSet<T, N> {
-        self.difference(rhs).cloned().collect()
+    fn sub(self, rhs: &SgSet<T, N, U, C>) -> SgSet<T, N, U, C> {
+        let mut set = SgSet::with_comparator(self.bst.cmp.clone());
+        set.extend(self.difference(rhs).cloned());
+        set
     }
 }
 
-impl<T: Ord + Default + Clone, const N: usize> BitAnd<&SgSet<T, N>> for &SgSet<T, N> {
-    type Output = SgSet<T, N>;
+impl<T: Ord + Clone, const N: usize, U: ArenaIdx, C: Compare<T>> BitAnd<&SgSet<T, N, U, C>>
+    for &SgSet<T, N, U, C>
+{
+    type Output = SgSet<T, N, U, C>;
 
-    /// Returns the intersection of `self` and `rhs` as a new `SgSet<T, N>`.
+    /// Returns the intersection of `self` and `rhs` as a new `SgSet<T, N, U, C>`.
+    /// Never exceeds capacity `N`, the result is a subset of `self`.
     ///
     /// # Examples
     ///
@@ -1070,15 +3031,23 @@ impl<T: Ord + Default + Clone, const N: usize> BitAnd<&SgSet<T, N>> for &SgSet<T
     /// let result_vec: Vec<_> = result.into_iter().collect();
     /// assert_eq!(result_vec, [2, 3]);
     /// ```
-    fn bitand(self, rhs: &SgSet<T, N>) -> SgSet<T, N> {
-        self.intersection(rhs).cloned().collect()
+    fn bitand(self, rhs: &SgSet<T, N, U, C>) -> SgSet<T, N, U, C> {
+        let mut set = SgSet::with_comparator(self.bst.cmp.clone());
+        set.extend(self.intersection(rhs).cloned());
+        set
     }
 }
 
-impl<T: Ord + Default + Clone, const N: usize> BitOr<&SgSet<T, N>> for &SgSet<T, N> {
-    type Output = SgSet<T, N>;
+impl<T: Ord + Clone, const N: usize, U: ArenaIdx, C: Compare<T>> BitOr<&SgSet<T, N, U, C>>
+    for &SgSet<T, N, U, C>
+{
+    type Output = SgSet<T, N, U, C>;
 
-    /// Returns the union of `self` and `rhs` as a new `SgSet<T, N>`.
+    /// Returns the union of `self` and `rhs` as a new `SgSet<T, N, U, C>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the union exceeds capacity `N`. See [`try_bitor`][SgSet::try_bitor] for a fallible alternative.
     ///
     /// # Examples
     ///
@@ -1092,15 +3061,23 @@ impl<T: Ord + Default + Clone, const N: usize> BitOr<&SgSet<T, N>> for &SgSet<T,
     /// let result_vec: Vec<_> = result.into_iter().collect();
     /// assert_eq!(result_vec, [1, 2, 3, 4, 5]);
     /// ```
-    fn bitor(self, rhs: &SgSet<T, N>) -> SgSet<T, N> {
-        self.union(rhs).cloned().collect()
+    fn bitor(self, rhs: &SgSet<T, N, U, C>) -> SgSet<T, N, U, C> {
+        let mut set = SgSet::with_comparator(self.bst.cmp.clone());
+        set.extend(self.union(rhs).cloned());
+        set
     }
 }
 
-impl<T: Ord + Default + Clone, const N: usize> BitXor<&SgSet<T, N>> for &SgSet<T, N> {
-    type Output = SgSet<T, N>;
+impl<T: Ord + Clone, const N: usize, U: ArenaIdx, C: Compare<T>> BitXor<&SgSet<T, N, U, C>>
+    for &SgSet<T, N, U, C>
+{
+    type Output = SgSet<T, N, U, C>;
 
-    /// Returns the symmetric difference of `self` and `rhs` as a new `SgSet<T, N>`.
+    /// Returns the symmetric difference of `self` and `rhs` as a new `SgSet<T, N, U, C>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the symmetric difference exceeds capacity `N`. See [`try_bitxor`][SgSet::try_bitxor] for a fallible alternative.
     ///
     /// # Examples
     ///
@@ -1114,7 +3091,9 @@ impl<T: Ord + Default + Clone, const N: usize> BitXor<&SgSet<T, N>> for &SgSet<T
     /// let result_vec: Vec<_> = result.into_iter().collect();
     /// assert_eq!(result_vec, [1, 4]);
     /// ```
-    fn bitxor(self, rhs: &SgSet<T, N>) -> SgSet<T, N> {
-        self.symmetric_difference(rhs).cloned().collect()
+    fn bitxor(self, rhs: &SgSet<T, N, U, C>) -> SgSet<T, N, U, C> {
+        let mut set = SgSet::with_comparator(self.bst.cmp.clone());
+        set.extend(self.symmetric_difference(rhs).cloned());
+        set
     }
 }
@@ -1,11 +1,14 @@
 use core::cmp::Ordering;
-use core::iter::FusedIterator;
+use core::iter::{FusedIterator, Peekable};
+use core::ops::{Deref, DerefMut};
 
 use crate::set::SgSet;
-use crate::tree::{Idx, IntoIter as TreeIntoIter, Iter as TreeIter, SmallNode};
+use crate::tree::{
+    ArenaIdx, Compare, Cursor as TreeCursor, Drain as TreeDrain, ExtractIf as TreeExtractIf,
+    IntoIter as TreeIntoIter, Iter as TreeIter, LevelIter as TreeLevelIter, Natural, SmallNode,
+};
 
-use smallnum::SmallUnsigned;
-use tinyvec::{ArrayVec, ArrayVecIterator};
+use crate::tree::StackVec;
 
 // General Iterators ---------------------------------------------------------------------------------------------------
 
@@ -13,404 +16,413 @@ use tinyvec::{ArrayVec, ArrayVecIterator};
 ///
 /// This `struct` is created by the [`iter`][crate::set::SgSet::iter] method on [`SgSet`][crate::set::SgSet].
 /// See its documentation for more.
-pub struct Iter<'a, T: Ord + Default, const N: usize> {
-    ref_iter: TreeIter<'a, T, (), N>,
+pub struct Iter<'a, T: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    ref_iter: TreeIter<'a, T, (), N, U, C>,
 }
 
-impl<'a, T: Ord + Default, const N: usize> Iter<'a, T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Iter<'a, T, N, U, C> {
     /// Construct reference iterator.
-    pub(crate) fn new(set: &'a SgSet<T, N>) -> Self {
+    pub(crate) fn new(set: &'a SgSet<T, N, U, C>) -> Self {
         Iter {
             ref_iter: TreeIter::new(&set.bst),
         }
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> Iterator for Iter<'a, T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Iterator for Iter<'a, T, N, U, C> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.ref_iter.next().map(|(k, _)| k)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ref_iter.size_hint()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.ref_iter.last().map(|(k, _)| k)
+    }
+
+    fn min(mut self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        match C::IS_NATURAL {
+            true => self.next(),
+            false => self.min_by(Ord::cmp),
+        }
+    }
+
+    fn max(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        match C::IS_NATURAL {
+            true => self.last(),
+            false => self.max_by(Ord::cmp),
+        }
+    }
 }
 
-impl<'a, T: Ord + Default, const N: usize> ExactSizeIterator for Iter<'a, T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> DoubleEndedIterator
+    for Iter<'a, T, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> ExactSizeIterator
+    for Iter<'a, T, N, U, C>
+{
     fn len(&self) -> usize {
         self.ref_iter.len()
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> FusedIterator for Iter<'a, T, N> {}
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> FusedIterator
+    for Iter<'a, T, N, U, C>
+{
+}
+
+/// A level-order (breadth-first) iterator over the items of a [`SgSet`][crate::set::SgSet], with each item's depth.
+///
+/// This `struct` is created by the [`iter_levels`][crate::set::SgSet::iter_levels] method on [`SgSet`][crate::set::SgSet].
+/// See its documentation for more.
+pub struct Levels<'a, T: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    level_iter: TreeLevelIter<'a, T, (), N, U, C>,
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Levels<'a, T, N, U, C> {
+    /// Construct level-order iterator.
+    pub(crate) fn new(set: &'a SgSet<T, N, U, C>) -> Self {
+        Levels {
+            level_iter: TreeLevelIter::new(&set.bst),
+        }
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Iterator for Levels<'a, T, N, U, C> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.level_iter.next().map(|(d, k, _)| (d, k))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.level_iter.size_hint()
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> ExactSizeIterator
+    for Levels<'a, T, N, U, C>
+{
+    fn len(&self) -> usize {
+        self.level_iter.len()
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> FusedIterator
+    for Levels<'a, T, N, U, C>
+{
+}
 
 /// An owning iterator over the items of a [`SgSet`][crate::set::SgSet].
 ///
 /// This `struct` is created by the [`into_iter`][crate::set::SgSet::into_iter] method on [`SgSet`][crate::set::SgSet]
 /// (provided by the IntoIterator trait). See its documentation for more.
-pub struct IntoIter<T: Ord + Default, const N: usize> {
-    cons_iter: TreeIntoIter<T, (), N>,
+pub struct IntoIter<T: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    cons_iter: TreeIntoIter<T, (), N, U, C>,
 }
 
-impl<T: Ord + Default, const N: usize> IntoIter<T, N> {
+impl<T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> IntoIter<T, N, U, C> {
     /// Construct owning iterator.
-    pub(crate) fn new(set: SgSet<T, N>) -> Self {
+    pub(crate) fn new(set: SgSet<T, N, U, C>) -> Self {
         IntoIter {
             cons_iter: TreeIntoIter::new(set.bst),
         }
     }
 }
 
-impl<T: Ord + Default, const N: usize> Iterator for IntoIter<T, N> {
+impl<T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Iterator for IntoIter<T, N, U, C> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.cons_iter.next().map(|(k, _)| k)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cons_iter.size_hint()
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        self.cons_iter.last().map(|(k, _)| k)
+    }
+
+    fn min(mut self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        match C::IS_NATURAL {
+            true => self.next(),
+            false => self.min_by(Ord::cmp),
+        }
+    }
+
+    fn max(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        match C::IS_NATURAL {
+            true => self.last(),
+            false => self.max_by(Ord::cmp),
+        }
+    }
 }
 
-impl<T: Ord + Default, const N: usize> ExactSizeIterator for IntoIter<T, N> {
+impl<T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> DoubleEndedIterator
+    for IntoIter<T, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.cons_iter.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> ExactSizeIterator
+    for IntoIter<T, N, U, C>
+{
     fn len(&self) -> usize {
         self.cons_iter.len()
     }
 }
 
-impl<T: Ord + Default, const N: usize> FusedIterator for IntoIter<T, N> {}
+impl<T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> FusedIterator for IntoIter<T, N, U, C> {}
 
-/*
-Workaround Note:
+// Set Algebra Iterators -----------------------------------------------------------------------------------------------
 
-The remaining iterators in this file only store indexes into the input set(s) iterator(s) and have to
-recover set elements with `set.iter().nth(idx)`. Rather inefficient, solves a blocking problem:
-in `ArrayVecIterator<[&'a T; N]>` `Default` is not implemented for `&'a T`.
+// Lockstep in-order walk over two sets, `O(n + m)` total. Each step yields the next smallest element,
+// from `this`, `other`, or both (if equal). Holds only the two underlying iterators, no index buffers.
+struct MergeIter<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> {
+    this_iter: Peekable<Iter<'a, T, N, U, C>>,
+    other_iter: Peekable<Iter<'a, T, N, U, C>>,
+    cmp: &'a C,
+}
 
-TODO: faster solution?
-*/
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> MergeIter<'a, T, N, U, C> {
+    fn new(this: &'a SgSet<T, N, U, C>, other: &'a SgSet<T, N, U, C>) -> Self {
+        MergeIter {
+            this_iter: this.iter().peekable(),
+            other_iter: other.iter().peekable(),
+            cmp: &this.bst.cmp,
+        }
+    }
 
-// TODO: without `feature(generic_const_exprs)`, `Union` and `SymmetricDifference` cannot compute `2 * N` length
-// iterator to support disjoint sets. This is a temporary workaround, documented in external API docs.
-const PLACEHOLDER_2N: usize = 4096;
+    fn nexts(&mut self) -> Option<(Option<&'a T>, Option<&'a T>)> {
+        let ord = match (self.this_iter.peek(), self.other_iter.peek()) {
+            (None, None) => return None,
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(this_val), Some(other_val)) => self.cmp.compare(this_val, other_val),
+        };
+
+        match ord {
+            Ordering::Less => Some((self.this_iter.next(), None)),
+            Ordering::Greater => Some((None, self.other_iter.next())),
+            Ordering::Equal => Some((self.this_iter.next(), self.other_iter.next())),
+        }
+    }
 
-// Intersection Iterator -----------------------------------------------------------------------------------------------
+    // Remaining lengths of (`this`, `other`).
+    fn lens(&self) -> (usize, usize) {
+        (self.this_iter.len(), self.other_iter.len())
+    }
+}
 
-// TODO: these need more trait implementations for full compatibility
-// TODO: make this a lazy iterator like `std::collections::btree_set::Intersection`
+// Intersection Iterator -----------------------------------------------------------------------------------------------
 
-/// An iterator producing elements in the intersection of [`SgSet`][crate::set::SgSet]s.
+/// A lazy iterator producing elements in the intersection of [`SgSet`][crate::set::SgSet]s, in ascending order.
 ///
-/// This `struct` is created by the [`intersection`][crate::set::SgSet::difference] method on [`SgSet`][crate::set::SgSet].
+/// This `struct` is created by the [`intersection`][crate::set::SgSet::intersection] method on [`SgSet`][crate::set::SgSet].
 /// See its documentation for more.
-pub struct Intersection<'a, T: Ord + Default, const N: usize> {
-    pub(crate) inner: ArrayVecIterator<[Idx; N]>,
-    set_this: &'a SgSet<T, N>,
-    total_cnt: usize,
-    spent_cnt: usize,
+pub struct Intersection<'a, T: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    merge_iter: MergeIter<'a, T, N, U, C>,
 }
 
-impl<'a, T: Ord + Default, const N: usize> Intersection<'a, T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Intersection<'a, T, N, U, C> {
     /// Construct `Intersection` iterator.
     /// Values that are both in `this` and `other`.
-    pub(crate) fn new(this: &'a SgSet<T, N>, other: &SgSet<T, N>) -> Self {
-        let mut self_enum_iter = this.iter().enumerate();
-        let mut other_enum_iter = other.iter().enumerate();
-
-        let mut opt_self = self_enum_iter.next();
-        let mut opt_other = other_enum_iter.next();
-
-        let mut inter = ArrayVec::default();
-        let mut len = 0;
-
-        // If either is shorter, short-circuit.
-        while let (Some((self_idx, self_val)), Some((_, other_val))) = (opt_self, opt_other) {
-            match self_val.cmp(other_val) {
-                Ordering::Less => {
-                    opt_self = self_enum_iter.next();
-                }
-                Ordering::Equal => {
-                    inter.push(Idx::checked_from(self_idx));
-                    len += 1;
-                    opt_self = self_enum_iter.next();
-                    opt_other = other_enum_iter.next();
-                }
-                Ordering::Greater => {
-                    opt_other = other_enum_iter.next();
-                }
-            }
-        }
-
+    pub(crate) fn new(this: &'a SgSet<T, N, U, C>, other: &'a SgSet<T, N, U, C>) -> Self {
         Intersection {
-            inner: inter.into_iter(),
-            set_this: this,
-            total_cnt: len,
-            spent_cnt: 0,
+            merge_iter: MergeIter::new(this, other),
         }
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> Iterator for Intersection<'a, T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Iterator
+    for Intersection<'a, T, N, U, C>
+{
     type Item = &'a T;
 
     fn next(&mut self) -> Option<&'a T> {
-        match self.inner.next() {
-            Some(idx) => match self.set_this.iter().nth(idx.usize()) {
-                Some(item) => {
-                    self.spent_cnt += 1;
-                    Some(item)
-                }
-                None => None,
-            },
-            None => None,
+        loop {
+            match self.merge_iter.nexts()? {
+                (Some(this_val), Some(_)) => return Some(this_val),
+                // Either side exhausted, nothing left in common
+                (Some(_), None) if self.merge_iter.other_iter.peek().is_none() => return None,
+                (None, Some(_)) if self.merge_iter.this_iter.peek().is_none() => return None,
+                _ => continue,
+            }
         }
     }
-}
 
-impl<'a, T: Ord + Default, const N: usize> ExactSizeIterator for Intersection<'a, T, N> {
-    fn len(&self) -> usize {
-        debug_assert!(self.spent_cnt <= self.total_cnt);
-        self.total_cnt - self.spent_cnt
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (this_len, other_len) = self.merge_iter.lens();
+        (0, Some(this_len.min(other_len)))
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> FusedIterator for Intersection<'a, T, N> {}
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> FusedIterator
+    for Intersection<'a, T, N, U, C>
+{
+}
 
 // Difference Iterator -------------------------------------------------------------------------------------------------
 
-// TODO: these need more trait implementations for full compatibility
-// TODO: make this a lazy iterator like `std::collections::btree_set::Difference`
-
-/// An iterator producing elements in the difference of [`SgSet`][crate::set::SgSet]s.
+/// A lazy iterator producing elements in the difference of [`SgSet`][crate::set::SgSet]s, in ascending order.
 ///
 /// This `struct` is created by the [`difference`][crate::set::SgSet::difference] method
 /// on [`SgSet`][crate::set::SgSet]. See its documentation for more.
-pub struct Difference<'a, T: Ord + Default, const N: usize> {
-    pub(crate) inner: ArrayVecIterator<[Idx; N]>,
-    set_this: &'a SgSet<T, N>,
-    total_cnt: usize,
-    spent_cnt: usize,
+pub struct Difference<'a, T: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    merge_iter: MergeIter<'a, T, N, U, C>,
 }
 
-impl<'a, T: Ord + Default, const N: usize> Difference<'a, T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Difference<'a, T, N, U, C> {
     /// Construct `Difference` iterator.
     /// Values that are in `this` but not in `other`.
-    pub(crate) fn new(this: &'a SgSet<T, N>, other: &SgSet<T, N>) -> Self {
-        let mut diff = ArrayVec::default();
-        let mut len = 0;
-
-        for (idx, val) in this.iter().enumerate() {
-            if !other.contains(val) {
-                diff.push(Idx::checked_from(idx));
-                len += 1;
-            }
-        }
-
+    pub(crate) fn new(this: &'a SgSet<T, N, U, C>, other: &'a SgSet<T, N, U, C>) -> Self {
         Difference {
-            inner: diff.into_iter(),
-            set_this: this,
-            total_cnt: len,
-            spent_cnt: 0,
+            merge_iter: MergeIter::new(this, other),
         }
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> Iterator for Difference<'a, T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Iterator
+    for Difference<'a, T, N, U, C>
+{
     type Item = &'a T;
 
     fn next(&mut self) -> Option<&'a T> {
-        match self.inner.next() {
-            Some(idx) => match self.set_this.iter().nth(idx.usize()) {
-                Some(item) => {
-                    self.spent_cnt += 1;
-                    Some(item)
-                }
-                None => None,
-            },
-            None => None,
+        loop {
+            match self.merge_iter.nexts()? {
+                (Some(this_val), None) => return Some(this_val),
+                // `this` exhausted, nothing left to subtract from
+                (None, Some(_)) if self.merge_iter.this_iter.peek().is_none() => return None,
+                _ => continue,
+            }
         }
     }
-}
 
-impl<'a, T: Ord + Default, const N: usize> ExactSizeIterator for Difference<'a, T, N> {
-    fn len(&self) -> usize {
-        debug_assert!(self.spent_cnt <= self.total_cnt);
-        self.total_cnt - self.spent_cnt
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (this_len, other_len) = self.merge_iter.lens();
+        (this_len.saturating_sub(other_len), Some(this_len))
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> FusedIterator for Difference<'a, T, N> {}
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> FusedIterator
+    for Difference<'a, T, N, U, C>
+{
+}
 
 // Symmetric Difference Iterator ---------------------------------------------------------------------------------------
 
-// TODO: these need more trait implementations for full compatibility
-// TODO: make this a lazy iterator like `std::collections::btree_set::Difference`
-
-/// An iterator producing elements in the symmetric difference of [`SgSet`][crate::set::SgSet]s.
+/// A lazy iterator producing elements in the symmetric difference of [`SgSet`][crate::set::SgSet]s, in ascending order.
 ///
 /// This `struct` is created by the [`symmetric_difference`][crate::set::SgSet::symmetric_difference]
 /// method on [`SgSet`][crate::set::SgSet]. See its documentation for more.
-pub struct SymmetricDifference<'a, T: Ord + Default, const N: usize> {
-    pub(crate) inner: ArrayVecIterator<[(Idx, bool); PLACEHOLDER_2N]>, // TODO: placeholder
-    set_this: &'a SgSet<T, N>,
-    set_other: &'a SgSet<T, N>,
-    total_cnt: usize,
-    spent_cnt: usize,
+pub struct SymmetricDifference<
+    'a,
+    T: Ord,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<T> = Natural,
+> {
+    merge_iter: MergeIter<'a, T, N, U, C>,
 }
 
-impl<'a, T: Ord + Default, const N: usize> SymmetricDifference<'a, T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> SymmetricDifference<'a, T, N, U, C> {
     /// Construct `SymmetricDifference` iterator.
     /// Values that are in `this` or in `other` but not in both.
-    pub(crate) fn new(this: &'a SgSet<T, N>, other: &'a SgSet<T, N>) -> Self {
-        let mut sym_diff = ArrayVec::default();
-        let mut len = 0;
-
-        for (idx, val) in this.iter().enumerate() {
-            if !other.contains(val) {
-                sym_diff.push((Idx::checked_from(idx), true));
-                len += 1;
-            }
-        }
-
-        for (idx, val) in other.iter().enumerate() {
-            if !this.contains(val) {
-                sym_diff.push((Idx::checked_from(idx), false));
-                len += 1;
-            }
-        }
-
-        // Ascending order
-        sym_diff.sort_unstable_by_key(|(idx, in_this): &(Idx, bool)| match in_this {
-            true => this.iter().nth(idx.usize()),
-            false => other.iter().nth(idx.usize()),
-        });
-
+    pub(crate) fn new(this: &'a SgSet<T, N, U, C>, other: &'a SgSet<T, N, U, C>) -> Self {
         SymmetricDifference {
-            inner: sym_diff.into_iter(),
-            set_this: this,
-            set_other: other,
-            total_cnt: len,
-            spent_cnt: 0,
+            merge_iter: MergeIter::new(this, other),
         }
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> Iterator for SymmetricDifference<'a, T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Iterator
+    for SymmetricDifference<'a, T, N, U, C>
+{
     type Item = &'a T;
 
     fn next(&mut self) -> Option<&'a T> {
-        match self.inner.next() {
-            Some((idx, in_this)) => match in_this {
-                true => match self.set_this.iter().nth(idx.usize()) {
-                    Some(item) => {
-                        self.spent_cnt += 1;
-                        Some(item)
-                    }
-                    None => None,
-                },
-                false => match self.set_other.iter().nth(idx.usize()) {
-                    Some(item) => {
-                        self.spent_cnt += 1;
-                        Some(item)
-                    }
-                    None => None,
-                },
-            },
-            None => None,
+        loop {
+            match self.merge_iter.nexts()? {
+                (Some(_), Some(_)) => continue,
+                (this_opt, other_opt) => return this_opt.or(other_opt),
+            }
         }
     }
-}
 
-impl<'a, T: Ord + Default, const N: usize> ExactSizeIterator for SymmetricDifference<'a, T, N> {
-    fn len(&self) -> usize {
-        debug_assert!(self.spent_cnt <= self.total_cnt);
-        self.total_cnt - self.spent_cnt
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (this_len, other_len) = self.merge_iter.lens();
+        (0, this_len.checked_add(other_len))
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> FusedIterator for SymmetricDifference<'a, T, N> {}
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> FusedIterator
+    for SymmetricDifference<'a, T, N, U, C>
+{
+}
 
 // Union Iterator ------------------------------------------------------------------------------------------------------
 
-// TODO: these need more trait implementations for full compatibility
-// TODO: make this a lazy iterator like `std::collections::btree_set::Union`
-
-/// An iterator producing elements in the union of [`SgSet`][crate::set::SgSet]s.
+/// A lazy iterator producing elements in the union of [`SgSet`][crate::set::SgSet]s, in ascending order.
 ///
-/// This `struct` is created by the [`union`][crate::set::SgSet::difference] method on [`SgSet`][crate::set::SgSet].
+/// This `struct` is created by the [`union`][crate::set::SgSet::union] method on [`SgSet`][crate::set::SgSet].
 /// See its documentation for more.
-pub struct Union<'a, T: Ord + Default, const N: usize> {
-    pub(crate) inner: ArrayVecIterator<[(Idx, bool); PLACEHOLDER_2N]>,
-    set_this: &'a SgSet<T, N>,
-    set_other: &'a SgSet<T, N>,
-    total_cnt: usize,
-    spent_cnt: usize,
+pub struct Union<'a, T: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    merge_iter: MergeIter<'a, T, N, U, C>,
 }
 
-impl<'a, T: Ord + Default, const N: usize> Union<'a, T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Union<'a, T, N, U, C> {
     /// Construct `Union` iterator.
     /// Values in `this` or `other`, without duplicates.
-    pub(crate) fn new(this: &'a SgSet<T, N>, other: &'a SgSet<T, N>) -> Self {
-        let mut uni = ArrayVec::default();
-        let mut len = 0;
-
-        for (idx, _) in this.iter().enumerate() {
-            uni.push((Idx::checked_from(idx), true));
-            len += 1;
-        }
-
-        for (idx, val) in other.iter().enumerate() {
-            if !this.contains(val) {
-                uni.push((Idx::checked_from(idx), false));
-                len += 1;
-            }
-        }
-
-        // Ascending order
-        uni.sort_unstable_by_key(|(idx, in_this): &(Idx, bool)| match in_this {
-            true => this.iter().nth(idx.usize()),
-            false => other.iter().nth(idx.usize()),
-        });
-
+    pub(crate) fn new(this: &'a SgSet<T, N, U, C>, other: &'a SgSet<T, N, U, C>) -> Self {
         Union {
-            inner: uni.into_iter(),
-            set_this: this,
-            set_other: other,
-            total_cnt: len,
-            spent_cnt: 0,
+            merge_iter: MergeIter::new(this, other),
         }
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> Iterator for Union<'a, T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Iterator for Union<'a, T, N, U, C> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<&'a T> {
-        match self.inner.next() {
-            Some((idx, in_this)) => match in_this {
-                true => match self.set_this.iter().nth(idx.usize()) {
-                    Some(item) => {
-                        self.spent_cnt += 1;
-                        Some(item)
-                    }
-                    None => None,
-                },
-                false => match self.set_other.iter().nth(idx.usize()) {
-                    Some(item) => {
-                        self.spent_cnt += 1;
-                        Some(item)
-                    }
-                    None => None,
-                },
-            },
-            None => None,
-        }
+        let (this_opt, other_opt) = self.merge_iter.nexts()?;
+        this_opt.or(other_opt)
     }
-}
 
-impl<'a, T: Ord + Default, const N: usize> ExactSizeIterator for Union<'a, T, N> {
-    fn len(&self) -> usize {
-        debug_assert!(self.spent_cnt <= self.total_cnt);
-        self.total_cnt - self.spent_cnt
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (this_len, other_len) = self.merge_iter.lens();
+        (this_len.max(other_len), this_len.checked_add(other_len))
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> FusedIterator for Union<'a, T, N> {}
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> FusedIterator
+    for Union<'a, T, N, U, C>
+{
+}
 
 // Range APIs ----------------------------------------------------------------------------------------------------------
 
@@ -420,12 +432,12 @@ impl<'a, T: Ord + Default, const N: usize> FusedIterator for Union<'a, T, N> {}
 /// See its documentation for more.
 ///
 /// [`range`]: SgSet::range
-pub struct Range<'a, T: Ord + Default, const N: usize> {
-    pub(crate) table: &'a SgSet<T, N>,
-    pub(crate) node_idx_iter: <ArrayVec<[usize; N]> as IntoIterator>::IntoIter,
+pub struct Range<'a, T: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    pub(crate) table: &'a SgSet<T, N, U, C>,
+    pub(crate) node_idx_iter: <StackVec<[usize; N]> as IntoIterator>::IntoIter,
 }
 
-impl<'a, T: Ord + Default, const N: usize> Iterator for Range<'a, T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Iterator for Range<'a, T, N, U, C> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -435,7 +447,9 @@ impl<'a, T: Ord + Default, const N: usize> Iterator for Range<'a, T, N> {
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> DoubleEndedIterator for Range<'a, T, N> {
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> DoubleEndedIterator
+    for Range<'a, T, N, U, C>
+{
     fn next_back(&mut self) -> Option<Self::Item> {
         let node_idx = self.node_idx_iter.next_back()?;
         let node = &self.table.bst.arena[node_idx];
@@ -443,4 +457,199 @@ impl<'a, T: Ord + Default, const N: usize> DoubleEndedIterator for Range<'a, T,
     }
 }
 
-impl<'a, T: Ord + Default, const N: usize> FusedIterator for Range<'a, T, N> {}
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> FusedIterator
+    for Range<'a, T, N, U, C>
+{
+}
+
+// Draining Iterator ---------------------------------------------------------------------------------------------------
+
+/// A draining iterator over the items of a [`SgSet`][crate::set::SgSet].
+///
+/// This `struct` is created by the [`drain`][crate::set::SgSet::drain] method on [`SgSet`][crate::set::SgSet].
+/// See its documentation for more.
+pub struct Drain<'a, T: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    inner: TreeDrain<'a, T, (), N, U, C>,
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Drain<'a, T, N, U, C> {
+    /// Construct draining iterator.
+    pub(crate) fn new(set: &'a mut SgSet<T, N, U, C>) -> Self {
+        Drain {
+            inner: TreeDrain::new(&mut set.bst),
+        }
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Iterator for Drain<'a, T, N, U, C> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> DoubleEndedIterator
+    for Drain<'a, T, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> ExactSizeIterator
+    for Drain<'a, T, N, U, C>
+{
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> FusedIterator
+    for Drain<'a, T, N, U, C>
+{
+}
+
+// Extracting Iterator -------------------------------------------------------------------------------------------------
+
+/// An iterator produced by calling [`extract_if`][crate::set::SgSet::extract_if] on [`SgSet`][crate::set::SgSet].
+/// See its documentation for more.
+pub struct ExtractIf<'a, T: Ord, F, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural>
+where
+    F: FnMut(&T) -> bool,
+{
+    inner: TreeExtractIf<'a, T, (), N, U, C>,
+    pred: F,
+}
+
+impl<'a, T: Ord, F, const N: usize, U: ArenaIdx, C: Compare<T>> ExtractIf<'a, T, F, N, U, C>
+where
+    F: FnMut(&T) -> bool,
+{
+    /// Construct extracting iterator.
+    pub(crate) fn new(set: &'a mut SgSet<T, N, U, C>, pred: F) -> Self {
+        ExtractIf {
+            inner: TreeExtractIf::new(&mut set.bst),
+            pred,
+        }
+    }
+}
+
+impl<'a, T: Ord, F, const N: usize, U: ArenaIdx, C: Compare<T>> Iterator
+    for ExtractIf<'a, T, F, N, U, C>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pred = &mut self.pred;
+        self.inner.next_with(&mut |k, _| pred(k)).map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.inner.remaining()))
+    }
+}
+
+impl<'a, T: Ord, F, const N: usize, U: ArenaIdx, C: Compare<T>> FusedIterator
+    for ExtractIf<'a, T, F, N, U, C>
+where
+    F: FnMut(&T) -> bool,
+{
+}
+
+// Cursor APIs ---------------------------------------------------------------------------------------------------------
+
+/// A read-only cursor over a [`SgSet`][crate::set::SgSet].
+///
+/// A cursor points into the gap between two adjacent items (or before the first/after the last),
+/// and can move in either direction. Each move is `O(log n)`, the cursor itself is `O(1)` space.
+///
+/// This `struct` is created by the [`lower_bound`][crate::set::SgSet::lower_bound] and
+/// [`upper_bound`][crate::set::SgSet::upper_bound] methods on [`SgSet`][crate::set::SgSet].
+/// See their documentation for more.
+pub struct Cursor<'a, T: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    cursor: TreeCursor<'a, T, (), N, U, C>,
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Cursor<'a, T, N, U, C> {
+    /// Construct cursor.
+    pub(crate) fn new(cursor: TreeCursor<'a, T, (), N, U, C>) -> Self {
+        Cursor { cursor }
+    }
+
+    /// Returns the item after the cursor, without moving it.
+    pub fn peek_next(&self) -> Option<&'a T> {
+        self.cursor.peek_next().map(|(k, _)| k)
+    }
+
+    /// Returns the item before the cursor, without moving it.
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        self.cursor.peek_prev().map(|(k, _)| k)
+    }
+
+    /// Advances the cursor past the next item, returning it.
+    /// Returns `None`, without moving, if the cursor is after the last item.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<&'a T> {
+        self.cursor.next().map(|(k, _)| k)
+    }
+
+    /// Moves the cursor back past the previous item, returning it.
+    /// Returns `None`, without moving, if the cursor is before the first item.
+    pub fn prev(&mut self) -> Option<&'a T> {
+        self.cursor.prev().map(|(k, _)| k)
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Clone for Cursor<'a, T, N, U, C> {
+    fn clone(&self) -> Self {
+        Cursor {
+            cursor: self.cursor.clone(),
+        }
+    }
+}
+
+// Bulk Load Guard -----------------------------------------------------------------------------------------------------
+
+/// A scoped guard that suspends rebalancing of a [`SgSet`][crate::set::SgSet] for a batched insert phase.
+///
+/// This `struct` is created by the [`bulk_load`][crate::set::SgSet::bulk_load] method on [`SgSet`][crate::set::SgSet].
+/// See its documentation for more.
+pub struct BulkLoad<'a, T: Ord, const N: usize, U: ArenaIdx = u16, C: Compare<T> = Natural> {
+    set: &'a mut SgSet<T, N, U, C>,
+    saved_alpha: (u32, u32),
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> BulkLoad<'a, T, N, U, C> {
+    /// Construct bulk load guard.
+    pub(crate) fn new(set: &'a mut SgSet<T, N, U, C>) -> Self {
+        let saved_alpha = set.bst.begin_bulk_load();
+        BulkLoad { set, saved_alpha }
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Deref for BulkLoad<'a, T, N, U, C> {
+    type Target = SgSet<T, N, U, C>;
+
+    fn deref(&self) -> &Self::Target {
+        self.set
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> DerefMut for BulkLoad<'a, T, N, U, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.set
+    }
+}
+
+impl<'a, T: Ord, const N: usize, U: ArenaIdx, C: Compare<T>> Drop for BulkLoad<'a, T, N, U, C> {
+    fn drop(&mut self) {
+        self.set.bst.end_bulk_load(self.saved_alpha);
+    }
+}
@@ -1,11 +1,13 @@
+use core::marker::PhantomData;
 use core::ops::{Index, IndexMut};
 use core::slice::{Iter, IterMut};
 
+use super::idx::ArenaIdx;
 use super::node::{Node, NodeGetHelper, NodeSwapHistHelper};
 use super::node_dispatch::SmallNode;
+use super::storage::SlotStorage;
 
-use smallnum::SmallUnsigned;
-use tinyvec::ArrayVec;
+use super::stack_vec::StackVec;
 
 /*
 Note:
@@ -16,34 +18,42 @@ If caller obeys contract, `U` will be smallest unsigned capable of representing
 */
 
 /// An arena allocator, meta programmable for low memory footprint.
+/// Slots live in `S`, inline storage unless otherwise specified. See [`SlotStorage`].
 #[derive(Clone, Debug)]
-pub struct Arena<K: Default, V: Default, U: Default, const N: usize> {
-    vec: ArrayVec<[Option<Node<K, V, U>>; N]>,
+pub struct Arena<K, V, U: ArenaIdx, const N: usize, S = StackVec<[Option<Node<K, V, U>>; N]>> {
+    vec: S,
 
     #[cfg(not(feature = "low_mem_insert"))]
-    free_list: ArrayVec<[U; N]>,
+    free_list: StackVec<[U; N]>,
+
+    // Bumped whenever a node leaves its slot, see `NodeHandle`
+    epoch: usize,
+
+    // `S` holds the nodes
+    _nodes: PhantomData<Node<K, V, U>>,
 }
 
-impl<
-        K: Default,
-        V: Default,
-        U: Default + Copy + SmallUnsigned + Ord + PartialEq + PartialOrd,
-        const N: usize,
-    > Arena<K, V, U, N>
+impl<K, V, U, const N: usize, S> Arena<K, V, U, N, S>
+where
+    U: ArenaIdx,
+    S: SlotStorage<Option<Node<K, V, U>>>,
 {
     // TODO: is this function necessary?
     /// Const associated constructor for index scratch vector.
-    pub fn new_idx_vec() -> ArrayVec<[U; N]> {
-        ArrayVec::<[U; N]>::default()
+    pub fn new_idx_vec() -> StackVec<[U; N]> {
+        StackVec::<[U; N]>::default()
     }
 
     /// Constructor.
     pub fn new() -> Self {
         let a = Arena {
-            vec: ArrayVec::<[Option<Node<K, V, U>>; N]>::new(),
+            vec: S::default(),
 
             #[cfg(not(feature = "low_mem_insert"))]
-            free_list: ArrayVec::<[U; N]>::new(),
+            free_list: StackVec::<[U; N]>::new(),
+
+            epoch: 0,
+            _nodes: PhantomData,
         };
 
         #[cfg(not(feature = "low_mem_insert"))]
@@ -71,6 +81,16 @@ impl<
         N
     }
 
+    /// Generation counter, incremented every time a node is removed or moved.
+    pub fn epoch(&self) -> usize {
+        self.epoch
+    }
+
+    /// Overwrite generation counter (e.g. to carry it across a reset).
+    pub fn set_epoch(&mut self, epoch: usize) {
+        self.epoch = epoch;
+    }
+
     /// Add node to area, growing if necessary, and return addition index.
     pub fn add(&mut self, key: K, val: V) -> usize {
         // O(1) find, constant time
@@ -111,12 +131,13 @@ impl<
 
         if self.is_occupied(idx) {
             // Extract node
-            let node = core::mem::replace(&mut self.vec[idx], None);
+            let node = self.vec[idx].take();
 
             // Append removed index to free list
             #[cfg(not(feature = "low_mem_insert"))]
             self.free_list.push(U::checked_from(idx));
 
+            self.epoch = self.epoch.wrapping_add(1);
             return node;
         }
 
@@ -139,11 +160,12 @@ impl<
     pub fn sort(
         &mut self,
         root_idx: usize,
-        sort_metadata: ArrayVec<[NodeGetHelper<usize>; N]>, // `usize` here avoids `U` in tree iter signatures
+        sort_metadata: StackVec<[NodeGetHelper<usize>; N]>, // `usize` here avoids `U` in tree iter signatures
     ) -> usize {
         debug_assert!(sort_metadata.iter().all(|ngh| ngh.node_idx().is_some()));
 
         let mut swap_history = NodeSwapHistHelper::<U, N>::new();
+        self.epoch = self.epoch.wrapping_add(1);
 
         // Sort as requested
         for (sorted_idx, ngh) in sort_metadata.iter().enumerate() {
@@ -184,9 +206,18 @@ impl<
         swap_history.curr_idx(root_idx)
     }
 
-    /// Returns the number of entries in the arena, some of which may be `None`.
-    pub fn len(&self) -> usize {
-        self.vec.len()
+    /// Release trailing free slots, so that the last entry is occupied.
+    /// Later additions without a free slot reuse the released positions, in order.
+    pub fn shrink_to_fit(&mut self) {
+        while let Some(None) = self.vec.last() {
+            self.vec.pop();
+        }
+
+        #[cfg(not(feature = "low_mem_insert"))]
+        {
+            let len = self.vec.len();
+            self.free_list.retain(|free_idx| free_idx.usize() < len);
+        }
     }
 
     /// Returns true if the index is occupied, e.g. `Some(node)`.
@@ -198,13 +229,126 @@ impl<
     pub fn node_size(&self) -> usize {
         core::mem::size_of::<Node<K, V, U>>()
     }
+
+    /// Get the size of an individual arena slot (occupied or not), in bytes.
+    pub fn slot_size(&self) -> usize {
+        core::mem::size_of::<Option<Node<K, V, U>>>()
+    }
+
+    /// Number of slots in use or freed, e.g. one past the highest slot index ever handed out (unless since released).
+    pub fn slot_cnt(&self) -> usize {
+        self.vec.len()
+    }
+
+    /// Append a slot, occupied or not, past the current last slot. Vacant slots join the free list.
+    /// For reconstituting a known layout, e.g. from a raw snapshot.
+    #[cfg(feature = "bytemuck")]
+    pub fn push_slot(&mut self, opt_node: Option<Node<K, V, U>>) {
+        #[cfg(not(feature = "low_mem_insert"))]
+        if opt_node.is_none() {
+            self.free_list.push(U::checked_from(self.vec.len()));
+        }
+
+        self.vec.push(opt_node);
+    }
+
+    /// Get mutable references to two distinct nodes at once.
+    /// Both indexed locations MUST be occupied.
+    pub fn pair_mut(
+        &mut self,
+        idx_a: usize,
+        idx_b: usize,
+    ) -> (&mut Node<K, V, U>, &mut Node<K, V, U>) {
+        debug_assert_ne!(idx_a, idx_b, "API misuse: requested aliasing node pair!");
+
+        let (lo_idx, hi_idx) = (idx_a.min(idx_b), idx_a.max(idx_b));
+        let (lo_slice, hi_slice) = self.vec.split_at_mut(hi_idx);
+        match (lo_slice[lo_idx].as_mut(), hi_slice[0].as_mut()) {
+            (Some(lo_node), Some(hi_node)) => match idx_a < idx_b {
+                true => (lo_node, hi_node),
+                false => (hi_node, lo_node),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    /// Get a mutable reference to a node alongside immutable references to its children.
+    /// Indexed location MUST be occupied.
+    #[allow(clippy::type_complexity)]
+    pub fn node_and_children_mut(
+        &mut self,
+        idx: usize,
+    ) -> (
+        &mut Node<K, V, U>,
+        Option<&Node<K, V, U>>,
+        Option<&Node<K, V, U>>,
+    ) {
+        let (opt_left_idx, opt_right_idx) = (self[idx].left_idx(), self[idx].right_idx());
+        let (lo_slice, hi_slice) = self.vec.split_at_mut(idx);
+        let (node_slot, hi_slice) = match hi_slice.split_first_mut() {
+            Some(split) => split,
+            None => unreachable!(),
+        };
+
+        // Children never alias their parent, so each lies strictly on one side of it
+        let (lo_slice, hi_slice) = (&*lo_slice, &*hi_slice);
+        let child = move |child_idx: usize| match child_idx < idx {
+            true => lo_slice[child_idx].as_ref(),
+            false => hi_slice[child_idx - idx - 1].as_ref(),
+        };
+
+        match node_slot.as_mut() {
+            Some(node) => (
+                node,
+                opt_left_idx.and_then(child),
+                opt_right_idx.and_then(child),
+            ),
+            None => unreachable!(),
+        }
+    }
+}
+
+impl<K, V, U, const N: usize> Arena<K, V, U, N>
+where
+    U: ArenaIdx,
+{
+    /// Map every value into a new arena with identical layout (slots, links, free list, and epoch).
+    /// Nodes are visited in `order`, which must list every occupied index exactly once.
+    pub fn map_vals<W, F: FnMut(&K, V) -> W>(
+        mut self,
+        order: &[usize],
+        mut f: F,
+    ) -> Arena<K, W, U, N> {
+        let mut vec = (0..self.vec.len())
+            .map(|_| None)
+            .collect::<StackVec<[Option<Node<K, W, U>>; N]>>();
+
+        for &idx in order {
+            vec[idx] = self.vec[idx].take().map(|node| node.map_val(&mut f));
+        }
+
+        debug_assert!(self.vec.iter().all(|slot| slot.is_none()));
+
+        Arena {
+            vec,
+
+            #[cfg(not(feature = "low_mem_insert"))]
+            free_list: self.free_list,
+
+            epoch: self.epoch,
+            _nodes: PhantomData,
+        }
+    }
 }
 
 // Convenience Traits --------------------------------------------------------------------------------------------------
 
 /// Immutable indexing.
 /// Indexed location MUST be occupied.
-impl<K: Default, V: Default, U: Default, const N: usize> Index<usize> for Arena<K, V, U, N> {
+impl<K, V, U: ArenaIdx, const N: usize, S> Index<usize> for Arena<K, V, U, N, S>
+where
+    S: SlotStorage<Option<Node<K, V, U>>>,
+{
     type Output = Node<K, V, U>;
 
     fn index(&self, index: usize) -> &Self::Output {
@@ -217,7 +361,10 @@ impl<K: Default, V: Default, U: Default, const N: usize> Index<usize> for Arena<
 
 /// Mutable indexing
 /// Indexed location MUST be occupied.
-impl<K: Default, V: Default, U: Default, const N: usize> IndexMut<usize> for Arena<K, V, U, N> {
+impl<K, V, U: ArenaIdx, const N: usize, S> IndexMut<usize> for Arena<K, V, U, N, S>
+where
+    S: SlotStorage<Option<Node<K, V, U>>>,
+{
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         match self.vec.index_mut(index) {
             Some(node) => node,
@@ -226,12 +373,10 @@ impl<K: Default, V: Default, U: Default, const N: usize> IndexMut<usize> for Are
     }
 }
 
-impl<
-        K: Ord + Default,
-        V: Default,
-        U: Default + Copy + SmallUnsigned + Ord + PartialEq + PartialOrd,
-        const N: usize,
-    > Default for Arena<K, V, U, N>
+impl<K: Ord, V, U, const N: usize, S> Default for Arena<K, V, U, N, S>
+where
+    U: ArenaIdx,
+    S: SlotStorage<Option<Node<K, V, U>>>,
 {
     fn default() -> Self {
         Self::new()
@@ -243,11 +388,11 @@ NOTE: This is draft code for upgrades when `feature(generic_const_exprs)` stabil
 
 // Wrapper Iterators ---------------------------------------------------------------------------------------------------
 
-pub struct ArenaIter<'a, K: Default, V: Default, U, const N: usize> {
+pub struct ArenaIter<'a, K, V, U, const N: usize> {
     arena_iter: core::slice::Iter<'a, Option<Node<K, V, U>>>,
 }
 
-impl<'a, K: Default, V: Default, U, const N: usize> ArenaIter<'a, K, V, U, N> {
+impl<'a, K, V, U, const N: usize> ArenaIter<'a, K, V, U, N> {
     pub fn new(arena: &'a Arena<K, V, U, N>) -> Self {
         ArenaIter {
             arena_iter: arena.vec.iter(),
@@ -255,7 +400,7 @@ impl<'a, K: Default, V: Default, U, const N: usize> ArenaIter<'a, K, V, U, N> {
     }
 }
 
-impl<'a, K: Default, V: Default, U: SmallUnsigned + Copy, const N: usize> Iterator for ArenaIter<'a, K, V, U, N> {
+impl<'a, K, V, U: ArenaIdx, const N: usize> Iterator for ArenaIter<'a, K, V, U, N> {
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -266,11 +411,11 @@ impl<'a, K: Default, V: Default, U: SmallUnsigned + Copy, const N: usize> Iterat
     }
 }
 
-pub struct ArenaIterMut<'a, K: Default, V: Default, U, const N: usize> {
+pub struct ArenaIterMut<'a, K, V, U, const N: usize> {
     arena_iter_mut: core::slice::IterMut<'a, Option<Node<K, V, U>>>,
 }
 
-impl<'a, K: Default, V: Default, U, const N: usize> ArenaIterMut<'a, K, V, U, N> {
+impl<'a, K, V, U, const N: usize> ArenaIterMut<'a, K, V, U, N> {
     pub fn new(arena: &'a mut Arena<K, V, U, N>) -> Self {
         ArenaIterMut {
             arena_iter_mut: arena.vec.iter_mut(),
@@ -278,7 +423,7 @@ impl<'a, K: Default, V: Default, U, const N: usize> ArenaIterMut<'a, K, V, U, N>
     }
 }
 
-impl<'a, K: Default, V: Default, U: SmallUnsigned + Copy, const N: usize> Iterator for ArenaIterMut<'a, K, V, U, N> {
+impl<'a, K, V, U: ArenaIdx, const N: usize> Iterator for ArenaIterMut<'a, K, V, U, N> {
     type Item = (&'a K, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -297,9 +442,9 @@ mod tests {
     use super::Arena;
     use crate::tree::node::NodeGetHelper;
     use crate::tree::node_dispatch::SmallNode;
+    use crate::tree::StackVec;
     use core::mem::size_of_val;
     use smallnum::small_unsigned;
-    use tinyvec::array_vec;
 
     const CAPACITY: usize = 1024;
 
@@ -332,7 +477,7 @@ mod tests {
         let n_1_idx = arena.add(1, "n/a");
         assert_eq!(arena[n_1_idx].val(), &"n/a");
         let n_1_mut_ref = &mut arena[n_1_idx];
-        n_1_mut_ref.set_val("This is a value. There are many like it but this one is mine.");
+        n_1_mut_ref.replace_val("This is a value. There are many like it but this one is mine.");
         assert_ne!(arena[n_1_idx].val(), &"n/a");
     }
 
@@ -387,11 +532,13 @@ mod tests {
         assert_eq!(arena.vec[2].as_ref().unwrap().key(), &1);
 
         // Would be supplied for the above tree
-        let sort_metadata = array_vec! { [NodeGetHelper<usize>; CAPACITY] =>
+        let sort_metadata: StackVec<[NodeGetHelper<usize>; CAPACITY]> = vec![
             NodeGetHelper::new(Some(2), Some(1), false),
             NodeGetHelper::new(Some(1), None, false),
             NodeGetHelper::new(Some(0), Some(1), false),
-        };
+        ]
+        .into_iter()
+        .collect();
 
         arena.sort(1, sort_metadata);
 
@@ -0,0 +1,88 @@
+use core::cmp::Ordering;
+use core::ops::{
+    Bound::{self, Excluded, Included, Unbounded},
+    RangeBounds,
+};
+
+/// A total order over `T`, selected via the last (optional) generic parameter of [`SgMap`][crate::SgMap] and
+/// [`SgSet`][crate::SgSet].
+///
+/// The comparator, not `T`'s [`Ord`] implementation, determines where elements are stored, how lookups
+/// resolve, and the order of iteration. Lookups by a borrowed form `Q` of the key require `Compare<Q>`,
+/// and the two orders *must* agree (same contract as [`Borrow`][core::borrow::Borrow]).
+///
+/// Implemented for [`Natural`] (the default), [`Reversed`], and any `Clone`-able closure or function pointer
+/// of the form `Fn(&T, &T) -> Ordering`.
+pub trait Compare<T: ?Sized>: Clone {
+    /// Compares `a` with `b`.
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+
+    /// Whether this comparator always agrees with `T`'s [`Ord`] implementation.
+    /// Enables shortcuts like `Iterator::min` returning the first item.
+    #[doc(hidden)]
+    const IS_NATURAL: bool = false;
+}
+
+/// Default comparator, uses `T`'s [`Ord`] implementation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Natural;
+
+impl<T: Ord + ?Sized> Compare<T> for Natural {
+    const IS_NATURAL: bool = true;
+
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Comparator adapter that inverts another comparator, e.g. `Reversed<Natural>` for descending order.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Reversed<C = Natural>(pub C);
+
+impl<T: ?Sized, C: Compare<T>> Compare<T> for Reversed<C> {
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self.0.compare(b, a)
+    }
+}
+
+impl<T: ?Sized, F> Compare<T> for F
+where
+    F: Fn(&T, &T) -> Ordering + Clone,
+{
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+// Crate-internal Helpers ----------------------------------------------------------------------------------------------
+
+/// Comparator-aware equivalent of `range.start_bound()` exclusion: `item` falls before the range start.
+pub(crate) fn below_start<T: ?Sized, C: Compare<T>>(cmp: &C, start: Bound<&T>, item: &T) -> bool {
+    match start {
+        Included(start) => cmp.compare(item, start) == Ordering::Less,
+        Excluded(start) => cmp.compare(item, start) != Ordering::Greater,
+        Unbounded => false,
+    }
+}
+
+/// Comparator-aware equivalent of `range.end_bound()` exclusion: `item` falls after the range end.
+pub(crate) fn above_end<T: ?Sized, C: Compare<T>>(cmp: &C, end: Bound<&T>, item: &T) -> bool {
+    match end {
+        Included(end) => cmp.compare(item, end) == Ordering::Greater,
+        Excluded(end) => cmp.compare(item, end) != Ordering::Less,
+        Unbounded => false,
+    }
+}
+
+/// Comparator-aware equivalent of [`RangeBounds::contains`].
+pub(crate) fn range_contains<T, C, R>(cmp: &C, range: &R, item: &T) -> bool
+where
+    T: ?Sized,
+    C: Compare<T>,
+    R: RangeBounds<T>,
+{
+    !below_start(cmp, range.start_bound(), item) && !above_end(cmp, range.end_bound(), item)
+}
@@ -0,0 +1,33 @@
+use core::fmt;
+
+/// Optional node annotations for [Graphviz](https://graphviz.org/) DOT export.
+///
+/// See [`SgMap::to_dot_with`][crate::SgMap::to_dot_with] and [`SgSet::to_dot_with`][crate::SgSet::to_dot_with].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DotOptions {
+    /// Label each node with its internal arena index.
+    pub arena_idxs: bool,
+
+    /// Label each node with the size of the subtree it roots.
+    pub subtree_sizes: bool,
+}
+
+// Escapes `Debug` output for use inside a double-quoted DOT label.
+pub(crate) struct DotEscape<'a, W: fmt::Write>(pub(crate) &'a mut W);
+
+impl<W: fmt::Write> fmt::Write for DotEscape<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' | '\\' => {
+                    self.0.write_char('\\')?;
+                    self.0.write_char(c)?;
+                }
+                '\n' => self.0.write_str("\\n")?,
+                _ => self.0.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use core::fmt;
+
 /// Errors for fallible operations.
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 #[non_exhaustive]
@@ -12,21 +14,17 @@ pub enum SgError {
     /// Requested operation cannot complete, heap storage is full.
     HeapCapacityExceeded,
     */
-    /// Reserved for future use
-    #[doc(hidden)]
-    Reserved3,
+    /// Requested operation cannot complete, key already present.
+    KeyAlreadyExists,
 
-    /// Reserved for future use
-    #[doc(hidden)]
-    Reserved4,
+    /// Requested operation cannot complete, key not present.
+    KeyNotFound,
 
-    /// Reserved for future use
-    #[doc(hidden)]
-    Reserved5,
+    /// Requested operation cannot complete, key would break sorted order at the given position.
+    KeyOutOfOrder,
 
-    /// Reserved for future use
-    #[doc(hidden)]
-    Reserved6,
+    /// Requested operation cannot complete, destination buffer is too small.
+    BufferTooSmall,
 
     /// Reserved for future use
     #[doc(hidden)]
@@ -34,6 +32,95 @@ pub enum SgError {
 
     /// Invalid rebalance factor requested, cannot set.
     RebalanceFactorOutOfRange,
+
+    /// Raw snapshot is malformed, incompatible with the target type, or describes an invalid tree.
+    InvalidSnapshot,
+}
+
+impl fmt::Display for SgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            SgError::MaximumCapacityExceeded => "maximum supported capacity exceeded",
+            SgError::StackCapacityExceeded => "stack storage capacity exceeded",
+            SgError::KeyAlreadyExists => "key already exists",
+            SgError::KeyNotFound => "key not found",
+            SgError::KeyOutOfOrder => "key out of order for position",
+            SgError::BufferTooSmall => "destination buffer too small",
+            SgError::Reserved7 => "reserved error",
+            SgError::RebalanceFactorOutOfRange => {
+                "rebalance factor out of range, requires 0.5 <= alpha < 1.0"
+            }
+            SgError::InvalidSnapshot => "invalid raw snapshot",
+        };
+
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "core_error")]
+impl core::error::Error for SgError {}
+
+/// Error returned when an insert fails for lack of stack storage, handing back the rejected payload.
+///
+/// Modeled after [`SendError`](https://doc.rust-lang.org/std/sync/mpsc/struct.SendError.html):
+/// ownership is never lost, so non-`Clone` payloads can be retried or routed elsewhere.
+/// For maps the payload is the rejected `(key, value)` pair, for sets it's the rejected element.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct CapacityError<T>(pub T);
+
+impl<T> CapacityError<T> {
+    /// Consumes the error, returning the rejected payload.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<CapacityError<T>> for SgError {
+    fn from(_: CapacityError<T>) -> Self {
+        SgError::StackCapacityExceeded
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&SgError::StackCapacityExceeded, f)
+    }
+}
+
+#[cfg(feature = "core_error")]
+impl<T: fmt::Debug> core::error::Error for CapacityError<T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CapacityError, SgError};
+
+    #[test]
+    fn test_err_display() {
+        assert_eq!(
+            SgError::StackCapacityExceeded.to_string(),
+            "stack storage capacity exceeded"
+        );
+        assert_eq!(
+            format!("insert failed: {}", SgError::KeyAlreadyExists),
+            "insert failed: key already exists"
+        );
+    }
+
+    #[test]
+    fn test_capacity_err() {
+        let err = CapacityError((1, "one"));
+        assert_eq!(err.to_string(), "stack storage capacity exceeded");
+        assert_eq!(SgError::from(err), SgError::StackCapacityExceeded);
+        assert_eq!(err.into_inner(), (1, "one"));
+    }
+
+    #[cfg(feature = "core_error")]
+    #[test]
+    fn test_err_source() {
+        let err: Box<dyn std::error::Error> = Box::new(SgError::KeyNotFound);
+        assert_eq!(err.to_string(), "key not found");
+        assert!(err.source().is_none());
+    }
 }
 
 /*
@@ -0,0 +1,13 @@
+/// Opaque reference to a stored key-value pair, for repeated access without re-searching the tree.
+///
+/// Returned by [`SgMap::insert_with_handle`][crate::SgMap::insert_with_handle]
+/// and [`SgMap::get_handle`][crate::SgMap::get_handle].
+/// Rebalancing doesn't move nodes, so a handle survives any number of inserts.
+/// Any removal (including clearing or draining) and any arena compaction invalidates *every* outstanding handle,
+/// after which lookups by handle return `None`.
+/// A handle is only meaningful to the collection that issued it (or a clone taken after it was issued).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    pub(crate) idx: usize,
+    pub(crate) epoch: usize,
+}
@@ -0,0 +1,155 @@
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::num::{NonZeroU16, NonZeroU32, NonZeroU8};
+use core::ops::Sub;
+
+use smallnum::SmallUnsigned;
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+}
+
+/// Unsigned integer type used for a tree's internal child links, selected via the last (optional) generic parameter
+/// of [`SgMap`][crate::SgMap] and [`SgSet`][crate::SgSet].
+///
+/// Narrower types mean smaller nodes, but a lower maximum capacity (`N <= ArenaIdx::MAX`).
+/// Two of each type's values are reserved, so absent child links and vacant arena slots take no extra space:
+///
+/// | Type | Maximum capacity |
+/// | --- | --- |
+/// | `u8` | `254` |
+/// | `u16` (default) | `65_534` |
+/// | `u32` | `4_294_967_294` (in practice, bounded by available stack) |
+///
+/// [`arena_idx!`][crate::arena_idx] selects the narrowest type for a given capacity.
+///
+/// A capacity beyond the index type's maximum fails the build, at the constructor's call site:
+///
+/// ```
+/// use scapegoat::SgMap;
+///
+/// let map = SgMap::<u8, u8, 254, u8>::new();
+/// assert_eq!(map.capacity(), 254);
+/// ```
+///
+/// ```compile_fail
+/// use scapegoat::SgMap;
+///
+/// let map = SgMap::<u8, u8, 255, u8>::new();
+/// ```
+///
+/// ```compile_fail
+/// use scapegoat::SgSet;
+///
+/// let set = SgSet::<u8, 65_535>::new();
+/// ```
+///
+/// This trait is sealed, it can't be implemented outside of this crate.
+pub trait ArenaIdx:
+    private::Sealed + SmallUnsigned + Default + Copy + Ord + Sub + Hash + Debug + Send + Sync
+{
+    /// Maximum capacity of a tree using this index type.
+    const MAX: usize;
+
+    /// Child link: `idx + 1` if present, the type's maximum value if absent.
+    /// Zero is never stored, which leaves a niche for the arena's `Option<Node>` slots.
+    #[doc(hidden)]
+    type Link: Copy + Debug + Send + Sync;
+
+    /// Encode an optional index, which must be below `MAX`, as a link.
+    #[doc(hidden)]
+    fn to_link(opt_idx: Option<usize>) -> Self::Link;
+
+    /// Decode a link back to an optional index.
+    #[doc(hidden)]
+    fn from_link(link: Self::Link) -> Option<usize>;
+}
+
+impl ArenaIdx for u8 {
+    const MAX: usize = u8::MAX as usize - 1;
+
+    type Link = NonZeroU8;
+
+    fn to_link(opt_idx: Option<usize>) -> Self::Link {
+        match NonZeroU8::new(Self::checked_from(
+            opt_idx.map_or(u8::MAX as usize, |idx| idx + 1),
+        )) {
+            Some(link) => link,
+            None => unreachable!(),
+        }
+    }
+
+    fn from_link(link: Self::Link) -> Option<usize> {
+        match link.get() {
+            u8::MAX => None,
+            val => Some(val as usize - 1),
+        }
+    }
+}
+
+impl ArenaIdx for u16 {
+    const MAX: usize = u16::MAX as usize - 1;
+
+    type Link = NonZeroU16;
+
+    fn to_link(opt_idx: Option<usize>) -> Self::Link {
+        match NonZeroU16::new(Self::checked_from(
+            opt_idx.map_or(u16::MAX as usize, |idx| idx + 1),
+        )) {
+            Some(link) => link,
+            None => unreachable!(),
+        }
+    }
+
+    fn from_link(link: Self::Link) -> Option<usize> {
+        match link.get() {
+            u16::MAX => None,
+            val => Some(val as usize - 1),
+        }
+    }
+}
+
+impl ArenaIdx for u32 {
+    const MAX: usize = u32::MAX as usize - 1;
+
+    type Link = NonZeroU32;
+
+    fn to_link(opt_idx: Option<usize>) -> Self::Link {
+        match NonZeroU32::new(Self::checked_from(
+            opt_idx.map_or(u32::MAX as usize, |idx| idx + 1),
+        )) {
+            Some(link) => link,
+            None => unreachable!(),
+        }
+    }
+
+    fn from_link(link: Self::Link) -> Option<usize> {
+        match link.get() {
+            u32::MAX => None,
+            val => Some(val as usize - 1),
+        }
+    }
+}
+
+#[doc(hidden)] // Plumbing for `arena_idx!`, API users should never have to name it.
+/// Maps capacity bounds to the narrowest sufficient [`ArenaIdx`].
+pub trait FitArenaIdx<const FITS_U8: bool, const FITS_U16: bool> {
+    /// Narrowest index type for the bounds.
+    type Idx: ArenaIdx;
+}
+
+impl FitArenaIdx<true, true> for () {
+    type Idx = u8;
+}
+
+impl FitArenaIdx<false, true> for () {
+    type Idx = u16;
+}
+
+impl FitArenaIdx<false, false> for () {
+    type Idx = u32;
+}
@@ -0,0 +1,50 @@
+/// Structural validation results, see [`SgMap::check_invariants`][crate::SgMap::check_invariants].
+///
+/// Each `bool` is `true` if the corresponding invariant holds.
+/// Intended for fuzz harnesses and integration tests, a correct tree always passes every check.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvariantReport {
+    /// Every key is greater than all keys in its left subtree and less than all keys in its right subtree.
+    pub ordered: bool,
+
+    /// No node is reachable from the root more than once, e.g. no cycles and no shared children.
+    pub acyclic: bool,
+
+    /// Every child link targets an occupied arena slot and every occupied slot is reachable from the root.
+    pub arena_consistent: bool,
+
+    /// Cached length matches the number of nodes reachable from the root.
+    pub len_correct: bool,
+
+    /// Cached minimum and maximum point at the first and last nodes in key order.
+    pub min_max_correct: bool,
+
+    /// Cached subtree sizes are correct. Always `true` unless the `fast_rebalance` feature is enabled.
+    pub subtree_sizes_correct: bool,
+
+    /// Height doesn't exceed the alpha bound, [`max_height`][InvariantReport::max_height].
+    /// Can be `false` after [`set_rebal_ratio`][crate::SgMap::set_rebal_ratio] lowers alpha, until the next rebuild.
+    pub height_bounded: bool,
+
+    /// Number of nodes reachable from the root.
+    pub node_cnt: usize,
+
+    /// Node count along the longest root-to-leaf path.
+    pub height: usize,
+
+    /// Maximum height permitted by the current alpha.
+    pub max_height: usize,
+}
+
+impl InvariantReport {
+    /// Returns `true` if every invariant holds.
+    pub fn is_valid(&self) -> bool {
+        self.ordered
+            && self.acyclic
+            && self.arena_consistent
+            && self.len_correct
+            && self.min_max_correct
+            && self.subtree_sizes_correct
+            && self.height_bounded
+    }
+}
@@ -1,74 +1,155 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
 use core::iter::FusedIterator;
+use core::ops::Bound::{self, Excluded, Included, Unbounded};
 
-use tinyvec::ArrayVec;
+use super::stack_vec::StackVec;
 
+use super::arena::Arena;
+use super::compare::{Compare, Natural};
+use super::error::SgError;
+use super::idx::ArenaIdx;
 use super::node::Node;
 use super::node_dispatch::SmallNode;
-use super::tree::{Idx, SgTree};
+use super::tree::{AugmentHook, NoAugment, SgTree};
 
 // Immutable Reference Iterator ----------------------------------------------------------------------------------------
 
 /// Uses iterative in-order tree traversal algorithm.
-/// Maintains a small stack of arena indexes (won't contain all indexes simultaneously for a balanced tree).
-pub struct Iter<'a, K: Default, V: Default, const N: usize> {
-    bst: &'a SgTree<K, V, N>,
-    idx_stack: ArrayVec<[usize; N]>,
+/// Maintains a small stack of arena indexes per direction (won't contain all indexes simultaneously for a balanced tree).
+/// Stacks hold the tree's index type, `U`, not `usize`, and the reverse stack is only populated on the first `next_back`.
+pub struct Iter<
+    'a,
+    K,
+    V,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+    A: AugmentHook<K, V> = NoAugment,
+> {
+    bst: &'a SgTree<K, V, N, U, C, A>,
+    idx_stack: StackVec<[U; N]>,
+    rev_idx_stack: StackVec<[U; N]>,
     total_cnt: usize,
     spent_cnt: usize,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iter<'a, K, V, N> {
-    pub fn new(bst: &'a SgTree<K, V, N>) -> Self {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    Iter<'a, K, V, N, U, C, A>
+{
+    pub fn new(bst: &'a SgTree<K, V, N, U, C, A>) -> Self {
         let mut ordered_iter = Iter {
             bst,
-            idx_stack: ArrayVec::<[usize; N]>::new(),
+            idx_stack: Arena::<K, V, U, N>::new_idx_vec(),
+            rev_idx_stack: Arena::<K, V, U, N>::new_idx_vec(),
             total_cnt: bst.len(),
             spent_cnt: 0,
         };
 
         if let Some(root_idx) = ordered_iter.bst.opt_root_idx {
-            let mut curr_idx = root_idx;
-            loop {
-                let node = &ordered_iter.bst.arena[curr_idx];
-                match node.left_idx() {
-                    Some(lt_idx) => {
-                        ordered_iter.idx_stack.push(curr_idx);
-                        curr_idx = lt_idx;
-                    }
-                    None => {
-                        ordered_iter.idx_stack.push(curr_idx);
-                        break;
-                    }
-                }
-            }
+            ordered_iter.push_left_spine(root_idx);
         }
 
         ordered_iter
     }
+
+    // Push `idx` and all of its left descendants, minimum ends up on top
+    fn push_left_spine(&mut self, idx: usize) {
+        let mut opt_idx = Some(idx);
+        while let Some(curr_idx) = opt_idx {
+            self.idx_stack.push(U::checked_from(curr_idx));
+            opt_idx = self.bst.arena[curr_idx].left_idx();
+        }
+    }
+
+    // Push `idx` and all of its right descendants, maximum ends up on top
+    fn push_right_spine(&mut self, idx: usize) {
+        let mut opt_idx = Some(idx);
+        while let Some(curr_idx) = opt_idx {
+            self.rev_idx_stack.push(U::checked_from(curr_idx));
+            opt_idx = self.bst.arena[curr_idx].right_idx();
+        }
+    }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Iter<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Iterator
+    for Iter<'a, K, V, N, U, C, A>
+{
     type Item = (&'a K, &'a V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.idx_stack.pop() {
+        // Front and back traversals meet in the middle
+        if self.len() == 0 {
+            return None;
+        }
+
+        match self.idx_stack.pop().map(|idx| idx.usize()) {
             Some(pop_idx) => {
                 let node = &self.bst.arena[pop_idx];
                 if let Some(gt_idx) = node.right_idx() {
-                    let mut curr_idx = gt_idx;
-                    loop {
-                        let node = &self.bst.arena[curr_idx];
-                        match node.left_idx() {
-                            Some(lt_idx) => {
-                                self.idx_stack.push(curr_idx);
-                                curr_idx = lt_idx;
-                            }
-                            None => {
-                                self.idx_stack.push(curr_idx);
-                                break;
-                            }
-                        }
-                    }
+                    self.push_left_spine(gt_idx);
+                }
+
+                let node = &self.bst.arena[pop_idx];
+                self.spent_cnt += 1;
+                Some((node.key(), node.val()))
+            }
+            None => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
+    // Maximum is always the final item of in-order traversal, so take it from the back instead of traversing.
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    // Keys are unique, so under the natural order the next item is the minimum.
+    fn min(mut self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        match C::IS_NATURAL {
+            true => self.next(),
+            false => self.min_by(Ord::cmp),
+        }
+    }
+
+    // Keys are unique, so under the natural order the last item is the maximum.
+    fn max(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        match C::IS_NATURAL {
+            true => self.last(),
+            false => self.max_by(Ord::cmp),
+        }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    DoubleEndedIterator for Iter<'a, K, V, N, U, C, A>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len() == 0 {
+            return None;
+        }
+
+        // Lazy init: once populated, the stack only empties when no items remain
+        if self.rev_idx_stack.is_empty() {
+            if let Some(root_idx) = self.bst.opt_root_idx {
+                self.push_right_spine(root_idx);
+            }
+        }
+
+        match self.rev_idx_stack.pop().map(|idx| idx.usize()) {
+            Some(pop_idx) => {
+                let node = &self.bst.arena[pop_idx];
+                if let Some(lt_idx) = node.left_idx() {
+                    self.push_right_spine(lt_idx);
                 }
 
                 let node = &self.bst.arena[pop_idx];
@@ -80,31 +161,505 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for Iter<'a, K,
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for Iter<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    ExactSizeIterator for Iter<'a, K, V, N, U, C, A>
+{
     fn len(&self) -> usize {
         debug_assert!(self.spent_cnt <= self.total_cnt);
         self.total_cnt - self.spent_cnt
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for Iter<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> FusedIterator
+    for Iter<'a, K, V, N, U, C, A>
+{
+}
+
+// Level-order Reference Iterator --------------------------------------------------------------------------------------
+
+/// Uses iterative breadth-first tree traversal algorithm.
+/// Every node is enqueued exactly once, so the queue is a fixed array with a read cursor (no wrap around needed).
+/// Levels are contiguous in the queue, so depth is tracked per level instead of stored per entry.
+pub struct LevelIter<
+    'a,
+    K,
+    V,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+    A: AugmentHook<K, V> = NoAugment,
+> {
+    bst: &'a SgTree<K, V, N, U, C, A>,
+    idx_queue: StackVec<[U; N]>,
+    queue_head: usize,
+    level_end: usize,
+    depth: usize,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    LevelIter<'a, K, V, N, U, C, A>
+{
+    pub fn new(bst: &'a SgTree<K, V, N, U, C, A>) -> Self {
+        let mut level_iter = LevelIter {
+            bst,
+            idx_queue: StackVec::<[U; N]>::new(),
+            queue_head: 0,
+            level_end: 1,
+            depth: 0,
+        };
+
+        if let Some(root_idx) = bst.opt_root_idx {
+            level_iter.idx_queue.push(U::checked_from(root_idx));
+        }
+
+        level_iter
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Iterator
+    for LevelIter<'a, K, V, N, U, C, A>
+{
+    type Item = (usize, &'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.idx_queue.get(self.queue_head)?.usize();
+
+        // Previous level fully dequeued, everything enqueued since is the next one
+        if self.queue_head == self.level_end {
+            self.depth += 1;
+            self.level_end = self.idx_queue.len();
+        }
+        self.queue_head += 1;
+
+        let node = &self.bst.arena[idx];
+        if let Some(left_idx) = node.left_idx() {
+            self.idx_queue.push(U::checked_from(left_idx));
+        }
+        if let Some(right_idx) = node.right_idx() {
+            self.idx_queue.push(U::checked_from(right_idx));
+        }
+
+        Some((self.depth, node.key(), node.val()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    ExactSizeIterator for LevelIter<'a, K, V, N, U, C, A>
+{
+    fn len(&self) -> usize {
+        self.bst.len() - self.queue_head
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> FusedIterator
+    for LevelIter<'a, K, V, N, U, C, A>
+{
+}
+
+// Cursor --------------------------------------------------------------------------------------------------------------
+
+/// Points into a gap between two adjacent elements (or before the first/after the last).
+/// Has no traversal state: each move is a bounded search from the root, so the cursor is `O(1)` space.
+pub struct Cursor<
+    'a,
+    K,
+    V,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+    A: AugmentHook<K, V> = NoAugment,
+> {
+    bst: &'a SgTree<K, V, N, U, C, A>,
+    opt_prev_idx: Option<usize>,
+    opt_next_idx: Option<usize>,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    Cursor<'a, K, V, N, U, C, A>
+{
+    // Cursor before the first element above `bound`.
+    pub fn lower_bound<Q>(bst: &'a SgTree<K, V, N, U, C, A>, bound: Bound<&Q>) -> Self
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let opt_prev_idx = match bound {
+            Included(b) => bst.upper_bound_idx(Excluded(b)),
+            Excluded(b) => bst.upper_bound_idx(Included(b)),
+            Unbounded => None,
+        };
+
+        Cursor {
+            bst,
+            opt_prev_idx,
+            opt_next_idx: bst.lower_bound_idx(bound),
+        }
+    }
+
+    // Cursor after the last element below `bound`.
+    pub fn upper_bound<Q>(bst: &'a SgTree<K, V, N, U, C, A>, bound: Bound<&Q>) -> Self
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let opt_next_idx = match bound {
+            Included(b) => bst.lower_bound_idx(Excluded(b)),
+            Excluded(b) => bst.lower_bound_idx(Included(b)),
+            Unbounded => None,
+        };
+
+        Cursor {
+            bst,
+            opt_prev_idx: bst.upper_bound_idx(bound),
+            opt_next_idx,
+        }
+    }
+
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        let node = &self.bst.arena[self.opt_next_idx?];
+        Some((node.key(), node.val()))
+    }
+
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        let node = &self.bst.arena[self.opt_prev_idx?];
+        Some((node.key(), node.val()))
+    }
+
+    // Advance over the next element, returning it. No-op at the end.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let next_idx = self.opt_next_idx?;
+        let node = &self.bst.arena[next_idx];
+        self.opt_prev_idx = Some(next_idx);
+        self.opt_next_idx = self.bst.lower_bound_idx(Excluded(node.key()));
+        Some((node.key(), node.val()))
+    }
+
+    // Retreat over the previous element, returning it. No-op at the start.
+    pub fn prev(&mut self) -> Option<(&'a K, &'a V)> {
+        let prev_idx = self.opt_prev_idx?;
+        let node = &self.bst.arena[prev_idx];
+        self.opt_next_idx = Some(prev_idx);
+        self.opt_prev_idx = self.bst.upper_bound_idx(Excluded(node.key()));
+        Some((node.key(), node.val()))
+    }
+}
+
+impl<'a, K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Clone
+    for Cursor<'a, K, V, N, U, C, A>
+{
+    fn clone(&self) -> Self {
+        Cursor {
+            bst: self.bst,
+            opt_prev_idx: self.opt_prev_idx,
+            opt_next_idx: self.opt_next_idx,
+        }
+    }
+}
+
+// Mutable Cursor ------------------------------------------------------------------------------------------------------
+
+/// Points at an element, or at the "ghost" non-element between the last and first elements.
+/// Holds arena indexes only, every move is a bounded search from the root.
+pub struct CursorMut<
+    'a,
+    K,
+    V,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+    A: AugmentHook<K, V> = NoAugment,
+> {
+    bst: &'a mut SgTree<K, V, N, U, C, A>,
+    opt_curr_idx: Option<usize>,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    CursorMut<'a, K, V, N, U, C, A>
+{
+    // Cursor at the first element above `bound`, or the ghost if none.
+    pub fn lower_bound<Q>(bst: &'a mut SgTree<K, V, N, U, C, A>, bound: Bound<&Q>) -> Self
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let opt_curr_idx = bst.lower_bound_idx(bound);
+        CursorMut { bst, opt_curr_idx }
+    }
+
+    // Cursor at the last element below `bound`, or the ghost if none.
+    pub fn upper_bound<Q>(bst: &'a mut SgTree<K, V, N, U, C, A>, bound: Bound<&Q>) -> Self
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let opt_curr_idx = bst.upper_bound_idx(bound);
+        CursorMut { bst, opt_curr_idx }
+    }
+
+    pub fn current(&mut self) -> Option<(&K, &mut V)> {
+        let curr_idx = self.opt_curr_idx?;
+        Some(self.bst.arena[curr_idx].get_mut())
+    }
+
+    pub fn peek_next(&self) -> Option<(&K, &V)> {
+        let node = &self.bst.arena[self.next_idx()?];
+        Some((node.key(), node.val()))
+    }
+
+    pub fn peek_prev(&self) -> Option<(&K, &V)> {
+        let node = &self.bst.arena[self.prev_idx()?];
+        Some((node.key(), node.val()))
+    }
+
+    // Move to the next element, from the last element to the ghost, and from the ghost to the first element.
+    pub fn move_next(&mut self) {
+        self.opt_curr_idx = self.next_idx();
+    }
+
+    // Move to the previous element, from the first element to the ghost, and from the ghost to the last element.
+    pub fn move_prev(&mut self) {
+        self.opt_curr_idx = self.prev_idx();
+    }
+
+    // Remove the current element and move to the next one. No-op at the ghost.
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        let curr_idx = self.opt_curr_idx?;
+        let (key, val) = self.bst.priv_remove_by_idx(curr_idx)?;
+        self.bst.rebuild_if_sparse();
+        self.opt_curr_idx = self.bst.lower_bound_idx(Excluded(&key));
+        Some((key, val))
+    }
+
+    // Insert between the previous and current elements, cursor doesn't move.
+    pub fn insert_before(&mut self, key: K, val: V) -> Result<(), SgError> {
+        self.insert_between(self.prev_idx(), self.opt_curr_idx, key, val)
+    }
+
+    // Insert between the current and next elements, cursor doesn't move.
+    pub fn insert_after(&mut self, key: K, val: V) -> Result<(), SgError> {
+        self.insert_between(self.opt_curr_idx, self.next_idx(), key, val)
+    }
+
+    // Insert, if `key` falls strictly between the two (optional) neighbors.
+    // Arena indexes survive insertion and rebalancing, so the cursor stays valid.
+    fn insert_between(
+        &mut self,
+        opt_lo_idx: Option<usize>,
+        opt_hi_idx: Option<usize>,
+        key: K,
+        val: V,
+    ) -> Result<(), SgError> {
+        let cmp_neighbor = |opt_idx: Option<usize>| {
+            opt_idx.map(|idx| self.bst.cmp.compare(&key, self.bst.arena[idx].key()))
+        };
+
+        match (cmp_neighbor(opt_lo_idx), cmp_neighbor(opt_hi_idx)) {
+            (Some(Ordering::Equal), _) | (_, Some(Ordering::Equal)) => {
+                Err(SgError::KeyAlreadyExists)
+            }
+            (Some(Ordering::Less), _) | (_, Some(Ordering::Greater)) => Err(SgError::KeyOutOfOrder),
+            _ => self.bst.try_insert(key, val).map(|_| ()),
+        }
+    }
+
+    fn next_idx(&self) -> Option<usize> {
+        match self.opt_curr_idx {
+            Some(curr_idx) => self
+                .bst
+                .lower_bound_idx(Excluded(self.bst.arena[curr_idx].key())),
+            None => self.bst.lower_bound_idx::<K>(Unbounded),
+        }
+    }
+
+    fn prev_idx(&self) -> Option<usize> {
+        match self.opt_curr_idx {
+            Some(curr_idx) => self
+                .bst
+                .upper_bound_idx(Excluded(self.bst.arena[curr_idx].key())),
+            None => self.bst.upper_bound_idx::<K>(Unbounded),
+        }
+    }
+}
+
+// Draining Iterator ---------------------------------------------------------------------------------------------------
+
+/// Takes every node, in order, out of a tree that was emptied up-front (so a leaked `Drain` can't expose stale links).
+/// Nodes are removed from the arena individually, their slots are released for reuse rather than re-initialized.
+pub struct Drain<
+    'a,
+    K: Ord,
+    V,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+    A: AugmentHook<K, V> = NoAugment,
+> {
+    bst: &'a mut SgTree<K, V, N, U, C, A>,
+    sorted_idxs: StackVec<[usize; N]>,
+    front_pos: usize,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    Drain<'a, K, V, N, U, C, A>
+{
+    pub fn new(bst: &'a mut SgTree<K, V, N, U, C, A>) -> Self {
+        let sorted_idxs = bst.priv_detach_all();
+        Drain {
+            bst,
+            sorted_idxs,
+            front_pos: 0,
+        }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Iterator
+    for Drain<'a, K, V, N, U, C, A>
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.sorted_idxs.get(self.front_pos).copied()?;
+        self.front_pos += 1;
+        Some(self.bst.arena.hard_remove(idx).into_kv())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    DoubleEndedIterator for Drain<'a, K, V, N, U, C, A>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.len() {
+            0 => None,
+            _ => self
+                .sorted_idxs
+                .pop()
+                .map(|idx| self.bst.arena.hard_remove(idx).into_kv()),
+        }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    ExactSizeIterator for Drain<'a, K, V, N, U, C, A>
+{
+    fn len(&self) -> usize {
+        self.sorted_idxs.len() - self.front_pos
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> FusedIterator
+    for Drain<'a, K, V, N, U, C, A>
+{
+}
+
+// Pairs not yet yielded are dropped with the iterator
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Drop
+    for Drain<'a, K, V, N, U, C, A>
+{
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+// Extracting Iterator -------------------------------------------------------------------------------------------------
+
+/// Removes matching pairs in a single in-order pass, predicate supplied per call (so wrappers can adapt it).
+/// Relies on arena slots being stable across removal: after an up-front sort, arena index order is key order.
+pub struct ExtractIf<
+    'a,
+    K: Ord,
+    V,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+    A: AugmentHook<K, V> = NoAugment,
+> {
+    bst: &'a mut SgTree<K, V, N, U, C, A>,
+    next_idx: usize,
+    end_idx: usize,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    ExtractIf<'a, K, V, N, U, C, A>
+{
+    pub fn new(bst: &'a mut SgTree<K, V, N, U, C, A>) -> Self {
+        bst.sort_arena();
+        let end_idx = bst.len();
+        ExtractIf {
+            bst,
+            next_idx: 0,
+            end_idx,
+        }
+    }
+
+    // Remove and return the next pair matching `pred`, if any.
+    pub fn next_with<F>(&mut self, pred: &mut F) -> Option<(K, V)>
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        while self.next_idx < self.end_idx {
+            let idx = self.next_idx;
+            self.next_idx += 1;
+
+            debug_assert!(self.bst.arena.is_occupied(idx));
+            let (k, v) = self.bst.arena[idx].get_mut();
+            if pred(k, v) {
+                return self.bst.priv_remove_by_idx(idx);
+            }
+        }
+
+        None
+    }
+
+    // Upper bound on pairs left to yield.
+    pub fn remaining(&self) -> usize {
+        self.end_idx - self.next_idx
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Drop
+    for ExtractIf<'a, K, V, N, U, C, A>
+{
+    fn drop(&mut self) {
+        self.bst.rebuild_if_sparse();
+    }
+}
 
 // Mutable Reference Iterator ------------------------------------------------------------------------------------------
 
-pub struct IterMut<'a, K, V, const N: usize> {
-    arena_iter_mut: core::slice::IterMut<'a, Option<Node<K, V, Idx>>>,
+pub struct IterMut<'a, K, V, const N: usize, U: ArenaIdx = u16> {
+    arena_iter_mut: core::slice::IterMut<'a, Option<Node<K, V, U>>>,
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> IterMut<'a, K, V, N> {
-    pub fn new(bst: &'a mut SgTree<K, V, N>) -> Self {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> IterMut<'a, K, V, N, U> {
+    pub fn new<C: Compare<K>, A: AugmentHook<K, V>>(bst: &'a mut SgTree<K, V, N, U, C, A>) -> Self {
         bst.sort_arena();
         IterMut {
             arena_iter_mut: bst.arena.iter_mut(),
         }
     }
+
+    // Iterate a contiguous run of in-order (by key) arena slots.
+    pub(crate) fn from_slice(sorted: &'a mut [Option<Node<K, V, U>>]) -> Self {
+        IterMut {
+            arena_iter_mut: sorted.iter_mut(),
+        }
+    }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for IterMut<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> Iterator for IterMut<'a, K, V, N, U> {
     type Item = (&'a K, &'a mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -113,11 +668,13 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> Iterator for IterMut<'a,
             _ => None,
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> DoubleEndedIterator
-    for IterMut<'a, K, V, N>
-{
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> DoubleEndedIterator for IterMut<'a, K, V, N, U> {
     fn next_back(&mut self) -> Option<Self::Item> {
         match self.arena_iter_mut.next_back() {
             Some(Some(node)) => Some(node.get_mut()),
@@ -126,60 +683,129 @@ impl<'a, K: Ord + Default, V: Default, const N: usize> DoubleEndedIterator
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for IterMut<'a, K, V, N> {
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> ExactSizeIterator for IterMut<'a, K, V, N, U> {
     fn len(&self) -> usize {
         self.arena_iter_mut.len()
     }
 }
 
-impl<'a, K: Ord + Default, V: Default, const N: usize> FusedIterator for IterMut<'a, K, V, N> {}
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx> FusedIterator for IterMut<'a, K, V, N, U> {}
 
 // Consuming Iterator --------------------------------------------------------------------------------------------------
 
 /// Cheats a little by using internal flattening logic to sort, instead of re-implementing proper traversal.
-/// Maintains a shrinking list of arena indexes, initialized with all of them.
-pub struct IntoIter<K: Default, V: Default, const N: usize> {
-    bst: SgTree<K, V, N>,
-    sorted_idxs: ArrayVec<[usize; N]>,
+/// Consumes a sorted list of arena indexes, initialized with all of them, from both ends.
+pub struct IntoIter<
+    K,
+    V,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+    A: AugmentHook<K, V> = NoAugment,
+> {
+    bst: SgTree<K, V, N, U, C, A>,
+    sorted_idxs: StackVec<[usize; N]>,
+    front_pos: usize,
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> IntoIter<K, V, N> {
-    pub fn new(bst: SgTree<K, V, N>) -> Self {
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    IntoIter<K, V, N, U, C, A>
+{
+    pub fn new(bst: SgTree<K, V, N, U, C, A>) -> Self {
         let mut ordered_iter = IntoIter {
             bst,
-            sorted_idxs: ArrayVec::<[usize; N]>::new(),
+            sorted_idxs: StackVec::<[usize; N]>::new(),
+            front_pos: 0,
         };
 
         if let Some(root_idx) = ordered_iter.bst.opt_root_idx {
             ordered_iter.sorted_idxs = ordered_iter.bst.flatten_subtree_to_sorted_idxs(root_idx);
-            ordered_iter.sorted_idxs.reverse();
         }
 
         ordered_iter
     }
+
+    fn remove_by_idx(&mut self, idx: usize) -> Option<(K, V)> {
+        match self.bst.priv_remove_by_idx(idx) {
+            Some((key, val)) => Some((key, val)),
+            None => {
+                debug_assert!(false, "Use of invalid index in consuming iterator!");
+                None
+            }
+        }
+    }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> Iterator for IntoIter<K, V, N> {
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Iterator
+    for IntoIter<K, V, N, U, C, A>
+{
     type Item = (K, V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.sorted_idxs.pop() {
-            Some(idx) => match self.bst.priv_remove_by_idx(idx) {
-                Some((key, val)) => Some((key, val)),
-                None => {
-                    debug_assert!(false, "Use of invalid index in consuming iterator!");
-                    None
-                }
-            },
+        match self.sorted_idxs.get(self.front_pos).copied() {
+            Some(idx) => {
+                self.front_pos += 1;
+                self.remove_by_idx(idx)
+            }
             None => None,
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
+    // Maximum is always the final item of in-order traversal, so take it from the back instead of traversing.
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    // Keys are unique, so under the natural order the next item is the minimum.
+    fn min(mut self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        match C::IS_NATURAL {
+            true => self.next(),
+            false => self.min_by(Ord::cmp),
+        }
+    }
+
+    // Keys are unique, so under the natural order the last item is the maximum.
+    fn max(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        match C::IS_NATURAL {
+            true => self.last(),
+            false => self.max_by(Ord::cmp),
+        }
+    }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> ExactSizeIterator for IntoIter<K, V, N> {
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    DoubleEndedIterator for IntoIter<K, V, N, U, C, A>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.len() {
+            0 => None,
+            _ => match self.sorted_idxs.pop() {
+                Some(idx) => self.remove_by_idx(idx),
+                None => None,
+            },
+        }
+    }
+}
+
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> ExactSizeIterator
+    for IntoIter<K, V, N, U, C, A>
+{
     fn len(&self) -> usize {
-        self.sorted_idxs.len()
+        self.sorted_idxs.len() - self.front_pos
     }
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> FusedIterator for IntoIter<K, V, N> {}
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> FusedIterator
+    for IntoIter<K, V, N, U, C, A>
+{
+}
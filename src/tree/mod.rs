@@ -8,16 +8,49 @@ mod arena;
 #[cfg(fuzzing)]
 pub use arena::Arena;
 
+mod storage;
+#[cfg(fuzzing)]
+pub use storage::SlotStorage;
+
+mod stack_vec;
+pub use stack_vec::StackVec;
+
 pub(super) mod node;
 #[cfg(fuzzing)]
 pub use node::{Node, NodeGetHelper, NodeRebuildHelper};
 
 mod iter;
-pub use iter::{IntoIter, Iter, IterMut};
+pub use iter::{Cursor, CursorMut, Drain, ExtractIf, IntoIter, Iter, IterMut, LevelIter};
+
+mod compare;
+pub(crate) use compare::{above_end, below_start, range_contains};
+pub use compare::{Compare, Natural, Reversed};
 
 mod error;
-pub use error::SgError;
+pub use error::{CapacityError, SgError};
+
+mod idx;
+pub use idx::{ArenaIdx, FitArenaIdx};
+
+mod policy;
+pub use policy::{DupPolicy, EvictPolicy, LayoutPolicy};
+
+mod handle;
+pub use handle::NodeHandle;
+
+mod stats;
+#[cfg(feature = "rebal_stats")]
+pub use stats::RebalStats;
+#[cfg(feature = "telemetry")]
+pub use stats::Telemetry;
+pub use stats::{MemoryUsage, TreeStats};
+
+mod invariants;
+pub use invariants::InvariantReport;
+
+mod dot;
+pub use dot::DotOptions;
 
 #[allow(clippy::module_inception)]
 mod tree;
-pub use tree::{Idx, SgTree};
+pub use tree::{AugmentHook, SgTree};
@@ -1,9 +1,10 @@
 use core::ops::Sub;
 
+use super::idx::ArenaIdx;
 use super::node_dispatch::SmallNode;
 
+use super::stack_vec::StackVec;
 use smallnum::SmallUnsigned;
-use tinyvec::ArrayVec;
 
 /*
 Note:
@@ -19,33 +20,49 @@ const `N` (e.g. static capacity).
 /// Binary tree node, meta programmable for low memory footprint.
 /// Users of it's APIs only need to declare `U` type or trait bounds at construction.
 /// All APIs take/return `usize` and normalize to `U` internally.
-#[derive(Clone, Debug, Default)]
-pub struct Node<K, V, U> {
+///
+/// Child links are sentinel-encoded (see `ArenaIdx::Link`), so neither they nor the arena's `Option<Node>` slots
+/// need a discriminant.
+#[derive(Clone, Debug)]
+pub struct Node<K, V, U: ArenaIdx> {
     key: K,
     val: V,
-    left_idx: Option<U>,
-    right_idx: Option<U>,
+    left_idx: U::Link,
+    right_idx: U::Link,
 
     #[cfg(feature = "fast_rebalance")]
     subtree_size: U,
 }
 
-impl<K, V, U: SmallUnsigned> Node<K, V, U> {
+impl<K, V, U: ArenaIdx> Node<K, V, U> {
     /// Constructor.
     pub fn new(key: K, val: V) -> Self {
         Node {
             key,
             val,
-            left_idx: None,
-            right_idx: None,
+            left_idx: U::to_link(None),
+            right_idx: U::to_link(None),
 
             #[cfg(feature = "fast_rebalance")]
             subtree_size: U::checked_from(1),
         }
     }
+
+    /// Replace value with `f(&key, val)`, keeping key and links.
+    pub fn map_val<W, F: FnOnce(&K, V) -> W>(self, f: F) -> Node<K, W, U> {
+        Node {
+            val: f(&self.key, self.val),
+            key: self.key,
+            left_idx: self.left_idx,
+            right_idx: self.right_idx,
+
+            #[cfg(feature = "fast_rebalance")]
+            subtree_size: self.subtree_size,
+        }
+    }
 }
 
-impl<K: Default, V: Default, U: SmallUnsigned + Copy> SmallNode<K, V> for Node<K, V, U> {
+impl<K, V, U: ArenaIdx> SmallNode<K, V> for Node<K, V, U> {
     fn key(&self) -> &K {
         &self.key
     }
@@ -54,10 +71,6 @@ impl<K: Default, V: Default, U: SmallUnsigned + Copy> SmallNode<K, V> for Node<K
         self.key = key;
     }
 
-    fn take_key(&mut self) -> K {
-        core::mem::take(&mut self.key)
-    }
-
     fn val(&self) -> &V {
         &self.val
     }
@@ -66,34 +79,33 @@ impl<K: Default, V: Default, U: SmallUnsigned + Copy> SmallNode<K, V> for Node<K
         (&self.key, &mut self.val)
     }
 
-    fn take_val(&mut self) -> V {
-        core::mem::take(&mut self.val)
+    fn replace_val(&mut self, val: V) -> V {
+        core::mem::replace(&mut self.val, val)
     }
 
-    fn set_val(&mut self, val: V) {
-        self.val = val;
+    fn into_kv(self) -> (K, V) {
+        (self.key, self.val)
     }
 
     fn left_idx(&self) -> Option<usize> {
-        self.left_idx.map(|i| i.usize())
+        U::from_link(self.left_idx)
     }
 
     fn set_left_idx(&mut self, opt_idx: Option<usize>) {
-        match opt_idx {
-            Some(idx) => self.left_idx = Some(U::checked_from(idx)),
-            None => self.left_idx = None,
-        }
+        self.left_idx = U::to_link(opt_idx);
     }
 
     fn right_idx(&self) -> Option<usize> {
-        self.right_idx.map(|i| i.usize())
+        U::from_link(self.right_idx)
     }
 
     fn set_right_idx(&mut self, opt_idx: Option<usize>) {
-        match opt_idx {
-            Some(idx) => self.right_idx = Some(U::checked_from(idx)),
-            None => self.right_idx = None,
-        }
+        self.right_idx = U::to_link(opt_idx);
+    }
+
+    #[cfg(feature = "branchless")]
+    fn child_idx(&self, is_right: bool) -> Option<usize> {
+        U::from_link([self.left_idx, self.right_idx][is_right as usize])
     }
 
     #[cfg(feature = "fast_rebalance")]
@@ -112,7 +124,7 @@ impl<K: Default, V: Default, U: SmallUnsigned + Copy> SmallNode<K, V> for Node<K
 /// Helper for node retrieval, usage eliminates the need a store parent pointer in each node.
 /// Users of it's APIs only need to declare `U` type or trait bounds at construction.
 /// All APIs take/return `usize` and normalize to `U` internally.
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct NodeGetHelper<U> {
     node_idx: Option<U>,
     parent_idx: Option<U>,
@@ -182,14 +194,14 @@ impl<U: SmallUnsigned + Ord + Sub + Copy> NodeRebuildHelper<U> {
 #[derive(Debug, Default)]
 pub struct NodeSwapHistHelper<U: Default, const N: usize> {
     /// Map `original_idx` -> `current_idx`
-    history: ArrayVec<[(U, U); N]>,
+    history: StackVec<[(U, U); N]>,
 }
 
 impl<U: Ord + Default + Copy + SmallUnsigned, const N: usize> NodeSwapHistHelper<U, N> {
     /// Constructor.
     pub fn new() -> Self {
         NodeSwapHistHelper {
-            history: ArrayVec::<[(U, U); N]>::default(),
+            history: StackVec::<[(U, U); N]>::default(),
         }
     }
 
@@ -256,6 +268,7 @@ impl<U: Ord + Default + Copy + SmallUnsigned, const N: usize> NodeSwapHistHelper
 #[cfg(test)]
 mod tests {
     use super::Node;
+    use crate::tree::node_dispatch::SmallNode;
     use smallnum::small_unsigned;
     use std::mem::size_of;
 
@@ -265,14 +278,47 @@ mod tests {
         #[cfg(target_pointer_width = "64")]
         #[cfg(not(feature = "fast_rebalance"))]
         {
-            assert_eq!(size_of::<Node<u32, u32, small_unsigned!(1024)>>(), 16);
+            assert_eq!(size_of::<Node<u32, u32, small_unsigned!(1024)>>(), 12);
         }
 
         // fast_rebalance only
         #[cfg(target_pointer_width = "64")]
         #[cfg(feature = "fast_rebalance")]
         {
-            assert_eq!(size_of::<Node<u32, u32, small_unsigned!(1024)>>(), 20);
+            assert_eq!(size_of::<Node<u32, u32, small_unsigned!(1024)>>(), 16);
+        }
+
+        // Vacant arena slots are free, via the links' niche
+        assert_eq!(
+            size_of::<Option<Node<u32, u32, u8>>>(),
+            size_of::<Node<u32, u32, u8>>()
+        );
+        assert_eq!(
+            size_of::<Option<Node<u64, (), u32>>>(),
+            size_of::<Node<u64, (), u32>>()
+        );
+    }
+
+    #[test]
+    fn test_node_links() {
+        let mut node = Node::<u8, (), u8>::new(0, ());
+        assert_eq!(node.left_idx(), None);
+        assert_eq!(node.right_idx(), None);
+
+        // Lowest and highest valid indexes, next to the reserved values
+        node.set_left_idx(Some(0));
+        node.set_right_idx(Some(u8::MAX as usize - 2));
+        assert_eq!(node.left_idx(), Some(0));
+        assert_eq!(node.right_idx(), Some(u8::MAX as usize - 2));
+
+        node.set_left_idx(None);
+        assert_eq!(node.left_idx(), None);
+        assert_eq!(node.right_idx(), Some(u8::MAX as usize - 2));
+
+        #[cfg(feature = "branchless")]
+        {
+            assert_eq!(node.child_idx(false), None);
+            assert_eq!(node.child_idx(true), Some(u8::MAX as usize - 2));
         }
     }
 }
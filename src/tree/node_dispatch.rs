@@ -1,27 +1,24 @@
 // Size-optimized Node Trait -------------------------------------------------------------------------------------------
 
 /// Interface encapsulates `U`.
-pub trait SmallNode<K, V: Default> {
+pub trait SmallNode<K, V> {
     /// Get key.
     fn key(&self) -> &K;
 
     /// Set key.
     fn set_key(&mut self, key: K);
 
-    // Take key, replacing current with `K::Default()`.
-    fn take_key(&mut self) -> K;
-
     /// Get value.
     fn val(&self) -> &V;
 
     /// Get key and mutable value.
     fn get_mut(&mut self) -> (&K, &mut V);
 
-    /// Set value.
-    fn set_val(&mut self, val: V);
+    /// Replace value, returning the old one.
+    fn replace_val(&mut self, val: V) -> V;
 
-    // Take value, replacing current with `V::Default()`.
-    fn take_val(&mut self) -> V;
+    /// Consume node, returning key and value.
+    fn into_kv(self) -> (K, V);
 
     /// Get left index as `usize`.
     fn left_idx(&self) -> Option<usize>;
@@ -35,6 +32,10 @@ pub trait SmallNode<K, V: Default> {
     /// Set right index.
     fn set_right_idx(&mut self, opt_idx: Option<usize>);
 
+    /// Get right index if `is_right`, else left index, without branching on `is_right`.
+    #[cfg(feature = "branchless")]
+    fn child_idx(&self, is_right: bool) -> Option<usize>;
+
     /// Get subtree size.
     #[cfg(feature = "fast_rebalance")]
     fn subtree_size(&self) -> usize;
@@ -53,7 +54,7 @@ use smallnum::SmallUnsignedLabel;
 // Enum Dispatch -------------------------------------------------------------------------------------------------------
 
 #[derive(Clone)]
-pub enum SmallNodeDispatch<K: Default, V: Default> {
+pub enum SmallNodeDispatch<K, V> {
     NodeUSIZE(Node<K, V, usize>),
     NodeU8(Node<K, V, u8>),
 
@@ -79,7 +80,7 @@ pub enum SmallNodeDispatch<K: Default, V: Default> {
     NodeU128(Node<K, V, u128>),
 }
 
-impl<K: Default, V: Default> SmallNodeDispatch<K, V> {
+impl<K, V> SmallNodeDispatch<K, V> {
     pub const fn new(key: K, val: V, uint: SmallUnsignedLabel) -> Self {
         match uint {
             SmallUnsignedLabel::USIZE => SmallNodeDispatch::NodeUSIZE(Node::<K, V, usize>::new(key, val)),
@@ -141,7 +142,7 @@ macro_rules! dispatch {
     };
 }
 
-impl<K: Default, V: Default> SmallNode<K, V> for SmallNodeDispatch<K, V> {
+impl<K, V> SmallNode<K, V> for SmallNodeDispatch<K, V> {
     fn key(&self) -> &K {
         dispatch!(self, key)
     }
@@ -150,10 +151,6 @@ impl<K: Default, V: Default> SmallNode<K, V> for SmallNodeDispatch<K, V> {
         dispatch!(self, set_key, key);
     }
 
-    fn take_key(&mut self) -> K {
-        dispatch!(self, take_key)
-    }
-
     fn val(&self) -> &V {
         dispatch!(self, val)
     }
@@ -162,12 +159,12 @@ impl<K: Default, V: Default> SmallNode<K, V> for SmallNodeDispatch<K, V> {
         dispatch!(self, get_mut)
     }
 
-    fn set_val(&mut self, val: V) {
-        dispatch!(self, set_val, val);
+    fn replace_val(&mut self, val: V) -> V {
+        dispatch!(self, replace_val, val)
     }
 
-    fn take_val(&mut self) -> V {
-        dispatch!(self, take_val)
+    fn into_kv(self) -> (K, V) {
+        dispatch!(self, into_kv)
     }
 
     fn left_idx(&self) -> Option<usize> {
@@ -0,0 +1,42 @@
+/// How to handle duplicate keys when building a collection from an iterator.
+///
+/// See [`SgMap::from_iter_with_policy`][crate::SgMap::from_iter_with_policy]
+/// and [`SgSet::from_iter_with_policy`][crate::SgSet::from_iter_with_policy].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DupPolicy {
+    /// Keep the first occurrence, ignore later ones.
+    KeepFirst,
+
+    /// Keep the last occurrence, each overwrites the previous (behavior of `FromIterator`).
+    #[default]
+    KeepLast,
+
+    /// Fail construction with [`SgError::KeyAlreadyExists`][crate::SgError::KeyAlreadyExists].
+    Error,
+}
+
+/// Which pair to evict when inserting a new key into a full collection.
+///
+/// See [`SgMap::insert_or_evict`][crate::SgMap::insert_or_evict]
+/// and [`SgSet::insert_or_evict`][crate::SgSet::insert_or_evict].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvictPolicy {
+    /// Evict the minimum key, e.g. keep the `N` largest (or most recent, for monotonic keys).
+    First,
+
+    /// Evict the maximum key, e.g. keep the `N` smallest.
+    Last,
+}
+
+/// Slot order for [`SgMap::optimize_layout`][crate::SgMap::optimize_layout]
+/// and [`SgSet::optimize_layout`][crate::SgSet::optimize_layout].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LayoutPolicy {
+    /// Ascending key order, e.g. iteration and range scans walk memory sequentially.
+    #[default]
+    InOrder,
+
+    /// [van Emde Boas](https://en.wikipedia.org/wiki/Van_Emde_Boas_tree) order: each subtree of about `sqrt(n)` nodes
+    /// is stored contiguously, so searches touch fewer cache lines (at any cache line size).
+    VanEmdeBoas,
+}
@@ -0,0 +1,320 @@
+use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
+use core::iter::{FromIterator, FusedIterator};
+use core::mem;
+use core::ops::{Deref, DerefMut};
+
+use tinyvec::Array;
+
+/// Fixed-capacity, inline vector with a `usize` length.
+///
+/// A subset of [`tinyvec::ArrayVec`](https://docs.rs/tinyvec/latest/tinyvec/struct.ArrayVec.html)'s API,
+/// for buffers sized by a tree's capacity: `tinyvec`'s length is a `u16`, capping every buffer at `65_535` items
+/// regardless of the backing array's size. Unused slots hold `A::Item::default()`.
+pub struct StackVec<A: Array> {
+    data: A,
+    len: usize,
+}
+
+impl<A: Array> StackVec<A> {
+    /// Makes a new, empty `StackVec`.
+    pub fn new() -> Self {
+        StackVec {
+            data: A::default(),
+            len: 0,
+        }
+    }
+
+    /// Maximum number of items.
+    pub fn capacity(&self) -> usize {
+        A::CAPACITY
+    }
+
+    /// Appends an item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector is full.
+    pub fn push(&mut self, item: A::Item) {
+        match self.data.as_slice_mut().get_mut(self.len) {
+            Some(slot) => {
+                *slot = item;
+                self.len += 1;
+            }
+            None => panic!("StackVec::push> capacity overflow!"),
+        }
+    }
+
+    /// Removes and returns the last item, `None` if empty.
+    pub fn pop(&mut self) -> Option<A::Item> {
+        match self.len {
+            0 => None,
+            _ => {
+                self.len -= 1;
+                Some(mem::take(&mut self.data.as_slice_mut()[self.len]))
+            }
+        }
+    }
+
+    /// Inserts an item at position `index`, shifting all items after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len` or if the vector is full.
+    pub fn insert(&mut self, index: usize, item: A::Item) {
+        assert!(index <= self.len, "StackVec::insert> index out of bounds!");
+        self.push(item);
+        self[index..].rotate_right(1);
+    }
+
+    /// Removes and returns the item at position `index`, shifting all items after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index >= len`.
+    pub fn remove(&mut self, index: usize) -> A::Item {
+        assert!(index < self.len, "StackVec::remove> index out of bounds!");
+        self[index..].rotate_left(1);
+        match self.pop() {
+            Some(item) => item,
+            None => unreachable!(),
+        }
+    }
+
+    /// Shortens the vector to `len` items, resetting the removed slots. No-op if `len` isn't shorter.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            self.data.as_slice_mut()[len..self.len]
+                .iter_mut()
+                .for_each(|slot| *slot = A::Item::default());
+            self.len = len;
+        }
+    }
+
+    /// Removes all items.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Retains only the items specified by the predicate, preserving their order.
+    pub fn retain<F: FnMut(&A::Item) -> bool>(&mut self, mut f: F) {
+        let mut kept = 0;
+        for i in 0..self.len {
+            if f(&self.data.as_slice()[i]) {
+                self.data.as_slice_mut().swap(kept, i);
+                kept += 1;
+            }
+        }
+
+        self.truncate(kept);
+    }
+}
+
+// Convenience Traits --------------------------------------------------------------------------------------------------
+
+impl<A: Array> Default for StackVec<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Array> Clone for StackVec<A>
+where
+    A::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut data = A::default();
+        data.as_slice_mut()[..self.len].clone_from_slice(self);
+        StackVec {
+            data,
+            len: self.len,
+        }
+    }
+}
+
+impl<A: Array> Debug for StackVec<A>
+where
+    A::Item: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<A: Array> PartialEq for StackVec<A>
+where
+    A::Item: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<A: Array> Eq for StackVec<A> where A::Item: Eq {}
+
+impl<A: Array> Hash for StackVec<A>
+where
+    A::Item: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self[..].hash(state);
+    }
+}
+
+impl<A: Array> Deref for StackVec<A> {
+    type Target = [A::Item];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data.as_slice()[..self.len]
+    }
+}
+
+impl<A: Array> DerefMut for StackVec<A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data.as_slice_mut()[..self.len]
+    }
+}
+
+impl<A: Array> Extend<A::Item> for StackVec<A> {
+    fn extend<I: IntoIterator<Item = A::Item>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|item| self.push(item));
+    }
+}
+
+impl<A: Array> FromIterator<A::Item> for StackVec<A> {
+    fn from_iter<I: IntoIterator<Item = A::Item>>(iter: I) -> Self {
+        let mut sv = StackVec::new();
+        sv.extend(iter);
+        sv
+    }
+}
+
+impl<'a, A: Array> IntoIterator for &'a StackVec<A> {
+    type Item = &'a A::Item;
+    type IntoIter = core::slice::Iter<'a, A::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, A: Array> IntoIterator for &'a mut StackVec<A> {
+    type Item = &'a mut A::Item;
+    type IntoIter = core::slice::IterMut<'a, A::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<A: Array> IntoIterator for StackVec<A> {
+    type Item = A::Item;
+    type IntoIter = StackVecIntoIter<A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        StackVecIntoIter {
+            back: self.len,
+            data: self.data,
+            front: 0,
+        }
+    }
+}
+
+// Owning Iterator -----------------------------------------------------------------------------------------------------
+
+/// Owning iterator over a [`StackVec`]'s items, taking each out of its slot.
+pub struct StackVecIntoIter<A: Array> {
+    data: A,
+    front: usize,
+    back: usize,
+}
+
+impl<A: Array> Iterator for StackVecIntoIter<A> {
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.front += 1;
+            Some(mem::take(&mut self.data.as_slice_mut()[self.front - 1]))
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<A: Array> DoubleEndedIterator for StackVecIntoIter<A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(mem::take(&mut self.data.as_slice_mut()[self.back]))
+        } else {
+            None
+        }
+    }
+}
+
+impl<A: Array> ExactSizeIterator for StackVecIntoIter<A> {
+    fn len(&self) -> usize {
+        self.back - self.front
+    }
+}
+
+impl<A: Array> FusedIterator for StackVecIntoIter<A> {}
+
+// Test ----------------------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::StackVec;
+
+    #[test]
+    fn test_len_beyond_u16_max() {
+        const BIG_CAP: usize = (u16::MAX as usize) + 2;
+
+        let mut sv = Box::new(StackVec::<[u32; BIG_CAP]>::new());
+        assert_eq!(sv.capacity(), BIG_CAP);
+
+        sv.extend(0..(BIG_CAP as u32));
+        assert_eq!(sv.len(), BIG_CAP);
+        assert_eq!(sv.last(), Some(&(u16::MAX as u32 + 1)));
+
+        assert_eq!(sv.pop(), Some(u16::MAX as u32 + 1));
+        sv.retain(|i| i % 2 == 0);
+        assert_eq!(sv.len(), BIG_CAP / 2);
+        assert!(sv.iter().copied().eq((0..(BIG_CAP as u32 - 1)).step_by(2)));
+
+        sv.clear();
+        assert!(sv.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "StackVec::push> capacity overflow!")]
+    fn test_push_overflow() {
+        let mut sv = StackVec::<[u8; 2]>::new();
+        sv.extend(0..3);
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut sv: StackVec<[u8; 4]> = [1, 3].iter().copied().collect();
+        sv.insert(1, 2);
+        sv.insert(0, 0);
+        assert_eq!(sv[..], [0, 1, 2, 3]);
+
+        assert_eq!(sv.remove(1), 1);
+        assert_eq!(sv.remove(2), 3);
+        assert_eq!(sv[..], [0, 2]);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let sv: StackVec<[u8; 4]> = (1..4).collect();
+        assert!(sv.clone().into_iter().eq(1..4));
+        assert!(sv.into_iter().rev().eq((1..4).rev()));
+    }
+}
@@ -0,0 +1,135 @@
+/// Rebalance cost accounting, since creation or the last reset.
+///
+/// A bare rebalance count can't distinguish many small subtree rebuilds from a single `O(n)` rebuild of the whole tree.
+/// Node counts can.
+///
+/// Only available with the `rebal_stats` feature, the counters cost 3 `usize`s per set/map.
+#[cfg(feature = "rebal_stats")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RebalStats {
+    /// Number of rebuilds (subtree or whole tree).
+    pub rebuild_cnt: usize,
+
+    /// Total number of nodes re-linked, summed across all rebuilds.
+    pub nodes_moved: usize,
+
+    /// Number of nodes re-linked by the largest single rebuild.
+    pub max_rebuild_size: usize,
+}
+
+#[cfg(feature = "rebal_stats")]
+impl RebalStats {
+    /// Record a single rebuild of `size` nodes.
+    pub(crate) fn record(&mut self, size: usize) {
+        self.rebuild_cnt = self.rebuild_cnt.saturating_add(1);
+        self.nodes_moved = self.nodes_moved.saturating_add(size);
+        self.max_rebuild_size = self.max_rebuild_size.max(size);
+    }
+}
+
+/// Resource usage high-water marks, since creation or the last reset.
+///
+/// Intended for sizing capacity (const `N`) from field data.
+/// There's no heap-spill counter: storage is fixed-size, it never spills to the heap.
+///
+/// Only available with the `telemetry` feature, the high-water marks cost 2 `usize`s per set/map.
+#[cfg(feature = "telemetry")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Telemetry {
+    /// Maximum number of elements stored at once.
+    pub max_len: usize,
+
+    /// Maximum number of entries used in a single transient index scratch buffer (insertion path or rebuild).
+    /// Scratch buffers have capacity `N`, on the stack.
+    pub max_scratch_len: usize,
+}
+
+/// Storage breakdown, in bytes, at the time of the call.
+///
+/// Storage is a fixed-size array embedded in the container (no heap, it never spills).
+/// So [`total_bytes`](MemoryUsage::total_bytes) is constant for a given type, the other fields describe how it's used.
+/// The four components sum to the total.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemoryUsage {
+    /// Size of the whole container, e.g. `core::mem::size_of_val` of the set/map.
+    pub total_bytes: usize,
+
+    /// Size of a single node (one element plus child links), same as `NODE_SIZE`.
+    pub node_bytes: usize,
+
+    /// Bytes holding live elements, e.g. `len() * node_bytes`.
+    pub used_bytes: usize,
+
+    /// Bytes in freed slots below the highest used slot (holes left by removals).
+    /// Reused by subsequent inserts, [`compact`](crate::SgMap::compact) releases them.
+    pub free_slot_bytes: usize,
+
+    /// Bytes in slots never used (or released), e.g. remaining capacity past the highest used slot.
+    pub spare_bytes: usize,
+
+    /// Everything else: per-slot tags and padding, the free list, and tree metadata.
+    pub overhead_bytes: usize,
+}
+
+/// Point-in-time summary of a tree's shape, rebalancing history, and arena occupancy.
+///
+/// Rebalance fields are accumulated since creation, or the last reset of rebalance statistics.
+/// `nodes_moved` and `max_rebuild_size` are only tracked with the `rebal_stats` feature, they're `0` otherwise.
+/// With the `metrics` feature, [`emit_metrics`](TreeStats::emit_metrics) reports them to the
+/// [`metrics`](https://crates.io/crates/metrics) crate's installed recorder.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TreeStats {
+    /// Number of elements stored.
+    pub len: usize,
+
+    /// Node count along the longest root-to-leaf path (`0` if empty).
+    pub height: usize,
+
+    /// Number of times the tree rebalanced itself (wraps on overflow, never reset).
+    pub rebal_cnt: usize,
+
+    /// Total number of nodes re-linked, summed across all rebuilds.
+    pub nodes_moved: usize,
+
+    /// Number of nodes re-linked by the largest single rebuild.
+    pub max_rebuild_size: usize,
+
+    /// Number of arena slots in use or freed (holes left by removals), e.g. the arena's high-water mark.
+    pub arena_slots: usize,
+
+    /// Total capacity, e.g. maximum number of elements.
+    pub capacity: usize,
+}
+
+#[cfg(feature = "metrics")]
+impl TreeStats {
+    /// Record every field with the [`metrics`](https://crates.io/crates/metrics) crate, labeled `collection = name`.
+    ///
+    /// Cumulative fields (`rebal_cnt`, `nodes_moved`) are reported as counters, the rest as gauges.
+    /// All metric names are prefixed with `scapegoat_`, e.g. `scapegoat_height`.
+    pub fn emit_metrics(&self, name: &'static str) {
+        let labels = [("collection", name)];
+
+        metrics::counter!("scapegoat_rebal_cnt", &labels).absolute(self.rebal_cnt as u64);
+        metrics::counter!("scapegoat_nodes_moved", &labels).absolute(self.nodes_moved as u64);
+
+        metrics::gauge!("scapegoat_len", &labels).set(self.len as f64);
+        metrics::gauge!("scapegoat_height", &labels).set(self.height as f64);
+        metrics::gauge!("scapegoat_max_rebuild_size", &labels).set(self.max_rebuild_size as f64);
+        metrics::gauge!("scapegoat_arena_slots", &labels).set(self.arena_slots as f64);
+        metrics::gauge!("scapegoat_capacity", &labels).set(self.capacity as f64);
+    }
+}
+
+#[cfg(feature = "telemetry")]
+impl Telemetry {
+    /// Record current element count.
+    pub(crate) fn record_len(&mut self, len: usize) {
+        self.max_len = self.max_len.max(len);
+    }
+
+    /// Record scratch buffer usage.
+    pub(crate) fn record_scratch(&mut self, scratch_len: usize) {
+        self.max_scratch_len = self.max_scratch_len.max(scratch_len);
+    }
+}
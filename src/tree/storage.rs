@@ -0,0 +1,36 @@
+use core::ops::{Deref, DerefMut};
+
+use tinyvec::Array;
+
+use super::stack_vec::StackVec;
+
+/// Backing store for an arena's slots: a slice that grows and shrinks at its end, up to a fixed capacity.
+///
+/// Everything else (indexing, iteration, swaps, splits) goes through the slice, via `Deref`.
+/// Implementations must not allocate, and must report the same capacity for the lifetime of the store.
+pub trait SlotStorage<T>: Default + Deref<Target = [T]> + DerefMut {
+    /// Maximum number of slots.
+    fn capacity(&self) -> usize;
+
+    /// Append a slot past the current last slot.
+    /// Callers check `len() < capacity()` first, implementations may panic otherwise.
+    fn push(&mut self, slot: T);
+
+    /// Remove and return the last slot, `None` if there are no slots.
+    fn pop(&mut self) -> Option<T>;
+}
+
+// Inline, fixed-capacity storage: the default for every tree.
+impl<A: Array> SlotStorage<A::Item> for StackVec<A> {
+    fn capacity(&self) -> usize {
+        StackVec::capacity(self)
+    }
+
+    fn push(&mut self, slot: A::Item) {
+        StackVec::push(self, slot)
+    }
+
+    fn pop(&mut self) -> Option<A::Item> {
+        StackVec::pop(self)
+    }
+}
@@ -1,10 +1,16 @@
 use core::fmt::Debug;
 use core::iter::FromIterator;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 
+use super::iter::Iter;
 use super::node_dispatch::SmallNode;
-use super::tree::{Idx, SgTree};
-use super::SgError;
+use super::tree::SgTree;
+#[cfg(feature = "rebal_stats")]
+use super::RebalStats;
+#[cfg(feature = "telemetry")]
+use super::Telemetry;
+use super::{ArenaIdx, LayoutPolicy, SgError, StackVec, TreeStats};
 
 use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
@@ -40,7 +46,10 @@ pub fn get_test_tree_and_keys() -> (SgTree<usize, &'static str, CAPACITY>, Vec<u
 // 1. A right child node's key is always greater than it's parent's key.
 // 2. A left child node's key is always less than it's parent's key.
 // 3. Every node has at most 1 parent.
-fn assert_logical_invariants<K: Ord + Default, V: Default, const N: usize>(sgt: &SgTree<K, V, N>) {
+fn assert_logical_invariants<K: Ord, V, const N: usize, U: ArenaIdx>(sgt: &SgTree<K, V, N, U>) {
+    let report = sgt.check_invariants();
+    assert!(report.is_valid(), "Internal invariant failed: {:?}", report);
+
     if let Some(root_idx) = sgt.opt_root_idx {
         let mut child_idxs = vec![root_idx]; // Count as "child" to make sure there's no other ref to this index
         let mut subtree_worklist = vec![&sgt.arena[root_idx]];
@@ -53,7 +62,7 @@ fn assert_logical_invariants<K: Ord + Default, V: Default, const N: usize>(sgt:
                     "Internal invariant failed: left child >= parent!"
                 );
                 child_idxs.push(left_idx);
-                subtree_worklist.push(&left_child_node);
+                subtree_worklist.push(left_child_node);
             }
 
             if let Some(right_idx) = node.right_idx() {
@@ -63,7 +72,7 @@ fn assert_logical_invariants<K: Ord + Default, V: Default, const N: usize>(sgt:
                     "Internal invariant failed: right child <= parent!"
                 );
                 child_idxs.push(right_idx);
-                subtree_worklist.push(&right_child_node);
+                subtree_worklist.push(right_child_node);
             }
         }
 
@@ -78,8 +87,8 @@ fn assert_logical_invariants<K: Ord + Default, V: Default, const N: usize>(sgt:
 }
 
 // Inserts random `usize` keys, and randomly removes 20%.
-fn logical_fuzz<const N: usize>(
-    sgt: &mut SgTree<usize, &str, N>,
+fn logical_fuzz<const N: usize, U: ArenaIdx>(
+    sgt: &mut SgTree<usize, &str, N, U>,
     iter_cnt: usize,
     check_invars: bool,
 ) {
@@ -88,12 +97,11 @@ fn logical_fuzz<const N: usize>(
     let mut slow_rng = rand::thread_rng();
 
     for i in 0..iter_cnt {
-        let rand_key: usize;
-        if check_invars {
-            rand_key = slow_rng.gen();
+        let rand_key: usize = if check_invars {
+            slow_rng.gen()
         } else {
-            rand_key = fast_rng.gen();
-        }
+            fast_rng.gen()
+        };
 
         // Rand value insert
         shadow_keys.insert(rand_key);
@@ -101,7 +109,7 @@ fn logical_fuzz<const N: usize>(
 
         // Verify internal state post-insert
         if check_invars {
-            assert_logical_invariants(&sgt);
+            assert_logical_invariants(sgt);
             assert_eq!(
                 sgt.len(),
                 shadow_keys.len(),
@@ -121,7 +129,7 @@ fn logical_fuzz<const N: usize>(
 
             // Verify internal state post-remove
             if check_invars {
-                assert_logical_invariants(&sgt);
+                assert_logical_invariants(sgt);
                 assert_eq!(
                     sgt.len(),
                     shadow_keys.len(),
@@ -158,10 +166,10 @@ fn logical_fuzz<const N: usize>(
 // Identity permutation fill: (0, 0), (1, 1), (2, 2), ... , (n, n)
 // This does a bunch of dynamic checks for testing purposes.
 #[allow(dead_code)]
-fn id_perm_fill<K, V, const N: usize>(sgt: &mut SgTree<K, V, N>)
+fn id_perm_fill<K, V, const N: usize, U: ArenaIdx>(sgt: &mut SgTree<K, V, N, U>)
 where
-    K: From<usize> + Eq + Debug + Ord + Default,
-    V: From<usize> + Eq + Debug + Default,
+    K: From<usize> + Eq + Debug + Ord,
+    V: From<usize> + Eq + Debug,
 {
     sgt.clear();
     for i in 0..sgt.capacity() {
@@ -220,6 +228,8 @@ fn test_tree_packing() {
 }
 
 #[test]
+#[cfg(not(feature = "rebal_stats"))]
+#[cfg(not(feature = "telemetry"))]
 fn test_tree_sizing() {
     assert_eq!(CAPACITY, 1024);
 
@@ -228,7 +238,7 @@ fn test_tree_sizing() {
     #[cfg(not(feature = "low_mem_insert"))]
     #[cfg(not(feature = "fast_rebalance"))]
     {
-        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 18_504);
+        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 14_424);
     }
 
     // All features
@@ -236,7 +246,7 @@ fn test_tree_sizing() {
     #[cfg(feature = "low_mem_insert")]
     #[cfg(feature = "fast_rebalance")]
     {
-        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 20_552);
+        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 16_464);
     }
 
     // low_mem_insert only
@@ -244,7 +254,7 @@ fn test_tree_sizing() {
     #[cfg(feature = "low_mem_insert")]
     #[cfg(not(feature = "fast_rebalance"))]
     {
-        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 16_456);
+        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 12_368);
     }
 
     // fast_rebalance only
@@ -252,10 +262,19 @@ fn test_tree_sizing() {
     #[cfg(not(feature = "low_mem_insert"))]
     #[cfg(feature = "fast_rebalance")]
     {
-        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 22_600);
+        assert_eq!(core::mem::size_of::<SgTree<u32, u32, CAPACITY>>(), 18_520);
     }
 }
 
+#[test]
+fn test_iter_sizing() {
+    // Spine stacks hold the arena index type, both fit in the footprint of a single `usize` stack
+    assert!(
+        core::mem::size_of::<Iter<u32, u32, CAPACITY>>()
+            < core::mem::size_of::<[usize; CAPACITY]>()
+    );
+}
+
 #[test]
 fn test_ref_iter() {
     let (sgt, keys) = get_test_tree_and_keys();
@@ -288,12 +307,9 @@ fn test_iter() {
 
 #[test]
 fn test_from_iter() {
-    let mut key_val_tuples = Vec::new();
-    key_val_tuples.push((1, "1"));
-    key_val_tuples.push((2, "2"));
-    key_val_tuples.push((3, "3"));
+    let key_val_tuples = vec![(1, "1"), (2, "2"), (3, "3")];
 
-    let sgt = SgTree::<_, _, CAPACITY>::from_iter(key_val_tuples.into_iter());
+    let sgt = SgTree::<_, _, CAPACITY>::from_iter(key_val_tuples);
 
     assert!(sgt.len() == 3);
     assert_eq!(
@@ -333,6 +349,373 @@ fn test_append() {
     );
 }
 
+#[test]
+fn test_append_merge() {
+    let mut rng = SmallRng::from_entropy();
+
+    for _ in 0..50 {
+        let mut a = SgTree::<usize, usize, CAPACITY>::new();
+        let mut b = SgTree::<usize, usize, CAPACITY>::new();
+        let mut shadow_a = BTreeMap::new();
+        let mut shadow_b = BTreeMap::new();
+
+        // Overlapping key spaces, other's value wins on a match
+        for i in 0..rng.gen_range(0, CAPACITY / 2) {
+            let key = rng.gen_range(0, CAPACITY);
+            assert_eq!(a.insert(key, i), shadow_a.insert(key, i));
+        }
+        for i in 0..rng.gen_range(0, CAPACITY / 2) {
+            let key = rng.gen_range(0, CAPACITY);
+            assert_eq!(
+                b.insert(key, i + CAPACITY),
+                shadow_b.insert(key, i + CAPACITY)
+            );
+        }
+
+        // Empty on either side is a move, disjoint key ranges a splice, not a merge
+        let merged = match (a.first_key(), a.last_key(), b.first_key(), b.last_key()) {
+            (Some(a_first), Some(a_last), Some(b_first), Some(b_last)) => {
+                (a_first <= b_last) && (b_first <= a_last)
+            }
+            _ => false,
+        };
+        a.append(&mut b);
+        shadow_a.append(&mut shadow_b);
+
+        assert!(b.is_empty());
+        assert_eq!(a.len(), shadow_a.len());
+        assert!(a.iter().eq(shadow_a.iter()));
+        assert!(a.iter().rev().eq(shadow_a.iter().rev()));
+        assert_eq!(a.first_key_value(), shadow_a.first_key_value());
+        assert_eq!(a.last_key_value(), shadow_a.last_key_value());
+        assert_logical_invariants(&a);
+
+        // Single rebuild, perfectly balanced result
+        if merged {
+            assert_eq!(
+                a.height(),
+                SgTree::<usize, usize, CAPACITY>::min_height(a.len())
+            );
+        }
+
+        // Still fully functional post-merge
+        for (n, (k, v)) in shadow_a.iter().enumerate() {
+            assert_eq!(a.get_index(n), Some((k, v)));
+        }
+        for (k, v) in shadow_a.iter() {
+            assert_eq!(a.remove(k), Some(*v));
+        }
+        assert!(a.is_empty());
+    }
+}
+
+#[test]
+fn test_choose_with() {
+    const SAMPLES: usize = 64_000;
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut sgt = SgTree::<usize, usize, 64>::new();
+    assert_eq!(sgt.choose_with(|len| rng.gen_range(0, len)), None);
+    assert_eq!(sgt.choose_mut_with(|len| rng.gen_range(0, len)), None);
+
+    // Skewed insert order, so the shape isn't perfectly balanced
+    for k in 0..sgt.capacity() {
+        sgt.insert(k, 0);
+    }
+
+    for _ in 0..SAMPLES {
+        let (k, v) = sgt.choose_mut_with(|len| rng.gen_range(0, len)).unwrap();
+        assert!(*k < 64);
+        *v += 1;
+    }
+
+    // Each key expected 1_000 times, allow generous slack
+    for (_, cnt) in sgt.iter() {
+        assert!(
+            (700..1_300).contains(cnt),
+            "Non-uniform sample count: {}",
+            cnt
+        );
+    }
+    assert_eq!(sgt.iter().map(|(_, v)| *v).sum::<usize>(), SAMPLES);
+    assert!(sgt.choose_with(|len| rng.gen_range(0, len)).is_some());
+    assert_eq!(sgt.choose_with(|len| len), None);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_choose() {
+    const SAMPLES: usize = 64_000;
+
+    let mut rng = SmallRng::seed_from_u64(0);
+    let mut sgt = SgTree::<usize, usize, 64>::new();
+    assert_eq!(sgt.choose(&mut rng), None);
+    assert_eq!(sgt.choose_mut(&mut rng), None);
+
+    for k in 0..sgt.capacity() {
+        sgt.insert(k, 0);
+    }
+
+    for _ in 0..SAMPLES {
+        let (_, v) = sgt.choose_mut(&mut rng).unwrap();
+        *v += 1;
+    }
+
+    for (_, cnt) in sgt.iter() {
+        assert!(
+            (700..1_300).contains(cnt),
+            "Non-uniform sample count: {}",
+            cnt
+        );
+    }
+    assert_eq!(sgt.iter().map(|(_, v)| *v).sum::<usize>(), SAMPLES);
+
+    // Unsized `Rng` works too
+    let dyn_rng: &mut dyn rand::RngCore = &mut rng;
+    assert!(sgt.choose(dyn_rng).is_some());
+}
+
+#[test]
+fn test_partition_point() {
+    let mut rng = SmallRng::from_entropy();
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    while !sgt.is_full() {
+        let k = rng.gen_range(0, CAPACITY * 4);
+        sgt.insert(k, k);
+    }
+    let sorted: Vec<usize> = sgt.iter().map(|(k, _)| *k).collect();
+
+    for _ in 0..CAPACITY {
+        let pivot = rng.gen_range(0, CAPACITY * 5);
+        let expected = sorted.partition_point(|k| *k < pivot);
+        assert_eq!(sgt.partition_point(|k| *k < pivot), expected);
+        assert_eq!(
+            sgt.get_partition_point(|k| *k < pivot),
+            sgt.get_index(expected)
+        );
+    }
+
+    assert_eq!(sgt.partition_point(|_| true), CAPACITY);
+    assert_eq!(sgt.partition_point(|_| false), 0);
+    assert_eq!(sgt.get_partition_point(|_| false), sgt.first_key_value());
+
+    sgt.clear();
+    assert_eq!(sgt.partition_point(|_| false), 0);
+    assert_eq!(sgt.get_partition_point(|_| false), None);
+}
+
+#[test]
+fn test_remove_nth() {
+    let mut rng = SmallRng::from_entropy();
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    while !sgt.is_full() {
+        let k = rng.gen::<usize>();
+        sgt.insert(k, k);
+    }
+    let mut sorted: Vec<_> = sgt.iter().map(|(k, v)| (*k, *v)).collect();
+
+    assert_eq!(sgt.remove_nth(CAPACITY), None);
+    while !sorted.is_empty() {
+        let n = rng.gen_range(0, sorted.len());
+        assert_eq!(sgt.remove_nth(n), Some(sorted.remove(n)));
+        assert_eq!(sgt.len(), sorted.len());
+        assert_eq!(sgt.first_key_value(), sorted.first().map(|(k, v)| (k, v)));
+        assert_eq!(sgt.last_key_value(), sorted.last().map(|(k, v)| (k, v)));
+        assert_logical_invariants(&sgt);
+    }
+
+    assert!(sgt.is_empty());
+    assert_eq!(sgt.remove_nth(0), None);
+}
+
+#[test]
+fn test_truncate() {
+    let mut rng = SmallRng::from_entropy();
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    while !sgt.is_full() {
+        let k = rng.gen::<usize>();
+        sgt.insert(k, k);
+    }
+    let sorted: Vec<_> = sgt.iter().map(|(k, v)| (*k, *v)).collect();
+
+    for len in [0, 1, CAPACITY / 3, CAPACITY - 1, CAPACITY, CAPACITY + 1] {
+        let kept = len.min(CAPACITY);
+
+        let mut front = sgt.clone();
+        front.truncate(len);
+        assert_eq!(front.len(), kept);
+        assert!(front
+            .iter()
+            .map(|(k, v)| (*k, *v))
+            .eq(sorted[..kept].iter().copied()));
+        assert_logical_invariants(&front);
+
+        let mut back = sgt.clone();
+        back.truncate_back(len);
+        assert_eq!(back.len(), kept);
+        assert!(back
+            .iter()
+            .map(|(k, v)| (*k, *v))
+            .eq(sorted[(CAPACITY - kept)..].iter().copied()));
+        assert_logical_invariants(&back);
+    }
+}
+
+#[test]
+fn test_get_or_insert_with() {
+    let mut rng = SmallRng::from_entropy();
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    let mut bt_map = BTreeMap::new();
+
+    while !sgt.is_full() {
+        let k = rng.gen_range(0, CAPACITY * 2);
+        let mut called = false;
+        let (stored_k, v) = sgt.get_or_insert_with(k, || {
+            called = true;
+            k * 2
+        });
+        assert_eq!(*stored_k, k);
+        *v += 1;
+
+        assert_eq!(called, !bt_map.contains_key(&k));
+        *bt_map.entry(k).or_insert(k * 2) += 1;
+        assert_eq!(sgt.len(), bt_map.len());
+    }
+
+    assert!(sgt.iter().eq(bt_map.iter()));
+    assert_logical_invariants(&sgt);
+
+    // Full, present keys only
+    let (k, v) = bt_map.iter().next().unwrap();
+    assert_eq!(sgt.get_or_insert_with(*k, || unreachable!()).1, v);
+}
+
+#[test]
+fn test_get_batch() {
+    static CMP_CNT: AtomicUsize = AtomicUsize::new(0);
+    let counting_cmp = |a: &usize, b: &usize| {
+        CMP_CNT.fetch_add(1, AtomicOrdering::Relaxed);
+        a.cmp(b)
+    };
+
+    let mut rng = SmallRng::from_entropy();
+    let mut sgt = SgTree::<usize, usize, CAPACITY, u16, _>::with_comparator(counting_cmp);
+    let mut bt_map = BTreeMap::new();
+    for _ in 0..CAPACITY {
+        let k = rng.gen_range(0, CAPACITY * 2);
+        sgt.insert(k, k * 2);
+        bt_map.insert(k, k * 2);
+    }
+
+    // Sorted, with hits, misses and repeats
+    let mut keys: Vec<usize> = (0..CAPACITY)
+        .map(|_| rng.gen_range(0, CAPACITY * 3))
+        .collect();
+    keys.sort_unstable();
+
+    CMP_CNT.store(0, AtomicOrdering::Relaxed);
+    let batch: Vec<_> = sgt.get_batch(keys.iter()).collect();
+    let batch_cmp_cnt = CMP_CNT.load(AtomicOrdering::Relaxed);
+
+    CMP_CNT.store(0, AtomicOrdering::Relaxed);
+    let single: Vec<_> = keys.iter().map(|k| sgt.get(k)).collect();
+    let single_cmp_cnt = CMP_CNT.load(AtomicOrdering::Relaxed);
+
+    assert_eq!(batch, single);
+    assert!(batch.iter().zip(&keys).all(|(v, k)| *v == bt_map.get(k)));
+    assert!(batch_cmp_cnt < single_cmp_cnt);
+
+    // Unsorted, and descending runs
+    keys.reverse();
+    keys.extend((0..CAPACITY).map(|_| rng.gen_range(0, CAPACITY * 3)));
+    assert!(sgt
+        .get_batch(keys.iter())
+        .zip(&keys)
+        .all(|(v, k)| v == bt_map.get(k)));
+
+    // Empty
+    assert_eq!(sgt.get_batch(core::iter::empty()).count(), 0);
+    sgt.clear();
+    assert!(sgt.get_batch(keys.iter()).all(|v| v.is_none()));
+}
+
+#[test]
+fn test_append_splice() {
+    static CMP_CNT: AtomicUsize = AtomicUsize::new(0);
+    let counting_cmp = |a: &usize, b: &usize| {
+        CMP_CNT.fetch_add(1, AtomicOrdering::Relaxed);
+        a.cmp(b)
+    };
+
+    let mut rng = SmallRng::from_entropy();
+    for _ in 0..50 {
+        let split = rng.gen_range(0, CAPACITY);
+        let mut lo = SgTree::<usize, usize, CAPACITY, u16, _>::with_comparator(counting_cmp);
+        let mut hi = SgTree::<usize, usize, CAPACITY, u16, _>::with_comparator(counting_cmp);
+        let mut shadow = BTreeMap::new();
+
+        // Random fill, and random removals so neither arena is dense
+        for _ in 0..rng.gen_range(1, CAPACITY / 2) {
+            let (lo_key, hi_key) = (
+                rng.gen_range(0, split + 1),
+                rng.gen_range(split + 1, CAPACITY * 2),
+            );
+            lo.insert(lo_key, lo_key);
+            hi.insert(hi_key, hi_key);
+            shadow.insert(lo_key, lo_key);
+            shadow.insert(hi_key, hi_key);
+        }
+        for _ in 0..rng.gen_range(0, lo.len()) {
+            let key = rng.gen_range(0, split + 1);
+            assert_eq!(lo.remove(&key), shadow.remove(&key));
+        }
+
+        // Either order, without a key-by-key merge
+        let (mut dst, mut src) = match rng.gen::<bool>() {
+            true => (lo, hi),
+            false => (hi, lo),
+        };
+        let handle = dst.get_handle(dst.first_key().unwrap()).unwrap();
+        CMP_CNT.store(0, AtomicOrdering::Relaxed);
+        dst.append(&mut src);
+        assert!(CMP_CNT.load(AtomicOrdering::Relaxed) <= 64);
+
+        assert!(src.is_empty());
+        assert!(dst.iter().eq(shadow.iter()));
+        assert_eq!(dst.first_key_value(), shadow.first_key_value());
+        assert_eq!(dst.last_key_value(), shadow.last_key_value());
+        assert!(dst.get_by_handle(handle).is_some());
+        let report = dst.check_invariants();
+        assert!(report.is_valid(), "{:?}", report);
+        for (n, (k, v)) in shadow.iter().enumerate() {
+            assert_eq!(dst.get_index(n), Some((k, v)));
+        }
+    }
+}
+
+#[test]
+fn test_try_append_capacity() {
+    let mut a = SgTree::<usize, usize, 4>::from([(1, 1), (2, 2), (3, 3), (4, 4)]);
+    let mut b = SgTree::<usize, usize, 4>::from([(3, 30), (4, 40), (5, 50), (6, 60)]);
+
+    // Unchanged on failure
+    assert_eq!(a.try_append(&mut b), Err(SgError::StackCapacityExceeded));
+    assert!(a
+        .iter()
+        .eq([(1, 1), (2, 2), (3, 3), (4, 4)].iter().map(|(k, v)| (k, v))));
+    assert_eq!(b.len(), 4);
+
+    // Full overlap fits
+    assert_eq!(b.pop_last(), Some((6, 60)));
+    assert_eq!(b.pop_last(), Some((5, 50)));
+    assert_eq!(a.try_append(&mut b), Ok(()));
+    assert!(b.is_empty());
+    assert!(a.iter().eq([(1, 1), (2, 2), (3, 30), (4, 40)]
+        .iter()
+        .map(|(k, v)| (k, v))));
+    assert_logical_invariants(&a);
+}
+
 #[test]
 fn test_flatten() {
     let keys = vec![2, 1, 3];
@@ -345,14 +728,55 @@ fn test_flatten() {
     let root_idx = sgt.opt_root_idx.unwrap();
     let sorted_idxs = sgt.flatten_subtree_to_sorted_idxs::<u16>(root_idx);
 
-    assert_eq!(sorted_idxs, array_vec![[u16; CAPACITY] => 1, 0, 2]);
+    assert_eq!(sorted_idxs[..], [1, 0, 2]);
 
     sgt.remove(&2);
 
     let root_idx = sgt.opt_root_idx.unwrap();
     let sorted_idxs = sgt.flatten_subtree_to_sorted_idxs::<u16>(root_idx);
 
-    assert_eq!(sorted_idxs, array_vec![[u16; CAPACITY] => 1, 2]);
+    assert_eq!(sorted_idxs[..], [1, 2]);
+}
+
+#[test]
+fn test_flatten_in_place() {
+    let mut sgt = SgTree::<_, _, CAPACITY>::new();
+    sgt.set_rebal_ratio(9, 10).unwrap();
+
+    // Skewed shape, so the in-place traversal has to thread through long left spines
+    for k in (0..64).rev().chain(64..128) {
+        sgt.insert(k, k);
+    }
+
+    let root_idx = sgt.opt_root_idx.unwrap();
+    let expected = sgt.flatten_subtree_to_sorted_idxs::<u16>(root_idx);
+    let shape_before: Vec<_> = sgt.iter_levels().map(|(d, k, _)| (d, *k)).collect();
+
+    let mut sorted_idxs = StackVec::<[u16; CAPACITY]>::new();
+    sgt.flatten_subtree_in_place(root_idx, &mut sorted_idxs);
+
+    // Same order, and all temporary threads removed
+    assert_eq!(sorted_idxs, expected);
+    assert!(sgt.iter_levels().map(|(d, k, _)| (d, *k)).eq(shape_before));
+    assert_logical_invariants(&sgt);
+}
+
+#[test]
+fn test_full_rebuild_height() {
+    for len in 1..=130_usize {
+        let mut sgt = SgTree::<_, _, CAPACITY>::new();
+        sgt.set_rebal_ratio(99, 100).unwrap();
+        for k in 0..len {
+            sgt.insert(k, k);
+        }
+
+        // Sorted inserts under lax alpha leave a skewed tree, a full rebuild must make it minimal-height
+        sgt.set_rebal_ratio_and_rebalance(1, 2).unwrap();
+        let min_height = (usize::BITS - len.leading_zeros()) as usize;
+        assert_eq!(sgt.height(), min_height);
+        assert!(sgt.iter().map(|(k, _)| *k).eq(0..len));
+        assert_logical_invariants(&sgt);
+    }
 }
 
 #[test]
@@ -568,6 +992,395 @@ fn test_retain() {
     assert!(sg_map.iter().eq(bt_map.iter()));
 }
 
+#[test]
+fn test_retain_single_rebuild() {
+    let mut rng = SmallRng::from_entropy();
+
+    for _ in 0..10 {
+        let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+        let mut bt_map = BTreeMap::new();
+
+        while sgt.len() < (CAPACITY / 2) {
+            let k = rng.gen_range(0, CAPACITY * 4);
+            sgt.insert(k, k);
+            bt_map.insert(k, k);
+        }
+
+        // Visited in key order, values mutable
+        let mut visited = Vec::new();
+        let divisor = rng.gen_range(1, 8);
+        let rebuilds = sgt.rebal_cnt();
+        sgt.retain(|&k, v| {
+            visited.push(k);
+            *v += 1;
+            k % divisor == 0
+        });
+        bt_map.retain(|&k, v| {
+            *v += 1;
+            k % divisor == 0
+        });
+
+        assert!(visited
+            .iter()
+            .eq(visited.iter().copied().collect::<BTreeSet<_>>().iter()));
+        assert!(sgt.rebal_cnt() - rebuilds <= 1);
+        assert_logical_invariants(&sgt);
+        assert!(sgt.iter().eq(bt_map.iter()));
+        assert_eq!(sgt.first_key_value(), bt_map.iter().next());
+        assert_eq!(sgt.last_key_value(), bt_map.iter().next_back());
+
+        // Still usable after rebuild
+        sgt.insert(1, 1);
+        bt_map.insert(1, 1);
+        assert!(sgt.iter().eq(bt_map.iter()));
+    }
+
+    let mut sgt = SgTree::from([(1, 1), (2, 2), (3, 3)]);
+    sgt.retain(|_, _| true);
+    assert_eq!(sgt.len(), 3);
+    sgt.retain(|&k, _| k == 2);
+    assert!(sgt.iter().eq([(&2, &2)]));
+    sgt.retain(|_, _| false);
+    assert!(sgt.is_empty());
+}
+
+#[test]
+fn test_compact_and_shrink_to_fit() {
+    let mut rng = SmallRng::from_entropy();
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    let mut bt_map = BTreeMap::new();
+
+    for _ in 0..CAPACITY {
+        let k = rng.gen_range(0, CAPACITY * 4);
+        sgt.insert(k, k);
+        bt_map.insert(k, k);
+    }
+    for _ in 0..(CAPACITY / 2) {
+        let k = rng.gen_range(0, CAPACITY * 4);
+        assert_eq!(sgt.remove(&k), bt_map.remove(&k));
+    }
+
+    // Trailing slots released, nothing moved
+    let layout: Vec<_> = sgt
+        .arena
+        .iter()
+        .map(|n| n.as_ref().map(|n| *n.key()))
+        .collect();
+    sgt.arena.shrink_to_fit();
+    assert!(sgt.arena.iter().last().unwrap().is_some());
+    assert!(sgt
+        .arena
+        .iter()
+        .map(|n| n.as_ref().map(|n| *n.key()))
+        .eq(layout.into_iter().take(sgt.arena.iter().count())));
+    assert!(sgt.iter().eq(bt_map.iter()));
+
+    // Dense, in-order slots
+    sgt.compact();
+    assert_eq!(sgt.arena.iter().count(), sgt.len());
+    assert!(sgt.is_arena_sorted());
+    assert_logical_invariants(&sgt);
+    assert!(sgt.iter().eq(bt_map.iter()));
+    assert_eq!(sgt.first_key_value(), bt_map.iter().next());
+    assert_eq!(sgt.last_key_value(), bt_map.iter().next_back());
+
+    // New pairs fill slots after the last element
+    let len = sgt.len();
+    let (_, idx) = sgt.priv_balancing_insert(CAPACITY * 4, 0);
+    assert_eq!(idx, len);
+
+    sgt.clear();
+    sgt.compact();
+    assert_eq!(sgt.arena.iter().count(), 0);
+}
+
+#[test]
+fn test_optimize_layout() {
+    // Perfect tree, no rebalancing: inserted in level order
+    let keys = [8, 4, 12, 2, 6, 10, 14, 1, 3, 5, 7, 9, 11, 13, 15];
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    for k in keys.iter() {
+        sgt.insert(*k, *k * 2);
+    }
+    assert_eq!(sgt.rebal_cnt(), 0);
+
+    let slot_keys = |sgt: &SgTree<usize, usize, CAPACITY>| -> Vec<usize> {
+        sgt.arena
+            .iter()
+            .filter_map(|n| n.as_ref().map(|n| *n.key()))
+            .collect()
+    };
+
+    // Top half of the levels, then each bottom subtree, recursively
+    sgt.optimize_layout(LayoutPolicy::VanEmdeBoas);
+    assert_eq!(
+        slot_keys(&sgt),
+        [8, 4, 12, 2, 1, 3, 6, 5, 7, 10, 9, 11, 14, 13, 15]
+    );
+    assert_logical_invariants(&sgt);
+    assert_eq!(sgt.height(), 4);
+    assert!(sgt
+        .iter()
+        .map(|(k, v)| (*k, *v))
+        .eq((1..=15).map(|k| (k, k * 2))));
+
+    sgt.optimize_layout(LayoutPolicy::InOrder);
+    assert!(slot_keys(&sgt).into_iter().eq(1..=15));
+    assert!(sgt.is_arena_sorted());
+    assert_logical_invariants(&sgt);
+
+    // Irregular shape, with free slots
+    let mut rng = SmallRng::from_entropy();
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    let mut bt_map = BTreeMap::new();
+    for _ in 0..CAPACITY {
+        let k = rng.gen_range(0, CAPACITY * 4);
+        sgt.insert(k, k);
+        bt_map.insert(k, k);
+    }
+    for _ in 0..(CAPACITY / 2) {
+        let k = rng.gen_range(0, CAPACITY * 4);
+        assert_eq!(sgt.remove(&k), bt_map.remove(&k));
+    }
+
+    let height = sgt.height();
+    sgt.optimize_layout(LayoutPolicy::VanEmdeBoas);
+    assert_eq!(sgt.height(), height);
+    assert_eq!(slot_keys(&sgt).len(), sgt.len());
+    assert_eq!(
+        sgt.arena[sgt.opt_root_idx.unwrap()].key(),
+        slot_keys(&sgt).first().unwrap()
+    );
+    assert_logical_invariants(&sgt);
+    assert!(sgt.iter().eq(bt_map.iter()));
+    assert_eq!(sgt.first_key_value(), bt_map.iter().next());
+    assert_eq!(sgt.last_key_value(), bt_map.iter().next_back());
+
+    // Still fully functional
+    for _ in 0..(CAPACITY / 2) {
+        let k = rng.gen_range(0, CAPACITY * 4);
+        assert_eq!(sgt.remove(&k), bt_map.remove(&k));
+        if !sgt.is_full() {
+            let k = rng.gen_range(0, CAPACITY * 4);
+            assert_eq!(sgt.insert(k, k), bt_map.insert(k, k));
+        }
+    }
+    assert_logical_invariants(&sgt);
+    assert!(sgt.iter().eq(bt_map.iter()));
+}
+
+#[test]
+fn test_optimize_layout_tall_tree() {
+    const BIG_CAP: usize = 8_192;
+
+    // Arena and scratch buffers are stack-allocated, size well past the default test thread's stack
+    let handle = std::thread::Builder::new()
+        .stack_size(1 << 28)
+        .spawn(|| {
+            // `a` near `1.0` rebalances rarely, so ascending inserts build a tall, chain-like tree
+            let mut sgt = Box::new(SgTree::<usize, usize, BIG_CAP>::new());
+            sgt.set_rebal_ratio(999, 1_000).unwrap();
+            for k in 0..BIG_CAP {
+                sgt.insert(k, k);
+            }
+            let height = sgt.height();
+            assert!(height > 1_000);
+
+            sgt.optimize_layout(LayoutPolicy::VanEmdeBoas);
+            assert_eq!(sgt.height(), height);
+            assert_eq!(sgt.opt_root_idx, Some(0));
+            assert_logical_invariants(&sgt);
+            assert!(sgt
+                .iter()
+                .map(|(k, v)| (*k, *v))
+                .eq((0..BIG_CAP).map(|k| (k, k))));
+        })
+        .unwrap();
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_reserve() {
+    let mut sgt = SgTree::<usize, usize, 4>::new();
+    assert_eq!(sgt.try_reserve(4), Ok(()));
+    assert_eq!(sgt.try_reserve(5), Err(SgError::StackCapacityExceeded));
+
+    sgt.insert(1, 1);
+    assert_eq!(sgt.try_reserve(3), Ok(()));
+    assert_eq!(sgt.try_reserve(4), Err(SgError::StackCapacityExceeded));
+    assert_eq!(
+        sgt.try_reserve(usize::MAX),
+        Err(SgError::StackCapacityExceeded)
+    );
+
+    // Full, only a no-op reservation succeeds
+    sgt.extend([(2, 2), (3, 3), (4, 4)]);
+    assert_eq!(sgt.try_reserve(0), Ok(()));
+    assert_eq!(sgt.try_reserve(1), Err(SgError::StackCapacityExceeded));
+}
+
+#[test]
+fn test_check_invariants() {
+    let (mut sgt, keys) = get_test_tree_and_keys();
+
+    let report = sgt.check_invariants();
+    assert!(report.is_valid());
+    assert_eq!(report.node_cnt, keys.len());
+    assert_eq!(report.height, sgt.height());
+
+    let empty = SgTree::<usize, usize, CAPACITY>::new();
+    assert!(empty.check_invariants().is_valid());
+    assert_eq!(empty.check_invariants().node_cnt, 0);
+
+    // Ordering violation
+    let mut bad = sgt.clone();
+    let root_idx = bad.opt_root_idx.unwrap();
+    bad.arena[bad.max_idx].set_key(0);
+    let report = bad.check_invariants();
+    assert!(!report.ordered);
+    assert!(!report.is_valid());
+
+    // Stale min/max cache
+    let mut bad = sgt.clone();
+    bad.min_idx = bad.max_idx;
+    let report = bad.check_invariants();
+    assert!(report.ordered);
+    assert!(!report.min_max_correct);
+
+    // Cycle
+    let mut bad = sgt.clone();
+    let max_idx = bad.max_idx;
+    bad.arena[max_idx].set_right_idx(Some(root_idx));
+    let report = bad.check_invariants();
+    assert!(!report.acyclic);
+    assert!(!report.is_valid());
+
+    let mut bad = sgt.clone();
+    let min_idx = bad.min_idx;
+    bad.arena[min_idx].set_left_idx(Some(root_idx));
+    let report = bad.check_invariants();
+    assert!(!report.acyclic);
+    assert!(!report.is_valid());
+
+    // Shared child
+    let mut bad = sgt.clone();
+    let (min_idx, max_idx) = (bad.min_idx, bad.max_idx);
+    bad.arena[min_idx].set_left_idx(Some(max_idx));
+    let report = bad.check_invariants();
+    assert!(!report.acyclic);
+    assert!(!report.is_valid());
+
+    // Dangling link and unreachable node
+    let mut bad = sgt.clone();
+    let min_idx = bad.min_idx;
+    bad.arena[min_idx].set_left_idx(Some(CAPACITY - 1));
+    assert!(!bad.check_invariants().arena_consistent);
+
+    let mut bad = sgt.clone();
+    let root = &mut bad.arena[root_idx];
+    root.set_left_idx(None);
+    root.set_right_idx(None);
+    let report = bad.check_invariants();
+    assert!(!report.arena_consistent);
+    assert!(!report.len_correct);
+    assert_eq!(report.node_cnt, 1);
+
+    // Alpha bound tightened after the fact
+    sgt.clear();
+    assert!(sgt.set_rebal_ratio(99, 100).is_ok());
+    for k in 0..100 {
+        sgt.insert(k, "n/a");
+    }
+    assert!(sgt.check_invariants().is_valid());
+    assert!(sgt.set_rebal_ratio(1, 2).is_ok());
+    let report = sgt.check_invariants();
+    assert!(!report.height_bounded);
+    assert!(report.height > report.max_height);
+}
+
+#[test]
+fn test_set_rebal_ratio_and_rebalance() {
+    let mut sgt = SgTree::<_, _, CAPACITY>::new();
+    assert!(sgt.set_rebal_ratio(99, 100).is_ok());
+    for k in 0..100 {
+        sgt.insert(k, k);
+    }
+
+    // Already within the bound, no rebuild
+    let rebal_cnt = sgt.rebal_cnt();
+    assert!(sgt.set_rebal_ratio_and_rebalance(99, 100).is_ok());
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt);
+
+    // Tightened bound, immediate rebuild
+    assert!(sgt.height() > SgTree::<usize, usize, CAPACITY>::min_height(sgt.len()));
+    assert!(sgt.set_rebal_ratio_and_rebalance(1, 2).is_ok());
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt + 1);
+    assert_eq!(
+        sgt.height(),
+        SgTree::<usize, usize, CAPACITY>::min_height(sgt.len())
+    );
+    assert_logical_invariants(&sgt);
+
+    // Invalid rebalance factor leaves the tree untouched
+    assert_eq!(
+        sgt.set_rebal_ratio_and_rebalance(2, 1),
+        Err(SgError::RebalanceFactorOutOfRange)
+    );
+    assert_eq!(sgt.rebal_ratio(), (1, 2));
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt + 1);
+}
+
+#[test]
+fn test_to_dot() {
+    use super::DotOptions;
+
+    let (sgt, keys) = get_test_tree_and_keys();
+    let root_idx = sgt.opt_root_idx.unwrap();
+
+    let mut dot = String::new();
+    sgt.to_dot(&mut dot).unwrap();
+
+    assert!(dot.starts_with("digraph {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert_eq!(
+        dot.matches(" -> ").count() - dot.matches("[style=invis];").count() / 2,
+        keys.len() - 1
+    );
+    assert!(dot.contains(&format!(
+        "n{} [label=\"{:?}\"];",
+        root_idx,
+        sgt.arena[root_idx].key()
+    )));
+    for k in &keys {
+        assert!(dot.contains(&format!("label=\"{}\"", k)));
+    }
+
+    let opts = DotOptions {
+        arena_idxs: true,
+        subtree_sizes: true,
+    };
+    let mut dot = String::new();
+    sgt.to_dot_with(&mut dot, opts).unwrap();
+    assert!(dot.contains(&format!("\\nidx: {}\\nsize: {}\"", root_idx, keys.len())));
+
+    // Only child is offset by an invisible sibling
+    let mut sgt = SgTree::<&str, (), CAPACITY>::new();
+    sgt.insert("a\"b", ());
+    sgt.insert("c\\d", ());
+    let mut dot = String::new();
+    sgt.to_dot(&mut dot).unwrap();
+    assert_eq!(dot.matches("[style=invis];").count(), 2);
+    assert!(dot.contains(r#"[label="\"a\\\"b\""];"#));
+    assert!(dot.contains(r#"[label="\"c\\\\d\""];"#));
+
+    let mut dot = String::new();
+    SgTree::<usize, usize, CAPACITY>::new()
+        .to_dot(&mut dot)
+        .unwrap();
+    assert_eq!(dot, "digraph {\n    node [shape=box];\n}\n");
+}
+
 #[test]
 fn test_extend() {
     let mut sgt_1 = SgTree::<_, _, CAPACITY>::new();
@@ -655,7 +1468,7 @@ fn test_extend_panic() {
 
     // Attempt to extend already full tree
     assert_eq!(sgt.len(), sgt.capacity());
-    sgt.extend(sgt_2.into_iter()); // Should panic
+    sgt.extend(sgt_2); // Should panic
 }
 
 #[test]
@@ -701,26 +1514,114 @@ fn test_clone() {
     assert_eq!(sgt_1, sgt_2);
 }
 
+#[test]
+fn test_alpha_balance_depth() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+
+    // `a = 1/2`, exact at powers of two
+    assert!(sgt.set_rebal_ratio(1, 2).is_ok());
+    assert_eq!(sgt.alpha_balance_depth(0), 0);
+    assert_eq!(sgt.alpha_balance_depth(1), 0);
+    for exp in 1..32 {
+        assert_eq!(sgt.alpha_balance_depth(1 << exp), exp);
+        assert_eq!(sgt.alpha_balance_depth((1 << exp) - 1), exp - 1);
+    }
+
+    // `a = 2/3`: 1.5^5 = 7.59..., 1.5^6 = 11.39...
+    assert!(sgt.set_rebal_ratio(2, 3).is_ok());
+    assert_eq!(sgt.alpha_balance_depth(7), 4);
+    assert_eq!(sgt.alpha_balance_depth(8), 5);
+    assert_eq!(sgt.alpha_balance_depth(11), 5);
+    assert_eq!(sgt.alpha_balance_depth(12), 6);
+
+    // Unreduced ratios are equivalent
+    assert!(sgt.set_rebal_ratio(4, 6).is_ok());
+    assert_eq!(sgt.alpha_balance_depth(12), 6);
+
+    // Range checks are exact
+    assert!(sgt.set_rebal_ratio(50, 100).is_ok());
+    assert!(sgt.set_rebal_ratio(49, 100).is_err());
+    assert!(sgt.set_rebal_ratio(1, 1).is_err());
+    assert!(sgt.set_rebal_ratio(0, 0).is_err());
+
+    // Matches a 128-bit reference, including ratios whose terms use all 32 bits
+    let reference = |num: u32, denom: u32, val: usize| {
+        let (one, mut scaled, mut depth) = (1_u128 << 32, (val as u128) << 32, 0);
+        loop {
+            scaled = scaled * u128::from(num) / u128::from(denom);
+            if scaled < one {
+                return depth;
+            }
+            depth += 1;
+        }
+    };
+    for (num, denom) in [
+        (1, 2),
+        (2, 3),
+        (7, 10),
+        (99, 100),
+        (4_000_000_000, u32::MAX),
+    ] {
+        assert!(sgt.set_rebal_ratio(num, denom).is_ok());
+        for val in (0..2_000).chain((10..32).map(|exp| (1 << exp) - 1)) {
+            assert_eq!(sgt.alpha_balance_depth(val), reference(num, denom, val));
+        }
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_set_rebal_param_f32() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    assert_eq!(sgt.rebal_param(), (2.0, 3.0));
+
+    // Whole numbers convert exactly
+    assert!(sgt.set_rebal_param(9.0, 10.0).is_ok());
+    assert_eq!(sgt.rebal_ratio(), (9, 10));
+
+    // Fractions are rounded down to a multiple of `1 / 2^16`
+    assert!(sgt.set_rebal_param(0.75, 1.0).is_ok());
+    assert_eq!(sgt.rebal_ratio(), (49_152, 65_536));
+    assert!(sgt.set_rebal_param(0.7, 1.0).is_ok());
+    assert_eq!(sgt.rebal_ratio(), (45_875, 65_536));
+    assert_eq!(sgt.rebal_param(), (45_875.0, 65_536.0));
+
+    // Invalid rebalance factor, previous value kept
+    for (num, denom) in [
+        (0.4, 1.0),
+        (1.0, 1.0),
+        (2.0, 1.0),
+        (1.0, 0.0),
+        (f32::NAN, 1.0),
+    ] {
+        assert_eq!(
+            sgt.set_rebal_param(num, denom),
+            Err(SgError::RebalanceFactorOutOfRange)
+        );
+    }
+    assert_eq!(sgt.rebal_ratio(), (45_875, 65_536));
+}
+
 #[cfg(not(feature = "alt_impl"))] // This affects rebalance count and is experimental.
 #[test]
-fn test_set_rebal_param() {
-    assert!(CAPACITY >= 100);
+fn test_set_rebal_ratio() {
+    const _: () = assert!(CAPACITY >= 100);
     let data: Vec<(usize, usize)> = (0..100).map(|x| (x, x)).collect();
-    let sgt_1 = SgTree::<_, _, CAPACITY>::from_iter(data.clone().into_iter());
+    let sgt_1 = SgTree::<_, _, CAPACITY>::from_iter(data.clone());
 
     // Lax rebalancing
     let mut sgt_2 = SgTree::<_, _, CAPACITY>::new();
-    assert!(sgt_2.set_rebal_param(0.9, 1.0).is_ok());
-    sgt_2.extend(data.clone().into_iter());
+    assert!(sgt_2.set_rebal_ratio(9, 10).is_ok());
+    sgt_2.extend(data.clone());
 
     // Strict rebalancing
     let mut sgt_3 = SgTree::<_, _, CAPACITY>::new();
-    assert!(sgt_3.set_rebal_param(1.0, 2.0).is_ok());
-    sgt_3.extend(data.into_iter());
+    assert!(sgt_3.set_rebal_ratio(1, 2).is_ok());
+    sgt_3.extend(data);
 
     // Invalid rebalance factor
     assert_eq!(
-        sgt_3.set_rebal_param(2.0, 1.0),
+        sgt_3.set_rebal_ratio(2, 1),
         Err(SgError::RebalanceFactorOutOfRange)
     );
 
@@ -729,10 +1630,14 @@ fn test_set_rebal_param() {
     assert!(sgt_1.rebal_cnt() > sgt_2.rebal_cnt());
     assert!(sgt_3.rebal_cnt() > sgt_1.rebal_cnt());
 
-    // Exact counts, useful to verify that different features being enabled don't change these numbers
-    assert_eq!(sgt_1.rebal_cnt(), 52);
-    assert_eq!(sgt_2.rebal_cnt(), 8);
-    assert_eq!(sgt_3.rebal_cnt(), 93);
+    // Exact counts, useful to verify that different features being enabled don't change these numbers.
+    // Except `low_mem_rebuild`: DSW builds complete trees, a different (but equally balanced) shape.
+    #[cfg(not(feature = "low_mem_rebuild"))]
+    {
+        assert_eq!(sgt_1.rebal_cnt(), 52);
+        assert_eq!(sgt_2.rebal_cnt(), 8);
+        assert_eq!(sgt_3.rebal_cnt(), 93);
+    }
 }
 
 #[test]
@@ -752,11 +1657,90 @@ fn test_intersect_cnt() {
     assert_eq!(sgt_1.intersect_cnt(&sgt_2), 0);
 }
 
-#[should_panic(expected = "Max stack item capacity (0xffff) exceeded!")]
 #[test]
-fn test_capacity_exceed() {
-    const OVER_CAP: usize = (Idx::MAX as usize) + 1;
-    let _ = SgTree::<u8, u8, OVER_CAP>::new();
+fn test_index_width() {
+    const SMALL_CAP: usize = 254;
+
+    // Narrower links, smaller nodes
+    let node_sizes = [
+        SgTree::<u8, u8, SMALL_CAP, u8>::NODE_SIZE,
+        SgTree::<u8, u8, SMALL_CAP, u16>::NODE_SIZE,
+        SgTree::<u8, u8, SMALL_CAP, u32>::NODE_SIZE,
+    ];
+    assert!(node_sizes.windows(2).all(|w| w[0] < w[1]));
+    assert!(
+        core::mem::size_of::<SgTree<u8, u8, SMALL_CAP, u8>>()
+            < core::mem::size_of::<SgTree<u8, u8, SMALL_CAP>>()
+    );
+    assert_eq!(SgTree::<u8, u8, SMALL_CAP, u8>::new().capacity(), SMALL_CAP);
+
+    // Same logical behavior at every width
+    let mut sgt_u8 = SgTree::<usize, &str, SMALL_CAP, u8>::new();
+    let mut sgt_u32 = SgTree::<usize, &str, SMALL_CAP, u32>::new();
+    logical_fuzz(&mut sgt_u8, SMALL_CAP, true);
+    logical_fuzz(&mut sgt_u32, SMALL_CAP, true);
+
+    // Full u8-indexed tree
+    let mut sgt = SgTree::<u8, u8, SMALL_CAP, u8>::new();
+    for i in 0..(SMALL_CAP as u8) {
+        assert_eq!(sgt.try_insert(i, i), Ok(None));
+    }
+    assert_eq!(sgt.len(), SMALL_CAP);
+    assert_eq!(
+        sgt.try_insert(u8::MAX, 0),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert_logical_invariants(&sgt);
+    assert!(sgt.iter().map(|(k, _)| *k).eq(0..(SMALL_CAP as u8)));
+
+    // Compile-time selection, at each boundary
+    use core::any::TypeId;
+    assert_eq!(
+        TypeId::of::<crate::arena_idx!(SMALL_CAP)>(),
+        TypeId::of::<u8>()
+    );
+    assert_eq!(
+        TypeId::of::<crate::arena_idx!(SMALL_CAP + 1)>(),
+        TypeId::of::<u16>()
+    );
+    assert_eq!(
+        TypeId::of::<crate::arena_idx!(u16::MAX - 1)>(),
+        TypeId::of::<u16>()
+    );
+    assert_eq!(
+        TypeId::of::<crate::arena_idx!(u16::MAX)>(),
+        TypeId::of::<u32>()
+    );
+    assert_eq!(
+        SgTree::<u8, u8, SMALL_CAP, crate::arena_idx!(SMALL_CAP)>::NODE_SIZE,
+        node_sizes[0]
+    );
+}
+
+#[test]
+fn test_capacity_beyond_u16_max() {
+    const BIG_CAP: usize = (u16::MAX as usize) + 4_465;
+
+    // Arena and scratch buffers are stack-allocated, size well past the default test thread's stack
+    let handle = std::thread::Builder::new()
+        .stack_size(1 << 28)
+        .spawn(|| {
+            let mut sgt = Box::new(SgTree::<u32, u32, BIG_CAP, u32>::new());
+            assert_eq!(sgt.capacity(), BIG_CAP);
+
+            // Every insert zero-fills `BIG_CAP`-sized scratch, so filling the tree is too slow for a debug build.
+            // Pushing past `u16::MAX` items is covered by `StackVec`'s own tests.
+            for i in (0..1_000).rev() {
+                assert_eq!(sgt.try_insert(i, i), Ok(None));
+            }
+            assert_eq!(sgt.len(), 1_000);
+            assert_eq!(sgt.remove(&500), Some(500));
+            assert_eq!(sgt.first_key_value(), Some((&0, &0)));
+            assert_logical_invariants(&sgt);
+        })
+        .unwrap();
+
+    handle.join().unwrap();
 }
 
 #[test]
@@ -774,3 +1758,451 @@ fn test_double_ended_iter_mut() {
     assert_eq!(None, iter.next());
     assert_eq!(None, iter.next_back());
 }
+
+#[test]
+fn test_iter_last_min_max() {
+    let sgt = SgTree::from([(3, 4), (1, 2), (5, 6)]);
+
+    // Fresh iterators
+    assert_eq!(sgt.iter().last(), Some((&5, &6)));
+    assert_eq!(sgt.iter().min(), Some((&1, &2)));
+    assert_eq!(sgt.iter().max(), Some((&5, &6)));
+    assert_eq!(sgt.clone().into_iter().last(), Some((5, 6)));
+    assert_eq!(sgt.clone().into_iter().min(), Some((1, 2)));
+    assert_eq!(sgt.clone().into_iter().max(), Some((5, 6)));
+
+    // Partially consumed iterators
+    let mut iter = sgt.iter();
+    assert_eq!(iter.next(), Some((&1, &2)));
+    assert_eq!(iter.min(), Some((&3, &4)));
+
+    let mut iter = sgt.clone().into_iter();
+    assert_eq!(iter.next(), Some((1, 2)));
+    assert_eq!(iter.min(), Some((3, 4)));
+
+    // Exhausted iterators
+    let mut iter = sgt.iter();
+    while iter.next().is_some() {}
+    assert_eq!(iter.last(), None);
+
+    let mut iter = sgt.into_iter();
+    while iter.next().is_some() {}
+    assert_eq!(iter.max(), None);
+
+    // Empty tree
+    let sgt = SgTree::<usize, usize, CAPACITY>::new();
+    assert_eq!(sgt.iter().last(), None);
+    assert_eq!(sgt.into_iter().last(), None);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_retain() {
+    let mut rng = SmallRng::from_entropy();
+
+    for _ in 0..10 {
+        let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+        let mut bt_map = BTreeMap::new();
+
+        while sgt.len() < (CAPACITY / 2) {
+            let k = rng.gen::<usize>();
+            sgt.insert(k, k);
+            bt_map.insert(k, k);
+        }
+
+        let divisor = rng.gen_range(1, 8);
+        sgt.par_retain(|&k, _| k % divisor == 0);
+        bt_map.retain(|&k, _| k % divisor == 0);
+
+        assert_logical_invariants(&sgt);
+        assert!(sgt.iter().eq(bt_map.iter()));
+        assert_eq!(sgt.first_key_value(), bt_map.iter().next());
+        assert_eq!(sgt.last_key_value(), bt_map.iter().next_back());
+
+        // Still usable after rebuild
+        sgt.insert(0, 0);
+        bt_map.insert(0, 0);
+        assert!(sgt.iter().eq(bt_map.iter()));
+    }
+
+    let mut sgt = SgTree::from([(1, 1), (2, 2), (3, 3)]);
+    sgt.par_retain(|&k, _| k == 2);
+    assert!(sgt.iter().eq([(&2, &2)]));
+    sgt.par_retain(|_, _| false);
+    assert!(sgt.is_empty());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_range_for_each() {
+    use core::ops::Bound::{Excluded, Included, Unbounded};
+
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::from_iter((0..100).map(|k| (k * 2, 0)));
+
+    sgt.par_range_for_each(10..20, |_, v| *v += 1);
+    sgt.par_range_for_each((Excluded(11), Included(31)), |_, v| *v += 1);
+    sgt.par_range_for_each((Unbounded, Excluded(3)), |_, v| *v += 1);
+    sgt.par_range_for_each(1000.., |_, v| *v += 1);
+
+    for (k, v) in sgt.iter() {
+        let expected = (10..20).contains(k) as usize
+            + (12..=31).contains(k) as usize
+            + (0..3).contains(k) as usize;
+        assert_eq!(*v, expected, "key {}", k);
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_iter() {
+    use rayon::prelude::*;
+
+    let mut rng = SmallRng::from_entropy();
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    let mut bt_map = BTreeMap::new();
+
+    while sgt.len() < (CAPACITY / 2) {
+        let k = rng.gen::<usize>() % (CAPACITY * 4);
+        sgt.insert(k, k);
+        bt_map.insert(k, k);
+    }
+
+    // Leave gaps in the arena
+    sgt.retain(|&k, _| k % 3 != 0);
+    bt_map.retain(|&k, _| k % 3 != 0);
+
+    let mut entries: Vec<(&usize, &usize)> = sgt.par_iter().collect();
+    entries.sort();
+    assert!(entries.into_iter().eq(bt_map.iter()));
+
+    sgt.par_iter_mut().for_each(|(k, v)| *v = k * 2);
+    bt_map.iter_mut().for_each(|(k, v)| *v = k * 2);
+    assert!(sgt.iter().eq(bt_map.iter()));
+    assert_logical_invariants(&sgt);
+
+    // Indexed, so collect preserves key order
+    let keys: Vec<usize> = sgt.par_iter_mut().map(|(k, _)| *k).collect();
+    assert!(keys.iter().eq(bt_map.keys()));
+
+    let mut empty = SgTree::<usize, usize, CAPACITY>::new();
+    assert_eq!(empty.par_iter().count(), 0);
+    assert_eq!(empty.par_iter_mut().count(), 0);
+}
+
+#[test]
+fn test_range_search() {
+    use core::ops::Bound::{self, Excluded, Included, Unbounded};
+
+    let mut rng = SmallRng::from_entropy();
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    let mut bt_map = BTreeMap::new();
+
+    while sgt.len() < (CAPACITY / 2) {
+        let k = rng.gen_range(0, CAPACITY * 4);
+        sgt.insert(k, k);
+        bt_map.insert(k, k);
+    }
+
+    let rand_bound = |rng: &mut SmallRng, k: usize| -> Bound<usize> {
+        match rng.gen_range(0, 3) {
+            0 => Included(k),
+            1 => Excluded(k),
+            _ => Unbounded,
+        }
+    };
+
+    for _ in 0..1000 {
+        let mut a = rng.gen_range(0, CAPACITY * 4);
+        let mut b = rng.gen_range(0, CAPACITY * 4);
+        if a > b {
+            core::mem::swap(&mut a, &mut b);
+        }
+        if a == b {
+            b += 1;
+        }
+
+        let range = (rand_bound(&mut rng, a), rand_bound(&mut rng, b));
+        let sgt_keys: Vec<_> = sgt
+            .range_search(&range)
+            .into_iter()
+            .map(|idx| *sgt.arena[idx].key())
+            .collect();
+
+        assert!(sgt_keys.iter().eq(bt_map.range(range).map(|(k, _)| k)));
+    }
+}
+
+#[cfg(feature = "rebal_stats")]
+#[test]
+fn test_rebal_stats() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    assert_eq!(sgt.rebal_stats(), RebalStats::default());
+
+    // Sequential insert, worst case for rebalancing
+    for k in 0..CAPACITY {
+        sgt.insert(k, k);
+    }
+
+    let stats = sgt.rebal_stats();
+    assert_eq!(stats.rebuild_cnt, sgt.rebal_cnt());
+    assert!(stats.max_rebuild_size > 1);
+    assert!(stats.max_rebuild_size <= CAPACITY);
+    assert!(stats.nodes_moved >= stats.rebuild_cnt * 2);
+    assert!(stats.nodes_moved >= stats.max_rebuild_size);
+
+    // Persists across clear, like rebal_cnt
+    sgt.clear();
+    assert_eq!(sgt.rebal_stats(), stats);
+
+    // Reset doesn't impact rebal_cnt
+    let rebal_cnt = sgt.rebal_cnt();
+    sgt.reset_rebal_stats();
+    assert_eq!(sgt.rebal_stats(), RebalStats::default());
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt);
+}
+
+#[test]
+fn test_maintain() {
+    let mut rng = SmallRng::from_entropy();
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    let mut bt_map = BTreeMap::new();
+
+    while sgt.len() < CAPACITY {
+        let k = rng.gen::<usize>();
+        sgt.insert(k, k);
+        bt_map.insert(k, k);
+    }
+
+    for k in bt_map
+        .keys()
+        .take(CAPACITY / 3)
+        .copied()
+        .collect::<Vec<_>>()
+    {
+        sgt.remove(&k);
+        bt_map.remove(&k);
+    }
+
+    // Nothing done if budget too small
+    let rebal_cnt = sgt.rebal_cnt();
+    assert!(sgt.maintain(sgt.len() - 1));
+    assert_eq!(sgt.rebal_cnt(), rebal_cnt);
+
+    // Checks alone are charged, so a zero budget can't confirm there's nothing left
+    assert!(sgt.maintain(0));
+
+    // Run to completion, two checks plus a task fit in each call
+    while sgt.maintain(3 * sgt.len()) {}
+    assert!(sgt.maintain(0));
+    assert!(!sgt.maintain(3 * sgt.len()));
+
+    assert_logical_invariants(&sgt);
+    assert_eq!(
+        sgt.height(),
+        SgTree::<usize, usize, CAPACITY>::min_height(sgt.len())
+    );
+    assert!(sgt.is_arena_sorted());
+    assert!(sgt.iter().eq(bt_map.iter()));
+
+    // Empty tree has nothing to do
+    sgt.clear();
+    assert!(!sgt.maintain(0));
+}
+
+#[test]
+fn test_bulk_load() {
+    let mut rng = SmallRng::from_entropy();
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    let mut bt_map = BTreeMap::new();
+    let alpha = sgt.rebal_ratio();
+
+    let saved_alpha = sgt.begin_bulk_load();
+    assert_eq!(saved_alpha, alpha);
+
+    while sgt.len() < CAPACITY {
+        let k = rng.gen::<usize>();
+        sgt.insert(k, k);
+        bt_map.insert(k, k);
+    }
+
+    // No scapegoat rebuilds mid-load
+    assert_eq!(sgt.rebal_cnt(), 0);
+    assert!(sgt.iter().eq(bt_map.iter()));
+
+    sgt.end_bulk_load(saved_alpha);
+    assert_eq!(sgt.rebal_ratio(), alpha);
+    assert_eq!(sgt.rebal_cnt(), 1);
+    assert_eq!(
+        sgt.height(),
+        SgTree::<usize, usize, CAPACITY>::min_height(sgt.len())
+    );
+    assert_logical_invariants(&sgt);
+    assert!(sgt.iter().eq(bt_map.iter()));
+
+    // Steady-state balancing resumes
+    let k = sgt.pop_first().unwrap().0;
+    sgt.insert(k, k);
+    assert_logical_invariants(&sgt);
+}
+
+#[test]
+#[cfg(feature = "telemetry")]
+fn test_telemetry() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    assert_eq!(sgt.telemetry(), Telemetry::default());
+
+    for k in 0..(CAPACITY / 2) {
+        sgt.insert(k, k);
+    }
+
+    for k in 0..(CAPACITY / 4) {
+        sgt.remove(&k);
+    }
+
+    // High-water mark, not current length
+    let telemetry = sgt.telemetry();
+    assert_eq!(telemetry.max_len, CAPACITY / 2);
+    #[cfg(not(feature = "low_mem_rebuild"))]
+    #[cfg(feature = "rebal_stats")]
+    assert!(telemetry.max_scratch_len >= sgt.rebal_stats().max_rebuild_size);
+    assert!(telemetry.max_scratch_len <= CAPACITY);
+
+    // Persists across clear
+    sgt.clear();
+    assert_eq!(sgt.telemetry(), telemetry);
+
+    sgt.reset_telemetry();
+    assert_eq!(sgt.telemetry(), Telemetry::default());
+}
+
+#[test]
+fn test_stats() {
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    assert_eq!(
+        sgt.stats(),
+        TreeStats {
+            capacity: CAPACITY,
+            ..Default::default()
+        }
+    );
+
+    for k in 0..(CAPACITY / 2) {
+        sgt.insert(k, k);
+    }
+
+    for k in 0..(CAPACITY / 4) {
+        sgt.remove(&k);
+    }
+
+    let stats = sgt.stats();
+    assert_eq!(stats.len, sgt.len());
+    assert_eq!(
+        stats.height,
+        sgt.iter_levels().map(|(d, _, _)| d + 1).max().unwrap()
+    );
+    assert_eq!(stats.rebal_cnt, sgt.rebal_cnt());
+    #[cfg(feature = "rebal_stats")]
+    {
+        assert_eq!(stats.nodes_moved, sgt.rebal_stats().nodes_moved);
+        assert_eq!(stats.max_rebuild_size, sgt.rebal_stats().max_rebuild_size);
+    }
+    #[cfg(not(feature = "rebal_stats"))]
+    assert_eq!((stats.nodes_moved, stats.max_rebuild_size), (0, 0));
+    assert!(stats.arena_slots >= CAPACITY / 4 && stats.arena_slots <= CAPACITY / 2);
+    assert_eq!(stats.capacity, CAPACITY);
+}
+
+#[test]
+fn test_iter_levels() {
+    let (sgt, keys) = get_test_tree_and_keys();
+    let levels: Vec<_> = sgt.iter_levels().collect();
+
+    // Every node visited once
+    assert_eq!(sgt.iter_levels().len(), keys.len());
+    assert_eq!(levels.len(), keys.len());
+    let mut level_keys: Vec<_> = levels.iter().map(|(_, k, _)| **k).collect();
+    level_keys.sort_unstable();
+    assert!(level_keys.iter().eq(sgt.iter().map(|(k, _)| k)));
+
+    // Breadth-first: depths non-decreasing, root first
+    assert!(levels.windows(2).all(|w| w[0].0 <= w[1].0));
+    assert_eq!(levels[0].0, 0);
+    assert_eq!(
+        Some(levels[0].1),
+        sgt.opt_root_idx.map(|idx| sgt.arena[idx].key())
+    );
+    assert_eq!(levels.last().unwrap().0 + 1, sgt.height());
+
+    // Perfect tree, no rebalancing: inserted in level order, so depths are known exactly
+    let keys = [8, 4, 12, 2, 6, 10, 14, 1, 3, 5, 7, 9, 11, 13, 15];
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    for k in keys.iter() {
+        sgt.insert(*k, *k);
+    }
+    assert!(sgt.iter_levels().map(|(d, k, _)| (d, *k)).eq(keys
+        .iter()
+        .enumerate()
+        .map(|(i, k)| (SgTree::<usize, usize, CAPACITY>::min_height(i + 1) - 1, *k))));
+
+    // Empty
+    assert_eq!(
+        SgTree::<usize, usize, CAPACITY>::new().iter_levels().next(),
+        None
+    );
+}
+
+#[test]
+fn test_order_statistics() {
+    fn assert_ranks(sgt: &SgTree<usize, usize, CAPACITY>, shadow: &BTreeMap<usize, usize>) {
+        for (n, (k, v)) in shadow.iter().enumerate() {
+            assert_eq!(sgt.get_index(n), Some((k, v)));
+            assert_eq!(sgt.rank(k), Some(n));
+        }
+        assert_eq!(sgt.get_index(shadow.len()), None);
+        assert_eq!(sgt.rank(&CAPACITY), None);
+    }
+
+    let mut sgt = SgTree::<usize, usize, CAPACITY>::new();
+    let mut shadow = BTreeMap::new();
+    let mut rng = SmallRng::from_entropy();
+
+    assert_eq!(sgt.get_index(0), None);
+    assert_eq!(sgt.rank(&0), None);
+
+    for i in 0..4_000 {
+        // Narrow key space: exercises replacement of existing keys
+        let key = rng.gen_range(0, CAPACITY / 2);
+        match rng.gen_range(0, 4) {
+            0 => assert_eq!(sgt.remove(&key), shadow.remove(&key)),
+            1 => assert_eq!(sgt.pop_first(), shadow.pop_first()),
+            _ => assert_eq!(sgt.insert(key, i), shadow.insert(key, i)),
+        }
+
+        if i % 100 == 0 {
+            assert_ranks(&sgt, &shadow);
+        }
+    }
+
+    // Bulk mutation
+    sgt.retain(|k, _| k % 3 != 0);
+    shadow.retain(|k, _| k % 3 != 0);
+    assert_ranks(&sgt, &shadow);
+
+    let split = CAPACITY / 4;
+    let mut sgt_hi = sgt.split_off(&split);
+    let shadow_hi = shadow.split_off(&split);
+    assert_ranks(&sgt, &shadow);
+    assert_ranks(&sgt_hi, &shadow_hi);
+
+    // Mutation by rank
+    let len = sgt_hi.len();
+    assert!(len > 0);
+    if let Some((_, v)) = sgt_hi.get_index_mut(len / 2) {
+        *v = usize::MAX;
+    }
+    assert_eq!(
+        sgt_hi.iter().nth(len / 2).map(|(_, v)| *v),
+        Some(usize::MAX)
+    );
+    assert_eq!(sgt_hi.get_index_mut(len), None);
+}
@@ -1,37 +1,100 @@
 use core::borrow::Borrow;
 use core::cmp::Ordering;
-use core::fmt::{self, Debug};
+#[cfg(feature = "bytemuck")]
+use core::convert::TryFrom;
+use core::fmt::{self, Debug, Write};
 use core::hash::{Hash, Hasher};
-use core::iter::FromIterator;
+use core::iter::{self, FromIterator};
+use core::marker::PhantomData;
 use core::mem;
 use core::ops::{
-    Bound::{Excluded, Included},
-    Index, RangeBounds, Sub,
+    Bound::{self, Excluded, Included, Unbounded},
+    Index, RangeBounds,
 };
 
 use super::arena::Arena;
-use super::error::SgError;
-use super::iter::{IntoIter, Iter, IterMut};
-use super::node::{NodeGetHelper, NodeRebuildHelper};
+use super::compare::{above_end, below_start, Compare, Natural};
+use super::dot::{DotEscape, DotOptions};
+use super::error::{CapacityError, SgError};
+use super::handle::NodeHandle;
+use super::idx::ArenaIdx;
+use super::invariants::InvariantReport;
+use super::iter::{ExtractIf, IntoIter, Iter, IterMut, LevelIter};
+use super::node::{Node, NodeGetHelper, NodeRebuildHelper};
 use super::node_dispatch::SmallNode;
-
-#[allow(unused_imports)] // micromath only used if `no_std`
-use micromath::F32Ext;
+use super::policy::{DupPolicy, EvictPolicy, LayoutPolicy};
+#[cfg(feature = "rebal_stats")]
+use super::stats::RebalStats;
+#[cfg(feature = "telemetry")]
+use super::stats::Telemetry;
+use super::stats::{MemoryUsage, TreeStats};
+
+use super::stack_vec::StackVec;
+#[cfg(feature = "bytemuck")]
+use bytemuck::{bytes_of, pod_read_unaligned, Pod};
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use smallnum::SmallUnsigned;
-use tinyvec::{array_vec, ArrayVec};
-
-// The `u16::MAX` limit is documented in our main `README.md`.
-pub type Idx = u16;
 
 // See: https://github.com/tnballo/scapegoat/blob/master/CONFIG.md
-const DEFAULT_ALPHA_NUM: f32 = 2.0;
-const DEFAULT_ALPHA_DENOM: f32 = 3.0;
+const DEFAULT_ALPHA_NUM: u32 = 2;
+const DEFAULT_ALPHA_DENOM: u32 = 3;
+
+// Denominator for a fractional alpha passed to the deprecated `f32` API, e.g. `0.7` becomes `45_875 / 65_536`.
+const F32_ALPHA_DENOM: u32 = 1 << 16;
+
+// Rebuild worklist bound, independent of `N`.
+// A perfectly balanced rebuild of at most `usize::MAX` nodes is at most `usize::BITS` levels tall,
+// and a post-order walk over it holds at most two pending ranges per level (plus the one being expanded).
+const REBUILD_WORKLIST_CAP: usize = (2 * usize::BITS as usize) + 1;
+
+// Raw snapshot layout, native-endian `u64` words: a header, then one record per arena slot.
+// Header: magic (format version in the last byte), key size, value size, alpha numerator, alpha denominator,
+// max size, len, root index, min index, max index, slot count.
+// Slot: left index, right index, key bytes, value bytes. A vacant slot has both links `RAW_VACANT` and zeroed bytes.
+#[cfg(feature = "bytemuck")]
+const RAW_MAGIC: u64 = u64::from_le_bytes(*b"SGRAW\0\0\x01");
+#[cfg(feature = "bytemuck")]
+const RAW_HEADER_WORDS: usize = 11;
+#[cfg(feature = "bytemuck")]
+const RAW_NONE: u64 = u64::MAX;
+#[cfg(feature = "bytemuck")]
+const RAW_VACANT: u64 = u64::MAX - 1;
+
+/// Recomputes a node's derived data, stored in its value, from its key and its children's values.
+/// Selected by type, so it costs no space per tree, and the no-op default compiles augmentation passes out entirely.
+pub trait AugmentHook<K, V> {
+    /// `false` only for [`NoAugment`], skips augmentation passes at compile time.
+    const ENABLED: bool = true;
+
+    /// Recompute `val`'s derived data.
+    fn augment(key: &K, val: &mut V, opt_left: Option<&V>, opt_right: Option<&V>);
+}
+
+/// Default [`AugmentHook`], for trees without per-node derived data.
+#[derive(Clone, Copy)]
+pub struct NoAugment;
+
+impl<K, V> AugmentHook<K, V> for NoAugment {
+    const ENABLED: bool = false;
+
+    fn augment(_key: &K, _val: &mut V, _opt_left: Option<&V>, _opt_right: Option<&V>) {}
+}
 
 /// A memory-efficient, self-balancing binary search tree.
 #[derive(Clone)]
-pub struct SgTree<K: Default, V: Default, const N: usize> {
+pub struct SgTree<
+    K,
+    V,
+    const N: usize,
+    U: ArenaIdx = u16,
+    C: Compare<K> = Natural,
+    A: AugmentHook<K, V> = NoAugment,
+> {
     // Storage
-    pub(crate) arena: Arena<K, V, Idx, N>,
+    pub(crate) arena: Arena<K, V, U, N>,
     pub(crate) opt_root_idx: Option<usize>,
 
     // Query cache
@@ -40,23 +103,52 @@ pub struct SgTree<K: Default, V: Default, const N: usize> {
     curr_size: usize,
 
     // Balance control
-    alpha_num: f32,
-    alpha_denom: f32,
+    alpha_num: u32,
+    alpha_denom: u32,
     max_size: usize,
     rebal_cnt: usize,
+    #[cfg(feature = "rebal_stats")]
+    rebal_stats: RebalStats,
+    #[cfg(feature = "telemetry")]
+    telemetry: Telemetry,
+
+    // Ordering
+    pub(crate) cmp: C,
+
+    // Per-node derived data, maintained through every structural change
+    augment: PhantomData<A>,
 }
 
-impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    SgTree<K, V, N, U, C, A>
+{
     // Public API ------------------------------------------------------------------------------------------------------
 
+    /// Size of an individual node in this tree, in bytes. Usable in `const` contexts.
+    pub const NODE_SIZE: usize = mem::size_of::<Node<K, V, U>>();
+
+    // Evaluated per instantiation, so an over-large `N` is a build error instead of a constructor panic.
+    const CAPACITY_CHECK: () = assert!(
+        N <= U::MAX,
+        "Max stack item capacity exceeded! Use a wider `ArenaIdx`."
+    );
+
     /// Makes a new, empty `SgTree`.
-    pub fn new() -> Self {
-        if N > SgTree::<K, V, N>::max_capacity() {
-            panic!("Max stack item capacity (0x{:x}) exceeded!", Idx::MAX);
-        }
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_comparator(C::default())
+    }
+
+    /// Makes a new, empty `SgTree` ordered by `cmp`.
+    ///
+    /// Fails to build (post-monomorphization) if `N` exceeds `U`'s maximum capacity, see [`ArenaIdx`].
+    pub fn with_comparator(cmp: C) -> Self {
+        let () = Self::CAPACITY_CHECK;
 
         SgTree {
-            arena: Arena::<K, V, Idx, N>::default(),
+            arena: Arena::<K, V, U, N>::default(),
             opt_root_idx: None,
             max_idx: 0,
             min_idx: 0,
@@ -65,9 +157,26 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
             alpha_denom: DEFAULT_ALPHA_DENOM,
             max_size: 0,
             rebal_cnt: 0,
+            #[cfg(feature = "rebal_stats")]
+            rebal_stats: RebalStats::default(),
+            #[cfg(feature = "telemetry")]
+            telemetry: Telemetry::default(),
+            cmp,
+            augment: PhantomData,
         }
     }
 
+    /// Makes a new, empty `SgTree` with rebalance parameter `alpha_num / alpha_denom`.
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    pub fn try_with_rebal_ratio(alpha_num: u32, alpha_denom: u32) -> Result<Self, SgError>
+    where
+        C: Default,
+    {
+        let mut sgt = Self::new();
+        sgt.set_rebal_ratio(alpha_num, alpha_denom)?;
+        Ok(sgt)
+    }
+
     /// The [original scapegoat tree paper's](https://people.csail.mit.edu/rivest/pubs/GR93.pdf) alpha, `a`, can be chosen in the range `0.5 <= a < 1.0`.
     /// `a` tunes how "aggressively" the data structure self-balances.
     /// It controls the trade-off between total rebuild time and maximum height guarantees.
@@ -78,10 +187,13 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     /// * As `a` approaches `1.0`, the tree will rebalance less often. This means quicker insertions, but slower lookups and deletions.
     ///     * If `a` reached `1.0`, it'd mean a tree that never rebalances.
     ///
+    /// `a` is an integer ratio, `alpha_num / alpha_denom`, so balance decisions never need floating point math (e.g. on FPU-less targets).
+    ///
     /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
-    pub fn set_rebal_param(&mut self, alpha_num: f32, alpha_denom: f32) -> Result<(), SgError> {
-        let a = alpha_num / alpha_denom;
-        match (0.5..1.0).contains(&a) {
+    pub fn set_rebal_ratio(&mut self, alpha_num: u32, alpha_denom: u32) -> Result<(), SgError> {
+        // `0.5 <= num / denom < 1.0`, cross-multiplied to stay in integers
+        let (num, denom) = (u64::from(alpha_num), u64::from(alpha_denom));
+        match (2 * num >= denom) && (num < denom) {
             true => {
                 self.alpha_num = alpha_num;
                 self.alpha_denom = alpha_denom;
@@ -91,12 +203,51 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         }
     }
 
+    /// Set the rebalance parameter, then rebuild the whole tree if its current height exceeds the new bound.
+    /// Without a rebuild, a tightened alpha only takes effect as subsequent inserts trigger rebalances.
+    ///
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    pub fn set_rebal_ratio_and_rebalance(
+        &mut self,
+        alpha_num: u32,
+        alpha_denom: u32,
+    ) -> Result<(), SgError> {
+        self.set_rebal_ratio(alpha_num, alpha_denom)?;
+
+        if let Some(root_idx) = self.opt_root_idx {
+            if self.height() > self.alpha_balance_depth(self.max_size).saturating_add(1) {
+                self.rebuild(root_idx);
+                self.max_size = self.curr_size;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get the current rebalance parameter, alpha, as a tuple of `(alpha_numerator, alpha_denominator)`.
-    /// See [the corresponding setter method][SgTree::set_rebal_param] for more details.
-    pub fn rebal_param(&self) -> (f32, f32) {
+    /// See [the corresponding setter method][SgTree::set_rebal_ratio] for more details.
+    pub fn rebal_ratio(&self) -> (u32, u32) {
         (self.alpha_num, self.alpha_denom)
     }
 
+    /// Set the rebalance parameter from a floating point `alpha_num / alpha_denom`.
+    /// Whole-number arguments (e.g. `2.0, 3.0`) convert exactly, otherwise `a` is rounded down to a multiple of `1 / 2^16`.
+    ///
+    /// Returns `Err` if `0.5 <= alpha_num / alpha_denom < 1.0` isn't `true` (invalid `a`, out of range).
+    #[deprecated(note = "use `set_rebal_ratio`, which takes an integer ratio")]
+    pub fn set_rebal_param(&mut self, alpha_num: f32, alpha_denom: f32) -> Result<(), SgError> {
+        match Self::f32_alpha_ratio(alpha_num, alpha_denom) {
+            Some((num, denom)) => self.set_rebal_ratio(num, denom),
+            None => Err(SgError::RebalanceFactorOutOfRange),
+        }
+    }
+
+    /// Get the current rebalance parameter, alpha, as a floating point tuple of `(alpha_numerator, alpha_denominator)`.
+    #[deprecated(note = "use `rebal_ratio`, which returns an integer ratio")]
+    pub fn rebal_param(&self) -> (f32, f32) {
+        (self.alpha_num as f32, self.alpha_denom as f32)
+    }
+
     /// Total capacity, e.g. maximum number of tree pairs.
     pub fn capacity(&self) -> usize {
         self.arena.capacity()
@@ -107,59 +258,84 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         self.arena.node_size()
     }
 
-    /// Moves all elements from `other` into `self`, leaving `other` empty.
-    pub fn append(&mut self, other: &mut SgTree<K, V, N>)
-    where
-        K: Ord,
-    {
-        // Nothing to append!
-        if other.is_empty() {
-            return;
+    /// Checks that `additional` more elements fit in the fixed capacity `N`, returning `Err` otherwise.
+    /// Nothing is reserved or allocated, capacity never changes.
+    pub fn try_reserve(&self, additional: usize) -> Result<(), SgError> {
+        match self.len().checked_add(additional) {
+            Some(total) if total <= self.capacity() => Ok(()),
+            _ => Err(SgError::StackCapacityExceeded),
         }
+    }
 
-        // Nothing to append to!
-        if self.is_empty() {
-            mem::swap(self, other);
-            return;
-        }
+    /// Defragment storage: elements are moved into contiguous, in-order (by key) slots and all free slots are released.
+    /// Subsequent insertions fill slots directly after the last element.
+    pub fn compact(&mut self) {
+        self.sort_arena();
+        self.arena.shrink_to_fit();
+    }
 
-        // Rip elements directly out of other's arena and clear it
-        for arena_idx in 0..other.arena.len() {
-            if let Some(mut node) = other.arena.remove(arena_idx) {
-                self.insert(node.take_key(), node.take_val());
+    /// Reorder storage slots for cache locality, see [`LayoutPolicy`]. The tree's shape is unchanged.
+    /// Worth it after a build-then-read-mostly phase: later insertions and removals gradually undo the layout.
+    /// Invalidates all handles.
+    pub fn optimize_layout(&mut self, policy: LayoutPolicy) {
+        match policy {
+            LayoutPolicy::InOrder => self.sort_arena(),
+            LayoutPolicy::VanEmdeBoas => {
+                if let Some(root_idx) = self.opt_root_idx {
+                    let mut sort_metadata = StackVec::<[NodeGetHelper<usize>; N]>::new();
+                    let root_ngh = NodeGetHelper::new(Some(root_idx), None, false);
+                    self.veb_order(root_ngh, self.height(), &mut sort_metadata);
+
+                    self.opt_root_idx = Some(self.arena.sort(root_idx, sort_metadata));
+                    self.update_max_idx();
+                    self.update_min_idx();
+                }
             }
         }
-        other.clear();
     }
 
-    /// Attempts to move all elements from `other` into `self`, leaving `other` empty.
-    pub fn try_append(&mut self, other: &mut SgTree<K, V, N>) -> Result<(), SgError> {
-        // Nothing to append!
-        if other.is_empty() {
-            return Ok(());
-        }
+    /// Consumes the tree, replacing every value with `f(&key, val)` (called in ascending key order).
+    /// The arena layout, and so the tree's shape, is kept as-is: no comparisons or rebalancing, `O(n)`.
+    /// Handles issued by `self` remain valid for the returned tree.
+    ///
+    /// An augmentation is specific to the value type, so the returned tree has none.
+    pub fn map_values<W, F: FnMut(&K, V) -> W>(self, f: F) -> SgTree<K, W, N, U, C> {
+        let order = self.in_order_idxs();
 
-        // Nothing to append to!
-        if self.is_empty() {
-            mem::swap(self, other);
-            return Ok(());
+        SgTree {
+            arena: self.arena.map_vals(&order, f),
+            opt_root_idx: self.opt_root_idx,
+            max_idx: self.max_idx,
+            min_idx: self.min_idx,
+            curr_size: self.curr_size,
+            alpha_num: self.alpha_num,
+            alpha_denom: self.alpha_denom,
+            max_size: self.max_size,
+            rebal_cnt: self.rebal_cnt,
+            #[cfg(feature = "rebal_stats")]
+            rebal_stats: self.rebal_stats,
+            #[cfg(feature = "telemetry")]
+            telemetry: self.telemetry,
+            cmp: self.cmp,
+            augment: PhantomData,
         }
+    }
 
-        // Rip elements directly out of other's arena and clear it
-        if (self.len() + other.len() - self.intersect_cnt(other)) <= self.capacity() {
-            for arena_idx in 0..other.arena.len() {
-                if let Some(mut node) = other.arena.remove(arena_idx) {
-                    self.try_insert(node.take_key(), node.take_val())?;
-                }
-            }
-            other.clear();
-        } else {
-            // Preemptive - we haven't mutated `self` or `other`!
-            // Caller can assume unchanged state.
-            return Err(SgError::StackCapacityExceeded);
+    /// Moves all elements from `other` into `self`, leaving `other` empty.
+    /// Both trees are merged in sorted order, then rebuilt once, in `O(n + m)` time.
+    /// If the key ranges don't overlap (and `C` is stateless), `other`'s nodes are spliced in without merging.
+    pub fn append(&mut self, other: &mut SgTree<K, V, N, U, C, A>)
+    where
+        K: Ord,
+    {
+        if self.priv_merge_append(other).is_err() {
+            panic!("Stack-storage capacity exceeded!");
         }
+    }
 
-        Ok(())
+    /// Attempts to move all elements from `other` into `self`, leaving `other` empty.
+    pub fn try_append(&mut self, other: &mut SgTree<K, V, N, U, C, A>) -> Result<(), SgError> {
+        self.priv_merge_append(other)
     }
 
     /// Insert a key-value pair into the tree.
@@ -170,22 +346,192 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     where
         K: Ord,
     {
-        self.priv_balancing_insert::<Idx>(key, val).0
+        self.priv_balancing_insert(key, val).0
     }
 
     /// Insert a key-value pair into the tree.
     /// Returns `Err` if tree's stack capacity is full, else the `Ok` contains:
     /// * `None` if the tree did not have this key present.
     /// * The old value if the tree did have this key present (both the value and key are updated,
-    /// this accommodates types that can be `==` without being identical).
+    ///   this accommodates types that can be `==` without being identical).
     pub fn try_insert(&mut self, key: K, val: V) -> Result<Option<V>, SgError>
+    where
+        K: Ord,
+    {
+        self.insert_within_capacity(key, val).map_err(SgError::from)
+    }
+
+    /// Insert a key-value pair into the tree, never exceeding capacity.
+    /// If the tree is full and the key isn't present, first evicts the pair at the end selected by `policy`.
+    /// Returns the old value (as [`insert`][SgTree::insert]) alongside the evicted pair, if any.
+    /// If the new key would itself be the one evicted, it's returned as the evicted pair and the tree is unchanged.
+    pub fn insert_or_evict(
+        &mut self,
+        key: K,
+        val: V,
+        policy: EvictPolicy,
+    ) -> (Option<V>, Option<(K, V)>)
+    where
+        K: Ord,
+    {
+        if !self.is_full() || self.contains_key::<K>(&key) {
+            return (self.insert(key, val), None);
+        }
+
+        let (opt_end, evict_ord) = match policy {
+            EvictPolicy::First => (self.first_key(), Ordering::Less),
+            EvictPolicy::Last => (self.last_key(), Ordering::Greater),
+        };
+
+        match opt_end {
+            Some(end_key) if self.cmp.compare(&key, end_key) != evict_ord => {
+                let opt_evicted = match policy {
+                    EvictPolicy::First => self.pop_first(),
+                    EvictPolicy::Last => self.pop_last(),
+                };
+                (self.insert(key, val), opt_evicted)
+            }
+            // Zero capacity, or new key lies beyond the evicted end
+            _ => (None, Some((key, val))),
+        }
+    }
+
+    /// Insert a key-value pair into the tree, returning the old value (as [`insert`][SgTree::insert])
+    /// alongside a handle to the stored pair.
+    pub fn insert_with_handle(&mut self, key: K, val: V) -> (Option<V>, NodeHandle)
+    where
+        K: Ord,
+    {
+        let (opt_val, idx) = self.priv_balancing_insert(key, val);
+        (opt_val, self.handle(idx))
+    }
+
+    /// Insert a key-value pair into the tree, returning the old value (as [`try_insert`][SgTree::try_insert])
+    /// alongside a handle to the stored pair.
+    /// Returns `Err` if tree's stack capacity is full.
+    pub fn try_insert_with_handle(
+        &mut self,
+        key: K,
+        val: V,
+    ) -> Result<(Option<V>, NodeHandle), SgError>
+    where
+        K: Ord,
+    {
+        if self.is_full() && !self.contains_key::<K>(&key) {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        Ok(self.insert_with_handle(key, val))
+    }
+
+    /// Get the pair corresponding to the key, first inserting it with the value returned by `f` if absent.
+    /// Takes a single traversal. A present pair is left untouched: `f` isn't called and the given key is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tree is full and the key isn't already present.
+    pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> (&K, &mut V)
+    where
+        K: Ord,
+        F: FnOnce() -> V,
+    {
+        let idx = self.priv_get_or_insert_with(key, f);
+        self.arena[idx].get_mut()
+    }
+
+    /// Append/prepend helper: insert a key-value pair past the first or last pair, given a handle to that pair
+    /// (e.g. the handle returned by the previous call). Returns the old value, if any, and a handle to the stored pair.
+    ///
+    /// If `end` is live and its key equals `key`, the pair is replaced in place.
+    /// If `end` is the last (or first) pair and `key` sorts after (or before) it, the pair is linked below it
+    /// without comparing `key` to any other key. Subtree sizes and scapegoat detection still walk that spine, `O(log n)`.
+    /// Otherwise, this is a regular insert from the root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tree is full and the key isn't already present.
+    pub fn insert_at_end(&mut self, end: NodeHandle, key: K, val: V) -> (Option<V>, NodeHandle)
+    where
+        K: Ord,
+    {
+        match self.priv_insert_at_end(end, key, val) {
+            Ok((opt_val, idx)) => (opt_val, self.handle(idx)),
+            Err(_) => panic!("Stack-storage capacity exceeded!"),
+        }
+    }
+
+    /// Insert a key-value pair into the tree, as per [`insert_at_end`][SgTree::insert_at_end].
+    /// Returns `Err` if tree's stack capacity is full and the key isn't already present.
+    pub fn try_insert_at_end(
+        &mut self,
+        end: NodeHandle,
+        key: K,
+        val: V,
+    ) -> Result<(Option<V>, NodeHandle), SgError>
+    where
+        K: Ord,
+    {
+        self.priv_insert_at_end(end, key, val)
+            .map(|(opt_val, idx)| (opt_val, self.handle(idx)))
+    }
+
+    /// Get a handle to the pair corresponding to key, for later O(1) access.
+    pub fn get_handle<Q>(&self, key: &Q) -> Option<NodeHandle>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let ngh: NodeGetHelper<U> = self.priv_get(None, key);
+        ngh.node_idx().map(|idx| self.handle(idx))
+    }
+
+    /// Get the key-value pair referenced by a handle, or `None` if the handle has been invalidated.
+    pub fn get_by_handle(&self, handle: NodeHandle) -> Option<(&K, &V)> {
+        if self.is_live_handle(handle) {
+            let node = &self.arena[handle.idx];
+            Some((node.key(), node.val()))
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to the value referenced by a handle, or `None` if the handle has been invalidated.
+    pub fn get_mut_by_handle(&mut self, handle: NodeHandle) -> Option<&mut V> {
+        if self.is_live_handle(handle) {
+            let (_, val) = self.arena[handle.idx].get_mut();
+            Some(val)
+        } else {
+            None
+        }
+    }
+
+    /// Remove the key-value pair referenced by a handle, returning it.
+    /// Returns `None` if the handle has been invalidated.
+    /// Invalidates all other handles.
+    pub fn remove_by_handle(&mut self, handle: NodeHandle) -> Option<(K, V)> {
+        if self.is_live_handle(handle) {
+            let opt_kv = self.priv_remove_by_idx(handle.idx);
+            self.rebuild_if_sparse();
+            opt_kv
+        } else {
+            None
+        }
+    }
+
+    // Attempt to insert a key-value pair, handing the pair back if there's no room for it.
+    pub fn insert_within_capacity(
+        &mut self,
+        key: K,
+        val: V,
+    ) -> Result<Option<V>, CapacityError<(K, V)>>
     where
         K: Ord,
     {
         // Replace current slot or safely fill a new one
         match self.contains_key(&key) || (self.capacity() > self.len()) {
-            true => Ok(self.priv_balancing_insert::<Idx>(key, val).0),
-            false => Err(SgError::StackCapacityExceeded),
+            true => Ok(self.priv_balancing_insert(key, val).0),
+            false => Err(CapacityError((key, val))),
         }
     }
 
@@ -205,26 +551,58 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     }
 
     // Attempt conversion from an iterator.
-    /// Will fail if iterator length exceeds `u16::MAX`.
+    /// Will fail if iterator length exceeds the index type's maximum capacity (`65_534` by default),
+    /// or if the tree's capacity, `N`, is exceeded.
+    /// Duplicate keys keep the last occurrence, same as [`FromIterator`](core::iter::FromIterator).
     pub fn try_from_iter<I: ExactSizeIterator + IntoIterator<Item = (K, V)>>(
         iter: I,
-    ) -> Result<Self, SgError> {
-        match iter.len() <= SgTree::<K, V, N>::max_capacity() {
-            true => Ok(SgTree::from_iter(iter)),
+    ) -> Result<Self, SgError>
+    where
+        C: Default,
+    {
+        match iter.len() <= SgTree::<K, V, N, U, C, A>::max_capacity() {
+            true => SgTree::from_iter_with_policy(iter, DupPolicy::KeepLast),
             false => Err(SgError::MaximumCapacityExceeded),
         }
     }
 
     /// Gets an iterator over the entries of the tree, sorted by key.
-    pub fn iter(&self) -> Iter<'_, K, V, N> {
+    pub fn iter(&self) -> Iter<'_, K, V, N, U, C, A> {
         Iter::new(self)
     }
 
     /// Gets a mutable iterator over the entries of the tree, sorted by key.
-    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, N> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V, N, U> {
         IterMut::new(self)
     }
 
+    /// Splits the entries into two mutable iterators, both sorted by key:
+    /// keys less than `key`, and keys greater than or equal to it.
+    /// The iterators borrow disjoint entries, so both can be used at once.
+    pub fn split_iter_mut<Q>(
+        &mut self,
+        key: &Q,
+    ) -> (IterMut<'_, K, V, N, U>, IterMut<'_, K, V, N, U>)
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        // In-order arena layout, so arena index == rank
+        let len = self.len();
+        self.sort_arena();
+
+        let cmp = &self.cmp;
+        let sorted = &mut self.arena.iter_mut().into_slice()[..len];
+        let mid = sorted.partition_point(|opt_node| match opt_node {
+            Some(node) => cmp.compare(node.key().borrow(), key) == Ordering::Less,
+            None => unreachable!("Internal invariant failed: sorted arena has gap!"),
+        });
+
+        let (lo_slice, hi_slice) = sorted.split_at_mut(mid);
+        (IterMut::from_slice(lo_slice), IterMut::from_slice(hi_slice))
+    }
+
     /// Removes a key from the tree, returning the stored key and value if the key was previously in the tree.
     ///
     /// The key may be any borrowed form of the map’s key type, but the ordering
@@ -232,16 +610,12 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
     where
         K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
         match self.priv_remove_by_key(key) {
             Some((key, val)) => {
-                if self.max_size > (2 * self.curr_size) {
-                    if let Some(root_idx) = self.opt_root_idx {
-                        self.rebuild::<Idx>(root_idx);
-                        self.max_size = self.curr_size;
-                    }
-                }
+                self.rebuild_if_sparse();
                 Some((key, val))
             }
             None => None,
@@ -255,7 +629,8 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
     where
         K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
         self.remove_entry(key).map(|(_, v)| v)
     }
@@ -266,16 +641,117 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         F: FnMut(&K, &mut V) -> bool,
         K: Ord,
     {
-        self.priv_drain_filter(|k, v| !f(k, v));
+        let mut sorted_idxs = self.in_order_idxs();
+        let len = sorted_idxs.len();
+
+        // Evaluate every predicate before unlinking anything, a panicking `f` leaves the tree intact.
+        // Rejected indexes are flagged in place, no separate mark array.
+        for idx in sorted_idxs.iter_mut() {
+            let (k, v) = self.arena[*idx].get_mut();
+            if !f(k, v) {
+                *idx = !*idx;
+            }
+        }
+
+        self.priv_remove_flagged(&mut sorted_idxs);
+        self.priv_relink_retained(&sorted_idxs, len);
+    }
+
+    /// Keeps only the `len` smallest pairs, dropping the rest. No-op if `len` is at least the current length.
+    pub fn truncate(&mut self, len: usize)
+    where
+        K: Ord,
+    {
+        self.priv_truncate(len, false);
+    }
+
+    /// Keeps only the `len` largest pairs, dropping the rest. No-op if `len` is at least the current length.
+    pub fn truncate_back(&mut self, len: usize)
+    where
+        K: Ord,
+    {
+        self.priv_truncate(len, true);
     }
 
     /// Splits the collection into two at the given key. Returns everything after the given key, including the key.
     pub fn split_off<Q>(&mut self, key: &Q) -> Self
     where
         K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let cmp = self.cmp.clone();
+        self.priv_drain_filter(|k, _| cmp.compare(k, key) != Ordering::Less)
+    }
+
+    /// Moves everything after the given key, including the key, into `dest`.
+    /// Existing pairs in `dest` are kept, unless overwritten by a moved pair with an equal key.
+    /// Returns `Err` (and leaves both trees unmodified) if `dest` lacks capacity.
+    pub fn split_off_into<Q>(&mut self, key: &Q, dest: &mut Self) -> Result<(), SgError>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let split_idxs = self.range_search(&(Included(key), Unbounded));
+        let new_cnt = split_idxs
+            .iter()
+            .filter(|idx| !dest.contains_key::<K>(self.arena[**idx].key()))
+            .count();
+
+        if (dest.len() + new_cnt) > dest.capacity() {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        for idx in split_idxs {
+            if let Some((k, v)) = self.priv_remove_by_idx(idx) {
+                dest.insert(k, v);
+            }
+        }
+
+        self.rebuild_if_sparse();
+        Ok(())
+    }
+
+    /// Splits the collection into two at the given key, returning everything after the given key (including the key)
+    /// in a new tree of capacity `M`. Returns `Err` (and leaves this tree unmodified) if the split-off pairs won't fit.
+    pub fn try_split_off<Q, const M: usize>(
+        &mut self,
+        key: &Q,
+    ) -> Result<SgTree<K, V, M, U, C>, SgError>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let split_idxs = self.range_search(&(Included(key), Unbounded));
+        if split_idxs.len() > M {
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        let mut dest = SgTree::with_comparator(self.cmp.clone());
+        for idx in split_idxs {
+            if let Some((k, v)) = self.priv_remove_by_idx(idx) {
+                dest.try_insert(k, v)?;
+            }
+        }
+
+        self.rebuild_if_sparse();
+        Ok(dest)
+    }
+
+    /// Splits the collection into two at sorted position `n` (0-indexed), returning the pairs of rank `n` or greater
+    /// in a new tree. If `n` is at least the length, the returned tree is empty.
+    pub fn split_at_rank(&mut self, n: usize) -> Self
+    where
+        K: Ord,
     {
-        self.priv_drain_filter(|k, _| k >= key)
+        // In-order pass: everything after the first `n` pairs visited
+        let mut rank = 0;
+        self.priv_drain_filter::<K, _>(|_, _| {
+            rank += 1;
+            rank > n
+        })
     }
 
     /// Returns the key-value pair corresponding to the given key.
@@ -285,9 +761,10 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
     where
         K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
-        let ngh: NodeGetHelper<Idx> = self.priv_get(None, key);
+        let ngh: NodeGetHelper<U> = self.priv_get(None, key);
         match ngh.node_idx() {
             Some(idx) => {
                 let node = &self.arena[idx];
@@ -304,11 +781,34 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     pub fn get<Q>(&self, key: &Q) -> Option<&V>
     where
         K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
         self.get_key_value(key).map(|(_, v)| v)
     }
 
+    /// Get the value corresponding to each key, in input order.
+    /// Sorted (ascending) runs of keys resume each search from the previous one's path instead of the root.
+    ///
+    /// The key may be any borrowed form of the map’s key type, but the ordering
+    /// on the borrowed form must match the ordering on the key type.
+    pub fn get_batch<'a, Q, I>(&'a self, keys: I) -> impl Iterator<Item = Option<&'a V>> + 'a
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized + 'a,
+        C: Compare<Q>,
+        I: IntoIterator<Item = &'a Q>,
+        I::IntoIter: 'a,
+    {
+        let mut path = StackVec::<[(U, bool); N]>::new();
+        let mut opt_prev = None;
+        keys.into_iter().map(move |key| {
+            let opt_idx = self.priv_get_resume(&mut path, opt_prev, key);
+            opt_prev = Some(key);
+            opt_idx.map(|idx| self.arena[idx].val())
+        })
+    }
+
     /// Get mutable reference corresponding to key.
     ///
     /// The key may be any borrowed form of the map’s key type,
@@ -316,9 +816,10 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
     where
         K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
-        let ngh: NodeGetHelper<Idx> = self.priv_get(None, key);
+        let ngh: NodeGetHelper<U> = self.priv_get(None, key);
         match ngh.node_idx() {
             Some(idx) => {
                 let (_, val) = self.arena[idx].get_mut();
@@ -328,23 +829,290 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         }
     }
 
-    /// Clears the tree, removing all elements.
-    pub fn clear(&mut self) {
-        if !self.is_empty() {
-            let rebal_cnt = self.rebal_cnt;
-            *self = SgTree::new();
-            self.rebal_cnt = rebal_cnt;
-        }
-    }
-
-    /// Returns `true` if the tree contains a value for the given key.
-    ///
-    /// The key may be any borrowed form of the map’s key type, but the
-    /// ordering on the borrowed form must match the ordering on the key type.
-    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    /// Returns mutable references to the values corresponding to each of `M` keys, in order.
+    /// Returns `None` if any key isn't present, or if any two keys are equal.
+    pub fn get_many_mut<Q, const M: usize>(&mut self, keys: [&Q; M]) -> Option<[&mut V; M]>
     where
         K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        // (arena index, key position), sorted by arena index to split the arena into disjoint slots
+        let mut order = [(0, 0); M];
+        for (pos, key) in keys.iter().enumerate() {
+            let ngh: NodeGetHelper<U> = self.priv_get(None, *key);
+            order[pos] = (ngh.node_idx()?, pos);
+        }
+
+        order.sort_unstable();
+        if order.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+            return None;
+        }
+
+        let mut opt_vals = [(); M].map(|_| None);
+        let mut rest = self.arena.iter_mut().into_slice();
+        let mut offset = 0;
+        for (idx, pos) in &order {
+            let (head, tail) = mem::take(&mut rest).split_at_mut(idx - offset + 1);
+            opt_vals[*pos] = head
+                .last_mut()
+                .and_then(|opt_node| opt_node.as_mut())
+                .map(|node| node.get_mut().1);
+            offset = idx + 1;
+            rest = tail;
+        }
+
+        Some(opt_vals.map(|opt_val| match opt_val {
+            Some(val) => val,
+            None => unreachable!("Internal invariant failed: found key has no node!"),
+        }))
+    }
+
+    /// Returns the key-value pair at sorted position `n` (0-indexed), if any.
+    /// `O(log n)` if feature `fast_rebalance` is enabled (cached subtree sizes), `O(n)` otherwise.
+    pub fn get_index(&self, n: usize) -> Option<(&K, &V)> {
+        self.priv_select(n).map(|idx| {
+            let node = &self.arena[idx];
+            (node.key(), node.val())
+        })
+    }
+
+    /// Returns the key and a mutable reference to the value at sorted position `n` (0-indexed), if any.
+    /// `O(log n)` if feature `fast_rebalance` is enabled (cached subtree sizes), `O(n)` otherwise.
+    pub fn get_index_mut(&mut self, n: usize) -> Option<(&K, &mut V)> {
+        match self.priv_select(n) {
+            Some(idx) => Some(self.arena[idx].get_mut()),
+            None => None,
+        }
+    }
+
+    /// Returns a uniformly random key-value pair, or `None` if the tree is empty.
+    /// `O(log n)` if feature `fast_rebalance` is enabled (cached subtree sizes), `O(n)` otherwise.
+    #[cfg(feature = "rand")]
+    pub fn choose<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<(&K, &V)> {
+        self.choose_with(|len| rng.gen_range(0, len))
+    }
+
+    /// Returns the key and a mutable reference to the value of a uniformly random pair, or `None` if the tree is empty.
+    /// `O(log n)` if feature `fast_rebalance` is enabled (cached subtree sizes), `O(n)` otherwise.
+    #[cfg(feature = "rand")]
+    pub fn choose_mut<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<(&K, &mut V)> {
+        self.choose_mut_with(|len| rng.gen_range(0, len))
+    }
+
+    /// Returns the pair at the sorted position picked by `rand_idx`, or `None` if the tree is empty.
+    /// `rand_idx` is called once, with the tree's length, and should return a uniformly random index below it.
+    /// An out-of-range index returns `None`.
+    /// `O(log n)` if feature `fast_rebalance` is enabled (cached subtree sizes), `O(n)` otherwise.
+    pub fn choose_with<F: FnOnce(usize) -> usize>(&self, rand_idx: F) -> Option<(&K, &V)> {
+        match self.len() {
+            0 => None,
+            len => self.get_index(rand_idx(len)),
+        }
+    }
+
+    /// Mutable version of [`choose_with`][SgTree::choose_with].
+    /// `O(log n)` if feature `fast_rebalance` is enabled (cached subtree sizes), `O(n)` otherwise.
+    pub fn choose_mut_with<F: FnOnce(usize) -> usize>(
+        &mut self,
+        rand_idx: F,
+    ) -> Option<(&K, &mut V)> {
+        match self.len() {
+            0 => None,
+            len => self.get_index_mut(rand_idx(len)),
+        }
+    }
+
+    /// Removes and returns the key-value pair at sorted position `n` (0-indexed), if any.
+    /// `O(log n)` if feature `fast_rebalance` is enabled (cached subtree sizes), `O(n)` otherwise.
+    pub fn remove_nth(&mut self, n: usize) -> Option<(K, V)>
+    where
+        K: Ord,
+    {
+        let opt_kv = self
+            .priv_select(n)
+            .and_then(|idx| self.priv_remove_by_idx(idx));
+
+        if opt_kv.is_some() {
+            self.rebuild_if_sparse();
+        }
+
+        opt_kv
+    }
+
+    /// Returns the key-value pair with the greatest key less than or equal to the given key (floor), if any.
+    /// Single root-to-leaf traversal.
+    pub fn get_prev<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.upper_bound_idx(Included(key)).map(|idx| {
+            let node = &self.arena[idx];
+            (node.key(), node.val())
+        })
+    }
+
+    /// Returns the key-value pair with the smallest key greater than or equal to the given key (ceiling), if any.
+    /// Single root-to-leaf traversal.
+    pub fn get_next<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        self.lower_bound_idx(Included(key)).map(|idx| {
+            let node = &self.arena[idx];
+            (node.key(), node.val())
+        })
+    }
+
+    /// Returns the sorted position (0-indexed) of the given key, if present.
+    /// `O(log n)` if feature `fast_rebalance` is enabled (cached subtree sizes), `O(n)` otherwise.
+    ///
+    /// The key may be any borrowed form of the map’s key type,
+    /// but the ordering on the borrowed form must match the ordering on the key type.
+    pub fn rank<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let mut opt_idx = self.opt_root_idx;
+        let mut rank = 0;
+
+        while let Some(idx) = opt_idx {
+            let node = &self.arena[idx];
+            match self.cmp.compare(key, node.key().borrow()) {
+                Ordering::Less => opt_idx = node.left_idx(),
+                Ordering::Equal => return Some(rank + self.get_left_subtree_size(idx)),
+                Ordering::Greater => {
+                    rank += self.get_left_subtree_size(idx) + 1;
+                    opt_idx = node.right_idx();
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns the sorted position (0-indexed) of the first key for which `pred` is `false`, or the length if none is.
+    /// `pred` must partition the keys: `true` for some prefix, `false` for the rest.
+    /// `O(log n)` if feature `fast_rebalance` is enabled (cached subtree sizes), `O(n)` otherwise.
+    pub fn partition_point<F>(&self, mut pred: F) -> usize
+    where
+        F: FnMut(&K) -> bool,
+    {
+        let mut opt_idx = self.opt_root_idx;
+        let mut rank = 0;
+
+        while let Some(idx) = opt_idx {
+            let node = &self.arena[idx];
+            match pred(node.key()) {
+                true => {
+                    rank += self.get_left_subtree_size(idx) + 1;
+                    opt_idx = node.right_idx();
+                }
+                false => opt_idx = node.left_idx(),
+            }
+        }
+
+        rank
+    }
+
+    /// Returns the key-value pair with the first key for which `pred` is `false`, if any.
+    /// `pred` must partition the keys: `true` for some prefix, `false` for the rest.
+    /// Single root-to-leaf traversal.
+    pub fn get_partition_point<F>(&self, pred: F) -> Option<(&K, &V)>
+    where
+        F: FnMut(&K) -> bool,
+    {
+        self.partition_point_idx(pred).map(|idx| {
+            let node = &self.arena[idx];
+            (node.key(), node.val())
+        })
+    }
+
+    /// Exchanges the values of two existing keys, without moving either node.
+    /// Returns `false` (and leaves the tree unmodified) if either key isn't present.
+    pub fn swap_values<Q>(&mut self, key_a: &Q, key_b: &Q) -> bool
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let ngh_a: NodeGetHelper<U> = self.priv_get(None, key_a);
+        let ngh_b: NodeGetHelper<U> = self.priv_get(None, key_b);
+        match (ngh_a.node_idx(), ngh_b.node_idx()) {
+            (Some(idx_a), Some(idx_b)) if idx_a == idx_b => true,
+            (Some(idx_a), Some(idx_b)) => {
+                let (node_a, node_b) = self.arena.pair_mut(idx_a, idx_b);
+                mem::swap(node_a.get_mut().1, node_b.get_mut().1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves the value at `old_key` to `new_key`.
+    /// On `Err`, the tree is left unmodified.
+    pub fn rename_key<Q>(&mut self, old_key: &Q, new_key: K) -> Result<(), SgError>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let ngh: NodeGetHelper<U> = self.priv_get(None, old_key);
+        let old_idx = ngh.node_idx().ok_or(SgError::KeyNotFound)?;
+
+        // Equivalent key, ordering unchanged so update in-place
+        if self.cmp.compare(new_key.borrow(), old_key) == Ordering::Equal {
+            self.arena[old_idx].set_key(new_key);
+            return Ok(());
+        }
+
+        if self.contains_key::<K>(&new_key) {
+            return Err(SgError::KeyAlreadyExists);
+        }
+
+        // Removal frees a slot, so re-insertion can't exceed capacity
+        match self.remove(old_key) {
+            Some(val) => match self.try_insert(new_key, val) {
+                Ok(_) => Ok(()),
+                Err(_) => unreachable!("Internal invariant failed: rename exceeded capacity!"),
+            },
+            None => unreachable!(),
+        }
+    }
+
+    /// Clears the tree, removing all elements.
+    pub fn clear(&mut self) {
+        if !self.is_empty() {
+            let mut cleared = SgTree::with_comparator(self.cmp.clone());
+            cleared.arena.set_epoch(self.arena.epoch().wrapping_add(1));
+            cleared.rebal_cnt = self.rebal_cnt;
+            #[cfg(feature = "rebal_stats")]
+            {
+                cleared.rebal_stats = self.rebal_stats;
+            }
+            #[cfg(feature = "telemetry")]
+            {
+                cleared.telemetry = self.telemetry;
+            }
+            *self = cleared;
+        }
+    }
+
+    /// Returns `true` if the tree contains a value for the given key.
+    ///
+    /// The key may be any borrowed form of the map’s key type, but the
+    /// ordering on the borrowed form must match the ordering on the key type.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
         self.get(key).is_some()
     }
@@ -395,53 +1163,659 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     /// The key in this pair is the maximum key in the tree.
     pub fn last_key_value(&self) -> Option<(&K, &V)>
     where
-        K: Ord,
+        K: Ord,
+    {
+        if !self.is_empty() {
+            let node = &self.arena[self.max_idx];
+            Some((node.key(), node.val()))
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the last/maximum key in the tree, if any.
+    pub fn last_key(&self) -> Option<&K>
+    where
+        K: Ord,
+    {
+        self.last_key_value().map(|(k, _)| k)
+    }
+
+    /// Removes and returns the last element in the tree.
+    /// The key of this element is the maximum key that was in the tree.
+    pub fn pop_last(&mut self) -> Option<(K, V)>
+    where
+        K: Ord,
+    {
+        self.priv_remove_by_idx(self.max_idx)
+    }
+
+    /// Attempt conversion from an iterator, handling duplicate keys as specified by `policy`.
+    /// Returns `Err` if the tree's capacity is exceeded, or on a duplicate key under [`DupPolicy::Error`].
+    pub fn from_iter_with_policy<I: IntoIterator<Item = (K, V)>>(
+        iter: I,
+        policy: DupPolicy,
+    ) -> Result<Self, SgError>
+    where
+        C: Default,
+    {
+        let mut sgt = SgTree::new();
+
+        for (k, v) in iter {
+            match (policy, sgt.contains_key(&k)) {
+                (_, false) | (DupPolicy::KeepLast, true) => {
+                    sgt.try_insert(k, v)?;
+                }
+                (DupPolicy::KeepFirst, true) => continue,
+                (DupPolicy::Error, true) => return Err(SgError::KeyAlreadyExists),
+            }
+        }
+
+        Ok(sgt)
+    }
+
+    /// Gets an iterator over the entries of the tree in level order (breadth-first), with each node's depth.
+    pub fn iter_levels(&self) -> LevelIter<'_, K, V, N, U, C, A> {
+        LevelIter::new(self)
+    }
+
+    /// Returns the number of elements in the tree.
+    pub fn len(&self) -> usize {
+        self.curr_size
+    }
+
+    /// Get the number of times this tree rebalanced itself (for testing and/or performance engineering).
+    /// This count will wrap if `usize::MAX` is exceeded.
+    pub fn rebal_cnt(&self) -> usize {
+        self.rebal_cnt
+    }
+
+    /// Get rebalance cost statistics, accumulated since creation or the last [`reset_rebal_stats`][SgTree::reset_rebal_stats].
+    #[cfg(feature = "rebal_stats")]
+    pub fn rebal_stats(&self) -> RebalStats {
+        self.rebal_stats
+    }
+
+    /// Reset rebalance cost statistics. Doesn't impact [`rebal_cnt`][SgTree::rebal_cnt].
+    #[cfg(feature = "rebal_stats")]
+    pub fn reset_rebal_stats(&mut self) {
+        self.rebal_stats = RebalStats::default();
+    }
+
+    /// Get a summary of shape, rebalancing history, and arena occupancy. Runs in `O(n)` time (to measure height).
+    pub fn stats(&self) -> TreeStats {
+        #[cfg(feature = "rebal_stats")]
+        let (nodes_moved, max_rebuild_size) = (
+            self.rebal_stats.nodes_moved,
+            self.rebal_stats.max_rebuild_size,
+        );
+        #[cfg(not(feature = "rebal_stats"))]
+        let (nodes_moved, max_rebuild_size) = (0, 0);
+
+        TreeStats {
+            len: self.len(),
+            height: self.height(),
+            rebal_cnt: self.rebal_cnt,
+            nodes_moved,
+            max_rebuild_size,
+            arena_slots: self.arena.slot_cnt(),
+            capacity: self.capacity(),
+        }
+    }
+
+    /// Get resource usage high-water marks, accumulated since creation or the last [`reset_telemetry`][SgTree::reset_telemetry].
+    #[cfg(feature = "telemetry")]
+    pub fn telemetry(&self) -> Telemetry {
+        self.telemetry
+    }
+
+    /// Reset resource usage high-water marks.
+    #[cfg(feature = "telemetry")]
+    pub fn reset_telemetry(&mut self) {
+        self.telemetry = Telemetry::default();
+    }
+
+    /// Get a breakdown of storage usage, in bytes.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let total_bytes = mem::size_of::<Self>();
+        let node_bytes = self.node_size();
+        let used_bytes = self.len() * node_bytes;
+        let free_slot_bytes = (self.arena.slot_cnt() - self.len()) * self.arena.slot_size();
+        let spare_bytes = (self.capacity() - self.arena.slot_cnt()) * self.arena.slot_size();
+
+        MemoryUsage {
+            total_bytes,
+            node_bytes,
+            used_bytes,
+            free_slot_bytes,
+            spare_bytes,
+            overhead_bytes: total_bytes - used_bytes - free_slot_bytes - spare_bytes,
+        }
+    }
+
+    /// Feed the length, then each entry in key order, to `hasher` and return the result.
+    /// Independent of arena layout and rebalance history, unlike a hash over the raw storage.
+    pub fn digest<H: Hasher>(&self, mut hasher: H) -> u64
+    where
+        K: Hash,
+        V: Hash,
+    {
+        hasher.write_u64(self.len() as u64);
+        for (k, v) in self.iter() {
+            k.hash(&mut hasher);
+            v.hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    /// Validate internal structure, see [`InvariantReport`] for the checks performed.
+    /// Runs in `O(n)` time, never panics, even on a corrupted tree.
+    pub fn check_invariants(&self) -> InvariantReport {
+        let mut report = InvariantReport {
+            ordered: true,
+            acyclic: true,
+            arena_consistent: true,
+            len_correct: true,
+            min_max_correct: true,
+            subtree_sizes_correct: true,
+            height_bounded: true,
+            ..Default::default()
+        };
+
+        let occupied_cnt = self
+            .arena
+            .iter()
+            .filter(|opt_node| opt_node.is_some())
+            .count();
+
+        // Dangling links are reported, then treated as absent
+        let mut has_dangling = false;
+        let mut checked_link = |opt_child_idx: Option<usize>| match opt_child_idx {
+            Some(child_idx) if !self.arena.is_occupied(child_idx) => {
+                has_dangling = true;
+                None
+            }
+            opt_child_idx => opt_child_idx,
+        };
+
+        // In-order walk, keeping only pending ancestors (at most one per level) and their depths.
+        // Keys must strictly increase in visit order. A path deeper than `N`, or more visits than stored nodes,
+        // means some node was reached twice.
+        let mut path = StackVec::<[(U, U); N]>::default();
+        let (mut opt_min_idx, mut opt_prev_idx) = (None, None);
+        let mut opt_curr_idx = checked_link(self.opt_root_idx);
+        let (mut curr_depth, mut visit_cnt) = (1, 0);
+
+        'walk: loop {
+            while let Some(curr_idx) = opt_curr_idx {
+                if curr_depth > N {
+                    report.acyclic = false;
+                    break 'walk;
+                }
+
+                path.push((U::checked_from(curr_idx), U::checked_from(curr_depth)));
+                report.height = report.height.max(curr_depth);
+                opt_curr_idx = checked_link(self.arena[curr_idx].left_idx());
+                curr_depth += 1;
+            }
+
+            let idx = match path.pop() {
+                Some((idx, depth)) => {
+                    curr_depth = depth.usize() + 1;
+                    idx.usize()
+                }
+                None => break,
+            };
+
+            visit_cnt += 1;
+            if visit_cnt > occupied_cnt {
+                report.acyclic = false;
+                break;
+            }
+
+            let node = &self.arena[idx];
+            match opt_prev_idx {
+                Some(prev_idx) => {
+                    if self.cmp.compare(self.arena[prev_idx].key(), node.key()) != Ordering::Less {
+                        report.ordered = false;
+                    }
+                }
+                None => opt_min_idx = Some(idx),
+            }
+
+            #[cfg(feature = "fast_rebalance")]
+            {
+                let child_size = |opt_child_idx: Option<usize>| match opt_child_idx {
+                    Some(child_idx) if self.arena.is_occupied(child_idx) => {
+                        self.arena[child_idx].subtree_size()
+                    }
+                    _ => 0,
+                };
+
+                if node.subtree_size()
+                    != 1 + child_size(node.left_idx()) + child_size(node.right_idx())
+                {
+                    report.subtree_sizes_correct = false;
+                }
+            }
+
+            opt_prev_idx = Some(idx);
+            opt_curr_idx = checked_link(node.right_idx());
+        }
+
+        report.node_cnt = visit_cnt.min(occupied_cnt);
+        let opt_max_idx = opt_prev_idx;
+
+        // Root's cached size covers the whole tree, local checks above cover the rest
+        #[cfg(feature = "fast_rebalance")]
+        if let Some(root_idx) = self.opt_root_idx {
+            if self.arena.is_occupied(root_idx)
+                && (self.arena[root_idx].subtree_size() != report.node_cnt)
+            {
+                report.subtree_sizes_correct = false;
+            }
+        }
+
+        // Dangling or unreachable nodes
+        if has_dangling || (occupied_cnt != report.node_cnt) {
+            report.arena_consistent = false;
+        }
+
+        report.len_correct = self.curr_size == report.node_cnt;
+
+        if report.node_cnt > 0 {
+            report.min_max_correct =
+                (opt_min_idx == Some(self.min_idx)) && (opt_max_idx == Some(self.max_idx));
+        }
+
+        report.max_height = self.alpha_balance_depth(self.max_size).saturating_add(1);
+        report.height_bounded = report.height <= report.max_height;
+
+        report
+    }
+
+    /// Size, in bytes, of the image [`write_raw_snapshot`][SgTree::write_raw_snapshot] produces.
+    #[cfg(feature = "bytemuck")]
+    pub fn raw_snapshot_len(&self) -> usize
+    where
+        K: Pod,
+        V: Pod,
+    {
+        let slot_len = (2 * mem::size_of::<u64>()) + mem::size_of::<K>() + mem::size_of::<V>();
+        (RAW_HEADER_WORDS * mem::size_of::<u64>()) + (self.arena.slot_cnt() * slot_len)
+    }
+
+    /// Write the arena and tree metadata to `dst` as a plain byte image, returning the number of bytes written.
+    /// Returns `Err(SgError::BufferTooSmall)`, and writes nothing, if `dst` is shorter than
+    /// [`raw_snapshot_len`][SgTree::raw_snapshot_len].
+    ///
+    /// The image is native-endian, only meant to be read back on the same target.
+    #[cfg(feature = "bytemuck")]
+    pub fn write_raw_snapshot(&self, dst: &mut [u8]) -> Result<usize, SgError>
+    where
+        K: Pod,
+        V: Pod,
+    {
+        let len = self.raw_snapshot_len();
+        if dst.len() < len {
+            return Err(SgError::BufferTooSmall);
+        }
+
+        let raw_idx = |opt_idx: Option<usize>| opt_idx.map_or(RAW_NONE, |idx| idx as u64);
+        let header: [u64; RAW_HEADER_WORDS] = [
+            RAW_MAGIC,
+            mem::size_of::<K>() as u64,
+            mem::size_of::<V>() as u64,
+            u64::from(self.alpha_num),
+            u64::from(self.alpha_denom),
+            self.max_size as u64,
+            self.curr_size as u64,
+            raw_idx(self.opt_root_idx),
+            self.min_idx as u64,
+            self.max_idx as u64,
+            self.arena.slot_cnt() as u64,
+        ];
+
+        let mut pos = 0;
+        let mut put = |bytes: &[u8]| {
+            dst[pos..(pos + bytes.len())].copy_from_slice(bytes);
+            pos += bytes.len();
+        };
+
+        put(bytes_of(&header));
+        for opt_node in self.arena.iter() {
+            match opt_node {
+                Some(node) => {
+                    put(bytes_of(&[
+                        raw_idx(node.left_idx()),
+                        raw_idx(node.right_idx()),
+                    ]));
+                    put(bytes_of(node.key()));
+                    put(bytes_of(node.val()));
+                }
+                None => {
+                    put(bytes_of(&[RAW_VACANT, RAW_VACANT]));
+                    put(bytes_of(&K::zeroed()));
+                    put(bytes_of(&V::zeroed()));
+                }
+            }
+        }
+
+        Ok(len)
+    }
+
+    /// Reconstitute a tree from an image written by [`write_raw_snapshot`][SgTree::write_raw_snapshot].
+    /// Slots are copied as-is: no key-by-key insertion and no rebalancing, the tree's shape is unchanged.
+    /// Trailing bytes past the image are ignored, and `src` needn't be aligned.
+    ///
+    /// The result is validated (see [`check_invariants`][SgTree::check_invariants]) before being returned.
+    /// Returns `Err(SgError::InvalidSnapshot)` if the image is truncated or corrupt, was written for different
+    /// key/value sizes, has more slots than `N`, or doesn't describe a valid tree under `C`.
+    #[cfg(feature = "bytemuck")]
+    pub fn from_raw_snapshot(src: &[u8]) -> Result<Self, SgError>
+    where
+        K: Pod,
+        V: Pod,
+        C: Default,
+    {
+        const WORD_LEN: usize = mem::size_of::<u64>();
+        let (key_len, val_len) = (mem::size_of::<K>(), mem::size_of::<V>());
+        let header_len = RAW_HEADER_WORDS * WORD_LEN;
+
+        let header: [u64; RAW_HEADER_WORDS] =
+            pod_read_unaligned(src.get(..header_len).ok_or(SgError::InvalidSnapshot)?);
+        let [magic, raw_key_len, raw_val_len, alpha_num, alpha_denom, max_size, len, root_idx, min_idx, max_idx, slot_cnt] =
+            header;
+
+        let to_usize = |word: u64| usize::try_from(word).map_err(|_| SgError::InvalidSnapshot);
+        let slot_cnt = to_usize(slot_cnt)?;
+        if (magic != RAW_MAGIC)
+            || (raw_key_len != key_len as u64)
+            || (raw_val_len != val_len as u64)
+            || (slot_cnt > N)
+        {
+            return Err(SgError::InvalidSnapshot);
+        }
+
+        let slot_len = (2 * WORD_LEN) + key_len + val_len;
+        let slots = src
+            .get(header_len..(header_len + (slot_cnt * slot_len)))
+            .ok_or(SgError::InvalidSnapshot)?;
+
+        let to_idx = |word: u64| match word {
+            RAW_NONE => Ok(None),
+            idx if idx < slot_cnt as u64 => Ok(Some(idx as usize)),
+            _ => Err(SgError::InvalidSnapshot),
+        };
+
+        let mut sgt = Self::new();
+        sgt.set_rebal_ratio(
+            u32::try_from(alpha_num).map_err(|_| SgError::InvalidSnapshot)?,
+            u32::try_from(alpha_denom).map_err(|_| SgError::InvalidSnapshot)?,
+        )
+        .map_err(|_| SgError::InvalidSnapshot)?;
+
+        for slot in slots.chunks_exact(slot_len) {
+            let (links, pair) = slot.split_at(2 * WORD_LEN);
+            let (key, val) = pair.split_at(key_len);
+            let [left_idx, right_idx]: [u64; 2] = pod_read_unaligned(links);
+
+            if (left_idx == RAW_VACANT) && (right_idx == RAW_VACANT) {
+                sgt.arena.push_slot(None);
+            } else {
+                let mut node = Node::new(pod_read_unaligned(key), pod_read_unaligned(val));
+                node.set_left_idx(to_idx(left_idx)?);
+                node.set_right_idx(to_idx(right_idx)?);
+                sgt.arena.push_slot(Some(node));
+            }
+        }
+
+        sgt.opt_root_idx = to_idx(root_idx)?;
+        sgt.curr_size = to_usize(len)?;
+        sgt.max_size = to_usize(max_size)?;
+        if sgt.opt_root_idx.is_some() {
+            sgt.min_idx = to_idx(min_idx)?.ok_or(SgError::InvalidSnapshot)?;
+            sgt.max_idx = to_idx(max_idx)?.ok_or(SgError::InvalidSnapshot)?;
+        }
+
+        // Subtree sizes aren't part of the image, they're recomputed once the structure checks out
+        let report = InvariantReport {
+            subtree_sizes_correct: true,
+            ..sgt.check_invariants()
+        };
+        if !report.is_valid() || (sgt.max_size < sgt.curr_size) {
+            return Err(SgError::InvalidSnapshot);
+        }
+
+        #[cfg(feature = "fast_rebalance")]
+        sgt.refresh_subtree_sizes();
+
+        #[cfg(feature = "telemetry")]
+        sgt.telemetry.record_len(sgt.curr_size);
+        Ok(sgt)
+    }
+
+    /// Write the node/link structure in [Graphviz](https://graphviz.org/) DOT format, labelling nodes with their keys.
+    pub fn to_dot<W: fmt::Write>(&self, w: &mut W) -> fmt::Result
+    where
+        K: Debug,
+    {
+        self.to_dot_with(w, DotOptions::default())
+    }
+
+    /// Write the node/link structure in [Graphviz](https://graphviz.org/) DOT format, with optional node annotations.
+    /// An only child gets an invisible sibling, so left and right links stay distinguishable when rendered.
+    pub fn to_dot_with<W: fmt::Write>(&self, w: &mut W, opts: DotOptions) -> fmt::Result
+    where
+        K: Debug,
+    {
+        writeln!(w, "digraph {{")?;
+        writeln!(w, "    node [shape=box];")?;
+
+        let mut worklist = StackVec::<[usize; N]>::default();
+        if let Some(root_idx) = self.opt_root_idx {
+            worklist.push(root_idx);
+        }
+
+        while let Some(idx) = worklist.pop() {
+            let node = &self.arena[idx];
+
+            write!(w, "    n{} [label=\"", idx)?;
+            write!(DotEscape(&mut *w), "{:?}", node.key())?;
+            if opts.arena_idxs {
+                write!(w, "\\nidx: {}", idx)?;
+            }
+            if opts.subtree_sizes {
+                write!(w, "\\nsize: {}", self.get_subtree_size(idx))?;
+            }
+            writeln!(w, "\"];")?;
+
+            let is_leaf = node.left_idx().is_none() && node.right_idx().is_none();
+            for (side, opt_child_idx) in [("l", node.left_idx()), ("r", node.right_idx())] {
+                match opt_child_idx {
+                    Some(child_idx) => {
+                        writeln!(w, "    n{} -> n{};", idx, child_idx)?;
+                        worklist.push(child_idx);
+                    }
+                    None if !is_leaf => {
+                        writeln!(w, "    n{}{} [style=invis];", idx, side)?;
+                        writeln!(w, "    n{} -> n{}{} [style=invis];", idx, idx, side)?;
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        writeln!(w, "}}")
+    }
+
+    /// Perform up to `max_work` units of optional housekeeping, returning `true` if more may remain.
+    /// A unit of work is one node visited, checks included. Tasks, in order, each check if they're needed
+    /// (up to `self.len()` units, stopping early once the answer is known) and, if so, cost `self.len()` more units.
+    /// A task is only started if it fits in what's left of the budget, a check that runs out of budget counts as remaining work:
+    ///
+    /// 1. Rebuild the whole tree to minimal height (faster lookups).
+    /// 2. Compact the arena into in-order layout (faster iteration).
+    pub fn maintain(&mut self, max_work: usize) -> bool {
+        let len = self.len();
+        let mut budget = max_work;
+
+        if let Some(root_idx) = self.opt_root_idx {
+            match self.priv_height_exceeds(Self::min_height(len), &mut budget) {
+                None => return true,
+                Some(true) if len > budget => return true,
+                Some(true) => {
+                    self.rebuild(root_idx);
+                    self.max_size = len;
+                    budget -= len;
+                }
+                Some(false) => {}
+            }
+
+            match self.priv_is_arena_sorted(&mut budget) {
+                None => return true,
+                Some(false) if len > budget => return true,
+                Some(false) => self.compact(),
+                Some(true) => {}
+            }
+        }
+
+        false
+    }
+
+    /// Retains only the elements specified by the predicate, evaluating the predicate in parallel.
+    /// All removals are merged into a single rebuild.
+    #[cfg(feature = "rayon")]
+    pub fn par_retain<F>(&mut self, f: F)
+    where
+        K: Send + Sync,
+        V: Send + Sync,
+        F: Fn(&K, &mut V) -> bool + Send + Sync,
     {
-        if !self.is_empty() {
-            let node = &self.arena[self.max_idx];
-            Some((node.key(), node.val()))
-        } else {
-            None
+        let len = self.len();
+        if len == 0 {
+            return;
         }
+
+        // In-order arena layout, so arena index == rank
+        self.sort_arena();
+
+        // Rejected indexes are flagged in place, no separate mark array
+        let mut sorted_idxs: StackVec<[usize; N]> = (0..len).collect();
+        self.arena.iter_mut().into_slice()[..len]
+            .par_iter_mut()
+            .zip(sorted_idxs.par_iter_mut())
+            .for_each(|(opt_node, idx)| {
+                if let Some(node) = opt_node {
+                    let (k, v) = node.get_mut();
+                    if !f(k, v) {
+                        *idx = !*idx;
+                    }
+                }
+            });
+
+        self.priv_remove_flagged(&mut sorted_idxs);
+        self.priv_relink_retained(&sorted_idxs, len);
     }
 
-    /// Returns a reference to the last/maximum key in the tree, if any.
-    pub fn last_key(&self) -> Option<&K>
+    /// Calls a closure on each element within a range, in parallel.
+    /// The range is located by rank, so only in-range elements are visited.
+    #[cfg(feature = "rayon")]
+    pub fn par_range_for_each<T, R, F>(&mut self, range: R, f: F)
     where
-        K: Ord,
+        T: ?Sized,
+        C: Compare<T>,
+        K: Borrow<T> + Send + Sync,
+        V: Send,
+        R: RangeBounds<T>,
+        F: Fn(&K, &mut V) + Send + Sync,
     {
-        self.last_key_value().map(|(k, _)| k)
+        self.assert_valid_range(&range);
+
+        // In-order arena layout, so arena index == rank
+        let len = self.len();
+        self.sort_arena();
+
+        let cmp = self.cmp.clone();
+        let sorted = &mut self.arena.iter_mut().into_slice()[..len];
+        fn key<K, V, I: ArenaIdx>(opt_node: &Option<Node<K, V, I>>) -> &K {
+            match opt_node {
+                Some(node) => node.key(),
+                None => unreachable!("Internal invariant failed: sorted arena has gap!"),
+            }
+        }
+
+        let start =
+            sorted.partition_point(|n| below_start(&cmp, range.start_bound(), key(n).borrow()));
+        let end = sorted.partition_point(|n| !above_end(&cmp, range.end_bound(), key(n).borrow()));
+
+        sorted[start..end.max(start)]
+            .par_iter_mut()
+            .filter_map(|opt_node| opt_node.as_mut())
+            .for_each(|node| {
+                let (k, v) = node.get_mut();
+                f(k, v);
+            });
     }
 
-    /// Removes and returns the last element in the tree.
-    /// The key of this element is the maximum key that was in the tree.
-    pub fn pop_last(&mut self) -> Option<(K, V)>
+    /// Gets a parallel iterator over the entries of the tree, in no particular order.
+    /// Elements are partitioned by arena slot, so no sort is needed.
+    #[cfg(feature = "rayon")]
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = (&K, &V)>
     where
-        K: Ord,
+        K: Sync,
+        V: Sync,
     {
-        self.priv_remove_by_idx(self.max_idx)
+        self.arena
+            .iter()
+            .as_slice()
+            .par_iter()
+            .filter_map(|opt_node| opt_node.as_ref().map(|node| (node.key(), node.val())))
     }
 
-    /// Returns the number of elements in the tree.
-    pub fn len(&self) -> usize {
-        self.curr_size
-    }
+    /// Gets a parallel mutable iterator over the entries of the tree, sorted by key.
+    /// Elements are partitioned by rank, so work splits evenly across threads.
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_mut(&mut self) -> impl IndexedParallelIterator<Item = (&K, &mut V)>
+    where
+        K: Send + Sync,
+        V: Send,
+    {
+        // In-order arena layout, so arena index == rank
+        let len = self.len();
+        self.sort_arena();
 
-    /// Get the number of times this tree rebalanced itself (for testing and/or performance engineering).
-    /// This count will wrap if `usize::MAX` is exceeded.
-    pub fn rebal_cnt(&self) -> usize {
-        self.rebal_cnt
+        self.arena.iter_mut().into_slice()[..len]
+            .par_iter_mut()
+            .map(|opt_node| match opt_node {
+                Some(node) => node.get_mut(),
+                None => unreachable!("Internal invariant failed: sorted arena has gap!"),
+            })
     }
 
     // Crate-internal API ----------------------------------------------------------------------------------------------
 
+    // Unlink every node at once, returning their arena indexes in key order.
+    // Nodes stay in the arena for the caller to take, the tree itself is empty from here on.
+    // Arena storage, stats, and settings are kept (unlike `clear`, which starts over).
+    pub(crate) fn priv_detach_all(&mut self) -> StackVec<[usize; N]> {
+        let sorted_idxs = self.in_order_idxs();
+        self.opt_root_idx = None;
+        self.curr_size = 0;
+        self.max_size = 0;
+        self.min_idx = 0;
+        self.max_idx = 0;
+        sorted_idxs
+    }
+
     // Remove a node by index.
     // A wrapper for by-key removal, traversal is still required to determine node parent.
     #[cfg(not(feature = "fast_rebalance"))]
     pub(crate) fn priv_remove_by_idx(&mut self, idx: usize) -> Option<(K, V)> {
         if self.arena.is_occupied(idx) {
             let node = &self.arena[idx];
-            let ngh: NodeGetHelper<Idx> = self.priv_get(None, node.key());
+            let ngh: NodeGetHelper<U> = self.priv_get(None, node.key());
             debug_assert!(
                 ngh.node_idx().unwrap() == idx,
                 "By-key retrieval index doesn't match arena storage index!"
@@ -458,7 +1832,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     pub(crate) fn priv_remove_by_idx(&mut self, idx: usize) -> Option<(K, V)> {
         if self.arena.is_occupied(idx) {
             let node = &self.arena[idx];
-            let mut path = Arena::<K, V, Idx, N>::new_idx_vec();
+            let mut path = Arena::<K, V, U, N>::new_idx_vec();
             let ngh = self.priv_get(Some(&mut path), node.key());
             debug_assert!(
                 ngh.node_idx().unwrap() == idx,
@@ -471,24 +1845,24 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     }
 
     // Flatten subtree into array of node indexes sorted by node key
-    pub(crate) fn flatten_subtree_to_sorted_idxs<U: SmallUnsigned + Default + Copy>(
+    pub(crate) fn flatten_subtree_to_sorted_idxs<I: SmallUnsigned + Default + Copy>(
         &self,
         idx: usize,
-    ) -> ArrayVec<[U; N]> {
-        let mut subtree_worklist = array_vec![[U; N] => U::checked_from(idx)];
-        let mut subtree_flattened = array_vec![[U; N] => U::checked_from(idx)];
+    ) -> StackVec<[I; N]> {
+        let mut subtree_worklist = StackVec::<[I; N]>::from_iter(iter::once(I::checked_from(idx)));
+        let mut subtree_flattened = StackVec::<[I; N]>::from_iter(iter::once(I::checked_from(idx)));
 
         while let Some(idx) = subtree_worklist.pop() {
             let node = &self.arena[idx.usize()];
 
             if let Some(left_idx) = node.left_idx() {
-                let left = U::checked_from(left_idx);
+                let left = I::checked_from(left_idx);
                 subtree_worklist.push(left);
                 subtree_flattened.push(left);
             }
 
             if let Some(right_idx) = node.right_idx() {
-                let right = U::checked_from(right_idx);
+                let right = I::checked_from(right_idx);
                 subtree_worklist.push(right);
                 subtree_flattened.push(right);
             }
@@ -496,8 +1870,10 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
 
         // Sort by key
         // Faster than sort_by() but may not preserve order of equal elements - OK b/c tree won't have equal nodes
-        subtree_flattened
-            .sort_unstable_by(|a, b| self.arena[a.usize()].key().cmp(self.arena[b.usize()].key()));
+        subtree_flattened.sort_unstable_by(|a, b| {
+            self.cmp
+                .compare(self.arena[a.usize()].key(), self.arena[b.usize()].key())
+        });
 
         subtree_flattened
     }
@@ -508,12 +1884,16 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
             let mut sort_metadata = self
                 .arena
                 .iter()
-                .filter(|n| n.is_some())
-                .map(|n| n.as_ref().unwrap())
+                .filter_map(|n| n.as_ref())
                 .map(|n| self.priv_get(None, n.key()))
-                .collect::<ArrayVec<[NodeGetHelper<usize>; N]>>();
+                .collect::<StackVec<[NodeGetHelper<usize>; N]>>();
 
-            sort_metadata.sort_unstable_by_key(|ngh| self.arena[ngh.node_idx().unwrap()].key());
+            sort_metadata.sort_unstable_by(|a, b| {
+                self.cmp.compare(
+                    self.arena[a.node_idx().unwrap()].key(),
+                    self.arena[b.node_idx().unwrap()].key(),
+                )
+            });
             let sorted_root_idx = self.arena.sort(root_idx, sort_metadata);
 
             self.opt_root_idx = Some(sorted_root_idx);
@@ -522,77 +1902,477 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         }
     }
 
+    // Append the top `height` levels of the subtree at `ngh` in van Emde Boas order:
+    // the top half of the levels, then each subtree hanging below it (left to right), each laid out the same way.
+    // Iterative, pending subtrees are disjoint and non-empty so neither work stack exceeds `N` entries.
+    fn veb_order(
+        &self,
+        ngh: NodeGetHelper<usize>,
+        height: usize,
+        sort_metadata: &mut StackVec<[NodeGetHelper<usize>; N]>,
+    ) {
+        let mut worklist = StackVec::<[(NodeGetHelper<usize>, usize); N]>::new();
+        let mut descent = StackVec::<[(NodeGetHelper<usize>, usize); N]>::new();
+        worklist.push((ngh, height));
+
+        while let Some((ngh, height)) = worklist.pop() {
+            match (height, ngh.node_idx()) {
+                (0, _) | (_, None) => {}
+                (1, _) => sort_metadata.push(ngh),
+                (_, Some(_)) => {
+                    // Bottom subtrees, rooted `top_height` levels below `ngh`, found left to right.
+                    // Reversed so the leftmost is popped first, after the top subtree pushed last.
+                    let top_height = height - (height / 2);
+                    let bottom_start = worklist.len();
+                    descent.push((ngh, 0));
+                    while let Some((curr_ngh, depth)) = descent.pop() {
+                        if depth == top_height {
+                            worklist.push((curr_ngh, height / 2));
+                        } else if let Some(curr_idx) = curr_ngh.node_idx() {
+                            let node = &self.arena[curr_idx];
+                            for (opt_child_idx, is_right) in
+                                [(node.right_idx(), true), (node.left_idx(), false)]
+                            {
+                                if let Some(child_idx) = opt_child_idx {
+                                    let child_ngh = NodeGetHelper::new(
+                                        Some(child_idx),
+                                        Some(curr_idx),
+                                        is_right,
+                                    );
+                                    descent.push((child_ngh, depth + 1));
+                                }
+                            }
+                        }
+                    }
+                    worklist[bottom_start..].reverse();
+
+                    worklist.push((ngh, top_height));
+                }
+            }
+        }
+    }
+
     /// Total common elements between two trees
-    pub(crate) fn intersect_cnt(&self, other: &SgTree<K, V, N>) -> usize {
-        self.iter().filter(|(k, _)| other.contains_key(k)).count()
+    #[cfg(test)]
+    pub(crate) fn intersect_cnt(&self, other: &SgTree<K, V, N, U, C, A>) -> usize {
+        self.sorted_intersect_cnt(
+            other,
+            &self.in_order_idxs(),
+            &other.in_order_idxs_by(&self.cmp),
+        )
+    }
+
+    // In-order arena indexes of all nodes, without key comparisons.
+    fn in_order_idxs(&self) -> StackVec<[usize; N]> {
+        let mut sorted = StackVec::<[usize; N]>::default();
+        let mut stack = StackVec::<[usize; N]>::default();
+        let mut opt_curr_idx = self.opt_root_idx;
+
+        loop {
+            while let Some(curr_idx) = opt_curr_idx {
+                stack.push(curr_idx);
+                opt_curr_idx = self.arena[curr_idx].left_idx();
+            }
+
+            match stack.pop() {
+                Some(idx) => {
+                    sorted.push(idx);
+                    opt_curr_idx = self.arena[idx].right_idx();
+                }
+                None => return sorted,
+            }
+        }
+    }
+
+    // In-order arena indexes of all nodes, ordered by a (possibly different) comparator instance.
+    // Linear if `cmp` agrees with this tree's own, which is the common case.
+    fn in_order_idxs_by(&self, cmp: &C) -> StackVec<[usize; N]> {
+        let mut sorted = self.in_order_idxs();
+        let is_sorted = sorted
+            .windows(2)
+            .all(|w| cmp.compare(self.arena[w[0]].key(), self.arena[w[1]].key()) == Ordering::Less);
+
+        if !is_sorted {
+            sorted.sort_unstable_by(|a, b| cmp.compare(self.arena[*a].key(), self.arena[*b].key()));
+        }
+
+        sorted
+    }
+
+    // Linear merge walk counting keys present in both trees, given sorted index lists for each.
+    fn sorted_intersect_cnt(
+        &self,
+        other: &SgTree<K, V, N, U, C, A>,
+        self_sorted: &[usize],
+        other_sorted: &[usize],
+    ) -> usize {
+        let (mut i, mut j, mut cnt) = (0, 0, 0);
+
+        while (i < self_sorted.len()) && (j < other_sorted.len()) {
+            match self.cmp.compare(
+                self.arena[self_sorted[i]].key(),
+                other.arena[other_sorted[j]].key(),
+            ) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    cnt += 1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+
+        cnt
     }
 
     // Maximum tree capacity (const N value).
     pub(crate) fn max_capacity() -> usize {
-        Idx::MAX as usize
+        U::MAX
     }
 
-    /// Find arena indexes for a given range
-    pub(crate) fn range_search<T, R>(&self, range: &R) -> ArrayVec<[usize; N]>
+    /// Find arena indexes for a given range, in sorted order.
+    /// Subtrees outside the range are pruned, so runtime is `O(log n + k)` for `k` matches (given bounded height).
+    pub(crate) fn range_search<T, R>(&self, range: &R) -> StackVec<[usize; N]>
     where
-        T: Ord + ?Sized,
+        T: ?Sized,
+        C: Compare<T>,
         R: RangeBounds<T>,
         K: Borrow<T> + Ord,
     {
-        let mut node_idxs = ArrayVec::<[usize; N]>::new();
+        let mut node_idxs = StackVec::<[usize; N]>::new();
+        let mut stack = StackVec::<[usize; N]>::new();
+        let mut opt_idx = self.opt_root_idx;
 
-        for (idx, node) in self
-            .arena
-            .iter()
-            .enumerate()
-            .filter_map(|(i, node)| Some((i, node.as_ref()?)))
-        {
-            if range.contains(node.key().borrow()) {
-                node_idxs.push(idx);
+        // In-order traversal, skipping left subtrees entirely below the start bound
+        loop {
+            while let Some(idx) = opt_idx {
+                let node = &self.arena[idx];
+                match below_start(&self.cmp, range.start_bound(), node.key().borrow()) {
+                    true => opt_idx = node.right_idx(),
+                    false => {
+                        stack.push(idx);
+                        opt_idx = node.left_idx();
+                    }
+                }
+            }
+
+            match stack.pop() {
+                Some(idx) => {
+                    let node = &self.arena[idx];
+
+                    // All remaining nodes are greater
+                    if above_end(&self.cmp, range.end_bound(), node.key().borrow()) {
+                        break;
+                    }
+
+                    node_idxs.push(idx);
+                    opt_idx = node.right_idx();
+                }
+                None => break,
             }
         }
 
-        node_idxs.sort_unstable_by_key(|idx| self.arena[*idx].key());
+        debug_assert!(node_idxs.windows(2).all(|w| self
+            .cmp
+            .compare(self.arena[w[0]].key(), self.arena[w[1]].key())
+            == Ordering::Less));
+
+        node_idxs
+    }
+
+    /// Find arena indexes for keys starting with a given byte prefix, in sorted order.
+    /// Assumes keys are ordered by their bytes (e.g. `Natural` on strings), so matches are contiguous.
+    /// Runtime is `O(log n + k)` for `k` matches (given bounded height).
+    pub(crate) fn prefix_search(&self, prefix: &[u8]) -> StackVec<[usize; N]>
+    where
+        K: AsRef<[u8]>,
+    {
+        let mut node_idxs = StackVec::<[usize; N]>::new();
+        let mut stack = StackVec::<[usize; N]>::new();
+        let mut opt_idx = self.opt_root_idx;
+
+        // In-order traversal, skipping left subtrees entirely below the prefix
+        loop {
+            while let Some(idx) = opt_idx {
+                let node = &self.arena[idx];
+                match node.key().as_ref() < prefix {
+                    true => opt_idx = node.right_idx(),
+                    false => {
+                        stack.push(idx);
+                        opt_idx = node.left_idx();
+                    }
+                }
+            }
+
+            match stack.pop() {
+                Some(idx) => {
+                    let node = &self.arena[idx];
+
+                    // At or above the prefix, but not extending it: all remaining nodes are greater
+                    if !node.key().as_ref().starts_with(prefix) {
+                        break;
+                    }
+
+                    node_idxs.push(idx);
+                    opt_idx = node.right_idx();
+                }
+                None => break,
+            }
+        }
 
         node_idxs
     }
 
     /// Validate range
-    pub(crate) fn assert_valid_range<T, R>(range: &R)
+    pub(crate) fn assert_valid_range<T, R>(&self, range: &R)
     where
-        T: Ord + ?Sized,
+        T: ?Sized,
+        C: Compare<T>,
         R: RangeBounds<T>,
         K: Borrow<T> + Ord,
     {
         match (range.start_bound(), range.end_bound()) {
             (Included(start), Included(end))
             | (Included(start), Excluded(end))
-            | (Excluded(start), Included(end)) => {
-                if start > end {
-                    panic!("range start is greater than range end");
+            | (Excluded(start), Included(end))
+                if self.cmp.compare(start, end) == Ordering::Greater =>
+            {
+                panic!("range start is greater than range end");
+            }
+            (Excluded(start), Excluded(end)) if self.cmp.compare(start, end) == Ordering::Equal => {
+                panic!("range start and end are equal and excluded");
+            }
+            _ => {}
+        }
+    }
+
+    // Height of the tree, e.g. node count along the longest root-to-leaf path.
+    pub(crate) fn height(&self) -> usize {
+        let mut max_depth = 0;
+        self.priv_walk_depths(|depth| {
+            max_depth = max_depth.max(depth);
+            true
+        });
+
+        max_depth
+    }
+
+    // Check if the tree is taller than `max_height`, visiting at most `budget` nodes (deducted as visited).
+    // Returns `None` if the budget ran out before the answer was known.
+    fn priv_height_exceeds(&self, max_height: usize, budget: &mut usize) -> Option<bool> {
+        let mut opt_exceeds = Some(false);
+        self.priv_walk_depths(|depth| match *budget {
+            0 => {
+                opt_exceeds = None;
+                false
+            }
+            _ => {
+                *budget -= 1;
+                if depth > max_height {
+                    opt_exceeds = Some(true);
+                }
+                opt_exceeds == Some(false)
+            }
+        });
+
+        opt_exceeds
+    }
+
+    // Depth-first walk calling `visit` with each node's depth (root is `1`), until it returns `false`.
+    // The stack holds the path to the current node (and whether its right subtree was entered), so depth is its length.
+    fn priv_walk_depths<F: FnMut(usize) -> bool>(&self, mut visit: F) {
+        let mut path = StackVec::<[(U, bool); N]>::new();
+        let mut opt_curr_idx = self.opt_root_idx;
+
+        loop {
+            while let Some(curr_idx) = opt_curr_idx {
+                path.push((U::checked_from(curr_idx), false));
+                if !visit(path.len()) {
+                    return;
                 }
+                opt_curr_idx = self.arena[curr_idx].left_idx();
             }
-            (Excluded(start), Excluded(end)) => {
-                if start == end {
-                    panic!("range start and end are equal and excluded");
+
+            match path.last_mut() {
+                None => return,
+                Some((idx, right_entered)) => {
+                    match (*right_entered, self.arena[idx.usize()].right_idx()) {
+                        (false, Some(right_idx)) => {
+                            *right_entered = true;
+                            opt_curr_idx = Some(right_idx);
+                        }
+                        _ => {
+                            path.pop();
+                        }
+                    }
                 }
             }
-            _ => {}
         }
     }
 
+    // Height of a perfectly balanced tree with `len` nodes.
+    pub(crate) fn min_height(len: usize) -> usize {
+        (usize::BITS - len.leading_zeros()) as usize
+    }
+
+    // Check if arena is compacted in in-order layout, e.g. the post-condition of `sort_arena`.
+    #[cfg(test)]
+    pub(crate) fn is_arena_sorted(&self) -> bool {
+        let mut budget = usize::MAX;
+        self.priv_is_arena_sorted(&mut budget) == Some(true)
+    }
+
+    // As `is_arena_sorted`, visiting at most `budget` slots (deducted as visited).
+    // Returns `None` if the budget ran out before the answer was known.
+    fn priv_is_arena_sorted(&self, budget: &mut usize) -> Option<bool> {
+        let mut prev_key = None;
+        for opt_node in self.arena.iter().take(self.len()) {
+            match *budget {
+                0 => return None,
+                _ => *budget -= 1,
+            }
+
+            match (opt_node, prev_key) {
+                (None, _) => return Some(false),
+                (Some(node), Some(prev_key))
+                    if self.cmp.compare(prev_key, node.key()) != Ordering::Less =>
+                {
+                    return Some(false)
+                }
+                (Some(node), _) => prev_key = Some(node.key()),
+            }
+        }
+
+        Some(true)
+    }
+
+    // Handle to the node at a given arena index, valid until the next removal or compaction.
+    fn handle(&self, idx: usize) -> NodeHandle {
+        NodeHandle {
+            idx,
+            epoch: self.arena.epoch(),
+        }
+    }
+
+    // A handle is live if nothing has been removed or moved since it was issued.
+    fn is_live_handle(&self, handle: NodeHandle) -> bool {
+        (handle.epoch == self.arena.epoch()) && self.arena.is_occupied(handle.idx)
+    }
+
+    // Rebuild if removals since the last rebuild have left the tree sparse enough to violate height bounds.
+    pub(crate) fn rebuild_if_sparse(&mut self) {
+        if self.max_size > (2 * self.curr_size) {
+            if let Some(root_idx) = self.opt_root_idx {
+                self.rebuild(root_idx);
+                self.max_size = self.curr_size;
+            }
+        }
+    }
+
+    // Arena index of the first node, in order, for which `pred` is `false`, if any.
+    // `pred` must partition the keys: `true` for some prefix, `false` for the rest.
+    pub(crate) fn partition_point_idx<F>(&self, mut pred: F) -> Option<usize>
+    where
+        F: FnMut(&K) -> bool,
+    {
+        let mut opt_idx = self.opt_root_idx;
+        let mut opt_point_idx = None;
+
+        while let Some(idx) = opt_idx {
+            let node = &self.arena[idx];
+            match pred(node.key()) {
+                true => opt_idx = node.right_idx(),
+                false => {
+                    opt_point_idx = Some(idx);
+                    opt_idx = node.left_idx();
+                }
+            }
+        }
+
+        opt_point_idx
+    }
+
+    // Arena index of the smallest key above `bound`, if any.
+    pub(crate) fn lower_bound_idx<Q>(&self, bound: Bound<&Q>) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let mut opt_idx = self.opt_root_idx;
+        let mut opt_bound_idx = None;
+
+        while let Some(idx) = opt_idx {
+            let node = &self.arena[idx];
+            match !below_start(&self.cmp, bound, node.key().borrow()) {
+                true => {
+                    opt_bound_idx = Some(idx);
+                    opt_idx = node.left_idx();
+                }
+                false => opt_idx = node.right_idx(),
+            }
+        }
+
+        opt_bound_idx
+    }
+
+    // Arena index of the largest key below `bound`, if any.
+    pub(crate) fn upper_bound_idx<Q>(&self, bound: Bound<&Q>) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let mut opt_idx = self.opt_root_idx;
+        let mut opt_bound_idx = None;
+
+        while let Some(idx) = opt_idx {
+            let node = &self.arena[idx];
+            match !above_end(&self.cmp, bound, node.key().borrow()) {
+                true => {
+                    opt_bound_idx = Some(idx);
+                    opt_idx = node.right_idx();
+                }
+                false => opt_idx = node.left_idx(),
+            }
+        }
+
+        opt_bound_idx
+    }
+
+    // Suspend scapegoat checks (`a == 1.0`) for a batched insert phase, returning the alpha to later restore.
+    pub(crate) fn begin_bulk_load(&mut self) -> (u32, u32) {
+        let saved_alpha = self.rebal_ratio();
+        self.alpha_num = self.alpha_denom;
+        saved_alpha
+    }
+
+    // Restore alpha and rebuild once, ending a batched insert phase.
+    pub(crate) fn end_bulk_load(&mut self, saved_alpha: (u32, u32)) {
+        (self.alpha_num, self.alpha_denom) = saved_alpha;
+        if let Some(root_idx) = self.opt_root_idx {
+            if self.height() > Self::min_height(self.len()) {
+                self.rebuild(root_idx);
+            }
+        }
+        self.max_size = self.len();
+    }
+
     // Private API -----------------------------------------------------------------------------------------------------
 
     // Iterative search. If key found, returns node idx, parent idx, and a bool indicating if node is right child
     // `opt_path` is only populated if `Some` and key is found.
-    pub(crate) fn priv_get<Q, U: SmallUnsigned + Default + Copy>(
+    #[cfg(not(feature = "branchless"))]
+    pub(crate) fn priv_get<Q, I: SmallUnsigned + Default + Copy>(
         &self,
-        mut opt_path: Option<&mut ArrayVec<[U; N]>>,
+        mut opt_path: Option<&mut StackVec<[I; N]>>,
         key: &Q,
-    ) -> NodeGetHelper<U>
+    ) -> NodeGetHelper<I>
     where
         K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
         match self.opt_root_idx {
             Some(root_idx) => {
@@ -603,10 +2383,10 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                     let node = &self.arena[curr_idx];
 
                     if let Some(ref mut path) = opt_path {
-                        path.push(U::checked_from(curr_idx));
+                        path.push(I::checked_from(curr_idx));
                     }
 
-                    match key.cmp(node.key().borrow()) {
+                    match self.cmp.compare(key, node.key().borrow()) {
                         Ordering::Less => match node.left_idx() {
                             Some(lt_idx) => {
                                 opt_parent_idx = Some(curr_idx);
@@ -653,31 +2433,238 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         }
     }
 
-    // Sorted insert of node into the tree (outer).
-    // Re-balances the tree if necessary.
-    //
-    // Returns the old value, if any, and the index of the new node in the arena.
-    pub(crate) fn priv_balancing_insert<U: Default + Copy + Ord + Sub + SmallUnsigned>(
+    // Iterative search, same contract as above. The next child is selected by indexing with the comparison result,
+    // not by branching on it: only the well-predicted "found" and "missing child" exits branch.
+    #[cfg(feature = "branchless")]
+    pub(crate) fn priv_get<Q, I: SmallUnsigned + Default + Copy>(
+        &self,
+        mut opt_path: Option<&mut StackVec<[I; N]>>,
+        key: &Q,
+    ) -> NodeGetHelper<I>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        let mut opt_parent_idx = None;
+        let mut opt_curr_idx = self.opt_root_idx;
+        let mut is_right_child = false;
+        while let Some(curr_idx) = opt_curr_idx {
+            let node = &self.arena[curr_idx];
+
+            let ord = self.cmp.compare(key, node.key().borrow());
+            if ord == Ordering::Equal {
+                return NodeGetHelper::new(Some(curr_idx), opt_parent_idx, is_right_child);
+            }
+
+            if let Some(ref mut path) = opt_path {
+                path.push(I::checked_from(curr_idx));
+            }
+
+            is_right_child = ord == Ordering::Greater;
+            opt_parent_idx = Some(curr_idx);
+            opt_curr_idx = node.child_idx(is_right_child);
+        }
+
+        if let Some(path) = opt_path {
+            path.clear(); // Find failed, clear path
+        }
+
+        NodeGetHelper::new(None, None, false)
+    }
+
+    // Search for `key`, resuming from `path`: the previous search's `(node, turned left)` steps, ending at the node
+    // it found or stopped at. Only valid if `key` isn't below the previous key, else restarts from the root.
+    //
+    // Left turns along a path are at increasingly larger keys, bottom to top. The search resumes at the highest
+    // left turn at or below `key` (its right subtree bounds `key`), or at the path's end if there is none.
+    fn priv_get_resume<Q>(
+        &self,
+        path: &mut StackVec<[(U, bool); N]>,
+        opt_prev: Option<&Q>,
+        key: &Q,
+    ) -> Option<usize>
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+    {
+        match opt_prev {
+            Some(prev) if self.cmp.compare(key, prev) != Ordering::Less => {
+                let mut resume_len = path.len();
+                for (pos, (idx, turned_left)) in path.iter().enumerate().rev() {
+                    if *turned_left {
+                        if self
+                            .cmp
+                            .compare(key, self.arena[idx.usize()].key().borrow())
+                            == Ordering::Less
+                        {
+                            break;
+                        }
+                        resume_len = pos + 1;
+                    }
+                }
+                path.truncate(resume_len);
+            }
+            _ => path.clear(),
+        }
+
+        let mut opt_curr_idx = match path.pop() {
+            Some((idx, _)) => Some(idx.usize()),
+            None => self.opt_root_idx,
+        };
+
+        while let Some(curr_idx) = opt_curr_idx {
+            let node = &self.arena[curr_idx];
+            let ord = self.cmp.compare(key, node.key().borrow());
+            path.push((U::checked_from(curr_idx), ord == Ordering::Less));
+            opt_curr_idx = match ord {
+                Ordering::Less => node.left_idx(),
+                Ordering::Equal => return Some(curr_idx),
+                Ordering::Greater => node.right_idx(),
+            };
+        }
+
+        None
+    }
+
+    // Sorted insert of node into the tree (outer).
+    // Re-balances the tree if necessary.
+    //
+    // Returns the old value, if any, and the index of the new node in the arena.
+    pub(crate) fn priv_balancing_insert(&mut self, key: K, val: V) -> (Option<V>, usize) {
+        let mut path: StackVec<[U; N]> = Arena::<K, V, U, N>::new_idx_vec();
+        let (opt_val, ngh) = self.priv_insert(&mut path, key, move || val, true);
+        self.priv_finish_insert(&path, opt_val, ngh)
+    }
+
+    // Single-traversal lookup, inserting the key with `f`'s value only if absent.
+    // A present pair is left untouched (the given key is dropped).
+    //
+    // Returns the index of the found or new node in the arena.
+    fn priv_get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> usize {
+        let mut path: StackVec<[U; N]> = Arena::<K, V, U, N>::new_idx_vec();
+        let prev_len = self.len();
+        let (_, ngh) = self.priv_insert(&mut path, key, f, false);
+        match self.len() == prev_len {
+            true => ngh.node_idx().expect("Found node index must be `Some`"),
+            false => self.priv_finish_insert(&path, None, ngh).1,
+        }
+    }
+
+    // Insert using a handle to the first or last pair, falling back to a full search if it doesn't apply.
+    // An equal key is replaced in place, a key past that end (min or max) extends its spine.
+    // A full tree is rejected before any traversal, unless the key turns out to be present.
+    fn priv_insert_at_end(
+        &mut self,
+        end: NodeHandle,
+        key: K,
+        val: V,
+    ) -> Result<(Option<V>, usize), SgError> {
+        if self.is_live_handle(end) {
+            let end_node = &mut self.arena[end.idx];
+            let is_right = match self.cmp.compare(&key, end_node.key()) {
+                Ordering::Equal => {
+                    end_node.set_key(key);
+                    let old_val = end_node.replace_val(val);
+                    if A::ENABLED {
+                        self.augment_root_path(end.idx);
+                    }
+                    return Ok((Some(old_val), end.idx));
+                }
+                Ordering::Greater if end.idx == self.max_idx => Some(true),
+                Ordering::Less if end.idx == self.min_idx => Some(false),
+                _ => None,
+            };
+
+            if let Some(is_right) = is_right {
+                return match self.is_full() {
+                    true => Err(SgError::StackCapacityExceeded),
+                    false => Ok(self.priv_spine_insert(is_right, key, val)),
+                };
+            }
+        }
+
+        match self.is_full() && !self.contains_key::<K>(&key) {
+            true => Err(SgError::StackCapacityExceeded),
+            false => Ok(self.priv_balancing_insert(key, val)),
+        }
+    }
+
+    // Insert a new extreme key below the rightmost (or leftmost) node, without key comparisons:
+    // the insertion path is the tree's right (or left) spine.
+    fn priv_spine_insert(&mut self, is_right: bool, key: K, val: V) -> (Option<V>, usize) {
+        let mut path: StackVec<[U; N]> = Arena::<K, V, U, N>::new_idx_vec();
+        let mut opt_curr_idx = self.opt_root_idx;
+        while let Some(curr_idx) = opt_curr_idx {
+            path.push(U::checked_from(curr_idx));
+            let curr_node = &self.arena[curr_idx];
+            opt_curr_idx = match is_right {
+                true => curr_node.right_idx(),
+                false => curr_node.left_idx(),
+            };
+        }
+
+        let parent_idx = match path.last() {
+            Some(idx) => idx.usize(),
+            None => unreachable!("Internal invariant failed: spine insert into empty tree!"),
+        };
+
+        let new_node_idx = self.arena.add(key, val);
+        let parent_node = &mut self.arena[parent_idx];
+        if is_right {
+            debug_assert_eq!(parent_idx, self.max_idx);
+            parent_node.set_right_idx(Some(new_node_idx));
+            self.max_idx = new_node_idx;
+        } else {
+            debug_assert_eq!(parent_idx, self.min_idx);
+            parent_node.set_left_idx(Some(new_node_idx));
+            self.min_idx = new_node_idx;
+        }
+
+        self.curr_size += 1;
+        self.max_size += 1;
+
+        let ngh = NodeGetHelper::new(Some(new_node_idx), Some(parent_idx), is_right);
+        self.priv_finish_insert(&path, None, ngh)
+    }
+
+    // Post-insert bookkeeping, given the path to the new (or replaced) node: cached metadata and potential rebalance.
+    fn priv_finish_insert(
         &mut self,
-        key: K,
-        val: V,
+        path: &[U],
+        opt_val: Option<V>,
+        ngh: NodeGetHelper<U>,
     ) -> (Option<V>, usize) {
-        let mut path: ArrayVec<[U; N]> = Arena::<K, V, U, N>::new_idx_vec();
-        let (opt_val, ngh) = self.priv_insert(&mut path, key, val);
+        #[cfg(feature = "telemetry")]
+        {
+            self.telemetry.record_len(self.len());
+            self.telemetry.record_scratch(path.len());
+        }
 
+        // Update subtree sizes, unless an existing key was replaced in-place
         #[cfg(feature = "fast_rebalance")]
-        {
-            // Update subtree sizes
-            for parent_idx in &path {
+        if opt_val.is_none() {
+            for parent_idx in path {
                 let parent_node = &mut self.arena[(*parent_idx).usize()];
                 parent_node.set_subtree_size(parent_node.subtree_size() + 1);
             }
         }
 
+        // Update derived data, bottom-up from the new (or replaced) node
+        if A::ENABLED {
+            if let Some(node_idx) = ngh.node_idx() {
+                self.augment_node(node_idx);
+            }
+            for parent_idx in path.iter().rev() {
+                self.augment_node((*parent_idx).usize());
+            }
+        }
+
         // Potential rebalance
         if path.len() > self.alpha_balance_depth(self.max_size) {
-            if let Some(scapegoat_idx) = self.find_scapegoat(&path) {
-                self.rebuild::<U>(scapegoat_idx);
+            if let Some(scapegoat_idx) = self.find_scapegoat(path) {
+                self.rebuild(scapegoat_idx);
             }
         }
 
@@ -689,13 +2676,16 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     // Sorted insert of node into the tree (inner).
     // Maintains a traversal path to avoid nodes needing to maintain a parent index.
     // Returns a tuple of the old value, if any, and the `NodeGetHelper` of the new node.
+    // The value is only produced, by calling `val`, if it's stored.
     //
-    // If a node with the same key existed, overwrites both that nodes key and value with the new one's and returns the old value.
-    fn priv_insert<U: SmallUnsigned + Default + Copy>(
+    // If a node with the same key existed and `replace` is set, overwrites both that nodes key and value with the
+    // new one's and returns the old value. Otherwise that node is left as-is.
+    fn priv_insert<F: FnOnce() -> V>(
         &mut self,
-        path: &mut ArrayVec<[U; N]>,
+        path: &mut StackVec<[U; N]>,
         key: K,
-        val: V,
+        val: F,
+        replace: bool,
     ) -> (Option<V>, NodeGetHelper<U>) {
         match self.opt_root_idx {
             // Sorted insert
@@ -708,7 +2698,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                     let curr_node = &mut self.arena[curr_idx];
                     path.push(U::checked_from(curr_idx));
 
-                    match key.cmp(curr_node.key()) {
+                    match self.cmp.compare(&key, curr_node.key()) {
                         Ordering::Less => {
                             match curr_node.left_idx() {
                                 Some(left_idx) => curr_idx = left_idx,
@@ -716,12 +2706,12 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                                     // New min check
                                     let mut new_min_found = false;
                                     let min_node = &self.arena[self.min_idx];
-                                    if &key < min_node.key() {
+                                    if self.cmp.compare(&key, min_node.key()) == Ordering::Less {
                                         new_min_found = true;
                                     }
 
                                     // Left insert
-                                    let new_node_idx = self.arena.add(key, val);
+                                    let new_node_idx = self.arena.add(key, val());
 
                                     // New min update
                                     if new_min_found {
@@ -738,12 +2728,13 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                             }
                         }
                         Ordering::Equal => {
-                            // Replacing key necessary b/c custom Eq impl may not consider all K's fields
-                            curr_node.set_key(key);
+                            if replace {
+                                // Replacing key necessary b/c custom Eq impl may not consider all K's fields
+                                curr_node.set_key(key);
 
-                            // Replacing val necessary b/c it may be different
-                            opt_val = Some(curr_node.take_val());
-                            curr_node.set_val(val);
+                                // Replacing val necessary b/c it may be different
+                                opt_val = Some(curr_node.replace_val(val()));
+                            }
 
                             // Key/val updated "in-place": no need to update `curr_node`'s parent or children
                             ngh = NodeGetHelper::new(Some(curr_idx), None, false);
@@ -756,12 +2747,12 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                                     // New max check
                                     let mut new_max_found = false;
                                     let max_node = &self.arena[self.max_idx];
-                                    if &key > max_node.key() {
+                                    if self.cmp.compare(&key, max_node.key()) == Ordering::Greater {
                                         new_max_found = true;
                                     }
 
                                     // Right insert
-                                    let new_node_idx = self.arena.add(key, val);
+                                    let new_node_idx = self.arena.add(key, val());
 
                                     // New max update
                                     if new_max_found {
@@ -803,7 +2794,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                 self.curr_size += 1;
                 self.max_size += 1;
 
-                let root_idx = self.arena.add(key, val);
+                let root_idx = self.arena.add(key, val());
                 self.opt_root_idx = Some(root_idx);
                 self.max_idx = root_idx;
                 self.min_idx = root_idx;
@@ -819,9 +2810,10 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     fn priv_remove_by_key<Q>(&mut self, key: &Q) -> Option<(K, V)>
     where
         K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
-        let ngh: NodeGetHelper<Idx> = self.priv_get(None, key);
+        let ngh: NodeGetHelper<U> = self.priv_get(None, key);
         self.priv_remove(None, ngh)
     }
 
@@ -830,18 +2822,19 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     fn priv_remove_by_key<Q>(&mut self, key: &Q) -> Option<(K, V)>
     where
         K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
+        Q: ?Sized,
+        C: Compare<Q>,
     {
-        let mut path = Arena::<K, V, Idx, N>::new_idx_vec();
+        let mut path = Arena::<K, V, U, N>::new_idx_vec();
         let ngh = self.priv_get(Some(&mut path), key);
         self.priv_remove(Some(&path), ngh)
     }
 
     // Remove a node from the tree, re-linking remaining nodes as necessary.
     #[allow(unused_variables)] // `opt_path` only used when feature `fast_rebalance` is enabled
-    fn priv_remove<U: SmallUnsigned + Default + Copy>(
+    fn priv_remove(
         &mut self,
-        opt_path: Option<&ArrayVec<[U; N]>>,
+        opt_path: Option<&StackVec<[U; N]>>,
         ngh: NodeGetHelper<U>,
     ) -> Option<(K, V)> {
         match ngh.node_idx() {
@@ -852,6 +2845,9 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                 let node_to_remove_left_idx = node_to_remove.left_idx();
                 let mut node_to_remove_right_idx = node_to_remove.right_idx();
 
+                // Deepest node whose subtree changes, every other changed node is one of its ancestors
+                let mut opt_augment_idx = ngh.parent_idx();
+
                 let new_child = match (node_to_remove_left_idx, node_to_remove_right_idx) {
                     // No children
                     (None, None) => None,
@@ -875,6 +2871,14 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                             match min_node.left_idx() {
                                 // Continue search for min node
                                 Some(lt_idx) => {
+                                    // Every node on the way down loses the min node
+                                    #[cfg(feature = "fast_rebalance")]
+                                    {
+                                        let min_path_node = &mut self.arena[min_idx];
+                                        min_path_node
+                                            .set_subtree_size(min_path_node.subtree_size() - 1);
+                                    }
+
                                     min_parent_idx = min_idx;
                                     min_idx = lt_idx;
                                 }
@@ -887,13 +2891,6 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                                         } else {
                                             let min_parent_node = &mut self.arena[min_parent_idx];
                                             min_parent_node.set_left_idx(unlink_new_child);
-
-                                            #[cfg(feature = "fast_rebalance")]
-                                            {
-                                                min_parent_node.set_subtree_size(
-                                                    min_parent_node.subtree_size() - 1,
-                                                );
-                                            }
                                         }
                                         break;
                                     }
@@ -903,13 +2900,6 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                                         } else {
                                             let min_parent_node = &mut self.arena[min_parent_idx];
                                             min_parent_node.set_left_idx(None);
-
-                                            #[cfg(feature = "fast_rebalance")]
-                                            {
-                                                min_parent_node.set_subtree_size(
-                                                    min_parent_node.subtree_size() - 1,
-                                                );
-                                            }
                                         }
                                         break;
                                     }
@@ -917,6 +2907,11 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                             }
                         }
 
+                        opt_augment_idx = match min_parent_idx == node_idx {
+                            true => Some(min_idx),
+                            false => Some(min_parent_idx),
+                        };
+
                         // Re-link min node to removed node's children
                         let min_node = &mut self.arena[min_idx];
                         min_node.set_right_idx(node_to_remove_right_idx);
@@ -948,7 +2943,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                 }
 
                 // Perform removal
-                let mut removed_node = self.arena.hard_remove(node_idx);
+                let removed_node = self.arena.hard_remove(node_idx);
                 self.curr_size -= 1;
 
                 // Update min/max
@@ -971,59 +2966,311 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                     }
                 }
 
-                Some((removed_node.take_key(), removed_node.take_val()))
+                // Update derived data
+                if let (true, Some(augment_idx)) = (A::ENABLED, opt_augment_idx) {
+                    self.augment_root_path(augment_idx);
+                }
+
+                Some(removed_node.into_kv())
             }
             None => None,
         }
     }
 
-    /// Temporary internal drain_filter() implementation. To be replaced/supplemented with a public implementation.
-    fn priv_drain_filter<Q, F>(&mut self, mut pred: F) -> Self
-    where
-        K: Borrow<Q> + Ord,
-        Q: Ord + ?Sized,
-        F: FnMut(&Q, &mut V) -> bool,
-    {
-        /*
-        // TODO: make public version with this signature
-        pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<'_, K, V, F>
-        where
-            K: Ord,
-            F: FnMut(&K, &mut V) -> bool,
-        {
-        */
+    // Move all of `other` into `self` via a sorted merge and a single rebuild, `O(n + m)`,
+    // or, for disjoint key ranges, a splice that moves nodes without comparing keys.
+    // On a matching key, `other`'s pair overwrites `self`'s.
+    // Capacity is checked before any mutation, so on `Err` both trees are unchanged.
+    fn priv_merge_append(&mut self, other: &mut SgTree<K, V, N, U, C, A>) -> Result<(), SgError> {
+        // Nothing to append!
+        if other.is_empty() {
+            return Ok(());
+        }
+
+        // Nothing to append to!
+        if self.is_empty() {
+            // Handles issued by either tree must not resolve in the other
+            let epoch = self.arena.epoch().max(other.arena.epoch()).wrapping_add(1);
+            mem::swap(self, other);
+            self.arena.set_epoch(epoch);
+            other.arena.set_epoch(epoch);
+            return Ok(());
+        }
+
+        // Disjoint key ranges: splice `other` in whole, no merge needed.
+        // Only for stateless comparators, whose instances can't disagree on order.
+        if mem::size_of::<C>() == 0 {
+            if let Some(other_is_after) = self.disjoint_order(other) {
+                if self.len() + other.len() > self.capacity() {
+                    return Err(SgError::StackCapacityExceeded);
+                }
 
-        // TODO: this implementation is rather inefficient!
+                self.priv_splice_append(other, other_is_after);
+                return Ok(());
+            }
+        }
 
-        let mut key_idxs = Arena::<K, V, Idx, N>::new_idx_vec();
-        let mut remove_idxs = Arena::<K, V, Idx, N>::new_idx_vec();
+        let self_sorted = self.in_order_idxs();
+        let other_sorted = other.in_order_idxs_by(&self.cmp);
+        let merged_len = self.len() + other.len()
+            - self.sorted_intersect_cnt(other, &self_sorted, &other_sorted);
 
-        // Below iter_mut() will want to sort, require want consistent indexes, so do work up front
-        self.sort_arena();
+        if merged_len > self.capacity() {
+            // Preemptive - we haven't mutated `self` or `other`!
+            // Caller can assume unchanged state.
+            return Err(SgError::StackCapacityExceeded);
+        }
+
+        // Merge, ripping nodes directly out of other's arena
+        let mut merged = StackVec::<[usize; N]>::default();
+        let mut self_iter = self_sorted.iter().copied().peekable();
+
+        for other_idx in other_sorted {
+            let (key, val) = other.arena.hard_remove(other_idx).into_kv();
+
+            while let Some(self_idx) = self_iter.next_if(|self_idx| {
+                self.cmp.compare(self.arena[*self_idx].key(), &key) == Ordering::Less
+            }) {
+                merged.push(self_idx);
+            }
+
+            let dup_idx = self_iter.next_if(|self_idx| {
+                self.cmp.compare(self.arena[*self_idx].key(), &key) == Ordering::Equal
+            });
 
-        // Safely treat mutable ref as immutable, init list of node's arena indexes
-        for (k, _) in &(*self) {
-            let ngh: NodeGetHelper<Idx> = self.priv_get(None, k.borrow());
-            debug_assert!(ngh.node_idx().is_some());
-            key_idxs.push(Idx::checked_from(ngh.node_idx().unwrap()));
+            match dup_idx {
+                Some(self_idx) => {
+                    let node = &mut self.arena[self_idx];
+                    node.set_key(key);
+                    node.replace_val(val);
+                    merged.push(self_idx);
+                }
+                None => merged.push(self.arena.add(key, val)),
+            }
+        }
+        merged.extend(self_iter);
+        other.clear();
+
+        debug_assert_eq!(merged.len(), merged_len);
+
+        // Single rebuild of the merged whole
+        if let Some(root_idx) = self.opt_root_idx {
+            self.rebalance_subtree_from_sorted_idxs(root_idx, &merged);
+            self.rebal_cnt = self.rebal_cnt.wrapping_add(1);
+            #[cfg(feature = "rebal_stats")]
+            self.rebal_stats.record(merged.len());
+            #[cfg(feature = "telemetry")]
+            self.telemetry.record_scratch(merged.len());
+        }
+
+        self.curr_size = merged.len();
+        self.max_size = merged.len();
+        #[cfg(feature = "telemetry")]
+        self.telemetry.record_len(merged.len());
+        self.update_min_idx();
+        self.update_max_idx();
+
+        Ok(())
+    }
+
+    // If every key of `other` sorts after every key of `self`, `Some(true)`. If before, `Some(false)`. Both non-empty.
+    fn disjoint_order(&self, other: &SgTree<K, V, N, U, C, A>) -> Option<bool> {
+        let self_min = self.arena[self.min_idx].key();
+        let self_max = self.arena[self.max_idx].key();
+        let other_min = other.arena[other.min_idx].key();
+        let other_max = other.arena[other.max_idx].key();
+
+        if self.cmp.compare(self_max, other_min) == Ordering::Less {
+            Some(true)
+        } else if self.cmp.compare(other_max, self_min) == Ordering::Less {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    // Move all of `other` into `self`, given that `other`'s keys all sort after (or before) `self`'s.
+    // Moved nodes keep their links (remapped to new arena slots), so `other`'s shape and derived data carry over as-is.
+    // The two trees are then joined under the lower tree's maximum, and rebuilt only if that exceeds the height bound.
+    fn priv_splice_append(&mut self, other: &mut SgTree<K, V, N, U, C, A>, other_is_after: bool) {
+        let total_len = self.len() + other.len();
+
+        // Move nodes, links still point into `other`'s arena
+        let mut remap = StackVec::<[Option<U>; N]>::default();
+        for other_idx in 0..other.arena.slot_cnt() {
+            let opt_new_idx = other.arena.remove(other_idx).map(|node| {
+                let (opt_left_idx, opt_right_idx) = (node.left_idx(), node.right_idx());
+                #[cfg(feature = "fast_rebalance")]
+                let subtree_size = node.subtree_size();
+
+                let (key, val) = node.into_kv();
+                let new_idx = self.arena.add(key, val);
+                let new_node = &mut self.arena[new_idx];
+                new_node.set_left_idx(opt_left_idx);
+                new_node.set_right_idx(opt_right_idx);
+                #[cfg(feature = "fast_rebalance")]
+                new_node.set_subtree_size(subtree_size);
+
+                U::checked_from(new_idx)
+            });
+            remap.push(opt_new_idx);
+        }
+
+        // Remap links into `self`'s arena
+        let moved_idx = |other_idx: usize| match remap[other_idx] {
+            Some(new_idx) => new_idx.usize(),
+            None => unreachable!("Internal invariant failed: link to unoccupied slot!"),
+        };
+        for new_idx in remap.iter().flatten() {
+            let node = &mut self.arena[new_idx.usize()];
+            node.set_left_idx(node.left_idx().map(moved_idx));
+            node.set_right_idx(node.right_idx().map(moved_idx));
+        }
+
+        let (self_root_idx, other_root_idx) = match (self.opt_root_idx, other.opt_root_idx) {
+            (Some(self_root_idx), Some(other_root_idx)) => {
+                (self_root_idx, moved_idx(other_root_idx))
+            }
+            _ => unreachable!("Internal invariant failed: splice of empty tree!"),
+        };
+        let (lo_root_idx, hi_root_idx) = match other_is_after {
+            true => {
+                self.max_idx = moved_idx(other.max_idx);
+                (self_root_idx, other_root_idx)
+            }
+            false => {
+                self.min_idx = moved_idx(other.min_idx);
+                (other_root_idx, self_root_idx)
+            }
+        };
+        other.clear();
+
+        // Detach the lower tree's maximum (rightmost, so no right child) to serve as the new root
+        let mut spine = Arena::<K, V, U, N>::new_idx_vec();
+        let mut join_idx = lo_root_idx;
+        while let Some(right_idx) = self.arena[join_idx].right_idx() {
+            spine.push(U::checked_from(join_idx));
+            join_idx = right_idx;
+        }
+
+        let join_left_idx = self.arena[join_idx].left_idx();
+        let opt_lo_rest_idx = match spine.last() {
+            Some(parent_idx) => {
+                self.arena[parent_idx.usize()].set_right_idx(join_left_idx);
+                Some(lo_root_idx)
+            }
+            None => join_left_idx,
+        };
+
+        #[cfg(feature = "fast_rebalance")]
+        for spine_idx in &spine {
+            let spine_node = &mut self.arena[spine_idx.usize()];
+            spine_node.set_subtree_size(spine_node.subtree_size() - 1);
+        }
+        if A::ENABLED {
+            for spine_idx in spine.iter().rev() {
+                self.augment_node(spine_idx.usize());
+            }
+        }
+
+        // Join
+        let join_node = &mut self.arena[join_idx];
+        join_node.set_left_idx(opt_lo_rest_idx);
+        join_node.set_right_idx(Some(hi_root_idx));
+        #[cfg(feature = "fast_rebalance")]
+        join_node.set_subtree_size(total_len);
+        self.augment_node(join_idx);
+
+        self.opt_root_idx = Some(join_idx);
+        self.curr_size = total_len;
+        self.max_size = total_len;
+        #[cfg(feature = "telemetry")]
+        self.telemetry.record_len(total_len);
+
+        if self.height() > self.alpha_balance_depth(self.max_size).saturating_add(1) {
+            self.rebuild(join_idx);
+        }
+    }
+
+    // Drop all but `len` pairs from the front (or back) of the key order, with a single rebuild.
+    fn priv_truncate(&mut self, len: usize, keep_back: bool) {
+        let prev_len = self.len();
+        if len >= prev_len {
+            return;
         }
 
-        // Filter arena index list to those not matching predicate
-        for (i, (k, v)) in self.iter_mut().enumerate() {
-            if pred(k.borrow(), v) {
-                remove_idxs.push(key_idxs[i]);
+        let sorted_idxs = self.in_order_idxs();
+        let (dropped_idxs, kept_idxs) = match keep_back {
+            true => sorted_idxs.split_at(prev_len - len),
+            false => {
+                let (kept_idxs, dropped_idxs) = sorted_idxs.split_at(len);
+                (dropped_idxs, kept_idxs)
             }
+        };
+
+        for idx in dropped_idxs {
+            self.arena.hard_remove(*idx);
         }
 
-        // Drain non-matches
-        let mut drained_sgt = Self::new();
-        for i in remove_idxs {
-            if let Some((k, v)) = self.priv_remove_by_idx(i.usize()) {
-                drained_sgt
-                    .try_insert(k, v)
-                    .expect("Stack-storage capacity exceeded!");
+        self.priv_relink_retained(kept_idxs, prev_len);
+    }
+
+    // Hard-remove every index flagged by a retain pass (bitwise complemented, so `>= N`), keeping the rest in order.
+    fn priv_remove_flagged(&mut self, sorted_idxs: &mut StackVec<[usize; N]>) {
+        sorted_idxs.retain(|idx| match *idx < N {
+            true => true,
+            false => {
+                self.arena.hard_remove(!*idx);
+                false
+            }
+        });
+    }
+
+    // Re-link the survivors of a filtering pass with a single rebuild.
+    // `sorted_idxs` are the retained nodes in key order, all others must already be removed from the arena.
+    fn priv_relink_retained(&mut self, sorted_idxs: &[usize], prev_len: usize) {
+        match sorted_idxs.len() {
+            0 => self.clear(),
+            cnt if cnt == prev_len => {}
+            cnt => {
+                let new_root_idx = sorted_idxs[0];
+                let new_root = &mut self.arena[new_root_idx];
+                new_root.set_left_idx(None);
+                new_root.set_right_idx(None);
+                #[cfg(feature = "fast_rebalance")]
+                new_root.set_subtree_size(1);
+
+                self.opt_root_idx = Some(new_root_idx);
+                self.rebalance_subtree_from_sorted_idxs(new_root_idx, sorted_idxs);
+                self.rebal_cnt = self.rebal_cnt.wrapping_add(1);
+                #[cfg(feature = "rebal_stats")]
+                self.rebal_stats.record(cnt);
+                #[cfg(feature = "telemetry")]
+                self.telemetry.record_scratch(prev_len);
+
+                self.curr_size = cnt;
+                self.max_size = cnt;
+                self.update_min_idx();
+                self.update_max_idx();
             }
         }
+    }
+
+    // Remove all pairs matching the predicate, collecting them into a new tree.
+    fn priv_drain_filter<Q, F>(&mut self, mut pred: F) -> Self
+    where
+        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        C: Compare<Q>,
+        F: FnMut(&Q, &mut V) -> bool,
+    {
+        let mut drained_sgt = Self::with_comparator(self.cmp.clone());
+        let mut extract_if = ExtractIf::new(self);
+        while let Some((k, v)) = extract_if.next_with(&mut |k: &K, v: &mut V| pred(k.borrow(), v)) {
+            drained_sgt
+                .try_insert(k, v)
+                .expect("Stack-storage capacity exceeded!");
+        }
 
         drained_sgt
     }
@@ -1071,22 +3318,22 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     // Traverse upward, using path information, to find first unbalanced parent.
     // Uses the algorithm proposed in the original paper (Galperin and Rivest, 1993).
     #[cfg(not(feature = "alt_impl"))]
-    fn find_scapegoat<U: SmallUnsigned + Default>(&self, path: &[U]) -> Option<usize> {
+    fn find_scapegoat(&self, path: &[U]) -> Option<usize> {
         if path.len() <= 1 {
             return None;
         }
 
         let mut node_subtree_size = 1; // Newly inserted
         let mut parent_path_idx = path.len() - 1; // Parent of newly inserted
-        let mut parent_subtree_size = self.get_subtree_size::<U>(path[parent_path_idx].usize());
+        let mut parent_subtree_size = self.get_subtree_size(path[parent_path_idx].usize());
 
         while (parent_path_idx > 0)
-            && (self.alpha_denom * node_subtree_size as f32)
-                <= (self.alpha_num * parent_subtree_size as f32)
+            && (u64::from(self.alpha_denom) * node_subtree_size as u64)
+                <= (u64::from(self.alpha_num) * parent_subtree_size as u64)
         {
             node_subtree_size = parent_subtree_size;
             parent_path_idx -= 1;
-            parent_subtree_size = self.get_subtree_size_differential::<U>(
+            parent_subtree_size = self.get_subtree_size_differential(
                 path[parent_path_idx].usize(),     // Parent index
                 path[parent_path_idx + 1].usize(), // Child index
                 node_subtree_size,                 // Child subtree size
@@ -1101,7 +3348,7 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     // Traverse upward, using path information, to find first unbalanced parent.
     // Uses an alternate algorithm proposed in Galperin's PhD thesis (1996).
     #[cfg(feature = "alt_impl")]
-    fn find_scapegoat<U: SmallUnsigned + Default>(&self, path: &[U]) -> Option<usize> {
+    fn find_scapegoat(&self, path: &[U]) -> Option<usize> {
         if path.len() <= 1 {
             return None;
         }
@@ -1109,13 +3356,13 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         let mut i = 0;
         let mut node_subtree_size = 1; // Newly inserted
         let mut parent_path_idx = path.len() - 1; // Parent of newly inserted
-        let mut parent_subtree_size = self.get_subtree_size::<U>(path[parent_path_idx].usize());
+        let mut parent_subtree_size = self.get_subtree_size(path[parent_path_idx].usize());
 
         while (parent_path_idx > 0) && (i <= self.alpha_balance_depth(node_subtree_size)) {
             node_subtree_size = parent_subtree_size;
             parent_path_idx -= 1;
             i += 1;
-            parent_subtree_size = self.get_subtree_size_differential::<U>(
+            parent_subtree_size = self.get_subtree_size_differential(
                 path[parent_path_idx].usize(),     // Parent index
                 path[parent_path_idx + 1].usize(), // Child index
                 node_subtree_size,                 // Child subtree size
@@ -1129,8 +3376,8 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
 
     // Iterative subtree size computation
     #[cfg(not(feature = "fast_rebalance"))]
-    fn get_subtree_size<U: SmallUnsigned + Default>(&self, idx: usize) -> usize {
-        let mut subtree_worklist = array_vec![[U; N] => U::checked_from(idx)];
+    fn get_subtree_size(&self, idx: usize) -> usize {
+        let mut subtree_worklist = StackVec::<[U; N]>::from_iter(iter::once(U::checked_from(idx)));
         let mut subtree_size = 0;
 
         while let Some(idx) = subtree_worklist.pop() {
@@ -1151,13 +3398,48 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
 
     // Retrieve cached subtree size
     #[cfg(feature = "fast_rebalance")]
-    fn get_subtree_size<U: SmallUnsigned>(&self, idx: usize) -> usize {
+    #[allow(clippy::extra_unused_type_parameters)] // Signature parity with non-`fast_rebalance` variant
+    fn get_subtree_size(&self, idx: usize) -> usize {
         self.arena[idx].subtree_size()
     }
 
+    // Size of a node's left subtree, i.e. the number of keys ordered before it within its own subtree
+    fn get_left_subtree_size(&self, idx: usize) -> usize {
+        match self.arena[idx].left_idx() {
+            Some(left_idx) => self.get_subtree_size(left_idx),
+            None => 0,
+        }
+    }
+
+    // Order-statistic search: arena index of the node at sorted position `n`
+    fn priv_select(&self, mut n: usize) -> Option<usize> {
+        if n >= self.len() {
+            return None;
+        }
+
+        let mut opt_idx = self.opt_root_idx;
+        while let Some(idx) = opt_idx {
+            let left_size = self.get_left_subtree_size(idx);
+            match n.cmp(&left_size) {
+                Ordering::Less => opt_idx = self.arena[idx].left_idx(),
+                Ordering::Equal => return Some(idx),
+                Ordering::Greater => {
+                    n -= left_size + 1;
+                    opt_idx = self.arena[idx].right_idx();
+                }
+            }
+        }
+
+        debug_assert!(
+            false,
+            "Internal invariant failed: subtree sizes don't sum to length!"
+        );
+        None
+    }
+
     // Differential subtree size helper
     #[cfg(not(feature = "fast_rebalance"))]
-    fn get_subtree_size_differential<U: SmallUnsigned + Default>(
+    fn get_subtree_size_differential(
         &self,
         parent_idx: usize,
         child_idx: usize,
@@ -1178,22 +3460,19 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
 
         let other_child_subtree_size = if is_right_child {
             match parent.left_idx() {
-                Some(idx) => self.get_subtree_size::<U>(idx),
+                Some(idx) => self.get_subtree_size(idx),
                 None => 0,
             }
         } else {
             match parent.right_idx() {
-                Some(idx) => self.get_subtree_size::<U>(idx),
+                Some(idx) => self.get_subtree_size(idx),
                 None => 0,
             }
         };
 
         let computed_subtree_size = child_subtree_size + other_child_subtree_size + 1;
 
-        debug_assert_eq!(
-            computed_subtree_size,
-            self.get_subtree_size::<U>(parent_idx)
-        );
+        debug_assert_eq!(computed_subtree_size, self.get_subtree_size(parent_idx));
 
         computed_subtree_size
     }
@@ -1201,30 +3480,258 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
     // Subtree size helper
     // Size already cached if `fast_rebalance` is enabled, no need for differential logic
     #[cfg(feature = "fast_rebalance")]
-    fn get_subtree_size_differential<U: SmallUnsigned>(
+    #[allow(clippy::extra_unused_type_parameters)] // Signature parity with non-`fast_rebalance` variant
+    fn get_subtree_size_differential(
         &self,
         parent_idx: usize,
         _child_idx: usize,
         _child_subtree_size: usize,
     ) -> usize {
-        self.get_subtree_size::<U>(parent_idx)
+        self.get_subtree_size(parent_idx)
+    }
+
+    // Iterative in-place rebuild for balanced subtree.
+    // Peak scratch is a single `N`-length buffer of `U` indexes, plus a fixed-size worklist (`REBUILD_WORKLIST_CAP`).
+    #[cfg(not(feature = "low_mem_rebuild"))]
+    fn rebuild(&mut self, idx: usize) {
+        let mut sorted_sub = Arena::<K, V, U, N>::new_idx_vec();
+        self.flatten_subtree_in_place(idx, &mut sorted_sub);
+        self.rebalance_subtree_from_sorted_idxs(idx, &sorted_sub);
+        self.rebal_cnt = self.rebal_cnt.wrapping_add(1);
+        #[cfg(feature = "rebal_stats")]
+        self.rebal_stats.record(sorted_sub.len());
+        #[cfg(feature = "telemetry")]
+        self.telemetry.record_scratch(sorted_sub.len());
     }
 
-    // Iterative in-place rebuild for balanced subtree
-    fn rebuild<U: Copy + Ord + Sub + SmallUnsigned + Default>(&mut self, idx: usize) {
-        let sorted_sub = self.flatten_subtree_to_sorted_idxs(idx);
-        self.rebalance_subtree_from_sorted_idxs::<U>(idx, &sorted_sub);
+    // Day-Stout-Warren in-place rebuild for balanced subtree: rotate into a sorted, right-leaning "vine",
+    // then compress the vine into a complete tree. Re-points existing child links only, no index buffer.
+    // Scratch is the fixed-size worklist used to refresh per-node metadata, and only if there's metadata to refresh.
+    #[cfg(feature = "low_mem_rebuild")]
+    fn rebuild(&mut self, idx: usize) {
+        // Find the parent while the subtree is still searchable
+        let ngh: NodeGetHelper<U> = self.priv_get(None, self.arena[idx].key());
+
+        let (vine_head_idx, subtree_size) = self.subtree_to_vine(idx);
+        let subtree_root_idx = self.vine_to_subtree(vine_head_idx, subtree_size);
+
+        // Update tree root or subtree parent
+        match ngh.parent_idx() {
+            Some(parent_idx) => {
+                let parent_node = &mut self.arena[parent_idx];
+                if ngh.is_right_child() {
+                    parent_node.set_right_idx(Some(subtree_root_idx));
+                } else {
+                    parent_node.set_left_idx(Some(subtree_root_idx));
+                }
+            }
+            None => self.opt_root_idx = Some(subtree_root_idx),
+        }
+
+        self.refresh_balanced_subtree(subtree_root_idx);
+        if A::ENABLED {
+            self.augment_root_path(subtree_root_idx);
+        }
+
         self.rebal_cnt = self.rebal_cnt.wrapping_add(1);
+        #[cfg(feature = "rebal_stats")]
+        self.rebal_stats.record(subtree_size);
+    }
+
+    // DSW phase 1: right-rotate every left child up, until the subtree is a right-only chain in key order.
+    // Returns the chain's head and length.
+    #[cfg(feature = "low_mem_rebuild")]
+    fn subtree_to_vine(&mut self, idx: usize) -> (usize, usize) {
+        let mut vine_head_idx = idx;
+        let mut opt_vine_tail_idx = None;
+        let mut opt_rest_idx = Some(idx);
+        let mut vine_len = 0;
+
+        while let Some(rest_idx) = opt_rest_idx {
+            match self.arena[rest_idx].left_idx() {
+                Some(left_idx) => {
+                    // Rotate right
+                    let left_right = self.arena[left_idx].right_idx();
+                    self.arena[rest_idx].set_left_idx(left_right);
+                    self.arena[left_idx].set_right_idx(Some(rest_idx));
+
+                    match opt_vine_tail_idx {
+                        Some(tail_idx) => self.arena[tail_idx].set_right_idx(Some(left_idx)),
+                        None => vine_head_idx = left_idx,
+                    }
+                    opt_rest_idx = Some(left_idx);
+                }
+                None => {
+                    opt_vine_tail_idx = Some(rest_idx);
+                    opt_rest_idx = self.arena[rest_idx].right_idx();
+                    vine_len += 1;
+                }
+            }
+        }
+
+        (vine_head_idx, vine_len)
+    }
+
+    // DSW phase 2: repeatedly left-rotate every other vine node, halving the vine each pass.
+    // The first pass only rotates the surplus over a perfect tree, so the result is complete. Returns its root.
+    #[cfg(feature = "low_mem_rebuild")]
+    fn vine_to_subtree(&mut self, mut vine_head_idx: usize, vine_len: usize) -> usize {
+        // Largest perfect tree size (e.g. `2^k - 1`) not exceeding the vine's length
+        let perfect_len = (1 << (usize::BITS - 1 - (vine_len + 1).leading_zeros())) - 1;
+        self.compress_vine(&mut vine_head_idx, vine_len - perfect_len);
+
+        let mut spine_len = perfect_len;
+        while spine_len > 1 {
+            spine_len /= 2;
+            self.compress_vine(&mut vine_head_idx, spine_len);
+        }
+
+        vine_head_idx
+    }
+
+    // Left-rotate the first `cnt` odd nodes of the vine into left children of their successors.
+    #[cfg(feature = "low_mem_rebuild")]
+    fn compress_vine(&mut self, vine_head_idx: &mut usize, cnt: usize) {
+        let mut opt_scanner_idx: Option<usize> = None;
+
+        for _ in 0..cnt {
+            let child_idx = match opt_scanner_idx {
+                Some(scanner_idx) => self.arena[scanner_idx].right_idx(),
+                None => Some(*vine_head_idx),
+            };
+            let opt_next_idx = child_idx.and_then(|i| self.arena[i].right_idx());
+
+            debug_assert!(
+                opt_next_idx.is_some(),
+                "Internal invariant failed: vine compression past end of vine!"
+            );
+
+            if let (Some(child_idx), Some(next_idx)) = (child_idx, opt_next_idx) {
+                match opt_scanner_idx {
+                    Some(scanner_idx) => self.arena[scanner_idx].set_right_idx(Some(next_idx)),
+                    None => *vine_head_idx = next_idx,
+                }
+
+                // Rotate left
+                let next_left = self.arena[next_idx].left_idx();
+                self.arena[child_idx].set_right_idx(next_left);
+                self.arena[next_idx].set_left_idx(Some(child_idx));
+                opt_scanner_idx = Some(next_idx);
+            }
+        }
+    }
+
+    // Recompute cached subtree sizes and derived data for a freshly balanced subtree, children before parents.
+    // Balanced height is at most `usize::BITS`, so the worklist is bounded by `REBUILD_WORKLIST_CAP`, not `N`.
+    #[cfg(feature = "low_mem_rebuild")]
+    fn refresh_balanced_subtree(&mut self, idx: usize) {
+        if !cfg!(feature = "fast_rebalance") && !A::ENABLED {
+            return;
+        }
+
+        let mut subtree_worklist = StackVec::<[(U, bool); REBUILD_WORKLIST_CAP]>::default();
+        subtree_worklist.push((U::checked_from(idx), false));
+
+        while let Some((curr_idx, children_done)) = subtree_worklist.pop() {
+            let curr_idx = curr_idx.usize();
+            let node = &self.arena[curr_idx];
+
+            if !children_done {
+                subtree_worklist.push((U::checked_from(curr_idx), true));
+                subtree_worklist.extend(node.left_idx().map(|i| (U::checked_from(i), false)));
+                subtree_worklist.extend(node.right_idx().map(|i| (U::checked_from(i), false)));
+                continue;
+            }
+
+            #[cfg(feature = "fast_rebalance")]
+            {
+                let subtree_size = 1
+                    + node.left_idx().map_or(0, |i| self.arena[i].subtree_size())
+                    + node.right_idx().map_or(0, |i| self.arena[i].subtree_size());
+                self.arena[curr_idx].set_subtree_size(subtree_size);
+            }
+
+            self.augment_node(curr_idx);
+        }
+    }
+
+    // Recompute every cached subtree size, children before parents (reverse breadth-first order).
+    // Unlike `refresh_balanced_subtree`, makes no assumption about height: the worklist is bounded by `N`.
+    #[cfg(all(feature = "fast_rebalance", feature = "bytemuck"))]
+    fn refresh_subtree_sizes(&mut self) {
+        let mut bfs_order = StackVec::<[U; N]>::default();
+        bfs_order.extend(self.opt_root_idx.map(U::checked_from));
+
+        let mut i = 0;
+        while let Some(idx) = bfs_order.get(i).map(|idx| idx.usize()) {
+            let node = &self.arena[idx];
+            let children = [node.left_idx(), node.right_idx()];
+            bfs_order.extend(
+                children
+                    .iter()
+                    .flatten()
+                    .map(|child_idx| U::checked_from(*child_idx)),
+            );
+            i += 1;
+        }
+
+        for idx in bfs_order.iter().rev().map(|idx| idx.usize()) {
+            let node = &self.arena[idx];
+            let subtree_size = 1
+                + node.left_idx().map_or(0, |i| self.arena[i].subtree_size())
+                + node.right_idx().map_or(0, |i| self.arena[i].subtree_size());
+            self.arena[idx].set_subtree_size(subtree_size);
+        }
+    }
+
+    // Flatten subtree into a buffer of node indexes sorted by node key, without a stack or a sort.
+    // Morris traversal: each in-order predecessor's empty right link temporarily threads back to its successor,
+    // every thread is removed on its second visit, so links are unchanged on return.
+    #[cfg(any(test, not(feature = "low_mem_rebuild")))]
+    pub(crate) fn flatten_subtree_in_place(&mut self, idx: usize, sorted: &mut StackVec<[U; N]>) {
+        let mut opt_curr_idx = Some(idx);
+
+        while let Some(curr_idx) = opt_curr_idx {
+            match self.arena[curr_idx].left_idx() {
+                Some(left_idx) => {
+                    // Rightmost node of left subtree, unless already threaded back to current
+                    let mut pred_idx = left_idx;
+                    while let Some(right_idx) = self.arena[pred_idx].right_idx() {
+                        if right_idx == curr_idx {
+                            break;
+                        }
+                        pred_idx = right_idx;
+                    }
+
+                    let pred_node = &mut self.arena[pred_idx];
+                    if pred_node.right_idx().is_none() {
+                        pred_node.set_right_idx(Some(curr_idx));
+                        opt_curr_idx = Some(left_idx);
+                    } else {
+                        pred_node.set_right_idx(None);
+                        sorted.push(U::checked_from(curr_idx));
+                        opt_curr_idx = self.arena[curr_idx].right_idx();
+                    }
+                }
+                None => {
+                    sorted.push(U::checked_from(curr_idx));
+                    opt_curr_idx = self.arena[curr_idx].right_idx();
+                }
+            }
+        }
     }
 
     // Height re-balance of subtree (e.g. depth of the two subtrees of every node never differs by more than one).
     // Adapted from public interview question: https://afteracademy.com/blog/sorted-array-to-balanced-bst
-    fn rebalance_subtree_from_sorted_idxs<U: Copy + Ord + Default + Sub + SmallUnsigned>(
+    fn rebalance_subtree_from_sorted_idxs<I: SmallUnsigned + Copy>(
         &mut self,
         old_subtree_root_idx: usize,
-        sorted_arena_idxs: &[usize],
+        sorted_arena_idxs: &[I],
     ) {
         if sorted_arena_idxs.len() <= 1 {
+            if let Some(idx) = sorted_arena_idxs.first() {
+                self.augment_node(idx.usize());
+            }
             return;
         }
 
@@ -1234,19 +3741,11 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
         );
 
         let sorted_last_idx = sorted_arena_idxs.len() - 1;
-        let subtree_root_sorted_idx = sorted_last_idx / 2;
-        let subtree_root_arena_idx = sorted_arena_idxs[subtree_root_sorted_idx];
-        let mut subtree_worklist = ArrayVec::<[(U, NodeRebuildHelper<U>); N]>::default();
-
-        // Init worklist with middle node (balanced subtree root)
-        subtree_worklist.push((
-            U::checked_from(subtree_root_sorted_idx),
-            NodeRebuildHelper::new(0, sorted_last_idx),
-        ));
+        let subtree_root_arena_idx = sorted_arena_idxs[sorted_last_idx / 2].usize();
 
         // Update tree root or subtree parent
         if let Some(root_idx) = self.opt_root_idx {
-            if sorted_arena_idxs.contains(&root_idx) {
+            if sorted_arena_idxs.iter().any(|idx| idx.usize() == root_idx) {
                 self.opt_root_idx = Some(subtree_root_arena_idx);
             } else {
                 let old_subtree_root = &self.arena[old_subtree_root_idx];
@@ -1266,33 +3765,42 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
             }
         }
 
-        // Iteratively re-assign all children
-        while let Some((sorted_idx, parent_nrh)) = subtree_worklist.pop() {
-            let parent_node = &mut self.arena[sorted_arena_idxs[sorted_idx.usize()]];
+        // Init worklist with full range (balanced subtree root), children not yet expanded
+        let mut subtree_worklist =
+            StackVec::<[(NodeRebuildHelper<U>, bool); REBUILD_WORKLIST_CAP]>::default();
+        subtree_worklist.push((NodeRebuildHelper::new(0, sorted_last_idx), false));
 
-            parent_node.set_left_idx(None);
-            parent_node.set_right_idx(None);
-
-            // Set left child
-            if parent_nrh.low_idx < parent_nrh.mid_idx {
-                let child_nrh: NodeRebuildHelper<U> = NodeRebuildHelper::new(
+        // Iteratively re-assign all children.
+        // Post-order, so a node's derived data is recomputed only after both of its children are finished.
+        while let Some((parent_nrh, children_done)) = subtree_worklist.pop() {
+            let opt_left_nrh = (parent_nrh.low_idx < parent_nrh.mid_idx).then(|| {
+                NodeRebuildHelper::<U>::new(
                     parent_nrh.low_idx.usize(),
                     parent_nrh.mid_idx.usize() - 1,
-                );
-                parent_node.set_left_idx(Some(sorted_arena_idxs[child_nrh.mid_idx.usize()]));
-                subtree_worklist.push((child_nrh.mid_idx, child_nrh));
-            }
-
-            // Set right child
-            if parent_nrh.mid_idx < parent_nrh.high_idx {
-                let child_nrh: NodeRebuildHelper<U> = NodeRebuildHelper::new(
+                )
+            });
+            let opt_right_nrh = (parent_nrh.mid_idx < parent_nrh.high_idx).then(|| {
+                NodeRebuildHelper::<U>::new(
                     parent_nrh.mid_idx.usize() + 1,
                     parent_nrh.high_idx.usize(),
-                );
-                parent_node.set_right_idx(Some(sorted_arena_idxs[child_nrh.mid_idx.usize()]));
-                subtree_worklist.push((child_nrh.mid_idx, child_nrh));
+                )
+            });
+
+            if !children_done {
+                subtree_worklist.push((parent_nrh, true));
+                subtree_worklist.extend(opt_left_nrh.map(|nrh| (nrh, false)));
+                subtree_worklist.extend(opt_right_nrh.map(|nrh| (nrh, false)));
+                continue;
             }
 
+            let arena_idx_of =
+                |nrh: &NodeRebuildHelper<U>| sorted_arena_idxs[nrh.mid_idx.usize()].usize();
+            let parent_idx = arena_idx_of(&parent_nrh);
+            let parent_node = &mut self.arena[parent_idx];
+
+            parent_node.set_left_idx(opt_left_nrh.as_ref().map(arena_idx_of));
+            parent_node.set_right_idx(opt_right_nrh.as_ref().map(arena_idx_of));
+
             // Set subtree size
             #[cfg(feature = "fast_rebalance")]
             {
@@ -1300,30 +3808,108 @@ impl<K: Ord + Default, V: Default, const N: usize> SgTree<K, V, N> {
                     .set_subtree_size(parent_nrh.high_idx.usize() - parent_nrh.low_idx.usize() + 1);
                 debug_assert!(parent_node.subtree_size() >= 1);
             }
+
+            self.augment_node(parent_idx);
         }
 
         debug_assert!(
-            self.get_subtree_size::<U>(subtree_root_arena_idx) == (sorted_arena_idxs.len()),
+            self.get_subtree_size(subtree_root_arena_idx) == (sorted_arena_idxs.len()),
             "Internal invariant failed: rebalance changed node count! {} -> {}",
-            self.get_subtree_size::<U>(subtree_root_arena_idx),
+            self.get_subtree_size(subtree_root_arena_idx),
             sorted_arena_idxs.len()
         );
+
+        // Ancestors keep the same descendants, but shape-dependent derived data (e.g. Merkle digests)
+        // still sees a new child, so refresh the root path too
+        if A::ENABLED {
+            self.augment_root_path(subtree_root_arena_idx);
+        }
+    }
+
+    // Recompute a single node's derived data from its children, if augmentation is enabled.
+    fn augment_node(&mut self, idx: usize) {
+        if A::ENABLED {
+            let (node, opt_left, opt_right) = self.arena.node_and_children_mut(idx);
+            let (key, val) = node.get_mut();
+            A::augment(
+                key,
+                val,
+                opt_left.map(|n| n.val()),
+                opt_right.map(|n| n.val()),
+            );
+        }
+    }
+
+    // Recompute derived data for a node and all of its ancestors, bottom-up.
+    pub(crate) fn augment_root_path(&mut self, idx: usize) {
+        let mut path = Arena::<K, V, U, N>::new_idx_vec();
+        let key = self.arena[idx].key();
+        let ngh: NodeGetHelper<U> = self.priv_get(Some(&mut path), key);
+        debug_assert_eq!(ngh.node_idx(), Some(idx));
+
+        // Path holds parents only
+        self.augment_node(idx);
+        for path_idx in path.iter().rev() {
+            self.augment_node((*path_idx).usize());
+        }
+    }
+
+    // Integer ratio for an `f32` alpha, see `set_rebal_param`. `None` if out of range (or not a number).
+    fn f32_alpha_ratio(alpha_num: f32, alpha_denom: f32) -> Option<(u32, u32)> {
+        let whole = |x: f32| match (0.0..(u32::MAX as f32)).contains(&x) && ((x as u32) as f32 == x)
+        {
+            true => Some(x as u32),
+            false => None,
+        };
+
+        match (whole(alpha_num), whole(alpha_denom)) {
+            (Some(num), Some(denom)) => Some((num, denom)),
+            _ => {
+                let a = alpha_num / alpha_denom;
+                match (0.5..1.0).contains(&a) {
+                    true => Some(((a * F32_ALPHA_DENOM as f32) as u32, F32_ALPHA_DENOM)),
+                    false => None,
+                }
+            }
+        }
     }
 
     // Alpha weight balance computation helper.
-    fn alpha_balance_depth(&self, val: usize) -> usize {
-        // log base (1/alpha), hence (denom/num)
-        (val as f32).log(self.alpha_denom / self.alpha_num).floor() as usize
+    pub(crate) fn alpha_balance_depth(&self, val: usize) -> usize {
+        // `a == 1.0` only during bulk load, never rebalance
+        if self.alpha_num >= self.alpha_denom {
+            return usize::MAX;
+        }
+
+        // floor(log base (1/alpha)), e.g. how many times `val` can be scaled by alpha before dropping below `1`.
+        // Fixed-point with up to 32 fractional bits, so rounding error stays negligible and no float math is needed.
+        // `u64` throughout: `x * num / denom` is split as `(x / denom) * num + (x % denom) * num / denom`,
+        // exact and overflow-free because `num < denom <= u32::MAX`, so no 128-bit (software) division on 32-bit targets.
+        let (num, denom) = (u64::from(self.alpha_num), u64::from(self.alpha_denom));
+        let val = val as u64;
+        let frac_bits = val.leading_zeros().min(32);
+        let one = 1_u64 << frac_bits;
+        let mut scaled = val << frac_bits;
+        let mut depth = 0;
+
+        loop {
+            scaled = ((scaled / denom) * num) + (((scaled % denom) * num) / denom);
+            if scaled < one {
+                return depth;
+            }
+            depth += 1;
+        }
     }
 }
 
 // Convenience Traits --------------------------------------------------------------------------------------------------
 
 // Debug
-impl<K, V, const N: usize> Debug for SgTree<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Debug
+    for SgTree<K, V, N, U, C, A>
 where
-    K: Ord + Debug + Default,
-    V: Debug + Default,
+    K: Ord + Debug,
+    V: Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_map().entries(self.iter()).finish()
@@ -1331,21 +3917,21 @@ where
 }
 
 // Default
-impl<K, V, const N: usize> Default for SgTree<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Default
+    for SgTree<K, V, N, U, C, A>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
+    C: Default,
 {
     fn default() -> Self {
         Self::new()
     }
 }
 
-// From array
+// From array (default index width only, keeps `SgTree::from([...])` annotation-free)
 impl<K, V, const N: usize> From<[(K, V); N]> for SgTree<K, V, N>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     fn from(arr: [(K, V); N]) -> Self {
         IntoIterator::into_iter(arr).collect()
@@ -1365,15 +3951,14 @@ TODO: Currently a limitation of Rust's trait system? No workaround?
 See issue from 2018: https://github.com/rust-lang/rust/issues/50133#issuecomment-64690839
 
 // TryFrom array
-impl<K, V, const N: usize> TryFrom<[(K, V); N]> for SgTree<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> TryFrom<[(K, V); N]> for SgTree<K, V, N, U, C, A>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     type Error = SgError;
 
     fn try_from(arr: [(K, V); N]) -> Result<Self, Self::Error> {
-        match arr.len() <= Idx::MAX {
+        match arr.len() <= U::MAX {
             true => Ok(IntoIterator::into_iter(arr).collect()),
             false => Err(SgError::StackCapacityExceeded)
         }
@@ -1382,11 +3967,12 @@ where
 */
 
 // Indexing
-impl<K, V, Q, const N: usize> Index<&Q> for SgTree<K, V, N>
+impl<K, V, Q, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Index<&Q>
+    for SgTree<K, V, N, U, C, A>
 where
-    K: Borrow<Q> + Ord + Default,
-    Q: Ord + ?Sized,
-    V: Default,
+    K: Borrow<Q> + Ord,
+    Q: ?Sized,
+    C: Compare<Q>,
 {
     type Output = V;
 
@@ -1401,10 +3987,10 @@ where
 }
 
 // Extension from iterator.
-impl<K, V, const N: usize> Extend<(K, V)> for SgTree<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Extend<(K, V)>
+    for SgTree<K, V, N, U, C, A>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
         iter.into_iter().for_each(move |(k, v)| {
@@ -1415,10 +4001,11 @@ where
 }
 
 // Extension from reference iterator.
-impl<'a, K, V, const N: usize> Extend<(&'a K, &'a V)> for SgTree<K, V, N>
+impl<'a, K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>>
+    Extend<(&'a K, &'a V)> for SgTree<K, V, N, U, C, A>
 where
-    K: Ord + Copy + Default,
-    V: Copy + Default,
+    K: Ord + Copy,
+    V: Copy,
 {
     fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
         self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
@@ -1426,51 +4013,56 @@ where
 }
 
 // PartialEq
-impl<K, V, const N: usize> PartialEq for SgTree<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> PartialEq
+    for SgTree<K, V, N, U, C, A>
 where
-    K: Ord + PartialEq + Default,
-    V: PartialEq + Default,
+    K: Ord + PartialEq,
+    V: PartialEq,
 {
-    fn eq(&self, other: &SgTree<K, V, N>) -> bool {
+    fn eq(&self, other: &SgTree<K, V, N, U, C, A>) -> bool {
         self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
     }
 }
 
 // Eq
-impl<K, V, const N: usize> Eq for SgTree<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Eq
+    for SgTree<K, V, N, U, C, A>
 where
-    K: Ord + Eq + Default,
-    V: Eq + Default,
+    K: Ord + Eq,
+    V: Eq,
 {
 }
 
 // PartialOrd
-impl<K, V, const N: usize> PartialOrd for SgTree<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> PartialOrd
+    for SgTree<K, V, N, U, C, A>
 where
-    K: Ord + PartialOrd + Default,
-    V: PartialOrd + Default,
+    K: Ord + PartialOrd,
+    V: PartialOrd,
 {
-    fn partial_cmp(&self, other: &SgTree<K, V, N>) -> Option<Ordering> {
+    fn partial_cmp(&self, other: &SgTree<K, V, N, U, C, A>) -> Option<Ordering> {
         self.iter().partial_cmp(other.iter())
     }
 }
 
 // Ord
-impl<K, V, const N: usize> Ord for SgTree<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Ord
+    for SgTree<K, V, N, U, C, A>
 where
-    K: Ord + Default,
-    V: Ord + Default,
+    K: Ord,
+    V: Ord,
 {
-    fn cmp(&self, other: &SgTree<K, V, N>) -> Ordering {
+    fn cmp(&self, other: &SgTree<K, V, N, U, C, A>) -> Ordering {
         self.iter().cmp(other.iter())
     }
 }
 
 // Hash
-impl<K, V, const N: usize> Hash for SgTree<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> Hash
+    for SgTree<K, V, N, U, C, A>
 where
-    K: Ord + Hash + Default,
-    V: Hash + Default,
+    K: Ord + Hash,
+    V: Hash,
 {
     fn hash<H: Hasher>(&self, state: &mut H) {
         for i in self {
@@ -1482,10 +4074,11 @@ where
 // Iterators -----------------------------------------------------------------------------------------------------------
 
 // Construct from iterator.
-impl<K, V, const N: usize> FromIterator<(K, V)> for SgTree<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> FromIterator<(K, V)>
+    for SgTree<K, V, N, U, C, A>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
+    C: Default,
 {
     fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
         let mut sgt = SgTree::new();
@@ -1500,13 +4093,13 @@ where
 }
 
 // Reference iterator, mutable
-impl<'a, K, V, const N: usize> IntoIterator for &'a mut SgTree<K, V, N>
+impl<'a, K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> IntoIterator
+    for &'a mut SgTree<K, V, N, U, C, A>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     type Item = (&'a K, &'a mut V);
-    type IntoIter = IterMut<'a, K, V, N>;
+    type IntoIter = IterMut<'a, K, V, N, U>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter_mut()
@@ -1514,13 +4107,13 @@ where
 }
 
 // Reference iterator, immutable
-impl<'a, K, V, const N: usize> IntoIterator for &'a SgTree<K, V, N>
+impl<'a, K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> IntoIterator
+    for &'a SgTree<K, V, N, U, C, A>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     type Item = (&'a K, &'a V);
-    type IntoIter = Iter<'a, K, V, N>;
+    type IntoIter = Iter<'a, K, V, N, U, C, A>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
@@ -1528,13 +4121,13 @@ where
 }
 
 // Consuming iterator
-impl<K, V, const N: usize> IntoIterator for SgTree<K, V, N>
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>, A: AugmentHook<K, V>> IntoIterator
+    for SgTree<K, V, N, U, C, A>
 where
-    K: Ord + Default,
-    V: Default,
+    K: Ord,
 {
     type Item = (K, V);
-    type IntoIter = IntoIter<K, V, N>;
+    type IntoIter = IntoIter<K, V, N, U, C, A>;
 
     fn into_iter(self) -> Self::IntoIter {
         IntoIter::new(self)
@@ -0,0 +1,331 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+
+use crate::tree::{ArenaIdx, AugmentHook, Compare, Natural, SgError, SgTree, SmallNode};
+use crate::ttl_types::Iter;
+
+/// Ordered map whose entries each carry an expiry tick.
+///
+/// Ticks are caller-defined (e.g. milliseconds since boot, or a frame counter), entries don't expire on their own:
+/// they're evicted by [`expire_before`][crate::SgTtlMap::expire_before] (in bulk)
+/// or [`pop_expired`][crate::SgTtlMap::pop_expired] (one at a time). Until then, lookups still find them.
+///
+/// Each node stores the earliest expiry in its subtree, so lookups by key stay `O(log n)`,
+/// [`next_expiry`][crate::SgTtlMap::next_expiry] and [`pop_expired`][crate::SgTtlMap::pop_expired] run in `O(log n)` time,
+/// and [`expire_before`][crate::SgTtlMap::expire_before] is `O(1)` when nothing has expired.
+///
+/// ### Fallible APIs
+///
+/// * [`try_insert`][crate::SgTtlMap::try_insert]
+///
+/// # Examples
+///
+/// ```
+/// use scapegoat::SgTtlMap;
+///
+/// let mut cache = SgTtlMap::<&str, u32, 10>::new();
+/// let now = 100;
+/// cache.insert("session", 1, now + 30);
+/// cache.insert("token", 2, now + 10);
+/// cache.insert("nonce", 3, now + 5);
+///
+/// assert_eq!(cache.next_expiry(), Some((&"nonce", &3, 105)));
+///
+/// // Later on
+/// let now = 120;
+/// assert_eq!(cache.expire_before(now), 2);
+/// assert!(cache.iter().eq([(&"session", &1)]));
+///
+/// // Refresh the survivor
+/// assert!(cache.set_expiry(&"session", now + 30));
+/// assert_eq!(cache.get_expiry(&"session"), Some(150));
+/// ```
+pub struct SgTtlMap<K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    pub(crate) bst: SgTree<K, TtlVal<V>, N, U, C, UpdateMinExpiry>,
+}
+
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> SgTtlMap<K, V, N, U, C> {
+    /// Makes a new, empty `SgTtlMap`.
+    pub fn new() -> Self
+    where
+        C: Default,
+    {
+        Self::with_comparator(C::default())
+    }
+
+    /// Makes a new, empty `SgTtlMap` ordered by `cmp` instead of `K`'s [`Ord`] implementation.
+    /// See [`Compare`][crate::Compare].
+    pub fn with_comparator(cmp: C) -> Self {
+        SgTtlMap {
+            bst: SgTree::with_comparator(cmp),
+        }
+    }
+
+    /// Total capacity, e.g. maximum number of key-value pairs.
+    pub fn capacity(&self) -> usize {
+        self.bst.capacity()
+    }
+
+    /// Returns the number of elements in the map, including any that have expired but haven't been evicted yet.
+    pub fn len(&self) -> usize {
+        self.bst.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.bst.is_empty()
+    }
+
+    /// Returns `true` if the map's capacity is filled.
+    pub fn is_full(&self) -> bool {
+        self.bst.is_full()
+    }
+
+    /// Clears the map, removing all elements.
+    pub fn clear(&mut self) {
+        self.bst.clear();
+    }
+
+    /// Insert a key-value pair into the map, expiring at tick `expires_at`.
+    /// If the map did not have this key present, `None` is returned.
+    /// If the map did have this key present, the value and expiry are updated, the old value is returned,
+    /// and the key is updated. This accommodates types that can be `==` without being identical.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map is full and the key isn't already present.
+    /// See [`try_insert`][crate::SgTtlMap::try_insert] for a fallible alternative.
+    pub fn insert(&mut self, key: K, val: V, expires_at: u64) -> Option<V> {
+        self.bst
+            .insert(key, TtlVal::new(expires_at, val))
+            .map(|tv| tv.val)
+    }
+
+    /// Insert a key-value pair into the map, expiring at tick `expires_at`.
+    /// Returns `Err` if the map is full and the key isn't already present.
+    pub fn try_insert(&mut self, key: K, val: V, expires_at: u64) -> Result<Option<V>, SgError> {
+        self.bst
+            .try_insert(key, TtlVal::new(expires_at, val))
+            .map(|opt_tv| opt_tv.map(|tv| tv.val))
+    }
+
+    /// Returns a reference to the value corresponding to the key, whether or not it has expired.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get(key).map(|tv| &tv.val)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key. Doesn't affect its expiry.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get_mut(key).map(|tv| &mut tv.val)
+    }
+
+    /// Returns the expiry tick of the key, if present.
+    pub fn get_expiry<Q>(&self, key: &Q) -> Option<u64>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.get(key).map(|tv| tv.expires_at)
+    }
+
+    /// Changes the expiry tick of the key, e.g. to refresh its time-to-live.
+    /// Returns `false` if the key isn't present.
+    pub fn set_expiry<Q>(&mut self, key: &Q, expires_at: u64) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        match self.bst.priv_get::<Q, U>(None, key).node_idx() {
+            Some(idx) => {
+                self.bst.arena[idx].get_mut().1.expires_at = expires_at;
+                self.bst.augment_root_path(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key, whether or not it has expired.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.contains_key(key)
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        C: Compare<Q>,
+    {
+        self.bst.remove(key).map(|tv| tv.val)
+    }
+
+    /// Returns the key-value pair that expires first, along with its expiry tick, `None` if empty. `O(log n)`.
+    /// Ties are broken by key order.
+    pub fn next_expiry(&self) -> Option<(&K, &V, u64)> {
+        self.next_expiry_idx().map(|idx| {
+            let node = &self.bst.arena[idx];
+            (node.key(), &node.val().val, node.val().expires_at)
+        })
+    }
+
+    /// Removes and returns the key-value pair that expires first, if it expires before tick `now`. `O(log n)`.
+    /// Call repeatedly to evict expired pairs one at a time, e.g. to inspect or recycle each.
+    pub fn pop_expired(&mut self, now: u64) -> Option<(K, V)> {
+        let idx = self
+            .next_expiry_idx()
+            .filter(|idx| self.bst.arena[*idx].val().expires_at < now)?;
+        let (key, tv) = self.bst.priv_remove_by_idx(idx)?;
+        self.bst.rebuild_if_sparse();
+        Some((key, tv.val))
+    }
+
+    /// Removes every pair that expires before tick `now`, returning how many were removed.
+    /// `O(1)` if nothing has expired, otherwise a single in-order pass with a single rebuild (like `retain`).
+    pub fn expire_before(&mut self, now: u64) -> usize {
+        let min_expiry = match self.bst.opt_root_idx {
+            Some(root_idx) => self.bst.arena[root_idx].val().min_expiry,
+            None => return 0,
+        };
+
+        if min_expiry >= now {
+            return 0;
+        }
+
+        let prev_len = self.len();
+        self.bst.retain(|_, tv| tv.expires_at >= now);
+        prev_len - self.len()
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    pub fn iter(&self) -> Iter<'_, K, V, N, U, C> {
+        Iter::new(self)
+    }
+
+    // Follow the subtree minimum down to the node that holds it.
+    fn next_expiry_idx(&self) -> Option<usize> {
+        let mut idx = self.bst.opt_root_idx?;
+        loop {
+            let node = &self.bst.arena[idx];
+            let min_expiry = node.val().min_expiry;
+            let in_subtree = |opt_idx: Option<usize>| {
+                opt_idx.filter(|&i| self.bst.arena[i].val().min_expiry == min_expiry)
+            };
+
+            match (in_subtree(node.left_idx()), in_subtree(node.right_idx())) {
+                (Some(left_idx), _) => idx = left_idx,
+                (None, Some(right_idx)) if node.val().expires_at != min_expiry => idx = right_idx,
+                _ => return Some(idx),
+            }
+        }
+    }
+}
+
+// Convenience Traits --------------------------------------------------------------------------------------------------
+
+// Clone
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Clone for SgTtlMap<K, V, N, U, C>
+where
+    K: Ord + Clone,
+    V: Clone,
+    C: Clone,
+{
+    fn clone(&self) -> Self {
+        SgTtlMap {
+            bst: self.bst.clone(),
+        }
+    }
+}
+
+// Debug
+impl<K, V, const N: usize, U: ArenaIdx, C: Compare<K>> Debug for SgTtlMap<K, V, N, U, C>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Default
+impl<K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K> + Default> Default
+    for SgTtlMap<K, V, N, U, C>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Iterators -----------------------------------------------------------------------------------------------------------
+
+// Reference iterator
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> IntoIterator
+    for &'a SgTtlMap<K, V, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, N, U, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Internal Types ------------------------------------------------------------------------------------------------------
+
+// Value plus its expiry tick, and the earliest expiry tick in its subtree.
+#[derive(Clone, Debug)]
+pub(crate) struct TtlVal<V> {
+    pub(crate) val: V,
+    expires_at: u64,
+    min_expiry: u64,
+}
+
+impl<V> TtlVal<V> {
+    fn new(expires_at: u64, val: V) -> Self {
+        TtlVal {
+            val,
+            expires_at,
+            min_expiry: expires_at,
+        }
+    }
+}
+
+/// Augmentation callback, maintained by the tree through every insert, remove, and rebuild.
+#[derive(Clone, Copy)]
+pub(crate) struct UpdateMinExpiry;
+
+impl<K, V> AugmentHook<K, TtlVal<V>> for UpdateMinExpiry {
+    fn augment(
+        _key: &K,
+        node: &mut TtlVal<V>,
+        opt_left: Option<&TtlVal<V>>,
+        opt_right: Option<&TtlVal<V>>,
+    ) {
+        node.min_expiry = [opt_left, opt_right]
+            .iter()
+            .flatten()
+            .map(|child| child.min_expiry)
+            .fold(node.expires_at, u64::min);
+    }
+}
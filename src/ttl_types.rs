@@ -0,0 +1,58 @@
+use core::iter::FusedIterator;
+
+use crate::tree::{ArenaIdx, Compare, Iter as TreeIter, Natural};
+use crate::ttl::{SgTtlMap, TtlVal, UpdateMinExpiry};
+
+// General Iterators ---------------------------------------------------------------------------------------------------
+
+/// An iterator over the entries of a [`SgTtlMap`][crate::SgTtlMap], sorted by key.
+///
+/// This `struct` is created by the [`iter`][crate::SgTtlMap::iter] method on [`SgTtlMap`][crate::SgTtlMap].
+/// See its documentation for more.
+pub struct Iter<'a, K: Ord, V, const N: usize, U: ArenaIdx = u16, C: Compare<K> = Natural> {
+    ref_iter: TreeIter<'a, K, TtlVal<V>, N, U, C, UpdateMinExpiry>,
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iter<'a, K, V, N, U, C> {
+    /// Construct reference iterator.
+    pub(crate) fn new(tmap: &'a SgTtlMap<K, V, N, U, C>) -> Self {
+        Iter {
+            ref_iter: TreeIter::new(&tmap.bst),
+        }
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> Iterator
+    for Iter<'a, K, V, N, U, C>
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next().map(|(k, tv)| (k, &tv.val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ref_iter.size_hint()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> DoubleEndedIterator
+    for Iter<'a, K, V, N, U, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ref_iter.next_back().map(|(k, tv)| (k, &tv.val))
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> ExactSizeIterator
+    for Iter<'a, K, V, N, U, C>
+{
+    fn len(&self) -> usize {
+        self.ref_iter.len()
+    }
+}
+
+impl<'a, K: Ord, V, const N: usize, U: ArenaIdx, C: Compare<K>> FusedIterator
+    for Iter<'a, K, V, N, U, C>
+{
+}
@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::ops::Bound::{self, Excluded, Included, Unbounded};
+
+use scapegoat::{Augment, Reversed, SgAugmentedMap, SgError};
+
+use rand::Rng;
+
+const DEFAULT_CAPACITY: usize = 100;
+
+// Subtree sum and count, e.g. for range averages
+#[derive(Clone, Debug, PartialEq)]
+struct SumCnt {
+    sum: u64,
+    cnt: usize,
+}
+
+impl<K> Augment<K, u64> for SumCnt {
+    fn augment(_key: &K, val: &u64, left: Option<&Self>, right: Option<&Self>) -> Self {
+        [left, right]
+            .iter()
+            .flatten()
+            .fold(SumCnt { sum: *val, cnt: 1 }, |acc, child| SumCnt {
+                sum: acc.sum + child.sum,
+                cnt: acc.cnt + child.cnt,
+            })
+    }
+}
+
+// Order-sensitive: keys concatenated in-order, catches shape-dependent bugs
+#[derive(Clone, Debug, PartialEq)]
+struct Concat(String);
+
+impl Augment<char, ()> for Concat {
+    fn augment(key: &char, _val: &(), left: Option<&Self>, right: Option<&Self>) -> Self {
+        let mut s = left.map(|l| l.0.clone()).unwrap_or_default();
+        s.push(*key);
+        s.push_str(right.map_or("", |r| &r.0));
+        Concat(s)
+    }
+}
+
+#[test]
+fn test_debug() {
+    let amap = SgAugmentedMap::<_, _, SumCnt, DEFAULT_CAPACITY>::from_iter([(2, 20), (1, 10)]);
+    assert_eq!(format!("{:?}", amap), "{1: 10, 2: 20}");
+}
+
+#[test]
+fn test_augmented_map_basic() {
+    let mut amap = SgAugmentedMap::<_, _, SumCnt, 3>::new();
+    assert_eq!(amap.aggregate(), None);
+    assert_eq!(amap.range_aggregate(..), None);
+
+    assert_eq!(amap.insert(1, 10), None);
+    assert_eq!(amap.insert(2, 20), None);
+    assert_eq!(amap.insert(2, 25), Some(20));
+    assert_eq!(amap.try_insert(3, 30), Ok(None));
+    assert!(amap.is_full());
+    assert_eq!(amap.try_insert(4, 40), Err(SgError::StackCapacityExceeded));
+
+    assert_eq!(amap.aggregate(), Some(&SumCnt { sum: 65, cnt: 3 }));
+    assert_eq!(amap.range_aggregate(2..), Some(SumCnt { sum: 55, cnt: 2 }));
+    assert_eq!(amap.range_aggregate(4..), None);
+
+    assert!(amap.modify(&1, |v| *v = 0));
+    assert_eq!(amap.get(&1), Some(&0));
+    assert_eq!(amap.aggregate(), Some(&SumCnt { sum: 55, cnt: 3 }));
+
+    assert_eq!(amap.remove(&2), Some(25));
+    assert!(!amap.contains_key(&2));
+    assert_eq!(amap.aggregate(), Some(&SumCnt { sum: 30, cnt: 2 }));
+    assert!(amap.iter().eq([(&1, &0), (&3, &30)]));
+
+    amap.clear();
+    assert!(amap.is_empty());
+    amap.insert(7, 7);
+    assert_eq!(amap.aggregate(), Some(&SumCnt { sum: 7, cnt: 1 }));
+}
+
+#[test]
+fn test_augmented_map_in_order() {
+    let mut amap = SgAugmentedMap::<_, _, Concat, DEFAULT_CAPACITY>::new();
+    for c in "scapegoat".chars() {
+        amap.insert(c, ());
+    }
+
+    assert_eq!(amap.aggregate(), Some(&Concat("acegopst".to_string())));
+    assert_eq!(
+        amap.range_aggregate('d'..'p'),
+        Some(Concat("ego".to_string()))
+    );
+    assert_eq!(
+        amap.range_aggregate('a'..='c'),
+        Some(Concat("ac".to_string()))
+    );
+
+    let mut rev_amap = SgAugmentedMap::<_, _, Concat, DEFAULT_CAPACITY, u16, Reversed>::new();
+    rev_amap.extend("scapegoat".chars().map(|c| (c, ())));
+    assert_eq!(rev_amap.aggregate(), Some(&Concat("tspogeca".to_string())));
+    assert_eq!(
+        rev_amap.range_aggregate('p'..'d'),
+        Some(Concat("poge".to_string()))
+    );
+}
+
+fn rand_bound(rng: &mut impl Rng) -> Bound<u32> {
+    match rng.gen_range(0, 3) {
+        0 => Included(rng.gen_range(0, 200)),
+        1 => Excluded(rng.gen_range(0, 200)),
+        _ => Unbounded,
+    }
+}
+
+fn is_valid_range(start: Bound<u32>, end: Bound<u32>) -> bool {
+    match (start, end) {
+        (Excluded(s), Excluded(e)) => s < e,
+        (Included(s), Included(e)) | (Included(s), Excluded(e)) | (Excluded(s), Included(e)) => {
+            s <= e
+        }
+        _ => true,
+    }
+}
+
+#[test]
+fn test_augmented_map_rand_differential() {
+    let mut rng = rand::thread_rng();
+    let mut amap = SgAugmentedMap::<u32, u64, SumCnt, DEFAULT_CAPACITY>::new();
+    let mut model = BTreeMap::<u32, u64>::new();
+
+    for _ in 0..10_000 {
+        let key = rng.gen_range(0, 200);
+        let val = rng.gen_range(0, 1_000);
+        match rng.gen_range(0, 4) {
+            0 | 1 => {
+                if model.contains_key(&key) || (model.len() < DEFAULT_CAPACITY) {
+                    assert_eq!(amap.insert(key, val), model.insert(key, val));
+                }
+            }
+            2 => {
+                assert_eq!(amap.remove(&key), model.remove(&key));
+            }
+            _ => {
+                let found = amap.modify(&key, |v| *v = val);
+                assert_eq!(found, model.contains_key(&key));
+                if let Some(v) = model.get_mut(&key) {
+                    *v = val;
+                }
+            }
+        }
+
+        let (start, end) = (rand_bound(&mut rng), rand_bound(&mut rng));
+        if is_valid_range(start, end) {
+            let expected = model
+                .range((start, end))
+                .map(|(_, v)| SumCnt { sum: *v, cnt: 1 })
+                .reduce(|a, b| SumCnt {
+                    sum: a.sum + b.sum,
+                    cnt: a.cnt + b.cnt,
+                });
+            assert_eq!(amap.range_aggregate((start, end)), expected);
+        }
+    }
+
+    let total: u64 = model.values().sum();
+    assert_eq!(
+        amap.aggregate(),
+        Some(&SumCnt {
+            sum: total,
+            cnt: model.len()
+        })
+        .filter(|_| !model.is_empty())
+    );
+}
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use scapegoat::{Reversed, SgError, SgMap};
+
+use rand::Rng;
+
+const BASE_CAPACITY: usize = 100;
+const DELTA_CAPACITY: usize = 16;
+
+#[test]
+fn test_debug() {
+    let base = SgMap::from([(1, "a"), (2, "b")]);
+    let mut fork = base.fork::<2>();
+    fork.insert(0, "c");
+    fork.remove(&2);
+    assert_eq!(format!("{:?}", fork), r#"{0: "c", 1: "a"}"#);
+}
+
+#[test]
+fn test_fork_basic() {
+    let base = SgMap::from([(1, 'a'), (2, 'b'), (3, 'c')]);
+    let mut fork = base.fork::<2>();
+    assert_eq!(fork.delta_capacity(), 2);
+    assert!(fork.iter().eq(base.iter()));
+
+    // Overwrite, then remove, re-uses the same slot
+    assert_eq!(fork.try_insert(2, 'x'), Ok(false));
+    assert_eq!(fork.try_remove(&2), Ok(true));
+    assert_eq!(fork.try_remove(&2), Ok(false));
+    assert_eq!(fork.try_insert(2, 'y'), Ok(true));
+    assert_eq!(fork.delta_len(), 1);
+
+    // Fork-only entry, removal frees its slot
+    assert_eq!(fork.try_insert(4, 'd'), Ok(true));
+    assert_eq!(fork.try_insert(5, 'e'), Err(SgError::StackCapacityExceeded));
+    assert_eq!(fork.try_remove(&3), Err(SgError::StackCapacityExceeded));
+    assert_eq!(fork.try_remove(&4), Ok(true));
+    assert_eq!(fork.try_remove(&3), Ok(true));
+
+    assert_eq!(fork.len(), 2);
+    assert_eq!(fork.iter().len(), 2);
+    assert!(fork.iter().eq([(&1, &'a'), (&2, &'y')]));
+    assert!(base.iter().eq([(&1, &'a'), (&2, &'b'), (&3, &'c')]));
+
+    fork.revert();
+    assert!(fork.iter().eq(base.iter()));
+}
+
+#[test]
+fn test_fork_custom_comparator() {
+    let base: SgMap<_, _, 4, u16, Reversed> = (0..4).map(|k| (k, k)).collect();
+    let mut fork = base.fork::<4>();
+    fork.insert(10, 10);
+    fork.remove(&0);
+    assert!(fork.iter().map(|(k, _)| *k).eq([10, 3, 2, 1]));
+}
+
+#[test]
+fn test_fork_rand_differential() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..100 {
+        let base: SgMap<u32, u32, BASE_CAPACITY> = (0..BASE_CAPACITY)
+            .map(|_| (rng.gen_range(0, 200), rng.gen()))
+            .collect();
+        let mut fork = base.fork::<DELTA_CAPACITY>();
+        let mut model: BTreeMap<_, _> = base.iter().map(|(k, v)| (*k, *v)).collect();
+
+        for _ in 0..100 {
+            let key = rng.gen_range(0, 200);
+            if rng.gen() {
+                let val = rng.gen();
+                if let Ok(is_new) = fork.try_insert(key, val) {
+                    assert_eq!(is_new, model.insert(key, val).is_none());
+                }
+            } else if let Ok(was_present) = fork.try_remove(&key) {
+                assert_eq!(was_present, model.remove(&key).is_some());
+            }
+
+            assert_eq!(fork.get(&key), model.get(&key));
+            assert!(fork.delta_len() <= DELTA_CAPACITY);
+        }
+
+        assert_eq!(fork.len(), model.len());
+        assert!(fork.iter().eq(model.iter()));
+
+        // Applying the delta to a copy of the base reproduces the fork
+        let mut applied = base.clone();
+        for (key, opt_val) in fork.into_delta() {
+            match opt_val {
+                Some(val) => applied.insert(key, val),
+                None => applied.remove(&key),
+            };
+        }
+        assert!(applied.into_iter().eq(model.into_iter()));
+    }
+}
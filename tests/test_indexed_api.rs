@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use scapegoat::{SgError, SgIndexedMap};
+
+use rand::Rng;
+
+const DEFAULT_CAPACITY: usize = 100;
+
+#[test]
+fn test_debug() {
+    let imap: SgIndexedMap<_, _, DEFAULT_CAPACITY> = [(2, 10), (1, 20)].iter().copied().collect();
+    assert_eq!(format!("{:?}", imap), "{1: 20, 2: 10}");
+}
+
+#[test]
+fn test_indexed_map_basic() {
+    let mut imap = SgIndexedMap::<usize, usize, 4>::new();
+    assert_eq!(imap.min_by_value(), None);
+    assert_eq!(imap.max_by_value(), None);
+    assert_eq!(imap.pop_min_by_value(), None);
+    assert_eq!(imap.iter_by_value().next(), None);
+
+    assert_eq!(imap.insert(1, 30), None);
+    assert_eq!(imap.insert(2, 10), None);
+    assert_eq!(imap.insert(3, 20), None);
+    assert_eq!(imap.try_insert(4, 10), Ok(None));
+    assert!(imap.is_full());
+    assert_eq!(imap.try_insert(5, 0), Err(SgError::StackCapacityExceeded));
+
+    // Ties broken by key
+    assert!(imap
+        .iter_by_value()
+        .eq([(&2, &10), (&4, &10), (&3, &20), (&1, &30)]));
+    assert!(imap.iter_by_value().rev().map(|(k, _)| *k).eq([1, 3, 4, 2]));
+    assert_eq!(imap.iter_by_value().len(), 4);
+    assert!(imap.iter().map(|(k, _)| *k).eq([1, 2, 3, 4]));
+
+    // Re-insertion re-indexes
+    assert_eq!(imap.try_insert(2, 40), Ok(Some(10)));
+    assert_eq!(imap.max_by_value(), Some((&2, &40)));
+    assert_eq!(imap.min_by_value(), Some((&4, &10)));
+
+    // Ranges
+    assert!(imap.range_by_value(15..=30).map(|(k, _)| *k).eq([3, 1]));
+    assert!(imap.range_by_value(..20).map(|(k, _)| *k).eq([4]));
+    assert!(imap.range_by_value(41..).next().is_none());
+    assert!(imap.range_by_value(21..21).next().is_none());
+
+    // Modification re-indexes
+    assert!(imap.modify(&4, |v| *v = 50));
+    assert!(!imap.modify(&5, |v| *v = 50));
+    assert_eq!(imap.get(&4), Some(&50));
+    assert!(imap.iter_by_value().map(|(k, _)| *k).eq([3, 1, 2, 4]));
+
+    assert_eq!(imap.pop_min_by_value(), Some((3, 20)));
+    assert_eq!(imap.pop_max_by_value(), Some((4, 50)));
+    assert_eq!(imap.remove(&1), Some(30));
+    assert_eq!(imap.remove(&1), None);
+    assert!(imap.contains_key(&2));
+    assert_eq!(imap.len(), 1);
+
+    imap.clear();
+    assert!(imap.is_empty());
+    assert_eq!(imap.iter_by_value().next(), None);
+}
+
+#[test]
+fn test_indexed_rand_ops() {
+    let mut rng = rand::thread_rng();
+    let mut imap = SgIndexedMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    let mut bt_map = BTreeMap::new();
+
+    for _ in 0..2_000 {
+        let k = rng.gen_range(0, DEFAULT_CAPACITY * 2);
+        let v = rng.gen_range(0, DEFAULT_CAPACITY / 4);
+        match rng.gen_range(0, 6) {
+            0 | 1 if !imap.is_full() || imap.contains_key(&k) => {
+                assert_eq!(imap.insert(k, v), bt_map.insert(k, v));
+            }
+            2 => {
+                assert_eq!(imap.modify(&k, |val| *val = v), bt_map.contains_key(&k));
+                if let Some(val) = bt_map.get_mut(&k) {
+                    *val = v;
+                }
+            }
+            3 => {
+                assert_eq!(imap.remove(&k), bt_map.remove(&k));
+            }
+            4 => {
+                let expected = bt_map.iter().map(|(k, v)| (*v, *k)).min();
+                if let Some((_, k)) = expected {
+                    bt_map.remove(&k);
+                }
+                assert_eq!(imap.pop_min_by_value(), expected.map(|(v, k)| (k, v)));
+            }
+            _ => {
+                let (lo, hi) = (v, v + rng.gen_range(0, 10));
+                let mut expected: Vec<_> = bt_map
+                    .iter()
+                    .filter(|(_, v)| (lo..hi).contains(*v))
+                    .map(|(k, v)| (*v, *k))
+                    .collect();
+                expected.sort_unstable();
+                assert!(imap
+                    .range_by_value(lo..hi)
+                    .map(|(k, v)| (*v, *k))
+                    .eq(expected));
+            }
+        }
+
+        let mut by_val: Vec<_> = bt_map.iter().map(|(k, v)| (*v, *k)).collect();
+        by_val.sort_unstable();
+        assert!(imap.iter().eq(bt_map.iter()));
+        assert!(imap.iter_by_value().map(|(k, v)| (*v, *k)).eq(by_val));
+    }
+}
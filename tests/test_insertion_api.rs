@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+use scapegoat::{SgError, SgInsertionMap};
+
+use rand::Rng;
+
+const DEFAULT_CAPACITY: usize = 100;
+
+#[test]
+fn test_debug() {
+    let imap = SgInsertionMap::<_, _, DEFAULT_CAPACITY>::from_iter([(2, 20), (1, 10)]);
+    assert_eq!(format!("{:?}", imap), "{1: 10, 2: 20}");
+}
+
+#[test]
+fn test_insertion_map_basic() {
+    let mut imap = SgInsertionMap::<usize, usize, 3>::new();
+    assert_eq!(imap.oldest(), None);
+    assert_eq!(imap.pop_oldest(), None);
+
+    assert_eq!(imap.insert(3, 30), None);
+    assert_eq!(imap.insert(1, 10), None);
+    assert_eq!(imap.insert(3, 35), Some(30));
+    assert_eq!(imap.try_insert(2, 20), Ok(None));
+    assert!(imap.is_full());
+    assert_eq!(imap.try_insert(4, 40), Err(SgError::StackCapacityExceeded));
+
+    // Update keeps original position
+    assert!(imap.iter().eq([(&1, &10), (&2, &20), (&3, &35)]));
+    assert!(imap
+        .iter_insertion_order()
+        .eq([(&3, &35), (&1, &10), (&2, &20)]));
+    assert!(imap
+        .iter_insertion_order()
+        .rev()
+        .eq([(&2, &20), (&1, &10), (&3, &35)]));
+    assert_eq!(imap.iter_insertion_order().len(), 3);
+
+    // Re-insertion after removal moves to back
+    *imap.get_mut(&1).unwrap() += 1;
+    assert_eq!(imap.remove(&3), Some(35));
+    assert_eq!(imap.insert(3, 30), None);
+    assert_eq!(imap.oldest(), Some((&1, &11)));
+    assert_eq!(imap.pop_oldest(), Some((1, 11)));
+    assert_eq!(imap.pop_oldest(), Some((2, 20)));
+    assert!(imap.contains_key(&3));
+    assert_eq!(imap.get(&3), Some(&30));
+
+    imap.clear();
+    assert!(imap.is_empty());
+    assert_eq!(imap.iter_insertion_order().next(), None);
+}
+
+#[test]
+fn test_insertion_rand_ops() {
+    let mut rng = rand::thread_rng();
+    let mut imap = SgInsertionMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    let mut bt_map = BTreeMap::new();
+    let mut order = Vec::new();
+
+    for _ in 0..2_000 {
+        let k = rng.gen_range(0, DEFAULT_CAPACITY * 2);
+        match rng.gen_range(0, 4) {
+            0 | 1 if !imap.is_full() || imap.contains_key(&k) => {
+                let v = rng.gen::<usize>();
+                if bt_map.insert(k, v).is_none() {
+                    order.push(k);
+                }
+                imap.insert(k, v);
+            }
+            2 => {
+                assert_eq!(imap.remove(&k), bt_map.remove(&k));
+                order.retain(|o| *o != k);
+            }
+            _ => {
+                let expected = match order.is_empty() {
+                    true => None,
+                    false => Some(order.remove(0)),
+                };
+                let popped = imap.pop_oldest().map(|(k, _)| k);
+                assert_eq!(popped, expected);
+                if let Some(k) = popped {
+                    bt_map.remove(&k);
+                }
+            }
+        }
+
+        assert!(imap.iter().eq(bt_map.iter()));
+        assert!(imap
+            .iter_insertion_order()
+            .map(|(k, _)| *k)
+            .eq(order.iter().copied()));
+        assert_eq!(imap.oldest().map(|(k, _)| *k), order.first().copied());
+    }
+}
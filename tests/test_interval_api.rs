@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::ops::Range;
+
+use scapegoat::{SgError, SgIntervalMap};
+
+use rand::Rng;
+
+const DEFAULT_CAPACITY: usize = 100;
+
+#[test]
+fn test_debug() {
+    let imap = SgIntervalMap::<_, _, DEFAULT_CAPACITY>::from_iter([(3..5, "b"), (1..9, "a")]);
+    assert_eq!(format!("{:?}", imap), r#"{1..9: "a", 3..5: "b"}"#);
+}
+
+#[test]
+fn test_interval_map_basic() {
+    let mut imap = SgIntervalMap::<_, _, 3>::new();
+    assert!(imap.is_empty());
+    assert_eq!(imap.capacity(), 3);
+
+    assert_eq!(imap.insert(10..20, 'a'), None);
+    assert_eq!(imap.insert(10..15, 'b'), None);
+    assert_eq!(imap.insert(10..20, 'c'), Some('a'));
+    assert_eq!(imap.try_insert(0..1, 'd'), Ok(None));
+    assert!(imap.is_full());
+    assert_eq!(
+        imap.try_insert(0..2, 'e'),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert_eq!(imap.try_insert(0..1, 'e'), Ok(Some('d')));
+
+    assert_eq!(imap.len(), 3);
+    assert!(imap.contains_range(&(10..15)));
+    assert_eq!(imap.get(&(10..20)), Some(&'c'));
+    *imap.get_mut(&(10..15)).unwrap() = 'f';
+    assert!(imap
+        .iter()
+        .eq([(&(0..1), &'e'), (&(10..15), &'f'), (&(10..20), &'c')]));
+
+    assert_eq!(imap.remove(&(10..20)), Some('c'));
+    assert_eq!(imap.remove(&(10..20)), None);
+    assert!(imap.stab(&17).next().is_none());
+
+    imap.clear();
+    assert!(imap.is_empty());
+    imap.insert(5..6, 'g');
+    assert!(imap.stab(&5).eq([(&(5..6), &'g')]));
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn test_interval_map_edges() {
+    let imap = SgIntervalMap::<_, _, DEFAULT_CAPACITY>::from_iter([
+        (0..5, 'a'),
+        (5..10, 'b'),
+        (7..7, 'c'), // Empty, never matches
+        (9..3, 'd'), // Inverted, never matches
+    ]);
+
+    // Half-open: touching isn't overlapping
+    assert!(imap.overlaps(5..6).eq([(&(5..10), &'b')]));
+    assert!(imap.overlaps(4..5).eq([(&(0..5), &'a')]));
+    assert!(imap.stab(&5).eq([(&(5..10), &'b')]));
+    assert!(imap.stab(&7).eq([(&(5..10), &'b')]));
+
+    // Empty queries match nothing
+    assert_eq!(imap.overlaps(6..6).next(), None);
+    assert_eq!(imap.overlaps(8..2).next(), None);
+
+    assert_eq!(imap.overlaps(0..100).count(), 2);
+    assert_eq!(imap.len(), 4);
+}
+
+// Reference: linear scan, ordered by (start, end)
+fn scan<'a>(
+    model: &'a BTreeMap<(u32, u32), usize>,
+    pred: impl Fn(u32, u32) -> bool + 'a,
+) -> impl Iterator<Item = (Range<u32>, usize)> + 'a {
+    model
+        .iter()
+        .filter(move |((s, e), _)| (s < e) && pred(*s, *e))
+        .map(|((s, e), v)| (*s..*e, *v))
+}
+
+#[test]
+fn test_interval_map_rand_differential() {
+    let mut rng = rand::thread_rng();
+    let mut imap = SgIntervalMap::<u32, usize, DEFAULT_CAPACITY>::new();
+    let mut model = BTreeMap::<(u32, u32), usize>::new();
+
+    for op in 0..10_000 {
+        let start = rng.gen_range(0, 200);
+        let end = start + rng.gen_range(0, 30);
+        match rng.gen_range(0, 3) {
+            0 | 1 => {
+                let res = imap.try_insert(start..end, op);
+                match model.contains_key(&(start, end)) || (model.len() < DEFAULT_CAPACITY) {
+                    true => assert_eq!(res, Ok(model.insert((start, end), op))),
+                    false => assert_eq!(res, Err(SgError::StackCapacityExceeded)),
+                }
+            }
+            _ => {
+                // Remove an existing range, if any, to keep removals frequent
+                let opt_key = model
+                    .keys()
+                    .nth(rng.gen_range(0, model.len().max(1)))
+                    .copied();
+                if let Some((s, e)) = opt_key {
+                    assert_eq!(imap.remove(&(s..e)), model.remove(&(s, e)));
+                }
+            }
+        }
+
+        let (q_start, q_end) = (rng.gen_range(0, 240), rng.gen_range(0, 240));
+        let expected = scan(&model, |s, e| {
+            (q_start < q_end) && (s < q_end) && (q_start < e)
+        });
+        assert!(imap
+            .overlaps(q_start..q_end)
+            .map(|(r, v)| (r.clone(), *v))
+            .eq(expected));
+
+        let expected = scan(&model, |s, e| (s <= q_start) && (q_start < e));
+        assert!(imap
+            .stab(&q_start)
+            .map(|(r, v)| (r.clone(), *v))
+            .eq(expected));
+    }
+
+    assert_eq!(imap.len(), model.len());
+    assert!(imap
+        .iter()
+        .map(|(r, v)| ((r.start, r.end), *v))
+        .eq(model.into_iter()));
+}
@@ -1,8 +1,10 @@
 use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use std::iter::FromIterator;
+use std::mem::MaybeUninit;
 use std::ops::Bound::{Excluded, Included};
 
-use scapegoat::{SgError, SgMap};
+use scapegoat::{CapacityError, Compare, DupPolicy, EvictPolicy, Reversed, SgError, SgMap};
 
 use rand::Rng;
 
@@ -30,6 +32,91 @@ fn test_clone() {
     assert_eq!(sgm_1, sgm_2);
 }
 
+#[test]
+fn test_non_default_types() {
+    use std::num::NonZeroU32;
+
+    #[derive(Debug, PartialEq)]
+    struct Handle(NonZeroU32);
+
+    let key = |k| NonZeroU32::new(k).unwrap();
+    let mut sgm = SgMap::<NonZeroU32, Handle, DEFAULT_CAPACITY>::new();
+
+    for k in 1..=5 {
+        assert!(sgm.insert(key(k), Handle(key(k * 10))).is_none());
+    }
+
+    assert_eq!(sgm.insert(key(3), Handle(key(300))), Some(Handle(key(30))));
+    assert_eq!(sgm.remove(&key(1)), Some(Handle(key(10))));
+    assert_eq!(sgm.pop_last(), Some((key(5), Handle(key(50)))));
+    assert!(sgm.into_iter().eq([
+        (key(2), Handle(key(20))),
+        (key(3), Handle(key(300))),
+        (key(4), Handle(key(40)))
+    ]));
+}
+
+#[test]
+fn test_custom_comparator() {
+    use std::cmp::Ordering;
+
+    // Reverse order
+    let mut rev = SgMap::<u32, u32, DEFAULT_CAPACITY, u16, Reversed>::new();
+    let mut reference = BTreeMap::new();
+    for k in [5, 1, 9, 3, 7, 2, 8] {
+        assert_eq!(rev.insert(k, k * 10), reference.insert(k, k * 10));
+    }
+
+    assert!(rev.iter().eq(reference.iter().rev()));
+    assert_eq!(rev.first_key_value(), Some((&9, &90)));
+    assert_eq!(rev.last_key_value(), Some((&1, &10)));
+    assert!(rev
+        .range((Included(8), Included(3)))
+        .map(|(k, _)| *k)
+        .eq([8, 7, 5, 3]));
+    assert!(rev
+        .range_mut((Included(7), Excluded(2)))
+        .map(|(k, _)| *k)
+        .eq([7, 5, 3]));
+    assert_eq!(rev.remove(&5), Some(50));
+
+    let tail = rev.split_off(&3);
+    assert!(rev.keys().eq(&[9, 8, 7]));
+    assert!(tail.keys().eq(&[3, 2, 1]));
+
+    // Case-insensitive, supporting borrowed lookups
+    #[derive(Clone, Default)]
+    struct CaseInsensitive;
+
+    impl Compare<str> for CaseInsensitive {
+        fn compare(&self, a: &str, b: &str) -> Ordering {
+            let lower = |s: &str| {
+                s.bytes()
+                    .map(|c| c.to_ascii_lowercase())
+                    .collect::<Vec<_>>()
+            };
+            lower(a).cmp(&lower(b))
+        }
+    }
+
+    impl Compare<String> for CaseInsensitive {
+        fn compare(&self, a: &String, b: &String) -> Ordering {
+            self.compare(a.as_str(), b.as_str())
+        }
+    }
+
+    let mut ci = SgMap::<String, u32, DEFAULT_CAPACITY, u16, CaseInsensitive>::new();
+    ci.insert("Beta".to_string(), 2);
+    ci.insert("alpha".to_string(), 1);
+    ci.insert("GAMMA".to_string(), 3);
+
+    assert_eq!(ci.insert("BETA".to_string(), 20), Some(2));
+    assert_eq!(ci.len(), 3);
+    assert_eq!(ci.get("gamma"), Some(&3));
+    assert!(ci.contains_key("ALPHA"));
+    assert!(ci.values().eq(&[1, 20, 3]));
+}
+
 #[test]
 fn test_basic_map_functionality() {
     let mut sgm = SgMap::<_, _, DEFAULT_CAPACITY>::new();
@@ -111,7 +198,7 @@ fn test_basic_map_functionality() {
 #[test]
 fn test_map_from_iter() {
     let key_val_tuples = vec![(1, "1"), (2, "2"), (3, "3")];
-    let sgm = SgMap::<_, _, 3>::from_iter(key_val_tuples.into_iter());
+    let sgm = SgMap::<_, _, 3>::from_iter(key_val_tuples);
 
     assert!(sgm.len() == 3);
     assert_eq!(
@@ -127,10 +214,83 @@ fn test_map_from_iter_panic() {
         SgMap::from_iter((0..(DEFAULT_CAPACITY + 1)).map(|val| (val, val)));
 }
 
+#[test]
+fn test_map_try_from_iter() {
+    let sgm = SgMap::<_, _, DEFAULT_CAPACITY>::try_from_iter(
+        vec![(1, "1"), (2, "2"), (1, "3")].into_iter(),
+    )
+    .unwrap();
+    assert_eq!(
+        sgm.into_iter().collect::<Vec<_>>(),
+        vec![(1, "3"), (2, "2")]
+    );
+
+    // Fails cleanly instead of panicking like `from_iter`
+    assert_eq!(
+        SgMap::<usize, usize, DEFAULT_CAPACITY>::try_from_iter(
+            (0..(DEFAULT_CAPACITY + 1)).map(|val| (val, val))
+        ),
+        Err(SgError::StackCapacityExceeded)
+    );
+
+    // Duplicates don't count against capacity
+    let sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::try_from_iter(
+        (0..(DEFAULT_CAPACITY * 2)).map(|val| (val % DEFAULT_CAPACITY, val)),
+    )
+    .unwrap();
+    assert_eq!(sgm.len(), DEFAULT_CAPACITY);
+    assert_eq!(sgm.first_key_value(), Some((&0, &DEFAULT_CAPACITY)));
+}
+
+#[test]
+fn test_map_try_from_slice() {
+    let pairs: Vec<(usize, usize)> = (0..(DEFAULT_CAPACITY + 1)).map(|n| (n, n)).collect();
+
+    // Shorter than capacity
+    let sgm = SgMap::<_, _, DEFAULT_CAPACITY>::try_from(&pairs[..3]).unwrap();
+    assert!(sgm.into_iter().eq(pairs[..3].iter().cloned()));
+
+    // Longer than capacity
+    assert_eq!(
+        SgMap::<_, _, DEFAULT_CAPACITY>::try_from(&pairs[..]),
+        Err(SgError::StackCapacityExceeded)
+    );
+
+    #[cfg(feature = "alloc")]
+    {
+        let sgm = SgMap::<_, _, DEFAULT_CAPACITY>::try_from(pairs[..3].to_vec()).unwrap();
+        assert!(sgm.into_iter().eq(pairs[..3].iter().cloned()));
+        assert_eq!(
+            SgMap::<_, _, DEFAULT_CAPACITY>::try_from(pairs),
+            Err(SgError::StackCapacityExceeded)
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_map_btreemap_conversion() {
+    let btm: BTreeMap<usize, usize> = (0..DEFAULT_CAPACITY).map(|n| (n, n * 2)).collect();
+
+    let sgm = SgMap::<_, _, DEFAULT_CAPACITY>::try_from(btm.clone()).unwrap();
+    assert!(sgm.iter().eq(btm.iter()));
+    assert_eq!(BTreeMap::from(sgm), btm);
+
+    // Custom ordering doesn't carry over
+    let sgm = SgMap::<_, _, DEFAULT_CAPACITY, u16, Reversed>::try_from(btm.clone()).unwrap();
+    assert_eq!(sgm.first_key_value(), Some((&(DEFAULT_CAPACITY - 1), &18)));
+    assert_eq!(BTreeMap::from(sgm), btm);
+
+    assert_eq!(
+        SgMap::<_, _, { DEFAULT_CAPACITY - 1 }>::try_from(btm),
+        Err(SgError::StackCapacityExceeded)
+    );
+}
+
 #[test]
 fn test_map_iter() {
     let key_val_tuples = vec![(1, "1"), (2, "2"), (3, "3")];
-    let sgm = SgMap::<_, _, 3>::from_iter(key_val_tuples.into_iter());
+    let sgm = SgMap::<_, _, 3>::from_iter(key_val_tuples);
     let mut sgm_iter = sgm.iter();
 
     assert_eq!(sgm_iter.next(), Some((&1, &"1")));
@@ -139,6 +299,98 @@ fn test_map_iter() {
     assert_eq!(sgm_iter.next(), None);
 }
 
+#[test]
+fn test_map_double_ended_iters() {
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::from_iter((0..7).map(|k| (k, k * 10)));
+    let mut btm = BTreeMap::from_iter((0..7).map(|k| (k, k * 10)));
+
+    assert!(sgm.iter().rev().eq(btm.iter().rev()));
+    assert!(sgm.keys().rev().eq(btm.keys().rev()));
+    assert!(sgm.values().rev().eq(btm.values().rev()));
+    assert!(sgm.iter_mut().rev().eq(btm.iter_mut().rev()));
+    assert!(sgm.values_mut().rev().eq(btm.values_mut().rev()));
+    assert!(sgm
+        .clone()
+        .into_iter()
+        .rev()
+        .eq(btm.clone().into_iter().rev()));
+    assert!(sgm
+        .clone()
+        .into_keys()
+        .rev()
+        .eq(btm.clone().into_keys().rev()));
+    assert!(sgm
+        .clone()
+        .into_values()
+        .rev()
+        .eq(btm.clone().into_values().rev()));
+
+    // Alternating ends meet in the middle, without yielding any item twice
+    let mut sgm_iter = sgm.iter();
+    let mut btm_iter = btm.iter();
+    for i in 0..8 {
+        if i % 2 == 0 {
+            assert_eq!(sgm_iter.next(), btm_iter.next());
+        } else {
+            assert_eq!(sgm_iter.next_back(), btm_iter.next_back());
+        }
+        assert_eq!(sgm_iter.len(), btm_iter.len());
+    }
+
+    // Back end first used after the front end has passed the root
+    let mut sgm_iter = sgm.iter();
+    let mut btm_iter = btm.iter();
+    for _ in 1..sgm.len() {
+        assert_eq!(sgm_iter.next(), btm_iter.next());
+    }
+    assert_eq!(sgm_iter.next_back(), btm_iter.next_back());
+    assert_eq!(sgm_iter.next_back(), None);
+    assert_eq!(sgm_iter.next(), None);
+
+    let mut sgm_into_iter = sgm.clone().into_iter();
+    let mut btm_into_iter = btm.clone().into_iter();
+    assert_eq!(sgm_into_iter.next_back(), btm_into_iter.next_back());
+    assert_eq!(sgm_into_iter.next(), btm_into_iter.next());
+    assert_eq!(sgm_into_iter.len(), btm_into_iter.len());
+    assert!(sgm_into_iter.eq(btm_into_iter));
+
+    // `last` respects items already taken from the back
+    let mut sgm_keys = sgm.keys();
+    sgm_keys.next_back();
+    assert_eq!(sgm_keys.last(), Some(&5));
+}
+
+#[test]
+fn test_map_exact_size_hint() {
+    fn assert_exact<I: ExactSizeIterator>(mut iter: I) {
+        loop {
+            let len = iter.len();
+            assert_eq!(iter.size_hint(), (len, Some(len)));
+            if iter.next().is_none() {
+                assert_eq!(len, 0);
+                assert!(iter.next().is_none()); // Fused
+                break;
+            }
+        }
+    }
+
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::from_iter((0..7).map(|k| (k, k)));
+
+    assert_exact(sgm.iter());
+    assert_exact(sgm.keys());
+    assert_exact(sgm.values());
+    assert_exact(sgm.iter_mut());
+    assert_exact(sgm.values_mut());
+    assert_exact(sgm.iter_levels());
+    assert_exact(sgm.clone().into_iter());
+    assert_exact(sgm.clone().into_keys());
+    assert_exact(sgm.clone().into_values());
+
+    let range_mut = sgm.range_mut(2..5);
+    assert_eq!(range_mut.size_hint(), (3, Some(3)));
+    assert_eq!(range_mut.count(), 3);
+}
+
 #[test]
 fn test_map_iter_mut() {
     let key_val_tuples = vec![
@@ -152,7 +404,7 @@ fn test_map_iter_mut() {
         ("c", 3),
     ];
 
-    let mut sgm = SgMap::<_, _, 8>::from_iter(key_val_tuples.into_iter());
+    let mut sgm = SgMap::<_, _, 8>::from_iter(key_val_tuples);
     assert_eq!(sgm.len(), 8);
     assert_eq!(sgm.first_key_value(), Some((&"a", &1)));
     assert_eq!(sgm.last_key_value(), Some((&"h", &8)));
@@ -212,6 +464,49 @@ fn test_map_iter_mut_rand() {
     assert!(result_vec.iter().all(|(_, v)| *v == 25));
 }
 
+#[test]
+fn test_map_split_range_mut() {
+    let mut sgm: SgMap<_, _, 100> = (0..100).filter(|k| k % 3 != 0).map(|k| (k, k)).collect();
+
+    // Split key absent, processed concurrently
+    let len = sgm.len();
+    let (before, after) = sgm.split_range_mut(&30);
+    assert_eq!(before.len() + after.len(), len);
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            before.for_each(|(k, v)| {
+                assert!(*k < 30);
+                *v = -*v;
+            })
+        });
+        s.spawn(|| {
+            after.for_each(|(k, v)| {
+                assert!(*k > 30);
+                *v *= 10;
+            })
+        });
+    });
+
+    assert!(sgm
+        .iter()
+        .all(|(k, v)| if *k < 30 { *v == -k } else { *v == k * 10 }));
+
+    // Split key present, and at either end
+    let (mut before, after) = sgm.split_range_mut(&31);
+    assert!(after.map(|(k, _)| *k).eq((31..100).filter(|k| k % 3 != 0)));
+    assert_eq!(before.next_back().map(|(k, _)| *k), Some(29));
+
+    let (before, after) = sgm.split_range_mut(&0);
+    assert_eq!((before.len(), after.len()), (0, len));
+
+    let (before, after) = sgm.split_range_mut(&100);
+    assert_eq!((before.len(), after.len()), (len, 0));
+
+    let mut empty = SgMap::<u8, u8, 4>::new();
+    let (before, after) = empty.split_range_mut(&1);
+    assert_eq!((before.len(), after.len()), (0, 0));
+}
+
 #[test]
 fn test_map_append() {
     let mut a = SgMap::new();
@@ -236,6 +531,599 @@ fn test_map_append() {
     );
 }
 
+#[test]
+fn test_map_swap_values() {
+    let mut sgm: SgMap<_, _, DEFAULT_CAPACITY> = (0..8).map(|k| (k, k * 10)).collect();
+    assert!(sgm.swap_values(&1, &6));
+    assert_eq!(sgm[&1], 60);
+    assert_eq!(sgm[&6], 10);
+
+    // Same key is a no-op
+    assert!(sgm.swap_values(&3, &3));
+    assert_eq!(sgm[&3], 30);
+
+    // Missing key leaves map unmodified
+    let before = sgm.clone();
+    assert!(!sgm.swap_values(&0, &100));
+    assert!(!sgm.swap_values(&100, &0));
+    assert_eq!(sgm, before);
+
+    assert_eq!(sgm.len(), 8);
+}
+
+#[test]
+fn test_map_rename_key() {
+    let mut sgm = SgMap::<_, _, 3>::from([(1, "1"), (2, "2"), (3, "3")]);
+    let mut btm = BTreeMap::from([(1, "1"), (2, "2"), (3, "3")]);
+
+    assert_eq!(sgm.rename_key(&1, 10), Ok(()));
+    let val = btm.remove(&1).unwrap();
+    btm.insert(10, val);
+    assert!(sgm.iter().eq(btm.iter()));
+
+    // Full map, still succeeds
+    assert_eq!(sgm.rename_key(&10, 0), Ok(()));
+    let val = btm.remove(&10).unwrap();
+    btm.insert(0, val);
+    assert!(sgm.iter().eq(btm.iter()));
+
+    // Same key
+    assert_eq!(sgm.rename_key(&2, 2), Ok(()));
+    assert!(sgm.iter().eq(btm.iter()));
+
+    // Errors, no modification
+    assert_eq!(sgm.rename_key(&2, 3), Err(SgError::KeyAlreadyExists));
+    assert_eq!(sgm.rename_key(&4, 5), Err(SgError::KeyNotFound));
+    assert!(sgm.iter().eq(btm.iter()));
+}
+
+#[test]
+fn test_map_split_off_into() {
+    let mut sgm: SgMap<_, _, DEFAULT_CAPACITY> = (0..8).map(|k| (k, k)).collect();
+    let mut btm: BTreeMap<_, _> = (0..8).map(|k| (k, k)).collect();
+
+    // Re-used destination, with an overlapping key
+    let mut sgm_dest = SgMap::<_, _, DEFAULT_CAPACITY>::from_iter([(6, 60), (100, 100)]);
+    let mut btm_dest = BTreeMap::from_iter([(6, 60), (100, 100)]);
+
+    assert_eq!(sgm.split_off_into(&4, &mut sgm_dest), Ok(()));
+    btm_dest.append(&mut btm.split_off(&4));
+
+    assert!(sgm.iter().eq(btm.iter()));
+    assert!(sgm_dest.iter().eq(btm_dest.iter()));
+    assert_eq!(sgm_dest.len(), 5);
+
+    // Nothing to move
+    assert_eq!(sgm.split_off_into(&50, &mut sgm_dest), Ok(()));
+    assert!(sgm.iter().eq(btm.iter()));
+    assert!(sgm_dest.iter().eq(btm_dest.iter()));
+
+    // Bulk removal re-tightens the height bound to the remaining length
+    let mut sgm: SgMap<_, _, 1024> = (0..1024).map(|k| (k, k)).collect();
+    let mut sgm_dest = SgMap::<_, _, 1024>::new();
+    assert_eq!(sgm.split_off_into(&16, &mut sgm_dest), Ok(()));
+
+    let sgm_fresh: SgMap<_, _, 1024> = (0..16).map(|k| (k, k)).collect();
+    let report = sgm.check_invariants();
+    assert!(sgm.iter().eq(sgm_fresh.iter()));
+    assert!(report.is_valid());
+    assert_eq!(report.max_height, sgm_fresh.check_invariants().max_height);
+    assert!(report.height <= report.max_height);
+}
+
+#[test]
+fn test_map_extract_if() {
+    let mut rng = rand::thread_rng();
+    let mut sgm = SgMap::<usize, usize, 1024>::new();
+    let mut btm = BTreeMap::new();
+
+    while sgm.len() < 1024 {
+        let k = rng.gen::<usize>();
+        sgm.insert(k, k);
+        btm.insert(k, k);
+    }
+
+    // Kept values may be mutated
+    let divisor = rng.gen_range(2, 8);
+    let extracted: Vec<_> = sgm
+        .extract_if(|k, v| {
+            *v += 1;
+            k % divisor == 0
+        })
+        .collect();
+    let expected: Vec<_> = btm
+        .iter()
+        .filter(|(k, _)| *k % divisor == 0)
+        .map(|(k, v)| (*k, *v + 1))
+        .collect();
+    btm.retain(|k, v| {
+        *v += 1;
+        k % divisor != 0
+    });
+
+    assert_eq!(extracted, expected);
+    assert!(sgm.iter().eq(btm.iter()));
+
+    // Partial iteration retains the rest
+    let first_two: Vec<_> = sgm.extract_if(|_, _| true).take(2).collect();
+    assert_eq!(first_two.len(), 2);
+    assert_eq!(sgm.len(), btm.len() - 2);
+    assert!(sgm.iter().eq(btm.iter().skip(2)));
+}
+
+#[test]
+fn test_map_drain() {
+    let mut sgm: SgMap<_, _, DEFAULT_CAPACITY> = (0..10).map(|k| (k, k.to_string())).collect();
+
+    let mut drain = sgm.drain();
+    assert_eq!(drain.len(), 10);
+    assert_eq!(drain.next(), Some((0, "0".to_string())));
+    assert_eq!(drain.next_back(), Some((9, "9".to_string())));
+    assert_eq!(drain.len(), 8);
+    drop(drain);
+
+    // Un-yielded pairs go with the iterator, freed slots are reusable
+    assert!(sgm.is_empty());
+    assert_eq!(sgm.first_key_value(), None);
+    for k in (0..DEFAULT_CAPACITY).rev() {
+        assert_eq!(sgm.try_insert(k, k.to_string()), Ok(None));
+    }
+    assert!(sgm.is_full());
+    assert!(sgm.drain().map(|(k, _)| k).eq(0..DEFAULT_CAPACITY));
+    assert!(sgm.is_empty());
+}
+
+#[test]
+fn test_map_memory_usage() {
+    let mut sgm = SgMap::<u64, u64, DEFAULT_CAPACITY>::new();
+    let node_bytes = SgMap::<u64, u64, DEFAULT_CAPACITY>::NODE_SIZE;
+    let check_sum = |sgm: &SgMap<u64, u64, DEFAULT_CAPACITY>| {
+        let usage = sgm.memory_usage();
+        assert_eq!(usage.total_bytes, std::mem::size_of_val(sgm));
+        assert_eq!(usage.node_bytes, node_bytes);
+        assert_eq!(usage.used_bytes, sgm.len() * node_bytes);
+        assert_eq!(
+            usage.used_bytes + usage.free_slot_bytes + usage.spare_bytes + usage.overhead_bytes,
+            usage.total_bytes
+        );
+        usage
+    };
+
+    let empty = check_sum(&sgm);
+    assert_eq!(empty.used_bytes, 0);
+    assert_eq!(empty.free_slot_bytes, 0);
+
+    sgm.extend((0..DEFAULT_CAPACITY as u64).map(|k| (k, k)));
+    let full = check_sum(&sgm);
+    assert_eq!(full.spare_bytes, 0);
+    assert_eq!(full.total_bytes, empty.total_bytes);
+
+    // Holes, until defragmented
+    sgm.retain(|k, _| k % 2 == 0);
+    assert!(check_sum(&sgm).free_slot_bytes > 0);
+    sgm.compact();
+    let compacted = check_sum(&sgm);
+    assert_eq!(compacted.free_slot_bytes, 0);
+    assert_eq!(compacted.spare_bytes, empty.spare_bytes / 2);
+}
+
+#[test]
+fn test_map_digest() {
+    use std::collections::hash_map::DefaultHasher;
+
+    let mut rng = rand::thread_rng();
+    let mut sgm_1 = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    let mut sgm_2 = SgMap::<usize, usize, DEFAULT_CAPACITY>::with_rebal_ratio(1, 2);
+
+    // Same contents, different insertion order and arena layout
+    for k in 0..DEFAULT_CAPACITY {
+        sgm_1.insert(k, k);
+        sgm_2.insert(DEFAULT_CAPACITY - 1 - k, DEFAULT_CAPACITY - 1 - k);
+    }
+    let k = rng.gen_range(0, DEFAULT_CAPACITY);
+    sgm_1.remove(&k);
+    sgm_2.remove(&k);
+    assert_eq!(
+        sgm_1.digest(DefaultHasher::new()),
+        sgm_2.digest(DefaultHasher::new())
+    );
+
+    // Survives storage round trip
+    let mut restored = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    restored.extend(sgm_1.iter().rev().map(|(k, v)| (*k, *v)));
+    assert_eq!(
+        restored.digest(DefaultHasher::new()),
+        sgm_1.digest(DefaultHasher::new())
+    );
+
+    // Value change
+    *sgm_2.values_mut().next().unwrap() += 1;
+    assert_ne!(
+        sgm_1.digest(DefaultHasher::new()),
+        sgm_2.digest(DefaultHasher::new())
+    );
+
+    // Empty maps, different capacities
+    assert_eq!(
+        SgMap::<usize, usize, 1>::new().digest(DefaultHasher::new()),
+        SgMap::<usize, usize, 2>::new().digest(DefaultHasher::new())
+    );
+}
+
+#[test]
+fn test_map_get_many_mut() {
+    let mut rng = rand::thread_rng();
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    sgm.extend((0..DEFAULT_CAPACITY).map(|k| (k, k)));
+    sgm.remove(&0);
+
+    // Order of returned references follows key order, not arena order
+    for _ in 0..100 {
+        let a = rng.gen_range(1, DEFAULT_CAPACITY);
+        let b = rng.gen_range(1, DEFAULT_CAPACITY);
+        let c = rng.gen_range(1, DEFAULT_CAPACITY);
+        match sgm.get_many_mut([&a, &b, &c]) {
+            Some([va, vb, vc]) => {
+                assert!(a != b && b != c && a != c);
+                assert_eq!((*va % 1000, *vb % 1000, *vc % 1000), (a, b, c));
+                *va += 1000;
+            }
+            None => assert!(a == b || b == c || a == c),
+        }
+    }
+
+    assert!(sgm.get_many_mut([&0, &1]).is_none());
+    assert!(sgm.get_many_mut::<usize, 0>([]).is_some());
+    assert!(sgm.iter().all(|(k, v)| v % 1000 == *k));
+}
+
+#[test]
+fn test_map_node_handle() {
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    let handles: Vec<_> = (0..(DEFAULT_CAPACITY - 1))
+        .map(|k| sgm.insert_with_handle(k, k).1)
+        .collect();
+
+    // Survive inserts, updates, and the rebalances they trigger
+    assert_eq!(sgm.insert_with_handle(3, 30), (Some(3), handles[3]));
+    assert_eq!(sgm.get_handle(&3), Some(handles[3]));
+    assert_eq!(
+        sgm.try_insert_with_handle(DEFAULT_CAPACITY, 0).unwrap().0,
+        None
+    );
+    assert_eq!(
+        sgm.try_insert_with_handle(DEFAULT_CAPACITY + 1, 0),
+        Err(SgError::StackCapacityExceeded)
+    );
+    for (k, h) in handles.iter().enumerate() {
+        *sgm.get_mut_by_handle(*h).unwrap() += 100;
+        assert_eq!(sgm.get_by_handle(*h).unwrap().0, &k);
+    }
+    assert_eq!(sgm[&3], 130);
+
+    // Removal invalidates every handle, including the removed one
+    assert_eq!(sgm.remove_by_handle(handles[0]), Some((0, 100)));
+    assert!(handles.iter().all(|h| sgm.get_by_handle(*h).is_none()));
+    assert_eq!(sgm.remove_by_handle(handles[1]), None);
+    assert_eq!(sgm.len(), DEFAULT_CAPACITY - 1);
+
+    // Fresh handles work, until the map is cleared
+    let h = sgm.get_handle(&1).unwrap();
+    assert_eq!(sgm.get_by_handle(h), Some((&1, &101)));
+    sgm.clear();
+    sgm.insert(1, 1);
+    assert_eq!(sgm.get_by_handle(h), None);
+    assert_eq!(sgm.get_handle(&0), None);
+}
+
+#[test]
+fn test_map_insert_at_end() {
+    const CAPACITY: usize = 500;
+    let mut rng = rand::thread_rng();
+    let mut sgm = SgMap::<usize, usize, CAPACITY>::new();
+    let mut bt_map = BTreeMap::new();
+
+    // Nearly sorted, ascending then descending, with occasional repeats and stale hints
+    let (_, mut hint) = sgm.insert_with_handle(CAPACITY, 0);
+    bt_map.insert(CAPACITY, 0);
+    for i in 0..(CAPACITY - 50) {
+        let k = match i % 2 {
+            0 => CAPACITY + i + rng.gen_range(0, 4),
+            _ => CAPACITY - i,
+        };
+        if i % 97 == 0 {
+            sgm.remove(&k);
+            bt_map.remove(&k);
+        }
+
+        let (old, h) = sgm.insert_at_end(hint, k, i);
+        assert_eq!(old, bt_map.insert(k, i));
+        assert_eq!(sgm.get_by_handle(h), Some((&k, &i)));
+        hint = h;
+    }
+
+    assert!(sgm.iter().eq(bt_map.iter()));
+    assert!(sgm.check_invariants().is_valid());
+
+    // Fallible variant only fails on a new key
+    while !sgm.is_full() {
+        hint = sgm.try_insert_at_end(hint, rng.gen(), 0).unwrap().1;
+    }
+    let (k, _) = sgm.get_by_handle(hint).map(|(k, v)| (*k, *v)).unwrap();
+    assert_eq!(sgm.try_insert_at_end(hint, k, 1), Ok((Some(0), hint)));
+    assert_eq!(
+        sgm.try_insert_at_end(hint, CAPACITY * 10, 0),
+        Err(SgError::StackCapacityExceeded)
+    );
+
+    // Rejected at either end too, map unchanged
+    let first = sgm.get_handle(&sgm.first_key_value().map(|(k, _)| *k).unwrap());
+    let last = sgm.get_handle(&sgm.last_key_value().map(|(k, _)| *k).unwrap());
+    assert_eq!(
+        sgm.try_insert_at_end(last.unwrap(), usize::MAX, 0),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert_eq!(
+        sgm.try_insert_at_end(first.unwrap(), 0, 0),
+        Err(SgError::StackCapacityExceeded)
+    );
+    assert_eq!(sgm.len(), CAPACITY);
+}
+
+#[test]
+fn test_map_new_in() {
+    // Stand-in for a linker-placed region, handed out once as `&'static mut`
+    let region: &'static mut MaybeUninit<SgMap<u32, u32, 100>> =
+        Box::leak(Box::new(MaybeUninit::uninit()));
+    let region_addr = region.as_ptr() as usize;
+
+    let sgm: &'static mut SgMap<u32, u32, 100> = SgMap::new_in(region);
+    assert_eq!(sgm as *const _ as usize, region_addr);
+    assert!(sgm.is_empty());
+
+    for i in 0..100 {
+        sgm.insert(i, i * 2);
+    }
+    assert!(sgm.is_full());
+    assert!(sgm.iter().eq((0..100)
+        .map(|i| (i, i * 2))
+        .collect::<BTreeMap<_, _>>()
+        .iter()));
+}
+
+#[test]
+fn test_map_map_values() {
+    let mut rng = rand::thread_rng();
+    let mut sgm = SgMap::<u32, u32, 100>::new();
+    for _ in 0..200 {
+        let k = rng.gen_range(0, 200);
+        match rng.gen::<bool>() {
+            true => sgm.insert(k, k),
+            false => sgm.remove(&k),
+        };
+    }
+
+    let expected: Vec<(u32, String)> = sgm.iter().map(|(k, v)| (*k, format!("v{}", v))).collect();
+    let first_handle = sgm.first_key_value().and_then(|(k, _)| sgm.get_handle(k));
+    let stats = sgm.stats();
+
+    // Visited in key order, same keys and shape, handles still resolve
+    let mut visited = Vec::new();
+    let mapped = sgm.map_values(|k, v| {
+        visited.push(*k);
+        format!("v{}", v)
+    });
+    assert!(visited.iter().eq(expected.iter().map(|(k, _)| k)));
+    assert!(mapped
+        .iter()
+        .map(|(k, v)| (*k, v.clone()))
+        .eq(expected.into_iter()));
+    assert_eq!(mapped.stats(), stats);
+    assert!(mapped.check_invariants().is_valid());
+    if let Some(handle) = first_handle {
+        assert_eq!(
+            mapped.get_by_handle(handle).map(|(k, _)| k),
+            mapped.first_key()
+        );
+    }
+
+    // Still fully usable afterwards
+    let mut mapped = mapped;
+    while !mapped.is_full() {
+        let k = rng.gen_range(200, 400);
+        mapped.insert(k, format!("v{}", k));
+    }
+    assert!(mapped.check_invariants().is_valid());
+
+    let empty = SgMap::<u8, u8, 4>::new().map_values(|_, v| v as u16);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_map_range_prefix() {
+    let mut rng = rand::thread_rng();
+
+    // Small alphabet including the byte-increment edge cases
+    let alphabet = [0x00_u8, 0x01, 0x7f, 0xfe, 0xff];
+    let mut rand_bytes = |max_len: usize| -> Vec<u8> {
+        (0..rng.gen_range(0, max_len + 1))
+            .map(|_| alphabet[rng.gen_range(0, alphabet.len())])
+            .collect()
+    };
+
+    for _ in 0..100 {
+        let mut sgm = SgMap::<Vec<u8>, usize, 64>::new();
+        while !sgm.is_full() {
+            let k = rand_bytes(4);
+            sgm.insert(k, 0);
+        }
+
+        for _ in 0..10 {
+            let prefix = rand_bytes(3);
+            let expected: Vec<_> = sgm.iter().filter(|(k, _)| k.starts_with(&prefix)).collect();
+            assert!(sgm.range_prefix(&prefix).eq(expected.iter().copied()));
+            assert!(sgm
+                .range_prefix(&prefix)
+                .rev()
+                .eq(expected.into_iter().rev()));
+        }
+    }
+
+    let sgm: SgMap<String, (), DEFAULT_CAPACITY> = ["ab", "abc", "abd", "b"]
+        .iter()
+        .map(|s| (s.to_string(), ()))
+        .collect();
+    assert!(sgm
+        .range_prefix("ab")
+        .map(|(k, _)| k)
+        .eq(["ab", "abc", "abd"]));
+    assert_eq!(sgm.range_prefix("abc\u{0}").next(), None);
+}
+
+#[test]
+fn test_map_insert_or_evict() {
+    let mut rng = rand::thread_rng();
+
+    for policy in [EvictPolicy::First, EvictPolicy::Last] {
+        let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+        let mut bt_map = BTreeMap::new();
+
+        for _ in 0..1_000 {
+            let k = rng.gen_range(0, DEFAULT_CAPACITY * 4);
+            let v = rng.gen::<usize>();
+
+            // Model: insert unconditionally, then trim the policy's end
+            let expected_old = bt_map.insert(k, v);
+            let expected_evicted = match bt_map.len() > DEFAULT_CAPACITY {
+                true => match policy {
+                    EvictPolicy::First => bt_map.pop_first(),
+                    EvictPolicy::Last => bt_map.pop_last(),
+                },
+                false => None,
+            };
+
+            assert_eq!(
+                sgm.insert_or_evict(k, v, policy),
+                (expected_old, expected_evicted)
+            );
+            assert!(sgm.iter().eq(bt_map.iter()));
+        }
+    }
+
+    let mut empty = SgMap::<usize, usize, 0>::new();
+    assert_eq!(
+        empty.insert_or_evict(1, 1, EvictPolicy::First),
+        (None, Some((1, 1)))
+    );
+}
+
+#[test]
+fn test_map_transaction() {
+    let mut rng = rand::thread_rng();
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    sgm.extend((0..(DEFAULT_CAPACITY / 2)).map(|k| (k, k)));
+    let orig: Vec<(usize, usize)> = sgm.clone().into_iter().collect();
+
+    // Random batch, rolled back on drop
+    {
+        let mut txn = sgm.transaction::<DEFAULT_CAPACITY>();
+        let mut bt_map: BTreeMap<usize, usize> = orig.iter().cloned().collect();
+        while txn.log_len() < txn.log_capacity() {
+            let k = rng.gen_range(0, DEFAULT_CAPACITY);
+            match rng.gen::<bool>() {
+                true if !txn.is_full() || txn.contains_key(&k) => {
+                    assert_eq!(txn.insert(k, k * 10), bt_map.insert(k, k * 10).is_none());
+                }
+                _ => {
+                    assert_eq!(txn.remove(&k), bt_map.remove(&k).is_some());
+                }
+            }
+            assert!(txn.iter().eq(bt_map.iter()));
+        }
+        assert_eq!(txn.try_insert(0, 0), Err(SgError::StackCapacityExceeded));
+    }
+    assert!(sgm.iter().map(|(k, v)| (*k, *v)).eq(orig.iter().cloned()));
+    assert!(sgm.check_invariants().is_valid());
+
+    // Removing an absent key doesn't take a log slot
+    let mut txn = sgm.transaction::<1>();
+    assert_eq!(txn.try_remove(&DEFAULT_CAPACITY), Ok(false));
+    assert_eq!(txn.try_remove(&0), Ok(true));
+    assert_eq!(txn.try_remove(&1), Err(SgError::StackCapacityExceeded));
+    txn.commit();
+    assert_eq!(sgm.first_key_value(), Some((&1, &1)));
+
+    // Map capacity still applies
+    let free = sgm.capacity() - sgm.len();
+    sgm.extend((DEFAULT_CAPACITY..).take(free).map(|k| (k, k)));
+    assert!(sgm.is_full());
+    let mut txn = sgm.transaction::<4>();
+    assert_eq!(txn.try_insert(0, 0), Err(SgError::StackCapacityExceeded));
+    assert_eq!(txn.log_len(), 0);
+}
+
+#[test]
+fn test_map_with_rebal_ratio() {
+    let mut sgm = SgMap::<_, _, DEFAULT_CAPACITY>::with_rebal_ratio(1, 2);
+    assert_eq!(sgm.rebal_ratio(), (1, 2));
+
+    // Perfect balance maintained from the first insert
+    sgm.extend((0..DEFAULT_CAPACITY).map(|k| (k, k)));
+    assert!(sgm.check_invariants().is_valid());
+
+    assert_eq!(
+        SgMap::<usize, usize, DEFAULT_CAPACITY>::try_with_rebal_ratio(3, 3)
+            .map(|m| m.rebal_ratio()),
+        Err(SgError::RebalanceFactorOutOfRange)
+    );
+}
+
+#[should_panic(expected = "Rebalance factor out of range")]
+#[test]
+fn test_map_with_rebal_ratio_panic() {
+    let _ = SgMap::<usize, usize, DEFAULT_CAPACITY>::with_rebal_ratio(1, 0);
+}
+
+#[test]
+fn test_map_first_last_entry() {
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    let mut btm = BTreeMap::new();
+
+    assert!(sgm.first_entry().is_none());
+    assert!(sgm.last_entry().is_none());
+
+    // Bounded "keep the largest" structure: evict the minimum when full
+    for k in [5, 17, 3, 42, 8, 11, 29, 1, 36, 23, 14, 50, 2, 31] {
+        if sgm.is_full() {
+            let entry = sgm.first_entry().unwrap();
+            if *entry.key() < k {
+                assert_eq!(
+                    entry.remove_entry(),
+                    btm.first_entry().unwrap().remove_entry()
+                );
+            } else {
+                continue;
+            }
+        }
+        sgm.insert(k, k * 10);
+        btm.insert(k, k * 10);
+    }
+
+    assert!(sgm.iter().eq(btm.iter()));
+
+    // In-place update of the maximum
+    let mut sgm_last = sgm.last_entry().unwrap();
+    let mut btm_last = btm.last_entry().unwrap();
+    assert_eq!(sgm_last.key(), btm_last.key());
+    *sgm_last.get_mut() += 1;
+    *btm_last.get_mut() += 1;
+    assert_eq!(sgm_last.remove(), btm_last.remove());
+
+    assert!(sgm.iter().eq(btm.iter()));
+    assert_eq!(sgm.first_key_value(), btm.first_key_value());
+    assert_eq!(sgm.last_key_value(), btm.last_key_value());
+}
+
 // Fallible APIs -------------------------------------------------------------------------------------------------------
 
 #[test]
@@ -250,6 +1138,30 @@ fn test_map_insert_fallible() {
     assert_eq!(a.try_insert(4, "4"), Err(SgError::StackCapacityExceeded));
 }
 
+#[test]
+fn test_map_insert_within_capacity() {
+    // Neither `Clone` nor `Copy`
+    #[derive(Debug, PartialEq)]
+    struct Token(usize);
+
+    let mut a = SgMap::<_, _, 2>::new();
+    let mut b = SgMap::<_, _, 2>::new();
+
+    assert_eq!(a.insert_within_capacity(1, Token(1)), Ok(None));
+    assert_eq!(a.insert_within_capacity(2, Token(2)), Ok(None));
+    assert_eq!(a.insert_within_capacity(1, Token(10)), Ok(Some(Token(1))));
+
+    let err = a.insert_within_capacity(3, Token(3)).unwrap_err();
+    assert_eq!(err, CapacityError((3, Token(3))));
+    assert_eq!(err.to_string(), SgError::StackCapacityExceeded.to_string());
+    assert_eq!(a.len(), 2);
+
+    // Retry the rejected pair elsewhere
+    let (k, v) = err.into_inner();
+    assert_eq!(b.insert_within_capacity(k, v), Ok(None));
+    assert_eq!(b.get(&3), Some(&Token(3)));
+}
+
 #[test]
 fn test_map_append_fallible() {
     let mut a = SgMap::<_, _, 6>::new();
@@ -295,6 +1207,122 @@ fn test_map_append_fallible() {
     );
 }
 
+#[test]
+fn test_map_try_split_off() {
+    let mut sgm: SgMap<_, _, DEFAULT_CAPACITY> = (0..8).map(|k| (k, k)).collect();
+    let mut btm: BTreeMap<_, _> = (0..8).map(|k| (k, k)).collect();
+
+    // Tail doesn't fit, nothing moves
+    assert_eq!(
+        sgm.try_split_off::<_, 3>(&4).unwrap_err(),
+        SgError::StackCapacityExceeded
+    );
+    assert!(sgm.iter().eq(btm.iter()));
+
+    // Exact fit
+    let sgm_tail: SgMap<_, _, 4> = sgm.try_split_off(&4).unwrap();
+    let btm_tail = btm.split_off(&4);
+    assert!(sgm.iter().eq(btm.iter()));
+    assert!(sgm_tail.iter().eq(btm_tail.iter()));
+    assert!(sgm_tail.is_full());
+
+    // Empty tail
+    let sgm_empty: SgMap<_, _, 1> = sgm.try_split_off(&100).unwrap();
+    assert!(sgm_empty.is_empty());
+    assert_eq!(sgm.len(), 4);
+
+    // Bulk removal re-tightens the height bound to the remaining length
+    let mut sgm: SgMap<_, _, 1024> = (0..1024).map(|k| (k, k)).collect();
+    let sgm_tail: SgMap<_, _, 1024> = sgm.try_split_off(&16).unwrap();
+    assert_eq!(sgm_tail.len(), 1024 - 16);
+
+    let sgm_fresh: SgMap<_, _, 1024> = (0..16).map(|k| (k, k)).collect();
+    let report = sgm.check_invariants();
+    assert!(sgm.iter().eq(sgm_fresh.iter()));
+    assert!(report.is_valid());
+    assert_eq!(report.max_height, sgm_fresh.check_invariants().max_height);
+    assert!(report.height <= report.max_height);
+}
+
+#[test]
+fn test_map_split_at_rank() {
+    let mut rng = rand::thread_rng();
+    let mut sgm = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    while !sgm.is_full() {
+        let k = rng.gen::<usize>();
+        sgm.insert(k, k);
+    }
+
+    for n in [
+        0,
+        1,
+        DEFAULT_CAPACITY / 2,
+        DEFAULT_CAPACITY - 1,
+        DEFAULT_CAPACITY,
+    ] {
+        let mut head = sgm.clone();
+        let tail = head.split_at_rank(n);
+        assert_eq!(head.len(), n);
+        assert_eq!(tail.len(), DEFAULT_CAPACITY - n);
+        assert_eq!(tail.first_key_value(), sgm.get_index(n));
+        assert!(head.iter().chain(tail.iter()).eq(sgm.iter()));
+    }
+
+    // Past the end
+    let tail = sgm.split_at_rank(DEFAULT_CAPACITY * 2);
+    assert!(tail.is_empty());
+    assert_eq!(sgm.len(), DEFAULT_CAPACITY);
+}
+
+#[test]
+fn test_map_from_iter_with_policy() {
+    #[derive(Debug, Default, Clone, Copy)]
+    struct LoggedVal {
+        val: usize,
+        line: usize,
+    }
+
+    let log: Vec<(usize, LoggedVal)> = [1, 2, 1, 3, 2, 1]
+        .iter()
+        .enumerate()
+        .map(|(line, val)| (*val, LoggedVal { val: *val, line }))
+        .collect();
+
+    let first =
+        SgMap::<_, _, DEFAULT_CAPACITY>::from_iter_with_policy(log.clone(), DupPolicy::KeepFirst)
+            .unwrap();
+    assert!(first
+        .iter()
+        .map(|(k, v)| (*k, v.line))
+        .eq([(1, 0), (2, 1), (3, 3)]));
+
+    let last =
+        SgMap::<_, _, DEFAULT_CAPACITY>::from_iter_with_policy(log.clone(), DupPolicy::KeepLast)
+            .unwrap();
+    let from_iter = SgMap::<_, _, DEFAULT_CAPACITY>::from_iter(log.clone());
+    assert!(last
+        .iter()
+        .map(|(k, v)| (*k, v.line))
+        .eq([(1, 5), (2, 4), (3, 3)]));
+    assert!(last
+        .iter()
+        .map(|(_, v)| v.line)
+        .eq(from_iter.iter().map(|(_, v)| v.line)));
+    assert!(last.iter().all(|(k, v)| *k == v.val));
+
+    assert_eq!(
+        SgMap::<_, _, DEFAULT_CAPACITY>::from_iter_with_policy(log.clone(), DupPolicy::Error)
+            .map(|m| m.len()),
+        Err(SgError::KeyAlreadyExists)
+    );
+
+    // Capacity is checked, instead of panicking
+    assert_eq!(
+        SgMap::<_, _, 2>::from_iter_with_policy(log, DupPolicy::KeepFirst).map(|m| m.len()),
+        Err(SgError::StackCapacityExceeded)
+    );
+}
+
 #[should_panic]
 #[test]
 fn test_map_insert_panic() {
@@ -310,6 +1338,31 @@ fn test_map_insert_panic() {
 
 // Range APIs ----------------------------------------------------------------------------------------------------------
 
+#[test]
+fn test_map_iter_from() {
+    let mut sgm: SgMap<String, usize, DEFAULT_CAPACITY> = ["ant", "bee", "cat", "dog", "eel"]
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.to_string(), i))
+        .collect();
+
+    // Borrowed key form, present and absent
+    assert!(sgm.iter_from("cat").map(|(_, v)| *v).eq(2..5));
+    assert!(sgm.iter_from("cow").map(|(_, v)| *v).eq(3..5));
+    assert_eq!(sgm.iter_from("zebra").count(), 0);
+    assert_eq!(sgm.iter_from("").count(), 5);
+    assert_eq!(sgm.iter_from("bee").next_back().map(|(_, v)| *v), Some(4));
+
+    for (_, v) in sgm.iter_mut_from("dog") {
+        *v *= 10;
+    }
+    assert!(sgm.values().copied().eq([0, 1, 2, 30, 40]));
+
+    // Follows the comparator's order
+    let rev: SgMap<_, _, DEFAULT_CAPACITY, u16, Reversed> = (0..5).map(|k| (k, ())).collect();
+    assert!(rev.iter_from(&2).map(|(k, _)| *k).eq([2, 1, 0]));
+}
+
 #[test]
 fn test_map_range() {
     let array = [(1, "a"), (5, "e"), (3, "c"), (7, "g"), (9, "i")];
@@ -422,3 +1475,300 @@ fn test_sg_map_range_mut_panic_2() {
     map.insert(8, 8);
     let _bad_range = map.range_mut((Excluded(&5), Excluded(&5)));
 }
+
+#[test]
+fn test_map_copy_range_to_slice() {
+    use std::mem::MaybeUninit;
+
+    let sgm: SgMap<_, _, DEFAULT_CAPACITY> = (0..10).map(|k| (k, k * 10)).collect();
+    let btm: BTreeMap<_, _> = (0..10).map(|k| (k, k * 10)).collect();
+    let mut buf = [MaybeUninit::<(usize, usize)>::uninit(); 4];
+
+    for (range, expected_len) in [(0..10, 4), (2..5, 3), (7..10, 3), (5..5, 0)] {
+        let written = sgm.copy_range_to_slice(range.clone(), &mut buf);
+        assert_eq!(written, expected_len);
+
+        // SAFETY: the first `written` slots are initialized
+        let copied: Vec<_> = buf[..written]
+            .iter()
+            .map(|slot| unsafe { slot.assume_init() })
+            .collect();
+        let expected: Vec<_> = btm.range(range).take(4).map(|(k, v)| (*k, *v)).collect();
+        assert_eq!(copied, expected);
+    }
+}
+
+#[test]
+fn test_map_copy_to_slice() {
+    let sgm: SgMap<_, _, DEFAULT_CAPACITY> = (0..10).rev().map(|k| (k, k * 10)).collect();
+    let btm: BTreeMap<_, _> = (0..10).map(|k| (k, k * 10)).collect();
+
+    // Too small, untouched
+    let mut buf = [(0, 0); 12];
+    assert_eq!(
+        sgm.copy_to_slice(&mut buf[..9]),
+        Err(SgError::BufferTooSmall)
+    );
+    assert!(buf.iter().all(|p| *p == (0, 0)));
+
+    assert_eq!(sgm.copy_to_slice(&mut buf), Ok(10));
+    assert!(buf[..10]
+        .iter()
+        .eq(btm.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>().iter()));
+    assert_eq!(buf[10..], [(0, 0), (0, 0)]);
+
+    #[cfg(feature = "alloc")]
+    assert_eq!(sgm.into_sorted_vec(), btm.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_map_cursor() {
+    use std::ops::Bound::{self, Unbounded};
+
+    let sgm: SgMap<_, _, DEFAULT_CAPACITY> = (0..10).map(|k| (k * 2, k)).collect();
+    let btm: BTreeMap<_, _> = (0..10).map(|k| (k * 2, k)).collect();
+
+    let probes: Vec<usize> = (0..21).collect();
+    let bounds: Vec<Bound<&usize>> = probes
+        .iter()
+        .flat_map(|k| [Included(k), Excluded(k)])
+        .chain([Unbounded])
+        .collect();
+
+    for bound in bounds {
+        // Everything before/after the gap, per std's range semantics
+        let lower_next = btm.range((bound, Unbounded)).next();
+        let lower_prev = match bound {
+            Included(k) => btm.range(..k).next_back(),
+            Excluded(k) => btm.range(..=k).next_back(),
+            Unbounded => None,
+        };
+
+        let cursor = sgm.lower_bound(bound);
+        assert_eq!(cursor.peek_next(), lower_next);
+        assert_eq!(cursor.peek_prev(), lower_prev);
+
+        let upper_prev = btm.range((Unbounded, bound)).next_back();
+        let cursor = sgm.upper_bound(bound);
+        assert_eq!(cursor.peek_prev(), upper_prev);
+    }
+
+    // Walk both ways from the middle
+    let mut cursor = sgm.lower_bound(Included(&9));
+    assert!(std::iter::from_fn(|| cursor.next()).eq(btm.range(9..)));
+    assert_eq!(cursor.next(), None);
+    assert!(std::iter::from_fn(|| cursor.prev()).eq(btm.iter().rev()));
+    assert_eq!(cursor.prev(), None);
+    assert_eq!(cursor.peek_next(), btm.iter().next());
+}
+
+#[test]
+fn test_map_get_prev_next() {
+    let sgm: SgMap<_, _, DEFAULT_CAPACITY> = (0..10).map(|k| (k * 2, k)).collect();
+    let btm: BTreeMap<_, _> = (0..10).map(|k| (k * 2, k)).collect();
+
+    for probe in 0..21 {
+        assert_eq!(sgm.get_prev(&probe), btm.range(..=probe).next_back());
+        assert_eq!(sgm.get_next(&probe), btm.range(probe..).next());
+    }
+
+    // Custom order flips floor and ceiling
+    let rev: SgMap<_, _, DEFAULT_CAPACITY, u16, Reversed> = (0..10).map(|k| (k * 2, k)).collect();
+    assert_eq!(rev.get_prev(&5), Some((&6, &3)));
+    assert_eq!(rev.get_next(&5), Some((&4, &2)));
+    assert_eq!(rev.get_prev(&-1), Some((&0, &0)));
+    assert_eq!(rev.get_next(&-1), None);
+
+    let empty = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    assert_eq!(empty.get_prev(&0), None);
+    assert_eq!(empty.get_next(&0), None);
+}
+
+#[test]
+fn test_map_cursor_mut() {
+    use std::ops::Bound::Unbounded;
+
+    let mut rng = rand::thread_rng();
+    let mut sgm = SgMap::<usize, usize, 64>::new();
+    let mut btm = BTreeMap::<usize, usize>::new();
+
+    for _ in 0..100 {
+        let start = rng.gen_range(0, 100);
+        let mut cursor = sgm.lower_bound_mut(Included(&start));
+        let mut opt_pos = btm.range(start..).next().map(|(k, _)| *k);
+
+        // Random walk with edits, mirrored on the model
+        for _ in 0..50 {
+            assert_eq!(
+                cursor.current().map(|(k, v)| (*k, *v)),
+                opt_pos.map(|k| (k, btm[&k]))
+            );
+            let next = match opt_pos {
+                Some(k) => btm.range((Excluded(k), Unbounded)).next(),
+                None => btm.iter().next(),
+            };
+            let prev = match opt_pos {
+                Some(k) => btm.range(..k).next_back(),
+                None => btm.iter().next_back(),
+            };
+            assert_eq!(cursor.peek_next(), next);
+            assert_eq!(cursor.peek_prev(), prev);
+            let (next, prev) = (next.map(|(k, _)| *k), prev.map(|(k, _)| *k));
+
+            match rng.gen_range(0, 6) {
+                0 => {
+                    cursor.move_next();
+                    opt_pos = next;
+                }
+                1 => {
+                    cursor.move_prev();
+                    opt_pos = prev;
+                }
+                2 => {
+                    let removed = cursor.remove_current();
+                    assert_eq!(removed, opt_pos.and_then(|k| btm.remove_entry(&k)));
+                    if removed.is_some() {
+                        opt_pos = next;
+                    }
+                }
+                3 => {
+                    if let Some((_, v)) = cursor.current() {
+                        *v += 1;
+                        *btm.get_mut(&opt_pos.unwrap()).unwrap() += 1;
+                    }
+                }
+                op => {
+                    let key = rng.gen_range(0, 100);
+                    let (lo, hi) = match op {
+                        4 => (prev, opt_pos),
+                        _ => (opt_pos, next),
+                    };
+                    let res = match op {
+                        4 => cursor.insert_before(key, key),
+                        _ => cursor.insert_after(key, key),
+                    };
+
+                    let expected = if (lo == Some(key)) || (hi == Some(key)) {
+                        Err(SgError::KeyAlreadyExists)
+                    } else if matches!(lo, Some(lo) if key < lo)
+                        || matches!(hi, Some(hi) if key > hi)
+                    {
+                        Err(SgError::KeyOutOfOrder)
+                    } else if btm.len() == 64 {
+                        Err(SgError::StackCapacityExceeded)
+                    } else {
+                        btm.insert(key, key);
+                        Ok(())
+                    };
+                    assert_eq!(res, expected);
+                }
+            }
+        }
+
+        assert!(sgm.iter().eq(btm.iter()));
+    }
+}
+
+#[test]
+fn test_map_entry_ref() {
+    use scapegoat::map_types::EntryRef;
+    use std::borrow::Borrow;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CONVERSIONS: AtomicUsize = AtomicUsize::new(0);
+
+    // Owned key that counts conversions from its borrowed form
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Owned(String);
+
+    impl Borrow<str> for Owned {
+        fn borrow(&self) -> &str {
+            &self.0
+        }
+    }
+
+    impl From<&str> for Owned {
+        fn from(s: &str) -> Self {
+            CONVERSIONS.fetch_add(1, Ordering::Relaxed);
+            Owned(s.to_string())
+        }
+    }
+
+    let mut map = SgMap::<Owned, usize, 4>::new();
+    for word in "a b a c a b".split(' ') {
+        *map.entry_ref(word).or_default() += 1;
+    }
+    assert_eq!(CONVERSIONS.load(Ordering::Relaxed), 3);
+    assert_eq!(map.get("a"), Some(&3));
+    assert_eq!(map.get("b"), Some(&2));
+    assert_eq!(map.get("c"), Some(&1));
+
+    // Occupied
+    match map.entry_ref("b") {
+        EntryRef::Occupied(mut o) => {
+            assert_eq!(o.key(), &Owned("b".to_string()));
+            assert_eq!(o.insert(7), 2);
+        }
+        EntryRef::Vacant(_) => panic!("Expected occupied entry!"),
+    }
+    assert_eq!(map.entry_ref("b").key(), "b");
+    assert_eq!(map.entry_ref("b").and_modify(|v| *v += 1).or_insert(0), &8);
+
+    // Vacant, at capacity
+    map.insert(Owned::from("d"), 4);
+    assert!(map.is_full());
+    let conversions = CONVERSIONS.load(Ordering::Relaxed);
+    match map.entry_ref("e") {
+        EntryRef::Vacant(v) => {
+            assert_eq!(v.key(), "e");
+            assert_eq!(v.try_insert(5), Err(SgError::StackCapacityExceeded));
+        }
+        EntryRef::Occupied(_) => panic!("Expected vacant entry!"),
+    }
+    assert_eq!(CONVERSIONS.load(Ordering::Relaxed), conversions);
+
+    map.remove("a");
+    assert_eq!(map.entry_ref("e").or_insert_with_key(|k| k.len() * 10), &10);
+    assert_eq!(CONVERSIONS.load(Ordering::Relaxed), conversions + 1);
+}
+
+#[test]
+fn test_map_joins() {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..100 {
+        let mut this = SgMap::<usize, usize, DEFAULT_CAPACITY>::new();
+        let mut other = SgMap::<usize, char, { DEFAULT_CAPACITY * 2 }>::new();
+        while !this.is_full() && rng.gen::<bool>() {
+            this.insert(rng.gen_range(0, 30), rng.gen());
+        }
+        while !other.is_full() && rng.gen::<bool>() {
+            other.insert(rng.gen_range(0, 30), rng.gen());
+        }
+
+        let inner: Vec<_> = this
+            .iter()
+            .filter_map(|(k, v)| other.get(k).map(|v2| (k, v, v2)))
+            .collect();
+        assert!(this.join(&other).eq(inner));
+
+        let left: Vec<_> = this.iter().map(|(k, v)| (k, v, other.get(k))).collect();
+        assert_eq!(this.left_join(&other).len(), left.len());
+        assert!(this.left_join(&other).eq(left));
+
+        let mut full_keys: Vec<_> = this.keys().chain(other.keys()).collect();
+        full_keys.sort_unstable();
+        full_keys.dedup();
+        let full: Vec<_> = full_keys
+            .into_iter()
+            .map(|k| (k, this.get(k), other.get(k)))
+            .collect();
+        assert!(this.full_join(&other).eq(full));
+    }
+
+    // Custom order
+    let this: SgMap<_, _, DEFAULT_CAPACITY, u16, Reversed> = (0..5).map(|k| (k, k)).collect();
+    let other: SgMap<_, _, DEFAULT_CAPACITY, u16, Reversed> = (3..8).map(|k| (k, -k)).collect();
+    assert!(this.join(&other).eq([(&4, &4, &-4), (&3, &3, &-3)]));
+    assert_eq!(this.full_join(&other).next(), Some((&7, None, Some(&-7))));
+}
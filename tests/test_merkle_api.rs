@@ -0,0 +1,155 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+
+use scapegoat::{MerkleHasher, MerkleProof, SgError, SgMerkleMap};
+
+use rand::Rng;
+
+const DEFAULT_CAPACITY: usize = 100;
+const PROOF_CAPACITY: usize = 32;
+
+// Fixed-key SipHash, deterministic but not cryptographic
+struct Sip;
+
+impl<K: Hash, V: Hash> MerkleHasher<K, V> for Sip {
+    type Digest = u64;
+
+    fn hash_pair(key: &K, val: &V) -> u64 {
+        let mut h = DefaultHasher::new();
+        (0_u8, key, val).hash(&mut h);
+        h.finish()
+    }
+
+    fn hash_node(pair: &u64, left: Option<&u64>, right: Option<&u64>) -> u64 {
+        let mut h = DefaultHasher::new();
+        (1_u8, pair, left, right).hash(&mut h);
+        h.finish()
+    }
+}
+
+type Proof = MerkleProof<usize, usize, Sip, PROOF_CAPACITY>;
+
+// Every pair has a valid proof iff every cached digest is current
+fn assert_all_proofs_verify(mmap: &SgMerkleMap<usize, usize, Sip, DEFAULT_CAPACITY>) {
+    match mmap.root_hash() {
+        Some(root) => {
+            for (k, v) in mmap.iter() {
+                let proof: Proof = mmap.prove(k).unwrap();
+                assert!(proof.verify(root, k, v), "key {}", k);
+            }
+        }
+        None => assert!(mmap.is_empty()),
+    }
+}
+
+#[test]
+fn test_debug() {
+    let mmap = SgMerkleMap::<_, _, Sip, DEFAULT_CAPACITY>::from_iter([(2, 20), (1, 10)]);
+    assert_eq!(format!("{:?}", mmap), "{1: 10, 2: 20}");
+}
+
+#[test]
+fn test_merkle_map_basic() {
+    let mut mmap = SgMerkleMap::<usize, usize, Sip, 3>::new();
+    assert_eq!(mmap.root_hash(), None);
+    assert_eq!(
+        mmap.prove::<_, PROOF_CAPACITY>(&1).unwrap_err(),
+        SgError::KeyNotFound
+    );
+
+    assert_eq!(mmap.insert(1, 10), None);
+    assert_eq!(mmap.insert(2, 20), None);
+    assert_eq!(mmap.insert(2, 25), Some(20));
+    assert_eq!(mmap.try_insert(3, 30), Ok(None));
+    assert!(mmap.is_full());
+    assert_eq!(mmap.try_insert(4, 40), Err(SgError::StackCapacityExceeded));
+
+    assert_eq!(mmap.get(&2), Some(&25));
+    assert!(mmap.contains_key(&3));
+    assert_eq!(mmap.remove(&3), Some(30));
+    assert!(mmap.iter().eq([(&1, &10), (&2, &25)]));
+
+    mmap.clear();
+    assert!(mmap.is_empty());
+    assert_eq!(mmap.root_hash(), None);
+}
+
+#[test]
+fn test_merkle_proof() {
+    let mut mmap =
+        SgMerkleMap::<usize, usize, Sip, DEFAULT_CAPACITY>::from_iter((0..20).map(|k| (k, k)));
+    let root = *mmap.root_hash().unwrap();
+
+    let proof: Proof = mmap.prove(&7).unwrap();
+    assert!(proof.verify(&root, &7, &7));
+
+    // Wrong value, wrong key, wrong root
+    assert!(!proof.verify(&root, &7, &8));
+    assert!(!proof.verify(&root, &8, &7));
+    assert!(!proof.verify(&(root ^ 1), &7, &7));
+
+    // Stale after modification
+    assert!(mmap.modify(&7, |v| *v = 70));
+    assert!(!mmap.modify(&100, |v| *v = 0));
+    let new_root = *mmap.root_hash().unwrap();
+    assert_ne!(new_root, root);
+    assert!(!proof.verify(&new_root, &7, &7));
+
+    let proof: Proof = mmap.prove(&7).unwrap();
+    assert!(proof.verify(&new_root, &7, &70));
+
+    // Root has an empty path
+    let root_proof = (0..20)
+        .map(|k| mmap.prove::<_, PROOF_CAPACITY>(&k).unwrap())
+        .find(|p| p.is_empty())
+        .unwrap();
+    assert_eq!(root_proof.len(), 0);
+
+    // Too deep for a zero-step proof
+    let deepest = (0..20)
+        .max_by_key(|k| mmap.prove::<_, PROOF_CAPACITY>(k).unwrap().len())
+        .unwrap();
+    assert_eq!(
+        mmap.prove::<_, 0>(&deepest).unwrap_err(),
+        SgError::BufferTooSmall
+    );
+}
+
+#[test]
+fn test_merkle_rand_ops() {
+    let mut rng = rand::thread_rng();
+    let mut mmap = SgMerkleMap::<usize, usize, Sip, DEFAULT_CAPACITY>::new();
+    let mut bt_map = BTreeMap::new();
+
+    // Sequential inserts force rebuilds
+    for k in 0..(DEFAULT_CAPACITY / 2) {
+        mmap.insert(k, k);
+        bt_map.insert(k, k);
+        assert_all_proofs_verify(&mmap);
+    }
+
+    for _ in 0..1_000 {
+        let k = rng.gen_range(0, DEFAULT_CAPACITY * 2);
+        match rng.gen_range(0, 3) {
+            0 if !mmap.is_full() => {
+                let v = rng.gen::<usize>();
+                assert_eq!(mmap.insert(k, v), bt_map.insert(k, v));
+            }
+            1 => {
+                assert_eq!(mmap.remove(&k), bt_map.remove(&k));
+            }
+            _ => {
+                let found = mmap.modify(&k, |v| *v = v.wrapping_add(1));
+                if let Some(v) = bt_map.get_mut(&k) {
+                    *v = v.wrapping_add(1);
+                }
+                assert_eq!(found, bt_map.contains_key(&k));
+            }
+        }
+
+        assert!(mmap.iter().eq(bt_map.iter()));
+        assert_all_proofs_verify(&mmap);
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+use scapegoat::{Reversed, SgError, SgMultiMap};
+
+use rand::Rng;
+
+const DEFAULT_CAPACITY: usize = 100;
+
+#[test]
+fn test_debug() {
+    let mmap = SgMultiMap::<_, _, DEFAULT_CAPACITY>::from_iter([(2, "b"), (1, "a"), (2, "c")]);
+    assert_eq!(format!("{:?}", mmap), r#"{1: "a", 2: "b", 2: "c"}"#);
+}
+
+#[test]
+fn test_multimap_basic() {
+    let mut mmap = SgMultiMap::<_, _, 4>::new();
+    assert!(mmap.is_empty());
+    assert_eq!(mmap.capacity(), 4);
+
+    mmap.insert("k", 1);
+    mmap.insert("j", 0);
+    mmap.insert("k", 2);
+    assert_eq!(mmap.try_insert("k", 3), Ok(()));
+    assert!(mmap.is_full());
+    assert_eq!(mmap.try_insert("k", 4), Err(SgError::StackCapacityExceeded));
+
+    assert_eq!(mmap.len(), 4);
+    assert!(mmap.contains_key("k"));
+    assert!(!mmap.contains_key("l"));
+    assert_eq!(mmap.get("k"), Some(&1));
+    assert!(mmap.get_all("k").eq([&1, &2, &3]));
+    assert!(mmap.get_all("j").eq([&0]));
+    assert_eq!(mmap.get_all("l").next(), None);
+
+    assert_eq!(mmap.remove_one("k"), Some(1));
+    assert_eq!(mmap.remove_all("k"), 2);
+    assert_eq!(mmap.remove_all("k"), 0);
+    assert!(mmap.iter().eq([(&"j", &0)]));
+
+    mmap.clear();
+    assert!(mmap.is_empty());
+}
+
+#[test]
+fn test_multimap_custom_comparator() {
+    let mut mmap = SgMultiMap::<_, _, DEFAULT_CAPACITY, u16, Reversed>::new();
+    mmap.extend([(1, 'a'), (3, 'b'), (1, 'c'), (2, 'd'), (3, 'e')]);
+
+    // Keys descend, duplicates stay in insertion order
+    assert!(mmap
+        .iter()
+        .eq([(&3, &'b'), (&3, &'e'), (&2, &'d'), (&1, &'a'), (&1, &'c')]));
+    assert!(mmap.get_all(&3).eq([&'b', &'e']));
+    assert_eq!(mmap.remove_one(&1), Some('a'));
+    assert!(mmap
+        .iter()
+        .rev()
+        .eq([(&1, &'c'), (&2, &'d'), (&3, &'e'), (&3, &'b')]));
+}
+
+#[test]
+fn test_multimap_rand_differential() {
+    let mut rng = rand::thread_rng();
+    let mut mmap = SgMultiMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    let mut model = BTreeMap::<usize, Vec<usize>>::new();
+
+    for op in 0..10_000 {
+        let key = rng.gen_range(0, 10);
+        match rng.gen_range(0, 4) {
+            0 | 1 => {
+                let res = mmap.try_insert(key, op);
+                let model_len: usize = model.values().map(Vec::len).sum();
+                match model_len < DEFAULT_CAPACITY {
+                    true => {
+                        assert!(res.is_ok());
+                        model.entry(key).or_default().push(op);
+                    }
+                    false => assert_eq!(res, Err(SgError::StackCapacityExceeded)),
+                }
+            }
+            2 => {
+                let expected = model.get_mut(&key).and_then(|vals| match vals.is_empty() {
+                    true => None,
+                    false => Some(vals.remove(0)),
+                });
+                assert_eq!(mmap.remove_one(&key), expected);
+            }
+            _ => {
+                let expected = model.remove(&key).map_or(0, |vals| vals.len());
+                assert_eq!(mmap.remove_all(&key), expected);
+            }
+        }
+
+        model.retain(|_, vals| !vals.is_empty());
+        let expected = model.get(&key).map(Vec::as_slice).unwrap_or_default();
+        assert!(mmap.get_all(&key).eq(expected.iter()));
+        assert_eq!(mmap.get(&key), expected.first());
+    }
+
+    let flat: Vec<(&usize, &usize)> = model
+        .iter()
+        .flat_map(|(k, vals)| vals.iter().map(move |v| (k, v)))
+        .collect();
+    assert_eq!(mmap.len(), flat.len());
+    assert!(mmap.iter().eq(flat));
+}
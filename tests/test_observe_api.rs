@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use scapegoat::{Change, Observer, SgError, SgObservedMap};
+
+use rand::Rng;
+
+const DEFAULT_CAPACITY: usize = 100;
+
+// Mirrors the map's contents purely from notifications
+#[derive(Default)]
+struct Mirror {
+    map: BTreeMap<usize, usize>,
+    events: usize,
+}
+
+impl Observer<usize, usize> for Mirror {
+    fn notify(&mut self, change: Change, key: &usize, val: &usize) {
+        self.events += 1;
+        match change {
+            Change::Inserted => assert_eq!(self.map.insert(*key, *val), None),
+            Change::Updated => assert!(self.map.insert(*key, *val).is_some()),
+            Change::Removed => assert_eq!(self.map.remove(key), Some(*val)),
+        }
+    }
+}
+
+#[test]
+fn test_debug() {
+    let mut omap = SgObservedMap::<_, _, _, DEFAULT_CAPACITY>::new(Mirror::default());
+    omap.extend([(2, 20), (1, 10)]);
+    assert_eq!(format!("{:?}", omap), "{1: 10, 2: 20}");
+}
+
+#[test]
+fn test_observed_map_basic() {
+    let mut omap = SgObservedMap::<usize, usize, Mirror, 2>::new(Mirror::default());
+
+    assert_eq!(omap.insert(1, 10), None);
+    assert_eq!(omap.try_insert(1, 11), Ok(Some(10)));
+    assert_eq!(omap.try_insert(2, 20), Ok(None));
+    assert_eq!(omap.try_insert(3, 30), Err(SgError::StackCapacityExceeded));
+    assert_eq!(omap.observer().events, 3);
+
+    assert!(omap.modify(&2, |v| *v += 1));
+    assert!(!omap.modify(&3, |v| *v += 1));
+    assert_eq!(omap.observer().map[&2], 21);
+    assert_eq!(omap.observer().events, 4);
+
+    // Reads go through the inner map
+    assert_eq!(omap.get(&1), Some(&11));
+    assert!(omap.iter().eq(omap.observer().map.iter()));
+
+    assert_eq!(omap.pop_last(), Some((2, 21)));
+    assert_eq!(omap.remove(&2), None);
+    assert_eq!(omap.observer().events, 5);
+
+    omap.observer_mut().events = 0;
+    omap.clear();
+    assert_eq!(omap.observer().events, 1);
+
+    let (map, mirror) = omap.into_parts();
+    assert!(map.is_empty());
+    assert!(mirror.map.is_empty());
+}
+
+#[test]
+fn test_observed_map_rand_ops() {
+    let mut rng = rand::thread_rng();
+    let mut omap = SgObservedMap::<usize, usize, Mirror, DEFAULT_CAPACITY>::new(Mirror::default());
+
+    for _ in 0..2_000 {
+        let k = rng.gen_range(0, DEFAULT_CAPACITY * 2);
+        match rng.gen_range(0, 7) {
+            0 | 1 => {
+                let _ = omap.try_insert(k, rng.gen());
+            }
+            2 => {
+                omap.remove(&k);
+            }
+            3 => {
+                omap.modify(&k, |v| *v = v.wrapping_add(1));
+            }
+            4 => {
+                omap.pop_first();
+            }
+            5 => {
+                let parity = rng.gen_range(0, 2);
+                omap.retain(|k, _| k % 2 == parity);
+            }
+            _ => {
+                let _ = omap.remove_entry(&k);
+            }
+        }
+
+        assert!(omap.iter().eq(omap.observer().map.iter()));
+    }
+}
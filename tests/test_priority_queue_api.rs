@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+use scapegoat::{Reversed, SgError, SgPriorityQueue};
+
+use rand::Rng;
+
+const DEFAULT_CAPACITY: usize = 100;
+
+#[test]
+fn test_debug() {
+    let pq = SgPriorityQueue::<_, DEFAULT_CAPACITY>::from_iter([3, 1, 2, 1]);
+    assert_eq!(format!("{:?}", pq), "[1, 1, 2, 3]");
+}
+
+#[test]
+fn test_priority_queue_basic() {
+    let mut pq = SgPriorityQueue::<(u8, char), 4>::new();
+    assert_eq!(pq.peek_min(), None);
+    assert_eq!(pq.pop_max(), None);
+
+    pq.push((2, 'a'));
+    pq.push((1, 'b'));
+    pq.push((2, 'a'));
+    assert_eq!(pq.try_push((3, 'c')), Ok(()));
+    assert!(pq.is_full());
+    assert_eq!(pq.try_push((0, 'd')), Err(SgError::StackCapacityExceeded));
+
+    assert_eq!(pq.peek_min(), Some(&(1, 'b')));
+    assert_eq!(pq.peek_max(), Some(&(3, 'c')));
+    assert!(pq.contains(&(2, 'a')));
+
+    // Raise one duplicate above the max
+    assert!(pq.change_priority(&(2, 'a'), |item| item.0 = 9));
+    assert!(!pq.change_priority(&(7, 'z'), |item| item.0 = 0));
+    assert!(pq.iter().eq(&[(1, 'b'), (2, 'a'), (3, 'c'), (9, 'a')]));
+
+    assert_eq!(pq.pop_max(), Some((9, 'a')));
+    assert_eq!(pq.pop_min(), Some((1, 'b')));
+    assert_eq!(pq.len(), 2);
+
+    pq.clear();
+    assert!(pq.is_empty());
+}
+
+#[test]
+fn test_priority_queue_reversed() {
+    let mut pq = SgPriorityQueue::<_, 10, u16, Reversed>::with_comparator(Reversed::default());
+    pq.extend([1, 3, 2]);
+    assert_eq!(pq.peek_min(), Some(&3));
+    assert_eq!(pq.pop_max(), Some(1));
+}
+
+#[test]
+fn test_priority_queue_rand_ops() {
+    let mut rng = rand::thread_rng();
+    let mut pq = SgPriorityQueue::<usize, DEFAULT_CAPACITY>::new();
+
+    // Model: priority -> count
+    let mut bt_map = BTreeMap::<usize, usize>::new();
+    let bt_pop = |bt_map: &mut BTreeMap<usize, usize>, max: bool| {
+        let (&k, cnt) = match max {
+            true => bt_map.iter_mut().next_back()?,
+            false => bt_map.iter_mut().next()?,
+        };
+        *cnt -= 1;
+        if *cnt == 0 {
+            bt_map.remove(&k);
+        }
+        Some(k)
+    };
+
+    for _ in 0..2_000 {
+        let p = rng.gen_range(0, 20);
+        match rng.gen_range(0, 4) {
+            0 if !pq.is_full() => {
+                pq.push(p);
+                *bt_map.entry(p).or_default() += 1;
+            }
+            1 => assert_eq!(pq.pop_min(), bt_pop(&mut bt_map, false)),
+            2 => assert_eq!(pq.pop_max(), bt_pop(&mut bt_map, true)),
+            _ => {
+                let new_p = rng.gen_range(0, 20);
+                let found = pq.change_priority(&p, |item| *item = new_p);
+                assert_eq!(found, bt_map.contains_key(&p));
+                if found {
+                    bt_map.insert(p, bt_map[&p] - 1);
+                    bt_map.retain(|_, cnt| *cnt > 0);
+                    *bt_map.entry(new_p).or_default() += 1;
+                }
+            }
+        }
+
+        assert_eq!(pq.len(), bt_map.values().sum::<usize>());
+        assert_eq!(pq.peek_min(), bt_map.keys().next());
+        assert_eq!(pq.peek_max(), bt_map.keys().next_back());
+        assert!(pq.iter().copied().eq(bt_map
+            .iter()
+            .flat_map(|(&k, &cnt)| std::iter::repeat_n(k, cnt))));
+    }
+}
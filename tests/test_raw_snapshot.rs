@@ -0,0 +1,137 @@
+#![cfg(feature = "bytemuck")]
+
+use std::collections::BTreeMap;
+
+use scapegoat::{Reversed, SgError, SgMap, SgSet};
+
+use rand::Rng;
+
+const CAPACITY: usize = 100;
+const IMAGE_LEN: usize = 8192;
+
+// Random map with removals mixed in, so the arena has vacant slots
+fn rand_map() -> (SgMap<u32, u64, CAPACITY>, BTreeMap<u32, u64>) {
+    let mut rng = rand::thread_rng();
+    let mut sgm = SgMap::new();
+    let mut btm = BTreeMap::new();
+
+    for _ in 0..(CAPACITY * 2) {
+        let k = rng.gen_range(0, CAPACITY as u32 * 2);
+        if rng.gen_range(0, 3) == 0 {
+            assert_eq!(sgm.remove(&k), btm.remove(&k));
+        } else if !sgm.is_full() {
+            let v = rng.gen();
+            assert_eq!(sgm.insert(k, v), btm.insert(k, v));
+        }
+    }
+
+    (sgm, btm)
+}
+
+#[test]
+fn test_map_roundtrip() {
+    let (sgm, btm) = rand_map();
+    let mut image = [0; IMAGE_LEN];
+    let len = sgm.write_raw_snapshot(&mut image).unwrap();
+    assert_eq!(len, sgm.raw_snapshot_len());
+
+    let mut restored = SgMap::<u32, u64, CAPACITY>::from_raw_snapshot(&image[..len]).unwrap();
+    assert!(restored.iter().eq(btm.iter()));
+    assert!(restored.check_invariants().is_valid());
+    assert_eq!(restored.rebal_ratio(), sgm.rebal_ratio());
+
+    // Same layout, so the same image
+    let mut reimage = [0; IMAGE_LEN];
+    assert_eq!(restored.write_raw_snapshot(&mut reimage), Ok(len));
+    assert_eq!(image[..len], reimage[..len]);
+
+    // Fully usable, vacant slots included
+    let mut rng = rand::thread_rng();
+    while !restored.is_full() {
+        restored.insert(rng.gen(), 0);
+    }
+    assert!(restored.check_invariants().is_valid());
+
+    // Unaligned source, trailing bytes, and a larger target capacity are all fine
+    let mut shifted = [0; IMAGE_LEN + 1];
+    shifted[1..].copy_from_slice(&image);
+    let restored = SgMap::<u32, u64, { CAPACITY * 2 }>::from_raw_snapshot(&shifted[1..]).unwrap();
+    assert!(restored.iter().eq(btm.iter()));
+
+    // Empty
+    let empty = SgMap::<u32, u64, CAPACITY>::new();
+    let len = empty.write_raw_snapshot(&mut image).unwrap();
+    let restored = SgMap::<u32, u64, CAPACITY>::from_raw_snapshot(&image[..len]).unwrap();
+    assert!(restored.is_empty());
+}
+
+#[test]
+fn test_set_roundtrip() {
+    let set: SgSet<i16, CAPACITY> = (-50..50).rev().collect();
+    let mut image = [0; IMAGE_LEN];
+    let len = set.write_raw_snapshot(&mut image).unwrap();
+
+    let restored = SgSet::<i16, CAPACITY>::from_raw_snapshot(&image[..len]).unwrap();
+    assert_eq!(restored, set);
+}
+
+#[test]
+fn test_incompatible_image() {
+    let (sgm, _) = rand_map();
+    let mut image = [0; IMAGE_LEN];
+    let len = sgm.write_raw_snapshot(&mut image).unwrap();
+    let image = &image[..len];
+
+    assert_eq!(
+        sgm.write_raw_snapshot(&mut [0; 64]),
+        Err(SgError::BufferTooSmall)
+    );
+
+    // Truncated
+    assert_eq!(
+        SgMap::<u32, u64, CAPACITY>::from_raw_snapshot(&image[..len - 1]),
+        Err(SgError::InvalidSnapshot)
+    );
+    assert_eq!(
+        SgMap::<u32, u64, CAPACITY>::from_raw_snapshot(&[]),
+        Err(SgError::InvalidSnapshot)
+    );
+
+    // Different pair sizes
+    assert_eq!(
+        SgMap::<u64, u64, CAPACITY>::from_raw_snapshot(image),
+        Err(SgError::InvalidSnapshot)
+    );
+
+    // Too many slots for target
+    assert_eq!(
+        SgMap::<u32, u64, 8>::from_raw_snapshot(image),
+        Err(SgError::InvalidSnapshot)
+    );
+
+    // Out of order under the target's comparator
+    assert_eq!(
+        SgMap::<u32, u64, CAPACITY, u16, Reversed>::from_raw_snapshot(image),
+        Err(SgError::InvalidSnapshot)
+    );
+}
+
+#[test]
+fn test_corrupt_image() {
+    let mut rng = rand::thread_rng();
+    let (sgm, _) = rand_map();
+    let mut image = [0; IMAGE_LEN];
+    let len = sgm.write_raw_snapshot(&mut image).unwrap();
+
+    // Never panics: either rejected, or a valid tree (e.g. a value byte changed)
+    for _ in 0..1_000 {
+        let mut corrupt = image;
+        for _ in 0..rng.gen_range(1, 4) {
+            corrupt[rng.gen_range(0, len)] = rng.gen();
+        }
+
+        if let Ok(restored) = SgMap::<u32, u64, CAPACITY>::from_raw_snapshot(&corrupt[..len]) {
+            assert!(restored.check_invariants().is_valid());
+        }
+    }
+}
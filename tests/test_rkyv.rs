@@ -0,0 +1,85 @@
+#![cfg(feature = "rkyv")]
+
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+use rkyv::collections::btree_map::ArchivedBTreeMap;
+use rkyv::collections::btree_set::ArchivedBTreeSet;
+use rkyv::rancor::Error;
+use rkyv::string::ArchivedString;
+use rkyv::Archived;
+use scapegoat::{Reversed, SgMap, SgSet};
+
+#[test]
+fn test_map_roundtrip() {
+    let map = SgMap::<u32, String, 4>::from_iter([
+        (3, "c".to_string()),
+        (1, "a".to_string()),
+        (2, "b".to_string()),
+    ]);
+
+    let bytes = rkyv::to_bytes::<Error>(&map).unwrap();
+    let de = rkyv::from_bytes::<SgMap<u32, String, 4>, Error>(&bytes).unwrap();
+    assert_eq!(de, map);
+
+    // Any comparator can be deserialized into
+    let archived =
+        rkyv::access::<ArchivedBTreeMap<Archived<u32>, ArchivedString>, Error>(&bytes).unwrap();
+    let rev = rkyv::deserialize::<SgMap<u32, String, 4, u16, Reversed>, Error>(archived).unwrap();
+    assert!(rev.keys().eq([3, 2, 1].iter()));
+}
+
+#[test]
+fn test_map_access_in_place() {
+    let map = SgMap::<u32, u64, 128>::from_iter((0..100).map(|k| (k * 2, u64::from(k))));
+    let bytes = rkyv::to_bytes::<Error>(&map).unwrap();
+
+    let archived =
+        rkyv::access::<ArchivedBTreeMap<Archived<u32>, Archived<u64>>, Error>(&bytes).unwrap();
+    assert_eq!(archived.len(), 100);
+    for (k, v) in map.iter() {
+        assert_eq!(
+            archived
+                .get(&Archived::<u32>::from_native(*k))
+                .map(|v| v.to_native()),
+            Some(*v)
+        );
+    }
+    assert!(!archived.contains_key(&Archived::<u32>::from_native(1)));
+
+    // Layout-identical to an archived `BTreeMap`
+    let bt_map = BTreeMap::from_iter(map.iter().map(|(k, v)| (*k, *v)));
+    assert_eq!(
+        rkyv::to_bytes::<Error>(&bt_map).unwrap().as_slice(),
+        bytes.as_slice()
+    );
+}
+
+#[test]
+fn test_set_roundtrip() {
+    let set = SgSet::<String, 4>::from_iter(["b", "a", "c"].iter().map(|s| s.to_string()));
+    let bytes = rkyv::to_bytes::<Error>(&set).unwrap();
+
+    let archived = rkyv::access::<ArchivedBTreeSet<ArchivedString>, Error>(&bytes).unwrap();
+    assert!(archived.contains_key("a"));
+    assert!(!archived.contains_key("d"));
+
+    let de = rkyv::from_bytes::<SgSet<String, 4>, Error>(&bytes).unwrap();
+    assert_eq!(de, set);
+}
+
+#[test]
+fn test_capacity_exceeded() {
+    let map = SgMap::<u32, u32, 4>::from_iter([(1, 1), (2, 2), (3, 3)]);
+    let bytes = rkyv::to_bytes::<Error>(&map).unwrap();
+
+    let err = rkyv::from_bytes::<SgMap<u32, u32, 2>, Error>(&bytes).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "archived collection has 3 entries, exceeding capacity 2"
+    );
+
+    let set = SgSet::<u32, 4>::from_iter([1, 2, 3]);
+    let bytes = rkyv::to_bytes::<Error>(&set).unwrap();
+    assert!(rkyv::from_bytes::<SgSet<u32, 2>, Error>(&bytes).is_err());
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "serde")]
+
+use std::iter::FromIterator;
+
+use scapegoat::{Reversed, SgMap, SgSet};
+
+#[test]
+fn test_map_roundtrip() {
+    let map = SgMap::from([(3, "c"), (1, "a"), (2, "b")]);
+    let json = serde_json::to_string(&map).unwrap();
+    assert_eq!(json, r#"{"1":"a","2":"b","3":"c"}"#);
+
+    let de: SgMap<u32, String, 3> = serde_json::from_str(&json).unwrap();
+    assert!(de.iter().map(|(k, v)| (*k, v.as_str())).eq(map.into_iter()));
+}
+
+#[test]
+fn test_set_roundtrip() {
+    let set = SgSet::<u32, 4, u8, Reversed>::from_iter([1, 4, 2, 3]);
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(json, "[4,3,2,1]");
+
+    // Input order doesn't matter, duplicates are dropped
+    let de: SgSet<u32, 4, u8, Reversed> = serde_json::from_str("[1,2,3,4,4,1]").unwrap();
+    assert_eq!(de, set);
+}
+
+#[test]
+fn test_map_duplicate_keys() {
+    let de: SgMap<u32, u32, 2> = serde_json::from_str(r#"{"1":1,"2":2,"1":3}"#).unwrap();
+    assert!(de.into_iter().eq([(1, 3), (2, 2)]));
+}
+
+#[test]
+fn test_capacity_exceeded() {
+    let err = serde_json::from_str::<SgMap<u32, u32, 2>>(r#"{"1":1,"2":2,"3":3}"#).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("invalid length 3, expected a map with at most 2 entries"));
+
+    let err = serde_json::from_str::<SgSet<u32, 2>>("[1,2,3]").unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("invalid length 3, expected a sequence with at most 2 elements"));
+
+    // Fits after deduplication
+    let set: SgSet<u32, 2> = serde_json::from_str("[1,2,2,1]").unwrap();
+    assert_eq!(set.len(), 2);
+}
@@ -1,8 +1,9 @@
 use std::collections::BTreeSet;
+use std::convert::TryFrom;
 use std::iter::FromIterator;
 use std::ops::Bound::{Excluded, Included};
 
-use scapegoat::{SgError, SgSet};
+use scapegoat::{CapacityError, EvictPolicy, Reversed, SgError, SgSet};
 
 const DEFAULT_CAPACITY: usize = 10;
 
@@ -28,6 +29,25 @@ fn test_clone() {
     assert_eq!(sgs_1, sgs_2);
 }
 
+#[test]
+fn test_reversed_set_ops() {
+    let mut a = SgSet::<u32, 10, u16, Reversed>::new();
+    let mut b = SgSet::<u32, 10, u16, Reversed>::new();
+    a.extend([1, 2, 3, 4]);
+    b.extend([3, 4, 5, 6]);
+
+    assert!(a.iter().eq(&[4, 3, 2, 1]));
+    assert!(a.intersection(&b).eq(&[4, 3]));
+    assert!(a.difference(&b).eq(&[2, 1]));
+    assert!(a.symmetric_difference(&b).eq(&[6, 5, 2, 1]));
+    assert!(a.union(&b).eq(&[6, 5, 4, 3, 2, 1]));
+    assert!((&a | &b).into_iter().eq([6, 5, 4, 3, 2, 1]));
+    assert!(a.range((Included(3), Included(1))).eq(&[3, 2, 1]));
+    assert_eq!(a.first(), Some(&4));
+    assert_eq!(a.iter().min(), Some(&1));
+    assert!(!a.is_disjoint(&b));
+}
+
 #[test]
 fn test_basic_set_functionality() {
     let mut sgs = SgSet::<_, 10>::new();
@@ -92,7 +112,7 @@ fn test_basic_set_functionality() {
 #[test]
 fn test_set_from_iter() {
     let keys = vec![1, 10, 100];
-    let sgs = SgSet::<_, 3>::from_iter(keys.into_iter());
+    let sgs = SgSet::<_, 3>::from_iter(keys);
 
     assert!(sgs.len() == 3);
     assert_eq!(sgs.into_iter().collect::<Vec<usize>>(), vec![1, 10, 100]);
@@ -104,10 +124,60 @@ fn test_set_from_iter_panic() {
     let _: SgSet<usize, DEFAULT_CAPACITY> = SgSet::from_iter(0..(DEFAULT_CAPACITY + 1));
 }
 
+#[test]
+fn test_set_try_from_iter() {
+    let sgs = SgSet::<_, DEFAULT_CAPACITY>::try_from_iter(vec![3, 1, 2, 1].into_iter()).unwrap();
+    assert_eq!(sgs.into_iter().collect::<Vec<usize>>(), vec![1, 2, 3]);
+
+    // Fails cleanly instead of panicking like `from_iter`
+    assert_eq!(
+        SgSet::<usize, DEFAULT_CAPACITY>::try_from_iter(0..(DEFAULT_CAPACITY + 1)),
+        Err(SgError::StackCapacityExceeded)
+    );
+}
+
+#[test]
+fn test_set_try_from_slice() {
+    let keys: Vec<usize> = (0..(DEFAULT_CAPACITY + 1)).collect();
+
+    let sgs = SgSet::<_, DEFAULT_CAPACITY>::try_from(&keys[..3]).unwrap();
+    assert!(sgs.into_iter().eq(keys[..3].iter().cloned()));
+
+    assert_eq!(
+        SgSet::<_, DEFAULT_CAPACITY>::try_from(&keys[..]),
+        Err(SgError::StackCapacityExceeded)
+    );
+
+    #[cfg(feature = "alloc")]
+    {
+        let sgs = SgSet::<_, DEFAULT_CAPACITY>::try_from(keys[..3].to_vec()).unwrap();
+        assert!(sgs.into_iter().eq(keys[..3].iter().cloned()));
+        assert_eq!(
+            SgSet::<_, DEFAULT_CAPACITY>::try_from(keys),
+            Err(SgError::StackCapacityExceeded)
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_set_btreeset_conversion() {
+    let bts: BTreeSet<usize> = (0..DEFAULT_CAPACITY).collect();
+
+    let sgs = SgSet::<_, DEFAULT_CAPACITY>::try_from(bts.clone()).unwrap();
+    assert!(sgs.iter().eq(bts.iter()));
+    assert_eq!(BTreeSet::from(sgs), bts);
+
+    assert_eq!(
+        SgSet::<_, { DEFAULT_CAPACITY - 1 }>::try_from(bts),
+        Err(SgError::StackCapacityExceeded)
+    );
+}
+
 #[test]
 fn test_set_iter() {
     let keys = vec![1, 2, 3];
-    let sgs = SgSet::<_, 3>::from_iter(keys.into_iter());
+    let sgs = SgSet::<_, 3>::from_iter(keys);
     let mut sgs_iter = sgs.iter();
 
     assert_eq!(sgs_iter.next(), Some(&1));
@@ -116,6 +186,74 @@ fn test_set_iter() {
     assert_eq!(sgs_iter.next(), None);
 }
 
+#[test]
+fn test_set_double_ended_iters() {
+    let sgs = SgSet::<_, DEFAULT_CAPACITY>::from_iter([4, 1, 3, 5, 2]);
+    let bts = BTreeSet::from_iter([4, 1, 3, 5, 2]);
+
+    assert!(sgs.iter().rev().eq(bts.iter().rev()));
+    assert!(sgs
+        .clone()
+        .into_iter()
+        .rev()
+        .eq(bts.clone().into_iter().rev()));
+
+    let mut sgs_iter = sgs.iter();
+    assert_eq!(sgs_iter.next_back(), Some(&5));
+    assert_eq!(sgs_iter.next(), Some(&1));
+    assert!(sgs_iter.eq(&[2, 3, 4]));
+}
+
+#[test]
+fn test_set_exact_size_hint() {
+    fn assert_exact<I: ExactSizeIterator>(mut iter: I) {
+        loop {
+            let len = iter.len();
+            assert_eq!(iter.size_hint(), (len, Some(len)));
+            if iter.next().is_none() {
+                assert_eq!(len, 0);
+                assert!(iter.next().is_none()); // Fused
+                break;
+            }
+        }
+    }
+
+    let a = SgSet::<_, DEFAULT_CAPACITY>::from_iter([1, 2, 3, 4]);
+
+    assert_exact(a.iter());
+    assert_exact(a.clone().into_iter());
+}
+
+#[test]
+fn test_set_algebra_size_hint() {
+    // Lazy, so only bounds are known, like `BTreeSet`'s equivalents
+    fn assert_bounded<I: Iterator>(mut iter: I, total: usize) {
+        for spent in 0..=total {
+            let (lower, upper) = iter.size_hint();
+            assert!(lower <= (total - spent));
+            assert!(upper.unwrap() >= (total - spent));
+            if iter.next().is_none() {
+                assert_eq!(spent, total);
+                assert!(iter.next().is_none()); // Fused
+            }
+        }
+    }
+
+    let a = SgSet::<_, DEFAULT_CAPACITY>::from_iter([1, 2, 3, 4]);
+    let b = SgSet::<_, DEFAULT_CAPACITY>::from_iter([3, 4, 5]);
+    let empty = SgSet::<_, DEFAULT_CAPACITY>::new();
+
+    for (this, other) in [(&a, &b), (&b, &a), (&a, &empty), (&empty, &b), (&a, &a)] {
+        assert_bounded(this.intersection(other), this.intersection(other).count());
+        assert_bounded(this.difference(other), this.difference(other).count());
+        assert_bounded(
+            this.symmetric_difference(other),
+            this.symmetric_difference(other).count(),
+        );
+        assert_bounded(this.union(other), this.union(other).count());
+    }
+}
+
 #[test]
 fn test_set_append() {
     let mut a = SgSet::new();
@@ -168,6 +306,19 @@ fn test_set_intersection() {
     assert_eq!(intersection, []);
 }
 
+#[test]
+fn test_set_algebra_large_disjoint() {
+    // Lazy iterators have no `2 * N` buffer, so disjoint sets beyond the old 2048 capacity limit are fine
+    const CAP: usize = 3000;
+    let evens = SgSet::<_, CAP>::from_iter((0..(2 * CAP)).step_by(2));
+    let odds = SgSet::<_, CAP>::from_iter((1..(2 * CAP)).step_by(2));
+
+    assert!(evens.union(&odds).copied().eq(0..(2 * CAP)));
+    assert!(evens.symmetric_difference(&odds).copied().eq(0..(2 * CAP)));
+    assert!(evens.difference(&odds).eq(evens.iter()));
+    assert_eq!(evens.intersection(&odds).next(), None);
+}
+
 #[test]
 fn test_set_difference() {
     let a = SgSet::from_iter([1, 3, 9, 7]);
@@ -198,6 +349,65 @@ fn test_set_union() {
     );
 }
 
+#[test]
+fn test_set_operators() {
+    let a = SgSet::<_, 4>::from_iter([1, 2, 3]);
+    let b = SgSet::<_, 4>::from_iter([2, 3, 4]);
+
+    assert!((&a | &b).into_iter().eq([1, 2, 3, 4]));
+    assert!((&a & &b).into_iter().eq([2, 3]));
+    assert!((&a - &b).into_iter().eq([1]));
+    assert!((&a ^ &b).into_iter().eq([1, 4]));
+
+    assert_eq!(a.try_bitor(&b), Ok(&a | &b));
+    assert_eq!(a.try_bitxor(&b), Ok(&a ^ &b));
+
+    // Results that don't fit
+    let c = SgSet::<_, 4>::from_iter([5, 6]);
+    assert_eq!(a.try_bitor(&c), Err(SgError::StackCapacityExceeded));
+    assert_eq!(a.try_bitxor(&c), Err(SgError::StackCapacityExceeded));
+    assert_eq!(a.try_bitxor(&a), Ok(SgSet::new()));
+}
+
+#[test]
+#[should_panic]
+fn test_set_bitor_panic() {
+    let a = SgSet::<_, 2>::from_iter([1, 2]);
+    let b = SgSet::<_, 2>::from_iter([3]);
+    let _ = &a | &b;
+}
+
+#[test]
+fn test_set_relations_early_exit() {
+    use std::cell::Cell;
+
+    let cmp_cnt = Cell::new(0);
+    let cmp = |a: &usize, b: &usize| {
+        cmp_cnt.set(cmp_cnt.get() + 1);
+        a.cmp(b)
+    };
+
+    let mut a = SgSet::<_, DEFAULT_CAPACITY, u16, _>::with_comparator(cmp);
+    let mut b = SgSet::<_, DEFAULT_CAPACITY, u16, _>::with_comparator(cmp);
+    a.extend(0..DEFAULT_CAPACITY);
+    b.extend(0..DEFAULT_CAPACITY);
+    assert!(b.remove(&1));
+
+    // Differ right at the start
+    cmp_cnt.set(0);
+    assert!(!a.is_disjoint(&b));
+    assert!(cmp_cnt.get() <= 2);
+
+    cmp_cnt.set(0);
+    assert!(!a.is_subset(&b));
+    assert!(cmp_cnt.get() <= 4);
+
+    cmp_cnt.set(0);
+    assert!(b.is_subset(&a));
+    assert!(a.is_superset(&b));
+    assert!(cmp_cnt.get() >= DEFAULT_CAPACITY);
+}
+
 #[test]
 fn test_set_is_superset() {
     let a = SgSet::from_iter([1, 3, 5]);
@@ -241,6 +451,21 @@ fn test_set_insert_fallible() {
     assert_eq!(a.try_insert(4), Err(SgError::StackCapacityExceeded));
 }
 
+#[test]
+fn test_set_insert_within_capacity() {
+    let mut a = SgSet::<_, 2>::new();
+
+    assert_eq!(a.insert_within_capacity(String::from("a")), Ok(true));
+    assert_eq!(a.insert_within_capacity(String::from("b")), Ok(true));
+    assert_eq!(a.insert_within_capacity(String::from("a")), Ok(false));
+
+    let err = a.insert_within_capacity(String::from("c")).unwrap_err();
+    assert_eq!(err, CapacityError(String::from("c")));
+    assert_eq!(SgError::from(err.clone()), SgError::StackCapacityExceeded);
+    assert_eq!(err.into_inner(), "c");
+    assert_eq!(a.len(), 2);
+}
+
 #[test]
 fn test_set_append_fallible() {
     let mut a = SgSet::<_, 6>::new();
@@ -301,6 +526,19 @@ fn test_set_insert_panic() {
 
 // Range APIs ----------------------------------------------------------------------------------------------------------
 
+#[test]
+fn test_set_iter_from() {
+    let sgs: SgSet<_, DEFAULT_CAPACITY> = (0..20).step_by(2).collect();
+
+    assert!(sgs.iter_from(&6).copied().eq((6..20).step_by(2)));
+    assert!(sgs.iter_from(&7).copied().eq((8..20).step_by(2)));
+    assert_eq!(sgs.iter_from(&20).count(), 0);
+    assert_eq!(sgs.iter_from(&7).next_back(), Some(&18));
+
+    let rev: SgSet<_, DEFAULT_CAPACITY, u16, Reversed> = (0..5).collect();
+    assert!(rev.iter_from(&2).copied().eq([2, 1, 0]));
+}
+
 #[test]
 fn test_set_range() {
     let array = [1, 5, 3, 7, 9];
@@ -353,3 +591,182 @@ fn test_sg_set_range_panic_2() {
     set.insert(8);
     let _bad_range = set.range((Excluded(&5), Excluded(&5)));
 }
+
+#[test]
+fn test_set_drain() {
+    let mut sgs: SgSet<_, DEFAULT_CAPACITY> = (0..10).collect();
+
+    assert!(sgs.drain().rev().take(3).eq([9, 8, 7]));
+    assert!(sgs.is_empty());
+
+    sgs.extend((0..DEFAULT_CAPACITY).map(|k| k * 3));
+    assert!(sgs.is_full());
+    assert!(sgs.drain().eq((0..DEFAULT_CAPACITY).map(|k| k * 3)));
+    assert_eq!(sgs.first(), None);
+}
+
+#[test]
+fn test_set_get_prev_next() {
+    let sgs: SgSet<_, DEFAULT_CAPACITY> = (0..10).map(|k| k * 2).collect();
+    let bts: BTreeSet<_> = (0..10).map(|k| k * 2).collect();
+
+    for probe in 0..21 {
+        assert_eq!(sgs.get_prev(&probe), bts.range(..=probe).next_back());
+        assert_eq!(sgs.get_next(&probe), bts.range(probe..).next());
+    }
+}
+
+#[test]
+fn test_set_insert_or_evict() {
+    let mut sgs = SgSet::<usize, DEFAULT_CAPACITY>::new();
+    for v in 0..(DEFAULT_CAPACITY * 2) {
+        let expected = v.checked_sub(DEFAULT_CAPACITY);
+        assert_eq!(sgs.insert_or_evict(v, EvictPolicy::First), expected);
+    }
+    assert!(sgs
+        .iter()
+        .copied()
+        .eq(DEFAULT_CAPACITY..(DEFAULT_CAPACITY * 2)));
+
+    // Present value, nothing evicted
+    assert_eq!(
+        sgs.insert_or_evict(DEFAULT_CAPACITY, EvictPolicy::Last),
+        None
+    );
+
+    // Beyond the evicted end, rejected
+    assert_eq!(sgs.insert_or_evict(0, EvictPolicy::First), Some(0));
+    assert_eq!(
+        sgs.insert_or_evict(DEFAULT_CAPACITY * 3, EvictPolicy::Last),
+        Some(DEFAULT_CAPACITY * 3)
+    );
+
+    assert_eq!(
+        sgs.insert_or_evict(0, EvictPolicy::Last),
+        Some(DEFAULT_CAPACITY * 2 - 1)
+    );
+    assert_eq!(sgs.first(), Some(&0));
+    assert_eq!(sgs.len(), DEFAULT_CAPACITY);
+}
+
+#[test]
+fn test_set_contains_all_any() {
+    let sgs: SgSet<usize, DEFAULT_CAPACITY> = (0..DEFAULT_CAPACITY).map(|v| v * 2).collect();
+    let evens: Vec<usize> = sgs.iter().copied().collect();
+    let odds: Vec<usize> = evens.iter().map(|v| v + 1).collect();
+
+    // Sorted probes
+    assert!(sgs.contains_all(&evens));
+    assert!(!sgs.contains_any(&odds));
+
+    // Unsorted probes
+    let mut probe: Vec<usize> = evens.iter().rev().copied().collect();
+    assert!(sgs.contains_all(&probe));
+    probe.push(1);
+    assert!(!sgs.contains_all(&probe));
+    assert!(sgs.contains_any(&[3, 5, 0]));
+
+    // Empty probes
+    assert!(sgs.contains_all(&[]));
+    assert!(!sgs.contains_any(&[]));
+
+    // Borrowed form
+    let strs: SgSet<String, 4> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+    assert!(strs.contains_all(["a", "b"].iter().copied()));
+    assert!(!strs.contains_any(["c"].iter().copied()));
+}
+
+#[test]
+fn test_set_get_or_insert() {
+    // Equal by `Ord`, but distinguishable
+    #[derive(Debug)]
+    struct Tagged(u8, &'static str);
+
+    impl PartialEq for Tagged {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Tagged {}
+
+    impl PartialOrd for Tagged {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Tagged {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let mut sgs = SgSet::<Tagged, 2>::new();
+    assert_eq!(sgs.get_or_insert(Tagged(1, "first")).1, "first");
+    assert_eq!(sgs.get_or_insert(Tagged(1, "second")).1, "first");
+    assert_eq!(sgs.len(), 1);
+
+    assert_eq!(sgs.get_or_insert(Tagged(0, "third")).1, "third");
+    assert!(sgs.is_full());
+    assert_eq!(sgs.get_or_insert(Tagged(0, "fourth")).1, "third");
+    assert_eq!(sgs.first().map(|t| t.1), Some("third"));
+}
+
+#[test]
+fn test_set_in_place_ops() {
+    const CAPACITY: usize = 5;
+    let subset = |mask: u8| (0..7).filter(move |i| mask & (1 << i) != 0);
+
+    // Every pair of subsets of a 7-value universe that fit
+    for a_mask in (0..128).filter(|m: &u8| m.count_ones() as usize <= CAPACITY) {
+        for b_mask in (0..128).filter(|m: &u8| m.count_ones() as usize <= CAPACITY) {
+            let a = SgSet::<_, CAPACITY>::from_iter(subset(a_mask));
+            let b = SgSet::<_, CAPACITY>::from_iter(subset(b_mask));
+            let bt_a = BTreeSet::from_iter(subset(a_mask));
+            let bt_b = BTreeSet::from_iter(subset(b_mask));
+
+            let mut set = a.clone();
+            set.intersection_update(&b);
+            assert!(set.iter().eq(bt_a.intersection(&bt_b)));
+
+            let mut set = a.clone();
+            set.difference_update(&b);
+            assert!(set.iter().eq(bt_a.difference(&bt_b)));
+
+            let mut set = a.clone();
+            match set.try_union_update(&b) {
+                Ok(()) => assert!(set.iter().eq(bt_a.union(&bt_b))),
+                Err(err) => {
+                    assert_eq!(err, SgError::StackCapacityExceeded);
+                    assert!(bt_a.union(&bt_b).count() > CAPACITY);
+                    assert_eq!(set, a);
+                }
+            }
+
+            let mut set = a.clone();
+            match set.try_symmetric_difference_update(&b) {
+                Ok(()) => assert!(set.iter().eq(bt_a.symmetric_difference(&bt_b))),
+                Err(err) => {
+                    assert_eq!(err, SgError::StackCapacityExceeded);
+                    assert!(bt_a.symmetric_difference(&bt_b).count() > CAPACITY);
+                    assert_eq!(set, a);
+                }
+            }
+        }
+    }
+
+    // Custom order
+    let mut rev = SgSet::<_, DEFAULT_CAPACITY, u16, Reversed>::from_iter([1, 2, 3, 4]);
+    rev.symmetric_difference_update(&SgSet::from_iter([3, 4, 5]));
+    assert!(rev.iter().eq(&[5, 2, 1]));
+    rev.intersection_update(&SgSet::from_iter([1, 5, 6]));
+    assert!(rev.iter().eq(&[5, 1]));
+}
+
+#[test]
+#[should_panic(expected = "Stack-storage capacity exceeded!")]
+fn test_set_union_update_panic() {
+    let mut a = SgSet::<_, 2>::from_iter([1, 2]);
+    a.union_update(&SgSet::from_iter([3]));
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use scapegoat::{SgError, SgTtlMap};
+
+use rand::Rng;
+
+const DEFAULT_CAPACITY: usize = 100;
+
+#[test]
+fn test_debug() {
+    let mut tmap = SgTtlMap::<_, _, DEFAULT_CAPACITY>::new();
+    tmap.insert(2, 20, 5);
+    tmap.insert(1, 10, 7);
+    assert_eq!(format!("{:?}", tmap), "{1: 10, 2: 20}");
+}
+
+#[test]
+fn test_ttl_map_basic() {
+    let mut tmap = SgTtlMap::<usize, usize, 3>::new();
+    assert_eq!(tmap.next_expiry(), None);
+    assert_eq!(tmap.pop_expired(u64::MAX), None);
+    assert_eq!(tmap.expire_before(u64::MAX), 0);
+
+    assert_eq!(tmap.insert(3, 30, 10), None);
+    assert_eq!(tmap.insert(1, 10, 20), None);
+    assert_eq!(tmap.insert(3, 35, 30), Some(30));
+    assert_eq!(tmap.try_insert(2, 20, 5), Ok(None));
+    assert!(tmap.is_full());
+    assert_eq!(
+        tmap.try_insert(4, 40, 1),
+        Err(SgError::StackCapacityExceeded)
+    );
+
+    // Re-insertion updates expiry
+    assert_eq!(tmap.get_expiry(&3), Some(30));
+    assert_eq!(tmap.next_expiry(), Some((&2, &20, 5)));
+
+    // Expiry is exclusive of `now`
+    assert_eq!(tmap.pop_expired(5), None);
+    assert_eq!(tmap.expire_before(5), 0);
+    assert_eq!(tmap.pop_expired(6), Some((2, 20)));
+
+    // Refresh
+    assert!(tmap.set_expiry(&3, 15));
+    assert!(!tmap.set_expiry(&4, 15));
+    assert_eq!(tmap.next_expiry(), Some((&3, &35, 15)));
+
+    // Value updates keep expiry
+    *tmap.get_mut(&1).unwrap() += 1;
+    assert_eq!(tmap.get(&1), Some(&11));
+    assert_eq!(tmap.get_expiry(&1), Some(20));
+
+    // Expired entries are still visible until evicted
+    assert!(tmap.contains_key(&3));
+    assert_eq!(tmap.expire_before(100), 2);
+    assert!(tmap.is_empty());
+
+    tmap.insert(1, 10, 1);
+    assert_eq!(tmap.remove(&1), Some(10));
+    tmap.insert(2, 20, 1);
+    tmap.clear();
+    assert!(tmap.is_empty());
+    assert_eq!(tmap.iter().next(), None);
+}
+
+#[test]
+fn test_ttl_rand_ops() {
+    let mut rng = rand::thread_rng();
+    let mut tmap = SgTtlMap::<usize, usize, DEFAULT_CAPACITY>::new();
+    let mut bt_map: BTreeMap<usize, (usize, u64)> = BTreeMap::new();
+    let mut now = 0;
+
+    for _ in 0..2_000 {
+        let k = rng.gen_range(0, DEFAULT_CAPACITY * 2);
+        match rng.gen_range(0, 6) {
+            0 | 1 if !tmap.is_full() || tmap.contains_key(&k) => {
+                let (v, exp) = (rng.gen::<usize>(), now + rng.gen_range(1, 100));
+                assert_eq!(
+                    tmap.insert(k, v, exp),
+                    bt_map.insert(k, (v, exp)).map(|(v, _)| v)
+                );
+            }
+            2 => {
+                let exp = now + rng.gen_range(1, 100);
+                assert_eq!(tmap.set_expiry(&k, exp), bt_map.contains_key(&k));
+                if let Some(entry) = bt_map.get_mut(&k) {
+                    entry.1 = exp;
+                }
+            }
+            3 => {
+                assert_eq!(tmap.remove(&k), bt_map.remove(&k).map(|(v, _)| v));
+            }
+            4 => {
+                let prev_len = bt_map.len();
+                bt_map.retain(|_, (_, exp)| *exp >= now);
+                assert_eq!(tmap.expire_before(now), prev_len - bt_map.len());
+            }
+            _ => {
+                let expected = bt_map
+                    .iter()
+                    .filter(|(_, (_, exp))| *exp < now)
+                    .min_by_key(|(k, (_, exp))| (*exp, **k))
+                    .map(|(k, (v, _))| (*k, *v));
+                if let Some((k, _)) = expected {
+                    bt_map.remove(&k);
+                }
+                assert_eq!(tmap.pop_expired(now), expected);
+            }
+        }
+
+        assert!(tmap.iter().eq(bt_map.iter().map(|(k, (v, _))| (k, v))));
+        assert_eq!(
+            tmap.next_expiry().map(|(k, _, exp)| (*k, exp)),
+            bt_map
+                .iter()
+                .map(|(k, (_, exp))| (*k, *exp))
+                .min_by_key(|(k, exp)| (*exp, *k))
+        );
+
+        now += rng.gen_range(0, 3);
+    }
+}